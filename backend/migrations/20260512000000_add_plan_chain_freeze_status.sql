@@ -0,0 +1,17 @@
+-- Track whether an admin pause/unpause has reached the contract
+-- The DB pause commits first; the matching freeze_plan / unfreeze_plan call
+-- is recorded as pending and retried with back-off until it lands, instead
+-- of being dropped when the RPC is unavailable.
+
+ALTER TABLE plans
+    ADD COLUMN IF NOT EXISTS chain_freeze_status TEXT
+        CHECK (chain_freeze_status IN ('pending_freeze', 'pending_unfreeze', 'synced', 'failed')),
+    ADD COLUMN IF NOT EXISTS chain_freeze_attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS chain_freeze_next_attempt_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS chain_freeze_last_error TEXT;
+
+COMMENT ON COLUMN plans.chain_freeze_status IS 'On-chain mirror of is_paused; NULL for plans never paused on chain';
+
+CREATE INDEX IF NOT EXISTS idx_plans_chain_freeze_due
+    ON plans (chain_freeze_next_attempt_at)
+    WHERE chain_freeze_status IN ('pending_freeze', 'pending_unfreeze');
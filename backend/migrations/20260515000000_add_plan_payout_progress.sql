@@ -0,0 +1,17 @@
+-- Payout progress of periodic plans
+-- Mirrors the contract's installment schedule override and the installments
+-- paid per (plan, beneficiary index), so each beneficiary's next due date can
+-- be computed off chain without one claim advancing another's schedule. The
+-- inheritance trigger time is mirrored too, since a trigger brings the first
+-- installment forward. All are kept up to date by the chain indexer.
+
+ALTER TABLE plans ADD COLUMN IF NOT EXISTS payout_installment_count INTEGER;
+ALTER TABLE plans ADD COLUMN IF NOT EXISTS inheritance_triggered_at BIGINT;
+
+CREATE TABLE IF NOT EXISTS plan_payout_progress (
+    plan_id           UUID NOT NULL REFERENCES plans(id) ON DELETE CASCADE,
+    beneficiary_index INTEGER NOT NULL,
+    paid_installments INTEGER NOT NULL DEFAULT 0,
+    updated_at        TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (plan_id, beneficiary_index)
+);
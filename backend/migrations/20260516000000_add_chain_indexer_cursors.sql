@@ -0,0 +1,16 @@
+-- Chain indexer cursor persistence
+-- Stores the Soroban RPC `getEvents` paging cursor per indexer so that the
+-- worker resumes from the last ingested event after a restart.
+
+CREATE TABLE IF NOT EXISTS chain_indexer_cursors (
+    indexer_name    VARCHAR(100) PRIMARY KEY,
+    cursor          TEXT NOT NULL,
+    last_ledger     BIGINT NOT NULL,
+    updated_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Replay detection looks events up by their on-chain event id.
+CREATE INDEX IF NOT EXISTS idx_lending_events_chain_event_id
+    ON lending_events ((metadata->>'chain_event_id'));
+CREATE INDEX IF NOT EXISTS idx_will_event_log_chain_event_id
+    ON will_event_log ((event_data->>'chain_event_id'));
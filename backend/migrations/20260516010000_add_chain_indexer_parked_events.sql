@@ -0,0 +1,15 @@
+-- Parked chain events
+-- Contract events the indexer cannot attribute yet (no user has linked the
+-- wallet, or the plan is not recorded) are kept here and retried on every
+-- poll, instead of being skipped once the cursor moves past them.
+
+CREATE TABLE IF NOT EXISTS chain_indexer_parked_events (
+    indexer_name    VARCHAR(100) NOT NULL,
+    event_id        TEXT NOT NULL,
+    raw_event       JSONB NOT NULL,
+    reason          TEXT NOT NULL,
+    attempts        INTEGER NOT NULL DEFAULT 1,
+    parked_at       TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    last_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (indexer_name, event_id)
+);
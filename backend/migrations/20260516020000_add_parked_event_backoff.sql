@@ -0,0 +1,14 @@
+-- Back off parked chain event retries
+-- Parked events are retried once their next_attempt_at has passed, with an
+-- exponential back-off, and are moved to the 'dead' state after the attempt
+-- budget instead of being retried on every poll forever. Events that could
+-- not be decoded are parked too, with a 'decode_error' reason.
+
+ALTER TABLE chain_indexer_parked_events
+    ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'parked'
+        CHECK (status IN ('parked', 'dead')),
+    ADD COLUMN IF NOT EXISTS next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW();
+
+CREATE INDEX IF NOT EXISTS idx_chain_indexer_parked_events_due
+    ON chain_indexer_parked_events (indexer_name, next_attempt_at)
+    WHERE status = 'parked';
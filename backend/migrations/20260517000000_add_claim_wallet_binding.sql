@@ -0,0 +1,15 @@
+-- Wallet-signed claims
+-- Beneficiaries now prove their identity with an Ed25519 wallet signature
+-- instead of a shared code. The first KYC-approved wallet to claim for a
+-- beneficiary is bound to that (plan, beneficiary index); later claims for
+-- the same beneficiary must be signed by the same wallet.
+
+CREATE TABLE IF NOT EXISTS beneficiary_wallet_bindings (
+    plan_id           UUID NOT NULL REFERENCES plans(id) ON DELETE CASCADE,
+    beneficiary_index INTEGER NOT NULL,
+    wallet_address    VARCHAR(64) NOT NULL,
+    bound_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (plan_id, beneficiary_index)
+);
+
+ALTER TABLE claims ADD COLUMN IF NOT EXISTS wallet_address VARCHAR(64);
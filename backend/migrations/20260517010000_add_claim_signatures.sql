@@ -0,0 +1,16 @@
+-- Single-use claim signatures
+-- Claim messages now name the installment being claimed and when they were
+-- signed. Every accepted signature is recorded here so the same signed
+-- request cannot be submitted twice.
+
+CREATE TABLE IF NOT EXISTS claim_signatures (
+    signature_hex     TEXT PRIMARY KEY,
+    plan_id           UUID NOT NULL REFERENCES plans(id) ON DELETE CASCADE,
+    user_id           UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    beneficiary_index INTEGER NOT NULL,
+    installment       INTEGER NOT NULL,
+    signed_at         BIGINT NOT NULL,
+    used_at           TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_claim_signatures_plan_id ON claim_signatures(plan_id);
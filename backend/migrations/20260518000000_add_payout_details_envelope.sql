@@ -0,0 +1,6 @@
+-- Sealed payout details
+-- Fiat bank details written to the inheritance contract are sealed to a
+-- settlement key held by the backend (see src/payout_details.rs). The
+-- envelope kept here is what the claim path opens for fiat settlement.
+
+ALTER TABLE plans ADD COLUMN IF NOT EXISTS payout_details_envelope BYTEA;
@@ -0,0 +1,17 @@
+-- Multi-asset plans
+-- A plan may hold whitelisted token balances besides its primary
+-- currency; each is recorded here. XLM and EURC join USDC and FIAT as
+-- valid currency preferences.
+
+ALTER TABLE plans DROP CONSTRAINT IF EXISTS chk_currency_preference;
+ALTER TABLE plans
+ADD CONSTRAINT chk_currency_preference
+CHECK (currency_preference IS NULL OR currency_preference IN ('USDC', 'XLM', 'EURC', 'FIAT'));
+
+CREATE TABLE IF NOT EXISTS plan_assets (
+    plan_id UUID NOT NULL REFERENCES plans(id) ON DELETE CASCADE,
+    asset_code VARCHAR(12) NOT NULL CHECK (asset_code IN ('USDC', 'XLM', 'EURC')),
+    amount DECIMAL(20, 8) NOT NULL CHECK (amount > 0),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (plan_id, asset_code)
+);
@@ -0,0 +1,33 @@
+-- Transactional outbox
+-- Domain events are written here in the same transaction as the change that
+-- raised them. A relay worker fans each event out to notifications, webhook
+-- deliveries and the optional message bus, so a crash between the change
+-- and its side effects can no longer lose the event.
+
+CREATE TABLE IF NOT EXISTS outbox_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event_id TEXT NOT NULL UNIQUE,
+    event_type TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    user_id UUID NOT NULL,
+    payload JSONB NOT NULL,
+    notification_type VARCHAR(100),
+    notification_message TEXT,
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'relayed', 'failed')),
+    attempt_count INTEGER NOT NULL DEFAULT 0,
+    available_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    last_error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    relayed_at TIMESTAMP WITH TIME ZONE
+);
+
+COMMENT ON COLUMN outbox_events.payload IS 'Webhook event envelope: id, event_type, version, data, timestamp';
+COMMENT ON COLUMN outbox_events.notification_type IS 'In-app notification to create when relayed, if any';
+
+CREATE INDEX IF NOT EXISTS idx_outbox_events_due
+    ON outbox_events (available_at, created_at)
+    WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_outbox_events_failed
+    ON outbox_events (created_at)
+    WHERE status = 'failed';
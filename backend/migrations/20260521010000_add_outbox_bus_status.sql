@@ -0,0 +1,18 @@
+-- Track message bus publication apart from the relay
+-- Notifications and webhook deliveries are committed as soon as an event is
+-- relayed; publishing to the bus is retried on its own schedule, so a bus
+-- outage no longer holds back (or fails) the rest of the fan-out.
+
+ALTER TABLE outbox_events
+    ADD COLUMN IF NOT EXISTS bus_status TEXT
+        CHECK (bus_status IN ('pending', 'published', 'failed')),
+    ADD COLUMN IF NOT EXISTS bus_attempt_count INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS bus_available_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS bus_last_error TEXT,
+    ADD COLUMN IF NOT EXISTS bus_published_at TIMESTAMP WITH TIME ZONE;
+
+COMMENT ON COLUMN outbox_events.bus_status IS 'Message bus publication; NULL when no bus was configured at relay time';
+
+CREATE INDEX IF NOT EXISTS idx_outbox_events_bus_due
+    ON outbox_events (bus_available_at, created_at)
+    WHERE bus_status = 'pending';
@@ -0,0 +1,36 @@
+-- External alert delivery
+-- Email and SMS alerts (OTPs, emergency-access risk alerts, liquidation
+-- warnings) are rendered from per-locale templates and every message sent
+-- to a provider is recorded with its delivery status. SMS statuses are
+-- updated by the gateway's status callbacks. Alerts taken by the development
+-- log-only fallback are recorded as 'logged' so they are not mistaken for
+-- messages that reached a provider.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS locale VARCHAR(16) NOT NULL DEFAULT 'en';
+
+COMMENT ON COLUMN users.locale IS 'BCP 47 language tag used to pick alert templates, e.g. en or es-MX';
+
+CREATE TABLE IF NOT EXISTS alert_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    channel TEXT NOT NULL CHECK (channel IN ('email', 'sms')),
+    recipient TEXT NOT NULL,
+    notif_type VARCHAR(100) NOT NULL,
+    locale VARCHAR(16) NOT NULL,
+    subject TEXT,
+    provider TEXT NOT NULL,
+    provider_message_id TEXT,
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'sent', 'logged', 'delivered', 'undelivered', 'failed')),
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMP WITH TIME ZONE,
+    delivered_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_alert_deliveries_user
+    ON alert_deliveries (user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_alert_deliveries_provider_message
+    ON alert_deliveries (provider, provider_message_id)
+    WHERE provider_message_id IS NOT NULL;
@@ -0,0 +1,49 @@
+-- Notification preferences
+-- Users choose, per notification type, which channels (in-app, email, SMS,
+-- webhook) receive it and whether it goes out immediately or bundled in a
+-- daily or weekly digest. Quiet hours in the user's timezone hold back
+-- non-security notifications until they end.
+
+CREATE TABLE IF NOT EXISTS notification_settings (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    timezone TEXT NOT NULL DEFAULT 'UTC',
+    quiet_hours_start TIME,
+    quiet_hours_end TIME,
+    digest_hour SMALLINT NOT NULL DEFAULT 8 CHECK (digest_hour BETWEEN 0 AND 23),
+    phone_number VARCHAR(32),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CHECK ((quiet_hours_start IS NULL) = (quiet_hours_end IS NULL))
+);
+
+COMMENT ON COLUMN notification_settings.timezone IS 'IANA timezone for quiet hours and digest scheduling, e.g. Europe/Madrid';
+COMMENT ON COLUMN notification_settings.digest_hour IS 'Local hour digests are sent at; weekly digests go out on Mondays';
+
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    notif_type VARCHAR(100) NOT NULL,
+    channels TEXT[] NOT NULL,
+    frequency TEXT NOT NULL DEFAULT 'immediate'
+        CHECK (frequency IN ('immediate', 'daily', 'weekly')),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, notif_type),
+    CHECK (channels <@ ARRAY['in_app', 'email', 'sms', 'webhook'])
+);
+
+-- Existing notifications predate external channels and are not dispatched;
+-- new rows start out pending.
+ALTER TABLE notifications
+    ADD COLUMN IF NOT EXISTS dispatch_status TEXT NOT NULL DEFAULT 'dispatched'
+        CHECK (dispatch_status IN ('pending', 'digest', 'dispatched')),
+    ADD COLUMN IF NOT EXISTS dispatch_after TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS digest_frequency TEXT
+        CHECK (digest_frequency IN ('daily', 'weekly')),
+    ADD COLUMN IF NOT EXISTS dispatched_at TIMESTAMP WITH TIME ZONE;
+
+ALTER TABLE notifications ALTER COLUMN dispatch_status SET DEFAULT 'pending';
+
+CREATE INDEX IF NOT EXISTS idx_notifications_dispatch_due
+    ON notifications (dispatch_after, created_at)
+    WHERE dispatch_status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_notifications_digest
+    ON notifications (user_id, digest_frequency, created_at)
+    WHERE dispatch_status = 'digest';
@@ -0,0 +1,283 @@
+//! Email and SMS alert delivery with per-message status tracking.
+//!
+//! Every alert is rendered from its `notif_type` template in the recipient's
+//! locale and recorded in `alert_deliveries` before it is handed to the
+//! provider, so a message that never left (or that the gateway later reports
+//! as undelivered) is visible rather than lost in the logs.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::extract::{Form, State};
+use axum::http::{HeaderMap, StatusCode};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::alert_provider::{AlertChannel, AlertProvider, LOG_ONLY_PROVIDER};
+use crate::alert_templates::{self, TemplateVars, DEFAULT_LOCALE};
+use crate::api_error::ApiError;
+use crate::app::AppState;
+
+/// Delivery status values stored in `alert_deliveries.status`.
+pub mod delivery_status {
+    pub const PENDING: &str = "pending";
+    pub const SENT: &str = "sent";
+    /// Written to the logs by the development fallback; never left the
+    /// server.
+    pub const LOGGED: &str = "logged";
+    pub const DELIVERED: &str = "delivered";
+    pub const UNDELIVERED: &str = "undelivered";
+    pub const FAILED: &str = "failed";
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AlertDelivery {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub channel: String,
+    pub recipient: String,
+    pub notif_type: String,
+    pub locale: String,
+    pub subject: Option<String>,
+    pub provider: String,
+    pub provider_message_id: Option<String>,
+    pub status: String,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// One alert to render and send.
+#[derive(Debug, Clone)]
+pub struct AlertRequest<'a> {
+    /// The account the alert concerns, if any.
+    pub user_id: Option<Uuid>,
+    pub channel: AlertChannel,
+    pub recipient: &'a str,
+    pub notif_type: &'a str,
+    pub locale: &'a str,
+    pub vars: TemplateVars,
+}
+
+const DELIVERY_COLUMNS: &str = "id, user_id, channel, recipient, notif_type, locale, subject, \
+     provider, provider_message_id, status, error, created_at, updated_at, sent_at, delivered_at";
+
+/// Maps a Twilio `MessageStatus` onto our delivery status. Intermediate
+/// states we do not track (e.g. `accepted`) map to `None`.
+pub fn status_from_twilio(status: &str) -> Option<&'static str> {
+    match status {
+        "queued" | "sending" | "sent" => Some(delivery_status::SENT),
+        "delivered" | "read" => Some(delivery_status::DELIVERED),
+        "undelivered" => Some(delivery_status::UNDELIVERED),
+        "failed" | "canceled" => Some(delivery_status::FAILED),
+        _ => None,
+    }
+}
+
+pub struct AlertDeliveryService;
+
+impl AlertDeliveryService {
+    /// The locale alerts for `user_id` are rendered in.
+    pub async fn user_locale(db: &PgPool, user_id: Uuid) -> Result<String, ApiError> {
+        let locale: Option<String> = sqlx::query_scalar("SELECT locale FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(db)
+            .await?;
+        Ok(locale.unwrap_or_else(|| DEFAULT_LOCALE.to_string()))
+    }
+
+    /// Render, record and send one alert. The delivery row is marked `sent`
+    /// or `failed` depending on the provider's answer (`logged` when the
+    /// log-only fallback took it); a provider failure is returned as
+    /// [`ApiError::ExternalService`] after it has been recorded.
+    pub async fn send(
+        db: &PgPool,
+        provider: &dyn AlertProvider,
+        request: AlertRequest<'_>,
+    ) -> Result<AlertDelivery, ApiError> {
+        let rendered = alert_templates::render(request.notif_type, request.locale, &request.vars)?;
+        let subject = match request.channel {
+            AlertChannel::Email => Some(rendered.subject.as_str()),
+            AlertChannel::Sms => None,
+        };
+
+        let delivery_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO alert_deliveries (user_id, channel, recipient, notif_type, locale, subject, provider)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id
+            "#,
+        )
+        .bind(request.user_id)
+        .bind(request.channel.as_str())
+        .bind(request.recipient)
+        .bind(request.notif_type)
+        .bind(rendered.locale)
+        .bind(subject)
+        .bind(provider.provider_name(request.channel))
+        .fetch_one(db)
+        .await?;
+
+        let result = match request.channel {
+            AlertChannel::Email => {
+                provider
+                    .send_email(request.recipient, &rendered.subject, &rendered.body)
+                    .await
+            }
+            AlertChannel::Sms => provider.send_sms(request.recipient, &rendered.body).await,
+        };
+
+        match result {
+            Ok(receipt) => sqlx::query_as::<_, AlertDelivery>(&format!(
+                r#"
+                UPDATE alert_deliveries
+                SET status = $2, provider = $3, provider_message_id = $4,
+                    sent_at = CASE WHEN $2 = 'sent' THEN NOW() END, updated_at = NOW()
+                WHERE id = $1
+                RETURNING {DELIVERY_COLUMNS}
+                "#
+            ))
+            .bind(delivery_id)
+            .bind(if receipt.provider == LOG_ONLY_PROVIDER {
+                delivery_status::LOGGED
+            } else {
+                delivery_status::SENT
+            })
+            .bind(receipt.provider)
+            .bind(receipt.message_id)
+            .fetch_one(db)
+            .await
+            .map_err(ApiError::from),
+            Err(e) => {
+                let error = e.to_string();
+                sqlx::query(
+                    r#"
+                    UPDATE alert_deliveries
+                    SET status = $2, error = $3, updated_at = NOW()
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(delivery_id)
+                .bind(delivery_status::FAILED)
+                .bind(&error)
+                .execute(db)
+                .await?;
+                Err(ApiError::ExternalService(format!(
+                    "Failed to send {} alert: {}",
+                    request.channel.as_str(),
+                    error
+                )))
+            }
+        }
+    }
+
+    /// Apply a provider status report to the matching delivery. Final
+    /// statuses (`delivered`, `undelivered`, `failed`) are never overwritten,
+    /// so late or out-of-order reports cannot downgrade a delivered message.
+    /// Returns the updated delivery, or `None` if nothing changed.
+    pub async fn record_status(
+        db: &PgPool,
+        provider: &str,
+        provider_message_id: &str,
+        status: &str,
+        error: Option<&str>,
+    ) -> Result<Option<AlertDelivery>, ApiError> {
+        let row = sqlx::query_as::<_, AlertDelivery>(&format!(
+            r#"
+            UPDATE alert_deliveries
+            SET status = $3,
+                error = COALESCE($4, error),
+                delivered_at = CASE WHEN $3 = 'delivered' THEN NOW() ELSE delivered_at END,
+                updated_at = NOW()
+            WHERE provider = $1 AND provider_message_id = $2
+              AND status NOT IN ('delivered', 'undelivered', 'failed')
+            RETURNING {DELIVERY_COLUMNS}
+            "#
+        ))
+        .bind(provider)
+        .bind(provider_message_id)
+        .bind(status)
+        .bind(error)
+        .fetch_optional(db)
+        .await?;
+        Ok(row)
+    }
+}
+
+// ─── Handlers ────────────────────────────────────────────────────────────────
+
+/// Twilio message status callback. Requests must carry a valid
+/// `X-Twilio-Signature` for the configured callback URL.
+pub async fn twilio_status_callback(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Form(params): Form<BTreeMap<String, String>>,
+) -> Result<StatusCode, ApiError> {
+    let sms = state.alert_provider.sms().ok_or(ApiError::Unauthorized)?;
+    let url = sms.status_callback_url().ok_or(ApiError::Unauthorized)?;
+    let signature = headers
+        .get("X-Twilio-Signature")
+        .and_then(|v| v.to_str().ok())
+        .ok_or(ApiError::Unauthorized)?;
+    if !sms.verify_signature(url, &params, signature) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let message_sid = params
+        .get("MessageSid")
+        .ok_or_else(|| ApiError::BadRequest("MessageSid is required".to_string()))?;
+    let reported = params
+        .get("MessageStatus")
+        .ok_or_else(|| ApiError::BadRequest("MessageStatus is required".to_string()))?;
+    let Some(status) = status_from_twilio(reported) else {
+        return Ok(StatusCode::NO_CONTENT);
+    };
+    let error = params
+        .get("ErrorCode")
+        .map(|code| format!("Twilio error {code}"));
+
+    if AlertDeliveryService::record_status(
+        &state.db,
+        "twilio",
+        message_sid,
+        status,
+        error.as_deref(),
+    )
+    .await?
+    .is_none()
+    {
+        warn!(
+            "Ignored SMS status '{}' for unknown or finalised message {}",
+            reported, message_sid
+        );
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_twilio_statuses() {
+        assert_eq!(status_from_twilio("queued"), Some(delivery_status::SENT));
+        assert_eq!(
+            status_from_twilio("delivered"),
+            Some(delivery_status::DELIVERED)
+        );
+        assert_eq!(
+            status_from_twilio("undelivered"),
+            Some(delivery_status::UNDELIVERED)
+        );
+        assert_eq!(
+            status_from_twilio("canceled"),
+            Some(delivery_status::FAILED)
+        );
+        assert_eq!(status_from_twilio("accepted"), None);
+    }
+}
@@ -1,30 +1,633 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
 use async_trait::async_trait;
-use tracing::info;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use reqwest::Client;
+use ring::hmac;
+use serde::Deserialize;
+use tracing::{error, info, warn};
+
+const SMTP_TIMEOUT_SECS: u64 = 15;
+const SMS_TIMEOUT_SECS: u64 = 15;
+const DEFAULT_TWILIO_API_URL: &str = "https://api.twilio.com";
+
+/// Provider name of [`MockAlertProvider`], whose messages only reach the
+/// logs.
+pub const LOG_ONLY_PROVIDER: &str = "mock";
+/// Provider name recorded for a channel with no provider and no log-only
+/// fallback; every send on it fails.
+pub const UNCONFIGURED_PROVIDER: &str = "unconfigured";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertChannel {
+    Email,
+    Sms,
+}
+
+impl AlertChannel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlertChannel::Email => "email",
+            AlertChannel::Sms => "sms",
+        }
+    }
+}
+
+/// What a provider returns once it has accepted a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlertReceipt {
+    pub provider: &'static str,
+    /// The provider's id for the message, used to match later status
+    /// updates. `None` when the provider does not issue one.
+    pub message_id: Option<String>,
+}
 
 #[async_trait]
 pub trait AlertProvider: Send + Sync {
-    async fn send_sms(&self, to: &str, message: &str) -> anyhow::Result<()>;
-    async fn send_email(&self, to: &str, subject: &str, body: &str) -> anyhow::Result<()>;
+    /// The name recorded against deliveries made on `channel`.
+    fn provider_name(&self, channel: AlertChannel) -> &'static str;
+    async fn send_sms(&self, to: &str, message: &str) -> anyhow::Result<AlertReceipt>;
+    async fn send_email(&self, to: &str, subject: &str, body: &str)
+        -> anyhow::Result<AlertReceipt>;
 }
 
+/// Writes alerts to the log instead of sending them. Message bodies, OTPs
+/// included, end up in the logs, so this is for local development only.
 pub struct MockAlertProvider;
 
 #[async_trait]
 impl AlertProvider for MockAlertProvider {
-    async fn send_sms(&self, to: &str, message: &str) -> anyhow::Result<()> {
-        info!("--- [MOCK SMS ALERT] ---");
+    fn provider_name(&self, _channel: AlertChannel) -> &'static str {
+        LOG_ONLY_PROVIDER
+    }
+
+    async fn send_sms(&self, to: &str, message: &str) -> anyhow::Result<AlertReceipt> {
+        warn!("--- [MOCK SMS ALERT] not delivered: no SMS gateway configured ---");
         info!("To: {}", to);
         info!("Message: {}", message);
         info!("-----------------------");
-        Ok(())
+        Ok(AlertReceipt {
+            provider: LOG_ONLY_PROVIDER,
+            message_id: None,
+        })
     }
 
-    async fn send_email(&self, to: &str, subject: &str, body: &str) -> anyhow::Result<()> {
-        info!("--- [MOCK EMAIL ALERT] ---");
+    async fn send_email(
+        &self,
+        to: &str,
+        subject: &str,
+        body: &str,
+    ) -> anyhow::Result<AlertReceipt> {
+        warn!("--- [MOCK EMAIL ALERT] not delivered: no SMTP server configured ---");
         info!("To: {}", to);
         info!("Subject: {}", subject);
         info!("Body: {}", body);
         info!("-------------------------");
-        Ok(())
+        Ok(AlertReceipt {
+            provider: LOG_ONLY_PROVIDER,
+            message_id: None,
+        })
+    }
+}
+
+// ─── SMTP ────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    /// Upgrade a plain connection with STARTTLS (usually port 587).
+    StartTls,
+    /// TLS from the first byte (usually port 465).
+    Tls,
+    /// No encryption, for local SMTP sinks only.
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub security: SmtpSecurity,
+}
+
+impl SmtpConfig {
+    /// Reads the SMTP configuration from the environment. Returns `None` when
+    /// `SMTP_HOST` or `SMTP_FROM` is unset, in which case email alerts are
+    /// only logged in development and fail elsewhere.
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let host = var("SMTP_HOST")?;
+        let from = var("SMTP_FROM")?;
+        let security = match var("SMTP_TLS").as_deref() {
+            Some("tls") => SmtpSecurity::Tls,
+            Some("none") => SmtpSecurity::None,
+            _ => SmtpSecurity::StartTls,
+        };
+        let default_port = match security {
+            SmtpSecurity::Tls => 465,
+            _ => 587,
+        };
+        Some(Self {
+            host,
+            port: var("SMTP_PORT")
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(default_port),
+            username: var("SMTP_USERNAME"),
+            password: var("SMTP_PASSWORD"),
+            from,
+            security,
+        })
+    }
+}
+
+pub struct SmtpEmailProvider {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: lettre::message::Mailbox,
+}
+
+impl SmtpEmailProvider {
+    pub fn new(config: &SmtpConfig) -> anyhow::Result<Self> {
+        let builder = match config.security {
+            SmtpSecurity::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?
+            }
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)?,
+            SmtpSecurity::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+            }
+        };
+        let mut builder = builder
+            .port(config.port)
+            .timeout(Some(Duration::from_secs(SMTP_TIMEOUT_SECS)));
+        if let Some(username) = &config.username {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                config.password.clone().unwrap_or_default(),
+            ));
+        }
+        Ok(Self {
+            transport: builder.build(),
+            from: config.from.parse()?,
+        })
+    }
+}
+
+#[async_trait]
+impl AlertProvider for SmtpEmailProvider {
+    fn provider_name(&self, _channel: AlertChannel) -> &'static str {
+        "smtp"
+    }
+
+    async fn send_sms(&self, _to: &str, _message: &str) -> anyhow::Result<AlertReceipt> {
+        anyhow::bail!("SMTP provider cannot send SMS")
+    }
+
+    async fn send_email(
+        &self,
+        to: &str,
+        subject: &str,
+        body: &str,
+    ) -> anyhow::Result<AlertReceipt> {
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to.parse()?)
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(body.to_string())?;
+        let response = self.transport.send(message).await?;
+        // SMTP has no delivery receipts; the server's acceptance line (usually
+        // carrying its queue id) is the best handle we get.
+        Ok(AlertReceipt {
+            provider: "smtp",
+            message_id: response.first_line().map(str::to_string),
+        })
+    }
+}
+
+// ─── Twilio-compatible SMS gateway ───────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct TwilioConfig {
+    pub account_sid: String,
+    pub auth_token: String,
+    pub from_number: String,
+    /// API root; overridable for Twilio-compatible gateways and tests.
+    pub base_url: String,
+    /// Public URL of our status callback endpoint. Twilio reports delivery
+    /// status there and signs each request against it.
+    pub status_callback_url: Option<String>,
+}
+
+impl TwilioConfig {
+    /// Reads the SMS gateway configuration from the environment. Returns
+    /// `None` when the account SID, auth token or sender number is unset, in
+    /// which case SMS alerts are only logged in development and fail
+    /// elsewhere.
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        Some(Self {
+            account_sid: var("TWILIO_ACCOUNT_SID")?,
+            auth_token: var("TWILIO_AUTH_TOKEN")?,
+            from_number: var("TWILIO_FROM_NUMBER")?,
+            base_url: var("TWILIO_API_URL").unwrap_or_else(|| DEFAULT_TWILIO_API_URL.to_string()),
+            status_callback_url: var("TWILIO_STATUS_CALLBACK_URL"),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TwilioMessage {
+    sid: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TwilioError {
+    message: String,
+}
+
+pub struct TwilioSmsProvider {
+    client: Client,
+    config: TwilioConfig,
+}
+
+impl TwilioSmsProvider {
+    pub fn new(config: TwilioConfig) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(SMS_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            config: TwilioConfig {
+                base_url: config.base_url.trim_end_matches('/').to_string(),
+                ..config
+            },
+        }
+    }
+
+    pub fn status_callback_url(&self) -> Option<&str> {
+        self.config.status_callback_url.as_deref()
+    }
+
+    /// Checks an `X-Twilio-Signature` header: base64 HMAC-SHA1, keyed with
+    /// the auth token, over the callback URL followed by every form
+    /// parameter name and value in name order.
+    pub fn verify_signature(
+        &self,
+        url: &str,
+        params: &BTreeMap<String, String>,
+        signature: &str,
+    ) -> bool {
+        let Ok(tag) = BASE64.decode(signature) else {
+            return false;
+        };
+        let key = hmac::Key::new(
+            hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
+            self.config.auth_token.as_bytes(),
+        );
+        hmac::verify(&key, signed_payload(url, params).as_bytes(), &tag).is_ok()
+    }
+}
+
+fn signed_payload(url: &str, params: &BTreeMap<String, String>) -> String {
+    params
+        .iter()
+        .fold(url.to_string(), |mut acc, (name, value)| {
+            acc.push_str(name);
+            acc.push_str(value);
+            acc
+        })
+}
+
+#[async_trait]
+impl AlertProvider for TwilioSmsProvider {
+    fn provider_name(&self, _channel: AlertChannel) -> &'static str {
+        "twilio"
+    }
+
+    async fn send_sms(&self, to: &str, message: &str) -> anyhow::Result<AlertReceipt> {
+        let mut form = vec![
+            ("To", to),
+            ("From", self.config.from_number.as_str()),
+            ("Body", message),
+        ];
+        if let Some(callback) = self.status_callback_url() {
+            form.push(("StatusCallback", callback));
+        }
+        let response = self
+            .client
+            .post(format!(
+                "{}/2010-04-01/Accounts/{}/Messages.json",
+                self.config.base_url, self.config.account_sid
+            ))
+            .basic_auth(&self.config.account_sid, Some(&self.config.auth_token))
+            .form(&form)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response
+                .json::<TwilioError>()
+                .await
+                .map(|e| e.message)
+                .unwrap_or_default();
+            anyhow::bail!("SMS gateway returned {}: {}", status, detail);
+        }
+        let message: TwilioMessage = response.json().await?;
+        Ok(AlertReceipt {
+            provider: "twilio",
+            message_id: Some(message.sid),
+        })
+    }
+
+    async fn send_email(
+        &self,
+        _to: &str,
+        _subject: &str,
+        _body: &str,
+    ) -> anyhow::Result<AlertReceipt> {
+        anyhow::bail!("SMS gateway cannot send email")
+    }
+}
+
+// ─── Configured provider ─────────────────────────────────────────────────────
+
+/// Routes email through SMTP and SMS through the gateway when each is
+/// configured. An unconfigured channel falls back to [`MockAlertProvider`]
+/// logging in development and test; anywhere else its sends fail, so OTPs
+/// are never written to production logs.
+pub struct ConfiguredAlertProvider {
+    email: Option<SmtpEmailProvider>,
+    sms: Option<TwilioSmsProvider>,
+    log_only_fallback: bool,
+}
+
+impl ConfiguredAlertProvider {
+    pub fn new(email: Option<SmtpEmailProvider>, sms: Option<TwilioSmsProvider>) -> Self {
+        Self {
+            email,
+            sms,
+            log_only_fallback: crate::config::allows_local_fallbacks(),
+        }
+    }
+
+    /// Whether unconfigured channels log their messages (`true`) or fail.
+    pub fn with_log_only_fallback(mut self, enabled: bool) -> Self {
+        self.log_only_fallback = enabled;
+        self
+    }
+
+    pub fn from_env() -> Self {
+        let email = SmtpConfig::from_env().and_then(|config| {
+            SmtpEmailProvider::new(&config)
+                .map_err(|e| warn!("Invalid SMTP configuration, email alerts disabled: {}", e))
+                .ok()
+        });
+        let sms = TwilioConfig::from_env().map(TwilioSmsProvider::new);
+        let provider = Self::new(email, sms);
+        for (channel, configured) in [
+            ("Email", provider.email.is_some()),
+            ("SMS", provider.sms.is_some()),
+        ] {
+            match (configured, provider.log_only_fallback) {
+                (true, _) => {}
+                (false, true) => {
+                    warn!("{} alerts are not configured; they will only be logged", channel)
+                }
+                (false, false) => error!(
+                    "{} alerts are not configured; every {} alert will fail until a provider is set",
+                    channel, channel
+                ),
+            }
+        }
+        provider
+    }
+
+    pub fn sms(&self) -> Option<&TwilioSmsProvider> {
+        self.sms.as_ref()
+    }
+}
+
+#[async_trait]
+impl AlertProvider for ConfiguredAlertProvider {
+    fn provider_name(&self, channel: AlertChannel) -> &'static str {
+        match channel {
+            AlertChannel::Email => self.email.as_ref().map(|p| p.provider_name(channel)),
+            AlertChannel::Sms => self.sms.as_ref().map(|p| p.provider_name(channel)),
+        }
+        .unwrap_or(if self.log_only_fallback {
+            LOG_ONLY_PROVIDER
+        } else {
+            UNCONFIGURED_PROVIDER
+        })
+    }
+
+    async fn send_sms(&self, to: &str, message: &str) -> anyhow::Result<AlertReceipt> {
+        match &self.sms {
+            Some(provider) => provider.send_sms(to, message).await,
+            None if self.log_only_fallback => MockAlertProvider.send_sms(to, message).await,
+            None => anyhow::bail!("No SMS gateway configured (TWILIO_*)"),
+        }
+    }
+
+    async fn send_email(
+        &self,
+        to: &str,
+        subject: &str,
+        body: &str,
+    ) -> anyhow::Result<AlertReceipt> {
+        match &self.email {
+            Some(provider) => provider.send_email(to, subject, body).await,
+            None if self.log_only_fallback => MockAlertProvider.send_email(to, subject, body).await,
+            None => anyhow::bail!("No SMTP server configured (SMTP_HOST, SMTP_FROM)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// A single-connection SMTP sink that accepts one message and returns
+    /// its DATA section.
+    async fn smtp_sink() -> (u16, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let (read, mut write) = socket.into_split();
+            let mut lines = BufReader::new(read).lines();
+            let mut data = String::new();
+            let mut in_data = false;
+            write.write_all(b"220 sink ESMTP\r\n").await.unwrap();
+            while let Some(line) = lines.next_line().await.unwrap() {
+                if in_data {
+                    if line == "." {
+                        in_data = false;
+                        write
+                            .write_all(b"250 2.0.0 Ok: queued as SINK1\r\n")
+                            .await
+                            .unwrap();
+                    } else {
+                        data.push_str(&line);
+                        data.push('\n');
+                    }
+                    continue;
+                }
+                let command = line.to_ascii_uppercase();
+                let reply: &[u8] = if command.starts_with("EHLO") {
+                    b"250 sink\r\n"
+                } else if command.starts_with("DATA") {
+                    in_data = true;
+                    b"354 End data with <CR><LF>.<CR><LF>\r\n"
+                } else if command.starts_with("QUIT") {
+                    write.write_all(b"221 Bye\r\n").await.unwrap();
+                    break;
+                } else {
+                    b"250 Ok\r\n"
+                };
+                write.write_all(reply).await.unwrap();
+            }
+            data
+        });
+        (port, handle)
+    }
+
+    fn twilio_config(base_url: String) -> TwilioConfig {
+        TwilioConfig {
+            account_sid: "AC123".to_string(),
+            auth_token: "secret".to_string(),
+            from_number: "+15550000000".to_string(),
+            base_url,
+            status_callback_url: Some("https://api.example.com/api/alerts/sms/status".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn smtp_provider_delivers_to_sink() {
+        let (port, sink) = smtp_sink().await;
+        let provider = SmtpEmailProvider::new(&SmtpConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            username: None,
+            password: None,
+            from: "InheritX <alerts@inheritx.test>".to_string(),
+            security: SmtpSecurity::None,
+        })
+        .unwrap();
+
+        let receipt = provider
+            .send_email("user@example.com", "Your code", "Your code is 123456")
+            .await
+            .unwrap();
+        assert_eq!(receipt.provider, "smtp");
+        assert_eq!(
+            receipt.message_id.as_deref(),
+            Some("2.0.0 Ok: queued as SINK1")
+        );
+
+        drop(provider);
+        let data = sink.await.unwrap();
+        assert!(data.contains("Subject: Your code"));
+        assert!(data.contains("To: user@example.com"));
+        assert!(data.contains("Your code is 123456"));
+    }
+
+    #[tokio::test]
+    async fn twilio_provider_posts_message_and_returns_sid() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/2010-04-01/Accounts/AC123/Messages.json")
+                    .body_contains("To=%2B15551234567")
+                    .body_contains("Body=Your+code+is+123456")
+                    .body_contains("StatusCallback=");
+                then.status(201)
+                    .json_body(serde_json::json!({ "sid": "SM42", "status": "queued" }));
+            })
+            .await;
+
+        let provider = TwilioSmsProvider::new(twilio_config(server.base_url()));
+        let receipt = provider
+            .send_sms("+15551234567", "Your code is 123456")
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(receipt.provider, "twilio");
+        assert_eq!(receipt.message_id.as_deref(), Some("SM42"));
+    }
+
+    #[tokio::test]
+    async fn twilio_provider_surfaces_gateway_errors() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/2010-04-01/Accounts/AC123/Messages.json");
+                then.status(400).json_body(
+                    serde_json::json!({ "code": 21211, "message": "Invalid 'To' Phone Number" }),
+                );
+            })
+            .await;
+
+        let provider = TwilioSmsProvider::new(twilio_config(server.base_url()));
+        let err = provider.send_sms("bogus", "hi").await.unwrap_err();
+        assert!(err.to_string().contains("Invalid 'To' Phone Number"));
+    }
+
+    #[tokio::test]
+    async fn unconfigured_channels_fail_without_log_only_fallback() {
+        let provider = ConfiguredAlertProvider::new(None, None).with_log_only_fallback(false);
+        assert_eq!(
+            provider.provider_name(AlertChannel::Sms),
+            UNCONFIGURED_PROVIDER
+        );
+        assert!(provider.send_sms("+15551234567", "123456").await.is_err());
+        assert!(provider
+            .send_email("a@example.com", "s", "b")
+            .await
+            .is_err());
+
+        let provider = provider.with_log_only_fallback(true);
+        assert_eq!(
+            provider.provider_name(AlertChannel::Email),
+            LOG_ONLY_PROVIDER
+        );
+        let receipt = provider
+            .send_email("a@example.com", "s", "b")
+            .await
+            .unwrap();
+        assert_eq!(receipt.provider, LOG_ONLY_PROVIDER);
+    }
+
+    #[test]
+    fn twilio_signature_round_trip() {
+        let provider = TwilioSmsProvider::new(twilio_config(DEFAULT_TWILIO_API_URL.to_string()));
+        let url = provider.status_callback_url().unwrap().to_string();
+        let params: BTreeMap<String, String> =
+            [("MessageStatus", "delivered"), ("MessageSid", "SM42")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+
+        let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, b"secret");
+        let signature = BASE64.encode(hmac::sign(
+            &key,
+            format!("{url}MessageSidSM42MessageStatusdelivered").as_bytes(),
+        ));
+
+        assert!(provider.verify_signature(&url, &params, &signature));
+        assert!(!provider.verify_signature("https://other.example.com", &params, &signature));
+        assert!(!provider.verify_signature(&url, &params, "not base64!"));
     }
 }
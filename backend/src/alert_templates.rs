@@ -0,0 +1,325 @@
+//! Localized subject/body templates for email and SMS alerts.
+//!
+//! Each `notif_type` constant has an English template and translations where
+//! available. Placeholders are written `{{name}}`; every template accepts
+//! `{{message}}` (the in-app notification text) and some take extra values,
+//! e.g. `{{otp}}` for one-time passwords. Rendering fails on a placeholder
+//! with no value rather than sending a half-filled message.
+//!
+//! In-app messages are written in English, so translations do not embed
+//! `{{message}}`; they say what happened in their own language and point
+//! the user to the app for the details.
+
+use std::collections::BTreeMap;
+
+use crate::api_error::ApiError;
+use crate::notifications::notif_type;
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Values substituted into a template.
+pub type TemplateVars = BTreeMap<&'static str, String>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct AlertTemplate {
+    pub notif_type: &'static str,
+    pub locale: &'static str,
+    pub subject: &'static str,
+    pub body: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedAlert {
+    pub locale: &'static str,
+    pub subject: String,
+    pub body: String,
+}
+
+const fn template(
+    notif_type: &'static str,
+    locale: &'static str,
+    subject: &'static str,
+    body: &'static str,
+) -> AlertTemplate {
+    AlertTemplate {
+        notif_type,
+        locale,
+        subject,
+        body,
+    }
+}
+
+#[rustfmt::skip]
+const TEMPLATES: &[AlertTemplate] = &[
+    template(notif_type::TWO_FA_SENT, "en",
+        "Your InheritX verification code",
+        "Your InheritX verification code is {{otp}}. It expires in {{expires_in_minutes}} minutes. If you did not request it, ignore this message."),
+    template(notif_type::TWO_FA_SENT, "es",
+        "Tu código de verificación de InheritX",
+        "Tu código de verificación de InheritX es {{otp}}. Caduca en {{expires_in_minutes}} minutos. Si no lo solicitaste, ignora este mensaje."),
+    template(notif_type::LIQUIDATION_WARNING, "en",
+        "Action needed: your loan is at risk of liquidation",
+        "{{message}}"),
+    template(notif_type::LIQUIDATION_WARNING, "es",
+        "Acción necesaria: tu préstamo está en riesgo de liquidación",
+        "El factor de salud de tu préstamo ha caído por debajo del umbral de liquidación. Añade garantía o amortiza parte de la deuda para evitar la liquidación. Consulta los detalles en tu cuenta de InheritX."),
+    template(notif_type::SUSPICIOUS_ACTIVITY_FLAGGED, "en",
+        "Security alert on your InheritX account",
+        "We flagged unusual activity on your account: {{alert}}. {{message}}"),
+    template(notif_type::SUSPICIOUS_ACTIVITY_FLAGGED, "es",
+        "Alerta de seguridad en tu cuenta de InheritX",
+        "Detectamos actividad inusual en tu cuenta: {{alert}}. Si no la reconoces, revisa tus accesos de emergencia en InheritX."),
+    template(notif_type::EMERGENCY_CONTACT_ALERT, "en",
+        "Emergency alert for your contact: {{alert}}",
+        "You are listed as an emergency contact. Emergency alert: {{alert}}. {{message}}"),
+    template(notif_type::EMERGENCY_CONTACT_ALERT, "es",
+        "Alerta de emergencia de tu contacto",
+        "Figuras como contacto de emergencia de un usuario de InheritX. Alerta de emergencia: {{alert}}."),
+    template(notif_type::KYC_APPROVED, "en", "Your identity verification was approved", "{{message}}"),
+    template(notif_type::KYC_APPROVED, "es", "Tu verificación de identidad fue aprobada",
+        "Hemos aprobado tu verificación de identidad. Ya puedes usar todas las funciones de InheritX."),
+    template(notif_type::KYC_REJECTED, "en", "Your identity verification was rejected", "{{message}}"),
+    template(notif_type::KYC_REJECTED, "es", "Tu verificación de identidad fue rechazada",
+        "No hemos podido aprobar tu verificación de identidad. Consulta el motivo y vuelve a enviarla desde tu cuenta de InheritX."),
+    template(notif_type::PLAN_CREATED, "en", "Your inheritance plan was created", "{{message}}"),
+    template(notif_type::PLAN_CREATED, "es", "Tu plan de herencia fue creado",
+        "Tu plan de herencia se ha creado correctamente. Consulta los detalles en tu cuenta de InheritX."),
+    template(notif_type::PLAN_CLAIMED, "en", "An inheritance plan was claimed", "{{message}}"),
+    template(notif_type::PLAN_CLAIMED, "es", "Se reclamó un plan de herencia",
+        "Se ha reclamado un plan de herencia vinculado a tu cuenta. Consulta los detalles en InheritX."),
+    template(notif_type::PLAN_DEACTIVATED, "en", "Your inheritance plan was deactivated", "{{message}}"),
+    template(notif_type::PLAN_DEACTIVATED, "es", "Tu plan de herencia fue desactivado",
+        "Tu plan de herencia se ha desactivado. Consulta los detalles en tu cuenta de InheritX."),
+    template(notif_type::REPAYMENT_REMINDER, "en", "Loan repayment reminder", "{{message}}"),
+    template(notif_type::REPAYMENT_REMINDER, "es", "Recordatorio de pago del préstamo",
+        "Se acerca el vencimiento de un pago de tu préstamo. Consulta el importe y la fecha en tu cuenta de InheritX."),
+    template(notif_type::YIELD_UPDATE, "en", "Your yield update", "{{message}}"),
+    template(notif_type::YIELD_UPDATE, "es", "Actualización de tu rendimiento",
+        "Hay novedades sobre el rendimiento de tus activos. Consulta los detalles en tu cuenta de InheritX."),
+    template(notif_type::PLAN_PAUSED, "en", "Your plan was paused", "{{message}}"),
+    template(notif_type::PLAN_PAUSED, "es", "Tu plan fue pausado",
+        "Tu plan de herencia se ha pausado y no se procesarán reclamaciones hasta que se reanude. Consulta los detalles en InheritX."),
+    template(notif_type::PLAN_UNPAUSED, "en", "Your plan was resumed", "{{message}}"),
+    template(notif_type::PLAN_UNPAUSED, "es", "Tu plan fue reanudado",
+        "Tu plan de herencia se ha reanudado y vuelve a estar activo."),
+    template(notif_type::RISK_OVERRIDE_APPLIED, "en", "A risk override was applied to your plan", "{{message}}"),
+    template(notif_type::RISK_OVERRIDE_APPLIED, "es", "Se aplicó una excepción de riesgo a tu plan",
+        "Un administrador ha aplicado una excepción de riesgo a tu plan. Consulta los detalles en tu cuenta de InheritX."),
+    template(notif_type::RISK_OVERRIDE_REMOVED, "en", "A risk override was removed from your plan", "{{message}}"),
+    template(notif_type::RISK_OVERRIDE_REMOVED, "es", "Se retiró una excepción de riesgo de tu plan",
+        "Un administrador ha retirado la excepción de riesgo de tu plan. Consulta los detalles en tu cuenta de InheritX."),
+    template(notif_type::CONTINGENT_BENEFICIARY_ADDED, "en", "A contingent beneficiary was added", "{{message}}"),
+    template(notif_type::CONTINGENT_BENEFICIARY_ADDED, "es", "Se añadió un beneficiario contingente",
+        "Se ha añadido un beneficiario contingente a tu plan de herencia."),
+    template(notif_type::CONTINGENT_BENEFICIARY_REMOVED, "en", "A contingent beneficiary was removed", "{{message}}"),
+    template(notif_type::CONTINGENT_BENEFICIARY_REMOVED, "es", "Se eliminó un beneficiario contingente",
+        "Se ha eliminado un beneficiario contingente de tu plan de herencia."),
+    template(notif_type::CONTINGENT_PROMOTED, "en", "A contingent beneficiary was promoted", "{{message}}"),
+    template(notif_type::CONTINGENT_PROMOTED, "es", "Se promovió un beneficiario contingente",
+        "Un beneficiario contingente ha pasado a ser beneficiario principal de tu plan de herencia."),
+    template(notif_type::EMERGENCY_ACCESS_GRANTED, "en", "Emergency access was granted to your plan", "{{message}}"),
+    template(notif_type::EMERGENCY_ACCESS_GRANTED, "es", "Se concedió acceso de emergencia a tu plan",
+        "Se ha concedido acceso de emergencia a tu plan. Si no lo autorizaste, revócalo de inmediato desde tu cuenta de InheritX."),
+    template(notif_type::EMERGENCY_ACCESS_REVOKED, "en", "Emergency access to your plan was revoked", "{{message}}"),
+    template(notif_type::EMERGENCY_ACCESS_REVOKED, "es", "Se revocó el acceso de emergencia a tu plan",
+        "Se ha revocado el acceso de emergencia a tu plan."),
+    template(notif_type::EMERGENCY_ACCESS_EXPIRING, "en", "Emergency access to your plan is expiring", "{{message}}"),
+    template(notif_type::EMERGENCY_ACCESS_EXPIRING, "es", "El acceso de emergencia a tu plan está por caducar",
+        "Un acceso de emergencia a tu plan caducará pronto. Consulta los detalles en tu cuenta de InheritX."),
+    template(notif_type::ADMIN_ALERT, "en", "InheritX admin alert", "{{message}}"),
+    template(notif_type::FUND_STATUS_CHANGE, "en", "Insurance fund status changed", "{{message}}"),
+    template(notif_type::INSURANCE_CLAIM_PROCESSED, "en", "Your insurance claim was processed", "{{message}}"),
+    template(notif_type::INSURANCE_CLAIM_PROCESSED, "es", "Tu reclamación de seguro fue procesada",
+        "Hemos procesado tu reclamación de seguro. Consulta la resolución en tu cuenta de InheritX."),
+    template(notif_type::LOAN_LIQUIDATED, "en", "Your loan was liquidated", "{{message}}"),
+    template(notif_type::LOAN_LIQUIDATED, "es", "Tu préstamo fue liquidado",
+        "Tu préstamo ha sido liquidado porque su factor de salud cayó por debajo del umbral. Consulta los detalles en tu cuenta de InheritX."),
+    template(notif_type::WITNESS_SIGNED, "en", "A witness signed your will", "{{message}}"),
+    template(notif_type::WITNESS_SIGNED, "es", "Un testigo firmó tu testamento",
+        "Un testigo ha firmado tu testamento. Consulta el estado de las firmas en tu cuenta de InheritX."),
+    template(notif_type::NOTIFICATION_DIGEST, "en",
+        "Your InheritX summary: {{count}} updates",
+        "Here is what happened since your last summary:\n\n{{items}}"),
+    template(notif_type::NOTIFICATION_DIGEST, "es",
+        "Tu resumen de InheritX: {{count}} novedades",
+        "Esto es lo que ha pasado desde tu último resumen:\n\n{{items}}\n\nConsulta los detalles en tu cuenta de InheritX."),
+];
+
+/// Names of emergency-access risk alerts, `(alert_type, locale, label)`,
+/// filled into `{{alert}}`.
+#[rustfmt::skip]
+const RISK_ALERT_LABELS: &[(&str, &str, &str)] = &[
+    ("high_frequency_grants", "en", "several emergency access grants in a short time"),
+    ("high_frequency_grants", "es", "varios accesos de emergencia concedidos en poco tiempo"),
+    ("high_privilege_long_lived_access", "en", "long-lived high-privilege emergency access"),
+    ("high_privilege_long_lived_access", "es", "acceso de emergencia con privilegios elevados y larga duración"),
+    ("rapid_grant_revoke", "en", "emergency access revoked shortly after it was granted"),
+    ("rapid_grant_revoke", "es", "acceso de emergencia revocado poco después de concederse"),
+];
+
+/// The locales to try for `locale`: the exact tag (`es-MX`), then its
+/// language (`es`), then English.
+fn candidates(locale: &str) -> [&str; 3] {
+    let language = locale.split(['-', '_']).next().unwrap_or_default();
+    [locale, language, DEFAULT_LOCALE]
+}
+
+/// The template for `notif_type` in the closest available locale.
+pub fn find(notif_type: &str, locale: &str) -> Option<&'static AlertTemplate> {
+    candidates(locale).into_iter().find_map(|candidate| {
+        TEMPLATES
+            .iter()
+            .find(|t| t.notif_type == notif_type && t.locale.eq_ignore_ascii_case(candidate))
+    })
+}
+
+/// The name of risk alert `alert_type` in the closest available locale, or
+/// the raw type when it has none.
+pub fn risk_alert_label(alert_type: &str, locale: &str) -> String {
+    candidates(locale)
+        .into_iter()
+        .find_map(|candidate| {
+            RISK_ALERT_LABELS
+                .iter()
+                .find(|(t, l, _)| *t == alert_type && l.eq_ignore_ascii_case(candidate))
+        })
+        .map_or_else(|| alert_type.to_string(), |(_, _, label)| label.to_string())
+}
+
+/// One line of a digest for a notification with in-app text `message`. The
+/// English text is used as is; other locales get the notification's
+/// translated subject.
+pub fn digest_line(notif_type: &str, locale: &str, message: &str) -> String {
+    match find(notif_type, locale) {
+        Some(template) if template.locale != DEFAULT_LOCALE && !template.subject.contains("{{") => {
+            template.subject.to_string()
+        }
+        _ => message.to_string(),
+    }
+}
+
+pub fn render(
+    notif_type: &str,
+    locale: &str,
+    vars: &TemplateVars,
+) -> Result<RenderedAlert, ApiError> {
+    let template = find(notif_type, locale).ok_or_else(|| {
+        ApiError::Internal(anyhow::anyhow!("No alert template for '{}'", notif_type))
+    })?;
+    Ok(RenderedAlert {
+        locale: template.locale,
+        subject: fill(template.subject, vars)?,
+        body: fill(template.body, vars)?,
+    })
+}
+
+fn fill(text: &str, vars: &TemplateVars) -> Result<String, ApiError> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or_else(|| {
+            ApiError::Internal(anyhow::anyhow!("Unclosed placeholder in alert template"))
+        })?;
+        let name = after[..end].trim();
+        let value = vars.get(name).ok_or_else(|| {
+            ApiError::Internal(anyhow::anyhow!(
+                "Alert template needs a value for '{}'",
+                name
+            ))
+        })?;
+        out.push_str(value);
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&'static str, &str)]) -> TemplateVars {
+        pairs.iter().map(|(k, v)| (*k, v.to_string())).collect()
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let rendered = render(
+            notif_type::TWO_FA_SENT,
+            "en",
+            &vars(&[("otp", "123456"), ("expires_in_minutes", "5")]),
+        )
+        .unwrap();
+        assert_eq!(rendered.subject, "Your InheritX verification code");
+        assert!(rendered
+            .body
+            .starts_with("Your InheritX verification code is 123456."));
+        assert!(rendered.body.contains("expires in 5 minutes"));
+    }
+
+    #[test]
+    fn falls_back_from_region_to_language_to_english() {
+        let vars = vars(&[("message", "m")]);
+        assert_eq!(
+            render(notif_type::PLAN_CREATED, "es-MX", &vars)
+                .unwrap()
+                .locale,
+            "es"
+        );
+        assert_eq!(
+            render(notif_type::ADMIN_ALERT, "es", &vars).unwrap().locale,
+            "en"
+        );
+        assert_eq!(
+            render(notif_type::PLAN_CREATED, "de", &vars)
+                .unwrap()
+                .locale,
+            "en"
+        );
+    }
+
+    #[test]
+    fn translations_do_not_embed_english_text() {
+        for template in TEMPLATES.iter().filter(|t| t.locale != DEFAULT_LOCALE) {
+            assert!(
+                !template.body.contains("{{message}}"),
+                "{} ({})",
+                template.notif_type,
+                template.locale
+            );
+        }
+        assert_eq!(
+            risk_alert_label("rapid_grant_revoke", "es-MX"),
+            "acceso de emergencia revocado poco después de concederse"
+        );
+        assert_eq!(risk_alert_label("unknown_alert", "es"), "unknown_alert");
+        assert_eq!(
+            digest_line(notif_type::PLAN_CREATED, "es", "Plan created"),
+            "Tu plan de herencia fue creado"
+        );
+        assert_eq!(
+            digest_line(notif_type::PLAN_CREATED, "en", "Plan created"),
+            "Plan created"
+        );
+    }
+
+    #[test]
+    fn missing_value_is_an_error() {
+        assert!(render(notif_type::TWO_FA_SENT, "en", &vars(&[("otp", "1")])).is_err());
+        assert!(render("no_such_type", "en", &vars(&[])).is_err());
+    }
+
+    #[test]
+    fn every_template_has_an_english_version() {
+        for template in TEMPLATES {
+            assert_eq!(
+                find(template.notif_type, DEFAULT_LOCALE).map(|t| t.locale),
+                Some(DEFAULT_LOCALE),
+                "{}",
+                template.notif_type
+            );
+        }
+    }
+}
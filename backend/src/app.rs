@@ -1,5 +1,6 @@
 use axum::{
     extract::{Path, Query, State},
+    http::StatusCode,
     middleware,
     routing::{delete, get, post, put},
     Json, Router,
@@ -21,11 +22,16 @@ use crate::middleware::{
 };
 use uuid::Uuid;
 
+use crate::alert_delivery::twilio_status_callback;
+use crate::alert_provider::ConfiguredAlertProvider;
 use crate::analytics::analytics_router;
 use crate::api_error::ApiError;
 use crate::api_versioning::{list_api_versions, versioning_middleware};
 use crate::auth::{AuthenticatedAdmin, AuthenticatedUser};
-use crate::beneficiary_sync::{BeneficiarySyncService, DocumentBeneficiary};
+use crate::beneficiary_sync::{
+    BeneficiarySyncService, ContractPlanReader, DocumentBeneficiary, InMemoryContractPlanReader,
+    SorobanContractPlanReader,
+};
 use crate::collateral_management::{
     AddCollateralRequest, CollateralManagementService, RemoveCollateralRequest,
     SwapCollateralRequest,
@@ -44,7 +50,18 @@ use crate::insurance_fund::{CreateInsuranceClaimRequest, ProcessInsuranceClaimRe
 use crate::legacy_content::{ContentListFilters, LegacyContentService};
 use crate::loan_lifecycle::{CreateLoanRequest, LoanLifecycleService, LoanListFilters};
 use crate::message_access_audit::{MessageAccessAuditService, MessageAuditFilters};
+use crate::notification_dispatcher::NotificationDispatcher;
+use crate::notification_preferences::{
+    get_notification_preferences, update_notification_preference, update_notification_settings,
+};
 use crate::pagination::PaginationQuery;
+use crate::payout_details::{
+    PayoutDetails, SealPayoutDetailsRequest, SealedPayoutDetails, SettlementKeyring,
+};
+use crate::plan_freeze::{
+    LoggingPlanFreezer, OnChainPlanFreezer, PlanFreezeRetryWorker, PlanFreezerConfig,
+    SorobanPlanFreezer,
+};
 use crate::secure_messages::{
     CreateLegacyMessageRequest, LegacyMessageDeliveryService, MessageEncryptionService,
     MessageKeyService,
@@ -52,14 +69,18 @@ use crate::secure_messages::{
 use crate::service::{
     ClaimPlanRequest, CreateEmergencyAccessGrantRequest, CreateEmergencyContactRequest,
     CreatePlanRequest, EmergencyAccessAuditLogFilters, EmergencyAccessService,
-    EmergencyAdminService, EmergencyContactService, EmergencySessionService, KycRecord, KycService,
-    KycStatus, LoanSimulationRequest, LoanSimulationService, PausePlanRequest, PlanService,
-    RevokeEmergencyAccessGrantRequest, RiskOverrideRequest, StartSessionRequest,
-    UnpausePlanRequest, UpdateEmergencyContactRequest,
+    EmergencyActionResponse, EmergencyAdminService, EmergencyContactService,
+    EmergencySessionService, KycRecord, KycService, KycStatus, LoanSimulationRequest,
+    LoanSimulationService, PausePlanRequest, PlanService, RevokeEmergencyAccessGrantRequest,
+    RiskOverrideRequest, StartSessionRequest, UnpausePlanRequest, UpdateEmergencyContactRequest,
 };
 use crate::session::{list_sessions, logout, logout_all, revoke_session, session_guard_middleware};
 use crate::stress_testing::StressTestingEngine;
-use crate::webhook::{delete_webhook, get_webhooks, register_webhook, WebhookService};
+use crate::webhook::{
+    delete_webhook, enable_webhook, get_webhook_delivery, get_webhooks, list_event_types,
+    list_webhook_dead_letters, list_webhook_deliveries, register_webhook, resend_webhook_delivery,
+    send_test_webhook, WebhookService,
+};
 use crate::will_compliance::{ValidationResult, WillComplianceService};
 use crate::will_pdf::{WillDocumentInput, WillPdfService, WillTemplate};
 use crate::will_signature::{
@@ -78,6 +99,9 @@ pub struct AppState {
     pub stress_testing_engine: Arc<StressTestingEngine>,
     pub insurance_fund_service: Arc<crate::insurance_fund::InsuranceFundService>,
     pub webhook_service: Arc<WebhookService>,
+    pub plan_freezer: Arc<dyn OnChainPlanFreezer>,
+    pub plan_reader: Arc<dyn ContractPlanReader>,
+    pub alert_provider: Arc<ConfiguredAlertProvider>,
 }
 
 pub async fn create_app(
@@ -93,6 +117,8 @@ pub async fn create_app(
         tracing::warn!("Failed to initialize default price feeds: {}", e);
     }
 
+    let alert_provider = Arc::new(ConfiguredAlertProvider::from_env());
+
     let risk_engine = Arc::new(crate::risk_engine::RiskEngine::new(
         db.clone(),
         price_feed.clone(),
@@ -112,9 +138,46 @@ pub async fn create_app(
         Arc::new(crate::insurance_fund::InsuranceFundService::new(db.clone()));
     insurance_fund_service.clone().start();
 
+    Arc::new(NotificationDispatcher::new(
+        db.clone(),
+        alert_provider.clone(),
+    ))
+    .start();
+
     let webhook_service = Arc::new(WebhookService::new(db.clone()));
     let cache = Arc::new(crate::cache::CacheService::from_env().await);
 
+    let plan_reader: Arc<dyn ContractPlanReader> = match SorobanContractPlanReader::from_env() {
+        Some(reader) => Arc::new(reader),
+        None if crate::config::allows_local_fallbacks() => {
+            tracing::warn!(
+                "SOROBAN_RPC_URL or INHERITANCE_CONTRACT_ID not set; beneficiary sync will not find on-chain plans"
+            );
+            Arc::new(InMemoryContractPlanReader::new())
+        }
+        None => {
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "SOROBAN_RPC_URL and INHERITANCE_CONTRACT_ID must be set outside development"
+            )))
+        }
+    };
+
+    let plan_freezer: Arc<dyn OnChainPlanFreezer> = match PlanFreezerConfig::from_env() {
+        Some(freezer_config) => Arc::new(SorobanPlanFreezer::new(&freezer_config)?),
+        None if crate::config::allows_local_fallbacks() => {
+            tracing::warn!(
+                "PLAN_FREEZER_SECRET_KEY not set; plan pauses will not be mirrored on chain"
+            );
+            Arc::new(LoggingPlanFreezer::new())
+        }
+        None => {
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "SOROBAN_RPC_URL, INHERITANCE_CONTRACT_ID and PLAN_FREEZER_SECRET_KEY must be set outside development"
+            )))
+        }
+    };
+    Arc::new(PlanFreezeRetryWorker::new(db.clone(), plan_freezer.clone())).start();
+
     let state = Arc::new(AppState {
         db: db.clone(),
         config: config.clone(),
@@ -123,6 +186,9 @@ pub async fn create_app(
         stress_testing_engine,
         insurance_fund_service,
         webhook_service,
+        plan_freezer,
+        plan_reader,
+        alert_provider,
     });
 
     // ── Rate limiting (config-driven) ────────────────────────────────────────
@@ -226,7 +292,9 @@ pub async fn create_app(
             get(get_due_for_claim_plan),
         )
         .route("/api/plans/:plan_id/claim", post(claim_plan))
+        .route("/api/payout-details/seal", post(seal_payout_details))
         .route("/api/plans/:plan_id", get(get_plan))
+        .route("/api/plans/:plan_id/assets", get(get_plan_assets))
         .route("/api/plans", post(create_plan))
         .route(
             "/api/messages/legacy",
@@ -600,10 +668,40 @@ pub async fn create_app(
         )
         .route("/api/admin/will/audit/search", get(search_admin_audit_logs))
         .route("/api/admin/logs", get(get_admin_logs))
+        .route("/api/admin/outbox/requeue", post(requeue_failed_outbox_events))
         .route("/api/notifications", get(get_notifications))
+        .route(
+            "/api/notifications/preferences",
+            get(get_notification_preferences),
+        )
+        .route(
+            "/api/notifications/preferences/:notif_type",
+            put(update_notification_preference),
+        )
+        .route(
+            "/api/notifications/settings",
+            put(update_notification_settings),
+        )
+        .route("/api/alerts/sms/status", post(twilio_status_callback))
         // ── Webhook System ───────────────────────────────────────────────────
         .route("/api/webhooks", post(register_webhook).get(get_webhooks))
         .route("/api/webhooks/:webhook_id", delete(delete_webhook))
+        .route("/api/webhooks/event-types", get(list_event_types))
+        .route("/api/webhooks/dead-letters", get(list_webhook_dead_letters))
+        .route("/api/webhooks/:webhook_id/test", post(send_test_webhook))
+        .route("/api/webhooks/:webhook_id/enable", post(enable_webhook))
+        .route(
+            "/api/webhooks/:webhook_id/deliveries",
+            get(list_webhook_deliveries),
+        )
+        .route(
+            "/api/webhooks/:webhook_id/deliveries/:delivery_id",
+            get(get_webhook_delivery),
+        )
+        .route(
+            "/api/webhooks/:webhook_id/deliveries/:delivery_id/resend",
+            post(resend_webhook_delivery),
+        )
         .route(
             "/api/admin/will/audit/user/:user_id",
             get(get_user_audit_activity),
@@ -806,6 +904,21 @@ async fn get_plan(
     }
 }
 
+async fn get_plan_assets(
+    State(state): State<Arc<AppState>>,
+    Path(plan_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    PlanService::get_plan_by_id(&state.db, plan_id, user.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Plan {plan_id} not found")))?;
+    let assets = PlanService::get_plan_assets(&state.db, plan_id).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": assets
+    })))
+}
+
 async fn claim_plan(
     State(state): State<Arc<AppState>>,
     Path(plan_id): Path<Uuid>,
@@ -822,6 +935,34 @@ async fn claim_plan(
     })))
 }
 
+/// Seal fiat payout details to the current settlement key, for clients that
+/// write beneficiaries to the contract directly.
+async fn seal_payout_details(
+    AuthenticatedUser(_user): AuthenticatedUser,
+    Json(req): Json<SealPayoutDetailsRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let details = PayoutDetails {
+        beneficiary_name: req.beneficiary_name.trim().to_string(),
+        bank_name: req.bank_name.trim().to_string(),
+        bank_account_number: req.bank_account_number.trim().to_string(),
+    };
+    if details.beneficiary_name.is_empty()
+        || details.bank_name.is_empty()
+        || details.bank_account_number.is_empty()
+    {
+        return Err(ApiError::BadRequest(
+            "beneficiary_name, bank_name and bank_account_number are required".to_string(),
+        ));
+    }
+
+    let keyring = SettlementKeyring::require_from_env()?;
+    let sealed = SealedPayoutDetails {
+        key_id: keyring.current_key_id(),
+        envelope_hex: hex::encode(keyring.seal_details(&details)?),
+    };
+    Ok(Json(json!({ "status": "success", "data": sealed })))
+}
+
 async fn get_due_for_claim_plan(
     State(state): State<Arc<AppState>>,
     Path(plan_id): Path<Uuid>,
@@ -1048,7 +1189,13 @@ async fn create_emergency_access_grant(
     AuthenticatedUser(user): AuthenticatedUser,
     Json(req): Json<CreateEmergencyAccessGrantRequest>,
 ) -> Result<Json<Value>, ApiError> {
-    let result = EmergencyAccessService::grant_access(&state.db, user.user_id, &req).await?;
+    let result = EmergencyAccessService::grant_access(
+        &state.db,
+        state.alert_provider.as_ref(),
+        user.user_id,
+        &req,
+    )
+    .await?;
     Ok(Json(json!({ "status": "success", "data": result })))
 }
 
@@ -1058,8 +1205,14 @@ async fn revoke_emergency_access_grant(
     AuthenticatedUser(user): AuthenticatedUser,
     Json(req): Json<RevokeEmergencyAccessGrantRequest>,
 ) -> Result<Json<Value>, ApiError> {
-    let result =
-        EmergencyAccessService::revoke_access(&state.db, user.user_id, grant_id, &req).await?;
+    let result = EmergencyAccessService::revoke_access(
+        &state.db,
+        state.alert_provider.as_ref(),
+        user.user_id,
+        grant_id,
+        &req,
+    )
+    .await?;
     Ok(Json(json!({ "status": "success", "data": result })))
 }
 
@@ -1449,22 +1602,45 @@ async fn pause_plan(
     State(state): State<Arc<AppState>>,
     AuthenticatedAdmin(admin): AuthenticatedAdmin,
     Json(req): Json<PausePlanRequest>,
-) -> Result<Json<Value>, ApiError> {
-    let result = EmergencyAdminService::pause_plan(&state.db, admin.admin_id, &req).await?;
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    let result = EmergencyAdminService::pause_plan(
+        &state.db,
+        state.plan_freezer.as_ref(),
+        admin.admin_id,
+        &req,
+    )
+    .await?;
     let _ = state.cache.invalidate_prefix("analytics:plan").await;
     let _ = state.cache.invalidate("analytics:dashboard").await;
-    Ok(Json(json!({ "status": "success", "data": result })))
+    Ok(emergency_action_reply(result))
 }
 
 async fn unpause_plan(
     State(state): State<Arc<AppState>>,
     AuthenticatedAdmin(admin): AuthenticatedAdmin,
     Json(req): Json<UnpausePlanRequest>,
-) -> Result<Json<Value>, ApiError> {
-    let result = EmergencyAdminService::unpause_plan(&state.db, admin.admin_id, &req).await?;
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    let result = EmergencyAdminService::unpause_plan(
+        &state.db,
+        state.plan_freezer.as_ref(),
+        admin.admin_id,
+        &req,
+    )
+    .await?;
     let _ = state.cache.invalidate_prefix("analytics:plan").await;
     let _ = state.cache.invalidate("analytics:dashboard").await;
-    Ok(Json(json!({ "status": "success", "data": result })))
+    Ok(emergency_action_reply(result))
+}
+
+/// 202 Accepted while the on-chain side of a pause or unpause is still being
+/// retried, 200 once it has landed (or there is nothing to mirror).
+fn emergency_action_reply(result: EmergencyActionResponse) -> (StatusCode, Json<Value>) {
+    let status = if result.is_pending_on_chain() {
+        StatusCode::ACCEPTED
+    } else {
+        StatusCode::OK
+    };
+    (status, Json(json!({ "status": "success", "data": result })))
 }
 
 async fn set_risk_override(
@@ -1714,9 +1890,13 @@ async fn sync_beneficiaries(
     AuthenticatedUser(_user): AuthenticatedUser,
     Json(req): Json<SyncBeneficiariesRequest>,
 ) -> Result<Json<Value>, ApiError> {
-    let result =
-        BeneficiarySyncService::sync_and_validate(&state.db, plan_id, &req.document_beneficiaries)
-            .await?;
+    let result = BeneficiarySyncService::sync_and_validate(
+        &state.db,
+        state.plan_reader.as_ref(),
+        plan_id,
+        &req.document_beneficiaries,
+    )
+    .await?;
     Ok(Json(json!({ "status": "success", "data": result })))
 }
 
@@ -2054,7 +2234,8 @@ async fn download_will_document(
         .decode(&doc.pdf_base64)
         .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to decode PDF: {}", e)))?;
 
-    // Emit download event
+    // Record the access before serving the document, so a download that
+    // cannot be logged is refused rather than left out of the audit trail.
     let event = crate::will_events::WillEvent::WillDecrypted {
         vault_id: format!("plan_{}", doc.plan_id),
         document_id,
@@ -2062,9 +2243,7 @@ async fn download_will_document(
         accessed_by: user.user_id,
         timestamp: chrono::Utc::now(),
     };
-    if let Err(e) = crate::will_events::WillEventService::emit(&state.db, event).await {
-        tracing::warn!("Failed to emit document download event: {}", e);
-    }
+    crate::will_events::WillEventService::emit(&state.db, event).await?;
 
     // Build response with proper headers for download
     use axum::body::Body;
@@ -2099,7 +2278,8 @@ async fn download_will_document_by_version(
         .decode(&doc.pdf_base64)
         .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to decode PDF: {}", e)))?;
 
-    // Emit download event
+    // Record the access before serving the document, so a download that
+    // cannot be logged is refused rather than left out of the audit trail.
     let event = crate::will_events::WillEvent::WillDecrypted {
         vault_id: format!("plan_{}", doc.plan_id),
         document_id: doc.document_id,
@@ -2107,9 +2287,7 @@ async fn download_will_document_by_version(
         accessed_by: user.user_id,
         timestamp: chrono::Utc::now(),
     };
-    if let Err(e) = crate::will_events::WillEventService::emit(&state.db, event).await {
-        tracing::warn!("Failed to emit document download event: {}", e);
-    }
+    crate::will_events::WillEventService::emit(&state.db, event).await?;
 
     // Build response with proper headers for download
     use axum::body::Body;
@@ -2652,6 +2830,28 @@ async fn get_admin_logs(
     Ok(Json(json!(pagination.create_response(logs, total_count))))
 }
 
+#[derive(serde::Deserialize)]
+pub struct RequeueOutboxRequest {
+    /// Event ids to requeue; every failed event when omitted (send `{}`).
+    pub event_ids: Option<Vec<String>>,
+}
+
+/// Admin: give failed outbox events (or their bus publication) a fresh
+/// retry budget.
+///
+/// `POST /api/admin/outbox/requeue`
+async fn requeue_failed_outbox_events(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+    Json(payload): Json<RequeueOutboxRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let requeued =
+        crate::outbox::Outbox::requeue_failed(&state.db, payload.event_ids.as_deref()).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": { "requeued": requeued } }),
+    ))
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Collateral Management Handlers
 // ─────────────────────────────────────────────────────────────────────────────
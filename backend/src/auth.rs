@@ -1,7 +1,10 @@
+use crate::alert_delivery::{AlertDeliveryService, AlertRequest};
+use crate::alert_provider::AlertChannel;
+use crate::alert_templates::TemplateVars;
 use crate::api_error::ApiError;
 use crate::app::AppState;
 use crate::config::Config;
-use crate::notifications::{audit_action, entity_type, AuditLogService};
+use crate::notifications::{audit_action, entity_type, notif_type, AuditLogService};
 use axum::{extract::State, Json};
 use bcrypt::verify;
 use chrono::{DateTime, Duration, Utc};
@@ -13,6 +16,10 @@ use sqlx::FromRow;
 use std::sync::Arc;
 use stellar_strkey::Strkey;
 use uuid::Uuid;
+
+/// How long a 2FA code stays valid.
+const OTP_TTL_MINUTES: i64 = 5;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NonceResponse {
     pub nonce: String,
@@ -329,16 +336,13 @@ pub async fn send_2fa(
     State(state): State<Arc<AppState>>,
     Json(payload): Json<Send2faRequest>,
 ) -> Result<Json<TwoFaResponse>, ApiError> {
-    // 1. Check if user exists
-    let user_exists =
-        sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+    // 1. Look up where to send the code
+    let (email, locale) =
+        sqlx::query_as::<_, (String, String)>("SELECT email, locale FROM users WHERE id = $1")
             .bind(payload.user_id)
-            .fetch_one(&state.db)
-            .await?;
-
-    if !user_exists {
-        return Err(ApiError::NotFound("User not found".to_string()));
-    }
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
 
     // 2. Generate 6-digit OTP
     use ring::rand::SecureRandom;
@@ -355,7 +359,7 @@ pub async fn send_2fa(
     let otp_hash = bcrypt::hash(&otp, bcrypt::DEFAULT_COST)
         .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to hash OTP: {e}")))?;
 
-    let expires_at = Utc::now() + Duration::minutes(5);
+    let expires_at = Utc::now() + Duration::minutes(OTP_TTL_MINUTES);
 
     // 4. Store/Update OTP in user_2fa
     sqlx::query(
@@ -375,11 +379,23 @@ pub async fn send_2fa(
     .execute(&state.db)
     .await?;
 
-    // 5. Mock Email Notification
-    tracing::info!("--- [2FA OTP] ---");
-    tracing::info!("User ID: {}", payload.user_id);
-    tracing::info!("OTP Code: {}", otp);
-    tracing::info!("-----------------");
+    // 5. Email the code
+    AlertDeliveryService::send(
+        &state.db,
+        state.alert_provider.as_ref(),
+        AlertRequest {
+            user_id: Some(payload.user_id),
+            channel: AlertChannel::Email,
+            recipient: &email,
+            notif_type: notif_type::TWO_FA_SENT,
+            locale: &locale,
+            vars: TemplateVars::from([
+                ("otp", otp),
+                ("expires_in_minutes", OTP_TTL_MINUTES.to_string()),
+            ]),
+        },
+    )
+    .await?;
 
     // Optional: Log to audit logs and notifications
     AuditLogService::log(
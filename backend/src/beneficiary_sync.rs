@@ -2,24 +2,62 @@
 //!
 //! Validates that beneficiaries in a legal will document match the
 //! smart contract vault data, blocking document generation on mismatch.
+//!
+//! Contract state is read through a [`ContractPlanReader`]: in production a
+//! [`SorobanContractPlanReader`] simulates `get_plan_details` against Soroban
+//! RPC, while tests use [`InMemoryContractPlanReader`]. The contract only stores
+//! SHA-256 hashes of beneficiary names and emails, so document beneficiaries
+//! are hashed the same way (see [`hash_contract_string`]) before comparison.
 
 use crate::api_error::ApiError;
+use crate::chain_indexer::{scval_to_json, SorobanRpcClient};
+use axum::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use soroban_sdk::xdr::{
+    HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo, MuxedAccount, Operation,
+    OperationBody, Preconditions, ReadXdr, ScAddress, ScString, ScSymbol, ScVal, SequenceNumber,
+    Transaction, TransactionEnvelope, TransactionExt, TransactionV1Envelope, WriteXdr,
+};
 use sqlx::PgPool;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::RwLock;
 use uuid::Uuid;
 
+/// Account used as the source of simulated read-only transactions. Simulation
+/// never submits, so any syntactically valid account works.
+const DEFAULT_SIMULATION_SOURCE: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
 // ─── Types ────────────────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A beneficiary as stored in `InheritancePlan.beneficiaries` on chain.
+/// Hashes are hex-encoded.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ContractBeneficiary {
-    pub wallet_address: String,
-    pub allocation_percent: Decimal,
+    pub hashed_full_name: String,
+    pub hashed_email: String,
+    pub allocation_bp: u32,
+    pub priority: u32,
+    pub is_claimed: bool,
+}
+
+/// The subset of an on-chain `InheritancePlan` needed for sync checks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContractPlan {
+    pub owner: String,
+    pub is_active: bool,
+    pub total_allocation_bp: u32,
+    pub beneficiaries: Vec<ContractBeneficiary>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DocumentBeneficiary {
     pub wallet_address: String,
+    pub full_name: String,
+    pub email: String,
     pub allocation_percent: Decimal,
 }
 
@@ -48,46 +86,239 @@ pub struct BeneficiarySyncResult {
     pub checked_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Hashes `value` the way `InheritanceContract::hash_string` does: SHA-256
+/// over the XDR encoding of the `ScVal::String`. Returns lowercase hex.
+pub fn hash_contract_string(value: &str) -> Result<String, ApiError> {
+    let sc_string = ScString(
+        value
+            .try_into()
+            .map_err(|_| ApiError::BadRequest("Beneficiary field is too long".to_string()))?,
+    );
+    let xdr = ScVal::String(sc_string)
+        .to_xdr(Limits::none())
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to encode string XDR: {e}")))?;
+    Ok(hex::encode(Sha256::digest(xdr)))
+}
+
+// ─── Chain readers ────────────────────────────────────────────────────────────
+
+/// Reads inheritance plan state from the contract.
+#[async_trait]
+pub trait ContractPlanReader: Send + Sync {
+    /// Returns the plan stored under `contract_plan_id`, or `None` when the
+    /// contract has no such plan.
+    async fn get_plan_details(
+        &self,
+        contract_plan_id: u64,
+    ) -> Result<Option<ContractPlan>, ApiError>;
+}
+
+/// Reads plans by simulating `get_plan_details` through Soroban RPC.
+pub struct SorobanContractPlanReader {
+    rpc: SorobanRpcClient,
+    contract_id: String,
+    source_account: String,
+}
+
+impl SorobanContractPlanReader {
+    pub fn new(
+        rpc_url: impl Into<String>,
+        contract_id: impl Into<String>,
+        source_account: impl Into<String>,
+    ) -> Self {
+        Self {
+            rpc: SorobanRpcClient::new(rpc_url),
+            contract_id: contract_id.into(),
+            source_account: source_account.into(),
+        }
+    }
+
+    /// Builds a reader from `SOROBAN_RPC_URL` and `INHERITANCE_CONTRACT_ID`.
+    /// Returns `None` when either is unset.
+    pub fn from_env() -> Option<Self> {
+        let rpc_url = std::env::var("SOROBAN_RPC_URL")
+            .ok()
+            .filter(|v| !v.is_empty())?;
+        let contract_id = std::env::var("INHERITANCE_CONTRACT_ID")
+            .ok()
+            .filter(|v| !v.is_empty())?;
+        let source_account = std::env::var("SOROBAN_SIMULATION_SOURCE_ACCOUNT")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| DEFAULT_SIMULATION_SOURCE.to_string());
+        Some(Self::new(rpc_url, contract_id, source_account))
+    }
+
+    /// Builds the unsigned envelope invoking `get_plan_details(plan_id)`.
+    fn build_envelope(&self, contract_plan_id: u64) -> Result<String, ApiError> {
+        let invalid = |what: &str, e: &dyn std::fmt::Display| {
+            ApiError::Internal(anyhow::anyhow!("Invalid {what}: {e}"))
+        };
+
+        let contract_address = ScAddress::from_str(&self.contract_id)
+            .map_err(|e| invalid("inheritance contract id", &e))?;
+        let source_account = MuxedAccount::from_str(&self.source_account)
+            .map_err(|e| invalid("simulation source account", &e))?;
+        let function_name = ScSymbol(
+            "get_plan_details"
+                .try_into()
+                .map_err(|e| invalid("symbol", &e))?,
+        );
+        let args = vec![ScVal::U64(contract_plan_id)]
+            .try_into()
+            .map_err(|e| invalid("arguments", &e))?;
+
+        let operation = Operation {
+            source_account: None,
+            body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                    contract_address,
+                    function_name,
+                    args,
+                }),
+                auth: Default::default(),
+            }),
+        };
+
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account,
+                fee: 100,
+                seq_num: SequenceNumber(0),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: vec![operation]
+                    .try_into()
+                    .map_err(|e| invalid("operations", &e))?,
+                ext: TransactionExt::V0,
+            },
+            signatures: Default::default(),
+        });
+
+        let bytes = envelope
+            .to_xdr(Limits::none())
+            .map_err(|e| invalid("transaction envelope", &e))?;
+        Ok(BASE64.encode(bytes))
+    }
+}
+
+#[async_trait]
+impl ContractPlanReader for SorobanContractPlanReader {
+    async fn get_plan_details(
+        &self,
+        contract_plan_id: u64,
+    ) -> Result<Option<ContractPlan>, ApiError> {
+        let envelope = self.build_envelope(contract_plan_id)?;
+        let simulation = self.rpc.simulate_transaction(&envelope).await?;
+
+        if let Some(error) = simulation.error {
+            return Err(ApiError::ExternalService(format!(
+                "get_plan_details simulation failed: {error}"
+            )));
+        }
+        let result = simulation.results.first().ok_or_else(|| {
+            ApiError::ExternalService("get_plan_details simulation returned no result".to_string())
+        })?;
+
+        let bytes = BASE64.decode(&result.xdr).map_err(|e| {
+            ApiError::ExternalService(format!("Invalid get_plan_details result encoding: {e}"))
+        })?;
+        let value = ScVal::from_xdr(bytes, Limits::none()).map_err(|e| {
+            ApiError::ExternalService(format!("Invalid get_plan_details result XDR: {e}"))
+        })?;
+
+        parse_plan_details(&value)
+    }
+}
+
+/// Converts the `Option<InheritancePlan>` returned by `get_plan_details`.
+pub fn parse_plan_details(value: &ScVal) -> Result<Option<ContractPlan>, ApiError> {
+    if matches!(value, ScVal::Void) {
+        return Ok(None);
+    }
+    serde_json::from_value(scval_to_json(value))
+        .map(Some)
+        .map_err(|e| ApiError::ExternalService(format!("Unexpected InheritancePlan layout: {e}")))
+}
+
+/// In-memory reader for tests and local development.
+#[derive(Default)]
+pub struct InMemoryContractPlanReader {
+    plans: RwLock<HashMap<u64, ContractPlan>>,
+}
+
+impl InMemoryContractPlanReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert_plan(&self, contract_plan_id: u64, plan: ContractPlan) {
+        self.plans
+            .write()
+            .expect("plan reader lock poisoned")
+            .insert(contract_plan_id, plan);
+    }
+}
+
+#[async_trait]
+impl ContractPlanReader for InMemoryContractPlanReader {
+    async fn get_plan_details(
+        &self,
+        contract_plan_id: u64,
+    ) -> Result<Option<ContractPlan>, ApiError> {
+        Ok(self
+            .plans
+            .read()
+            .expect("plan reader lock poisoned")
+            .get(&contract_plan_id)
+            .cloned())
+    }
+}
+
 // ─── Service ──────────────────────────────────────────────────────────────────
 
 pub struct BeneficiarySyncService;
 
 impl BeneficiarySyncService {
-    /// Fetch beneficiaries stored in the DB for a plan (representing contract state).
+    /// Fetch the beneficiaries recorded on chain for a backend plan.
     pub async fn fetch_contract_beneficiaries(
         db: &PgPool,
+        reader: &dyn ContractPlanReader,
         plan_id: Uuid,
     ) -> Result<Vec<ContractBeneficiary>, ApiError> {
-        #[derive(sqlx::FromRow)]
-        struct Row {
-            wallet_address: String,
-            allocation_percent: Decimal,
-        }
-
-        let rows = sqlx::query_as::<_, Row>(
-            "SELECT wallet_address, allocation_percent \
-             FROM plan_beneficiaries WHERE plan_id = $1 ORDER BY wallet_address",
-        )
-        .bind(plan_id)
-        .fetch_all(db)
-        .await?;
-
-        Ok(rows
-            .into_iter()
-            .map(|r| ContractBeneficiary {
-                wallet_address: r.wallet_address,
-                allocation_percent: r.allocation_percent,
-            })
-            .collect())
+        let contract_plan_id: Option<Option<i64>> =
+            sqlx::query_scalar("SELECT contract_plan_id FROM plans WHERE id = $1")
+                .bind(plan_id)
+                .fetch_optional(db)
+                .await?;
+
+        let contract_plan_id = contract_plan_id
+            .ok_or_else(|| ApiError::NotFound(format!("Plan {plan_id} not found")))?
+            .ok_or_else(|| {
+                ApiError::BadRequest(format!("Plan {plan_id} has not been created on chain"))
+            })?;
+
+        let plan = reader
+            .get_plan_details(contract_plan_id as u64)
+            .await?
+            .ok_or_else(|| {
+                ApiError::NotFound(format!(
+                    "Contract plan {contract_plan_id} not found on chain"
+                ))
+            })?;
+
+        Ok(plan.beneficiaries)
     }
 
     /// Compare document beneficiaries against contract beneficiaries.
-    /// Returns a sync result — callers should block generation if status is Mismatched.
+    /// Beneficiaries are paired by email hash; names and allocations are then
+    /// compared field by field. Returns a sync result — callers should block
+    /// generation if status is Mismatched.
     pub fn validate(
         plan_id: Uuid,
         contract: &[ContractBeneficiary],
         document: &[DocumentBeneficiary],
-    ) -> BeneficiarySyncResult {
+    ) -> Result<BeneficiarySyncResult, ApiError> {
         let mut mismatches = Vec::new();
         let checked_at = chrono::Utc::now();
 
@@ -101,50 +332,54 @@ impl BeneficiarySyncService {
             });
         }
 
-        // Build lookup maps (normalise addresses to lowercase)
-        use std::collections::HashMap;
-        let contract_map: HashMap<String, &ContractBeneficiary> = contract
+        // Index contract entries by email hash; entries are removed as they
+        // are matched so that leftovers are contract-only beneficiaries.
+        let mut unmatched: HashMap<String, &ContractBeneficiary> = contract
             .iter()
-            .map(|b| (b.wallet_address.to_lowercase(), b))
+            .map(|b| (b.hashed_email.to_lowercase(), b))
             .collect();
 
-        let document_map: HashMap<String, &DocumentBeneficiary> = document
-            .iter()
-            .map(|b| (b.wallet_address.to_lowercase(), b))
-            .collect();
+        for db_entry in document {
+            let email_hash = hash_contract_string(&db_entry.email)?;
+            let Some(cb) = unmatched.remove(&email_hash) else {
+                mismatches.push(MismatchDetail {
+                    wallet_address: db_entry.wallet_address.clone(),
+                    field: "email".to_string(),
+                    contract_value: "MISSING".to_string(),
+                    document_value: db_entry.email.clone(),
+                });
+                continue;
+            };
 
-        // Check every contract entry exists in document with matching allocation
-        for (addr, cb) in &contract_map {
-            match document_map.get(addr) {
-                None => mismatches.push(MismatchDetail {
-                    wallet_address: cb.wallet_address.clone(),
-                    field: "wallet_address".to_string(),
-                    contract_value: cb.wallet_address.clone(),
-                    document_value: "MISSING".to_string(),
-                }),
-                Some(db_entry) => {
-                    if (cb.allocation_percent - db_entry.allocation_percent).abs()
-                        > Decimal::new(1, 4)
-                    {
-                        mismatches.push(MismatchDetail {
-                            wallet_address: cb.wallet_address.clone(),
-                            field: "allocation_percent".to_string(),
-                            contract_value: cb.allocation_percent.to_string(),
-                            document_value: db_entry.allocation_percent.to_string(),
-                        });
-                    }
-                }
+            let name_hash = hash_contract_string(&db_entry.full_name)?;
+            if !cb.hashed_full_name.eq_ignore_ascii_case(&name_hash) {
+                mismatches.push(MismatchDetail {
+                    wallet_address: db_entry.wallet_address.clone(),
+                    field: "full_name".to_string(),
+                    contract_value: cb.hashed_full_name.clone(),
+                    document_value: db_entry.full_name.clone(),
+                });
             }
-        }
 
-        // Check for extra document entries not in contract
-        for (addr, db_entry) in &document_map {
-            if !contract_map.contains_key(addr) {
+            let contract_percent = Decimal::from(cb.allocation_bp) / Decimal::from(100);
+            if (contract_percent - db_entry.allocation_percent).abs() > Decimal::new(1, 4) {
                 mismatches.push(MismatchDetail {
                     wallet_address: db_entry.wallet_address.clone(),
-                    field: "wallet_address".to_string(),
-                    contract_value: "MISSING".to_string(),
-                    document_value: db_entry.wallet_address.clone(),
+                    field: "allocation_percent".to_string(),
+                    contract_value: contract_percent.to_string(),
+                    document_value: db_entry.allocation_percent.to_string(),
+                });
+            }
+        }
+
+        // Contract entries with no counterpart in the document
+        for cb in contract {
+            if unmatched.contains_key(&cb.hashed_email.to_lowercase()) {
+                mismatches.push(MismatchDetail {
+                    wallet_address: "N/A".to_string(),
+                    field: "email".to_string(),
+                    contract_value: cb.hashed_email.clone(),
+                    document_value: "MISSING".to_string(),
                 });
             }
         }
@@ -155,31 +390,36 @@ impl BeneficiarySyncService {
             SyncStatus::Mismatched
         };
 
-        BeneficiarySyncResult {
+        Ok(BeneficiarySyncResult {
             plan_id,
             status,
             mismatches,
             contract_count: contract.len(),
             document_count: document.len(),
             checked_at,
-        }
+        })
     }
 
-    /// Full sync check: fetch from DB and validate against provided document list.
-    /// Returns `Err` if mismatched (blocks document generation).
+    /// Full sync check: read the plan from chain and validate against the
+    /// provided document list. Returns `Err` if mismatched (blocks document
+    /// generation).
     pub async fn sync_and_validate(
         db: &PgPool,
+        reader: &dyn ContractPlanReader,
         plan_id: Uuid,
         document_beneficiaries: &[DocumentBeneficiary],
     ) -> Result<BeneficiarySyncResult, ApiError> {
-        let contract_beneficiaries = Self::fetch_contract_beneficiaries(db, plan_id).await?;
+        let contract_beneficiaries =
+            Self::fetch_contract_beneficiaries(db, reader, plan_id).await?;
 
-        let result = Self::validate(plan_id, &contract_beneficiaries, document_beneficiaries);
+        let result = Self::validate(plan_id, &contract_beneficiaries, document_beneficiaries)?;
 
         if result.status == SyncStatus::Mismatched {
+            let fields: Vec<&str> = result.mismatches.iter().map(|m| m.field.as_str()).collect();
             return Err(ApiError::BadRequest(format!(
-                "Beneficiary mismatch detected for plan {plan_id}: {} issue(s) found",
-                result.mismatches.len()
+                "Beneficiary mismatch detected for plan {plan_id}: {} issue(s) found ({})",
+                result.mismatches.len(),
+                fields.join(", ")
             )));
         }
 
@@ -193,27 +433,44 @@ impl BeneficiarySyncService {
 mod tests {
     use super::*;
     use rust_decimal_macros::dec;
+    use soroban_sdk::xdr::{Hash, ScBytes, ScMap, ScMapEntry};
 
-    fn contract(addr: &str, alloc: Decimal) -> ContractBeneficiary {
+    fn contract(name: &str, email: &str, allocation_bp: u32) -> ContractBeneficiary {
         ContractBeneficiary {
-            wallet_address: addr.to_string(),
-            allocation_percent: alloc,
+            hashed_full_name: hash_contract_string(name).unwrap(),
+            hashed_email: hash_contract_string(email).unwrap(),
+            allocation_bp,
+            priority: 1,
+            is_claimed: false,
         }
     }
 
-    fn document(addr: &str, alloc: Decimal) -> DocumentBeneficiary {
+    fn document(addr: &str, name: &str, email: &str, alloc: Decimal) -> DocumentBeneficiary {
         DocumentBeneficiary {
             wallet_address: addr.to_string(),
+            full_name: name.to_string(),
+            email: email.to_string(),
             allocation_percent: alloc,
         }
     }
 
+    fn alice() -> ContractBeneficiary {
+        contract("Alice Doe", "alice@example.com", 6000)
+    }
+
+    fn bob() -> ContractBeneficiary {
+        contract("Bob Doe", "bob@example.com", 4000)
+    }
+
     #[test]
     fn test_matching_beneficiaries_passes() {
         let plan_id = Uuid::new_v4();
-        let c = vec![contract("GABC", dec!(60)), contract("GDEF", dec!(40))];
-        let d = vec![document("GABC", dec!(60)), document("GDEF", dec!(40))];
-        let result = BeneficiarySyncService::validate(plan_id, &c, &d);
+        let c = vec![alice(), bob()];
+        let d = vec![
+            document("GABC", "Alice Doe", "alice@example.com", dec!(60)),
+            document("GDEF", "Bob Doe", "bob@example.com", dec!(40)),
+        ];
+        let result = BeneficiarySyncService::validate(plan_id, &c, &d).unwrap();
         assert_eq!(result.status, SyncStatus::Matched);
         assert!(result.mismatches.is_empty());
     }
@@ -221,46 +478,163 @@ mod tests {
     #[test]
     fn test_count_mismatch_detected() {
         let plan_id = Uuid::new_v4();
-        let c = vec![contract("GABC", dec!(100))];
-        let d = vec![document("GABC", dec!(50)), document("GDEF", dec!(50))];
-        let result = BeneficiarySyncService::validate(plan_id, &c, &d);
+        let c = vec![contract("Alice Doe", "alice@example.com", 10000)];
+        let d = vec![
+            document("GABC", "Alice Doe", "alice@example.com", dec!(50)),
+            document("GDEF", "Bob Doe", "bob@example.com", dec!(50)),
+        ];
+        let result = BeneficiarySyncService::validate(plan_id, &c, &d).unwrap();
         assert_eq!(result.status, SyncStatus::Mismatched);
-        assert!(!result.mismatches.is_empty());
+        assert!(result
+            .mismatches
+            .iter()
+            .any(|m| m.field == "beneficiary_count"));
     }
 
     #[test]
     fn test_allocation_mismatch_detected() {
         let plan_id = Uuid::new_v4();
-        let c = vec![contract("GABC", dec!(60))];
-        let d = vec![document("GABC", dec!(40))];
-        let result = BeneficiarySyncService::validate(plan_id, &c, &d);
+        let c = vec![contract("Alice Doe", "alice@example.com", 6000)];
+        let d = vec![document("GABC", "Alice Doe", "alice@example.com", dec!(40))];
+        let result = BeneficiarySyncService::validate(plan_id, &c, &d).unwrap();
         assert_eq!(result.status, SyncStatus::Mismatched);
         let m = &result.mismatches[0];
         assert_eq!(m.field, "allocation_percent");
+        assert_eq!(m.contract_value, "60");
     }
 
     #[test]
-    fn test_missing_wallet_in_document_detected() {
+    fn test_name_mismatch_detected() {
         let plan_id = Uuid::new_v4();
-        let c = vec![contract("GABC", dec!(100))];
-        let d = vec![document("GXYZ", dec!(100))];
-        let result = BeneficiarySyncService::validate(plan_id, &c, &d);
+        let c = vec![alice()];
+        let d = vec![document(
+            "GABC",
+            "Alicia Doe",
+            "alice@example.com",
+            dec!(60),
+        )];
+        let result = BeneficiarySyncService::validate(plan_id, &c, &d).unwrap();
         assert_eq!(result.status, SyncStatus::Mismatched);
+        assert_eq!(result.mismatches.len(), 1);
+        assert_eq!(result.mismatches[0].field, "full_name");
     }
 
     #[test]
-    fn test_case_insensitive_address_matching() {
+    fn test_missing_beneficiary_reported_on_both_sides() {
         let plan_id = Uuid::new_v4();
-        let c = vec![contract("GABC", dec!(100))];
-        let d = vec![document("gabc", dec!(100))];
-        let result = BeneficiarySyncService::validate(plan_id, &c, &d);
-        assert_eq!(result.status, SyncStatus::Matched);
+        let c = vec![alice()];
+        let d = vec![document("GXYZ", "Carol Doe", "carol@example.com", dec!(60))];
+        let result = BeneficiarySyncService::validate(plan_id, &c, &d).unwrap();
+        assert_eq!(result.status, SyncStatus::Mismatched);
+        assert!(result
+            .mismatches
+            .iter()
+            .any(|m| m.field == "email" && m.contract_value == "MISSING"));
+        assert!(result
+            .mismatches
+            .iter()
+            .any(|m| m.field == "email" && m.document_value == "MISSING"));
     }
 
     #[test]
     fn test_empty_both_sides_matches() {
         let plan_id = Uuid::new_v4();
-        let result = BeneficiarySyncService::validate(plan_id, &[], &[]);
+        let result = BeneficiarySyncService::validate(plan_id, &[], &[]).unwrap();
         assert_eq!(result.status, SyncStatus::Matched);
     }
+
+    #[test]
+    fn test_hash_contract_string_is_content_sensitive() {
+        let a = hash_contract_string("alice").unwrap();
+        let b = hash_contract_string("bobby").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(a.len(), 64);
+    }
+
+    #[test]
+    fn test_parse_plan_details_from_scval() {
+        let sym = |s: &str| ScVal::Symbol(ScSymbol(s.try_into().unwrap()));
+        let bytes = |hex_str: &str| {
+            ScVal::Bytes(ScBytes(hex::decode(hex_str).unwrap().try_into().unwrap()))
+        };
+        let map = |fields: Vec<(&str, ScVal)>| {
+            let entries: Vec<ScMapEntry> = fields
+                .into_iter()
+                .map(|(k, v)| ScMapEntry {
+                    key: sym(k),
+                    val: v,
+                })
+                .collect();
+            ScVal::Map(Some(ScMap(entries.try_into().unwrap())))
+        };
+
+        let expected = alice();
+        let beneficiary = map(vec![
+            ("allocation_bp", ScVal::U32(expected.allocation_bp)),
+            ("bank_account", ScVal::Bytes(ScBytes(Default::default()))),
+            ("hashed_claim_code", bytes(&"00".repeat(32))),
+            ("hashed_email", bytes(&expected.hashed_email)),
+            ("hashed_full_name", bytes(&expected.hashed_full_name)),
+            ("is_claimed", ScVal::Bool(false)),
+            ("priority", ScVal::U32(1)),
+        ]);
+        let plan = map(vec![
+            (
+                "beneficiaries",
+                ScVal::Vec(Some(vec![beneficiary].try_into().unwrap())),
+            ),
+            ("is_active", ScVal::Bool(true)),
+            ("owner", ScVal::Address(ScAddress::Contract(Hash([7; 32])))),
+            ("total_allocation_bp", ScVal::U32(6000)),
+        ]);
+
+        let parsed = parse_plan_details(&plan).unwrap().unwrap();
+        assert_eq!(parsed.beneficiaries, vec![expected]);
+        assert_eq!(parsed.total_allocation_bp, 6000);
+        assert!(parse_plan_details(&ScVal::Void).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_reader_returns_inserted_plan() {
+        let reader = InMemoryContractPlanReader::new();
+        reader.insert_plan(
+            7,
+            ContractPlan {
+                owner: "GOWNER".to_string(),
+                is_active: true,
+                total_allocation_bp: 10000,
+                beneficiaries: vec![alice(), bob()],
+            },
+        );
+
+        let plan = reader.get_plan_details(7).await.unwrap().unwrap();
+        assert_eq!(plan.beneficiaries.len(), 2);
+        assert!(reader.get_plan_details(8).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_soroban_reader_builds_envelope() {
+        let reader = SorobanContractPlanReader::new(
+            "http://localhost:8000",
+            ScAddress::Contract(Hash([1; 32])).to_string(),
+            DEFAULT_SIMULATION_SOURCE,
+        );
+        let encoded = reader.build_envelope(42).unwrap();
+        let envelope =
+            TransactionEnvelope::from_xdr(BASE64.decode(encoded).unwrap(), Limits::none()).unwrap();
+        let TransactionEnvelope::Tx(v1) = envelope else {
+            panic!("expected a v1 envelope");
+        };
+        let OperationBody::InvokeHostFunction(op) = &v1.tx.operations[0].body else {
+            panic!("expected an invoke host function operation");
+        };
+        let HostFunction::InvokeContract(args) = &op.host_function else {
+            panic!("expected a contract invocation");
+        };
+        assert_eq!(
+            args.function_name.to_utf8_string_lossy(),
+            "get_plan_details"
+        );
+        assert_eq!(args.args[0], ScVal::U64(42));
+    }
 }
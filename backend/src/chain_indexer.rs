@@ -0,0 +1,1550 @@
+//! # Chain Indexer
+//!
+//! Polls a Soroban RPC `getEvents` endpoint for the configured contracts,
+//! decodes the XDR topics and values into typed records and ingests them into
+//! `lending_events` and `will_event_log`. Payout events update the installment
+//! progress columns of `plans`.
+//!
+//! The paging cursor is persisted in `chain_indexer_cursors` in the same
+//! database transaction as the rows it produced, so a restart resumes from the
+//! last committed page. Replayed events (a cursor rewind, an RPC node serving a
+//! ledger twice, or a row the API already wrote for the same transaction) are
+//! detected through [`EventService::get_by_transaction_hash`] and skipped.
+//!
+//! Events that cannot be attributed yet (a wallet no user has linked, a plan
+//! the API has not recorded) or cannot be decoded are parked in
+//! `chain_indexer_parked_events`, so the cursor can move past them without
+//! losing them. Parked events are retried at the start of a poll once their
+//! back-off has elapsed, and marked `dead` after `MAX_PARKED_ATTEMPTS`.
+
+use crate::api_error::ApiError;
+use crate::events::{EventService, EventType};
+use crate::webhook::backoff_delay;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use soroban_sdk::xdr::{Limits, ReadXdr, ScVal};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+const DEFAULT_INDEXER_NAME: &str = "soroban";
+const DEFAULT_PAGE_LIMIT: u32 = 100;
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 10;
+const UNKNOWN_ASSET_CODE: &str = "UNKNOWN";
+
+/// Attempts (including the one that parked it) before a parked event is
+/// marked `dead` and left for an operator.
+pub const MAX_PARKED_ATTEMPTS: i32 = 10;
+/// Parked reason prefix for events whose topics or value could not be decoded.
+pub const DECODE_ERROR_REASON: &str = "decode_error";
+
+// ─── Configuration ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct ChainIndexerConfig {
+    pub indexer_name: String,
+    pub rpc_url: String,
+    pub contract_ids: Vec<String>,
+    pub poll_interval_secs: u64,
+    pub page_limit: u32,
+    /// Ledger to start from when no cursor has been persisted yet. Defaults to
+    /// the RPC node's latest ledger.
+    pub start_ledger: Option<u32>,
+    /// Maps asset contract addresses to the asset codes stored in
+    /// `lending_events.asset_code`.
+    pub asset_codes: HashMap<String, String>,
+}
+
+impl ChainIndexerConfig {
+    /// Reads the indexer configuration from the environment. Returns `None`
+    /// when `SOROBAN_RPC_URL` or `CHAIN_INDEXER_CONTRACT_IDS` is unset, in which
+    /// case the indexer is disabled.
+    pub fn from_env() -> Option<Self> {
+        let rpc_url = std::env::var("SOROBAN_RPC_URL")
+            .ok()
+            .filter(|v| !v.is_empty())?;
+        let contract_ids = parse_list(&std::env::var("CHAIN_INDEXER_CONTRACT_IDS").ok()?);
+        if contract_ids.is_empty() {
+            return None;
+        }
+
+        let asset_codes = std::env::var("CHAIN_INDEXER_ASSET_CODES")
+            .map(|v| parse_asset_codes(&v))
+            .unwrap_or_default();
+
+        Some(Self {
+            indexer_name: std::env::var("CHAIN_INDEXER_NAME")
+                .unwrap_or_else(|_| DEFAULT_INDEXER_NAME.to_string()),
+            rpc_url,
+            contract_ids,
+            poll_interval_secs: std::env::var("CHAIN_INDEXER_POLL_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_POLL_INTERVAL_SECS),
+            page_limit: std::env::var("CHAIN_INDEXER_PAGE_LIMIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_PAGE_LIMIT),
+            start_ledger: std::env::var("CHAIN_INDEXER_START_LEDGER")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            asset_codes,
+        })
+    }
+}
+
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Parses `CONTRACT_ID=CODE,CONTRACT_ID=CODE` pairs.
+fn parse_asset_codes(value: &str) -> HashMap<String, String> {
+    parse_list(value)
+        .into_iter()
+        .filter_map(|pair| {
+            let (address, code) = pair.split_once('=')?;
+            Some((address.trim().to_string(), code.trim().to_uppercase()))
+        })
+        .collect()
+}
+
+// ─── Soroban RPC client ───────────────────────────────────────────────────────
+
+/// Where a `getEvents` request should start reading.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventsStart {
+    Ledger(u32),
+    Cursor(String),
+}
+
+/// A single event as returned by Soroban RPC `getEvents`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcEvent {
+    pub id: String,
+    pub ledger: u32,
+    pub ledger_closed_at: String,
+    pub contract_id: String,
+    pub tx_hash: String,
+    /// Base64-encoded `ScVal` topics.
+    pub topic: Vec<String>,
+    /// Base64-encoded `ScVal` body.
+    pub value: String,
+    #[serde(default)]
+    pub paging_token: Option<String>,
+    #[serde(default = "default_true")]
+    pub in_successful_contract_call: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEventsResult {
+    pub events: Vec<RpcEvent>,
+    pub latest_ledger: u32,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+impl GetEventsResult {
+    /// The cursor to resume from after this page. Older RPC versions do not
+    /// return a top-level cursor, in which case the last event's paging token
+    /// (or id) is used.
+    pub fn next_cursor(&self) -> Option<String> {
+        self.cursor.clone().filter(|c| !c.is_empty()).or_else(|| {
+            self.events
+                .last()
+                .map(|e| e.paging_token.clone().unwrap_or_else(|| e.id.clone()))
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LatestLedgerResult {
+    sequence: u32,
+}
+
+/// Result of `simulateTransaction` for a single host-function invocation.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateTransactionResult {
+    #[serde(default)]
+    pub results: Vec<SimulateHostFunctionResult>,
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Base64-encoded `SorobanTransactionData` (footprint and resources) to
+    /// attach before submitting.
+    #[serde(default)]
+    pub transaction_data: Option<String>,
+    /// Minimum resource fee in stroops, as a decimal string.
+    #[serde(default)]
+    pub min_resource_fee: Option<String>,
+    pub latest_ledger: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SimulateHostFunctionResult {
+    /// Base64-encoded `ScVal` return value.
+    pub xdr: String,
+    /// Base64-encoded `SorobanAuthorizationEntry` values the invocation needs.
+    #[serde(default)]
+    pub auth: Vec<String>,
+}
+
+/// Result of `getLedgerEntries`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLedgerEntriesResult {
+    #[serde(default)]
+    pub entries: Vec<LedgerEntryResult>,
+    pub latest_ledger: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerEntryResult {
+    /// Base64-encoded `LedgerKey`.
+    pub key: String,
+    /// Base64-encoded `LedgerEntryData`.
+    pub xdr: String,
+    /// Last ledger the entry is live for. Only set for Soroban entries.
+    #[serde(default)]
+    pub live_until_ledger_seq: Option<u32>,
+}
+
+/// Result of `sendTransaction`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendTransactionResult {
+    /// `PENDING`, `DUPLICATE`, `TRY_AGAIN_LATER` or `ERROR`.
+    pub status: String,
+    pub hash: String,
+    #[serde(default)]
+    pub error_result_xdr: Option<String>,
+}
+
+/// Result of `getTransaction`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionResult {
+    /// `SUCCESS`, `FAILED` or `NOT_FOUND`.
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcErrorBody>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcErrorBody {
+    code: i64,
+    message: String,
+}
+
+/// Minimal JSON-RPC client for the Soroban RPC methods the backend needs.
+pub struct SorobanRpcClient {
+    client: reqwest::Client,
+    rpc_url: String,
+    request_id: AtomicU64,
+}
+
+impl SorobanRpcClient {
+    pub fn new(rpc_url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+            rpc_url: rpc_url.into(),
+            request_id: AtomicU64::new(1),
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T, ApiError> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": self.request_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+
+        let response = self
+            .client
+            .post(&self.rpc_url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    ApiError::Timeout
+                } else {
+                    ApiError::ExternalService(format!("Soroban RPC request failed: {e}"))
+                }
+            })?;
+
+        if !response.status().is_success() {
+            return Err(ApiError::ExternalService(format!(
+                "Soroban RPC returned status {}",
+                response.status()
+            )));
+        }
+
+        let parsed: RpcResponse<T> = response.json().await.map_err(|e| {
+            ApiError::ExternalService(format!("Soroban RPC response parse failed: {e}"))
+        })?;
+
+        if let Some(err) = parsed.error {
+            return Err(ApiError::ExternalService(format!(
+                "Soroban RPC {method} error {}: {}",
+                err.code, err.message
+            )));
+        }
+
+        parsed.result.ok_or_else(|| {
+            ApiError::ExternalService(format!("Soroban RPC {method} returned no result"))
+        })
+    }
+
+    pub async fn get_latest_ledger(&self) -> Result<u32, ApiError> {
+        let result: LatestLedgerResult = self.call("getLatestLedger", json!({})).await?;
+        Ok(result.sequence)
+    }
+
+    /// Simulates a base64-encoded `TransactionEnvelope`. Read-only contract
+    /// calls stop here; state-changing ones use the result to prepare the
+    /// transaction for submission.
+    pub async fn simulate_transaction(
+        &self,
+        transaction_xdr: &str,
+    ) -> Result<SimulateTransactionResult, ApiError> {
+        self.call(
+            "simulateTransaction",
+            json!({ "transaction": transaction_xdr }),
+        )
+        .await
+    }
+
+    /// Reads ledger entries by base64-encoded `LedgerKey`.
+    pub async fn get_ledger_entries(
+        &self,
+        keys: &[String],
+    ) -> Result<GetLedgerEntriesResult, ApiError> {
+        self.call("getLedgerEntries", json!({ "keys": keys })).await
+    }
+
+    /// Submits a signed, base64-encoded `TransactionEnvelope`.
+    pub async fn send_transaction(
+        &self,
+        transaction_xdr: &str,
+    ) -> Result<SendTransactionResult, ApiError> {
+        self.call("sendTransaction", json!({ "transaction": transaction_xdr }))
+            .await
+    }
+
+    pub async fn get_transaction(&self, hash: &str) -> Result<GetTransactionResult, ApiError> {
+        self.call("getTransaction", json!({ "hash": hash })).await
+    }
+
+    pub async fn get_events(
+        &self,
+        start: &EventsStart,
+        contract_ids: &[String],
+        limit: u32,
+    ) -> Result<GetEventsResult, ApiError> {
+        let mut params = json!({
+            "filters": [{ "type": "contract", "contractIds": contract_ids }],
+        });
+        match start {
+            EventsStart::Ledger(ledger) => {
+                params["startLedger"] = json!(ledger);
+                params["pagination"] = json!({ "limit": limit });
+            }
+            EventsStart::Cursor(cursor) => {
+                params["pagination"] = json!({ "cursor": cursor, "limit": limit });
+            }
+        }
+
+        self.call("getEvents", params).await
+    }
+}
+
+// ─── XDR decoding ─────────────────────────────────────────────────────────────
+
+pub fn decode_scval(encoded: &str) -> Result<ScVal, ApiError> {
+    let bytes = BASE64
+        .decode(encoded)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid base64 event XDR: {e}")))?;
+    ScVal::from_xdr(bytes, Limits::none())
+        .map_err(|e| ApiError::BadRequest(format!("Invalid event XDR: {e}")))
+}
+
+/// Converts an `ScVal` into JSON. `#[contracttype]` structs are encoded as
+/// symbol-keyed maps and become JSON objects; addresses are rendered as
+/// strkeys and 128-bit integers as decimal strings.
+pub fn scval_to_json(val: &ScVal) -> Value {
+    match val {
+        ScVal::Void => Value::Null,
+        ScVal::Bool(b) => json!(b),
+        ScVal::U32(n) => json!(n),
+        ScVal::I32(n) => json!(n),
+        ScVal::U64(n) => json!(n),
+        ScVal::I64(n) => json!(n),
+        ScVal::Timepoint(t) => json!(t.0),
+        ScVal::Duration(d) => json!(d.0),
+        ScVal::U128(parts) => {
+            json!((((parts.hi as u128) << 64) | parts.lo as u128).to_string())
+        }
+        ScVal::I128(parts) => {
+            json!((((parts.hi as i128) << 64) | parts.lo as i128).to_string())
+        }
+        ScVal::Bytes(bytes) => json!(hex::encode(bytes.as_slice())),
+        ScVal::String(s) => json!(s.to_utf8_string_lossy()),
+        ScVal::Symbol(s) => json!(s.to_utf8_string_lossy()),
+        ScVal::Address(address) => json!(address.to_string()),
+        ScVal::Vec(Some(items)) => Value::Array(items.iter().map(scval_to_json).collect()),
+        ScVal::Vec(None) => Value::Array(Vec::new()),
+        ScVal::Map(Some(entries)) => {
+            let mut object = Map::new();
+            for entry in entries.iter() {
+                let key = match scval_to_json(&entry.key) {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                object.insert(key, scval_to_json(&entry.val));
+            }
+            Value::Object(object)
+        }
+        ScVal::Map(None) => Value::Object(Map::new()),
+        other => json!(format!("{other:?}")),
+    }
+}
+
+// ─── Typed contract events ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PoolDepositRecord {
+    pub depositor: String,
+    pub asset: String,
+    pub amount: u64,
+    pub shares_minted: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct BorrowRecord {
+    pub loan_id: u64,
+    pub borrower: String,
+    pub asset: String,
+    pub amount: u64,
+    pub collateral_amount: u64,
+    pub due_date: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RepayRecord {
+    pub loan_id: u64,
+    pub borrower: String,
+    pub asset: String,
+    pub principal: u64,
+    pub interest: u64,
+    pub total_amount: u64,
+    pub collateral_returned: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LiquidationRecord {
+    pub loan_id: u64,
+    pub borrower: String,
+    pub liquidator: String,
+    pub asset: String,
+    pub amount_repaid: u64,
+    pub collateral_seized: u64,
+    pub health_factor: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct InterestAccrualRecord {
+    pub loan_id: u64,
+    pub borrower: String,
+    pub asset: String,
+    pub principal: u64,
+    pub interest_accrued: u64,
+    pub interest_rate_bps: u32,
+    pub elapsed_seconds: u64,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LoanRefinancedRecord {
+    pub old_loan_id: u64,
+    pub new_loan_id: u64,
+    pub borrower: String,
+    pub asset: String,
+    pub old_principal: u64,
+    pub new_principal: u64,
+    pub refinancing_fee: u64,
+    pub old_interest_rate_bps: u32,
+    pub new_interest_rate_bps: u32,
+    pub old_due_date: u64,
+    pub new_due_date: u64,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct VaultDepositRecord {
+    pub plan_id: u64,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PayoutScheduleSetRecord {
+    pub plan_id: u64,
+    pub installment_count: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct InstallmentClaimedRecord {
+    pub plan_id: u64,
+    pub beneficiary_index: u32,
+    pub installment: u32,
+    pub amount: u64,
+    pub remaining_installments: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct InheritanceTriggeredRecord {
+    pub plan_id: u64,
+    pub triggered_at: u64,
+    pub outstanding_loans: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct WillSignedRecord {
+    pub vault_id: u64,
+    pub signer: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct WillFinalizedRecord {
+    pub vault_id: u64,
+    pub version: u32,
+    pub finalized_at: u64,
+}
+
+/// Contract events the indexer understands, keyed by their two-symbol topic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainEvent {
+    /// `("POOL", "DEPOSIT")` — `DepositEvent`
+    PoolDeposit(PoolDepositRecord),
+    /// `("POOL", "BORROW")` — `BorrowEvent`
+    Borrow(BorrowRecord),
+    /// `("POOL", "REPAY")` — `RepayEvent`
+    Repay(RepayRecord),
+    /// `("POOL", "LIQUIDATE")` — `LiquidationEvent`
+    Liquidation(LiquidationRecord),
+    /// `("POOL", "INTEREST")` — `InterestAccrualEvent`
+    InterestAccrual(InterestAccrualRecord),
+    /// `("POOL", "REFINANCE")` — `LoanRefinancedEvent`
+    LoanRefinanced(LoanRefinancedRecord),
+    /// `("VAULT", "DEPOSIT")` — `VaultDepositEvent`
+    VaultDeposit(VaultDepositRecord),
+    /// `("PAYOUT", "SCHEDULE")` — `PayoutScheduleSetEvent`
+    PayoutScheduleSet(PayoutScheduleSetRecord),
+    /// `("PAYOUT", "INSTALL")` — `InstallmentClaimedEvent`
+    InstallmentClaimed(InstallmentClaimedRecord),
+    /// `("INHERIT", "TRIGGER")` — `InheritanceTriggeredEvent`
+    InheritanceTriggered(InheritanceTriggeredRecord),
+    /// `("WILL", "SIGNED")` — `WillSignedEvent`
+    WillSigned(WillSignedRecord),
+    /// `("WILL", "FINAL")` — `WillFinalizedEvent`
+    WillFinalized(WillFinalizedRecord),
+}
+
+impl ChainEvent {
+    /// Decodes an event from its topic symbols and JSON-converted body.
+    /// Returns `Ok(None)` for topics the indexer does not ingest.
+    pub fn decode(topics: &[String], body: &Value) -> Result<Option<Self>, ApiError> {
+        let (Some(domain), Some(action)) = (topics.first(), topics.get(1)) else {
+            return Ok(None);
+        };
+
+        let event = match (domain.as_str(), action.as_str()) {
+            ("POOL", "DEPOSIT") => ChainEvent::PoolDeposit(parse_body(body)?),
+            ("POOL", "BORROW") => ChainEvent::Borrow(parse_body(body)?),
+            ("POOL", "REPAY") => ChainEvent::Repay(parse_body(body)?),
+            ("POOL", "LIQUIDATE") => ChainEvent::Liquidation(parse_body(body)?),
+            ("POOL", "INTEREST") => ChainEvent::InterestAccrual(parse_body(body)?),
+            ("POOL", "REFINANCE") => ChainEvent::LoanRefinanced(parse_body(body)?),
+            ("VAULT", "DEPOSIT") => ChainEvent::VaultDeposit(parse_body(body)?),
+            ("PAYOUT", "SCHEDULE") => ChainEvent::PayoutScheduleSet(parse_body(body)?),
+            ("PAYOUT", "INSTALL") => ChainEvent::InstallmentClaimed(parse_body(body)?),
+            ("INHERIT", "TRIGGER") => ChainEvent::InheritanceTriggered(parse_body(body)?),
+            ("WILL", "SIGNED") => ChainEvent::WillSigned(parse_body(body)?),
+            ("WILL", "FINAL") => ChainEvent::WillFinalized(parse_body(body)?),
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+}
+
+fn parse_body<T: DeserializeOwned>(body: &Value) -> Result<T, ApiError> {
+    serde_json::from_value(body.clone())
+        .map_err(|e| ApiError::BadRequest(format!("Unexpected contract event body: {e}")))
+}
+
+/// A decoded event together with its on-chain provenance.
+#[derive(Debug, Clone)]
+pub struct IndexedEvent {
+    pub event_id: String,
+    pub ledger: u32,
+    pub ledger_closed_at: DateTime<Utc>,
+    pub contract_id: String,
+    pub tx_hash: String,
+    pub event: ChainEvent,
+    pub body: Value,
+}
+
+impl IndexedEvent {
+    /// Decodes a raw RPC event. Returns `Ok(None)` for events from failed
+    /// invocations and for topics the indexer does not ingest.
+    pub fn from_rpc(raw: &RpcEvent) -> Result<Option<Self>, ApiError> {
+        if !raw.in_successful_contract_call {
+            return Ok(None);
+        }
+
+        let topics = raw
+            .topic
+            .iter()
+            .map(|t| {
+                decode_scval(t).map(|v| match scval_to_json(&v) {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let body = scval_to_json(&decode_scval(&raw.value)?);
+
+        let Some(event) = ChainEvent::decode(&topics, &body)? else {
+            return Ok(None);
+        };
+
+        let ledger_closed_at = DateTime::parse_from_rfc3339(&raw.ledger_closed_at)
+            .map(|t| t.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now());
+
+        Ok(Some(Self {
+            event_id: raw.id.clone(),
+            ledger: raw.ledger,
+            ledger_closed_at,
+            contract_id: raw.contract_id.clone(),
+            tx_hash: raw.tx_hash.clone(),
+            event,
+            body,
+        }))
+    }
+
+    fn provenance(&self) -> Value {
+        json!({
+            "chain_event_id": self.event_id,
+            "contract_id": self.contract_id,
+            "ledger": self.ledger,
+            "ledger_closed_at": self.ledger_closed_at,
+            "transaction_hash": self.tx_hash,
+            "source": "chain_indexer",
+        })
+    }
+}
+
+// ─── Indexer service ──────────────────────────────────────────────────────────
+
+/// What ingesting a decoded event did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum IngestOutcome {
+    Inserted,
+    /// Already ingested by an earlier page, or written by the API.
+    Duplicate,
+    /// The user or plan the event belongs to is not known yet.
+    Unattributed(String),
+}
+
+/// Lending row derived from a chain event, before user/plan resolution.
+struct LendingRow<'a> {
+    event_type: EventType,
+    /// Wallet address of the user the row belongs to.
+    wallet: Option<&'a str>,
+    /// On-chain plan id, used when the event is plan-scoped.
+    contract_plan_id: Option<u64>,
+    asset: Option<&'a str>,
+    amount: u64,
+}
+
+/// When a parked event that has had `attempt` attempts is next retried.
+fn next_parked_attempt_at(attempt: i32) -> DateTime<Utc> {
+    let delay = backoff_delay(attempt as u32, rand::thread_rng().gen::<f64>());
+    Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default()
+}
+
+pub struct ChainIndexerService {
+    db: PgPool,
+    rpc: SorobanRpcClient,
+    config: ChainIndexerConfig,
+}
+
+impl ChainIndexerService {
+    pub fn new(db: PgPool, config: ChainIndexerConfig) -> Self {
+        Self {
+            rpc: SorobanRpcClient::new(config.rpc_url.clone()),
+            db,
+            config,
+        }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        let interval_secs = self.config.poll_interval_secs;
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            loop {
+                interval.tick().await;
+                match self.poll_once().await {
+                    Ok(0) => {}
+                    Ok(n) => info!("Chain indexer ingested {} contract events", n),
+                    Err(e) => error!("Chain indexer poll failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Retries parked events, then fetches and ingests every page available
+    /// since the persisted cursor. Returns the number of newly inserted rows.
+    pub async fn poll_once(&self) -> Result<usize, ApiError> {
+        let mut ingested = self.retry_parked().await?;
+
+        let mut start = match self.load_cursor().await? {
+            Some(cursor) => EventsStart::Cursor(cursor),
+            None => EventsStart::Ledger(match self.config.start_ledger {
+                Some(ledger) => ledger,
+                None => self.rpc.get_latest_ledger().await?,
+            }),
+        };
+
+        loop {
+            let page = self
+                .rpc
+                .get_events(&start, &self.config.contract_ids, self.config.page_limit)
+                .await?;
+
+            let mut tx = self.db.begin().await?;
+            for raw in &page.events {
+                let indexed = match IndexedEvent::from_rpc(raw) {
+                    Ok(Some(indexed)) => indexed,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        warn!("Parking undecodable contract event {}: {}", raw.id, e);
+                        self.park(&mut tx, raw, &format!("{DECODE_ERROR_REASON}: {e}"))
+                            .await?;
+                        continue;
+                    }
+                };
+                match self.ingest(&mut tx, &indexed).await? {
+                    IngestOutcome::Inserted => ingested += 1,
+                    IngestOutcome::Duplicate => {
+                        debug!("Chain event {} already ingested", indexed.event_id);
+                    }
+                    IngestOutcome::Unattributed(reason) => {
+                        self.park(&mut tx, raw, &reason).await?;
+                    }
+                }
+            }
+
+            let next = page.next_cursor();
+            if let Some(cursor) = &next {
+                let last_ledger = page
+                    .events
+                    .last()
+                    .map(|e| e.ledger)
+                    .unwrap_or(page.latest_ledger);
+                self.save_cursor(&mut tx, cursor, last_ledger).await?;
+            }
+            tx.commit().await?;
+
+            match next {
+                Some(cursor) if page.events.len() as u32 >= self.config.page_limit => {
+                    start = EventsStart::Cursor(cursor);
+                }
+                _ => break,
+            }
+        }
+
+        Ok(ingested)
+    }
+
+    /// Retries up to one page of parked events whose back-off has elapsed,
+    /// oldest first. Events that can now be attributed are ingested and
+    /// unparked; the rest are backed off again, or marked `dead` once they
+    /// reach `MAX_PARKED_ATTEMPTS`. Returns the number of newly inserted rows.
+    async fn retry_parked(&self) -> Result<usize, ApiError> {
+        let mut tx = self.db.begin().await?;
+        let parked: Vec<(String, Value, i32)> = sqlx::query_as(
+            r#"
+            SELECT event_id, raw_event, attempts FROM chain_indexer_parked_events
+            WHERE indexer_name = $1 AND status = 'parked' AND next_attempt_at <= NOW()
+            ORDER BY next_attempt_at, event_id
+            LIMIT $2
+            "#,
+        )
+        .bind(&self.config.indexer_name)
+        .bind(self.config.page_limit as i64)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut ingested = 0;
+        for (event_id, raw_event, attempts) in parked {
+            let indexed = serde_json::from_value::<RpcEvent>(raw_event)
+                .map_err(|e| ApiError::BadRequest(e.to_string()))
+                .and_then(|raw| IndexedEvent::from_rpc(&raw));
+            let outcome = match indexed {
+                Ok(Some(indexed)) => self.ingest(&mut tx, &indexed).await?,
+                Ok(None) => IngestOutcome::Duplicate,
+                Err(e) => IngestOutcome::Unattributed(format!("{DECODE_ERROR_REASON}: {e}")),
+            };
+            match outcome {
+                IngestOutcome::Unattributed(reason) => {
+                    let attempt = attempts + 1;
+                    let status = if attempt >= MAX_PARKED_ATTEMPTS {
+                        warn!(
+                            "Giving up on parked event {} after {} attempts: {}",
+                            event_id, attempt, reason
+                        );
+                        "dead"
+                    } else {
+                        "parked"
+                    };
+                    sqlx::query(
+                        r#"
+                        UPDATE chain_indexer_parked_events
+                        SET attempts = $3, reason = $4, status = $5,
+                            last_attempt_at = NOW(), next_attempt_at = $6
+                        WHERE indexer_name = $1 AND event_id = $2
+                        "#,
+                    )
+                    .bind(&self.config.indexer_name)
+                    .bind(&event_id)
+                    .bind(attempt)
+                    .bind(reason)
+                    .bind(status)
+                    .bind(next_parked_attempt_at(attempt))
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                outcome => {
+                    if outcome == IngestOutcome::Inserted {
+                        ingested += 1;
+                    }
+                    sqlx::query(
+                        "DELETE FROM chain_indexer_parked_events WHERE indexer_name = $1 AND event_id = $2",
+                    )
+                    .bind(&self.config.indexer_name)
+                    .bind(&event_id)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+        }
+        tx.commit().await?;
+        Ok(ingested)
+    }
+
+    /// Keeps an event that cannot be attributed or decoded yet for a later
+    /// retry.
+    async fn park(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        raw: &RpcEvent,
+        reason: &str,
+    ) -> Result<(), ApiError> {
+        debug!("Parking chain event {}: {}", raw.id, reason);
+        sqlx::query(
+            r#"
+            INSERT INTO chain_indexer_parked_events
+                (indexer_name, event_id, raw_event, reason, next_attempt_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (indexer_name, event_id) DO NOTHING
+            "#,
+        )
+        .bind(&self.config.indexer_name)
+        .bind(&raw.id)
+        .bind(json!(raw))
+        .bind(reason)
+        .bind(next_parked_attempt_at(1))
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Writes a single decoded event.
+    async fn ingest(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        indexed: &IndexedEvent,
+    ) -> Result<IngestOutcome, ApiError> {
+        match &indexed.event {
+            ChainEvent::WillSigned(record) => {
+                self.ingest_will_event(tx, indexed, "will_signed", record.vault_id)
+                    .await
+            }
+            ChainEvent::WillFinalized(record) => {
+                self.ingest_will_event(tx, indexed, "will_finalized", record.vault_id)
+                    .await
+            }
+            ChainEvent::PayoutScheduleSet(record) => {
+                self.update_plan_payout(
+                    tx,
+                    record.plan_id,
+                    "payout_installment_count = $2",
+                    record.installment_count as i64,
+                )
+                .await
+            }
+            // A plan is triggered once; keeping the earliest time seen makes
+            // replays harmless.
+            ChainEvent::InheritanceTriggered(record) => {
+                self.update_plan_payout(
+                    tx,
+                    record.plan_id,
+                    "inheritance_triggered_at = LEAST(COALESCE(inheritance_triggered_at, $2), $2)",
+                    record.triggered_at as i64,
+                )
+                .await
+            }
+            ChainEvent::InstallmentClaimed(record) => {
+                self.record_installment_paid(tx, record).await
+            }
+            event => match Self::lending_row(event) {
+                Some(row) => self.ingest_lending_event(tx, indexed, row).await,
+                None => Ok(IngestOutcome::Duplicate),
+            },
+        }
+    }
+
+    fn lending_row(event: &ChainEvent) -> Option<LendingRow<'_>> {
+        let row = match event {
+            ChainEvent::PoolDeposit(r) => LendingRow {
+                event_type: EventType::Deposit,
+                wallet: Some(&r.depositor),
+                contract_plan_id: None,
+                asset: Some(&r.asset),
+                amount: r.amount,
+            },
+            ChainEvent::Borrow(r) => LendingRow {
+                event_type: EventType::Borrow,
+                wallet: Some(&r.borrower),
+                contract_plan_id: None,
+                asset: Some(&r.asset),
+                amount: r.amount,
+            },
+            ChainEvent::Repay(r) => LendingRow {
+                event_type: EventType::Repay,
+                wallet: Some(&r.borrower),
+                contract_plan_id: None,
+                asset: Some(&r.asset),
+                amount: r.total_amount,
+            },
+            ChainEvent::Liquidation(r) => LendingRow {
+                event_type: EventType::Liquidation,
+                wallet: Some(&r.borrower),
+                contract_plan_id: None,
+                asset: Some(&r.asset),
+                amount: r.amount_repaid,
+            },
+            ChainEvent::InterestAccrual(r) => LendingRow {
+                event_type: EventType::InterestAccrual,
+                wallet: Some(&r.borrower),
+                contract_plan_id: None,
+                asset: Some(&r.asset),
+                amount: r.interest_accrued,
+            },
+            // A refinance opens a new loan for the new principal.
+            ChainEvent::LoanRefinanced(r) => LendingRow {
+                event_type: EventType::Borrow,
+                wallet: Some(&r.borrower),
+                contract_plan_id: None,
+                asset: Some(&r.asset),
+                amount: r.new_principal,
+            },
+            ChainEvent::VaultDeposit(r) => LendingRow {
+                event_type: EventType::Deposit,
+                wallet: None,
+                contract_plan_id: Some(r.plan_id),
+                asset: None,
+                amount: r.amount,
+            },
+            ChainEvent::WillSigned(_)
+            | ChainEvent::WillFinalized(_)
+            | ChainEvent::PayoutScheduleSet(_)
+            | ChainEvent::InstallmentClaimed(_)
+            | ChainEvent::InheritanceTriggered(_) => return None,
+        };
+        Some(row)
+    }
+
+    async fn ingest_lending_event(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        indexed: &IndexedEvent,
+        row: LendingRow<'_>,
+    ) -> Result<IngestOutcome, ApiError> {
+        if self
+            .lending_event_exists(tx, &indexed.tx_hash, &indexed.event_id, row.event_type)
+            .await?
+        {
+            return Ok(IngestOutcome::Duplicate);
+        }
+
+        let (user_id, plan_id, plan_asset) = match (row.wallet, row.contract_plan_id) {
+            (Some(wallet), _) => match self.user_by_wallet(tx, wallet).await? {
+                Some(user_id) => (user_id, None, None),
+                None => {
+                    return Ok(IngestOutcome::Unattributed(format!(
+                        "no user for wallet {wallet}"
+                    )));
+                }
+            },
+            (None, Some(contract_plan_id)) => {
+                match self.plan_by_contract_id(tx, contract_plan_id).await? {
+                    Some((plan_id, user_id, asset_code)) => (user_id, Some(plan_id), asset_code),
+                    None => {
+                        return Ok(IngestOutcome::Unattributed(format!(
+                            "no plan for contract plan id {contract_plan_id}"
+                        )));
+                    }
+                }
+            }
+            (None, None) => {
+                return Ok(IngestOutcome::Unattributed(
+                    "event names neither a wallet nor a plan".to_string(),
+                ));
+            }
+        };
+
+        let asset_code = match (row.asset, plan_asset) {
+            (Some(asset), _) => self.asset_code(asset),
+            (None, Some(code)) => code,
+            (None, None) => UNKNOWN_ASSET_CODE.to_string(),
+        };
+
+        let mut metadata = indexed.provenance();
+        metadata["event"] = indexed.body.clone();
+
+        EventService::emit_indexed(
+            tx,
+            row.event_type,
+            user_id,
+            plan_id,
+            &asset_code,
+            Decimal::from(row.amount),
+            metadata,
+            Some(indexed.tx_hash.clone()),
+            Some(indexed.ledger as i64),
+        )
+        .await?;
+
+        Ok(IngestOutcome::Inserted)
+    }
+
+    async fn lending_event_exists(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        tx_hash: &str,
+        event_id: &str,
+        event_type: EventType,
+    ) -> Result<bool, ApiError> {
+        let existing = EventService::get_by_transaction_hash(&mut **tx, tx_hash).await?;
+        Ok(existing.iter().any(|e| {
+            match e.metadata.get("chain_event_id").and_then(Value::as_str) {
+                Some(id) => id == event_id,
+                // Row written by the API for the same transaction.
+                None => e.event_type == event_type,
+            }
+        }))
+    }
+
+    async fn ingest_will_event(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        indexed: &IndexedEvent,
+        event_type: &str,
+        vault_id: u64,
+    ) -> Result<IngestOutcome, ApiError> {
+        let already_logged: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM will_event_log
+                WHERE event_data->>'chain_event_id' = $1
+            )
+            "#,
+        )
+        .bind(&indexed.event_id)
+        .fetch_one(&mut **tx)
+        .await?;
+        if already_logged {
+            return Ok(IngestOutcome::Duplicate);
+        }
+
+        let Some((plan_id, user_id, _)) = self.plan_by_contract_id(tx, vault_id).await? else {
+            return Ok(IngestOutcome::Unattributed(format!(
+                "no plan for vault {vault_id}"
+            )));
+        };
+
+        let document_id: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM will_documents
+            WHERE plan_id = $1
+            ORDER BY version DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(plan_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+        let Some(document_id) = document_id else {
+            return Ok(IngestOutcome::Unattributed(format!(
+                "plan {plan_id} has no will document"
+            )));
+        };
+
+        let mut event_data = indexed.provenance();
+        event_data["event"] = indexed.body.clone();
+
+        sqlx::query(
+            r#"
+            INSERT INTO will_event_log
+                (event_type, document_id, plan_id, vault_id, event_data, user_id, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(event_type)
+        .bind(document_id)
+        .bind(plan_id)
+        .bind(vault_id.to_string())
+        .bind(event_data)
+        .bind(user_id)
+        .bind(indexed.ledger_closed_at)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(IngestOutcome::Inserted)
+    }
+
+    /// Applies `assignment` (with the event's value bound to `$2`) to the plan
+    /// mirroring `contract_plan_id`.
+    async fn update_plan_payout(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        contract_plan_id: u64,
+        assignment: &str,
+        value: i64,
+    ) -> Result<IngestOutcome, ApiError> {
+        let updated = sqlx::query(&format!(
+            "UPDATE plans SET {assignment}, updated_at = NOW() WHERE contract_plan_id = $1"
+        ))
+        .bind(contract_plan_id as i64)
+        .bind(value)
+        .execute(&mut **tx)
+        .await?;
+        if updated.rows_affected() == 0 {
+            return Ok(IngestOutcome::Unattributed(format!(
+                "no plan for contract plan id {contract_plan_id}"
+            )));
+        }
+        Ok(IngestOutcome::Inserted)
+    }
+
+    /// Advances one beneficiary's progress, as the contract's `Progress` is
+    /// kept per (plan, beneficiary index). Installments are paid in order,
+    /// so keeping the highest one seen makes replays harmless.
+    async fn record_installment_paid(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        record: &InstallmentClaimedRecord,
+    ) -> Result<IngestOutcome, ApiError> {
+        let updated = sqlx::query(
+            r#"
+            INSERT INTO plan_payout_progress (plan_id, beneficiary_index, paid_installments)
+            SELECT id, $2, $3 FROM plans WHERE contract_plan_id = $1
+            ON CONFLICT (plan_id, beneficiary_index) DO UPDATE
+            SET paid_installments = GREATEST(
+                    plan_payout_progress.paid_installments,
+                    EXCLUDED.paid_installments
+                ),
+                updated_at = NOW()
+            "#,
+        )
+        .bind(record.plan_id as i64)
+        .bind(record.beneficiary_index as i32)
+        .bind(record.installment as i32)
+        .execute(&mut **tx)
+        .await?;
+        if updated.rows_affected() == 0 {
+            return Ok(IngestOutcome::Unattributed(format!(
+                "no plan for contract plan id {}",
+                record.plan_id
+            )));
+        }
+        Ok(IngestOutcome::Inserted)
+    }
+
+    async fn user_by_wallet(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        wallet: &str,
+    ) -> Result<Option<Uuid>, ApiError> {
+        Ok(
+            sqlx::query_scalar("SELECT id FROM users WHERE wallet_address = $1 LIMIT 1")
+                .bind(wallet)
+                .fetch_optional(&mut **tx)
+                .await?,
+        )
+    }
+
+    async fn plan_by_contract_id(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        contract_plan_id: u64,
+    ) -> Result<Option<(Uuid, Uuid, Option<String>)>, ApiError> {
+        Ok(sqlx::query_as(
+            "SELECT id, user_id, asset_code FROM plans WHERE contract_plan_id = $1 LIMIT 1",
+        )
+        .bind(contract_plan_id as i64)
+        .fetch_optional(&mut **tx)
+        .await?)
+    }
+
+    fn asset_code(&self, asset: &str) -> String {
+        self.config
+            .asset_codes
+            .get(asset)
+            .cloned()
+            .unwrap_or_else(|| UNKNOWN_ASSET_CODE.to_string())
+    }
+
+    async fn load_cursor(&self) -> Result<Option<String>, ApiError> {
+        Ok(
+            sqlx::query_scalar("SELECT cursor FROM chain_indexer_cursors WHERE indexer_name = $1")
+                .bind(&self.config.indexer_name)
+                .fetch_optional(&self.db)
+                .await?,
+        )
+    }
+
+    async fn save_cursor(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        cursor: &str,
+        last_ledger: u32,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            INSERT INTO chain_indexer_cursors (indexer_name, cursor, last_ledger, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (indexer_name)
+            DO UPDATE SET cursor = EXCLUDED.cursor,
+                          last_ledger = EXCLUDED.last_ledger,
+                          updated_at = NOW()
+            "#,
+        )
+        .bind(&self.config.indexer_name)
+        .bind(cursor)
+        .bind(last_ledger as i64)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+    use soroban_sdk::xdr::{Hash, ScAddress, ScMap, ScMapEntry, ScSymbol, UInt128Parts, WriteXdr};
+
+    fn sym(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+    }
+
+    fn encode(val: &ScVal) -> String {
+        BASE64.encode(val.to_xdr(Limits::none()).unwrap())
+    }
+
+    fn contract_address(byte: u8) -> ScVal {
+        ScVal::Address(ScAddress::Contract(Hash([byte; 32])))
+    }
+
+    fn struct_val(fields: Vec<(&str, ScVal)>) -> ScVal {
+        let entries: Vec<ScMapEntry> = fields
+            .into_iter()
+            .map(|(k, v)| ScMapEntry {
+                key: sym(k),
+                val: v,
+            })
+            .collect();
+        ScVal::Map(Some(ScMap(entries.try_into().unwrap())))
+    }
+
+    fn borrow_event_body() -> ScVal {
+        struct_val(vec![
+            ("amount", ScVal::U64(5_000)),
+            ("asset", contract_address(2)),
+            ("borrower", contract_address(1)),
+            ("collateral_amount", ScVal::U64(7_500)),
+            ("due_date", ScVal::U64(1_700_000_000)),
+            ("loan_id", ScVal::U64(1)),
+        ])
+    }
+
+    fn rpc_event(id: &str, topics: [&str; 2], body: &ScVal) -> Value {
+        json!({
+            "type": "contract",
+            "ledger": 120,
+            "ledgerClosedAt": "2026-04-29T10:00:00Z",
+            "contractId": "CCONTRACT",
+            "id": id,
+            "pagingToken": id,
+            "topic": [encode(&sym(topics[0])), encode(&sym(topics[1]))],
+            "value": encode(body),
+            "inSuccessfulContractCall": true,
+            "txHash": "abc123",
+        })
+    }
+
+    #[test]
+    fn test_scval_to_json_converts_struct_maps() {
+        let json = scval_to_json(&borrow_event_body());
+        assert_eq!(json["amount"], 5_000);
+        assert_eq!(json["loan_id"], 1);
+        assert!(json["borrower"].as_str().unwrap().starts_with('C'));
+    }
+
+    #[test]
+    fn test_scval_to_json_renders_u128_as_string() {
+        let val = ScVal::U128(UInt128Parts { hi: 1, lo: 0 });
+        assert_eq!(scval_to_json(&val), json!("18446744073709551616"));
+    }
+
+    #[test]
+    fn test_decode_borrow_event() {
+        let raw: RpcEvent = serde_json::from_value(rpc_event(
+            "0001-1",
+            ["POOL", "BORROW"],
+            &borrow_event_body(),
+        ))
+        .unwrap();
+
+        let indexed = IndexedEvent::from_rpc(&raw).unwrap().unwrap();
+        match indexed.event {
+            ChainEvent::Borrow(record) => {
+                assert_eq!(record.loan_id, 1);
+                assert_eq!(record.amount, 5_000);
+                assert_eq!(record.collateral_amount, 7_500);
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+        assert_eq!(indexed.tx_hash, "abc123");
+        assert_eq!(indexed.ledger, 120);
+    }
+
+    #[test]
+    fn test_decode_will_signed_event() {
+        let body = struct_val(vec![
+            ("signer", contract_address(9)),
+            ("vault_id", ScVal::U64(42)),
+        ]);
+        let raw: RpcEvent =
+            serde_json::from_value(rpc_event("0001-2", ["WILL", "SIGNED"], &body)).unwrap();
+
+        let indexed = IndexedEvent::from_rpc(&raw).unwrap().unwrap();
+        assert!(matches!(
+            indexed.event,
+            ChainEvent::WillSigned(WillSignedRecord { vault_id: 42, .. })
+        ));
+    }
+
+    #[test]
+    fn test_decode_installment_claimed_event() {
+        let body = struct_val(vec![
+            ("amount", ScVal::U64(250)),
+            ("beneficiary_index", ScVal::U32(0)),
+            ("installment", ScVal::U32(2)),
+            ("plan_id", ScVal::U64(7)),
+            ("remaining_installments", ScVal::U32(10)),
+        ]);
+        let raw: RpcEvent =
+            serde_json::from_value(rpc_event("0001-3", ["PAYOUT", "INSTALL"], &body)).unwrap();
+
+        let indexed = IndexedEvent::from_rpc(&raw).unwrap().unwrap();
+        assert!(matches!(
+            indexed.event,
+            ChainEvent::InstallmentClaimed(InstallmentClaimedRecord {
+                plan_id: 7,
+                installment: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_decode_inheritance_triggered_event() {
+        let body = struct_val(vec![
+            ("outstanding_loans", ScVal::U64(0)),
+            ("plan_id", ScVal::U64(7)),
+            ("triggered_at", ScVal::U64(1_700_000_000)),
+        ]);
+        let raw: RpcEvent =
+            serde_json::from_value(rpc_event("0001-4", ["INHERIT", "TRIGGER"], &body)).unwrap();
+
+        let indexed = IndexedEvent::from_rpc(&raw).unwrap().unwrap();
+        assert!(matches!(
+            indexed.event,
+            ChainEvent::InheritanceTriggered(InheritanceTriggeredRecord {
+                plan_id: 7,
+                triggered_at: 1_700_000_000,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_topics_are_ignored() {
+        let raw: RpcEvent =
+            serde_json::from_value(rpc_event("0001-3", ["ORACLE", "SET"], &ScVal::Void)).unwrap();
+        assert!(IndexedEvent::from_rpc(&raw).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_failed_invocations_are_ignored() {
+        let mut value = rpc_event("0001-4", ["POOL", "BORROW"], &borrow_event_body());
+        value["inSuccessfulContractCall"] = json!(false);
+        let raw: RpcEvent = serde_json::from_value(value).unwrap();
+        assert!(IndexedEvent::from_rpc(&raw).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_malformed_event_body_is_rejected() {
+        let body = struct_val(vec![("loan_id", ScVal::U64(1))]);
+        let raw: RpcEvent =
+            serde_json::from_value(rpc_event("0001-5", ["POOL", "REPAY"], &body)).unwrap();
+        assert!(IndexedEvent::from_rpc(&raw).is_err());
+    }
+
+    #[test]
+    fn test_parse_asset_codes() {
+        let codes = parse_asset_codes("CAAA=usdc, CBBB=XLM,broken");
+        assert_eq!(codes.get("CAAA").map(String::as_str), Some("USDC"));
+        assert_eq!(codes.get("CBBB").map(String::as_str), Some("XLM"));
+        assert_eq!(codes.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_events_from_stub_rpc() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/")
+                    .json_body_partial(r#"{"method":"getEvents","params":{"startLedger":100}}"#);
+                then.status(200).json_body(json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": {
+                        "events": [rpc_event("0001-1", ["POOL", "BORROW"], &borrow_event_body())],
+                        "latestLedger": 130,
+                        "cursor": "0001-1",
+                    }
+                }));
+            })
+            .await;
+
+        let client = SorobanRpcClient::new(server.url("/"));
+        let page = client
+            .get_events(&EventsStart::Ledger(100), &["CCONTRACT".to_string()], 10)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(page.events.len(), 1);
+        assert_eq!(page.latest_ledger, 130);
+        assert_eq!(page.next_cursor().as_deref(), Some("0001-1"));
+    }
+
+    #[tokio::test]
+    async fn test_get_events_resumes_from_cursor() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST).json_body_partial(
+                    r#"{"method":"getEvents","params":{"pagination":{"cursor":"0001-1"}}}"#,
+                );
+                then.status(200).json_body(json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": { "events": [], "latestLedger": 131 }
+                }));
+            })
+            .await;
+
+        let client = SorobanRpcClient::new(server.url("/"));
+        let page = client
+            .get_events(
+                &EventsStart::Cursor("0001-1".to_string()),
+                &["CCONTRACT".to_string()],
+                10,
+            )
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert!(page.events.is_empty());
+        assert_eq!(page.next_cursor(), None);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_error_is_surfaced() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST);
+                then.status(200).json_body(json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "error": { "code": -32600, "message": "startLedger must be positive" }
+                }));
+            })
+            .await;
+
+        let client = SorobanRpcClient::new(server.url("/"));
+        let err = client
+            .get_events(&EventsStart::Ledger(0), &["CCONTRACT".to_string()], 10)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApiError::ExternalService(msg) if msg.contains("startLedger")));
+    }
+}
@@ -165,6 +165,18 @@ impl Config {
     }
 }
 
+/// Deployment environment from `RUN_ENV`, `development` when unset.
+pub fn run_env() -> String {
+    std::env::var("RUN_ENV").unwrap_or_else(|_| "development".to_string())
+}
+
+/// Whether unconfigured chain and delivery integrations may fall back to
+/// local stand-ins. Only `development` and `test` allow it; anywhere else a
+/// missing integration is a startup error.
+pub fn allows_local_fallbacks() -> bool {
+    matches!(run_env().as_str(), "development" | "test")
+}
+
 /// Parse an environment variable as `T`, falling back to `default` on any error.
 fn parse_env<T: std::str::FromStr>(key: &str, default: T) -> T {
     std::env::var(key)
@@ -7,6 +7,8 @@ use crate::api_error::ApiError;
 use crate::notifications::{
     audit_action, entity_type, notif_type, AuditLogService, NotificationService,
 };
+use crate::outbox::Outbox;
+use crate::webhook_events::ContingentBeneficiaryPromoted;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -639,6 +641,17 @@ impl ContingentBeneficiaryService {
         )
         .await?;
 
+        Outbox::publish(
+            &mut *tx,
+            user_id,
+            &ContingentBeneficiaryPromoted {
+                plan_id,
+                beneficiary_id: req.beneficiary_id,
+                reason: req.reason.clone(),
+            },
+        )
+        .await?;
+
         tx.commit().await?;
         Ok(promoted)
     }
@@ -0,0 +1,400 @@
+//! # Contract Invoker
+//!
+//! Submits state-changing contract calls through Soroban RPC. A call is
+//! built as an `InvokeHostFunction` transaction, simulated for its footprint,
+//! resource fee and authorization entries, signed with the invoking
+//! account's ed25519 key and polled until it is applied.
+//!
+//! The invoking account is the transaction source, so contract entry points
+//! that `require_auth` on that account are authorized by the envelope
+//! signature alone; reads gated the same way are answered by simulation
+//! without submitting. Each job that writes to a contract uses its own account,
+//! holding only the contract role that job needs.
+
+use crate::api_error::ApiError;
+use crate::chain_indexer::{SimulateTransactionResult, SorobanRpcClient};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use sha2::{Digest, Sha256};
+use soroban_sdk::xdr::{
+    AccountId, DecoratedSignature, Hash, HostFunction, InvokeContractArgs, InvokeHostFunctionOp,
+    LedgerEntryData, LedgerKey, LedgerKeyAccount, Limits, Memo, MuxedAccount, Operation,
+    OperationBody, Preconditions, PublicKey, ReadXdr, ScAddress, ScSymbol, ScVal, SequenceNumber,
+    Signature, SignatureHint, SorobanAuthorizationEntry, SorobanTransactionData, Transaction,
+    TransactionEnvelope, TransactionExt, TransactionSignaturePayload,
+    TransactionSignaturePayloadTaggedTransaction, TransactionV1Envelope, Uint256, WriteXdr,
+};
+use std::str::FromStr;
+use std::time::Duration;
+
+pub const DEFAULT_NETWORK_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+/// Inclusion fee offered on top of the simulated resource fee, in stroops.
+const BASE_FEE: u32 = 100;
+/// How long to wait for a submitted call to be applied.
+const CONFIRM_ATTEMPTS: u32 = 30;
+const CONFIRM_POLL: Duration = Duration::from_secs(1);
+
+/// Signs and submits calls to one contract on behalf of one account.
+pub struct ContractInvoker {
+    rpc: SorobanRpcClient,
+    contract: ScAddress,
+    keypair: Ed25519KeyPair,
+    network_id: [u8; 32],
+}
+
+impl ContractInvoker {
+    /// `secret` is the Stellar secret seed (`S...`) of the invoking account.
+    pub fn new(
+        rpc_url: &str,
+        contract_id: &str,
+        secret: &str,
+        network_passphrase: &str,
+    ) -> Result<Self, ApiError> {
+        let contract = ScAddress::from_str(contract_id)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Invalid contract id: {e}")))?;
+        let seed = stellar_strkey::ed25519::PrivateKey::from_string(secret)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Invalid secret key: {e}")))?;
+        let keypair = Ed25519KeyPair::from_seed_unchecked(&seed.0)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Invalid secret key: {e}")))?;
+
+        Ok(Self {
+            rpc: SorobanRpcClient::new(rpc_url),
+            contract,
+            keypair,
+            network_id: Sha256::digest(network_passphrase.as_bytes()).into(),
+        })
+    }
+
+    pub fn rpc(&self) -> &SorobanRpcClient {
+        &self.rpc
+    }
+
+    pub fn contract(&self) -> &ScAddress {
+        &self.contract
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        let mut key = [0u8; 32];
+        key.copy_from_slice(self.keypair.public_key().as_ref());
+        key
+    }
+
+    /// The invoking account as a contract `Address` argument.
+    pub fn account_address(&self) -> ScAddress {
+        ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+            self.public_key(),
+        ))))
+    }
+
+    /// Invokes `function(args)` on the contract, waits for the transaction
+    /// to be applied and returns its hash.
+    pub async fn invoke(&self, function: &str, args: Vec<ScVal>) -> Result<String, ApiError> {
+        let sequence = self.next_sequence().await?;
+        let mut tx =
+            invoke_transaction(&self.contract, self.public_key(), sequence, function, args)?;
+
+        let simulation = self.simulate_transaction(function, &tx).await?;
+        let transaction_data = simulation.transaction_data.ok_or_else(|| {
+            ApiError::ExternalService(format!("{function} simulation returned no footprint"))
+        })?;
+        let resource_fee: u32 = simulation
+            .min_resource_fee
+            .as_deref()
+            .unwrap_or("0")
+            .parse()
+            .map_err(|e| ApiError::ExternalService(format!("Invalid minResourceFee: {e}")))?;
+        let auth = simulation
+            .results
+            .first()
+            .map(|result| {
+                result
+                    .auth
+                    .iter()
+                    .map(|entry| decode_xdr::<SorobanAuthorizationEntry>(entry))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        tx = with_auth(tx, auth)?;
+        tx.ext = TransactionExt::V1(decode_xdr::<SorobanTransactionData>(&transaction_data)?);
+        tx.fee = BASE_FEE.saturating_add(resource_fee);
+
+        let sent = self.rpc.send_transaction(&self.sign(tx)?).await?;
+        match sent.status.as_str() {
+            "PENDING" | "DUPLICATE" => {}
+            status => {
+                return Err(ApiError::ExternalService(format!(
+                    "{function} submission rejected with status {status}: {}",
+                    sent.error_result_xdr.unwrap_or_default()
+                )))
+            }
+        }
+
+        self.wait_for(function, &sent.hash).await?;
+        Ok(sent.hash)
+    }
+
+    /// Simulates `function(args)` without submitting it and returns the
+    /// contract's return value. Reads that `require_auth` on the invoking
+    /// account are authorized the same way a submitted call would be.
+    pub async fn simulate(&self, function: &str, args: Vec<ScVal>) -> Result<ScVal, ApiError> {
+        let tx = invoke_transaction(&self.contract, self.public_key(), 0, function, args)?;
+        let simulation = self.simulate_transaction(function, &tx).await?;
+        let result = simulation.results.first().ok_or_else(|| {
+            ApiError::ExternalService(format!("{function} simulation returned no result"))
+        })?;
+        decode_xdr(&result.xdr)
+    }
+
+    async fn simulate_transaction(
+        &self,
+        function: &str,
+        tx: &Transaction,
+    ) -> Result<SimulateTransactionResult, ApiError> {
+        let simulation = self
+            .rpc
+            .simulate_transaction(&encode_xdr(&TransactionEnvelope::Tx(
+                TransactionV1Envelope {
+                    tx: tx.clone(),
+                    signatures: Default::default(),
+                },
+            ))?)
+            .await?;
+        if let Some(error) = simulation.error {
+            return Err(ApiError::ExternalService(format!(
+                "{function} simulation failed: {error}"
+            )));
+        }
+        Ok(simulation)
+    }
+
+    async fn next_sequence(&self) -> Result<i64, ApiError> {
+        let key = LedgerKey::Account(LedgerKeyAccount {
+            account_id: AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(self.public_key()))),
+        });
+        let result = self.rpc.get_ledger_entries(&[encode_xdr(&key)?]).await?;
+        let entry = result.entries.first().ok_or_else(|| {
+            ApiError::ExternalService(format!(
+                "Invoking account {} is not funded",
+                stellar_strkey::ed25519::PublicKey(self.public_key())
+            ))
+        })?;
+
+        match decode_xdr::<LedgerEntryData>(&entry.xdr)? {
+            LedgerEntryData::Account(account) => Ok(account.seq_num.0 + 1),
+            _ => Err(ApiError::ExternalService(
+                "Unexpected ledger entry for invoking account".to_string(),
+            )),
+        }
+    }
+
+    /// Signs `tx` and wraps it in an envelope.
+    pub(crate) fn sign(&self, tx: Transaction) -> Result<String, ApiError> {
+        let hash = transaction_hash(&tx, &self.network_id)?;
+        let signature = self
+            .keypair
+            .sign(&hash)
+            .as_ref()
+            .to_vec()
+            .try_into()
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Invalid signature: {e}")))?;
+        let public_key = self.public_key();
+
+        let decorated = DecoratedSignature {
+            hint: SignatureHint(public_key[28..].try_into().expect("4-byte hint")),
+            signature: Signature(signature),
+        };
+
+        encode_xdr(&TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: vec![decorated]
+                .try_into()
+                .map_err(|e| ApiError::Internal(anyhow::anyhow!("Invalid signatures: {e}")))?,
+        }))
+    }
+
+    async fn wait_for(&self, function: &str, hash: &str) -> Result<(), ApiError> {
+        for _ in 0..CONFIRM_ATTEMPTS {
+            tokio::time::sleep(CONFIRM_POLL).await;
+            match self.rpc.get_transaction(hash).await?.status.as_str() {
+                "SUCCESS" => return Ok(()),
+                "FAILED" => {
+                    return Err(ApiError::ExternalService(format!(
+                        "{function} transaction {hash} failed"
+                    )))
+                }
+                _ => {}
+            }
+        }
+        Err(ApiError::Timeout)
+    }
+}
+
+pub(crate) fn encode_xdr<T: WriteXdr>(value: &T) -> Result<String, ApiError> {
+    value
+        .to_xdr(Limits::none())
+        .map(|bytes| BASE64.encode(bytes))
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("XDR encoding failed: {e}")))
+}
+
+pub(crate) fn decode_xdr<T: ReadXdr>(encoded: &str) -> Result<T, ApiError> {
+    let bytes = BASE64
+        .decode(encoded)
+        .map_err(|e| ApiError::ExternalService(format!("Invalid base64 XDR: {e}")))?;
+    T::from_xdr(bytes, Limits::none())
+        .map_err(|e| ApiError::ExternalService(format!("Invalid XDR: {e}")))
+}
+
+/// Builds the unsigned, unsimulated transaction invoking `function(args)`.
+pub fn invoke_transaction(
+    contract: &ScAddress,
+    source: [u8; 32],
+    sequence: i64,
+    function: &str,
+    args: Vec<ScVal>,
+) -> Result<Transaction, ApiError> {
+    let invalid = |e: &dyn std::fmt::Display| ApiError::Internal(anyhow::anyhow!("{e}"));
+
+    let operation = Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                contract_address: contract.clone(),
+                function_name: ScSymbol(function.try_into().map_err(|e| invalid(&e))?),
+                args: args.try_into().map_err(|e| invalid(&e))?,
+            }),
+            auth: Default::default(),
+        }),
+    };
+
+    Ok(Transaction {
+        source_account: MuxedAccount::Ed25519(Uint256(source)),
+        fee: BASE_FEE,
+        seq_num: SequenceNumber(sequence),
+        cond: Preconditions::None,
+        memo: Memo::None,
+        operations: vec![operation].try_into().map_err(|e| invalid(&e))?,
+        ext: TransactionExt::V0,
+    })
+}
+
+/// Attaches the authorization entries returned by simulation to the
+/// transaction's single invocation.
+fn with_auth(
+    mut tx: Transaction,
+    auth: Vec<SorobanAuthorizationEntry>,
+) -> Result<Transaction, ApiError> {
+    let invalid = |e: &dyn std::fmt::Display| ApiError::Internal(anyhow::anyhow!("{e}"));
+    let mut operations = tx.operations.to_vec();
+    if let Some(OperationBody::InvokeHostFunction(op)) =
+        operations.first_mut().map(|operation| &mut operation.body)
+    {
+        op.auth = auth.try_into().map_err(|e| invalid(&e))?;
+    }
+    tx.operations = operations.try_into().map_err(|e| invalid(&e))?;
+    Ok(tx)
+}
+
+/// The hash a transaction's signers sign: SHA-256 of the XDR
+/// `TransactionSignaturePayload` for the given network.
+pub fn transaction_hash(tx: &Transaction, network_id: &[u8; 32]) -> Result<[u8; 32], ApiError> {
+    let payload = TransactionSignaturePayload {
+        network_id: Hash(*network_id),
+        tagged_transaction: TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+    };
+    let bytes = payload
+        .to_xdr(Limits::none())
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("XDR encoding failed: {e}")))?;
+    Ok(Sha256::digest(bytes).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{UnparsedPublicKey, ED25519};
+    use soroban_sdk::xdr::{
+        SorobanAddressCredentials, SorobanAuthorizedFunction, SorobanAuthorizedInvocation,
+        SorobanCredentials,
+    };
+
+    const CONTRACT: &str = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE";
+
+    fn invoker() -> ContractInvoker {
+        let secret = stellar_strkey::ed25519::PrivateKey([3u8; 32]).to_string();
+        ContractInvoker::new(
+            "http://localhost:8000",
+            CONTRACT,
+            &secret,
+            DEFAULT_NETWORK_PASSPHRASE,
+        )
+        .unwrap()
+    }
+
+    fn invocation(tx: &Transaction) -> &InvokeHostFunctionOp {
+        let OperationBody::InvokeHostFunction(op) = &tx.operations[0].body else {
+            panic!("expected invoke host function");
+        };
+        op
+    }
+
+    #[test]
+    fn invoke_transaction_calls_function_with_args() {
+        let contract = ScAddress::from_str(CONTRACT).unwrap();
+        let tx = invoke_transaction(&contract, [7u8; 32], 101, "bump_plan", vec![ScVal::U64(9)])
+            .unwrap();
+        assert_eq!(tx.seq_num, SequenceNumber(101));
+        let op = invocation(&tx);
+        let HostFunction::InvokeContract(args) = &op.host_function else {
+            panic!("expected contract invocation");
+        };
+        assert_eq!(args.function_name.0.to_utf8_string().unwrap(), "bump_plan");
+        assert_eq!(args.args.to_vec(), vec![ScVal::U64(9)]);
+        assert!(op.auth.is_empty());
+    }
+
+    #[test]
+    fn simulated_auth_is_attached_to_the_invocation() {
+        let invoker = invoker();
+        let tx =
+            invoke_transaction(invoker.contract(), invoker.public_key(), 1, "f", vec![]).unwrap();
+        let HostFunction::InvokeContract(args) = invocation(&tx).host_function.clone() else {
+            panic!("expected contract invocation");
+        };
+        let entry = SorobanAuthorizationEntry {
+            credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+                address: invoker.account_address(),
+                nonce: 1,
+                signature_expiration_ledger: 0,
+                signature: ScVal::Void,
+            }),
+            root_invocation: SorobanAuthorizedInvocation {
+                function: SorobanAuthorizedFunction::ContractFn(args),
+                sub_invocations: Default::default(),
+            },
+        };
+
+        let tx = with_auth(tx, vec![entry.clone()]).unwrap();
+        assert_eq!(invocation(&tx).auth.to_vec(), vec![entry]);
+    }
+
+    #[test]
+    fn signed_envelope_verifies_against_account_key() {
+        let invoker = invoker();
+        let tx =
+            invoke_transaction(invoker.contract(), invoker.public_key(), 5, "f", vec![]).unwrap();
+
+        let envelope: TransactionEnvelope = decode_xdr(&invoker.sign(tx.clone()).unwrap()).unwrap();
+        let TransactionEnvelope::Tx(v1) = envelope else {
+            panic!("expected v1 envelope");
+        };
+        let signature = &v1.signatures[0];
+        assert_eq!(signature.hint.0, invoker.public_key()[28..]);
+
+        let hash = transaction_hash(&tx, &invoker.network_id).unwrap();
+        UnparsedPublicKey::new(&ED25519, invoker.public_key())
+            .verify(&hash, signature.signature.0.as_slice())
+            .expect("signature verifies");
+    }
+}
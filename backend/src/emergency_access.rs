@@ -2,6 +2,8 @@ use crate::api_error::ApiError;
 use crate::notifications::{
     audit_action, entity_type, notif_type, AuditLogService, NotificationService,
 };
+use crate::outbox::Outbox;
+use crate::webhook_events::{EmergencyAccessGranted, EmergencyAccessRevoked};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
@@ -139,6 +141,18 @@ impl EmergencyAccessService {
         )
         .await?;
 
+        Outbox::publish(
+            &mut *tx,
+            plan_user_id,
+            &EmergencyAccessGranted {
+                access_id,
+                plan_id: req.plan_id,
+                access_type: req.access_type.clone(),
+                expires_at,
+            },
+        )
+        .await?;
+
         tx.commit().await?;
 
         Ok(EmergencyAccessResponse {
@@ -223,6 +237,17 @@ impl EmergencyAccessService {
         )
         .await?;
 
+        Outbox::publish(
+            &mut *tx,
+            plan_user_id,
+            &EmergencyAccessRevoked {
+                access_id: req.access_id,
+                plan_id: access.plan_id,
+                reason: req.reason.clone(),
+            },
+        )
+        .await?;
+
         tx.commit().await?;
 
         Ok(EmergencyAccessResponse {
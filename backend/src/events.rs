@@ -292,6 +292,36 @@ impl EventService {
         .await
     }
 
+    /// Emit an event ingested from the chain, keeping the decoded contract
+    /// event as raw JSON metadata
+    #[allow(clippy::too_many_arguments)]
+    pub async fn emit_indexed(
+        tx: &mut Transaction<'_, Postgres>,
+        event_type: EventType,
+        user_id: Uuid,
+        plan_id: Option<Uuid>,
+        asset_code: &str,
+        amount: rust_decimal::Decimal,
+        metadata: serde_json::Value,
+        transaction_hash: Option<String>,
+        block_number: Option<i64>,
+    ) -> Result<LendingEvent, ApiError> {
+        Self::emit_event(
+            tx,
+            EmitEventParams {
+                event_type,
+                user_id,
+                plan_id,
+                asset_code,
+                amount,
+                metadata,
+                transaction_hash,
+                block_number,
+            },
+        )
+        .await
+    }
+
     /// Internal method to emit any event type
     async fn emit_event(
         tx: &mut Transaction<'_, Postgres>,
@@ -435,10 +465,13 @@ impl EventService {
     }
 
     /// Get event by transaction hash
-    pub async fn get_by_transaction_hash(
-        pool: &PgPool,
+    pub async fn get_by_transaction_hash<'a, E>(
+        executor: E,
         transaction_hash: &str,
-    ) -> Result<Vec<LendingEvent>, ApiError> {
+    ) -> Result<Vec<LendingEvent>, ApiError>
+    where
+        E: sqlx::Executor<'a, Database = Postgres>,
+    {
         let rows = sqlx::query_as::<_, LendingEventRow>(
             r#"
             SELECT id, event_type, user_id, plan_id, asset_code, amount,
@@ -450,7 +483,7 @@ impl EventService {
             "#,
         )
         .bind(transaction_hash)
-        .fetch_all(pool)
+        .fetch_all(executor)
         .await?;
 
         rows.into_iter()
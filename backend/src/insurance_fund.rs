@@ -2,6 +2,8 @@ use crate::api_error::ApiError;
 use crate::notifications::{
     audit_action, entity_type, notif_type, AuditLogService, NotificationService,
 };
+use crate::outbox::{Outbox, OutboxMessage};
+use crate::webhook_events::InsuranceClaimProcessed;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -643,6 +645,19 @@ impl InsuranceFundService {
         )
         .await?;
 
+        let processed = InsuranceClaimProcessed {
+            claim_id: claim.id,
+            plan_id: claim.plan_id,
+            loan_id: claim.loan_id,
+            status: claim.status.clone(),
+            approved_amount: claim.approved_amount,
+        };
+        let message = OutboxMessage::new(claim.user_id, &processed)?.with_notification(
+            notif_type::INSURANCE_CLAIM_PROCESSED,
+            format!("Your insurance claim has been {}", claim.status),
+        );
+        Outbox::record(&mut *tx, &message).await?;
+
         tx.commit()
             .await
             .map_err(|e| ApiError::Internal(anyhow::anyhow!("Tx commit error: {}", e)))?;
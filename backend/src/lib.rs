@@ -1,4 +1,6 @@
+pub mod alert_delivery;
 pub mod alert_provider;
+pub mod alert_templates;
 pub mod analytics;
 pub mod api_error;
 pub mod api_versioning;
@@ -6,11 +8,13 @@ pub mod app;
 pub mod auth;
 pub mod beneficiary_sync;
 pub mod cache;
+pub mod chain_indexer;
 pub mod circuit_breaker;
 pub mod collateral_management;
 pub mod compliance;
 pub mod config;
 pub mod contingent_beneficiary;
+pub mod contract_invoker;
 pub mod csrf;
 pub mod db;
 pub mod data_retention;
@@ -34,8 +38,16 @@ pub mod loan_lifecycle;
 pub mod message_access_audit;
 pub mod metrics;
 pub mod middleware;
+pub mod notification_dispatcher;
+pub mod notification_preferences;
 pub mod notifications;
+pub mod outbox;
 pub mod pagination;
+pub mod payout_details;
+pub mod payout_schedule;
+pub mod pdf_typesetter;
+pub mod plan_freeze;
+pub mod plan_ttl_keeper;
 pub mod price_feed;
 pub mod price_feed_handlers;
 pub mod reputation;
@@ -48,7 +60,9 @@ pub mod service;
 pub mod session;
 pub mod stress_testing;
 pub mod telemetry;
+pub mod truetype;
 pub mod webhook;
+pub mod webhook_events;
 pub mod will_audit;
 pub mod will_compliance;
 pub mod will_events;
@@ -58,6 +72,7 @@ pub mod will_version;
 pub mod witness;
 pub mod yield_service;
 
+pub use alert_delivery::AlertDeliveryService;
 pub use api_error::ApiError;
 pub use app::create_app;
 pub use circuit_breaker::CircuitBreaker;
@@ -71,6 +86,8 @@ pub use lending_data_warehouse::LendingDataWarehouseService;
 pub use lending_notification_service::LendingNotificationService;
 pub use loan_lifecycle::{LoanLifecycleService, LoanStatus};
 pub use metrics::get_or_install_recorder;
+pub use notification_dispatcher::NotificationDispatcher;
+pub use outbox::{Outbox, OutboxRelay};
 pub use price_feed::{DefaultPriceFeedService, PriceFeedService, PriceFeedSource};
 pub use risk_engine::RiskEngine;
 pub use safe_math::SafeMath;
@@ -78,4 +95,5 @@ pub use secure_messages::{
     LegacyMessageDeliveryService, MessageEncryptionService, MessageKeyService,
 };
 pub use stress_testing::StressTestingEngine;
-pub use webhook::{event_types, WebhookService};
+pub use webhook::WebhookService;
+pub use webhook_events::event_types;
@@ -22,7 +22,9 @@
 //! invoked periodically by a background sweep or cron job.
 
 use crate::api_error::ApiError;
-use crate::notifications::{audit_action, entity_type, AuditLogService};
+use crate::notifications::{audit_action, entity_type, notif_type, AuditLogService};
+use crate::outbox::{Outbox, OutboxMessage};
+use crate::webhook_events::{LoanDisbursed, LoanLiquidated, LoanRepaid, LoanRepaymentReceived};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -530,6 +532,21 @@ impl LoanLifecycleService {
         )
         .await?;
 
+        Outbox::publish(
+            &mut *tx,
+            record.user_id,
+            &LoanDisbursed {
+                loan_id: record.id,
+                plan_id: record.plan_id,
+                borrow_asset: record.borrow_asset.clone(),
+                principal: record.principal,
+                collateral_asset: record.collateral_asset.clone(),
+                collateral_amount: record.collateral_amount,
+                due_date: record.due_date,
+            },
+        )
+        .await?;
+
         tx.commit().await?;
         Ok(record)
     }
@@ -627,6 +644,23 @@ impl LoanLifecycleService {
         )
         .await?;
 
+        if fully_repaid {
+            let event = LoanRepaid {
+                loan_id,
+                amount_repaid: record.amount_repaid,
+                repaid_at: record.repaid_at,
+            };
+            Outbox::publish(&mut *tx, user_id, &event).await?;
+        } else {
+            let event = LoanRepaymentReceived {
+                loan_id,
+                amount,
+                amount_repaid: record.amount_repaid,
+                principal: record.principal,
+            };
+            Outbox::publish(&mut *tx, user_id, &event).await?;
+        }
+
         tx.commit().await?;
         Ok(record)
     }
@@ -693,6 +727,22 @@ impl LoanLifecycleService {
         )
         .await?;
 
+        let liquidated = LoanLiquidated {
+            loan_id,
+            plan_id: record.plan_id,
+            principal: record.principal,
+            amount_repaid: record.amount_repaid,
+            liquidated_at: record.liquidated_at,
+        };
+        let message = OutboxMessage::new(record.user_id, &liquidated)?.with_notification(
+            notif_type::LOAN_LIQUIDATED,
+            format!(
+                "Your {} loan has been liquidated with {} of {} repaid",
+                record.borrow_asset, record.amount_repaid, record.principal
+            ),
+        );
+        Outbox::record(&mut *tx, &message).await?;
+
         tx.commit().await?;
         Ok(record)
     }
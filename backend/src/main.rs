@@ -1,6 +1,10 @@
+use inheritx_backend::chain_indexer::{ChainIndexerConfig, ChainIndexerService};
+use inheritx_backend::outbox::MessageBusConfig;
+use inheritx_backend::payout_details::{PayoutResealConfig, PayoutResealService};
+use inheritx_backend::plan_ttl_keeper::{PlanTtlKeeperConfig, PlanTtlKeeperService};
 use inheritx_backend::{
     create_app, db, error_tracking, metrics, telemetry, Config, LegacyMessageDeliveryService,
-    LendingDataWarehouseService, MessageKeyService,
+    LendingDataWarehouseService, MessageKeyService, OutboxRelay, WebhookService,
 };
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -89,6 +93,49 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Arc::new(LegacyMessageDeliveryService::new(db_pool.clone()));
     legacy_message_delivery_service.start();
 
+    // Relay domain events from the outbox to notifications, webhooks and,
+    // when OUTBOX_BUS_URL is set, the message bus.
+    let bus_config = MessageBusConfig::from_env();
+    if bus_config.is_none() {
+        info!("Outbox message bus disabled: OUTBOX_BUS_URL not set");
+    }
+    let outbox_relay = Arc::new(OutboxRelay::from_config(
+        db_pool.clone(),
+        bus_config.as_ref(),
+    ));
+    outbox_relay.start();
+
+    // Send queued webhook deliveries and retry failed ones.
+    let webhook_service = Arc::new(WebhookService::new(db_pool.clone()));
+    webhook_service.start();
+
+    // Start Soroban contract event indexer when an RPC endpoint is configured.
+    if let Some(indexer_config) = ChainIndexerConfig::from_env() {
+        let chain_indexer = Arc::new(ChainIndexerService::new(db_pool.clone(), indexer_config));
+        chain_indexer.start();
+    } else {
+        info!("Chain indexer disabled: SOROBAN_RPC_URL or CHAIN_INDEXER_CONTRACT_IDS not set");
+    }
+
+    // Keep active plans from being archived on chain when a keeper account is configured.
+    if let Some(keeper_config) = PlanTtlKeeperConfig::from_env() {
+        let plan_ttl_keeper = Arc::new(PlanTtlKeeperService::from_config(
+            db_pool.clone(),
+            &keeper_config,
+        )?);
+        plan_ttl_keeper.start();
+    } else {
+        info!("Plan TTL keeper disabled: SOROBAN_RPC_URL, INHERITANCE_CONTRACT_ID or PLAN_TTL_KEEPER_SECRET_KEY not set");
+    }
+
+    // Reseal on-chain payout details to the current settlement key when an admin account is configured.
+    if let Some(reseal_config) = PayoutResealConfig::from_env() {
+        let payout_reseal = Arc::new(PayoutResealService::from_config(&reseal_config)?);
+        payout_reseal.start();
+    } else {
+        info!("Payout reseal disabled: SOROBAN_RPC_URL, INHERITANCE_CONTRACT_ID or PAYOUT_RESEAL_SECRET_KEY not set");
+    }
+
     // Start server
     let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
     info!("Starting INHERITX backend server on {}", addr);
@@ -0,0 +1,375 @@
+//! Delivers notifications to the external channels users chose for them.
+//!
+//! New notifications start out `pending`. The dispatcher resolves each one
+//! against the user's [preferences](crate::notification_preferences): it
+//! delivers immediately, holds it until quiet hours end, or parks it as
+//! `digest` for the next daily or weekly digest. Email and SMS go through
+//! [`AlertDeliveryService`], which records every message; the webhook
+//! channel queues `notification.created` / `notification.digest` events.
+//! Failed email or SMS sends are recorded there and not retried here.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::alert_delivery::{AlertDeliveryService, AlertRequest};
+use crate::alert_provider::{AlertChannel, AlertProvider};
+use crate::alert_templates::{self, TemplateVars};
+use crate::api_error::ApiError;
+use crate::notification_preferences::{
+    NotificationChannel, NotificationFrequency, NotificationPreference,
+    NotificationPreferenceService, NotificationSettings,
+};
+use crate::notifications::notif_type;
+use crate::webhook_events::{
+    NotificationCreated, NotificationDigest, WebhookDispatcher, WebhookEvent,
+};
+
+const DISPATCH_INTERVAL_SECS: u64 = 30;
+const DISPATCH_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct PendingNotification {
+    id: Uuid,
+    user_id: Uuid,
+    #[sqlx(rename = "type")]
+    notif_type: String,
+    message: String,
+    created_at: DateTime<Utc>,
+    email: String,
+    locale: String,
+}
+
+impl PendingNotification {
+    fn webhook_payload(&self) -> NotificationCreated {
+        NotificationCreated {
+            notification_id: self.id,
+            notif_type: self.notif_type.clone(),
+            message: self.message.clone(),
+            created_at: self.created_at,
+        }
+    }
+}
+
+const PENDING_COLUMNS: &str = "n.id, n.user_id, n.type, n.message, n.created_at, u.email, u.locale";
+
+pub struct NotificationDispatcher {
+    db: PgPool,
+    alert_provider: Arc<dyn AlertProvider>,
+}
+
+impl NotificationDispatcher {
+    pub fn new(db: PgPool, alert_provider: Arc<dyn AlertProvider>) -> Self {
+        Self { db, alert_provider }
+    }
+
+    /// Spawn the worker that dispatches due notifications and digests.
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(DISPATCH_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                match self.dispatch_pending().await {
+                    Ok(0) => {}
+                    Ok(processed) => info!("Processed {} pending notifications", processed),
+                    Err(e) => error!("Notification dispatch run failed: {}", e),
+                }
+                match self.send_due_digests().await {
+                    Ok(0) => {}
+                    Ok(sent) => info!("Sent {} notification digests", sent),
+                    Err(e) => error!("Notification digest run failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Process up to one batch of due notifications, returning how many
+    /// were delivered, deferred or moved to a digest.
+    pub async fn dispatch_pending(&self) -> Result<usize, ApiError> {
+        let mut processed = 0;
+        while processed < DISPATCH_BATCH_SIZE && self.dispatch_next().await? {
+            processed += 1;
+        }
+        Ok(processed)
+    }
+
+    async fn dispatch_next(&self) -> Result<bool, ApiError> {
+        let mut tx = self.db.begin().await?;
+        let notification = sqlx::query_as::<_, PendingNotification>(&format!(
+            r#"
+            SELECT {PENDING_COLUMNS}
+            FROM notifications n
+            JOIN users u ON u.id = n.user_id
+            WHERE n.dispatch_status = 'pending' AND n.dispatch_after <= NOW()
+            ORDER BY n.dispatch_after, n.created_at
+            LIMIT 1
+            FOR UPDATE OF n SKIP LOCKED
+            "#
+        ))
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(notification) = notification else {
+            return Ok(false);
+        };
+
+        let preference = NotificationPreferenceService::get_preference(
+            &mut *tx,
+            notification.user_id,
+            &notification.notif_type,
+        )
+        .await?;
+        let settings =
+            NotificationPreferenceService::get_settings(&mut *tx, notification.user_id).await?;
+
+        if preference.external_channels().next().is_some() && !preference.is_mandatory() {
+            if preference.frequency != NotificationFrequency::Immediate {
+                sqlx::query(
+                    "UPDATE notifications SET dispatch_status = 'digest', digest_frequency = $2 WHERE id = $1",
+                )
+                .bind(notification.id)
+                .bind(preference.frequency.as_str())
+                .execute(&mut *tx)
+                .await?;
+                tx.commit().await?;
+                return Ok(true);
+            }
+            if let Some(until) = settings.quiet_until(Utc::now()) {
+                sqlx::query("UPDATE notifications SET dispatch_after = $2 WHERE id = $1")
+                    .bind(notification.id)
+                    .bind(until)
+                    .execute(&mut *tx)
+                    .await?;
+                tx.commit().await?;
+                return Ok(true);
+            }
+        }
+
+        self.deliver(&mut tx, &notification, &preference, &settings)
+            .await?;
+        mark_dispatched(&mut tx, &[notification.id]).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn deliver(
+        &self,
+        tx: &mut PgConnection,
+        notification: &PendingNotification,
+        preference: &NotificationPreference,
+        settings: &NotificationSettings,
+    ) -> Result<(), ApiError> {
+        // Queue the webhook first: it is part of this transaction, whereas
+        // email and SMS cannot be taken back if a later step fails.
+        if preference.channels.contains(&NotificationChannel::Webhook) {
+            let event = WebhookEvent::new(&notification.webhook_payload())?;
+            queue_webhook(tx, notification.user_id, &event).await?;
+        }
+
+        let vars = TemplateVars::from([("message", notification.message.clone())]);
+        for channel in preference.external_channels() {
+            self.send_alert(
+                notification,
+                channel,
+                &notification.notif_type,
+                settings,
+                vars.clone(),
+            )
+            .await;
+        }
+        Ok(())
+    }
+
+    /// Send one email or SMS to the owner of `notification`. Failures are
+    /// recorded by the delivery service and logged here.
+    async fn send_alert(
+        &self,
+        notification: &PendingNotification,
+        channel: NotificationChannel,
+        notif_type: &str,
+        settings: &NotificationSettings,
+        vars: TemplateVars,
+    ) {
+        let user_id = notification.user_id;
+        let (channel, to) = match channel {
+            NotificationChannel::Email => (AlertChannel::Email, notification.email.as_str()),
+            NotificationChannel::Sms => match &settings.phone_number {
+                Some(phone) => (AlertChannel::Sms, phone.as_str()),
+                None => {
+                    warn!(
+                        "User {} chose SMS for '{}' but has no phone number",
+                        user_id, notif_type
+                    );
+                    return;
+                }
+            },
+            NotificationChannel::InApp | NotificationChannel::Webhook => return,
+        };
+        let request = AlertRequest {
+            user_id: Some(user_id),
+            channel,
+            recipient: to,
+            notif_type,
+            locale: &notification.locale,
+            vars,
+        };
+        if let Err(e) =
+            AlertDeliveryService::send(&self.db, self.alert_provider.as_ref(), request).await
+        {
+            warn!(
+                "Could not send '{}' {} to user {}: {}",
+                notif_type,
+                channel.as_str(),
+                user_id,
+                e
+            );
+        }
+    }
+
+    /// Send every digest whose scheduled time has passed, returning how many
+    /// were sent. A failing digest is logged and retried on the next run.
+    pub async fn send_due_digests(&self) -> Result<usize, ApiError> {
+        let groups: Vec<(Uuid, String, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            SELECT user_id, digest_frequency, MIN(created_at)
+            FROM notifications
+            WHERE dispatch_status = 'digest'
+            GROUP BY user_id, digest_frequency
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let now = Utc::now();
+        let mut sent = 0;
+        for (user_id, frequency, oldest) in groups {
+            let Some(frequency) = NotificationFrequency::parse(&frequency) else {
+                continue;
+            };
+            let settings = NotificationPreferenceService::get_settings(&self.db, user_id).await?;
+            if now < settings.next_digest_at(frequency, oldest) {
+                continue;
+            }
+            match self.send_digest(user_id, frequency, &settings).await {
+                Ok(true) => sent += 1,
+                Ok(false) => {}
+                Err(e) => error!(
+                    "Could not send {} digest to user {}: {}",
+                    frequency.as_str(),
+                    user_id,
+                    e
+                ),
+            }
+        }
+        Ok(sent)
+    }
+
+    async fn send_digest(
+        &self,
+        user_id: Uuid,
+        frequency: NotificationFrequency,
+        settings: &NotificationSettings,
+    ) -> Result<bool, ApiError> {
+        let mut tx = self.db.begin().await?;
+        let notifications = sqlx::query_as::<_, PendingNotification>(&format!(
+            r#"
+            SELECT {PENDING_COLUMNS}
+            FROM notifications n
+            JOIN users u ON u.id = n.user_id
+            WHERE n.user_id = $1 AND n.dispatch_status = 'digest' AND n.digest_frequency = $2
+            ORDER BY n.created_at
+            FOR UPDATE OF n SKIP LOCKED
+            "#
+        ))
+        .bind(user_id)
+        .bind(frequency.as_str())
+        .fetch_all(&mut *tx)
+        .await?;
+        let Some(first) = notifications.first() else {
+            return Ok(false);
+        };
+
+        // Route each notification by the user's current preference for its
+        // type, so a channel removed since it was created is not used.
+        let mut preferences: HashMap<&str, NotificationPreference> = HashMap::new();
+        let mut by_channel: BTreeMap<NotificationChannel, Vec<&PendingNotification>> =
+            BTreeMap::new();
+        for notification in &notifications {
+            if !preferences.contains_key(notification.notif_type.as_str()) {
+                let preference = NotificationPreferenceService::get_preference(
+                    &mut *tx,
+                    user_id,
+                    &notification.notif_type,
+                )
+                .await?;
+                preferences.insert(&notification.notif_type, preference);
+            }
+            for channel in preferences[notification.notif_type.as_str()].external_channels() {
+                by_channel.entry(channel).or_default().push(notification);
+            }
+        }
+
+        if let Some(items) = by_channel.get(&NotificationChannel::Webhook) {
+            let event = WebhookEvent::new(&NotificationDigest {
+                frequency: frequency.as_str().to_string(),
+                notifications: items.iter().map(|n| n.webhook_payload()).collect(),
+            })?;
+            queue_webhook(&mut tx, user_id, &event).await?;
+        }
+
+        for (channel, items) in &by_channel {
+            let vars = TemplateVars::from([
+                ("count", items.len().to_string()),
+                (
+                    "items",
+                    items
+                        .iter()
+                        .map(|n| {
+                            let line =
+                                alert_templates::digest_line(&n.notif_type, &n.locale, &n.message);
+                            format!("- {}", line)
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                ),
+            ]);
+            self.send_alert(
+                first,
+                *channel,
+                notif_type::NOTIFICATION_DIGEST,
+                settings,
+                vars,
+            )
+            .await;
+        }
+
+        let ids: Vec<Uuid> = notifications.iter().map(|n| n.id).collect();
+        mark_dispatched(&mut tx, &ids).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+}
+
+async fn queue_webhook(
+    tx: &mut PgConnection,
+    user_id: Uuid,
+    event: &WebhookEvent,
+) -> Result<(), ApiError> {
+    let envelope = serde_json::to_value(event).map_err(|e| ApiError::Internal(e.into()))?;
+    WebhookDispatcher::dispatch(tx, user_id, &event.id, &event.event_type, &envelope).await?;
+    Ok(())
+}
+
+async fn mark_dispatched(tx: &mut PgConnection, ids: &[Uuid]) -> Result<(), ApiError> {
+    sqlx::query(
+        "UPDATE notifications SET dispatch_status = 'dispatched', dispatched_at = NOW() WHERE id = ANY($1)",
+    )
+    .bind(ids)
+    .execute(tx)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,698 @@
+//! Per-user notification preferences.
+//!
+//! Every notification is written to the `notifications` table; preferences
+//! decide what happens next. For each notification type a user picks the
+//! channels it reaches (in-app, email, SMS, webhook) and whether external
+//! channels get it immediately or bundled into a daily or weekly digest.
+//! Quiet hours, in the user's timezone, hold back immediate deliveries until
+//! they end. Security notifications have mandatory channels that cannot be
+//! removed, are always sent immediately and ignore quiet hours.
+//!
+//! The [`NotificationDispatcher`](crate::notification_dispatcher) applies
+//! these rules when it delivers notifications.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::Json;
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::api_error::ApiError;
+use crate::app::AppState;
+use crate::auth::AuthenticatedUser;
+use crate::notifications::notif_type;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannel {
+    InApp,
+    Email,
+    Sms,
+    Webhook,
+}
+
+impl NotificationChannel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotificationChannel::InApp => "in_app",
+            NotificationChannel::Email => "email",
+            NotificationChannel::Sms => "sms",
+            NotificationChannel::Webhook => "webhook",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "in_app" => Some(NotificationChannel::InApp),
+            "email" => Some(NotificationChannel::Email),
+            "sms" => Some(NotificationChannel::Sms),
+            "webhook" => Some(NotificationChannel::Webhook),
+            _ => None,
+        }
+    }
+}
+
+/// When external channels receive a notification. In-app notifications are
+/// always visible as soon as they are created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationFrequency {
+    #[default]
+    Immediate,
+    Daily,
+    Weekly,
+}
+
+impl NotificationFrequency {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotificationFrequency::Immediate => "immediate",
+            NotificationFrequency::Daily => "daily",
+            NotificationFrequency::Weekly => "weekly",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "immediate" => Some(NotificationFrequency::Immediate),
+            "daily" => Some(NotificationFrequency::Daily),
+            "weekly" => Some(NotificationFrequency::Weekly),
+            _ => None,
+        }
+    }
+}
+
+/// Notification types users can set preferences for. Types that are only
+/// ever sent as direct alerts (e.g. 2FA codes) are not listed.
+pub const CONFIGURABLE_TYPES: &[&str] = &[
+    notif_type::KYC_APPROVED,
+    notif_type::KYC_REJECTED,
+    notif_type::PLAN_CREATED,
+    notif_type::PLAN_CLAIMED,
+    notif_type::PLAN_DEACTIVATED,
+    notif_type::LIQUIDATION_WARNING,
+    notif_type::REPAYMENT_REMINDER,
+    notif_type::YIELD_UPDATE,
+    notif_type::PLAN_PAUSED,
+    notif_type::PLAN_UNPAUSED,
+    notif_type::RISK_OVERRIDE_APPLIED,
+    notif_type::RISK_OVERRIDE_REMOVED,
+    notif_type::CONTINGENT_BENEFICIARY_ADDED,
+    notif_type::CONTINGENT_BENEFICIARY_REMOVED,
+    notif_type::CONTINGENT_PROMOTED,
+    notif_type::EMERGENCY_ACCESS_GRANTED,
+    notif_type::EMERGENCY_ACCESS_REVOKED,
+    notif_type::EMERGENCY_ACCESS_EXPIRING,
+    notif_type::SUSPICIOUS_ACTIVITY_FLAGGED,
+    notif_type::ADMIN_ALERT,
+    notif_type::FUND_STATUS_CHANGE,
+    notif_type::INSURANCE_CLAIM_PROCESSED,
+    notif_type::LOAN_LIQUIDATED,
+    notif_type::WITNESS_SIGNED,
+];
+
+/// Channels a notification type always reaches, whatever the user's
+/// preference. Types with mandatory channels cannot be digested or held by
+/// quiet hours either.
+pub fn mandatory_channels(notif_type: &str) -> &'static [NotificationChannel] {
+    const SECURITY: &[NotificationChannel] =
+        &[NotificationChannel::InApp, NotificationChannel::Email];
+    match notif_type {
+        notif_type::EMERGENCY_ACCESS_GRANTED
+        | notif_type::EMERGENCY_ACCESS_REVOKED
+        | notif_type::SUSPICIOUS_ACTIVITY_FLAGGED
+        | notif_type::LIQUIDATION_WARNING => SECURITY,
+        _ => &[],
+    }
+}
+
+/// The effective preference for one notification type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NotificationPreference {
+    pub notif_type: String,
+    pub channels: Vec<NotificationChannel>,
+    pub frequency: NotificationFrequency,
+    pub mandatory_channels: Vec<NotificationChannel>,
+}
+
+impl NotificationPreference {
+    /// In-app only, sent immediately, plus any mandatory channels.
+    pub fn default_for(notif_type: &str) -> Self {
+        Self::resolve(
+            notif_type,
+            vec![NotificationChannel::InApp],
+            NotificationFrequency::Immediate,
+        )
+    }
+
+    /// Builds the preference from stored values, re-applying the mandatory
+    /// rules so that a stale row can never mute a security notification.
+    pub fn from_stored(notif_type: &str, channels: &[String], frequency: &str) -> Self {
+        Self::resolve(
+            notif_type,
+            channels
+                .iter()
+                .filter_map(|c| NotificationChannel::parse(c))
+                .collect(),
+            NotificationFrequency::parse(frequency).unwrap_or_default(),
+        )
+    }
+
+    fn resolve(
+        notif_type: &str,
+        mut channels: Vec<NotificationChannel>,
+        frequency: NotificationFrequency,
+    ) -> Self {
+        let mandatory = mandatory_channels(notif_type);
+        channels.extend_from_slice(mandatory);
+        channels.sort();
+        channels.dedup();
+        Self {
+            notif_type: notif_type.to_string(),
+            channels,
+            frequency: if mandatory.is_empty() {
+                frequency
+            } else {
+                NotificationFrequency::Immediate
+            },
+            mandatory_channels: mandatory.to_vec(),
+        }
+    }
+
+    pub fn is_mandatory(&self) -> bool {
+        !self.mandatory_channels.is_empty()
+    }
+
+    /// The channels other than in-app, which the dispatcher delivers to.
+    pub fn external_channels(&self) -> impl Iterator<Item = NotificationChannel> + '_ {
+        self.channels
+            .iter()
+            .copied()
+            .filter(|c| *c != NotificationChannel::InApp)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, sqlx::FromRow)]
+pub struct NotificationSettings {
+    pub timezone: String,
+    pub quiet_hours_start: Option<NaiveTime>,
+    pub quiet_hours_end: Option<NaiveTime>,
+    pub digest_hour: i16,
+    pub phone_number: Option<String>,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            timezone: "UTC".to_string(),
+            quiet_hours_start: None,
+            quiet_hours_end: None,
+            digest_hour: 8,
+            phone_number: None,
+        }
+    }
+}
+
+impl NotificationSettings {
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(Tz::UTC)
+    }
+
+    /// If `now` falls within quiet hours, the instant they end.
+    pub fn quiet_until(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let (start, end) = (self.quiet_hours_start?, self.quiet_hours_end?);
+        let tz = self.tz();
+        let local = now.with_timezone(&tz).naive_local();
+        let time = local.time();
+        let quiet = if start <= end {
+            start <= time && time < end
+        } else {
+            // Quiet hours span midnight, e.g. 22:00–07:00.
+            time >= start || time < end
+        };
+        if !quiet {
+            return None;
+        }
+        let mut ends = local.date().and_time(end);
+        if ends <= local {
+            ends += Duration::days(1);
+        }
+        Some(local_to_utc(tz, ends))
+    }
+
+    /// When a digest holding a notification created at `since` goes out:
+    /// the next `digest_hour` after it (on a Monday for weekly digests).
+    pub fn next_digest_at(
+        &self,
+        frequency: NotificationFrequency,
+        since: DateTime<Utc>,
+    ) -> DateTime<Utc> {
+        if frequency == NotificationFrequency::Immediate {
+            return since;
+        }
+        let tz = self.tz();
+        let local = since.with_timezone(&tz).naive_local();
+        let hour = NaiveTime::from_hms_opt(self.digest_hour.clamp(0, 23) as u32, 0, 0)
+            .unwrap_or(NaiveTime::MIN);
+        let mut at = local.date().and_time(hour);
+        if at <= local {
+            at += Duration::days(1);
+        }
+        if frequency == NotificationFrequency::Weekly {
+            while at.weekday() != Weekday::Mon {
+                at += Duration::days(1);
+            }
+        }
+        local_to_utc(tz, at)
+    }
+}
+
+/// Resolves a local time, moving forward past a DST gap if needed.
+fn local_to_utc(tz: Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    (0..3)
+        .find_map(|hours| {
+            tz.from_local_datetime(&(local + Duration::hours(hours)))
+                .earliest()
+        })
+        .map(|at| at.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&local))
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdatePreferenceRequest {
+    pub channels: Vec<NotificationChannel>,
+    #[serde(default)]
+    pub frequency: NotificationFrequency,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateSettingsRequest {
+    pub timezone: String,
+    pub quiet_hours_start: Option<NaiveTime>,
+    pub quiet_hours_end: Option<NaiveTime>,
+    pub digest_hour: Option<i16>,
+    pub phone_number: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationPreferences {
+    pub settings: NotificationSettings,
+    pub preferences: Vec<NotificationPreference>,
+}
+
+pub fn validate_preference(
+    notif_type: &str,
+    req: &UpdatePreferenceRequest,
+    has_phone_number: bool,
+) -> Result<(), ApiError> {
+    if !CONFIGURABLE_TYPES.contains(&notif_type) {
+        return Err(ApiError::BadRequest(format!(
+            "Unknown notification type '{notif_type}'"
+        )));
+    }
+    let mandatory = mandatory_channels(notif_type);
+    if let Some(missing) = mandatory.iter().find(|c| !req.channels.contains(c)) {
+        return Err(ApiError::BadRequest(format!(
+            "'{}' notifications cannot be turned off for the {} channel",
+            notif_type,
+            missing.as_str()
+        )));
+    }
+    if !mandatory.is_empty() && req.frequency != NotificationFrequency::Immediate {
+        return Err(ApiError::BadRequest(format!(
+            "'{notif_type}' notifications are always sent immediately"
+        )));
+    }
+    if req.channels.contains(&NotificationChannel::Sms) && !has_phone_number {
+        return Err(ApiError::BadRequest(
+            "Add a phone number to your notification settings before choosing SMS".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_settings(req: &UpdateSettingsRequest) -> Result<(), ApiError> {
+    if req.timezone.parse::<Tz>().is_err() {
+        return Err(ApiError::BadRequest(format!(
+            "Unknown timezone '{}'",
+            req.timezone
+        )));
+    }
+    match (req.quiet_hours_start, req.quiet_hours_end) {
+        (None, None) => {}
+        (Some(start), Some(end)) if start != end => {}
+        (Some(_), Some(_)) => {
+            return Err(ApiError::BadRequest(
+                "Quiet hours must start and end at different times".to_string(),
+            ))
+        }
+        _ => {
+            return Err(ApiError::BadRequest(
+                "Quiet hours need both a start and an end".to_string(),
+            ))
+        }
+    }
+    if let Some(hour) = req.digest_hour {
+        if !(0..=23).contains(&hour) {
+            return Err(ApiError::BadRequest(
+                "digest_hour must be between 0 and 23".to_string(),
+            ));
+        }
+    }
+    if let Some(phone) = &req.phone_number {
+        let digits = phone.strip_prefix('+').unwrap_or_default();
+        if !(8..=15).contains(&digits.len()) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ApiError::BadRequest(
+                "phone_number must be in E.164 format, e.g. +14155550100".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub struct NotificationPreferenceService;
+
+impl NotificationPreferenceService {
+    pub async fn get_settings(
+        executor: impl PgExecutor<'_>,
+        user_id: Uuid,
+    ) -> Result<NotificationSettings, ApiError> {
+        let settings = sqlx::query_as::<_, NotificationSettings>(
+            r#"
+            SELECT timezone, quiet_hours_start, quiet_hours_end, digest_hour, phone_number
+            FROM notification_settings
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(executor)
+        .await?;
+        Ok(settings.unwrap_or_default())
+    }
+
+    pub async fn get_preference(
+        executor: impl PgExecutor<'_>,
+        user_id: Uuid,
+        notif_type: &str,
+    ) -> Result<NotificationPreference, ApiError> {
+        let stored: Option<(Vec<String>, String)> = sqlx::query_as(
+            "SELECT channels, frequency FROM notification_preferences WHERE user_id = $1 AND notif_type = $2",
+        )
+        .bind(user_id)
+        .bind(notif_type)
+        .fetch_optional(executor)
+        .await?;
+        Ok(match stored {
+            Some((channels, frequency)) => {
+                NotificationPreference::from_stored(notif_type, &channels, &frequency)
+            }
+            None => NotificationPreference::default_for(notif_type),
+        })
+    }
+
+    /// Settings plus the effective preference of every configurable type.
+    pub async fn list(db: &PgPool, user_id: Uuid) -> Result<NotificationPreferences, ApiError> {
+        let stored: Vec<(String, Vec<String>, String)> = sqlx::query_as(
+            "SELECT notif_type, channels, frequency FROM notification_preferences WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_all(db)
+        .await?;
+
+        let preferences = CONFIGURABLE_TYPES
+            .iter()
+            .map(|notif_type| {
+                stored
+                    .iter()
+                    .find(|(t, _, _)| t == notif_type)
+                    .map(|(_, channels, frequency)| {
+                        NotificationPreference::from_stored(notif_type, channels, frequency)
+                    })
+                    .unwrap_or_else(|| NotificationPreference::default_for(notif_type))
+            })
+            .collect();
+
+        Ok(NotificationPreferences {
+            settings: Self::get_settings(db, user_id).await?,
+            preferences,
+        })
+    }
+
+    pub async fn update_preference(
+        db: &PgPool,
+        user_id: Uuid,
+        notif_type: &str,
+        req: &UpdatePreferenceRequest,
+    ) -> Result<NotificationPreference, ApiError> {
+        let settings = Self::get_settings(db, user_id).await?;
+        validate_preference(notif_type, req, settings.phone_number.is_some())?;
+
+        let channels: Vec<&str> = req.channels.iter().map(|c| c.as_str()).collect();
+        sqlx::query(
+            r#"
+            INSERT INTO notification_preferences (user_id, notif_type, channels, frequency)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id, notif_type) DO UPDATE
+            SET channels = EXCLUDED.channels,
+                frequency = EXCLUDED.frequency,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(user_id)
+        .bind(notif_type)
+        .bind(&channels)
+        .bind(req.frequency.as_str())
+        .execute(db)
+        .await?;
+
+        Self::get_preference(db, user_id, notif_type).await
+    }
+
+    pub async fn update_settings(
+        db: &PgPool,
+        user_id: Uuid,
+        req: &UpdateSettingsRequest,
+    ) -> Result<NotificationSettings, ApiError> {
+        validate_settings(req)?;
+
+        let mut tx = db.begin().await?;
+        if req.phone_number.is_none() {
+            let uses_sms: bool = sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM notification_preferences WHERE user_id = $1 AND 'sms' = ANY(channels))",
+            )
+            .bind(user_id)
+            .fetch_one(&mut *tx)
+            .await?;
+            if uses_sms {
+                return Err(ApiError::BadRequest(
+                    "Remove SMS from your notification preferences before removing the phone number"
+                        .to_string(),
+                ));
+            }
+        }
+
+        let settings = sqlx::query_as::<_, NotificationSettings>(
+            r#"
+            INSERT INTO notification_settings (
+                user_id, timezone, quiet_hours_start, quiet_hours_end, digest_hour, phone_number
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (user_id) DO UPDATE
+            SET timezone = EXCLUDED.timezone,
+                quiet_hours_start = EXCLUDED.quiet_hours_start,
+                quiet_hours_end = EXCLUDED.quiet_hours_end,
+                digest_hour = EXCLUDED.digest_hour,
+                phone_number = EXCLUDED.phone_number,
+                updated_at = NOW()
+            RETURNING timezone, quiet_hours_start, quiet_hours_end, digest_hour, phone_number
+            "#,
+        )
+        .bind(user_id)
+        .bind(&req.timezone)
+        .bind(req.quiet_hours_start)
+        .bind(req.quiet_hours_end)
+        .bind(
+            req.digest_hour
+                .unwrap_or(NotificationSettings::default().digest_hour),
+        )
+        .bind(&req.phone_number)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(settings)
+    }
+}
+
+// ─── Handlers ────────────────────────────────────────────────────────────────
+
+pub async fn get_notification_preferences(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<NotificationPreferences>, ApiError> {
+    let preferences = NotificationPreferenceService::list(&state.db, user.user_id).await?;
+    Ok(Json(preferences))
+}
+
+pub async fn update_notification_preference(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(notif_type): Path<String>,
+    Json(req): Json<UpdatePreferenceRequest>,
+) -> Result<Json<NotificationPreference>, ApiError> {
+    let preference = NotificationPreferenceService::update_preference(
+        &state.db,
+        user.user_id,
+        &notif_type,
+        &req,
+    )
+    .await?;
+    Ok(Json(preference))
+}
+
+pub async fn update_notification_settings(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<UpdateSettingsRequest>,
+) -> Result<Json<NotificationSettings>, ApiError> {
+    let settings =
+        NotificationPreferenceService::update_settings(&state.db, user.user_id, &req).await?;
+    Ok(Json(settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn settings(timezone: &str, quiet: Option<(&str, &str)>) -> NotificationSettings {
+        NotificationSettings {
+            timezone: timezone.to_string(),
+            quiet_hours_start: quiet.map(|(s, _)| s.parse().unwrap()),
+            quiet_hours_end: quiet.map(|(_, e)| e.parse().unwrap()),
+            ..NotificationSettings::default()
+        }
+    }
+
+    #[test]
+    fn mandatory_channels_cannot_be_muted() {
+        let stored = NotificationPreference::from_stored(
+            notif_type::EMERGENCY_ACCESS_GRANTED,
+            &["sms".to_string()],
+            "weekly",
+        );
+        assert_eq!(
+            stored.channels,
+            vec![
+                NotificationChannel::InApp,
+                NotificationChannel::Email,
+                NotificationChannel::Sms
+            ]
+        );
+        assert_eq!(stored.frequency, NotificationFrequency::Immediate);
+
+        let muted = UpdatePreferenceRequest {
+            channels: vec![NotificationChannel::InApp],
+            frequency: NotificationFrequency::Immediate,
+        };
+        assert!(validate_preference(notif_type::EMERGENCY_ACCESS_GRANTED, &muted, false).is_err());
+
+        let digest = UpdatePreferenceRequest {
+            channels: vec![NotificationChannel::InApp, NotificationChannel::Email],
+            frequency: NotificationFrequency::Daily,
+        };
+        assert!(validate_preference(notif_type::LIQUIDATION_WARNING, &digest, false).is_err());
+    }
+
+    #[test]
+    fn ordinary_types_can_be_muted_or_digested() {
+        let weekly = UpdatePreferenceRequest {
+            channels: vec![NotificationChannel::Email],
+            frequency: NotificationFrequency::Weekly,
+        };
+        assert!(validate_preference(notif_type::YIELD_UPDATE, &weekly, false).is_ok());
+        let muted = UpdatePreferenceRequest {
+            channels: vec![],
+            frequency: NotificationFrequency::Immediate,
+        };
+        assert!(validate_preference(notif_type::YIELD_UPDATE, &muted, false).is_ok());
+        assert!(validate_preference("not_a_type", &muted, false).is_err());
+    }
+
+    #[test]
+    fn sms_needs_a_phone_number() {
+        let sms = UpdatePreferenceRequest {
+            channels: vec![NotificationChannel::Sms],
+            frequency: NotificationFrequency::Immediate,
+        };
+        assert!(validate_preference(notif_type::YIELD_UPDATE, &sms, false).is_err());
+        assert!(validate_preference(notif_type::YIELD_UPDATE, &sms, true).is_ok());
+    }
+
+    #[test]
+    fn quiet_hours_spanning_midnight_in_user_timezone() {
+        // 22:00–07:00 in Madrid (UTC+2 in summer).
+        let s = settings("Europe/Madrid", Some(("22:00:00", "07:00:00")));
+        assert_eq!(
+            s.quiet_until(at("2026-06-10T21:30:00Z")),
+            Some(at("2026-06-11T05:00:00Z"))
+        );
+        assert_eq!(
+            s.quiet_until(at("2026-06-11T03:00:00Z")),
+            Some(at("2026-06-11T05:00:00Z"))
+        );
+        assert_eq!(s.quiet_until(at("2026-06-11T12:00:00Z")), None);
+        assert_eq!(settings("UTC", None).quiet_until(Utc::now()), None);
+    }
+
+    #[test]
+    fn digests_go_out_at_the_next_digest_hour() {
+        let s = settings("America/New_York", None);
+        // Wednesday 2026-06-10 14:00 in New York (UTC-4).
+        let created = at("2026-06-10T18:00:00Z");
+        assert_eq!(
+            s.next_digest_at(NotificationFrequency::Daily, created),
+            at("2026-06-11T12:00:00Z")
+        );
+        assert_eq!(
+            s.next_digest_at(NotificationFrequency::Weekly, created),
+            at("2026-06-15T12:00:00Z")
+        );
+    }
+
+    #[test]
+    fn settings_are_validated() {
+        let valid = UpdateSettingsRequest {
+            timezone: "Europe/Madrid".to_string(),
+            quiet_hours_start: Some("22:00:00".parse().unwrap()),
+            quiet_hours_end: Some("07:00:00".parse().unwrap()),
+            digest_hour: Some(9),
+            phone_number: Some("+34600000000".to_string()),
+        };
+        assert!(validate_settings(&valid).is_ok());
+        assert!(validate_settings(&UpdateSettingsRequest {
+            timezone: "Mars/Olympus".to_string(),
+            ..valid.clone()
+        })
+        .is_err());
+        assert!(validate_settings(&UpdateSettingsRequest {
+            quiet_hours_end: None,
+            ..valid.clone()
+        })
+        .is_err());
+        assert!(validate_settings(&UpdateSettingsRequest {
+            phone_number: Some("600 000 000".to_string()),
+            ..valid
+        })
+        .is_err());
+    }
+}
@@ -1,4 +1,6 @@
-use crate::alert_provider::AlertProvider;
+use crate::alert_delivery::{AlertDeliveryService, AlertRequest};
+use crate::alert_provider::{AlertChannel, AlertProvider};
+use crate::alert_templates::{self, TemplateVars};
 use crate::api_error::ApiError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -31,9 +33,27 @@ pub mod notif_type {
     pub const EMERGENCY_ACCESS_REVOKED: &str = "emergency_access_revoked";
     pub const EMERGENCY_ACCESS_EXPIRING: &str = "emergency_access_expiring";
     pub const SUSPICIOUS_ACTIVITY_FLAGGED: &str = "suspicious_activity_flagged";
+    pub const EMERGENCY_CONTACT_ALERT: &str = "emergency_contact_alert";
     // Insurance fund monitoring (Issue #249)
     pub const ADMIN_ALERT: &str = "admin_alert";
     pub const FUND_STATUS_CHANGE: &str = "fund_status_change";
+    pub const INSURANCE_CLAIM_PROCESSED: &str = "insurance_claim_processed";
+    // Loans and wills
+    pub const LOAN_LIQUIDATED: &str = "loan_liquidated";
+    pub const WITNESS_SIGNED: &str = "witness_signed";
+    // Bundled low-priority notifications
+    pub const NOTIFICATION_DIGEST: &str = "notification_digest";
+}
+
+/// Title stored with a notification of `notif_type`: `plan_paused` becomes
+/// "Plan paused".
+fn title_for(notif_type: &str) -> String {
+    let words = notif_type.replace('_', " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => words,
+    }
 }
 
 // ─── Notification ────────────────────────────────────────────────────────────
@@ -64,12 +84,13 @@ impl NotificationService {
         let message = message.into();
         let row = sqlx::query_as::<_, Notification>(
             r#"
-            INSERT INTO notifications (user_id, type, message, is_read)
-            VALUES ($1, $2, $3, false)
+            INSERT INTO notifications (user_id, title, type, message, is_read)
+            VALUES ($1, $2, $3, $4, false)
             RETURNING id, user_id, type, message, is_read, created_at
             "#,
         )
         .bind(user_id)
+        .bind(title_for(notif_type))
         .bind(notif_type)
         .bind(&message)
         .fetch_one(executor) // Use the passed connection/transaction
@@ -82,14 +103,20 @@ impl NotificationService {
     // Because atomic safety requires that if a notification fails,
     // the parent transaction MUST rollback.
 
-    /// Return all notifications for a user (Read-only, can stay using &PgPool)
+    /// Return all notifications for a user (Read-only, can stay using &PgPool).
+    /// Types the user has taken off the in-app channel are left out.
     pub async fn list_for_user(db: &PgPool, user_id: Uuid) -> Result<Vec<Notification>, ApiError> {
         let rows = sqlx::query_as::<_, Notification>(
             r#"
-            SELECT id, user_id, type, message, is_read, created_at
-            FROM notifications
-            WHERE user_id = $1
-            ORDER BY created_at DESC
+            SELECT n.id, n.user_id, n.type, n.message, n.is_read, n.created_at
+            FROM notifications n
+            WHERE n.user_id = $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM notification_preferences p
+                  WHERE p.user_id = n.user_id AND p.notif_type = n.type
+                    AND NOT ('in_app' = ANY(p.channels))
+              )
+            ORDER BY n.created_at DESC
             "#,
         )
         .bind(user_id)
@@ -108,10 +135,15 @@ impl NotificationService {
         let offset = ((page.saturating_sub(1)) as i64) * (limit as i64);
         let rows = sqlx::query_as::<_, Notification>(
             r#"
-            SELECT id, user_id, type, message, is_read, created_at
-            FROM notifications
-            WHERE user_id = $1
-            ORDER BY created_at DESC
+            SELECT n.id, n.user_id, n.type, n.message, n.is_read, n.created_at
+            FROM notifications n
+            WHERE n.user_id = $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM notification_preferences p
+                  WHERE p.user_id = n.user_id AND p.notif_type = n.type
+                    AND NOT ('in_app' = ANY(p.channels))
+              )
+            ORDER BY n.created_at DESC
             LIMIT $2 OFFSET $3
             "#,
         )
@@ -128,8 +160,13 @@ impl NotificationService {
         let count = sqlx::query_scalar::<_, i64>(
             r#"
             SELECT COUNT(*)
-            FROM notifications
-            WHERE user_id = $1
+            FROM notifications n
+            WHERE n.user_id = $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM notification_preferences p
+                  WHERE p.user_id = n.user_id AND p.notif_type = n.type
+                    AND NOT ('in_app' = ANY(p.channels))
+              )
             "#,
         )
         .bind(user_id)
@@ -168,19 +205,21 @@ pub struct EmergencyAlertService;
 
 impl EmergencyAlertService {
     /// Send risk alerts via SMS and Email to both the user and their emergency contact.
+    /// Every message is attempted and recorded; the first failure is returned.
     pub async fn send_risk_alert(
         db: &PgPool,
-        provider: &impl AlertProvider,
+        provider: &dyn AlertProvider,
         user_id: Uuid,
         contact_id: Uuid,
         alert_type: &str,
         message: &str,
     ) -> Result<(), ApiError> {
-        // 1. Fetch user email
-        let user_email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
-            .bind(user_id)
-            .fetch_one(db)
-            .await?;
+        // 1. Fetch user email and locale
+        let (user_email, locale): (String, String) =
+            sqlx::query_as("SELECT email, locale FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_one(db)
+                .await?;
 
         // 2. Fetch contact info
         let contact: (Option<String>, Option<String>) =
@@ -191,39 +230,51 @@ impl EmergencyAlertService {
 
         let (contact_email, contact_phone) = contact;
 
-        // 3. Send to user (Email)
-        provider
-            .send_email(
-                &user_email,
-                &format!("Security Alert: {}", alert_type),
-                message,
-            )
-            .await
-            .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
-
-        // 4. Send to contact (Email and/or SMS)
-        if let Some(email) = contact_email {
-            provider
-                .send_email(
-                    &email,
-                    &format!("Emergency Alert for your contact: {}", alert_type),
-                    message,
-                )
-                .await
-                .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
-        }
+        let vars = TemplateVars::from([
+            ("alert_type", alert_type.to_string()),
+            (
+                "alert",
+                alert_templates::risk_alert_label(alert_type, &locale),
+            ),
+            ("message", message.to_string()),
+        ]);
+        let alert = |channel, recipient, notif_type| AlertRequest {
+            user_id: Some(user_id),
+            channel,
+            recipient,
+            notif_type,
+            locale: &locale,
+            vars: vars.clone(),
+        };
 
-        if let Some(phone) = contact_phone {
-            provider
-                .send_sms(
-                    &phone,
-                    &format!("Emergency Alert: {}. Message: {}", alert_type, message),
-                )
-                .await
-                .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
+        // 3. Send to user (Email), then to contact (Email and/or SMS)
+        let mut requests = vec![alert(
+            AlertChannel::Email,
+            &user_email,
+            notif_type::SUSPICIOUS_ACTIVITY_FLAGGED,
+        )];
+        if let Some(email) = &contact_email {
+            requests.push(alert(
+                AlertChannel::Email,
+                email,
+                notif_type::EMERGENCY_CONTACT_ALERT,
+            ));
+        }
+        if let Some(phone) = &contact_phone {
+            requests.push(alert(
+                AlertChannel::Sms,
+                phone,
+                notif_type::EMERGENCY_CONTACT_ALERT,
+            ));
         }
 
-        Ok(())
+        let mut first_error = None;
+        for request in requests {
+            if let Err(e) = AlertDeliveryService::send(db, provider, request).await {
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
     }
 }
 // ─── Audit Log ───────────────────────────────────────────────────────────────
@@ -428,7 +479,7 @@ impl AuditLogService {
 
 #[cfg(test)]
 mod tests {
-    use super::{audit_action, entity_type, notif_type};
+    use super::{audit_action, entity_type, notif_type, title_for};
     use crate::notifications::{ActionLog, Notification};
     use chrono::Utc;
     use uuid::Uuid;
@@ -465,6 +516,13 @@ mod tests {
         assert_eq!(notif_type::TWO_FA_SENT, "2fa_sent");
     }
 
+    #[test]
+    fn title_is_derived_from_notif_type() {
+        assert_eq!(title_for(notif_type::PLAN_PAUSED), "Plan paused");
+        assert_eq!(title_for(notif_type::TWO_FA_SENT), "2fa sent");
+        assert_eq!(title_for(""), "");
+    }
+
     // ── audit_action constants ──────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,579 @@
+//! Transactional outbox for domain events.
+//!
+//! Services record an event with [`Outbox::publish`] (or [`Outbox::record`]
+//! for one that also carries an in-app notification) using the transaction
+//! of the change that raised it, so the event exists if and only if the
+//! change commits. [`OutboxRelay`] then fans each event out, in one
+//! transaction per event: it creates the notification and queues webhook
+//! deliveries. When a [`MessageBus`] is configured, relayed events are
+//! published to it in a separate pass tracked by `bus_status`, so a bus
+//! outage delays only the bus. Either step that fails is retried with
+//! back-off and marked `failed` after `MAX_RELAY_ATTEMPTS`;
+//! [`Outbox::requeue_failed`] puts failed events back in the queue.
+//!
+//! Bus delivery is at-least-once: if the relay crashes after publishing but
+//! before recording it, the event is published again. Bus consumers should
+//! de-duplicate on the message key, which is the event id.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::api_error::ApiError;
+use crate::notifications::NotificationService;
+use crate::webhook::backoff_delay;
+use crate::webhook_events::{WebhookDispatcher, WebhookEvent, WebhookPayload};
+
+/// Relay (or bus publish) attempts before an event is marked `failed`.
+pub const MAX_RELAY_ATTEMPTS: i32 = 10;
+
+const DEFAULT_TOPIC_PREFIX: &str = "inheritx";
+const RELAY_BATCH_SIZE: usize = 100;
+const RELAY_INTERVAL_SECS: u64 = 2;
+const BUS_TIMEOUT_SECS: u64 = 10;
+
+/// An event waiting to be written to the outbox.
+#[derive(Debug, Clone)]
+pub struct OutboxMessage {
+    pub user_id: Uuid,
+    pub event: WebhookEvent,
+    pub notification: Option<(String, String)>, // (notif_type, message)
+}
+
+impl OutboxMessage {
+    pub fn new<P: WebhookPayload>(user_id: Uuid, payload: &P) -> Result<Self, ApiError> {
+        Ok(Self {
+            user_id,
+            event: WebhookEvent::new(payload)?,
+            notification: None,
+        })
+    }
+
+    /// Also create an in-app notification for the user when relayed.
+    pub fn with_notification(mut self, notif_type: &str, message: impl Into<String>) -> Self {
+        self.notification = Some((notif_type.to_string(), message.into()));
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct OutboxEvent {
+    pub id: Uuid,
+    pub event_id: String,
+    pub event_type: String,
+    pub version: i32,
+    pub user_id: Uuid,
+    pub payload: Value,
+    pub notification_type: Option<String>,
+    pub notification_message: Option<String>,
+    pub status: String,
+    pub attempt_count: i32,
+    pub available_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub relayed_at: Option<DateTime<Utc>>,
+    pub bus_status: Option<String>,
+    pub bus_attempt_count: i32,
+    pub bus_available_at: Option<DateTime<Utc>>,
+    pub bus_last_error: Option<String>,
+    pub bus_published_at: Option<DateTime<Utc>>,
+}
+
+pub struct Outbox;
+
+impl Outbox {
+    /// Record `payload` as an event for `user_id`. Pass the transaction of
+    /// the change that raised it.
+    pub async fn publish<P: WebhookPayload>(
+        executor: impl sqlx::PgExecutor<'_>,
+        user_id: Uuid,
+        payload: &P,
+    ) -> Result<Uuid, ApiError> {
+        Self::record(executor, &OutboxMessage::new(user_id, payload)?).await
+    }
+
+    pub async fn record(
+        executor: impl sqlx::PgExecutor<'_>,
+        message: &OutboxMessage,
+    ) -> Result<Uuid, ApiError> {
+        let envelope =
+            serde_json::to_value(&message.event).map_err(|e| ApiError::Internal(e.into()))?;
+        let (notification_type, notification_message) = message.notification.clone().unzip();
+
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO outbox_events (
+                event_id, event_type, version, user_id, payload,
+                notification_type, notification_message
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id
+            "#,
+        )
+        .bind(&message.event.id)
+        .bind(&message.event.event_type)
+        .bind(message.event.version as i32)
+        .bind(message.user_id)
+        .bind(envelope)
+        .bind(notification_type)
+        .bind(notification_message)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Give events that exhausted their relay or bus attempts a fresh retry
+    /// budget, returning how many were requeued. Pass `event_ids` to requeue
+    /// only those events.
+    pub async fn requeue_failed(
+        db: &PgPool,
+        event_ids: Option<&[String]>,
+    ) -> Result<u64, ApiError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE outbox_events
+            SET status = CASE WHEN status = 'failed' THEN 'pending' ELSE status END,
+                attempt_count = CASE WHEN status = 'failed' THEN 0 ELSE attempt_count END,
+                available_at = CASE WHEN status = 'failed' THEN NOW() ELSE available_at END,
+                bus_status = CASE WHEN bus_status = 'failed' THEN 'pending' ELSE bus_status END,
+                bus_attempt_count = CASE WHEN bus_status = 'failed' THEN 0 ELSE bus_attempt_count END,
+                bus_available_at = CASE WHEN bus_status = 'failed' THEN NOW() ELSE bus_available_at END
+            WHERE (status = 'failed' OR bus_status = 'failed')
+              AND ($1::TEXT[] IS NULL OR event_id = ANY($1))
+            "#,
+        )
+        .bind(event_ids)
+        .execute(db)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+// ─── Message bus ─────────────────────────────────────────────────────────────
+
+/// A message published to the bus. Topics are dotted names such as
+/// `inheritx.loan.liquidated`, valid as both Kafka topics and NATS subjects.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BusMessage {
+    pub topic: String,
+    /// The event id, for consumers to de-duplicate on.
+    pub key: String,
+    pub payload: Value,
+}
+
+#[async_trait]
+pub trait MessageBus: Send + Sync {
+    async fn publish(&self, message: &BusMessage) -> anyhow::Result<()>;
+}
+
+/// Keeps published messages in memory. For tests and local development.
+#[derive(Default)]
+pub struct InMemoryMessageBus {
+    messages: Mutex<Vec<BusMessage>>,
+    failing: AtomicBool,
+}
+
+impl InMemoryMessageBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn messages(&self) -> Vec<BusMessage> {
+        self.messages.lock().unwrap().clone()
+    }
+
+    /// Make every publish fail until reset, to exercise relay retries.
+    pub fn set_failing(&self, failing: bool) {
+        self.failing.store(failing, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl MessageBus for InMemoryMessageBus {
+    async fn publish(&self, message: &BusMessage) -> anyhow::Result<()> {
+        if self.failing.load(Ordering::SeqCst) {
+            anyhow::bail!("in-memory bus is set to fail");
+        }
+        self.messages.lock().unwrap().push(message.clone());
+        Ok(())
+    }
+}
+
+/// Publishes through the Kafka REST Proxy v2 API, which Confluent REST Proxy
+/// and Redpanda's HTTP proxy both serve.
+pub struct RestProxyMessageBus {
+    client: Client,
+    base_url: String,
+}
+
+impl RestProxyMessageBus {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(BUS_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl MessageBus for RestProxyMessageBus {
+    async fn publish(&self, message: &BusMessage) -> anyhow::Result<()> {
+        let body = json!({
+            "records": [{ "key": message.key, "value": message.payload }]
+        });
+        let response = self
+            .client
+            .post(format!("{}/topics/{}", self.base_url, message.topic))
+            .header("Content-Type", "application/vnd.kafka.json.v2+json")
+            .body(serde_json::to_vec(&body)?)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("bus proxy returned {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MessageBusConfig {
+    pub rest_proxy_url: String,
+    pub topic_prefix: String,
+}
+
+impl MessageBusConfig {
+    /// Reads the bus configuration from the environment. Returns `None` when
+    /// `OUTBOX_BUS_URL` is unset, in which case events are not published to a
+    /// bus.
+    pub fn from_env() -> Option<Self> {
+        let rest_proxy_url = std::env::var("OUTBOX_BUS_URL")
+            .ok()
+            .filter(|v| !v.is_empty())?;
+        Some(Self {
+            rest_proxy_url,
+            topic_prefix: std::env::var("OUTBOX_BUS_TOPIC_PREFIX")
+                .unwrap_or_else(|_| DEFAULT_TOPIC_PREFIX.to_string()),
+        })
+    }
+}
+
+/// The bus topic for `event_type`.
+pub fn topic_for(prefix: &str, event_type: &str) -> String {
+    if prefix.is_empty() {
+        event_type.to_string()
+    } else {
+        format!("{}.{}", prefix, event_type)
+    }
+}
+
+// ─── Relay ───────────────────────────────────────────────────────────────────
+
+pub struct OutboxRelay {
+    db: PgPool,
+    bus: Option<Arc<dyn MessageBus>>,
+    topic_prefix: String,
+}
+
+impl OutboxRelay {
+    pub fn new(db: PgPool) -> Self {
+        Self {
+            db,
+            bus: None,
+            topic_prefix: DEFAULT_TOPIC_PREFIX.to_string(),
+        }
+    }
+
+    pub fn with_bus(mut self, bus: Arc<dyn MessageBus>, topic_prefix: impl Into<String>) -> Self {
+        self.bus = Some(bus);
+        self.topic_prefix = topic_prefix.into();
+        self
+    }
+
+    pub fn from_config(db: PgPool, config: Option<&MessageBusConfig>) -> Self {
+        match config {
+            Some(config) => Self::new(db).with_bus(
+                Arc::new(RestProxyMessageBus::new(&config.rest_proxy_url)),
+                &config.topic_prefix,
+            ),
+            None => Self::new(db),
+        }
+    }
+
+    /// Spawn the worker that relays pending events and publishes relayed
+    /// ones to the bus.
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(RELAY_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                match self.relay_pending().await {
+                    Ok(0) => {}
+                    Ok(relayed) => info!("Relayed {} outbox events", relayed),
+                    Err(e) => error!("Outbox relay run failed: {}", e),
+                }
+                match self.publish_pending().await {
+                    Ok(0) => {}
+                    Ok(published) => info!("Published {} outbox events to the bus", published),
+                    Err(e) => error!("Outbox bus publish run failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Relay up to one batch of due events, returning how many were
+    /// processed (relayed or rescheduled after a failure).
+    pub async fn relay_pending(&self) -> Result<usize, ApiError> {
+        let mut processed = 0;
+        while processed < RELAY_BATCH_SIZE && self.relay_next().await? {
+            processed += 1;
+        }
+        Ok(processed)
+    }
+
+    /// Relay the oldest due event. Returns `false` when none is due.
+    async fn relay_next(&self) -> Result<bool, ApiError> {
+        let mut tx = self.db.begin().await?;
+        // SKIP LOCKED lets several relays run side by side; each event is
+        // locked until its fan-out commits or is rolled back.
+        let event = sqlx::query_as::<_, OutboxEvent>(
+            r#"
+            SELECT * FROM outbox_events
+            WHERE status = 'pending' AND available_at <= NOW()
+            ORDER BY created_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+            "#,
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(event) = event else {
+            return Ok(false);
+        };
+
+        match self.fan_out(&mut tx, &event).await {
+            Ok(()) => {
+                sqlx::query(
+                    r#"
+                    UPDATE outbox_events
+                    SET status = 'relayed', relayed_at = NOW(),
+                        attempt_count = attempt_count + 1, last_error = NULL,
+                        bus_status = CASE WHEN $2 THEN 'pending' END,
+                        bus_available_at = CASE WHEN $2 THEN NOW() END
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(event.id)
+                .bind(self.bus.is_some())
+                .execute(&mut *tx)
+                .await?;
+                tx.commit().await?;
+            }
+            Err(e) => {
+                // Undo any partial fan-out, then reschedule in a fresh
+                // transaction so one bad event does not block the queue.
+                tx.rollback().await?;
+                self.record_failure(&event, &e.to_string()).await?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Create the notification and queue webhook deliveries in `tx`.
+    async fn fan_out(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        event: &OutboxEvent,
+    ) -> anyhow::Result<()> {
+        if let (Some(notif_type), Some(message)) =
+            (&event.notification_type, &event.notification_message)
+        {
+            NotificationService::create(tx, event.user_id, notif_type, message.clone()).await?;
+        }
+
+        WebhookDispatcher::dispatch(
+            &mut **tx,
+            event.user_id,
+            &event.event_id,
+            &event.event_type,
+            &event.payload,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Publish up to one batch of relayed events to the bus, returning how
+    /// many were processed (published or rescheduled after a failure).
+    /// Does nothing when no bus is configured.
+    pub async fn publish_pending(&self) -> Result<usize, ApiError> {
+        let Some(bus) = &self.bus else {
+            return Ok(0);
+        };
+        let mut processed = 0;
+        while processed < RELAY_BATCH_SIZE && self.publish_next(bus.as_ref()).await? {
+            processed += 1;
+        }
+        Ok(processed)
+    }
+
+    /// Publish the oldest event due on the bus. Returns `false` when none is
+    /// due.
+    async fn publish_next(&self, bus: &dyn MessageBus) -> Result<bool, ApiError> {
+        let mut tx = self.db.begin().await?;
+        let event = sqlx::query_as::<_, OutboxEvent>(
+            r#"
+            SELECT * FROM outbox_events
+            WHERE bus_status = 'pending' AND bus_available_at <= NOW()
+            ORDER BY created_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+            "#,
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(event) = event else {
+            return Ok(false);
+        };
+
+        let message = BusMessage {
+            topic: topic_for(&self.topic_prefix, &event.event_type),
+            key: event.event_id.clone(),
+            payload: event.payload.clone(),
+        };
+        match bus.publish(&message).await {
+            Ok(()) => {
+                sqlx::query(
+                    r#"
+                    UPDATE outbox_events
+                    SET bus_status = 'published', bus_published_at = NOW(),
+                        bus_attempt_count = bus_attempt_count + 1, bus_last_error = NULL
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(event.id)
+                .execute(&mut *tx)
+                .await?;
+            }
+            Err(e) => {
+                let attempt = event.bus_attempt_count + 1;
+                let delay = backoff_delay(attempt as u32, rand::thread_rng().gen::<f64>());
+                warn!(
+                    "Outbox event {} ({}) bus publish attempt {} failed: {}",
+                    event.event_id, event.event_type, attempt, e
+                );
+                sqlx::query(
+                    r#"
+                    UPDATE outbox_events
+                    SET bus_attempt_count = $2,
+                        bus_last_error = $3,
+                        bus_status = CASE WHEN $4 THEN 'failed' ELSE bus_status END,
+                        bus_available_at = NOW() + make_interval(secs => $5)
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(event.id)
+                .bind(attempt)
+                .bind(e.to_string())
+                .bind(attempt >= MAX_RELAY_ATTEMPTS)
+                .bind(delay.as_secs_f64())
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn record_failure(&self, event: &OutboxEvent, error: &str) -> Result<(), ApiError> {
+        let attempt = event.attempt_count + 1;
+        let failed = attempt >= MAX_RELAY_ATTEMPTS;
+        let delay = backoff_delay(attempt as u32, rand::thread_rng().gen::<f64>());
+        warn!(
+            "Outbox event {} ({}) relay attempt {} failed: {}",
+            event.event_id, event.event_type, attempt, error
+        );
+
+        sqlx::query(
+            r#"
+            UPDATE outbox_events
+            SET attempt_count = $2,
+                last_error = $3,
+                status = CASE WHEN $4 THEN 'failed' ELSE status END,
+                available_at = NOW() + make_interval(secs => $5)
+            WHERE id = $1
+            "#,
+        )
+        .bind(event.id)
+        .bind(attempt)
+        .bind(error)
+        .bind(failed)
+        .bind(delay.as_secs_f64())
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhook_events::{event_types, KycSubmitted};
+
+    #[test]
+    fn topics_are_prefixed_event_types() {
+        assert_eq!(
+            topic_for("inheritx", event_types::LOAN_LIQUIDATED),
+            "inheritx.loan.liquidated"
+        );
+        assert_eq!(topic_for("", event_types::PLAN_CREATED), "plan.created");
+    }
+
+    #[test]
+    fn message_carries_envelope_and_optional_notification() {
+        let user_id = Uuid::new_v4();
+        let message = OutboxMessage::new(user_id, &KycSubmitted { user_id }).unwrap();
+        assert_eq!(message.event.event_type, event_types::KYC_SUBMITTED);
+        assert!(message.notification.is_none());
+
+        let message = message.with_notification("kyc_submitted", "Submitted");
+        assert_eq!(
+            message.notification,
+            Some(("kyc_submitted".to_string(), "Submitted".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn in_memory_bus_records_messages_until_set_failing() {
+        let bus = InMemoryMessageBus::new();
+        let message = BusMessage {
+            topic: "inheritx.plan.created".to_string(),
+            key: "evt-1".to_string(),
+            payload: json!({ "id": "evt-1" }),
+        };
+
+        bus.publish(&message).await.unwrap();
+        bus.set_failing(true);
+        assert!(bus.publish(&message).await.is_err());
+        bus.set_failing(false);
+        bus.publish(&message).await.unwrap();
+
+        assert_eq!(bus.messages(), vec![message.clone(), message]);
+    }
+}
@@ -0,0 +1,711 @@
+//! Sealed beneficiary payout details.
+//!
+//! Bank details written to the inheritance contract are public ledger state,
+//! so they are stored as envelopes sealed to a settlement key that only this
+//! service holds. The envelope layout is shared with the contract's
+//! `payout_details` module:
+//!
+//! ```text
+//! version (1) ‖ key_id (4, big-endian) ‖ nonce (12) ‖ AES-256-GCM ciphertext ‖ tag (16)
+//! ```
+//!
+//! The header is the AEAD associated data, so an envelope cannot be relabelled
+//! with another key id. Keys are configured as `SETTLEMENT_KEYS=<id>:<secret>,…`
+//! with `SETTLEMENT_KEY_ID` naming the one new envelopes are sealed to; retired
+//! keys stay listed until every plan has been resealed.
+//!
+//! [`PayoutResealService`] does that resealing: it pages through the
+//! contract's `get_unsealed_plans`, seals legacy plaintext and re-seals
+//! envelopes under retired keys to the current key, and writes them back
+//! with `reseal_payout_details`. It signs as an account holding the
+//! contract's admin role, configured as `PAYOUT_RESEAL_SECRET_KEY`.
+
+use crate::api_error::ApiError;
+use crate::chain_indexer::scval_to_json;
+use crate::contract_invoker::{ContractInvoker, DEFAULT_NETWORK_PASSPHRASE};
+use axum::async_trait;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
+use ring::hkdf::{Salt, HKDF_SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use soroban_sdk::xdr::{ScBytes, ScVal, ScVec};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+pub const ENVELOPE_VERSION: u8 = 1;
+const HEADER_LEN: usize = 5;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+/// Plans requested per `get_unsealed_plans` page.
+const RESEAL_PAGE_SIZE: u32 = 50;
+const DEFAULT_RESEAL_INTERVAL_SECS: u64 = 60 * 60;
+
+/// Fiat payout details as sealed into an envelope.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayoutDetails {
+    pub beneficiary_name: String,
+    pub bank_name: String,
+    pub bank_account_number: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SealPayoutDetailsRequest {
+    pub beneficiary_name: String,
+    pub bank_name: String,
+    pub bank_account_number: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SealedPayoutDetails {
+    pub key_id: u32,
+    /// Hex-encoded envelope, ready to pass as a beneficiary's `bank_account`.
+    pub envelope_hex: String,
+}
+
+/// The settlement keys this service can seal to and open with.
+pub struct SettlementKeyring {
+    current_key_id: u32,
+    keys: BTreeMap<u32, LessSafeKey>,
+}
+
+fn derive_key(secret: &[u8]) -> Result<LessSafeKey, ApiError> {
+    let salt = Salt::new(HKDF_SHA256, b"inheritx-settlement-key");
+    let prk = salt.extract(secret);
+    let okm = prk
+        .expand(&[b"payout-details"], &AES_256_GCM)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Key derivation failed")))?;
+    let mut key_bytes = [0u8; KEY_LEN];
+    okm.fill(&mut key_bytes)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Unable to materialize key")))?;
+    let unbound = UnboundKey::new(&AES_256_GCM, &key_bytes)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Unable to create key")))?;
+    Ok(LessSafeKey::new(unbound))
+}
+
+fn invalid_config(message: &str) -> ApiError {
+    ApiError::Internal(anyhow::anyhow!("Invalid settlement key config: {message}"))
+}
+
+impl SettlementKeyring {
+    /// Parse `SETTLEMENT_KEYS` (`<id>:<secret>` pairs, comma separated) and
+    /// `SETTLEMENT_KEY_ID` (defaults to the highest id).
+    pub fn parse(keys: &str, current_key_id: Option<&str>) -> Result<Self, ApiError> {
+        let mut parsed = BTreeMap::new();
+        for entry in keys.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (id, secret) = entry
+                .split_once(':')
+                .ok_or_else(|| invalid_config("expected <id>:<secret>"))?;
+            let id: u32 = id
+                .trim()
+                .parse()
+                .map_err(|_| invalid_config("key id must be a u32"))?;
+            if secret.is_empty() {
+                return Err(invalid_config("empty secret"));
+            }
+            parsed.insert(id, derive_key(secret.as_bytes())?);
+        }
+
+        let current_key_id = match current_key_id.map(str::trim).filter(|s| !s.is_empty()) {
+            Some(id) => id
+                .parse()
+                .map_err(|_| invalid_config("SETTLEMENT_KEY_ID must be a u32"))?,
+            None => *parsed
+                .keys()
+                .next_back()
+                .ok_or_else(|| invalid_config("no keys configured"))?,
+        };
+        if !parsed.contains_key(&current_key_id) {
+            return Err(invalid_config("SETTLEMENT_KEY_ID is not in SETTLEMENT_KEYS"));
+        }
+
+        Ok(Self {
+            current_key_id,
+            keys: parsed,
+        })
+    }
+
+    /// The configured keyring, or `None` when `SETTLEMENT_KEYS` is unset.
+    pub fn from_env() -> Result<Option<Self>, ApiError> {
+        match std::env::var("SETTLEMENT_KEYS") {
+            Ok(keys) if !keys.trim().is_empty() => Self::parse(
+                &keys,
+                std::env::var("SETTLEMENT_KEY_ID").ok().as_deref(),
+            )
+            .map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Like [`Self::from_env`], but a missing keyring is an error.
+    pub fn require_from_env() -> Result<Self, ApiError> {
+        Self::from_env()?.ok_or_else(|| {
+            ApiError::Internal(anyhow::anyhow!("SETTLEMENT_KEYS must be set"))
+        })
+    }
+
+    pub fn current_key_id(&self) -> u32 {
+        self.current_key_id
+    }
+
+    /// The key id in an envelope's header, if it is a well-formed envelope.
+    pub fn key_id_of(envelope: &[u8]) -> Option<u32> {
+        if envelope.len() <= HEADER_LEN + NONCE_LEN || envelope[0] != ENVELOPE_VERSION {
+            return None;
+        }
+        Some(u32::from_be_bytes(envelope[1..HEADER_LEN].try_into().ok()?))
+    }
+
+    /// Seal `plaintext` to the current settlement key.
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, ApiError> {
+        let key = &self.keys[&self.current_key_id];
+        let mut envelope = Vec::with_capacity(HEADER_LEN + NONCE_LEN + plaintext.len() + 16);
+        envelope.push(ENVELOPE_VERSION);
+        envelope.extend_from_slice(&self.current_key_id.to_be_bytes());
+
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce_bytes)
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate nonce")))?;
+
+        let mut in_out = plaintext.to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce_bytes),
+            Aad::from(&envelope[..HEADER_LEN]),
+            &mut in_out,
+        )
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Encryption failed")))?;
+
+        envelope.extend_from_slice(&nonce_bytes);
+        envelope.extend_from_slice(&in_out);
+        Ok(envelope)
+    }
+
+    /// Open an envelope sealed to any key still in the keyring.
+    pub fn open(&self, envelope: &[u8]) -> Result<Vec<u8>, ApiError> {
+        let key_id = Self::key_id_of(envelope)
+            .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Malformed payout envelope")))?;
+        let key = self.keys.get(&key_id).ok_or_else(|| {
+            ApiError::Internal(anyhow::anyhow!("Unknown settlement key id {key_id}"))
+        })?;
+
+        let (header, rest) = envelope.split_at(HEADER_LEN);
+        let (nonce_bytes, ciphertext) = rest.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce_bytes)
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Invalid nonce length")))?;
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = key
+            .open_in_place(nonce, Aad::from(header), &mut in_out)
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Decryption failed")))?;
+        Ok(plaintext.to_vec())
+    }
+
+    pub fn seal_details(&self, details: &PayoutDetails) -> Result<Vec<u8>, ApiError> {
+        let plaintext = serde_json::to_vec(details)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Serialize payout details: {e}")))?;
+        self.seal(&plaintext)
+    }
+
+    pub fn open_details(&self, envelope: &[u8]) -> Result<PayoutDetails, ApiError> {
+        serde_json::from_slice(&self.open(envelope)?).map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Malformed payout details: {e}"))
+        })
+    }
+}
+
+// ─── Resealing ────────────────────────────────────────────────────────────────
+
+pub struct PayoutResealConfig {
+    pub rpc_url: String,
+    pub contract_id: String,
+    /// Stellar secret seed (`S...`) of an account holding the admin role.
+    pub admin_secret: String,
+    pub network_passphrase: String,
+    pub interval_secs: u64,
+}
+
+impl PayoutResealConfig {
+    /// Reads the reseal job configuration from the environment. Returns
+    /// `None` when `SOROBAN_RPC_URL`, `INHERITANCE_CONTRACT_ID` or
+    /// `PAYOUT_RESEAL_SECRET_KEY` is unset, in which case the job is disabled.
+    pub fn from_env() -> Option<Self> {
+        let non_empty = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+        Some(Self {
+            rpc_url: non_empty("SOROBAN_RPC_URL")?,
+            contract_id: non_empty("INHERITANCE_CONTRACT_ID")?,
+            admin_secret: non_empty("PAYOUT_RESEAL_SECRET_KEY")?,
+            network_passphrase: non_empty("SOROBAN_NETWORK_PASSPHRASE")
+                .unwrap_or_else(|| DEFAULT_NETWORK_PASSPHRASE.to_string()),
+            interval_secs: non_empty("PAYOUT_RESEAL_INTERVAL_SECS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_RESEAL_INTERVAL_SECS),
+        })
+    }
+}
+
+/// A plan whose payout details are not all sealed to the current key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsealedPlan {
+    pub plan_id: u64,
+    /// Each beneficiary's `bank_account`, in order.
+    pub bank_accounts: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnsealedPlanPage {
+    pub plans: Vec<UnsealedPlan>,
+    pub next_cursor: Option<u64>,
+}
+
+/// Lists plans that need resealing and writes resealed details back.
+#[async_trait]
+pub trait PayoutDetailsChain: Send + Sync {
+    async fn unsealed_plans(
+        &self,
+        start_after: Option<u64>,
+        limit: u32,
+    ) -> Result<UnsealedPlanPage, ApiError>;
+
+    /// Submits `reseal_payout_details` and waits for it to be applied.
+    /// Returns the transaction hash.
+    async fn reseal(&self, plan_id: u64, sealed: Vec<Vec<u8>>) -> Result<String, ApiError>;
+}
+
+/// Calls the inheritance contract through Soroban RPC as the admin account.
+pub struct SorobanPayoutDetailsChain {
+    invoker: ContractInvoker,
+}
+
+impl SorobanPayoutDetailsChain {
+    pub fn new(config: &PayoutResealConfig) -> Result<Self, ApiError> {
+        Ok(Self {
+            invoker: ContractInvoker::new(
+                &config.rpc_url,
+                &config.contract_id,
+                &config.admin_secret,
+                &config.network_passphrase,
+            )?,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct PlanPageJson {
+    plans: Vec<PlanEntryJson>,
+    next_cursor: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct PlanEntryJson {
+    plan_id: u64,
+    plan: PlanJson,
+}
+
+#[derive(Deserialize)]
+struct PlanJson {
+    beneficiaries: Vec<BeneficiaryJson>,
+}
+
+#[derive(Deserialize)]
+struct BeneficiaryJson {
+    /// Hex, as rendered by `scval_to_json`.
+    bank_account: String,
+}
+
+/// Decodes the `PlanPage` returned by `get_unsealed_plans`.
+fn parse_unsealed_page(value: &ScVal) -> Result<UnsealedPlanPage, ApiError> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        ApiError::ExternalService(format!("Invalid get_unsealed_plans result: {e}"))
+    };
+    let page: PlanPageJson =
+        serde_json::from_value(scval_to_json(value)).map_err(|e| invalid(&e))?;
+
+    Ok(UnsealedPlanPage {
+        plans: page
+            .plans
+            .into_iter()
+            .map(|entry| {
+                Ok(UnsealedPlan {
+                    plan_id: entry.plan_id,
+                    bank_accounts: entry
+                        .plan
+                        .beneficiaries
+                        .iter()
+                        .map(|b| hex::decode(&b.bank_account).map_err(|e| invalid(&e)))
+                        .collect::<Result<_, _>>()?,
+                })
+            })
+            .collect::<Result<_, ApiError>>()?,
+        next_cursor: page.next_cursor,
+    })
+}
+
+#[async_trait]
+impl PayoutDetailsChain for SorobanPayoutDetailsChain {
+    async fn unsealed_plans(
+        &self,
+        start_after: Option<u64>,
+        limit: u32,
+    ) -> Result<UnsealedPlanPage, ApiError> {
+        let value = self
+            .invoker
+            .simulate(
+                "get_unsealed_plans",
+                vec![
+                    ScVal::Address(self.invoker.account_address()),
+                    start_after.map_or(ScVal::Void, ScVal::U64),
+                    ScVal::U32(limit),
+                ],
+            )
+            .await?;
+        parse_unsealed_page(&value)
+    }
+
+    async fn reseal(&self, plan_id: u64, sealed: Vec<Vec<u8>>) -> Result<String, ApiError> {
+        let invalid = |e: &dyn std::fmt::Display| ApiError::Internal(anyhow::anyhow!("{e}"));
+        let sealed = sealed
+            .into_iter()
+            .map(|envelope| {
+                envelope
+                    .try_into()
+                    .map(|bytes| ScVal::Bytes(ScBytes(bytes)))
+                    .map_err(|e| invalid(&e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.invoker
+            .invoke(
+                "reseal_payout_details",
+                vec![
+                    ScVal::Address(self.invoker.account_address()),
+                    ScVal::U64(plan_id),
+                    ScVal::Vec(Some(ScVec(sealed.try_into().map_err(|e| invalid(&e))?))),
+                ],
+            )
+            .await
+    }
+}
+
+impl SettlementKeyring {
+    /// The details to write back for one plan: envelopes already sealed to
+    /// the current key unchanged, envelopes under a retired key opened and
+    /// sealed again, and legacy plaintext sealed as-is.
+    pub fn reseal_all(&self, bank_accounts: &[Vec<u8>]) -> Result<Vec<Vec<u8>>, ApiError> {
+        bank_accounts
+            .iter()
+            .map(|details| match Self::key_id_of(details) {
+                Some(key_id) if key_id == self.current_key_id => Ok(details.clone()),
+                Some(_) => self.seal(&self.open(details)?),
+                None => self.seal(details),
+            })
+            .collect()
+    }
+}
+
+/// Outcome of one reseal pass.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct ResealRunSummary {
+    pub checked: usize,
+    pub resealed: usize,
+    pub failed: usize,
+}
+
+pub struct PayoutResealService {
+    chain: Arc<dyn PayoutDetailsChain>,
+    keyring: SettlementKeyring,
+    interval_secs: u64,
+}
+
+impl PayoutResealService {
+    pub fn new(
+        chain: Arc<dyn PayoutDetailsChain>,
+        keyring: SettlementKeyring,
+        interval_secs: u64,
+    ) -> Self {
+        Self {
+            chain,
+            keyring,
+            interval_secs,
+        }
+    }
+
+    /// Builds the job from its configuration and the `SETTLEMENT_KEYS`
+    /// keyring, which must be set when the job is enabled.
+    pub fn from_config(config: &PayoutResealConfig) -> Result<Self, ApiError> {
+        Ok(Self::new(
+            Arc::new(SorobanPayoutDetailsChain::new(config)?),
+            SettlementKeyring::require_from_env()?,
+            config.interval_secs,
+        ))
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(self.interval_secs));
+            loop {
+                interval.tick().await;
+                match self.run_once().await {
+                    Ok(summary) => info!(
+                        "Payout reseal checked {} plans: {} resealed, {} failed",
+                        summary.checked, summary.resealed, summary.failed
+                    ),
+                    Err(e) => error!("Payout reseal run failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Reseals every plan the contract reports as unsealed. A failure on one
+    /// plan is logged and does not stop the pass.
+    pub async fn run_once(&self) -> Result<ResealRunSummary, ApiError> {
+        let mut summary = ResealRunSummary::default();
+        let mut cursor = None;
+
+        loop {
+            let page = self.chain.unsealed_plans(cursor, RESEAL_PAGE_SIZE).await?;
+            for plan in page.plans {
+                summary.checked += 1;
+                let result = match self.keyring.reseal_all(&plan.bank_accounts) {
+                    Ok(sealed) => self.chain.reseal(plan.plan_id, sealed).await,
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(hash) => {
+                        summary.resealed += 1;
+                        info!(
+                            "Resealed payout details of contract plan {} in {}",
+                            plan.plan_id, hash
+                        );
+                    }
+                    Err(e) => {
+                        summary.failed += 1;
+                        warn!(
+                            "Failed to reseal payout details of contract plan {}: {}",
+                            plan.plan_id, e
+                        );
+                    }
+                }
+            }
+
+            match page.next_cursor {
+                Some(next) if Some(next) > cursor => cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details() -> PayoutDetails {
+        PayoutDetails {
+            beneficiary_name: "Ada Heir".to_string(),
+            bank_name: "First Bank".to_string(),
+            bank_account_number: "0123456789".to_string(),
+        }
+    }
+
+    #[test]
+    fn seal_and_open_round_trip() {
+        let keyring = SettlementKeyring::parse("1:alpha", None).unwrap();
+        let envelope = keyring.seal_details(&details()).unwrap();
+
+        assert_eq!(envelope[0], ENVELOPE_VERSION);
+        assert_eq!(SettlementKeyring::key_id_of(&envelope), Some(1));
+        assert!(!envelope
+            .windows(10)
+            .any(|w| w == b"0123456789".as_slice()));
+        assert_eq!(keyring.open_details(&envelope).unwrap(), details());
+    }
+
+    #[test]
+    fn relabelled_or_tampered_envelopes_are_rejected() {
+        let keyring = SettlementKeyring::parse("1:alpha,2:alpha", Some("1")).unwrap();
+        let envelope = keyring.seal_details(&details()).unwrap();
+
+        let mut relabelled = envelope.clone();
+        relabelled[4] = 2;
+        assert!(keyring.open(&relabelled).is_err());
+
+        let mut tampered = envelope;
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(keyring.open(&tampered).is_err());
+    }
+
+    #[test]
+    fn rotated_keyring_opens_old_envelopes() {
+        let old = SettlementKeyring::parse("1:alpha", None).unwrap();
+        let envelope = old.seal_details(&details()).unwrap();
+
+        let rotated = SettlementKeyring::parse("1:alpha, 2:beta", None).unwrap();
+        assert_eq!(rotated.current_key_id(), 2);
+        assert_eq!(rotated.open_details(&envelope).unwrap(), details());
+        assert_eq!(
+            SettlementKeyring::key_id_of(&rotated.seal_details(&details()).unwrap()),
+            Some(2)
+        );
+
+        let retired = SettlementKeyring::parse("2:beta", None).unwrap();
+        assert!(retired.open(&envelope).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_bad_config() {
+        assert!(SettlementKeyring::parse("", None).is_err());
+        assert!(SettlementKeyring::parse("one:alpha", None).is_err());
+        assert!(SettlementKeyring::parse("1:", None).is_err());
+        assert!(SettlementKeyring::parse("1:alpha", Some("2")).is_err());
+    }
+
+    #[test]
+    fn reseal_all_only_touches_unsealed_details() {
+        let old = SettlementKeyring::parse("1:alpha", None).unwrap();
+        let keyring = SettlementKeyring::parse("1:alpha,2:beta", None).unwrap();
+        let current = keyring.seal_details(&details()).unwrap();
+        let retired = old.seal_details(&details()).unwrap();
+        let plaintext = b"0123456789".to_vec();
+
+        let sealed = keyring
+            .reseal_all(&[current.clone(), retired, plaintext.clone()])
+            .unwrap();
+
+        assert_eq!(sealed.len(), 3);
+        assert_eq!(sealed[0], current);
+        assert_eq!(SettlementKeyring::key_id_of(&sealed[1]), Some(2));
+        assert_eq!(keyring.open_details(&sealed[1]).unwrap(), details());
+        assert_eq!(SettlementKeyring::key_id_of(&sealed[2]), Some(2));
+        assert_eq!(keyring.open(&sealed[2]).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn parse_unsealed_page_reads_bank_accounts() {
+        use soroban_sdk::xdr::{ScMap, ScMapEntry, ScSymbol};
+
+        let sym = |s: &str| ScVal::Symbol(ScSymbol(s.try_into().unwrap()));
+        let map = |entries: Vec<(&str, ScVal)>| {
+            ScVal::Map(Some(ScMap(
+                entries
+                    .into_iter()
+                    .map(|(key, val)| ScMapEntry { key: sym(key), val })
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap(),
+            )))
+        };
+        let vec = |items: Vec<ScVal>| ScVal::Vec(Some(ScVec(items.try_into().unwrap())));
+        let beneficiary = |account: &[u8]| {
+            map(vec![
+                ("allocation_bp", ScVal::U32(10_000)),
+                (
+                    "bank_account",
+                    ScVal::Bytes(ScBytes(account.to_vec().try_into().unwrap())),
+                ),
+            ])
+        };
+        let page = map(vec![
+            ("next_cursor", ScVal::U64(7)),
+            (
+                "plans",
+                vec(vec![map(vec![
+                    (
+                        "plan",
+                        map(vec![("beneficiaries", vec(vec![beneficiary(b"acct")]))]),
+                    ),
+                    ("plan_id", ScVal::U64(7)),
+                ])]),
+            ),
+        ]);
+
+        assert_eq!(
+            parse_unsealed_page(&page).unwrap(),
+            UnsealedPlanPage {
+                plans: vec![UnsealedPlan {
+                    plan_id: 7,
+                    bank_accounts: vec![b"acct".to_vec()],
+                }],
+                next_cursor: Some(7),
+            }
+        );
+    }
+
+    struct FakeChain {
+        pages: Vec<UnsealedPlanPage>,
+        resealed: std::sync::Mutex<Vec<(u64, Vec<Vec<u8>>)>>,
+    }
+
+    #[async_trait]
+    impl PayoutDetailsChain for FakeChain {
+        async fn unsealed_plans(
+            &self,
+            start_after: Option<u64>,
+            _limit: u32,
+        ) -> Result<UnsealedPlanPage, ApiError> {
+            Ok(self
+                .pages
+                .iter()
+                .find(|page| page.plans.first().map(|p| p.plan_id) > start_after)
+                .cloned()
+                .unwrap_or_default())
+        }
+
+        async fn reseal(&self, plan_id: u64, sealed: Vec<Vec<u8>>) -> Result<String, ApiError> {
+            if plan_id == 2 {
+                return Err(ApiError::ExternalService("simulation failed".to_string()));
+            }
+            self.resealed.lock().unwrap().push((plan_id, sealed));
+            Ok(format!("hash-{plan_id}"))
+        }
+    }
+
+    #[tokio::test]
+    async fn run_once_reseals_every_page_and_skips_failures() {
+        let plan = |plan_id| UnsealedPlan {
+            plan_id,
+            bank_accounts: vec![b"0123456789".to_vec()],
+        };
+        let chain = Arc::new(FakeChain {
+            pages: vec![
+                UnsealedPlanPage {
+                    plans: vec![plan(1), plan(2)],
+                    next_cursor: Some(2),
+                },
+                UnsealedPlanPage {
+                    plans: vec![plan(3)],
+                    next_cursor: None,
+                },
+            ],
+            resealed: Default::default(),
+        });
+        let service = PayoutResealService::new(
+            chain.clone(),
+            SettlementKeyring::parse("1:alpha", None).unwrap(),
+            60,
+        );
+
+        let summary = service.run_once().await.unwrap();
+
+        assert_eq!(
+            summary,
+            ResealRunSummary {
+                checked: 3,
+                resealed: 2,
+                failed: 1,
+            }
+        );
+        let resealed = chain.resealed.lock().unwrap();
+        assert_eq!(
+            resealed.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert!(resealed
+            .iter()
+            .all(|(_, sealed)| SettlementKeyring::key_id_of(&sealed[0]) == Some(1)));
+    }
+}
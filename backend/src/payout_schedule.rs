@@ -0,0 +1,114 @@
+//! Off-chain mirror of the inheritance contract's periodic payout schedule.
+//!
+//! Monthly/Quarterly/Yearly plans pay each beneficiary in installments. The
+//! first installment falls due one period after the plan was created on chain,
+//! or when inheritance is triggered if that comes first, and each later
+//! installment one period after the previous one. The period lengths, default
+//! installment counts and trigger rule must match `payouts.rs` in the
+//! inheritance contract.
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayoutSchedule {
+    pub period_secs: i64,
+    pub installment_count: u32,
+    pub first_due_at: i64,
+}
+
+impl PayoutSchedule {
+    /// Build the schedule for a plan. Returns `None` for lump-sum or unknown
+    /// distribution methods. `triggered_at` is when inheritance was triggered
+    /// on chain, if it has been; `installment_count` is the owner's on-chain
+    /// override, if any.
+    pub fn for_plan(
+        distribution_method: &str,
+        contract_created_at: i64,
+        triggered_at: Option<i64>,
+        installment_count: Option<u32>,
+    ) -> Option<Self> {
+        let (period_secs, default_count) = match distribution_method {
+            "Monthly" => (30 * DAY_SECS, 12),
+            "Quarterly" => (90 * DAY_SECS, 4),
+            "Yearly" => (365 * DAY_SECS, 3),
+            _ => return None,
+        };
+        Some(Self {
+            period_secs,
+            installment_count: installment_count.unwrap_or(default_count),
+            first_due_at: triggered_at.map_or(contract_created_at + period_secs, |at| {
+                at.min(contract_created_at + period_secs)
+            }),
+        })
+    }
+
+    /// Number of installments that have fallen due by `now`.
+    pub fn installments_due(&self, now: i64) -> u32 {
+        if now < self.first_due_at {
+            return 0;
+        }
+        let due = (now - self.first_due_at) / self.period_secs + 1;
+        due.min(self.installment_count as i64) as u32
+    }
+
+    /// Due date of the installment after `paid_installments`, or `None` once
+    /// every installment has been paid.
+    pub fn next_due_at(&self, paid_installments: u32) -> Option<i64> {
+        if paid_installments >= self.installment_count {
+            return None;
+        }
+        Some(self.first_due_at + paid_installments as i64 * self.period_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lump_sum_has_no_schedule() {
+        assert!(PayoutSchedule::for_plan("LumpSum", 0, None, None).is_none());
+        assert!(PayoutSchedule::for_plan("Weekly", 0, None, None).is_none());
+    }
+
+    #[test]
+    fn defaults_match_contract() {
+        let monthly = PayoutSchedule::for_plan("Monthly", 1_000, None, None).unwrap();
+        assert_eq!(monthly.installment_count, 12);
+        assert_eq!(monthly.first_due_at, 1_000 + 30 * DAY_SECS);
+
+        let quarterly = PayoutSchedule::for_plan("Quarterly", 0, None, None).unwrap();
+        assert_eq!(quarterly.installment_count, 4);
+
+        let yearly = PayoutSchedule::for_plan("Yearly", 0, None, Some(5)).unwrap();
+        assert_eq!(yearly.installment_count, 5);
+        assert_eq!(yearly.period_secs, 365 * DAY_SECS);
+    }
+
+    #[test]
+    fn installments_fall_due_one_period_apart() {
+        let s = PayoutSchedule::for_plan("Monthly", 0, None, Some(3)).unwrap();
+        let month = 30 * DAY_SECS;
+        assert_eq!(s.installments_due(month - 1), 0);
+        assert_eq!(s.installments_due(month), 1);
+        assert_eq!(s.installments_due(2 * month), 2);
+        assert_eq!(s.installments_due(10 * month), 3);
+
+        assert_eq!(s.next_due_at(0), Some(month));
+        assert_eq!(s.next_due_at(2), Some(3 * month));
+        assert_eq!(s.next_due_at(3), None);
+    }
+
+    #[test]
+    fn trigger_brings_the_stream_forward() {
+        let month = 30 * DAY_SECS;
+        let s = PayoutSchedule::for_plan("Monthly", 0, Some(10), Some(3)).unwrap();
+        assert_eq!(s.first_due_at, 10);
+        assert_eq!(s.installments_due(10), 1);
+        assert_eq!(s.next_due_at(1), Some(10 + month));
+
+        // A trigger after the first due date changes nothing.
+        let late = PayoutSchedule::for_plan("Monthly", 0, Some(2 * month), Some(3)).unwrap();
+        assert_eq!(late.first_due_at, month);
+    }
+}
@@ -0,0 +1,965 @@
+//! # PDF Typesetter
+//!
+//! Lays out structured document blocks on US Letter pages and serialises them
+//! as PDF. Text is set in DejaVu Serif, embedded as a subset of the glyphs the
+//! document uses, so paragraphs wrap to the measure using the font's own
+//! advance widths and long documents paginate. Every page carries a footer
+//! with caller-supplied lines, a QR code and a "Page X of Y" counter.
+//!
+//! Output is byte-for-byte deterministic for the same blocks, footer and
+//! metadata: no clock reads, random IDs or compression are involved.
+//!
+//! The faces are embedded as Type0 fonts with Identity-H encoding and a
+//! ToUnicode map, so any character the font covers can be set and extracted
+//! again, and everything is painted in a calibrated grey. The document
+//! carries PDF/A-2B identification in its XMP metadata packet, mirrored in
+//! the Info dictionary. Characters the font has no glyph for are set as the
+//! replacement character U+FFFD rather than `.notdef`, which PDF/A forbids, so
+//! such text stays visible as a gap in coverage instead of failing the
+//! document.
+
+use crate::api_error::ApiError;
+use crate::truetype::TrueTypeFont;
+use chrono::{DateTime, Utc};
+use qrcode::{Color, EcLevel, QrCode};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::OnceLock;
+
+// ─── Page Geometry ────────────────────────────────────────────────────────────
+
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN_X: f32 = 72.0;
+const MARGIN_TOP: f32 = 72.0;
+/// Lowest baseline for body text; everything below belongs to the footer.
+const CONTENT_BOTTOM: f32 = 112.0;
+const CONTENT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN_X;
+
+const FIELD_INDENT: f32 = 18.0;
+const FIELD_FONT_SIZE: f32 = 10.0;
+const SIGNATURE_LINE_WIDTH: f32 = 220.0;
+const QR_SIZE: f32 = 60.0;
+const FOOTER_FONT_SIZE: f32 = 7.5;
+
+// ─── Blocks ───────────────────────────────────────────────────────────────────
+
+/// A unit of document content. Templates build a `Vec<Block>`; the same
+/// blocks drive both the PDF layout and the canonical plain-text rendering.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    /// Centred bold document title.
+    Title(String),
+    /// Centred small line under the title (dates, version).
+    Subtitle(String),
+    /// Bold section heading.
+    Heading(String),
+    /// Bold line introducing a group of fields, e.g. a numbered beneficiary.
+    Subheading(String),
+    /// Body paragraph, wrapped to the text measure. `\n` forces a line break.
+    Paragraph(String),
+    /// Indented `label: value` line with a hanging indent for the value.
+    Field { label: String, value: String },
+    /// Signature line with the signatory's role, name and identifying details.
+    /// Never split across pages.
+    Signature {
+        role: String,
+        name: String,
+        details: Vec<String>,
+    },
+}
+
+/// Canonical plain-text rendering of `blocks`. This is what document hashes
+/// are computed over, so it must only change when the content changes.
+pub fn plain_text(blocks: &[Block]) -> String {
+    let mut out = String::new();
+    for block in blocks {
+        match block {
+            Block::Title(t) => {
+                out.push_str(t);
+                out.push('\n');
+                out.push_str(&"=".repeat(t.chars().count()));
+                out.push('\n');
+            }
+            Block::Subtitle(t) | Block::Subheading(t) | Block::Paragraph(t) => {
+                out.push_str(t);
+                out.push('\n');
+            }
+            Block::Heading(h) => {
+                out.push('\n');
+                out.push_str(h);
+                out.push('\n');
+                out.push_str(&"-".repeat(h.chars().count()));
+                out.push('\n');
+            }
+            Block::Field { label, value } => {
+                out.push_str(&format!("   {label}: {value}\n"));
+            }
+            Block::Signature {
+                role,
+                name,
+                details,
+            } => {
+                out.push_str(&format!(
+                    "\n______________________________\n{role}: {name}\n"
+                ));
+                for detail in details {
+                    out.push_str(&format!("   {detail}\n"));
+                }
+                out.push_str("   Date: ______________\n");
+            }
+        }
+    }
+    out
+}
+
+// ─── Document Metadata ────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct PdfMetadata {
+    pub title: String,
+    pub author: String,
+    pub subject: String,
+    pub keywords: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    /// Stable identifier written to the trailer `/ID` and XMP `DocumentID`.
+    pub document_id: [u8; 16],
+}
+
+/// Content repeated at the bottom of every page.
+#[derive(Debug, Clone)]
+pub struct PageFooter {
+    pub lines: Vec<String>,
+    pub qr_payload: String,
+}
+
+// ─── Fonts ────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+
+    /// PostScript name of the face; the embedded subset adds a tag prefix.
+    fn postscript_name(self) -> &'static str {
+        match self {
+            Font::Regular => "DejaVuSerif",
+            Font::Bold => "DejaVuSerif-Bold",
+        }
+    }
+
+    /// Dominant vertical stem width for the font descriptor.
+    fn stem_v(self) -> u16 {
+        match self {
+            Font::Regular => 80,
+            Font::Bold => 140,
+        }
+    }
+
+    fn face(self) -> &'static TrueTypeFont<'static> {
+        static REGULAR: OnceLock<TrueTypeFont<'static>> = OnceLock::new();
+        static BOLD: OnceLock<TrueTypeFont<'static>> = OnceLock::new();
+        let (cell, data): (_, &'static [u8]) = match self {
+            Font::Regular => (&REGULAR, include_bytes!("../assets/fonts/DejaVuSerif.ttf")),
+            Font::Bold => (
+                &BOLD,
+                include_bytes!("../assets/fonts/DejaVuSerif-Bold.ttf"),
+            ),
+        };
+        cell.get_or_init(|| TrueTypeFont::parse(data).expect("bundled font is valid TrueType"))
+    }
+}
+
+/// Shown in place of characters the face has no glyph for.
+const REPLACEMENT_CHAR: char = '\u{FFFD}';
+
+/// Glyph that sets `c` in `face`, with the character it shows: `c` itself,
+/// or the replacement character when the face does not cover `c`.
+fn glyph_for(face: &TrueTypeFont<'_>, c: char) -> (u16, char) {
+    [c, REPLACEMENT_CHAR, '?']
+        .into_iter()
+        .find_map(|shown| face.glyph_id(shown).map(|glyph| (glyph, shown)))
+        .unwrap_or((0, c))
+}
+
+fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    let face = font.face();
+    let units: u32 = text
+        .chars()
+        .map(|c| face.advance_width(glyph_for(face, c).0) as u32)
+        .sum();
+    units as f32 * size / face.units_per_em() as f32
+}
+
+/// Glyphs each face has set so far, with the character each one shows.
+/// Drives the embedded subsets, their widths and the ToUnicode maps.
+#[derive(Default)]
+struct GlyphUsage {
+    regular: BTreeMap<u16, char>,
+    bold: BTreeMap<u16, char>,
+}
+
+impl GlyphUsage {
+    fn used(&self, font: Font) -> &BTreeMap<u16, char> {
+        match font {
+            Font::Regular => &self.regular,
+            Font::Bold => &self.bold,
+        }
+    }
+
+    /// Identity-H string operand for `text`: two-byte glyph ids in hex.
+    fn encode(&mut self, font: Font, text: &str) -> String {
+        let face = font.face();
+        let used = match font {
+            Font::Regular => &mut self.regular,
+            Font::Bold => &mut self.bold,
+        };
+        text.chars()
+            .map(|c| glyph_for(face, c))
+            .map(|(glyph, c)| {
+                used.entry(glyph).or_insert(c);
+                format!("{glyph:04X}")
+            })
+            .collect()
+    }
+}
+
+/// Greedy word wrap to `max_width`. Explicit newlines are honoured and words
+/// longer than a full line (addresses, hashes) are broken by character.
+fn wrap_text(text: &str, font: Font, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for hard_line in text.split('\n') {
+        let mut current = String::new();
+        for word in hard_line.split_whitespace() {
+            let candidate = if current.is_empty() {
+                word.to_string()
+            } else {
+                format!("{current} {word}")
+            };
+            if text_width(&candidate, font, size) <= max_width {
+                current = candidate;
+                continue;
+            }
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            for c in word.chars() {
+                current.push(c);
+                if text_width(&current, font, size) > max_width {
+                    current.pop();
+                    lines.push(std::mem::replace(&mut current, c.to_string()));
+                }
+            }
+        }
+        lines.push(current);
+    }
+    lines
+}
+
+// ─── Layout ───────────────────────────────────────────────────────────────────
+
+/// Content stream operators for one page, before the footer is added.
+struct PageLayout {
+    ops: String,
+}
+
+struct Typesetter {
+    pages: Vec<PageLayout>,
+    ops: String,
+    glyphs: GlyphUsage,
+    /// Baseline of the next line of text.
+    y: f32,
+}
+
+impl Typesetter {
+    fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            ops: String::new(),
+            glyphs: GlyphUsage::default(),
+            y: PAGE_HEIGHT - MARGIN_TOP,
+        }
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(PageLayout {
+            ops: std::mem::take(&mut self.ops),
+        });
+        self.y = PAGE_HEIGHT - MARGIN_TOP;
+    }
+
+    /// Start a new page unless `height` points fit above the footer.
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < CONTENT_BOTTOM && !self.ops.is_empty() {
+            self.new_page();
+        }
+    }
+
+    fn text_at(&mut self, x: f32, text: &str, font: Font, size: f32) {
+        let op = text_op(&mut self.glyphs, x, self.y, text, font, size);
+        self.ops.push_str(&op);
+    }
+
+    fn line(&mut self, x: f32, size: f32, leading: f32, text: &str, font: Font) {
+        self.ensure_space(leading);
+        self.text_at(x, text, font, size);
+        self.y -= leading;
+    }
+
+    fn centered(&mut self, text: &str, font: Font, size: f32, leading: f32) {
+        for line in wrap_text(text, font, size, CONTENT_WIDTH) {
+            let x = MARGIN_X + (CONTENT_WIDTH - text_width(&line, font, size)) / 2.0;
+            self.line(x, size, leading, &line, font);
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        match block {
+            Block::Title(title) => {
+                self.centered(title, Font::Bold, 16.0, 20.0);
+                self.y -= 4.0;
+            }
+            Block::Subtitle(text) => {
+                self.centered(text, Font::Regular, 9.0, 12.0);
+            }
+            Block::Heading(heading) => {
+                // Keep the heading with at least two lines of what follows.
+                self.y -= 10.0;
+                self.ensure_space(16.0 + 2.0 * 14.0);
+                self.line(MARGIN_X, 12.0, 16.0, heading, Font::Bold);
+            }
+            Block::Subheading(text) => {
+                self.y -= 4.0;
+                self.ensure_space(14.0 + 14.0);
+                for line in wrap_text(text, Font::Bold, 11.0, CONTENT_WIDTH) {
+                    self.line(MARGIN_X, 11.0, 14.0, &line, Font::Bold);
+                }
+            }
+            Block::Paragraph(text) => {
+                for line in wrap_text(text, Font::Regular, 11.0, CONTENT_WIDTH) {
+                    self.line(MARGIN_X, 11.0, 14.0, &line, Font::Regular);
+                }
+                self.y -= 6.0;
+            }
+            Block::Field { label, value } => {
+                // Set a point smaller than body text so a full Stellar
+                // address fits beside its label without breaking.
+                let label = format!("{label}:");
+                let value_x =
+                    MARGIN_X + FIELD_INDENT + text_width(&label, Font::Bold, FIELD_FONT_SIZE) + 4.0;
+                let width = MARGIN_X + CONTENT_WIDTH - value_x;
+                let lines = wrap_text(value, Font::Regular, FIELD_FONT_SIZE, width);
+                self.ensure_space(13.0);
+                self.text_at(MARGIN_X + FIELD_INDENT, &label, Font::Bold, FIELD_FONT_SIZE);
+                for line in lines {
+                    self.line(value_x, FIELD_FONT_SIZE, 13.0, &line, Font::Regular);
+                }
+            }
+            Block::Signature {
+                role,
+                name,
+                details,
+            } => {
+                let height = 36.0 + 14.0 + 12.0 * (details.len() as f32 + 1.0);
+                self.ensure_space(height);
+                self.y -= 30.0;
+                self.ops.push_str(&format!(
+                    "0.6 w {x:.2} {y:.2} m {x2:.2} {y:.2} l S\n",
+                    x = MARGIN_X,
+                    x2 = MARGIN_X + SIGNATURE_LINE_WIDTH,
+                    y = self.y + 10.0,
+                ));
+                self.line(MARGIN_X, 10.0, 14.0, &format!("{role}: {name}"), Font::Bold);
+                for detail in details {
+                    self.line(MARGIN_X, 9.0, 12.0, detail, Font::Regular);
+                }
+                self.line(MARGIN_X, 9.0, 12.0, "Date: ______________", Font::Regular);
+            }
+        }
+    }
+
+    fn finish(mut self) -> (Vec<PageLayout>, GlyphUsage) {
+        if !self.ops.is_empty() || self.pages.is_empty() {
+            self.new_page();
+        }
+        (self.pages, self.glyphs)
+    }
+}
+
+fn text_op(glyphs: &mut GlyphUsage, x: f32, y: f32, text: &str, font: Font, size: f32) -> String {
+    let hex = glyphs.encode(font, text);
+    format!(
+        "BT /{} {size:.1} Tf {x:.2} {y:.2} Td <{hex}> Tj ET\n",
+        font.resource()
+    )
+}
+
+/// Footer operators: caller lines on the left, QR code on the right and the
+/// page counter centred underneath.
+fn footer_ops(
+    glyphs: &mut GlyphUsage,
+    footer: &PageFooter,
+    qr: &QrCode,
+    page: usize,
+    total: usize,
+) -> String {
+    let mut ops = String::new();
+    let rule_y = CONTENT_BOTTOM - 16.0;
+    ops.push_str(&format!(
+        "0.4 w {:.2} {rule_y:.2} m {:.2} {rule_y:.2} l S\n",
+        MARGIN_X,
+        PAGE_WIDTH - MARGIN_X
+    ));
+
+    let text_width_limit = CONTENT_WIDTH - QR_SIZE - 12.0;
+    let mut y = rule_y - 14.0;
+    for line in &footer.lines {
+        for wrapped in wrap_text(line, Font::Regular, FOOTER_FONT_SIZE, text_width_limit) {
+            ops.push_str(&text_op(
+                glyphs,
+                MARGIN_X,
+                y,
+                &wrapped,
+                Font::Regular,
+                FOOTER_FONT_SIZE,
+            ));
+            y -= FOOTER_FONT_SIZE + 2.0;
+        }
+    }
+
+    let counter = format!("Page {page} of {total}");
+    let x = MARGIN_X + (CONTENT_WIDTH - text_width(&counter, Font::Regular, 9.0)) / 2.0;
+    ops.push_str(&text_op(glyphs, x, 36.0, &counter, Font::Regular, 9.0));
+
+    // QR modules as filled squares, top-left module first.
+    let width = qr.width();
+    let module = QR_SIZE / width as f32;
+    let origin_x = PAGE_WIDTH - MARGIN_X - QR_SIZE;
+    let top = rule_y - 6.0;
+    for (i, color) in qr.to_colors().iter().enumerate() {
+        if *color == Color::Dark {
+            let col = (i % width) as f32;
+            let row = (i / width) as f32;
+            ops.push_str(&format!(
+                "{:.3} {:.3} {module:.3} {module:.3} re\n",
+                origin_x + col * module,
+                top - (row + 1.0) * module,
+            ));
+        }
+    }
+    ops.push_str("f\n");
+    ops
+}
+
+// ─── Serialisation ────────────────────────────────────────────────────────────
+
+/// Render `blocks` into a complete PDF document.
+pub fn render_pdf(
+    blocks: &[Block],
+    footer: &PageFooter,
+    metadata: &PdfMetadata,
+) -> Result<Vec<u8>, ApiError> {
+    let qr = QrCode::with_error_correction_level(footer.qr_payload.as_bytes(), EcLevel::M)
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("QR code encoding failed: {e}")))?;
+
+    let mut typesetter = Typesetter::new();
+    for block in blocks {
+        typesetter.block(block);
+    }
+    let (pages, mut glyphs) = typesetter.finish();
+    let total = pages.len();
+
+    let mut writer = PdfWriter::new();
+    // Fixed objects: 1 catalog, 2 page tree, 3 XMP metadata, 4 info, then
+    // five objects per embedded face (see `write_font`). Each page then takes
+    // a page object and a content stream.
+    let page_ids: Vec<usize> = (0..total).map(|i| 15 + 2 * i).collect();
+
+    writer.object(
+        1,
+        b"<< /Type /Catalog /Pages 2 0 R /Metadata 3 0 R /Lang (en) >>".to_vec(),
+    );
+    let kids: Vec<String> = page_ids.iter().map(|id| format!("{id} 0 R")).collect();
+    writer.object(
+        2,
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {total} >>",
+            kids.join(" ")
+        )
+        .into_bytes(),
+    );
+    writer.stream(
+        3,
+        "/Type /Metadata /Subtype /XML",
+        xmp_packet(metadata).as_bytes(),
+    );
+    writer.object(4, info_dictionary(metadata).into_bytes());
+
+    for (index, page) in pages.iter().enumerate() {
+        let page_id = page_ids[index];
+        let content_id = page_id + 1;
+        writer.object(
+            page_id,
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                 /Contents {content_id} 0 R \
+                 /Resources << /Font << /F1 5 0 R /F2 10 0 R >> \
+                 /ColorSpace << /CS0 {CALIBRATED_GRAY} >> >> >>"
+            )
+            .into_bytes(),
+        );
+        // Paint in the calibrated grey rather than the implicit DeviceGray.
+        let mut content = String::from("/CS0 cs 0 sc /CS0 CS 0 SC\n");
+        content.push_str(&page.ops);
+        content.push_str(&footer_ops(&mut glyphs, footer, &qr, index + 1, total));
+        writer.stream(content_id, "", content.as_bytes());
+    }
+    // Fonts go last so the subsets cover the footers too.
+    write_font(&mut writer, 5, Font::Regular, glyphs.used(Font::Regular));
+    write_font(&mut writer, 10, Font::Bold, glyphs.used(Font::Bold));
+
+    let id_hex = hex::encode_upper(metadata.document_id);
+    Ok(writer.finish(&format!(
+        "/Root 1 0 R /Info 4 0 R /ID [<{id_hex}> <{id_hex}>]"
+    )))
+}
+
+/// Device-independent grey with a D65 white point, so no output intent is
+/// needed for PDF/A.
+const CALIBRATED_GRAY: &str = "[/CalGray << /WhitePoint [0.9505 1 1.089] >>]";
+
+/// Write `font` as objects `first..first + 5`: the Type0 font, its
+/// CIDFontType2 descendant, the font descriptor, the embedded subset and the
+/// ToUnicode map. Glyph ids are used as CIDs, hence Identity-H and an
+/// identity CID to GID map.
+fn write_font(writer: &mut PdfWriter, first: usize, font: Font, used: &BTreeMap<u16, char>) {
+    let face = font.face();
+    let scale = |units: i32| units * 1000 / face.units_per_em() as i32;
+
+    // Subset tag: six capitals derived from the glyph set, as PDF requires.
+    let mut hasher = Sha256::new();
+    for glyph in used.keys() {
+        hasher.update(glyph.to_be_bytes());
+    }
+    let tag: String = hasher.finalize()[..6]
+        .iter()
+        .map(|b| (b'A' + b % 26) as char)
+        .collect();
+    let name = format!("{tag}+{}", font.postscript_name());
+
+    writer.object(
+        first,
+        format!(
+            "<< /Type /Font /Subtype /Type0 /BaseFont /{name} /Encoding /Identity-H \
+             /DescendantFonts [{} 0 R] /ToUnicode {} 0 R >>",
+            first + 1,
+            first + 4
+        )
+        .into_bytes(),
+    );
+
+    let widths: Vec<String> = used
+        .keys()
+        .map(|&glyph| format!("{glyph} [{}]", scale(face.advance_width(glyph) as i32)))
+        .collect();
+    writer.object(
+        first + 1,
+        format!(
+            "<< /Type /Font /Subtype /CIDFontType2 /BaseFont /{name} \
+             /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> \
+             /FontDescriptor {} 0 R /DW 1000 /W [{}] /CIDToGIDMap /Identity >>",
+            first + 2,
+            widths.join(" ")
+        )
+        .into_bytes(),
+    );
+
+    let m = face.metrics();
+    let bbox = m.bbox.map(|v| scale(v as i32));
+    writer.object(
+        first + 2,
+        format!(
+            "<< /Type /FontDescriptor /FontName /{name} /Flags 6 \
+             /FontBBox [{} {} {} {}] /ItalicAngle {} /Ascent {} /Descent {} \
+             /CapHeight {} /StemV {} /FontFile2 {} 0 R >>",
+            bbox[0],
+            bbox[1],
+            bbox[2],
+            bbox[3],
+            m.italic_angle,
+            scale(m.ascent as i32),
+            scale(m.descent as i32),
+            scale(m.cap_height as i32),
+            font.stem_v(),
+            first + 3
+        )
+        .into_bytes(),
+    );
+
+    let glyphs: BTreeSet<u16> = used.keys().copied().collect();
+    let subset = face.subset(&glyphs);
+    writer.stream(first + 3, &format!("/Length1 {}", subset.len()), &subset);
+    writer.stream(first + 4, "", to_unicode_cmap(used).as_bytes());
+}
+
+/// ToUnicode CMap mapping each two-byte glyph id back to its character.
+fn to_unicode_cmap(used: &BTreeMap<u16, char>) -> String {
+    let mut cmap = String::from(
+        "/CIDInit /ProcSet findresource begin\n\
+         12 dict begin\n\
+         begincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n\
+         /CMapType 2 def\n\
+         1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n",
+    );
+    let entries: Vec<(&u16, &char)> = used.iter().collect();
+    // A bfchar section may hold at most 100 mappings.
+    for chunk in entries.chunks(100) {
+        cmap.push_str(&format!("{} beginbfchar\n", chunk.len()));
+        for (glyph, c) in chunk {
+            let mut units = [0u16; 2];
+            let hex: String = c
+                .encode_utf16(&mut units)
+                .iter()
+                .map(|u| format!("{u:04X}"))
+                .collect();
+            cmap.push_str(&format!("<{glyph:04X}> <{hex}>\n"));
+        }
+        cmap.push_str("endbfchar\n");
+    }
+    cmap.push_str(
+        "endcmap\n\
+         CMapName currentdict /CMap defineresource pop\n\
+         end\n\
+         end",
+    );
+    cmap
+}
+
+/// Minimal PDF object writer that tracks byte offsets for the xref table.
+struct PdfWriter {
+    buf: Vec<u8>,
+    offsets: Vec<(usize, usize)>,
+}
+
+impl PdfWriter {
+    fn new() -> Self {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"%PDF-1.7\n");
+        // Binary marker comment so transfer tools treat the file as binary (PDF/A 6.1.2).
+        buf.extend_from_slice(b"%\xE2\xE3\xCF\xD3\n");
+        Self {
+            buf,
+            offsets: Vec::new(),
+        }
+    }
+
+    fn object(&mut self, id: usize, body: Vec<u8>) {
+        self.offsets.push((id, self.buf.len()));
+        self.buf
+            .extend_from_slice(format!("{id} 0 obj\n").as_bytes());
+        self.buf.extend_from_slice(&body);
+        self.buf.extend_from_slice(b"\nendobj\n");
+    }
+
+    fn stream(&mut self, id: usize, dict_entries: &str, data: &[u8]) {
+        let separator = if dict_entries.is_empty() { "" } else { " " };
+        let mut body = format!(
+            "<< {dict_entries}{separator}/Length {} >>\nstream\n",
+            data.len()
+        )
+        .into_bytes();
+        body.extend_from_slice(data);
+        body.extend_from_slice(b"\nendstream");
+        self.object(id, body);
+    }
+
+    fn finish(mut self, trailer_entries: &str) -> Vec<u8> {
+        self.offsets.sort_by_key(|(id, _)| *id);
+        let size = self.offsets.len() + 1;
+        let xref_offset = self.buf.len();
+        let mut xref = format!("xref\n0 {size}\n0000000000 65535 f \n");
+        for (_, offset) in &self.offsets {
+            xref.push_str(&format!("{offset:010} 00000 n \n"));
+        }
+        self.buf.extend_from_slice(xref.as_bytes());
+        self.buf.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {size} {trailer_entries} >>\nstartxref\n{xref_offset}\n%%EOF\n"
+            )
+            .as_bytes(),
+        );
+        self.buf
+    }
+}
+
+/// PDF text string as UTF-16BE hex, which round-trips any Unicode metadata.
+fn pdf_text_string(s: &str) -> String {
+    let mut hex = String::from("<FEFF");
+    for unit in s.encode_utf16() {
+        hex.push_str(&format!("{unit:04X}"));
+    }
+    hex.push('>');
+    hex
+}
+
+fn info_dictionary(metadata: &PdfMetadata) -> String {
+    let date = format!("D:{}Z", metadata.created_at.format("%Y%m%d%H%M%S"));
+    format!(
+        "<< /Title {} /Author {} /Subject {} /Keywords {} /Creator {} /Producer {} \
+         /CreationDate ({date}) /ModDate ({date}) >>",
+        pdf_text_string(&metadata.title),
+        pdf_text_string(&metadata.author),
+        pdf_text_string(&metadata.subject),
+        pdf_text_string(&metadata.keywords.join(", ")),
+        pdf_text_string(PRODUCER),
+        pdf_text_string(PRODUCER),
+    )
+}
+
+const PRODUCER: &str = "InheritX Will Typesetter";
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// XMP packet with Dublin Core, XMP basic, PDF and PDF/A identification
+/// schemas. Values mirror the Info dictionary as PDF/A requires.
+fn xmp_packet(metadata: &PdfMetadata) -> String {
+    let date = metadata.created_at.format("%Y-%m-%dT%H:%M:%SZ");
+    let doc_id = uuid::Uuid::from_bytes(metadata.document_id);
+    format!(
+        r#"<?xpacket begin="" id="W5M0MpCehiHzreSzNTczkc9d"?>
+<x:xmpmeta xmlns:x="adobe:ns:meta/">
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+<rdf:Description rdf:about=""
+ xmlns:dc="http://purl.org/dc/elements/1.1/"
+ xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+ xmlns:pdf="http://ns.adobe.com/pdf/1.3/"
+ xmlns:xmpMM="http://ns.adobe.com/xap/1.0/mm/"
+ xmlns:pdfaid="http://www.aiim.org/pdfa/ns/id/">
+<dc:format>application/pdf</dc:format>
+<dc:title><rdf:Alt><rdf:li xml:lang="x-default">{title}</rdf:li></rdf:Alt></dc:title>
+<dc:creator><rdf:Seq><rdf:li>{author}</rdf:li></rdf:Seq></dc:creator>
+<dc:description><rdf:Alt><rdf:li xml:lang="x-default">{subject}</rdf:li></rdf:Alt></dc:description>
+<xmp:CreateDate>{date}</xmp:CreateDate>
+<xmp:ModifyDate>{date}</xmp:ModifyDate>
+<xmp:CreatorTool>{producer}</xmp:CreatorTool>
+<pdf:Producer>{producer}</pdf:Producer>
+<pdf:Keywords>{keywords}</pdf:Keywords>
+<xmpMM:DocumentID>uuid:{doc_id}</xmpMM:DocumentID>
+<pdfaid:part>2</pdfaid:part>
+<pdfaid:conformance>B</pdfaid:conformance>
+</rdf:Description>
+</rdf:RDF>
+</x:xmpmeta>
+<?xpacket end="w"?>"#,
+        title = xml_escape(&metadata.title),
+        author = xml_escape(&metadata.author),
+        subject = xml_escape(&metadata.subject),
+        keywords = xml_escape(&metadata.keywords.join(", ")),
+        producer = PRODUCER,
+    )
+}
+
+// ─── Unit Tests ───────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn metadata() -> PdfMetadata {
+        PdfMetadata {
+            title: "Last Will & Testament".to_string(),
+            author: "Alice Testator".to_string(),
+            subject: "Will for vault-001".to_string(),
+            keywords: vec!["will".to_string(), "vault-001".to_string()],
+            created_at: Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap(),
+            document_id: [7u8; 16],
+        }
+    }
+
+    fn footer() -> PageFooter {
+        PageFooter {
+            lines: vec!["Will hash: abc123".to_string()],
+            qr_payload: "inheritx-will:v1;hash=abc123;vault=vault-001".to_string(),
+        }
+    }
+
+    fn count(haystack: &[u8], needle: &[u8]) -> usize {
+        haystack
+            .windows(needle.len())
+            .filter(|w| *w == needle)
+            .count()
+    }
+
+    #[test]
+    fn test_wrap_respects_measure() {
+        let text = "I hereby revoke all former wills and codicils made by me and declare this \
+                    to be my last will and testament in respect of all my digital assets.";
+        let lines = wrap_text(text, Font::Regular, 11.0, 200.0);
+        assert!(lines.len() > 1);
+        for line in &lines {
+            assert!(text_width(line, Font::Regular, 11.0) <= 200.0);
+        }
+        assert_eq!(
+            lines.join(" "),
+            text.split_whitespace().collect::<Vec<_>>().join(" ")
+        );
+    }
+
+    #[test]
+    fn test_wrap_breaks_overlong_words() {
+        let address = "G".repeat(120);
+        let lines = wrap_text(&address, Font::Regular, 11.0, 100.0);
+        assert!(lines.len() > 1);
+        assert_eq!(lines.concat(), address);
+    }
+
+    #[test]
+    fn test_bold_is_wider_than_regular() {
+        let text = "LAST WILL AND TESTAMENT";
+        assert!(text_width(text, Font::Bold, 12.0) > text_width(text, Font::Regular, 12.0));
+    }
+
+    #[test]
+    fn test_text_beyond_win_ansi_is_embedded() {
+        let blocks = vec![
+            Block::Field {
+                label: "Name".to_string(),
+                value: "Zo\u{00EB} \u{0141}ukasz".to_string(),
+            },
+            Block::Paragraph("\u{0416}\u{0435}\u{043D}\u{044F} \u{03A9}".to_string()),
+        ];
+        let pdf = render_pdf(&blocks, &footer(), &metadata()).unwrap();
+        let face = Font::Regular.face();
+        let l_stroke = face.glyph_id('\u{0141}').unwrap();
+        assert_eq!(
+            count(&pdf, format!("<{l_stroke:04X}> <0141>").as_bytes()),
+            1
+        );
+        assert_eq!(count(&pdf, b"/Encoding /Identity-H"), 2);
+        assert_eq!(count(&pdf, b"/FontFile2 "), 2);
+    }
+
+    #[test]
+    fn test_text_without_a_glyph_is_replaced() {
+        let face = Font::Regular.face();
+        let replacement = face.glyph_id(REPLACEMENT_CHAR).unwrap();
+        assert_eq!(glyph_for(face, '\u{674E}'), (replacement, REPLACEMENT_CHAR));
+        assert_eq!(
+            text_width("\u{674E}", Font::Regular, 12.0),
+            text_width("\u{FFFD}", Font::Regular, 12.0)
+        );
+
+        let blocks = vec![Block::Field {
+            label: "Name".to_string(),
+            value: "\u{674E}\u{96F7}".to_string(),
+        }];
+        let mut footer = footer();
+        footer.lines.push("Witness: \u{674E}".to_string());
+        let pdf = render_pdf(&blocks, &footer, &metadata()).unwrap();
+        assert_eq!(
+            count(&pdf, format!("<{replacement:04X}> <FFFD>").as_bytes()),
+            1
+        );
+    }
+
+    #[test]
+    fn test_render_is_deterministic_and_well_formed() {
+        let blocks = vec![
+            Block::Title("LAST WILL AND TESTAMENT".to_string()),
+            Block::Heading("BENEFICIARIES".to_string()),
+            Block::Field {
+                label: "Name".to_string(),
+                value: "Bob".to_string(),
+            },
+        ];
+        let a = render_pdf(&blocks, &footer(), &metadata()).unwrap();
+        let b = render_pdf(&blocks, &footer(), &metadata()).unwrap();
+        assert_eq!(a, b);
+        assert!(a.starts_with(b"%PDF-1.7"));
+        assert!(a.ends_with(b"%%EOF\n"));
+        assert_eq!(count(&a, b"/Type /Page "), 1);
+        assert_eq!(count(&a, b"<pdfaid:part>2</pdfaid:part>"), 1);
+        assert_eq!(count(&a, b"Last Will &amp; Testament"), 1);
+    }
+
+    #[test]
+    fn test_long_documents_paginate_with_page_numbers() {
+        let mut blocks = vec![Block::Title("LONG WILL".to_string())];
+        for i in 0..120 {
+            blocks.push(Block::Paragraph(format!(
+                "Clause {i}: the residue of my estate."
+            )));
+        }
+        let pdf = render_pdf(&blocks, &footer(), &metadata()).unwrap();
+        let pages = count(&pdf, b"/Type /Page ");
+        assert!(pages > 1);
+        let last = format!("Page {pages} of {pages}");
+        let last_hex = GlyphUsage::default().encode(Font::Regular, &last);
+        assert_eq!(count(&pdf, last_hex.as_bytes()), 1);
+    }
+
+    #[test]
+    fn test_xref_offsets_point_at_objects() {
+        let pdf = render_pdf(
+            &[Block::Paragraph("Hello".to_string())],
+            &footer(),
+            &metadata(),
+        )
+        .unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        let xref_start: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|s| s.lines().next())
+            .and_then(|s| s.parse().ok())
+            .unwrap();
+        let xref = &text[xref_start..];
+        for (n, entry) in xref
+            .lines()
+            .skip(3)
+            .take_while(|l| l.ends_with(" n "))
+            .enumerate()
+        {
+            let offset: usize = entry[..10].parse().unwrap();
+            let expected = format!("{} 0 obj", n + 1);
+            assert!(pdf[offset..].starts_with(expected.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_plain_text_contains_all_blocks() {
+        let text = plain_text(&[
+            Block::Title("WILL".to_string()),
+            Block::Field {
+                label: "Wallet".to_string(),
+                value: "GABC".to_string(),
+            },
+            Block::Signature {
+                role: "Testator".to_string(),
+                name: "Alice".to_string(),
+                details: vec!["Wallet: GABC".to_string()],
+            },
+        ]);
+        assert!(text.starts_with("WILL\n====\n"));
+        assert!(text.contains("   Wallet: GABC\n"));
+        assert!(text.contains("Testator: Alice\n"));
+    }
+}
@@ -0,0 +1,390 @@
+use crate::api_error::ApiError;
+use crate::contract_invoker::{ContractInvoker, DEFAULT_NETWORK_PASSPHRASE};
+use crate::webhook::backoff_delay;
+use axum::async_trait;
+use chrono::Utc;
+use rand::Rng;
+use serde::Serialize;
+use soroban_sdk::xdr::{ScString, ScVal};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Submissions of one pause or unpause before it is marked `failed` and left
+/// for an operator.
+pub const MAX_FREEZE_ATTEMPTS: i32 = 8;
+
+const RETRY_INTERVAL_SECS: u64 = 60;
+const RETRY_BATCH_SIZE: i64 = 50;
+
+/// Mirrors admin plan pauses onto the inheritance contract's
+/// `freeze_plan` / `unfreeze_plan` entry points, so a paused plan cannot be
+/// withdrawn from or claimed on chain either.
+#[async_trait]
+pub trait OnChainPlanFreezer: Send + Sync {
+    /// Freeze `contract_plan_id` on chain. `expires_at` is a unix timestamp,
+    /// or `None` for a freeze without expiry.
+    async fn freeze_plan(
+        &self,
+        contract_plan_id: u64,
+        reason: &str,
+        expires_at: Option<u64>,
+    ) -> Result<(), ApiError>;
+
+    /// Lift the on-chain freeze on `contract_plan_id`.
+    async fn unfreeze_plan(&self, contract_plan_id: u64) -> Result<(), ApiError>;
+}
+
+pub struct PlanFreezerConfig {
+    pub rpc_url: String,
+    pub contract_id: String,
+    /// Stellar secret seed (`S...`) of an account holding the contract's
+    /// Guardian role.
+    pub signer_secret: String,
+    pub network_passphrase: String,
+}
+
+impl PlanFreezerConfig {
+    /// Reads the freezer configuration from the environment. Returns `None`
+    /// when `SOROBAN_RPC_URL`, `INHERITANCE_CONTRACT_ID` or
+    /// `PLAN_FREEZER_SECRET_KEY` is unset.
+    pub fn from_env() -> Option<Self> {
+        let non_empty = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+        Some(Self {
+            rpc_url: non_empty("SOROBAN_RPC_URL")?,
+            contract_id: non_empty("INHERITANCE_CONTRACT_ID")?,
+            signer_secret: non_empty("PLAN_FREEZER_SECRET_KEY")?,
+            network_passphrase: non_empty("SOROBAN_NETWORK_PASSPHRASE")
+                .unwrap_or_else(|| DEFAULT_NETWORK_PASSPHRASE.to_string()),
+        })
+    }
+}
+
+/// Calls `freeze_plan` / `unfreeze_plan` on the inheritance contract as the
+/// configured Guardian account.
+pub struct SorobanPlanFreezer {
+    invoker: ContractInvoker,
+}
+
+impl SorobanPlanFreezer {
+    pub fn new(config: &PlanFreezerConfig) -> Result<Self, ApiError> {
+        Ok(Self {
+            invoker: ContractInvoker::new(
+                &config.rpc_url,
+                &config.contract_id,
+                &config.signer_secret,
+                &config.network_passphrase,
+            )?,
+        })
+    }
+
+    /// Arguments of `freeze_plan(caller, plan_id, reason, expires_at)`.
+    fn freeze_args(
+        &self,
+        contract_plan_id: u64,
+        reason: &str,
+        expires_at: Option<u64>,
+    ) -> Result<Vec<ScVal>, ApiError> {
+        let reason = ScString(
+            reason
+                .try_into()
+                .map_err(|e| ApiError::BadRequest(format!("Freeze reason is too long: {e}")))?,
+        );
+        Ok(vec![
+            ScVal::Address(self.invoker.account_address()),
+            ScVal::U64(contract_plan_id),
+            ScVal::String(reason),
+            ScVal::U64(expires_at.unwrap_or(0)),
+        ])
+    }
+}
+
+#[async_trait]
+impl OnChainPlanFreezer for SorobanPlanFreezer {
+    async fn freeze_plan(
+        &self,
+        contract_plan_id: u64,
+        reason: &str,
+        expires_at: Option<u64>,
+    ) -> Result<(), ApiError> {
+        let args = self.freeze_args(contract_plan_id, reason, expires_at)?;
+        let hash = self.invoker.invoke("freeze_plan", args).await?;
+        tracing::info!(contract_plan_id, %hash, "Plan frozen on chain");
+        Ok(())
+    }
+
+    async fn unfreeze_plan(&self, contract_plan_id: u64) -> Result<(), ApiError> {
+        let args = vec![
+            ScVal::Address(self.invoker.account_address()),
+            ScVal::U64(contract_plan_id),
+        ];
+        let hash = self.invoker.invoke("unfreeze_plan", args).await?;
+        tracing::info!(contract_plan_id, %hash, "Plan unfrozen on chain");
+        Ok(())
+    }
+}
+
+/// Where a plan's on-chain freeze stands relative to its DB pause
+/// (`plans.chain_freeze_status`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainFreezeStatus {
+    /// The plan was paused; `freeze_plan` has not landed yet.
+    PendingFreeze,
+    /// The plan was unpaused; `unfreeze_plan` has not landed yet.
+    PendingUnfreeze,
+    /// The contract matches the DB pause.
+    Synced,
+    /// `MAX_FREEZE_ATTEMPTS` submissions failed; no further retries.
+    Failed,
+}
+
+impl ChainFreezeStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PendingFreeze => "pending_freeze",
+            Self::PendingUnfreeze => "pending_unfreeze",
+            Self::Synced => "synced",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending_freeze" => Some(Self::PendingFreeze),
+            "pending_unfreeze" => Some(Self::PendingUnfreeze),
+            "synced" => Some(Self::Synced),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+
+    pub fn is_pending(self) -> bool {
+        matches!(self, Self::PendingFreeze | Self::PendingUnfreeze)
+    }
+}
+
+/// Submit the pending on-chain freeze or unfreeze of `plan_id` and record the
+/// outcome: `synced` on success, otherwise a backed-off retry, or `failed`
+/// once `MAX_FREEZE_ATTEMPTS` is reached. The row stays locked for the call,
+/// so a pause and an unpause of the same plan cannot land out of order.
+///
+/// Returns the plan's status afterwards, or `None` for a plan with nothing to
+/// mirror on chain.
+pub async fn sync_plan_freeze(
+    pool: &PgPool,
+    freezer: &dyn OnChainPlanFreezer,
+    plan_id: Uuid,
+) -> Result<Option<ChainFreezeStatus>, ApiError> {
+    let mut tx = pool.begin().await?;
+    let pending: Option<(Option<i64>, String, Option<String>, i32)> = sqlx::query_as(
+        r#"
+        SELECT contract_plan_id, chain_freeze_status, pause_reason, chain_freeze_attempts
+        FROM plans
+        WHERE id = $1 AND chain_freeze_status IN ('pending_freeze', 'pending_unfreeze')
+        FOR UPDATE SKIP LOCKED
+        "#,
+    )
+    .bind(plan_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some((Some(contract_plan_id), status, reason, attempts)) = pending else {
+        // Already synced, never mirrored, or being submitted by another worker.
+        tx.rollback().await?;
+        let status: Option<String> =
+            sqlx::query_scalar("SELECT chain_freeze_status FROM plans WHERE id = $1")
+                .bind(plan_id)
+                .fetch_optional(pool)
+                .await?
+                .flatten();
+        return Ok(status.as_deref().and_then(ChainFreezeStatus::parse));
+    };
+
+    let result = match ChainFreezeStatus::parse(&status) {
+        Some(ChainFreezeStatus::PendingFreeze) => {
+            freezer
+                .freeze_plan(
+                    contract_plan_id as u64,
+                    reason.as_deref().unwrap_or_default(),
+                    None,
+                )
+                .await
+        }
+        _ => freezer.unfreeze_plan(contract_plan_id as u64).await,
+    };
+
+    let attempt = attempts + 1;
+    let (outcome, last_error) = match result {
+        Ok(()) => (ChainFreezeStatus::Synced, None),
+        Err(e) => {
+            warn!(
+                %plan_id,
+                contract_plan_id,
+                attempt,
+                "On-chain {} failed: {}",
+                status,
+                e
+            );
+            let outcome = if attempt >= MAX_FREEZE_ATTEMPTS {
+                ChainFreezeStatus::Failed
+            } else {
+                ChainFreezeStatus::parse(&status).unwrap_or(ChainFreezeStatus::Failed)
+            };
+            (outcome, Some(e.to_string()))
+        }
+    };
+    let next_attempt_at = outcome.is_pending().then(|| {
+        let delay = backoff_delay(attempt as u32, rand::thread_rng().gen::<f64>());
+        Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default()
+    });
+
+    sqlx::query(
+        r#"
+        UPDATE plans
+        SET chain_freeze_status = $2,
+            chain_freeze_attempts = $3,
+            chain_freeze_next_attempt_at = $4,
+            chain_freeze_last_error = $5
+        WHERE id = $1
+        "#,
+    )
+    .bind(plan_id)
+    .bind(outcome.as_str())
+    .bind(attempt)
+    .bind(next_attempt_at)
+    .bind(&last_error)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    if outcome == ChainFreezeStatus::Failed {
+        error!(
+            %plan_id,
+            contract_plan_id,
+            "Giving up on on-chain {} after {} attempts", status, attempt
+        );
+    }
+    Ok(Some(outcome))
+}
+
+/// Resubmits pending on-chain freezes whose back-off has elapsed.
+pub struct PlanFreezeRetryWorker {
+    db: PgPool,
+    freezer: Arc<dyn OnChainPlanFreezer>,
+}
+
+impl PlanFreezeRetryWorker {
+    pub fn new(db: PgPool, freezer: Arc<dyn OnChainPlanFreezer>) -> Self {
+        Self { db, freezer }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(RETRY_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                match self.retry_due().await {
+                    Ok(0) => {}
+                    Ok(attempted) => info!("Resubmitted {} on-chain plan freezes", attempted),
+                    Err(e) => error!("Plan freeze retry run failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Attempt every pending freeze or unfreeze that is due; returns how many
+    /// were attempted.
+    pub async fn retry_due(&self) -> Result<usize, ApiError> {
+        let due: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM plans
+            WHERE chain_freeze_status IN ('pending_freeze', 'pending_unfreeze')
+              AND chain_freeze_next_attempt_at <= NOW()
+            ORDER BY chain_freeze_next_attempt_at
+            LIMIT $1
+            "#,
+        )
+        .bind(RETRY_BATCH_SIZE)
+        .fetch_all(&self.db)
+        .await?;
+
+        for plan_id in &due {
+            sync_plan_freeze(&self.db, self.freezer.as_ref(), *plan_id).await?;
+        }
+        Ok(due.len())
+    }
+}
+
+/// Freezer for development and tests, where no Guardian account is
+/// configured: records the intended call in the logs without submitting a
+/// transaction.
+#[derive(Default)]
+pub struct LoggingPlanFreezer;
+
+impl LoggingPlanFreezer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl OnChainPlanFreezer for LoggingPlanFreezer {
+    async fn freeze_plan(
+        &self,
+        contract_plan_id: u64,
+        reason: &str,
+        expires_at: Option<u64>,
+    ) -> Result<(), ApiError> {
+        tracing::info!(
+            contract_plan_id,
+            reason,
+            expires_at,
+            "On-chain freeze_plan not submitted: no contract signer configured"
+        );
+        Ok(())
+    }
+
+    async fn unfreeze_plan(&self, contract_plan_id: u64) -> Result<(), ApiError> {
+        tracing::info!(
+            contract_plan_id,
+            "On-chain unfreeze_plan not submitted: no contract signer configured"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn freeze_args_match_contract_signature() {
+        let freezer = SorobanPlanFreezer::new(&PlanFreezerConfig {
+            rpc_url: "http://localhost:8000".to_string(),
+            contract_id: "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE".to_string(),
+            signer_secret: stellar_strkey::ed25519::PrivateKey([5u8; 32])
+                .to_string()
+                .as_str()
+                .to_string(),
+            network_passphrase: DEFAULT_NETWORK_PASSPHRASE.to_string(),
+        })
+        .unwrap();
+
+        let args = freezer.freeze_args(7, "Court order", None).unwrap();
+        assert_eq!(
+            args,
+            vec![
+                ScVal::Address(freezer.invoker.account_address()),
+                ScVal::U64(7),
+                ScVal::String(ScString("Court order".try_into().unwrap())),
+                ScVal::U64(0),
+            ]
+        );
+        let args = freezer.freeze_args(7, "x", Some(1_700_000_000)).unwrap();
+        assert_eq!(args[3], ScVal::U64(1_700_000_000));
+    }
+}
@@ -0,0 +1,311 @@
+//! # Plan TTL Keeper
+//!
+//! Soroban archives persistent entries whose TTL runs out, so a plan nobody
+//! touches for months would become unreadable before its beneficiaries claim
+//! it. The inheritance contract exposes a permissionless `bump_plan(plan_id)`
+//! that extends the plan record and every per-plan entry; this job calls it
+//! for each active plan whose TTL has fallen below the contract's bump
+//! threshold.
+//!
+//! The plan record's remaining TTL is read with `getLedgerEntries` first.
+//! The contract extends that record only at creation and in `bump_plan`,
+//! which renews every per-plan entry to the full bump amount, so the record
+//! always has the shortest TTL of the plan's entries. `bump_plan` is only
+//! submitted when the record is closer to archival than
+//! [`DEFAULT_BUMP_THRESHOLD_LEDGERS`], matching the contract's
+//! `PERSISTENT_LIFETIME_THRESHOLD`. Submissions are signed by a dedicated
+//! keeper account that only pays fees; it holds no role on the contract.
+
+use crate::api_error::ApiError;
+use crate::contract_invoker::{encode_xdr, ContractInvoker, DEFAULT_NETWORK_PASSPHRASE};
+use axum::async_trait;
+use serde::Serialize;
+use soroban_sdk::xdr::{
+    ContractDataDurability, LedgerKey, LedgerKeyContractData, ScAddress, ScSymbol, ScVal, ScVec,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Ledgers closed per day at the ~5 second target close time.
+const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Submit `bump_plan` once the plan record has fewer ledgers left than this.
+pub const DEFAULT_BUMP_THRESHOLD_LEDGERS: u32 = 90 * DAY_IN_LEDGERS;
+
+const DEFAULT_INTERVAL_SECS: u64 = 6 * 60 * 60;
+
+// ─── Configuration ────────────────────────────────────────────────────────────
+
+pub struct PlanTtlKeeperConfig {
+    pub rpc_url: String,
+    pub contract_id: String,
+    /// Stellar secret seed (`S...`) of the fee-paying keeper account.
+    pub keeper_secret: String,
+    pub network_passphrase: String,
+    pub interval_secs: u64,
+    pub bump_threshold_ledgers: u32,
+}
+
+impl PlanTtlKeeperConfig {
+    /// Reads the keeper configuration from the environment. Returns `None`
+    /// when `SOROBAN_RPC_URL`, `INHERITANCE_CONTRACT_ID` or
+    /// `PLAN_TTL_KEEPER_SECRET_KEY` is unset, in which case the keeper is
+    /// disabled.
+    pub fn from_env() -> Option<Self> {
+        let non_empty = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+        Some(Self {
+            rpc_url: non_empty("SOROBAN_RPC_URL")?,
+            contract_id: non_empty("INHERITANCE_CONTRACT_ID")?,
+            keeper_secret: non_empty("PLAN_TTL_KEEPER_SECRET_KEY")?,
+            network_passphrase: non_empty("SOROBAN_NETWORK_PASSPHRASE")
+                .unwrap_or_else(|| DEFAULT_NETWORK_PASSPHRASE.to_string()),
+            interval_secs: non_empty("PLAN_TTL_KEEPER_INTERVAL_SECS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_INTERVAL_SECS),
+            bump_threshold_ledgers: non_empty("PLAN_TTL_KEEPER_THRESHOLD_LEDGERS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_BUMP_THRESHOLD_LEDGERS),
+        })
+    }
+}
+
+// ─── Chain access ─────────────────────────────────────────────────────────────
+
+/// Remaining lifetime of a plan record on chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanTtl {
+    /// The record is live for this many more ledgers.
+    Live(u32),
+    /// The record does not exist or has already been archived.
+    Missing,
+}
+
+/// Reads plan TTLs and submits `bump_plan` calls.
+#[async_trait]
+pub trait PlanTtlBumper: Send + Sync {
+    async fn plan_ttl(&self, contract_plan_id: u64) -> Result<PlanTtl, ApiError>;
+
+    /// Submits `bump_plan(contract_plan_id)` and waits for it to be applied.
+    /// Returns the transaction hash.
+    async fn bump_plan(&self, contract_plan_id: u64) -> Result<String, ApiError>;
+}
+
+/// Submits `bump_plan` through Soroban RPC, signed by the keeper account.
+pub struct SorobanPlanTtlBumper {
+    invoker: ContractInvoker,
+}
+
+impl SorobanPlanTtlBumper {
+    pub fn new(config: &PlanTtlKeeperConfig) -> Result<Self, ApiError> {
+        Ok(Self {
+            invoker: ContractInvoker::new(
+                &config.rpc_url,
+                &config.contract_id,
+                &config.keeper_secret,
+                &config.network_passphrase,
+            )?,
+        })
+    }
+}
+
+#[async_trait]
+impl PlanTtlBumper for SorobanPlanTtlBumper {
+    async fn plan_ttl(&self, contract_plan_id: u64) -> Result<PlanTtl, ApiError> {
+        let key = plan_ledger_key(self.invoker.contract(), contract_plan_id)?;
+        let result = self
+            .invoker
+            .rpc()
+            .get_ledger_entries(&[encode_xdr(&key)?])
+            .await?;
+
+        Ok(match result.entries.first() {
+            Some(entry) => match entry.live_until_ledger_seq {
+                Some(live_until) if live_until >= result.latest_ledger => {
+                    PlanTtl::Live(live_until - result.latest_ledger)
+                }
+                _ => PlanTtl::Missing,
+            },
+            None => PlanTtl::Missing,
+        })
+    }
+
+    async fn bump_plan(&self, contract_plan_id: u64) -> Result<String, ApiError> {
+        self.invoker
+            .invoke("bump_plan", vec![ScVal::U64(contract_plan_id)])
+            .await
+    }
+}
+
+/// Ledger key of `DataKey::Plan(plan_id)` in the contract's persistent storage.
+pub fn plan_ledger_key(contract: &ScAddress, contract_plan_id: u64) -> Result<LedgerKey, ApiError> {
+    let invalid = |e: &dyn std::fmt::Display| ApiError::Internal(anyhow::anyhow!("{e}"));
+    let variant = ScVal::Symbol(ScSymbol("Plan".try_into().map_err(|e| invalid(&e))?));
+    let key = ScVal::Vec(Some(ScVec(
+        vec![variant, ScVal::U64(contract_plan_id)]
+            .try_into()
+            .map_err(|e| invalid(&e))?,
+    )));
+
+    Ok(LedgerKey::ContractData(LedgerKeyContractData {
+        contract: contract.clone(),
+        key,
+        durability: ContractDataDurability::Persistent,
+    }))
+}
+
+// ─── Service ──────────────────────────────────────────────────────────────────
+
+/// Outcome of one keeper pass.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct KeeperRunSummary {
+    pub checked: usize,
+    pub bumped: usize,
+    pub missing: usize,
+    pub failed: usize,
+}
+
+pub struct PlanTtlKeeperService {
+    db: PgPool,
+    bumper: Arc<dyn PlanTtlBumper>,
+    interval_secs: u64,
+    bump_threshold_ledgers: u32,
+}
+
+impl PlanTtlKeeperService {
+    pub fn new(
+        db: PgPool,
+        bumper: Arc<dyn PlanTtlBumper>,
+        interval_secs: u64,
+        bump_threshold_ledgers: u32,
+    ) -> Self {
+        Self {
+            db,
+            bumper,
+            interval_secs,
+            bump_threshold_ledgers,
+        }
+    }
+
+    pub fn from_config(db: PgPool, config: &PlanTtlKeeperConfig) -> Result<Self, ApiError> {
+        Ok(Self::new(
+            db,
+            Arc::new(SorobanPlanTtlBumper::new(config)?),
+            config.interval_secs,
+            config.bump_threshold_ledgers,
+        ))
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(self.interval_secs));
+            loop {
+                interval.tick().await;
+                match self.run_once().await {
+                    Ok(summary) => info!(
+                        "Plan TTL keeper checked {} plans: {} bumped, {} missing, {} failed",
+                        summary.checked, summary.bumped, summary.missing, summary.failed
+                    ),
+                    Err(e) => error!("Plan TTL keeper run failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Contract ids of plans that still hold funds on chain.
+    async fn active_contract_plan_ids(&self) -> Result<Vec<(Uuid, i64)>, ApiError> {
+        let rows = sqlx::query_as::<_, (Uuid, i64)>(
+            r#"
+            SELECT id, contract_plan_id
+            FROM plans
+            WHERE contract_plan_id IS NOT NULL
+              AND COALESCE(is_active, true)
+              AND status NOT IN ('claimed', 'deactivated')
+            ORDER BY contract_plan_id
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Checks every active plan once and bumps those nearing archival. A
+    /// failure on one plan is logged and does not stop the pass.
+    pub async fn run_once(&self) -> Result<KeeperRunSummary, ApiError> {
+        let mut summary = KeeperRunSummary::default();
+
+        for (plan_id, contract_plan_id) in self.active_contract_plan_ids().await? {
+            summary.checked += 1;
+            let contract_plan_id = contract_plan_id as u64;
+
+            match self.bumper.plan_ttl(contract_plan_id).await {
+                Ok(PlanTtl::Live(remaining)) if remaining >= self.bump_threshold_ledgers => {}
+                Ok(PlanTtl::Live(_)) => match self.bumper.bump_plan(contract_plan_id).await {
+                    Ok(hash) => {
+                        summary.bumped += 1;
+                        info!(
+                            "Extended TTL of plan {} (contract plan {}) in {}",
+                            plan_id, contract_plan_id, hash
+                        );
+                    }
+                    Err(e) => {
+                        summary.failed += 1;
+                        warn!(
+                            "Failed to bump plan {} (contract plan {}): {}",
+                            plan_id, contract_plan_id, e
+                        );
+                    }
+                },
+                Ok(PlanTtl::Missing) => {
+                    summary.missing += 1;
+                    warn!(
+                        "Plan {} (contract plan {}) is missing or archived on chain",
+                        plan_id, contract_plan_id
+                    );
+                }
+                Err(e) => {
+                    summary.failed += 1;
+                    warn!(
+                        "Failed to read TTL of plan {} (contract plan {}): {}",
+                        plan_id, contract_plan_id, e
+                    );
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const CONTRACT: &str = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE";
+
+    fn contract() -> ScAddress {
+        ScAddress::from_str(CONTRACT).unwrap()
+    }
+
+    #[test]
+    fn plan_ledger_key_matches_contract_data_key() {
+        let key = plan_ledger_key(&contract(), 42).unwrap();
+        let LedgerKey::ContractData(data) = key else {
+            panic!("expected contract data key");
+        };
+        assert_eq!(data.durability, ContractDataDurability::Persistent);
+        let ScVal::Vec(Some(ScVec(items))) = data.key else {
+            panic!("expected enum-variant vec");
+        };
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0],
+            ScVal::Symbol(ScSymbol("Plan".try_into().unwrap()))
+        );
+        assert_eq!(items[1], ScVal::U64(42));
+    }
+}
@@ -10,10 +10,17 @@ pub fn notify_plan_claimed(_user_id: uuid::Uuid, _plan_id: uuid::Uuid) {
 pub fn notify_plan_deactivated(_user_id: uuid::Uuid, _plan_id: uuid::Uuid) {
     // TODO: Implement email or in-app notification for plan deactivation
 }
+use crate::alert_provider::AlertProvider;
 use crate::api_error::ApiError;
 use crate::notifications::{
     audit_action, entity_type, notif_type, AuditLogService, NotificationService,
 };
+use crate::outbox::{Outbox, OutboxMessage};
+use crate::payout_details::{PayoutDetails, SettlementKeyring};
+use crate::payout_schedule::PayoutSchedule;
+use crate::plan_freeze::{sync_plan_freeze, ChainFreezeStatus, OnChainPlanFreezer};
+use crate::webhook_events::{KycApproved, KycRejected, KycSubmitted, PlanClaimed, PlanCreated};
+use crate::will_signature::WillSignatureService;
 use crate::yield_service::OnChainYieldService;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
@@ -24,11 +31,24 @@ use std::fmt;
 use std::str::FromStr;
 use uuid::Uuid;
 
+/// Installments paid to a plan's least-paid beneficiary, for plan-level due
+/// dates. The plan's own beneficiary (index 0) counts as unpaid until the
+/// indexer has seen a payout to them.
+const LEAST_PAID_INSTALLMENTS: &str = "COALESCE((
+    SELECT MIN(pp.paid_installments) FROM plan_payout_progress pp
+    WHERE pp.plan_id = p.id HAVING bool_or(pp.beneficiary_index = 0)
+), 0)";
+
+/// How far a claim's `signed_at` may be from the server clock, in seconds.
+pub const CLAIM_SIGNATURE_TOLERANCE_SECS: i64 = 300;
+
 /// Payout currency preference
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum CurrencyPreference {
     Usdc,
+    Xlm,
+    Eurc,
     Fiat,
 }
 
@@ -36,9 +56,16 @@ impl CurrencyPreference {
     pub fn as_str(&self) -> &'static str {
         match self {
             CurrencyPreference::Usdc => "USDC",
+            CurrencyPreference::Xlm => "XLM",
+            CurrencyPreference::Eurc => "EURC",
             CurrencyPreference::Fiat => "FIAT",
         }
     }
+
+    /// Whether this is an on-chain asset a plan can hold (everything but FIAT).
+    pub fn is_crypto(&self) -> bool {
+        *self != CurrencyPreference::Fiat
+    }
 }
 
 impl FromStr for CurrencyPreference {
@@ -47,9 +74,11 @@ impl FromStr for CurrencyPreference {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "USDC" | "usdc" => Ok(CurrencyPreference::Usdc),
+            "XLM" | "xlm" => Ok(CurrencyPreference::Xlm),
+            "EURC" | "eurc" => Ok(CurrencyPreference::Eurc),
             "FIAT" | "fiat" => Ok(CurrencyPreference::Fiat),
             _ => Err(ApiError::BadRequest(
-                "currency_preference must be USDC or FIAT".to_string(),
+                "currency_preference must be USDC, XLM, EURC or FIAT".to_string(),
             )),
         }
     }
@@ -69,6 +98,8 @@ pub struct DueForClaimPlan {
     pub is_active: Option<bool>,
     pub contract_created_at: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_due_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub beneficiary_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bank_name: Option<String>,
@@ -114,13 +145,43 @@ pub struct CreatePlanRequest {
     pub bank_account_number: Option<String>,
     pub bank_name: Option<String>,
     pub currency_preference: String,
+    /// Additional token balances held by the plan alongside `net_amount`.
+    #[serde(default)]
+    pub assets: Vec<PlanAssetRequest>,
     pub two_fa_code: String,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct PlanAssetRequest {
+    pub asset_code: String,
+    pub amount: rust_decimal::Decimal,
+}
+
+/// One token balance held by a plan, as recorded in `plan_assets`.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PlanAsset {
+    pub asset_code: String,
+    pub amount: rust_decimal::Decimal,
+}
+
+/// A beneficiary's claim, authenticated by a wallet signature over
+/// [`PlanService::claim_message`] rather than a shared code.
 #[derive(Debug, Deserialize)]
 pub struct ClaimPlanRequest {
     pub beneficiary_email: String,
-    pub two_fa_code: String,
+    /// The claimant's position among the plan's on-chain beneficiaries; 0 for
+    /// single-beneficiary plans.
+    #[serde(default)]
+    pub beneficiary_index: u32,
+    /// Stellar G-address (or hex Ed25519 public key) that signed the claim.
+    pub wallet_address: String,
+    /// The 1-based installment being claimed; 1 for lump-sum plans.
+    pub installment: u32,
+    /// Unix time the claim was signed at; must be within
+    /// `CLAIM_SIGNATURE_TOLERANCE_SECS` of the server clock.
+    pub signed_at: i64,
+    /// Hex-encoded Ed25519 signature over the claim message.
+    pub signature_hex: String,
 }
 
 #[derive(sqlx::FromRow)]
@@ -345,6 +406,7 @@ impl PlanService {
             .as_deref()
             .map(|s| s.trim().to_string());
         let currency_preference = Some(currency.as_str().to_string());
+        let assets = Self::validate_plan_assets(&req.assets)?;
 
         // 2. Insert Plan - using the transaction handle
         let row = sqlx::query_as::<_, PlanRowFull>(
@@ -374,6 +436,36 @@ impl PlanService {
 
         let plan = plan_row_to_plan_with_beneficiary(&row)?;
 
+        // Seal the fiat details so the copy written on chain is ciphertext.
+        if let (Some(keyring), Some(beneficiary_name), Some(bank_name), Some(bank_account_number)) = (
+            SettlementKeyring::from_env()?,
+            beneficiary_name,
+            bank_name,
+            bank_account_number,
+        ) {
+            let envelope = keyring.seal_details(&PayoutDetails {
+                beneficiary_name,
+                bank_name,
+                bank_account_number,
+            })?;
+            sqlx::query("UPDATE plans SET payout_details_envelope = $2 WHERE id = $1")
+                .bind(plan.id)
+                .bind(envelope)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        for asset in &assets {
+            sqlx::query(
+                "INSERT INTO plan_assets (plan_id, asset_code, amount) VALUES ($1, $2, $3)",
+            )
+            .bind(plan.id)
+            .bind(&asset.asset_code)
+            .bind(asset.amount)
+            .execute(&mut *tx)
+            .await?;
+        }
+
         // 3. Audit: This must now return Result and use the transaction
         AuditLogService::log(
             &mut *tx,
@@ -388,11 +480,73 @@ impl PlanService {
         )
         .await?; // If this fails, '?' triggers an early return
 
+        let created = PlanCreated {
+            plan_id: plan.id,
+            title: plan.title.clone(),
+            net_amount: plan.net_amount,
+            status: plan.status.clone(),
+        };
+        let message = OutboxMessage::new(user_id, &created)?.with_notification(
+            notif_type::PLAN_CREATED,
+            format!("Plan '{}' has been created", plan.title),
+        );
+        Outbox::record(&mut *tx, &message).await?;
+
         // 4. Commit: If we reached here, both Plan and Audit are saved
         tx.commit().await?;
 
         Ok(plan)
     }
+
+    /// Checks the extra assets requested for a plan: each must be a
+    /// distinct on-chain asset with a positive amount.
+    pub fn validate_plan_assets(assets: &[PlanAssetRequest]) -> Result<Vec<PlanAsset>, ApiError> {
+        let mut seen = HashSet::new();
+        let mut validated = Vec::with_capacity(assets.len());
+        for asset in assets {
+            let code = CurrencyPreference::from_str(asset.asset_code.trim())?;
+            if !code.is_crypto() {
+                return Err(ApiError::BadRequest(
+                    "plan assets must be USDC, XLM or EURC".to_string(),
+                ));
+            }
+            if asset.amount <= Decimal::ZERO {
+                return Err(ApiError::BadRequest(format!(
+                    "amount for {} must be positive",
+                    code.as_str()
+                )));
+            }
+            if !seen.insert(code) {
+                return Err(ApiError::BadRequest(format!(
+                    "{} is listed more than once",
+                    code.as_str()
+                )));
+            }
+            validated.push(PlanAsset {
+                asset_code: code.as_str().to_string(),
+                amount: asset.amount,
+            });
+        }
+        Ok(validated)
+    }
+
+    /// Token balances recorded for a plan, in asset-code order.
+    pub async fn get_plan_assets<'a, E>(
+        executor: E,
+        plan_id: Uuid,
+    ) -> Result<Vec<PlanAsset>, ApiError>
+    where
+        E: sqlx::Executor<'a, Database = sqlx::Postgres>,
+    {
+        let assets = sqlx::query_as::<_, PlanAsset>(
+            "SELECT asset_code, amount FROM plan_assets WHERE plan_id = $1 ORDER BY asset_code",
+        )
+        .bind(plan_id)
+        .fetch_all(executor)
+        .await?;
+        Ok(assets)
+    }
+
     pub async fn get_plan_by_id<'a, E>(
         executor: E,
         plan_id: Uuid,
@@ -431,7 +585,7 @@ impl PlanService {
     {
         let row = sqlx::query_as::<_, PlanRowFull>(
             r#"
-        SELECT id, user_id, title, description, fee, net_amount, status,
+        SELECT id, user_id, title, description, fee::TEXT AS fee, net_amount::TEXT AS net_amount, status,
                contract_plan_id, distribution_method, is_active, is_paused, risk_override_enabled,
                contract_created_at, beneficiary_name, bank_account_number, bank_name, currency_preference,
                created_at, updated_at
@@ -462,6 +616,42 @@ impl PlanService {
             ));
         }
 
+        let beneficiary_email = req.beneficiary_email.trim();
+        let wallet_address = req.wallet_address.trim();
+
+        // The claim must be signed by the wallet the claimant proved ownership
+        // of at sign-in; a wallet named only in the request proves nothing.
+        let registered_wallet: Option<String> =
+            sqlx::query_scalar("SELECT wallet_address FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(pool)
+                .await?
+                .flatten();
+        let Some(registered_wallet) = registered_wallet else {
+            return Err(ApiError::Forbidden(
+                "Register a wallet before claiming a plan".to_string(),
+            ));
+        };
+        if !WillSignatureService::same_wallet(&registered_wallet, wallet_address) {
+            return Err(ApiError::Forbidden(
+                "Claim must be signed by the claimant's registered wallet".to_string(),
+            ));
+        }
+        if (chrono::Utc::now().timestamp() - req.signed_at).abs() > CLAIM_SIGNATURE_TOLERANCE_SECS {
+            return Err(ApiError::Unauthorized);
+        }
+        WillSignatureService::verify_signature(
+            wallet_address,
+            &Self::claim_message(
+                plan_id,
+                beneficiary_email,
+                wallet_address,
+                req.installment,
+                req.signed_at,
+            ),
+            &req.signature_hex,
+        )?;
+
         // 2. Start the transaction
         let mut tx = pool.begin().await?;
 
@@ -502,15 +692,97 @@ impl PlanService {
             ));
         }
 
-        if !Self::is_due_for_claim(
+        // Only the claimant's own installments count: each beneficiary is paid
+        // on their own schedule.
+        let (triggered_at, installment_count, paid_installments): (Option<i64>, Option<i32>, i32) =
+            sqlx::query_as(
+                r#"
+            SELECT p.inheritance_triggered_at, p.payout_installment_count,
+                   COALESCE(pp.paid_installments, 0)
+            FROM plans p
+            LEFT JOIN plan_payout_progress pp
+              ON pp.plan_id = p.id AND pp.beneficiary_index = $2
+            WHERE p.id = $1
+            "#,
+            )
+            .bind(plan_id)
+            .bind(req.beneficiary_index as i32)
+            .fetch_one(&mut *tx)
+            .await?;
+        if req.installment as i64 != paid_installments as i64 + 1 {
+            return Err(ApiError::BadRequest(format!(
+                "Claim must be signed for installment {}",
+                paid_installments + 1
+            )));
+        }
+        if !Self::is_due_for_claim(Self::next_claim_due_at(
             plan.distribution_method.as_deref(),
             plan.contract_created_at,
-        ) {
+            triggered_at,
+            installment_count,
+            paid_installments,
+        )) {
             return Err(ApiError::BadRequest(
                 "Plan is not yet mature for claim".to_string(),
             ));
         }
 
+        // Each beneficiary is bound to the registered wallet of their first
+        // claim; every later claim for them must come from the same wallet.
+        let bound_wallet: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT wallet_address FROM beneficiary_wallet_bindings
+            WHERE plan_id = $1 AND beneficiary_index = $2
+            "#,
+        )
+        .bind(plan_id)
+        .bind(req.beneficiary_index as i32)
+        .fetch_optional(&mut *tx)
+        .await?;
+        match bound_wallet {
+            Some(bound) if !WillSignatureService::same_wallet(&bound, wallet_address) => {
+                return Err(ApiError::Forbidden(
+                    "Claim must be signed by the beneficiary's bound wallet".to_string(),
+                ));
+            }
+            Some(_) => {}
+            None => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO beneficiary_wallet_bindings
+                        (plan_id, beneficiary_index, wallet_address)
+                    VALUES ($1, $2, $3)
+                    "#,
+                )
+                .bind(plan_id)
+                .bind(req.beneficiary_index as i32)
+                .bind(wallet_address)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        // Each signed claim is accepted once.
+        let recorded = sqlx::query(
+            r#"
+            INSERT INTO claim_signatures
+                (signature_hex, plan_id, user_id, beneficiary_index, installment, signed_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (signature_hex) DO NOTHING
+            "#,
+        )
+        .bind(req.signature_hex.trim().to_lowercase())
+        .bind(plan_id)
+        .bind(user_id)
+        .bind(req.beneficiary_index as i32)
+        .bind(req.installment as i32)
+        .bind(req.signed_at)
+        .execute(&mut *tx)
+        .await?;
+        if recorded.rows_affected() == 0 {
+            return Err(ApiError::Unauthorized);
+        }
+
         let execution_safety =
             load_inheritance_execution_safety(&mut *tx, plan_id, plan.net_amount).await?;
         if let Some(reason) = execution_safety.blocking_reason() {
@@ -530,24 +802,43 @@ impl PlanService {
             })?;
 
         if currency == CurrencyPreference::Fiat {
-            Self::validate_beneficiary_for_currency(
-                &currency,
-                plan.beneficiary_name.as_deref(),
-                plan.bank_name.as_deref(),
-                plan.bank_account_number.as_deref(),
-            )?;
+            // Sealed details are opened only here, for fiat settlement; plans
+            // created before sealing fall back to the plaintext columns.
+            let envelope: Option<Vec<u8>> =
+                sqlx::query_scalar("SELECT payout_details_envelope FROM plans WHERE id = $1")
+                    .bind(plan_id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+            match envelope {
+                Some(envelope) => {
+                    let details = SettlementKeyring::require_from_env()?.open_details(&envelope)?;
+                    Self::validate_beneficiary_for_currency(
+                        &currency,
+                        Some(&details.beneficiary_name),
+                        Some(&details.bank_name),
+                        Some(&details.bank_account_number),
+                    )?;
+                }
+                None => Self::validate_beneficiary_for_currency(
+                    &currency,
+                    plan.beneficiary_name.as_deref(),
+                    plan.bank_name.as_deref(),
+                    plan.bank_account_number.as_deref(),
+                )?,
+            }
         }
 
         // 3. FIX: Changed 'db' to '&mut *tx' to keep it atomic
         sqlx::query(
             r#"
-        INSERT INTO claims (plan_id, contract_plan_id, beneficiary_email)
-        VALUES ($1, $2, $3)
+        INSERT INTO claims (plan_id, contract_plan_id, beneficiary_email, wallet_address)
+        VALUES ($1, $2, $3, $4)
         "#,
         )
         .bind(plan_id)
         .bind(contract_plan_id)
-        .bind(req.beneficiary_email.trim())
+        .bind(beneficiary_email)
+        .bind(wallet_address)
         .execute(&mut *tx) // <--- Use the transaction here!
         .await
         .map_err(|e| {
@@ -594,31 +885,68 @@ impl PlanService {
         )
         .await?; // Use ? to ensure failure here rolls back the claim
 
+        // Both the plan owner and the claimer may be watching for this.
+        let claimed = PlanClaimed {
+            plan_id,
+            title: plan.title.clone(),
+            claimed_by: user_id,
+        };
+        Outbox::publish(&mut *tx, plan.user_id, &claimed).await?;
+        if plan.user_id != user_id {
+            Outbox::publish(&mut *tx, user_id, &claimed).await?;
+        }
+
         // 6. Final Commit
         tx.commit().await?;
         Ok(plan)
     }
-    pub fn is_due_for_claim(
-        distribution_method: Option<&str>,
-        contract_created_at: Option<i64>,
-    ) -> bool {
-        let Some(method) = distribution_method else {
-            return false;
-        };
-        let Some(created_at) = contract_created_at else {
-            return false;
-        };
 
+    /// The message a beneficiary's wallet signs to claim `installment` of
+    /// `plan_id`. Naming the plan and installment keeps a signature from
+    /// being reused for a different plan or a later payout, and `signed_at`
+    /// bounds how long a captured signature stays usable.
+    pub fn claim_message(
+        plan_id: Uuid,
+        beneficiary_email: &str,
+        wallet_address: &str,
+        installment: u32,
+        signed_at: i64,
+    ) -> String {
+        format!(
+            "INHERITX_CLAIM:{plan_id}:{}:{wallet_address}:{installment}:{signed_at}",
+            beneficiary_email.to_lowercase()
+        )
+    }
+
+    /// A plan is due once its next payout has fallen due. `next_due_at` comes
+    /// from [`Self::next_claim_due_at`].
+    pub fn is_due_for_claim(next_due_at: Option<i64>) -> bool {
         let now = chrono::Utc::now().timestamp();
-        let elapsed = now - created_at;
-
-        match method {
-            "LumpSum" => true,
-            "Monthly" => elapsed >= 30 * 24 * 60 * 60,
-            "Quarterly" => elapsed >= 90 * 24 * 60 * 60,
-            "Yearly" => elapsed >= 365 * 24 * 60 * 60,
-            _ => false,
+        matches!(next_due_at, Some(due_at) if due_at <= now)
+    }
+
+    /// Unix timestamp at which a beneficiary's next payout falls due,
+    /// computed from the same schedule as the contract's
+    /// `get_payout_schedule`: immediately for lump-sum plans, and at the
+    /// installment after `paid_installments` for Monthly/Quarterly/Yearly
+    /// plans. `triggered_at` is when inheritance was triggered on chain and
+    /// `installment_count` the owner's schedule override, if any.
+    /// Returns `None` once every installment has been paid.
+    pub fn next_claim_due_at(
+        distribution_method: Option<&str>,
+        contract_created_at: Option<i64>,
+        triggered_at: Option<i64>,
+        installment_count: Option<i32>,
+        paid_installments: i32,
+    ) -> Option<i64> {
+        let method = distribution_method?;
+        let created_at = contract_created_at?;
+        if method == "LumpSum" {
+            return Some(created_at);
         }
+        let installment_count = installment_count.and_then(|n| u32::try_from(n).ok());
+        PayoutSchedule::for_plan(method, created_at, triggered_at, installment_count)?
+            .next_due_at(u32::try_from(paid_installments).unwrap_or(0))
     }
 
     pub async fn get_due_for_claim_plan_by_id(
@@ -639,6 +967,9 @@ impl PlanService {
             distribution_method: Option<String>,
             is_active: Option<bool>,
             contract_created_at: Option<i64>,
+            inheritance_triggered_at: Option<i64>,
+            payout_installment_count: Option<i32>,
+            paid_installments: i32,
             beneficiary_name: Option<String>,
             bank_account_number: Option<String>,
             bank_name: Option<String>,
@@ -647,11 +978,12 @@ impl PlanService {
             updated_at: DateTime<Utc>,
         }
 
-        let plan_row = sqlx::query_as::<_, PlanRow>(
+        let plan_row = sqlx::query_as::<_, PlanRow>(&format!(
             r#"
             SELECT p.id, p.user_id, p.title, p.description, p.fee, p.net_amount, p.status,
                    p.contract_plan_id, p.distribution_method, p.is_active, p.contract_created_at,
-                   p.beneficiary_name, p.bank_account_number, p.bank_name, p.currency_preference,
+                   p.inheritance_triggered_at, p.payout_installment_count,
+                   {LEAST_PAID_INSTALLMENTS} AS paid_installments, p.beneficiary_name, p.bank_account_number, p.bank_name, p.currency_preference,
                    p.created_at, p.updated_at
             FROM plans p
             WHERE p.id = $1
@@ -659,8 +991,8 @@ impl PlanService {
               AND (p.is_active IS NULL OR p.is_active = true)
               AND p.status != 'claimed'
               AND p.status != 'deactivated'
-            "#,
-        )
+            "#
+        ))
         .bind(plan_id)
         .bind(user_id)
         .fetch_optional(db)
@@ -681,6 +1013,13 @@ impl PlanService {
                 })?,
                 status: row.status,
                 contract_plan_id: row.contract_plan_id,
+                next_due_at: Self::next_claim_due_at(
+                    row.distribution_method.as_deref(),
+                    row.contract_created_at,
+                    row.inheritance_triggered_at,
+                    row.payout_installment_count,
+                    row.paid_installments,
+                ),
                 distribution_method: row.distribution_method,
                 is_active: row.is_active,
                 contract_created_at: row.contract_created_at,
@@ -696,10 +1035,7 @@ impl PlanService {
         };
 
         if let Some(plan) = plan {
-            if Self::is_due_for_claim(
-                plan.distribution_method.as_deref(),
-                plan.contract_created_at,
-            ) {
+            if Self::is_due_for_claim(plan.next_due_at) {
                 let has_claim = sqlx::query_scalar::<_, bool>(
                     "SELECT EXISTS(SELECT 1 FROM claims WHERE plan_id = $1)",
                 )
@@ -737,6 +1073,9 @@ impl PlanService {
             distribution_method: Option<String>,
             is_active: Option<bool>,
             contract_created_at: Option<i64>,
+            inheritance_triggered_at: Option<i64>,
+            payout_installment_count: Option<i32>,
+            paid_installments: i32,
             beneficiary_name: Option<String>,
             bank_account_number: Option<String>,
             bank_name: Option<String>,
@@ -745,11 +1084,12 @@ impl PlanService {
             updated_at: DateTime<Utc>,
         }
 
-        let plan_rows = sqlx::query_as::<_, PlanRow>(
+        let plan_rows = sqlx::query_as::<_, PlanRow>(&format!(
             r#"
             SELECT p.id, p.user_id, p.title, p.description, p.fee, p.net_amount, p.status,
                    p.contract_plan_id, p.distribution_method, p.is_active, p.contract_created_at,
-                   p.beneficiary_name, p.bank_account_number, p.bank_name, p.currency_preference,
+                   p.inheritance_triggered_at, p.payout_installment_count,
+                   {LEAST_PAID_INSTALLMENTS} AS paid_installments, p.beneficiary_name, p.bank_account_number, p.bank_name, p.currency_preference,
                    p.created_at, p.updated_at
             FROM plans p
             WHERE p.user_id = $1
@@ -757,8 +1097,8 @@ impl PlanService {
               AND p.status != 'claimed'
               AND p.status != 'deactivated'
             ORDER BY p.created_at DESC
-            "#,
-        )
+            "#
+        ))
         .bind(user_id)
         .fetch_all(db)
         .await?;
@@ -779,6 +1119,13 @@ impl PlanService {
                     })?,
                     status: row.status,
                     contract_plan_id: row.contract_plan_id,
+                    next_due_at: Self::next_claim_due_at(
+                        row.distribution_method.as_deref(),
+                        row.contract_created_at,
+                        row.inheritance_triggered_at,
+                        row.payout_installment_count,
+                        row.paid_installments,
+                    ),
                     distribution_method: row.distribution_method,
                     is_active: row.is_active,
                     contract_created_at: row.contract_created_at,
@@ -796,10 +1143,7 @@ impl PlanService {
         let mut due_plans = Vec::new();
 
         for plan in plans {
-            if Self::is_due_for_claim(
-                plan.distribution_method.as_deref(),
-                plan.contract_created_at,
-            ) {
+            if Self::is_due_for_claim(plan.next_due_at) {
                 let has_claim = sqlx::query_scalar::<_, bool>(
                     "SELECT EXISTS(SELECT 1 FROM claims WHERE plan_id = $1)",
                 )
@@ -861,6 +1205,9 @@ impl PlanService {
             distribution_method: Option<String>,
             is_active: Option<bool>,
             contract_created_at: Option<i64>,
+            inheritance_triggered_at: Option<i64>,
+            payout_installment_count: Option<i32>,
+            paid_installments: i32,
             beneficiary_name: Option<String>,
             bank_account_number: Option<String>,
             bank_name: Option<String>,
@@ -869,19 +1216,20 @@ impl PlanService {
             updated_at: DateTime<Utc>,
         }
 
-        let plan_rows = sqlx::query_as::<_, PlanRow>(
+        let plan_rows = sqlx::query_as::<_, PlanRow>(&format!(
             r#"
             SELECT p.id, p.user_id, p.title, p.description, p.fee, p.net_amount, p.status,
                    p.contract_plan_id, p.distribution_method, p.is_active, p.contract_created_at,
-                   p.beneficiary_name, p.bank_account_number, p.bank_name, p.currency_preference,
+                   p.inheritance_triggered_at, p.payout_installment_count,
+                   {LEAST_PAID_INSTALLMENTS} AS paid_installments, p.beneficiary_name, p.bank_account_number, p.bank_name, p.currency_preference,
                    p.created_at, p.updated_at
             FROM plans p
             WHERE (p.is_active IS NULL OR p.is_active = true)
               AND p.status != 'claimed'
               AND p.status != 'deactivated'
             ORDER BY p.created_at DESC
-            "#,
-        )
+            "#
+        ))
         .fetch_all(db)
         .await?;
 
@@ -901,6 +1249,13 @@ impl PlanService {
                     })?,
                     status: row.status,
                     contract_plan_id: row.contract_plan_id,
+                    next_due_at: Self::next_claim_due_at(
+                        row.distribution_method.as_deref(),
+                        row.contract_created_at,
+                        row.inheritance_triggered_at,
+                        row.payout_installment_count,
+                        row.paid_installments,
+                    ),
                     distribution_method: row.distribution_method,
                     is_active: row.is_active,
                     contract_created_at: row.contract_created_at,
@@ -918,10 +1273,7 @@ impl PlanService {
         let mut due_plans = Vec::new();
 
         for plan in plans {
-            if Self::is_due_for_claim(
-                plan.distribution_method.as_deref(),
-                plan.contract_created_at,
-            ) {
+            if Self::is_due_for_claim(plan.next_due_at) {
                 let has_claim = sqlx::query_scalar::<_, bool>(
                     "SELECT EXISTS(SELECT 1 FROM claims WHERE plan_id = $1)",
                 )
@@ -1117,6 +1469,8 @@ impl KycService {
         )
         .await?;
 
+        Outbox::publish(&mut *tx, user_id, &KycSubmitted { user_id }).await?;
+
         // 4. Commit
         tx.commit().await?;
         Ok(record)
@@ -1200,6 +1554,24 @@ impl KycService {
         )
         .await?;
 
+        match status {
+            KycStatus::Approved => {
+                let event = KycApproved {
+                    user_id,
+                    reviewed_at: record.reviewed_at,
+                };
+                Outbox::publish(&mut *tx, user_id, &event).await?;
+            }
+            KycStatus::Rejected => {
+                let event = KycRejected {
+                    user_id,
+                    reviewed_at: record.reviewed_at,
+                };
+                Outbox::publish(&mut *tx, user_id, &event).await?;
+            }
+            KycStatus::Pending => {}
+        }
+
         tx.commit().await?; // Commit all three operations
         Ok(record)
     }
@@ -2227,11 +2599,95 @@ impl LoanSimulationService {
 
 #[cfg(test)]
 mod tests {
-    use super::{CurrencyPreference, InheritanceExecutionSafety, PlanService};
+    use super::{CurrencyPreference, InheritanceExecutionSafety, PlanAssetRequest, PlanService};
     use crate::api_error::ApiError;
     use rust_decimal::Decimal;
     use std::str::FromStr;
 
+    #[test]
+    fn due_for_claim_follows_payout_schedule() {
+        let now = chrono::Utc::now().timestamp();
+        let day = 24 * 60 * 60;
+        let due = |method, created_at| {
+            PlanService::is_due_for_claim(PlanService::next_claim_due_at(
+                method, created_at, None, None, 0,
+            ))
+        };
+
+        assert!(due(Some("LumpSum"), Some(now)));
+        assert!(!due(Some("Monthly"), Some(now)));
+        assert!(due(Some("Monthly"), Some(now - 30 * day)));
+        assert!(!due(Some("Yearly"), Some(now - 364 * day)));
+        assert!(!due(None, Some(now)));
+
+        assert_eq!(
+            PlanService::next_claim_due_at(Some("Quarterly"), Some(1_000), None, None, 0),
+            Some(1_000 + 90 * day)
+        );
+        assert_eq!(
+            PlanService::next_claim_due_at(Some("Monthly"), None, None, None, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn next_claim_due_at_uses_schedule_override_and_progress() {
+        let day = 24 * 60 * 60;
+
+        assert_eq!(
+            PlanService::next_claim_due_at(Some("Monthly"), Some(0), None, Some(3), 2),
+            Some(3 * 30 * day)
+        );
+        // Every installment of the overridden schedule has been paid.
+        assert_eq!(
+            PlanService::next_claim_due_at(Some("Monthly"), Some(0), None, Some(3), 3),
+            None
+        );
+        // Without the override the default 12 installments still apply.
+        assert_eq!(
+            PlanService::next_claim_due_at(Some("Monthly"), Some(0), None, None, 3),
+            Some(4 * 30 * day)
+        );
+    }
+
+    #[test]
+    fn claim_signature_binds_plan_installment_and_time() {
+        use crate::will_signature::WillSignatureService;
+        use ring::signature::{Ed25519KeyPair, KeyPair};
+
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        let key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let wallet = hex::encode(key.public_key().as_ref());
+        let plan_id = uuid::Uuid::new_v4();
+
+        let message =
+            PlanService::claim_message(plan_id, "Heir@Example.com", &wallet, 1, 1_700_000_000);
+        assert_eq!(
+            message,
+            PlanService::claim_message(plan_id, "heir@example.com", &wallet, 1, 1_700_000_000)
+        );
+        let signature = hex::encode(key.sign(message.as_bytes()).as_ref());
+        assert!(WillSignatureService::verify_signature(&wallet, &message, &signature).is_ok());
+
+        for other in [
+            PlanService::claim_message(
+                uuid::Uuid::new_v4(),
+                "heir@example.com",
+                &wallet,
+                1,
+                1_700_000_000,
+            ),
+            PlanService::claim_message(plan_id, "heir@example.com", &wallet, 2, 1_700_000_000),
+            PlanService::claim_message(plan_id, "heir@example.com", &wallet, 1, 1_700_000_001),
+        ] {
+            assert!(matches!(
+                WillSignatureService::verify_signature(&wallet, &other, &signature),
+                Err(ApiError::Unauthorized)
+            ));
+        }
+    }
+
     #[test]
     fn currency_preference_accepts_usdc() {
         assert_eq!(
@@ -2262,7 +2718,45 @@ mod tests {
     fn currency_preference_rejects_invalid() {
         let err = CurrencyPreference::from_str("EUR").unwrap_err();
         assert!(matches!(err, ApiError::BadRequest(_)));
-        assert!(err.to_string().contains("USDC or FIAT"));
+        assert!(err.to_string().contains("USDC, XLM, EURC or FIAT"));
+    }
+
+    #[test]
+    fn currency_preference_accepts_xlm_and_eurc() {
+        assert_eq!(
+            CurrencyPreference::from_str("xlm").unwrap(),
+            CurrencyPreference::Xlm
+        );
+        assert_eq!(
+            CurrencyPreference::from_str("EURC").unwrap(),
+            CurrencyPreference::Eurc
+        );
+        assert!(CurrencyPreference::Eurc.is_crypto());
+        assert!(!CurrencyPreference::Fiat.is_crypto());
+    }
+
+    #[test]
+    fn validate_plan_assets_rejects_fiat_duplicates_and_non_positive() {
+        let asset = |code: &str, amount: i64| PlanAssetRequest {
+            asset_code: code.to_string(),
+            amount: Decimal::from(amount),
+        };
+
+        let assets =
+            PlanService::validate_plan_assets(&[asset("xlm", 500), asset("EURC", 20)]).unwrap();
+        assert_eq!(assets[0].asset_code, "XLM");
+        assert_eq!(assets[1].asset_code, "EURC");
+
+        for bad in [
+            vec![asset("FIAT", 10)],
+            vec![asset("XLM", 0)],
+            vec![asset("XLM", 10), asset("xlm", 5)],
+        ] {
+            assert!(matches!(
+                PlanService::validate_plan_assets(&bad),
+                Err(ApiError::BadRequest(_))
+            ));
+        }
     }
 
     #[test]
@@ -2831,6 +3325,17 @@ pub struct EmergencyActionResponse {
     pub success: bool,
     pub plan_id: Uuid,
     pub message: String,
+    /// State of the mirrored on-chain freeze; absent for plans that are not
+    /// on chain and for actions that are not mirrored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_freeze: Option<ChainFreezeStatus>,
+}
+
+impl EmergencyActionResponse {
+    /// True while the on-chain side of the action is still being retried.
+    pub fn is_pending_on_chain(&self) -> bool {
+        self.chain_freeze.is_some_and(ChainFreezeStatus::is_pending)
+    }
 }
 
 pub struct EmergencyContactService;
@@ -3060,9 +3565,8 @@ struct RiskAlertInput<'a> {
 
 impl EmergencyAccessService {
     async fn create_risk_alert(
-        pool: &PgPool,
         executor: &mut sqlx::PgConnection,
-        input: RiskAlertInput<'_>,
+        input: &RiskAlertInput<'_>,
     ) -> Result<(), ApiError> {
         sqlx::query(
             r#"
@@ -3078,38 +3582,44 @@ impl EmergencyAccessService {
         .bind(input.alert_type)
         .bind(input.severity)
         .bind(input.message)
-        .bind(input.metadata)
+        .bind(&input.metadata)
         .execute(executor)
         .await?;
 
-        // After persisting the alert, trigger external notifications
-        use crate::alert_provider::MockAlertProvider;
-        use crate::notifications::EmergencyAlertService;
+        Ok(())
+    }
 
-        let provider = MockAlertProvider;
-        if let Err(e) = EmergencyAlertService::send_risk_alert(
-            pool,
-            &provider,
-            input.user_id,
-            input.contact_id,
-            input.alert_type,
-            input.message,
-        )
-        .await
-        {
-            tracing::error!("Failed to send emergency risk alert: {}", e);
-            // We don't return error here to avoid rolling back the risk alert insertion
-            // because the alert itself is already in the DB.
+    /// Email and text the user and contact about alerts raised in a
+    /// committed transaction. Failures are logged: the alerts themselves are
+    /// already recorded.
+    async fn send_risk_alerts(
+        pool: &PgPool,
+        provider: &dyn AlertProvider,
+        alerts: &[RiskAlertInput<'_>],
+    ) {
+        for alert in alerts {
+            if let Err(e) = crate::notifications::EmergencyAlertService::send_risk_alert(
+                pool,
+                provider,
+                alert.user_id,
+                alert.contact_id,
+                alert.alert_type,
+                alert.message,
+            )
+            .await
+            {
+                tracing::error!("Failed to send emergency risk alert: {}", e);
+            }
         }
-
-        Ok(())
     }
 
+    /// Record the risk alerts raised by a new grant and return them for
+    /// sending once the transaction commits.
     async fn evaluate_grant_risk(
-        pool: &PgPool,
         executor: &mut sqlx::PgConnection,
         grant: &EmergencyAccessGrant,
-    ) -> Result<(), ApiError> {
+    ) -> Result<Vec<RiskAlertInput<'static>>, ApiError> {
+        let mut alerts = Vec::new();
         let recent_grant_count = sqlx::query_scalar::<_, i64>(
             r#"
             SELECT COUNT(*)
@@ -3123,21 +3633,16 @@ impl EmergencyAccessService {
         .await?;
 
         if recent_grant_count >= 4 {
-            Self::create_risk_alert(
-                pool,
-                executor,
-                RiskAlertInput {
-                    grant_id: grant.id,
-                    user_id: grant.user_id,
-                    contact_id: grant.emergency_contact_id,
-                    alert_type: "high_frequency_grants",
-                    severity: "high",
-                    message:
-                        "Multiple emergency access grants were created within a short time window.",
-                    metadata: serde_json::json!({ "recent_grant_count": recent_grant_count }),
-                },
-            )
-            .await?;
+            alerts.push(RiskAlertInput {
+                grant_id: grant.id,
+                user_id: grant.user_id,
+                contact_id: grant.emergency_contact_id,
+                alert_type: "high_frequency_grants",
+                severity: "high",
+                message:
+                    "Multiple emergency access grants were created within a short time window.",
+                metadata: serde_json::json!({ "recent_grant_count": recent_grant_count }),
+            });
         }
 
         let long_lived_high_privilege = grant.permissions.iter().any(|permission| {
@@ -3146,58 +3651,57 @@ impl EmergencyAccessService {
             >= Utc::now() + chrono::Duration::days(7);
 
         if long_lived_high_privilege {
-            Self::create_risk_alert(
-                pool,
-                executor,
-                RiskAlertInput {
-                    grant_id: grant.id,
-                    user_id: grant.user_id,
-                    contact_id: grant.emergency_contact_id,
-                    alert_type: "high_privilege_long_lived_access",
-                    severity: "medium",
-                    message: "High-privilege emergency access was granted with a long expiration window.",
-                    metadata: serde_json::json!({
-                        "permissions": grant.permissions,
-                        "expires_at": grant.expires_at
-                    }),
-                },
-            )
-            .await?;
+            alerts.push(RiskAlertInput {
+                grant_id: grant.id,
+                user_id: grant.user_id,
+                contact_id: grant.emergency_contact_id,
+                alert_type: "high_privilege_long_lived_access",
+                severity: "medium",
+                message:
+                    "High-privilege emergency access was granted with a long expiration window.",
+                metadata: serde_json::json!({
+                    "permissions": grant.permissions,
+                    "expires_at": grant.expires_at
+                }),
+            });
         }
 
-        Ok(())
+        for alert in &alerts {
+            Self::create_risk_alert(&mut *executor, alert).await?;
+        }
+        Ok(alerts)
     }
 
+    /// Record the risk alert raised by a revocation, if any, and return it
+    /// for sending once the transaction commits.
     async fn evaluate_revoke_risk(
-        pool: &PgPool,
         executor: &mut sqlx::PgConnection,
         grant: &EmergencyAccessGrant,
-    ) -> Result<(), ApiError> {
+    ) -> Result<Vec<RiskAlertInput<'static>>, ApiError> {
+        let mut alerts = Vec::new();
         if let Some(revoked_at) = grant.revoked_at {
             let active_duration = revoked_at - grant.created_at;
             if active_duration <= chrono::Duration::minutes(10) {
-                Self::create_risk_alert(
-                    pool,
-                    executor,
-                    RiskAlertInput {
-                        grant_id: grant.id,
-                        user_id: grant.user_id,
-                        contact_id: grant.emergency_contact_id,
-                        alert_type: "rapid_grant_revoke",
-                        severity: "medium",
-                        message: "Emergency access was revoked shortly after it was granted.",
-                        metadata: serde_json::json!({
-                            "granted_at": grant.created_at,
-                            "revoked_at": revoked_at,
-                            "active_duration_minutes": active_duration.num_minutes()
-                        }),
-                    },
-                )
-                .await?;
+                alerts.push(RiskAlertInput {
+                    grant_id: grant.id,
+                    user_id: grant.user_id,
+                    contact_id: grant.emergency_contact_id,
+                    alert_type: "rapid_grant_revoke",
+                    severity: "medium",
+                    message: "Emergency access was revoked shortly after it was granted.",
+                    metadata: serde_json::json!({
+                        "granted_at": grant.created_at,
+                        "revoked_at": revoked_at,
+                        "active_duration_minutes": active_duration.num_minutes()
+                    }),
+                });
             }
         }
 
-        Ok(())
+        for alert in &alerts {
+            Self::create_risk_alert(&mut *executor, alert).await?;
+        }
+        Ok(alerts)
     }
 
     fn normalize_permissions(permissions: &[String]) -> Vec<String> {
@@ -3291,6 +3795,7 @@ impl EmergencyAccessService {
 
     pub async fn grant_access(
         pool: &PgPool,
+        alert_provider: &dyn AlertProvider,
         user_id: Uuid,
         req: &CreateEmergencyAccessGrantRequest,
     ) -> Result<EmergencyAccessActionResponse, ApiError> {
@@ -3343,9 +3848,10 @@ impl EmergencyAccessService {
         )
         .await?;
 
-        Self::evaluate_grant_risk(pool, &mut tx, &grant).await?;
+        let alerts = Self::evaluate_grant_risk(&mut tx, &grant).await?;
 
         tx.commit().await?;
+        Self::send_risk_alerts(pool, alert_provider, &alerts).await;
 
         Ok(EmergencyAccessActionResponse {
             success: true,
@@ -3356,6 +3862,7 @@ impl EmergencyAccessService {
 
     pub async fn revoke_access(
         pool: &PgPool,
+        alert_provider: &dyn AlertProvider,
         user_id: Uuid,
         grant_id: Uuid,
         req: &RevokeEmergencyAccessGrantRequest,
@@ -3434,12 +3941,13 @@ impl EmergencyAccessService {
         )
         .await?;
 
-        Self::evaluate_revoke_risk(pool, &mut tx, &updated).await?;
+        let alerts = Self::evaluate_revoke_risk(&mut tx, &updated).await?;
 
         // Auto-end all active sessions for this grant (Issue #306)
         EmergencySessionService::end_sessions_for_grant(&mut *tx, updated.id).await?;
 
         tx.commit().await?;
+        Self::send_risk_alerts(pool, alert_provider, &alerts).await;
 
         Ok(EmergencyAccessActionResponse {
             success: true,
@@ -3581,9 +4089,13 @@ impl EmergencyAccessService {
 pub struct EmergencyAdminService;
 
 impl EmergencyAdminService {
-    /// Pause a plan - prevents claims and other operations
+    /// Pause a plan - prevents claims and other operations.
+    ///
+    /// The DB pause is authoritative; once committed it is mirrored onto the
+    /// contract's plan freeze when the plan has a `contract_plan_id`.
     pub async fn pause_plan(
         pool: &PgPool,
+        freezer: &dyn OnChainPlanFreezer,
         admin_id: Uuid,
         req: &PausePlanRequest,
     ) -> Result<EmergencyActionResponse, ApiError> {
@@ -3613,6 +4125,13 @@ impl EmergencyAdminService {
                 paused_by = $1,
                 paused_at = NOW(),
                 pause_reason = $2,
+                chain_freeze_status = CASE
+                    WHEN contract_plan_id IS NULL THEN chain_freeze_status
+                    ELSE 'pending_freeze'
+                END,
+                chain_freeze_attempts = 0,
+                chain_freeze_next_attempt_at = NOW(),
+                chain_freeze_last_error = NULL,
                 updated_at = NOW()
             WHERE id = $3
             "#,
@@ -3651,16 +4170,32 @@ impl EmergencyAdminService {
 
         tx.commit().await?;
 
+        let chain_freeze = Self::mirror_on_chain(
+            pool,
+            freezer,
+            req.plan_id,
+            plan.contract_plan_id,
+            ChainFreezeStatus::PendingFreeze,
+        )
+        .await;
+        let message = if chain_freeze.is_some_and(ChainFreezeStatus::is_pending) {
+            "Plan paused; on-chain freeze is pending and will be retried"
+        } else {
+            "Plan paused successfully"
+        };
+
         Ok(EmergencyActionResponse {
             success: true,
             plan_id: req.plan_id,
-            message: "Plan paused successfully".to_string(),
+            message: message.to_string(),
+            chain_freeze,
         })
     }
 
-    /// Unpause a plan - restores normal operations
+    /// Unpause a plan - restores normal operations, lifting the mirrored on-chain freeze.
     pub async fn unpause_plan(
         pool: &PgPool,
+        freezer: &dyn OnChainPlanFreezer,
         admin_id: Uuid,
         req: &UnpausePlanRequest,
     ) -> Result<EmergencyActionResponse, ApiError> {
@@ -3690,6 +4225,13 @@ impl EmergencyAdminService {
                 paused_by = NULL,
                 paused_at = NULL,
                 pause_reason = NULL,
+                chain_freeze_status = CASE
+                    WHEN contract_plan_id IS NULL THEN chain_freeze_status
+                    ELSE 'pending_unfreeze'
+                END,
+                chain_freeze_attempts = 0,
+                chain_freeze_next_attempt_at = NOW(),
+                chain_freeze_last_error = NULL,
                 updated_at = NOW()
             WHERE id = $1
             "#,
@@ -3726,13 +4268,49 @@ impl EmergencyAdminService {
 
         tx.commit().await?;
 
+        let chain_freeze = Self::mirror_on_chain(
+            pool,
+            freezer,
+            req.plan_id,
+            plan.contract_plan_id,
+            ChainFreezeStatus::PendingUnfreeze,
+        )
+        .await;
+        let message = if chain_freeze.is_some_and(ChainFreezeStatus::is_pending) {
+            "Plan unpaused; on-chain unfreeze is pending and will be retried"
+        } else {
+            "Plan unpaused successfully"
+        };
+
         Ok(EmergencyActionResponse {
             success: true,
             plan_id: req.plan_id,
-            message: "Plan unpaused successfully".to_string(),
+            message: message.to_string(),
+            chain_freeze,
         })
     }
 
+    /// Submit the pause or unpause just committed for `plan_id` to the
+    /// contract. The DB pause is authoritative, so a failure here leaves the
+    /// action pending for `PlanFreezeRetryWorker` rather than failing the
+    /// request.
+    async fn mirror_on_chain(
+        pool: &PgPool,
+        freezer: &dyn OnChainPlanFreezer,
+        plan_id: Uuid,
+        contract_plan_id: Option<i64>,
+        pending: ChainFreezeStatus,
+    ) -> Option<ChainFreezeStatus> {
+        contract_plan_id?;
+        match sync_plan_freeze(pool, freezer, plan_id).await {
+            Ok(status) => status,
+            Err(e) => {
+                tracing::warn!(%plan_id, "Failed to record on-chain freeze outcome: {}", e);
+                Some(pending)
+            }
+        }
+    }
+
     /// Apply or remove risk override for a plan
     pub async fn set_risk_override(
         pool: &PgPool,
@@ -3834,6 +4412,7 @@ impl EmergencyAdminService {
             success: true,
             plan_id: req.plan_id,
             message: action_msg.to_string(),
+            chain_freeze: None,
         })
     }
 
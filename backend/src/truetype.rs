@@ -0,0 +1,496 @@
+//! # TrueType Reader and Subsetter
+//!
+//! Just enough of the TrueType format to embed a font in a PDF: character to
+//! glyph lookup (`cmap` formats 4 and 12), advance widths, the metrics a PDF
+//! font descriptor needs, and a subsetter that keeps only the glyphs a
+//! document uses.
+//!
+//! Subsetting keeps glyph ids unchanged. Unused glyphs are emptied rather
+//! than removed, so the subset can be addressed with an identity CID to GID
+//! map and composite glyphs need no renumbering. Only the tables a PDF
+//! consumer reads from an embedded `FontFile2` are written.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Tables copied unchanged into a subset. `head`, `loca` and `glyf` are
+/// rebuilt.
+const COPIED_TABLES: [&[u8; 4]; 6] = [b"cvt ", b"fpgm", b"hhea", b"hmtx", b"maxp", b"prep"];
+
+// Composite glyph component flags.
+const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+const WE_HAVE_A_SCALE: u16 = 0x0008;
+const MORE_COMPONENTS: u16 = 0x0020;
+const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_i16(data: &[u8], offset: usize) -> Option<i16> {
+    read_u16(data, offset).map(|v| v as i16)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Font-wide metrics in font units, as a PDF font descriptor needs them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontMetrics {
+    pub bbox: [i16; 4],
+    pub ascent: i16,
+    pub descent: i16,
+    pub cap_height: i16,
+    pub italic_angle: f32,
+}
+
+/// A parsed TrueType font borrowing its file data.
+#[derive(Debug)]
+pub struct TrueTypeFont<'a> {
+    tables: BTreeMap<[u8; 4], &'a [u8]>,
+    cmap: HashMap<u32, u16>,
+    units_per_em: u16,
+    num_glyphs: u16,
+    num_h_metrics: u16,
+    long_loca: bool,
+}
+
+impl<'a> TrueTypeFont<'a> {
+    /// Parse a TrueType (`glyf` outline) font. Returns `None` if a required
+    /// table is missing or truncated, or the font has no Unicode `cmap`.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut font = Self::parse_tables(data)?;
+        font.cmap = font.parse_cmap()?;
+        Some(font)
+    }
+
+    /// Read the table directory and the header fields every lookup needs.
+    fn parse_tables(data: &'a [u8]) -> Option<Self> {
+        let num_tables = read_u16(data, 4)? as usize;
+        let mut tables = BTreeMap::new();
+        for i in 0..num_tables {
+            let record = 12 + 16 * i;
+            let tag: [u8; 4] = data.get(record..record + 4)?.try_into().ok()?;
+            let offset = read_u32(data, record + 8)? as usize;
+            let length = read_u32(data, record + 12)? as usize;
+            tables.insert(tag, data.get(offset..offset.checked_add(length)?)?);
+        }
+
+        let head = *tables.get(b"head")?;
+        let hhea = *tables.get(b"hhea")?;
+        let maxp = *tables.get(b"maxp")?;
+        tables.get(b"hmtx")?;
+        tables.get(b"loca")?;
+        tables.get(b"glyf")?;
+
+        Some(Self {
+            cmap: HashMap::new(),
+            units_per_em: read_u16(head, 18)?,
+            num_glyphs: read_u16(maxp, 4)?,
+            num_h_metrics: read_u16(hhea, 34)?,
+            long_loca: read_i16(head, 50)? == 1,
+            tables,
+        })
+    }
+
+    fn table(&self, tag: &[u8; 4]) -> &'a [u8] {
+        // Presence of every table read here is checked by `parse`.
+        self.tables.get(tag).copied().unwrap_or_default()
+    }
+
+    /// Build the character map from the best Unicode subtable: full-range
+    /// format 12 if present, otherwise BMP format 4.
+    fn parse_cmap(&self) -> Option<HashMap<u32, u16>> {
+        let cmap = *self.tables.get(b"cmap")?;
+        let mut format4 = None;
+        let mut format12 = None;
+        for i in 0..read_u16(cmap, 2)? as usize {
+            let record = 4 + 8 * i;
+            let platform = read_u16(cmap, record)?;
+            let encoding = read_u16(cmap, record + 2)?;
+            let offset = read_u32(cmap, record + 4)? as usize;
+            let unicode = platform == 0 || (platform == 3 && (encoding == 1 || encoding == 10));
+            if !unicode {
+                continue;
+            }
+            match read_u16(cmap, offset)? {
+                4 => format4 = format4.or(Some(offset)),
+                12 => format12 = format12.or(Some(offset)),
+                _ => {}
+            }
+        }
+
+        let mut map = HashMap::new();
+        if let Some(sub) = format12 {
+            for group in 0..read_u32(cmap, sub + 12)? as usize {
+                let record = sub + 16 + 12 * group;
+                let start = read_u32(cmap, record)?;
+                let end = read_u32(cmap, record + 4)?;
+                let first_glyph = read_u32(cmap, record + 8)?;
+                // Glyph ids grow with the code, so nothing past the font's
+                // last glyph can map; this bounds groups with bogus ranges.
+                let span = (self.num_glyphs as u32).saturating_sub(first_glyph);
+                let last = end.min(start.saturating_add(span)).min(char::MAX as u32);
+                for code in start..=last {
+                    let glyph = first_glyph + (code - start);
+                    if glyph != 0 && glyph < self.num_glyphs as u32 {
+                        map.insert(code, glyph as u16);
+                    }
+                }
+            }
+        } else {
+            let sub = format4?;
+            let seg_x2 = read_u16(cmap, sub + 6)? as usize;
+            let ends = sub + 14;
+            let starts = ends + seg_x2 + 2;
+            let deltas = starts + seg_x2;
+            let range_offsets = deltas + seg_x2;
+            for seg in (0..seg_x2).step_by(2) {
+                let end = read_u16(cmap, ends + seg)?;
+                let start = read_u16(cmap, starts + seg)?;
+                let delta = read_u16(cmap, deltas + seg)?;
+                let range_offset = read_u16(cmap, range_offsets + seg)? as usize;
+                for code in start..=end {
+                    if code == 0xFFFF {
+                        break;
+                    }
+                    let glyph = if range_offset == 0 {
+                        code.wrapping_add(delta)
+                    } else {
+                        let at = range_offsets + seg + range_offset + 2 * (code - start) as usize;
+                        match read_u16(cmap, at)? {
+                            0 => 0,
+                            g => g.wrapping_add(delta),
+                        }
+                    };
+                    if glyph != 0 && glyph < self.num_glyphs {
+                        map.insert(code as u32, glyph);
+                    }
+                }
+            }
+        }
+        Some(map)
+    }
+
+    pub fn units_per_em(&self) -> u16 {
+        self.units_per_em
+    }
+
+    /// Glyph for `c`, or `None` when the font would fall back to `.notdef`.
+    pub fn glyph_id(&self, c: char) -> Option<u16> {
+        self.cmap.get(&(c as u32)).copied()
+    }
+
+    /// Advance width of `glyph` in font units.
+    pub fn advance_width(&self, glyph: u16) -> u16 {
+        let index = glyph.min(self.num_h_metrics.saturating_sub(1)) as usize;
+        read_u16(self.table(b"hmtx"), 4 * index).unwrap_or(0)
+    }
+
+    pub fn metrics(&self) -> FontMetrics {
+        let head = self.table(b"head");
+        let hhea = self.table(b"hhea");
+        let bbox = [36, 38, 40, 42].map(|offset| read_i16(head, offset).unwrap_or(0));
+        let ascent = read_i16(hhea, 4).unwrap_or(0);
+        // OS/2 only carries a cap height from version 2, so measure the
+        // outline of 'H' instead.
+        let cap_height = self
+            .glyph_id('H')
+            .and_then(|g| read_i16(self.glyph_data(g), 8))
+            .unwrap_or(ascent);
+        let italic_angle = self
+            .tables
+            .get(b"post")
+            .and_then(|post| read_u32(post, 4))
+            .map(|fixed| fixed as i32 as f32 / 65536.0)
+            .unwrap_or(0.0);
+        FontMetrics {
+            bbox,
+            ascent,
+            descent: read_i16(hhea, 6).unwrap_or(0),
+            cap_height,
+            italic_angle,
+        }
+    }
+
+    fn glyph_offset(&self, glyph: u16) -> usize {
+        let loca = self.table(b"loca");
+        let glyph = glyph as usize;
+        if self.long_loca {
+            read_u32(loca, 4 * glyph).unwrap_or(0) as usize
+        } else {
+            read_u16(loca, 2 * glyph).unwrap_or(0) as usize * 2
+        }
+    }
+
+    fn glyph_data(&self, glyph: u16) -> &'a [u8] {
+        if glyph >= self.num_glyphs {
+            return &[];
+        }
+        let start = self.glyph_offset(glyph);
+        let end = self.glyph_offset(glyph + 1);
+        self.table(b"glyf").get(start..end).unwrap_or_default()
+    }
+
+    /// Glyphs a composite glyph is assembled from.
+    fn components(&self, glyph: u16) -> Vec<u16> {
+        let data = self.glyph_data(glyph);
+        let mut components = Vec::new();
+        if !matches!(read_i16(data, 0), Some(contours) if contours < 0) {
+            return components;
+        }
+        let mut at = 10;
+        while let (Some(flags), Some(component)) = (read_u16(data, at), read_u16(data, at + 2)) {
+            components.push(component);
+            at += 4;
+            at += if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+                4
+            } else {
+                2
+            };
+            if flags & WE_HAVE_A_SCALE != 0 {
+                at += 2;
+            } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+                at += 4;
+            } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+                at += 8;
+            }
+            if flags & MORE_COMPONENTS == 0 {
+                break;
+            }
+        }
+        components
+    }
+
+    /// Font file keeping only `glyphs`, the components they are built from
+    /// and `.notdef`. Glyph ids are preserved.
+    pub fn subset(&self, glyphs: &BTreeSet<u16>) -> Vec<u8> {
+        let mut keep = BTreeSet::new();
+        let mut pending: Vec<u16> = glyphs.iter().copied().chain([0]).collect();
+        while let Some(glyph) = pending.pop() {
+            if glyph < self.num_glyphs && keep.insert(glyph) {
+                pending.extend(self.components(glyph));
+            }
+        }
+
+        let mut glyf = Vec::new();
+        let mut loca = Vec::new();
+        for glyph in 0..self.num_glyphs {
+            loca.extend_from_slice(&(glyf.len() as u32).to_be_bytes());
+            if keep.contains(&glyph) {
+                glyf.extend_from_slice(self.glyph_data(glyph));
+                glyf.resize(glyf.len().next_multiple_of(4), 0);
+            }
+        }
+        loca.extend_from_slice(&(glyf.len() as u32).to_be_bytes());
+
+        let mut head = self.table(b"head").to_vec();
+        head[8..12].fill(0);
+        head[50..52].copy_from_slice(&1i16.to_be_bytes());
+
+        let mut tables: BTreeMap<[u8; 4], Vec<u8>> = BTreeMap::new();
+        for tag in COPIED_TABLES {
+            if let Some(table) = self.tables.get(tag) {
+                tables.insert(*tag, table.to_vec());
+            }
+        }
+        tables.insert(*b"glyf", glyf);
+        tables.insert(*b"head", head);
+        tables.insert(*b"loca", loca);
+
+        write_sfnt(&tables)
+    }
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+/// Serialise `tables` (already in tag order) as a TrueType file and set
+/// `head.checkSumAdjustment` so the whole file sums to the magic value.
+fn write_sfnt(tables: &BTreeMap<[u8; 4], Vec<u8>>) -> Vec<u8> {
+    let num_tables = tables.len() as u16;
+    let entry_selector = 15 - num_tables.leading_zeros() as u16;
+    let search_range = 16u16 << entry_selector;
+
+    let mut font = Vec::new();
+    font.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+    for value in [
+        num_tables,
+        search_range,
+        entry_selector,
+        num_tables * 16 - search_range,
+    ] {
+        font.extend_from_slice(&value.to_be_bytes());
+    }
+
+    let mut offset = 12 + 16 * tables.len();
+    let mut head_offset = None;
+    for (tag, table) in tables {
+        if tag == b"head" {
+            head_offset = Some(offset);
+        }
+        font.extend_from_slice(tag);
+        font.extend_from_slice(&checksum(table).to_be_bytes());
+        font.extend_from_slice(&(offset as u32).to_be_bytes());
+        font.extend_from_slice(&(table.len() as u32).to_be_bytes());
+        offset += table.len().next_multiple_of(4);
+    }
+    for table in tables.values() {
+        font.extend_from_slice(table);
+        font.resize(font.len().next_multiple_of(4), 0);
+    }
+    if let Some(at) = head_offset {
+        let adjustment = 0xB1B0_AFBAu32.wrapping_sub(checksum(&font));
+        font[at + 8..at + 12].copy_from_slice(&adjustment.to_be_bytes());
+    }
+    font
+}
+
+// ─── Unit Tests ───────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERIF: &[u8] = include_bytes!("../assets/fonts/DejaVuSerif.ttf");
+
+    #[test]
+    fn test_cmap_lookup_and_widths() {
+        let font = TrueTypeFont::parse(SERIF).unwrap();
+        assert_eq!(font.units_per_em(), 2048);
+        let m = font.glyph_id('m').unwrap();
+        let i = font.glyph_id('i').unwrap();
+        assert!(font.advance_width(m) > font.advance_width(i));
+        assert!(font.glyph_id('\u{0141}').is_some());
+        assert!(font.glyph_id('\u{0416}').is_some());
+        assert_eq!(font.glyph_id('\u{4E2D}'), None);
+
+        let metrics = font.metrics();
+        assert!(metrics.ascent > metrics.cap_height && metrics.cap_height > 0);
+        assert!(metrics.descent < 0);
+        assert_eq!(metrics.italic_angle, 0.0);
+    }
+
+    #[test]
+    fn test_subset_keeps_used_glyphs_and_ids() {
+        let font = TrueTypeFont::parse(SERIF).unwrap();
+        let used: BTreeSet<u16> = "Zo\u{00EB} \u{0141}ukasz"
+            .chars()
+            .filter_map(|c| font.glyph_id(c))
+            .collect();
+        let bytes = font.subset(&used);
+        assert!(bytes.len() < SERIF.len() / 4);
+        assert_eq!(checksum(&bytes), 0xB1B0_AFBA);
+
+        // Subsets carry no cmap, so read them by glyph id.
+        let subset = TrueTypeFont::parse_tables(&bytes).unwrap();
+        for &glyph in &used {
+            assert_eq!(subset.glyph_data(glyph), font.glyph_data(glyph));
+            assert_eq!(subset.advance_width(glyph), font.advance_width(glyph));
+            for component in font.components(glyph) {
+                assert!(!subset.glyph_data(component).is_empty());
+            }
+        }
+        let unused = font.glyph_id('Q').unwrap();
+        assert!(subset.glyph_data(unused).is_empty());
+    }
+
+    /// Four-glyph font with empty outlines and a format 12 `cmap` holding
+    /// `groups` of `(start, end, first_glyph)`.
+    fn font_with_cmap_groups(groups: &[(u32, u32, u32)]) -> Vec<u8> {
+        let mut head = vec![0u8; 54];
+        head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+        head[50..52].copy_from_slice(&1i16.to_be_bytes());
+        let mut hhea = vec![0u8; 36];
+        hhea[34..36].copy_from_slice(&4u16.to_be_bytes());
+        let mut maxp = vec![0u8; 6];
+        maxp[4..6].copy_from_slice(&4u16.to_be_bytes());
+
+        let mut cmap = Vec::new();
+        for value in [0u16, 1, 3, 10] {
+            cmap.extend_from_slice(&value.to_be_bytes());
+        }
+        cmap.extend_from_slice(&12u32.to_be_bytes());
+        cmap.extend_from_slice(&[0, 12, 0, 0]);
+        let length = 16 + 12 * groups.len() as u32;
+        for value in [length, 0, groups.len() as u32] {
+            cmap.extend_from_slice(&value.to_be_bytes());
+        }
+        for &(start, end, first_glyph) in groups {
+            for value in [start, end, first_glyph] {
+                cmap.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+
+        let tables = BTreeMap::from([
+            (*b"cmap", cmap),
+            (*b"glyf", Vec::new()),
+            (*b"head", head),
+            (*b"hhea", hhea),
+            (*b"hmtx", vec![0u8; 16]),
+            (*b"loca", vec![0u8; 20]),
+            (*b"maxp", maxp),
+        ]);
+        write_sfnt(&tables)
+    }
+
+    #[test]
+    fn test_truncated_fonts_are_rejected() {
+        assert!(TrueTypeFont::parse(&[]).is_none());
+        assert!(TrueTypeFont::parse(&SERIF[..12]).is_none());
+        assert!(TrueTypeFont::parse(&SERIF[..SERIF.len() / 2]).is_none());
+        assert!(TrueTypeFont::parse(b"not a font at all").is_none());
+
+        let minimal = font_with_cmap_groups(&[(0x41, 0x42, 1)]);
+        assert!(TrueTypeFont::parse(&minimal).is_some());
+        assert!(TrueTypeFont::parse(&minimal[..minimal.len() - 4]).is_none());
+    }
+
+    #[test]
+    fn test_corrupt_table_directory_does_not_panic() {
+        let num_tables = read_u16(SERIF, 4).unwrap() as usize;
+        for field in 0..num_tables * 4 {
+            for value in [0u32, 1, 0x7FFF_FFFF, u32::MAX] {
+                let mut data = SERIF.to_vec();
+                let at = 12 + 4 * field;
+                data[at..at + 4].copy_from_slice(&value.to_be_bytes());
+                if let Some(font) = TrueTypeFont::parse(&data) {
+                    font.metrics();
+                    let glyphs: BTreeSet<u16> = "Hm\u{0141}"
+                        .chars()
+                        .filter_map(|c| font.glyph_id(c))
+                        .collect();
+                    for &glyph in &glyphs {
+                        font.advance_width(glyph);
+                    }
+                    font.subset(&glyphs);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cmap_groups_are_bounded_by_the_glyph_count() {
+        let data = font_with_cmap_groups(&[
+            (0x41, u32::MAX, 1),
+            (0x61, 0x60, 1),
+            (0x100, 0x200, u32::MAX),
+        ]);
+        let font = TrueTypeFont::parse(&data).unwrap();
+        assert_eq!(font.glyph_id('A'), Some(1));
+        assert_eq!(font.glyph_id('C'), Some(3));
+        assert_eq!(font.glyph_id('D'), None);
+        assert_eq!(font.glyph_id('a'), None);
+        assert_eq!(font.glyph_id('\u{100}'), None);
+        assert_eq!(font.advance_width(3), 0);
+        assert!(!font.subset(&BTreeSet::from([1, 2, 3])).is_empty());
+    }
+}
@@ -1,26 +1,69 @@
+//! User webhooks and their delivery pipeline.
+//!
+//! Events reach webhooks through the [outbox](crate::outbox) relay, which
+//! queues deliveries; the worker started by [`WebhookService::start`] sends
+//! them.
+//!
+//! Each event sent to a webhook is stored as a delivery in Postgres and every
+//! attempt is logged (status code, latency, response snippet). Failed
+//! deliveries are retried with exponential back-off and jitter by a
+//! background worker, dead-lettered after `MAX_DELIVERY_ATTEMPTS`, and a
+//! webhook that fails `DISABLE_AFTER_CONSECUTIVE_FAILURES` times in a row is
+//! disabled until its owner re-enables it.
+//!
+//! Requests carry `X-Webhook-Timestamp` and an HMAC-SHA256 signature over
+//! `"{timestamp}.{body}"`, so receivers can reject stale or replayed
+//! requests (see [`verify_signature`]).
+//!
+//! Webhook URLs must resolve to public addresses. They are checked when the
+//! webhook is registered and again before every attempt, each request is
+//! pinned to the address that passed the check, and redirects are not
+//! followed, so a webhook cannot be pointed at the internal network.
+
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
 use base64::engine::general_purpose;
 use base64::Engine as _;
+use chrono::{DateTime, Utc};
 use rand::Rng;
-use reqwest::Client;
+use reqwest::{redirect, Client, Url};
 use ring::hmac;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 use sqlx::PgPool;
-use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
-use tokio::sync::Mutex;
-use tracing::warn;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
 use crate::api_error::ApiError;
 use crate::app::AppState;
 use crate::auth::AuthenticatedUser;
 use crate::notifications::AuditLogService;
+use crate::pagination::PaginationQuery;
+use crate::webhook_events::{catalogue, is_known_event_type, EventTypeInfo, WebhookTest};
+pub use crate::webhook_events::{event_types, WebhookEvent};
+
+/// Attempts (including the first) before a delivery is dead-lettered.
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 8;
+/// Consecutive failed attempts after which a webhook is disabled.
+pub const DISABLE_AFTER_CONSECUTIVE_FAILURES: i32 = 15;
+/// How old a signature timestamp may be before receivers should reject it.
+pub const SIGNATURE_TOLERANCE_SECS: i64 = 300;
+
+const BASE_BACKOFF_SECS: u64 = 30;
+const MAX_BACKOFF_SECS: u64 = 6 * 60 * 60;
+const DELIVERY_TIMEOUT_SECS: u64 = 10;
+const RESPONSE_SNIPPET_LEN: usize = 512;
+const RETRY_BATCH_SIZE: i64 = 100;
+/// How often the worker picks up queued and due deliveries.
+const POLL_INTERVAL_SECS: u64 = 10;
+/// How long a claimed delivery is hidden from other workers.
+const CLAIM_LEASE_SECS: i64 = 300;
 
 #[derive(Serialize, Deserialize, sqlx::FromRow)]
 pub struct Webhook {
@@ -33,6 +76,7 @@ pub struct Webhook {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub last_delivery: Option<chrono::DateTime<chrono::Utc>>,
     pub failure_count: i32,
+    pub disabled_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Deserialize)]
@@ -41,29 +85,124 @@ pub struct CreateWebhookRequest {
     pub events: Vec<String>,
 }
 
-#[derive(Serialize)]
-pub struct WebhookEvent {
-    pub id: String,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Pending,
+    Retrying,
+    Succeeded,
+    DeadLettered,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Retrying => "retrying",
+            Self::Succeeded => "succeeded",
+            Self::DeadLettered => "dead_lettered",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub event_id: String,
     pub event_type: String,
-    pub data: serde_json::Value,
-    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub payload: Value,
+    pub status: String,
+    pub attempt_count: i32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub replay_of: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct WebhookDeliveryAttempt {
+    pub id: Uuid,
+    pub delivery_id: Uuid,
+    pub attempt: i32,
+    pub status_code: Option<i32>,
+    pub latency_ms: i32,
+    pub response_snippet: Option<String>,
+    pub error: Option<String>,
+    pub attempted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryDetail {
+    #[serde(flatten)]
+    pub delivery: WebhookDelivery,
+    pub attempts: Vec<WebhookDeliveryAttempt>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryFilter {
+    pub status: Option<DeliveryStatus>,
+}
+
+/// What one HTTP attempt produced.
+struct AttemptOutcome {
+    status_code: Option<i32>,
+    latency_ms: i32,
+    response_snippet: Option<String>,
+    error: Option<String>,
+}
+
+impl AttemptOutcome {
+    fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Where a delivery is sent: the URL's host and the public address that
+/// passed [`resolve_target`].
+struct DeliveryTarget {
+    host: String,
+    addr: SocketAddr,
 }
 
 pub struct WebhookService {
     db: PgPool,
-    client: Client,
-    retry_queue: Arc<Mutex<HashMap<Uuid, Vec<WebhookEvent>>>>,
+    allow_private_targets: bool,
 }
 
 impl WebhookService {
     pub fn new(db: PgPool) -> Self {
         Self {
             db,
-            client: Client::new(),
-            retry_queue: Arc::new(Mutex::new(HashMap::new())),
+            allow_private_targets: false,
         }
     }
 
+    /// Whether webhooks may target loopback, private and link-local
+    /// addresses. Off by default; only for local receivers in tests.
+    pub fn with_private_targets(mut self, allowed: bool) -> Self {
+        self.allow_private_targets = allowed;
+        self
+    }
+
+    /// Spawn the worker that sends queued deliveries and retries due ones.
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(POLL_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                match self.retry_failed_deliveries().await {
+                    Ok(0) => {}
+                    Ok(attempted) => info!("Attempted {} webhook deliveries", attempted),
+                    Err(e) => error!("Webhook retry run failed: {}", e),
+                }
+            }
+        });
+    }
+
     pub async fn register_webhook(
         &self,
         user_id: Uuid,
@@ -74,6 +213,15 @@ impl WebhookService {
                 "At least one event type required".to_string(),
             ));
         }
+        if let Some(unknown) = request.events.iter().find(|e| !is_known_event_type(e)) {
+            return Err(ApiError::BadRequest(format!(
+                "Unknown event type '{}'; see /api/webhooks/event-types",
+                unknown
+            )));
+        }
+        resolve_target(&request.url, self.allow_private_targets)
+            .await
+            .map_err(ApiError::BadRequest)?;
 
         let secret = self.generate_secret();
         let webhook_id = Uuid::new_v4();
@@ -112,7 +260,7 @@ impl WebhookService {
 
     pub async fn get_webhooks(&self, user_id: Uuid) -> Result<Vec<Webhook>, ApiError> {
         let webhooks = sqlx::query_as::<_, Webhook>(
-            "SELECT * FROM webhooks WHERE user_id = $1 AND is_active = true",
+            "SELECT * FROM webhooks WHERE user_id = $1 AND (is_active = true OR disabled_at IS NOT NULL)",
         )
         .bind(user_id)
         .fetch_all(&self.db)
@@ -122,16 +270,20 @@ impl WebhookService {
     }
 
     pub async fn delete_webhook(&self, user_id: Uuid, webhook_id: Uuid) -> Result<(), ApiError> {
-        let result =
-            sqlx::query("UPDATE webhooks SET is_active = false WHERE id = $1 AND user_id = $2")
-                .bind(webhook_id)
-                .bind(user_id)
-                .execute(&self.db)
-                .await?;
+        let mut tx = self.db.begin().await?;
+        let result = sqlx::query(
+            "UPDATE webhooks SET is_active = false, disabled_at = NULL WHERE id = $1 AND user_id = $2",
+        )
+        .bind(webhook_id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
 
         if result.rows_affected() == 0 {
             return Err(ApiError::NotFound("Webhook not found".to_string()));
         }
+        Self::dead_letter_outstanding(&mut tx, webhook_id).await?;
+        tx.commit().await?;
 
         AuditLogService::log(
             &self.db,
@@ -149,81 +301,536 @@ impl WebhookService {
         Ok(())
     }
 
-    pub async fn deliver_event(&self, event: WebhookEvent) -> Result<(), ApiError> {
-        let webhooks = sqlx::query_as::<_, Webhook>(
-            "SELECT * FROM webhooks WHERE is_active = true AND $1 = ANY(events)",
+    /// Re-enable a webhook that was disabled after repeated failures.
+    pub async fn enable_webhook(
+        &self,
+        user_id: Uuid,
+        webhook_id: Uuid,
+    ) -> Result<Webhook, ApiError> {
+        let webhook = sqlx::query_as::<_, Webhook>(
+            r#"
+            UPDATE webhooks
+            SET is_active = true, disabled_at = NULL, failure_count = 0
+            WHERE id = $1 AND user_id = $2 AND disabled_at IS NOT NULL
+            RETURNING *
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(user_id)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Disabled webhook not found".to_string()))?;
+
+        AuditLogService::log(
+            &self.db,
+            Some(user_id),
+            None,
+            "webhook_enabled",
+            Some(webhook_id),
+            Some("webhook"),
+            None,
+            None,
+            None,
         )
-        .bind(&event.event_type)
-        .fetch_all(&self.db)
         .await?;
 
-        for webhook in webhooks {
-            if let Err(e) = self.deliver_to_webhook(&webhook, &event).await {
-                warn!("Failed to deliver webhook {}: {}", webhook.id, e);
-                self.handle_delivery_failure(webhook.id).await?;
-            }
-        }
+        Ok(webhook)
+    }
 
-        Ok(())
+    /// Insert a delivery that the caller is about to attempt inline. The row
+    /// starts out leased, so the worker leaves it alone unless the inline
+    /// attempt never records its outcome.
+    async fn enqueue_delivery(
+        &self,
+        webhook_id: Uuid,
+        event_id: &str,
+        event_type: &str,
+        payload: &Value,
+        replay_of: Option<Uuid>,
+    ) -> Result<WebhookDelivery, ApiError> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            INSERT INTO webhook_deliveries (webhook_id, event_id, event_type, payload, status, next_attempt_at, replay_of)
+            VALUES ($1, $2, $3, $4, 'pending', NOW() + make_interval(secs => $6), $5)
+            RETURNING *
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(event_id)
+        .bind(event_type)
+        .bind(payload)
+        .bind(replay_of)
+        .bind(CLAIM_LEASE_SECS as f64)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(delivery)
     }
 
-    async fn deliver_to_webhook(
+    /// Send one attempt of `delivery` and record its outcome.
+    async fn attempt_delivery(
         &self,
         webhook: &Webhook,
-        event: &WebhookEvent,
-    ) -> Result<(), ApiError> {
-        let payload = serde_json::to_string(event).map_err(|e| ApiError::Internal(e.into()))?;
-        let signature = self.generate_signature(&webhook.secret, &payload);
+        delivery: &WebhookDelivery,
+    ) -> Result<DeliveryStatus, ApiError> {
+        let outcome = self.send(webhook, delivery).await;
+        let attempt = delivery.attempt_count + 1;
 
-        let response = self
-            .client
+        let mut tx = self.db.begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_delivery_attempts (delivery_id, attempt, status_code, latency_ms, response_snippet, error)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(delivery.id)
+        .bind(attempt)
+        .bind(outcome.status_code)
+        .bind(outcome.latency_ms)
+        .bind(&outcome.response_snippet)
+        .bind(&outcome.error)
+        .execute(&mut *tx)
+        .await?;
+
+        let status = if outcome.succeeded() {
+            sqlx::query(
+                "UPDATE webhooks SET last_delivery = NOW(), failure_count = 0 WHERE id = $1",
+            )
+            .bind(webhook.id)
+            .execute(&mut *tx)
+            .await?;
+            DeliveryStatus::Succeeded
+        } else {
+            let failure_count: i32 = sqlx::query_scalar(
+                "UPDATE webhooks SET failure_count = failure_count + 1 WHERE id = $1 RETURNING failure_count",
+            )
+            .bind(webhook.id)
+            .fetch_one(&mut *tx)
+            .await?;
+            warn!(
+                "Webhook {} delivery {} attempt {} failed: {}",
+                webhook.id,
+                delivery.id,
+                attempt,
+                outcome.error.as_deref().unwrap_or_default()
+            );
+            if failure_count >= DISABLE_AFTER_CONSECUTIVE_FAILURES {
+                Self::disable_webhook(&mut tx, webhook, failure_count).await?;
+            }
+            if attempt >= MAX_DELIVERY_ATTEMPTS {
+                DeliveryStatus::DeadLettered
+            } else {
+                DeliveryStatus::Retrying
+            }
+        };
+
+        let next_attempt_at = (status == DeliveryStatus::Retrying).then(|| {
+            let delay = backoff_delay(attempt as u32, rand::thread_rng().gen::<f64>());
+            Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default()
+        });
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = CASE WHEN status = 'dead_lettered' THEN status ELSE $2 END,
+                attempt_count = $3,
+                next_attempt_at = CASE WHEN status = 'dead_lettered' THEN NULL ELSE $4 END,
+                last_status_code = $5,
+                last_error = $6,
+                delivered_at = CASE WHEN $2 = 'succeeded' THEN NOW() ELSE delivered_at END,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(delivery.id)
+        .bind(status.as_str())
+        .bind(attempt)
+        .bind(next_attempt_at)
+        .bind(outcome.status_code)
+        .bind(&outcome.error)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(status)
+    }
+
+    async fn send(&self, webhook: &Webhook, delivery: &WebhookDelivery) -> AttemptOutcome {
+        let payload = delivery.payload.to_string();
+        let timestamp = Utc::now().timestamp();
+        let signature = sign_payload(&webhook.secret, timestamp, &payload);
+
+        let started = Instant::now();
+        let client = match resolve_target(&webhook.url, self.allow_private_targets)
+            .await
+            .and_then(|target| {
+                Client::builder()
+                    .timeout(Duration::from_secs(DELIVERY_TIMEOUT_SECS))
+                    .redirect(redirect::Policy::none())
+                    .resolve(&target.host, target.addr)
+                    .build()
+                    .map_err(|e| e.to_string())
+            }) {
+            Ok(client) => client,
+            Err(e) => {
+                return AttemptOutcome {
+                    status_code: None,
+                    latency_ms: elapsed_ms(started),
+                    response_snippet: None,
+                    error: Some(e),
+                }
+            }
+        };
+        let result = client
             .post(&webhook.url)
             .header("Content-Type", "application/json")
             .header("X-Webhook-Signature", signature)
+            .header("X-Webhook-Timestamp", timestamp.to_string())
             .header("X-Webhook-ID", webhook.id.to_string())
+            .header("X-Webhook-Delivery-ID", delivery.id.to_string())
+            .header("X-Webhook-Event-ID", &delivery.event_id)
             .body(payload)
             .send()
-            .await
-            .map_err(|e| ApiError::Internal(e.into()))?;
+            .await;
 
-        if !response.status().is_success() {
-            return Err(ApiError::ExternalService(format!(
-                "Webhook delivery failed: {}",
-                response.status(),
-            )));
+        match result {
+            Ok(response) => {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                AttemptOutcome {
+                    status_code: Some(status.as_u16() as i32),
+                    latency_ms: elapsed_ms(started),
+                    response_snippet: Some(truncate_snippet(&body)).filter(|s| !s.is_empty()),
+                    error: (!status.is_success())
+                        .then(|| format!("Receiver responded with {}", status)),
+                }
+            }
+            Err(e) => AttemptOutcome {
+                status_code: None,
+                latency_ms: elapsed_ms(started),
+                response_snippet: None,
+                error: Some(e.to_string()),
+            },
         }
+    }
 
-        // Update last delivery
-        sqlx::query("UPDATE webhooks SET last_delivery = NOW() WHERE id = $1")
-            .bind(webhook.id)
-            .execute(&self.db)
-            .await?;
+    /// Disable a webhook that keeps failing and dead-letter what it still owes.
+    async fn disable_webhook(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        webhook: &Webhook,
+        failure_count: i32,
+    ) -> Result<(), ApiError> {
+        let disabled = sqlx::query(
+            "UPDATE webhooks SET is_active = false, disabled_at = NOW() WHERE id = $1 AND is_active = true",
+        )
+        .bind(webhook.id)
+        .execute(&mut **tx)
+        .await?;
+        if disabled.rows_affected() == 0 {
+            return Ok(());
+        }
+        Self::dead_letter_outstanding(tx, webhook.id).await?;
+
+        warn!(
+            "Disabled webhook {} after {} consecutive failures",
+            webhook.id, failure_count
+        );
+        AuditLogService::log(
+            &mut **tx,
+            Some(webhook.user_id),
+            None,
+            "webhook_disabled",
+            Some(webhook.id),
+            Some("webhook"),
+            None,
+            None,
+            Some(json!({ "consecutive_failures": failure_count })),
+        )
+        .await
+    }
 
+    async fn dead_letter_outstanding(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        webhook_id: Uuid,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'dead_lettered', next_attempt_at = NULL, updated_at = NOW()
+            WHERE webhook_id = $1 AND status IN ('pending', 'retrying')
+            "#,
+        )
+        .bind(webhook_id)
+        .execute(&mut **tx)
+        .await?;
         Ok(())
     }
 
-    async fn handle_delivery_failure(&self, webhook_id: Uuid) -> Result<(), ApiError> {
-        let failure_count: i32 = sqlx::query_scalar(
-            "UPDATE webhooks SET failure_count = failure_count + 1 WHERE id = $1 RETURNING failure_count",
+    /// Attempt every delivery whose retry is due, returning how many were
+    /// attempted. Due rows are leased first so concurrent workers (or a
+    /// restart mid-run) never send the same attempt twice. A delivery that
+    /// cannot be attempted is logged and retried once its lease expires;
+    /// the rest of the batch still goes out.
+    pub async fn retry_failed_deliveries(&self) -> Result<usize, ApiError> {
+        let due = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            UPDATE webhook_deliveries
+            SET next_attempt_at = NOW() + make_interval(secs => $2)
+            WHERE id IN (
+                SELECT d.id
+                FROM webhook_deliveries d
+                JOIN webhooks w ON w.id = d.webhook_id
+                WHERE d.status IN ('pending', 'retrying')
+                  AND d.next_attempt_at <= NOW()
+                  AND w.is_active = true
+                ORDER BY d.next_attempt_at
+                LIMIT $1
+                FOR UPDATE OF d SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+        )
+        .bind(RETRY_BATCH_SIZE)
+        .bind(CLAIM_LEASE_SECS as f64)
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut attempted = 0;
+        for delivery in due {
+            let webhook = match sqlx::query_as::<_, Webhook>(
+                "SELECT * FROM webhooks WHERE id = $1 AND is_active = true",
+            )
+            .bind(delivery.webhook_id)
+            .fetch_optional(&self.db)
+            .await
+            {
+                Ok(Some(webhook)) => webhook,
+                Ok(None) => continue,
+                Err(e) => {
+                    error!(
+                        "Failed to load webhook {} for delivery {}: {}",
+                        delivery.webhook_id, delivery.id, e
+                    );
+                    continue;
+                }
+            };
+            match self.attempt_delivery(&webhook, &delivery).await {
+                Ok(_) => attempted += 1,
+                Err(e) => error!(
+                    "Failed to record webhook {} delivery {} attempt: {}",
+                    webhook.id, delivery.id, e
+                ),
+            }
+        }
+
+        Ok(attempted)
+    }
+
+    /// A page of a webhook's deliveries, newest first, with the total count.
+    pub async fn list_deliveries(
+        &self,
+        user_id: Uuid,
+        webhook_id: Uuid,
+        status: Option<DeliveryStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<WebhookDelivery>, i64), ApiError> {
+        self.require_owned_webhook(user_id, webhook_id).await?;
+        let status = status.map(DeliveryStatus::as_str);
+
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT * FROM webhook_deliveries
+            WHERE webhook_id = $1 AND ($2::text IS NULL OR status = $2)
+            ORDER BY created_at DESC
+            LIMIT $3 OFFSET $4
+            "#,
         )
         .bind(webhook_id)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db)
+        .await?;
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM webhook_deliveries WHERE webhook_id = $1 AND ($2::text IS NULL OR status = $2)",
+        )
+        .bind(webhook_id)
+        .bind(status)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok((deliveries, total))
+    }
+
+    /// A page of the user's dead-lettered deliveries across all webhooks.
+    pub async fn list_dead_letters(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<WebhookDelivery>, i64), ApiError> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT d.* FROM webhook_deliveries d
+            JOIN webhooks w ON w.id = d.webhook_id
+            WHERE w.user_id = $1 AND d.status = 'dead_lettered'
+            ORDER BY d.updated_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db)
+        .await?;
+        let total: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM webhook_deliveries d
+            JOIN webhooks w ON w.id = d.webhook_id
+            WHERE w.user_id = $1 AND d.status = 'dead_lettered'
+            "#,
+        )
+        .bind(user_id)
         .fetch_one(&self.db)
         .await?;
 
-        if failure_count >= 5 {
-            // Deactivate webhook after 5 failures
-            sqlx::query("UPDATE webhooks SET is_active = false WHERE id = $1")
-                .bind(webhook_id)
-                .execute(&self.db)
+        Ok((deliveries, total))
+    }
+
+    /// A delivery with its full attempt log.
+    pub async fn get_delivery(
+        &self,
+        user_id: Uuid,
+        webhook_id: Uuid,
+        delivery_id: Uuid,
+    ) -> Result<WebhookDeliveryDetail, ApiError> {
+        let delivery = self
+            .owned_delivery(user_id, webhook_id, delivery_id)
+            .await?;
+        let attempts = sqlx::query_as::<_, WebhookDeliveryAttempt>(
+            "SELECT * FROM webhook_delivery_attempts WHERE delivery_id = $1 ORDER BY attempt",
+        )
+        .bind(delivery_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(WebhookDeliveryDetail { delivery, attempts })
+    }
+
+    /// Send a delivery's event again as a new delivery (with a fresh retry
+    /// budget) and make its first attempt.
+    pub async fn resend_delivery(
+        &self,
+        user_id: Uuid,
+        webhook_id: Uuid,
+        delivery_id: Uuid,
+    ) -> Result<WebhookDelivery, ApiError> {
+        let original = self
+            .owned_delivery(user_id, webhook_id, delivery_id)
+            .await?;
+        let webhook = self.require_owned_webhook(user_id, webhook_id).await?;
+        if !webhook.is_active {
+            return Err(ApiError::BadRequest(
+                "Webhook is not active; re-enable it before re-sending".to_string(),
+            ));
+        }
+
+        let delivery = self
+            .enqueue_delivery(
+                webhook.id,
+                &original.event_id,
+                &original.event_type,
+                &original.payload,
+                Some(original.id),
+            )
+            .await?;
+        self.attempt_delivery(&webhook, &delivery).await?;
+
+        AuditLogService::log(
+            &self.db,
+            Some(user_id),
+            None,
+            "webhook_delivery_resent",
+            Some(delivery.id),
+            Some("webhook_delivery"),
+            None,
+            None,
+            Some(json!({ "replay_of": original.id, "event_id": original.event_id })),
+        )
+        .await?;
+
+        let delivery =
+            sqlx::query_as::<_, WebhookDelivery>("SELECT * FROM webhook_deliveries WHERE id = $1")
+                .bind(delivery.id)
+                .fetch_one(&self.db)
                 .await?;
-        } else {
-            // Add to retry queue
-            let _queue = self.retry_queue.lock().await;
-            // For simplicity, just increment failure count
-            // In production, implement proper retry logic with exponential backoff
+        Ok(delivery)
+    }
+
+    /// Send a `webhook.test` event to one of the user's webhooks, whether or
+    /// not it subscribes to that type, and return the recorded delivery.
+    pub async fn send_test_event(
+        &self,
+        user_id: Uuid,
+        webhook_id: Uuid,
+    ) -> Result<WebhookDelivery, ApiError> {
+        let webhook = self.require_owned_webhook(user_id, webhook_id).await?;
+        if !webhook.is_active {
+            return Err(ApiError::BadRequest(
+                "Webhook is not active; re-enable it before testing".to_string(),
+            ));
         }
 
-        Ok(())
+        let event = WebhookEvent::new(&WebhookTest {
+            webhook_id,
+            message: "Test event sent from the InheritX API".to_string(),
+        })?;
+        let payload = serde_json::to_value(&event).map_err(|e| ApiError::Internal(e.into()))?;
+        let delivery = self
+            .enqueue_delivery(webhook.id, &event.id, &event.event_type, &payload, None)
+            .await?;
+        self.attempt_delivery(&webhook, &delivery).await?;
+
+        let delivery =
+            sqlx::query_as::<_, WebhookDelivery>("SELECT * FROM webhook_deliveries WHERE id = $1")
+                .bind(delivery.id)
+                .fetch_one(&self.db)
+                .await?;
+        Ok(delivery)
+    }
+
+    async fn require_owned_webhook(
+        &self,
+        user_id: Uuid,
+        webhook_id: Uuid,
+    ) -> Result<Webhook, ApiError> {
+        sqlx::query_as::<_, Webhook>("SELECT * FROM webhooks WHERE id = $1 AND user_id = $2")
+            .bind(webhook_id)
+            .bind(user_id)
+            .fetch_optional(&self.db)
+            .await?
+            .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))
+    }
+
+    async fn owned_delivery(
+        &self,
+        user_id: Uuid,
+        webhook_id: Uuid,
+        delivery_id: Uuid,
+    ) -> Result<WebhookDelivery, ApiError> {
+        sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT d.* FROM webhook_deliveries d
+            JOIN webhooks w ON w.id = d.webhook_id
+            WHERE d.id = $1 AND d.webhook_id = $2 AND w.user_id = $3
+            "#,
+        )
+        .bind(delivery_id)
+        .bind(webhook_id)
+        .bind(user_id)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Webhook delivery not found".to_string()))
     }
 
     fn generate_secret(&self) -> String {
@@ -234,29 +841,122 @@ impl WebhookService {
             .collect();
         secret
     }
+}
+
+/// Delay before retrying after `attempt` failed attempts: exponential from
+/// `BASE_BACKOFF_SECS`, capped at `MAX_BACKOFF_SECS`, with the upper half
+/// randomised by `jitter` (in `[0, 1)`) so receivers recovering from an
+/// outage are not hit by every retry at once.
+pub fn backoff_delay(attempt: u32, jitter: f64) -> Duration {
+    let exp = BASE_BACKOFF_SECS.saturating_mul(1u64 << attempt.saturating_sub(1).min(20));
+    let capped = exp.min(MAX_BACKOFF_SECS) as f64;
+    Duration::from_secs_f64(capped / 2.0 + capped / 2.0 * jitter.clamp(0.0, 1.0))
+}
+
+/// The `X-Webhook-Signature` value for `payload` sent at `timestamp`.
+pub fn sign_payload(secret: &str, timestamp: i64, payload: &str) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, format!("{}.{}", timestamp, payload).as_bytes());
+    format!("sha256={}", general_purpose::STANDARD.encode(tag.as_ref()))
+}
 
-    fn generate_signature(&self, secret: &str, payload: &str) -> String {
-        let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
-        let tag = hmac::sign(&key, payload.as_bytes());
-        format!("sha256={}", general_purpose::STANDARD.encode(tag.as_ref()),)
+/// Check a received webhook: the signature must match and `timestamp` must be
+/// within `SIGNATURE_TOLERANCE_SECS` of `now`, so captured requests cannot be
+/// replayed later.
+pub fn verify_signature(
+    secret: &str,
+    timestamp: i64,
+    payload: &str,
+    signature: &str,
+    now: i64,
+) -> bool {
+    if (now - timestamp).abs() > SIGNATURE_TOLERANCE_SECS {
+        return false;
     }
+    let Some(tag) = signature
+        .strip_prefix("sha256=")
+        .and_then(|b64| general_purpose::STANDARD.decode(b64).ok())
+    else {
+        return false;
+    };
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::verify(&key, format!("{}.{}", timestamp, payload).as_bytes(), &tag).is_ok()
+}
 
-    pub async fn retry_failed_deliveries(&self) -> Result<(), ApiError> {
-        // Implement retry logic for failed deliveries
-        // This would check the retry queue and attempt redelivery
-        Ok(())
+/// Check that `url` is an http(s) URL whose host resolves only to public
+/// addresses (unless `allow_private`), returning the address to connect to.
+async fn resolve_target(url: &str, allow_private: bool) -> Result<DeliveryTarget, String> {
+    let parsed = Url::parse(url).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("Webhook URL must use http or https".to_string());
     }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| "Webhook URL has no host".to_string())?
+        .to_string();
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let lookup = host.trim_start_matches('[').trim_end_matches(']');
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((lookup, port))
+        .await
+        .map_err(|e| format!("Could not resolve webhook host '{}': {}", host, e))?
+        .collect();
+    if !allow_private {
+        if let Some(blocked) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+            return Err(format!(
+                "Webhook host '{}' resolves to a non-public address ({})",
+                host,
+                blocked.ip()
+            ));
+        }
+    }
+    let addr = addrs
+        .first()
+        .copied()
+        .ok_or_else(|| format!("Could not resolve webhook host '{}'", host))?;
+    Ok(DeliveryTarget { host, addr })
 }
 
-// Event types
-pub mod event_types {
-    pub const PLAN_CREATED: &str = "plan.created";
-    pub const PLAN_CLAIMED: &str = "plan.claimed";
-    pub const LOAN_DISBURSED: &str = "loan.disbursed";
-    pub const LOAN_REPAID: &str = "loan.repaid";
-    pub const KYC_SUBMITTED: &str = "kyc.submitted";
-    pub const KYC_APPROVED: &str = "kyc.approved";
-    pub const KYC_REJECTED: &str = "kyc.rejected";
+/// False for loopback, private, link-local, shared (CGNAT), unspecified,
+/// broadcast, multicast and documentation addresses, including IPv4 ones
+/// wrapped in IPv6.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || v4.is_documentation()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_public_ip(IpAddr::V4(v4)),
+            None => {
+                let first = v6.segments()[0];
+                !(v6.is_loopback()
+                    || v6.is_unspecified()
+                    || v6.is_multicast()
+                    || (first & 0xfe00) == 0xfc00 // unique local
+                    || (first & 0xffc0) == 0xfe80) // link-local
+            }
+        },
+    }
+}
+
+fn truncate_snippet(body: &str) -> String {
+    match body.char_indices().nth(RESPONSE_SNIPPET_LEN) {
+        Some((end, _)) => body[..end].to_string(),
+        None => body.to_string(),
+    }
+}
+
+fn elapsed_ms(started: Instant) -> i32 {
+    started.elapsed().as_millis().min(i32::MAX as u128) as i32
 }
 
 // API handlers
@@ -264,15 +964,12 @@ pub async fn register_webhook(
     State(state): State<Arc<AppState>>,
     AuthenticatedUser(user): AuthenticatedUser,
     Json(request): Json<CreateWebhookRequest>,
-) -> Result<Json<Webhook>, (StatusCode, String)> {
-    match state
+) -> Result<Json<Webhook>, ApiError> {
+    let webhook = state
         .webhook_service
         .register_webhook(user.user_id, request)
-        .await
-    {
-        Ok(webhook) => Ok(Json(webhook)),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())),
-    }
+        .await?;
+    Ok(Json(webhook))
 }
 
 pub async fn get_webhooks(
@@ -299,3 +996,230 @@ pub async fn delete_webhook(
         Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())),
     }
 }
+
+pub async fn enable_webhook(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<Webhook>, ApiError> {
+    let webhook = state
+        .webhook_service
+        .enable_webhook(user.user_id, webhook_id)
+        .await?;
+    Ok(Json(webhook))
+}
+
+pub async fn list_webhook_deliveries(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(webhook_id): Path<Uuid>,
+    Query(filter): Query<DeliveryFilter>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let (_page, limit, offset) = pagination.normalize();
+    let (deliveries, total_count) = state
+        .webhook_service
+        .list_deliveries(
+            user.user_id,
+            webhook_id,
+            filter.status,
+            limit as i64,
+            offset,
+        )
+        .await?;
+    Ok(Json(json!(
+        pagination.create_response(deliveries, total_count)
+    )))
+}
+
+pub async fn list_webhook_dead_letters(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let (_page, limit, offset) = pagination.normalize();
+    let (deliveries, total_count) = state
+        .webhook_service
+        .list_dead_letters(user.user_id, limit as i64, offset)
+        .await?;
+    Ok(Json(json!(
+        pagination.create_response(deliveries, total_count)
+    )))
+}
+
+pub async fn get_webhook_delivery(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path((webhook_id, delivery_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<WebhookDeliveryDetail>, ApiError> {
+    let detail = state
+        .webhook_service
+        .get_delivery(user.user_id, webhook_id, delivery_id)
+        .await?;
+    Ok(Json(detail))
+}
+
+pub async fn resend_webhook_delivery(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path((webhook_id, delivery_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<WebhookDelivery>, ApiError> {
+    let delivery = state
+        .webhook_service
+        .resend_delivery(user.user_id, webhook_id, delivery_id)
+        .await?;
+    Ok(Json(delivery))
+}
+
+pub async fn list_event_types() -> Json<Vec<EventTypeInfo>> {
+    Json(catalogue())
+}
+
+pub async fn send_test_webhook(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<WebhookDelivery>, ApiError> {
+    let delivery = state
+        .webhook_service
+        .send_test_event(user.user_id, webhook_id)
+        .await?;
+    Ok(Json(delivery))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_exponentially_and_is_capped() {
+        assert_eq!(
+            backoff_delay(1, 1.0),
+            Duration::from_secs(BASE_BACKOFF_SECS)
+        );
+        assert_eq!(
+            backoff_delay(3, 1.0),
+            Duration::from_secs(BASE_BACKOFF_SECS * 4)
+        );
+        assert_eq!(
+            backoff_delay(40, 1.0),
+            Duration::from_secs(MAX_BACKOFF_SECS)
+        );
+    }
+
+    #[test]
+    fn backoff_jitter_stays_in_upper_half() {
+        let low = backoff_delay(4, 0.0);
+        let high = backoff_delay(4, 0.999);
+        assert_eq!(low, Duration::from_secs(BASE_BACKOFF_SECS * 4));
+        assert!(high > low && high < Duration::from_secs(BASE_BACKOFF_SECS * 8));
+    }
+
+    #[test]
+    fn signature_round_trips_within_tolerance() {
+        let payload = r#"{"id":"evt_1"}"#;
+        let signature = sign_payload("secret", 1_700_000_000, payload);
+
+        assert!(verify_signature(
+            "secret",
+            1_700_000_000,
+            payload,
+            &signature,
+            1_700_000_100
+        ));
+        assert!(!verify_signature(
+            "other",
+            1_700_000_000,
+            payload,
+            &signature,
+            1_700_000_100
+        ));
+        assert!(!verify_signature(
+            "secret",
+            1_700_000_000,
+            "{}",
+            &signature,
+            1_700_000_100
+        ));
+        // Same body and signature replayed under a newer timestamp.
+        assert!(!verify_signature(
+            "secret",
+            1_700_000_200,
+            payload,
+            &signature,
+            1_700_000_200
+        ));
+    }
+
+    #[test]
+    fn stale_signatures_are_rejected() {
+        let payload = "{}";
+        let signature = sign_payload("secret", 1_700_000_000, payload);
+        let too_late = 1_700_000_000 + SIGNATURE_TOLERANCE_SECS + 1;
+        assert!(!verify_signature(
+            "secret",
+            1_700_000_000,
+            payload,
+            &signature,
+            too_late
+        ));
+    }
+
+    #[test]
+    fn internal_addresses_are_not_public() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(
+                !is_public_ip(ip.parse().unwrap()),
+                "{} should be blocked",
+                ip
+            );
+        }
+        for ip in ["93.184.216.34", "2606:2800:220:1:248:1893:25c8:1946"] {
+            assert!(
+                is_public_ip(ip.parse().unwrap()),
+                "{} should be allowed",
+                ip
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn internal_webhook_targets_are_rejected() {
+        for url in [
+            "http://127.0.0.1:9/webhook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/webhook",
+            "ftp://93.184.216.34/webhook",
+            "not a url",
+        ] {
+            assert!(resolve_target(url, false).await.is_err(), "{}", url);
+        }
+
+        let target = resolve_target("http://127.0.0.1:9/webhook", true)
+            .await
+            .unwrap();
+        assert_eq!(target.addr, "127.0.0.1:9".parse().unwrap());
+    }
+
+    #[test]
+    fn response_snippets_are_truncated_on_char_boundaries() {
+        let body = "é".repeat(RESPONSE_SNIPPET_LEN + 10);
+        assert_eq!(
+            truncate_snippet(&body).chars().count(),
+            RESPONSE_SNIPPET_LEN
+        );
+        assert_eq!(truncate_snippet("ok"), "ok");
+    }
+}
@@ -0,0 +1,612 @@
+//! Webhook event catalogue and the dispatcher that fans events out to webhooks.
+//!
+//! Every event type has a versioned payload struct and a JSON Schema for the
+//! `data` object it delivers; both are listed by
+//! `GET /api/webhooks/event-types`. Domain services record events with
+//! [`Outbox::publish`](crate::outbox::Outbox::publish) in the transaction of
+//! the change that raised them; the outbox relay queues a delivery for each
+//! of the user's subscribed webhooks and the webhook worker in
+//! [`crate::webhook`] sends it.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::api_error::ApiError;
+
+pub mod event_types {
+    pub const PLAN_CREATED: &str = "plan.created";
+    pub const PLAN_CLAIMED: &str = "plan.claimed";
+    pub const LOAN_DISBURSED: &str = "loan.disbursed";
+    pub const LOAN_REPAYMENT_RECEIVED: &str = "loan.repayment_received";
+    pub const LOAN_REPAID: &str = "loan.repaid";
+    pub const LOAN_LIQUIDATED: &str = "loan.liquidated";
+    pub const KYC_SUBMITTED: &str = "kyc.submitted";
+    pub const KYC_APPROVED: &str = "kyc.approved";
+    pub const KYC_REJECTED: &str = "kyc.rejected";
+    pub const EMERGENCY_ACCESS_GRANTED: &str = "emergency_access.granted";
+    pub const EMERGENCY_ACCESS_REVOKED: &str = "emergency_access.revoked";
+    pub const WITNESS_SIGNED: &str = "witness.signed";
+    pub const INSURANCE_CLAIM_PROCESSED: &str = "insurance_claim.processed";
+    pub const CONTINGENT_BENEFICIARY_PROMOTED: &str = "beneficiary.contingent_promoted";
+    pub const NOTIFICATION_CREATED: &str = "notification.created";
+    pub const NOTIFICATION_DIGEST: &str = "notification.digest";
+    pub const WEBHOOK_TEST: &str = "webhook.test";
+}
+
+/// The `data` object of one event type. Bump `VERSION` whenever a field is
+/// renamed, removed or changes meaning.
+pub trait WebhookPayload: Serialize {
+    const EVENT_TYPE: &'static str;
+    const VERSION: u32;
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanCreated {
+    pub plan_id: Uuid,
+    pub title: String,
+    pub net_amount: Decimal,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanClaimed {
+    pub plan_id: Uuid,
+    pub title: String,
+    pub claimed_by: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoanDisbursed {
+    pub loan_id: Uuid,
+    pub plan_id: Option<Uuid>,
+    pub borrow_asset: String,
+    pub principal: Decimal,
+    pub collateral_asset: String,
+    pub collateral_amount: Decimal,
+    pub due_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoanRepaymentReceived {
+    pub loan_id: Uuid,
+    pub amount: Decimal,
+    pub amount_repaid: Decimal,
+    pub principal: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoanRepaid {
+    pub loan_id: Uuid,
+    pub amount_repaid: Decimal,
+    pub repaid_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoanLiquidated {
+    pub loan_id: Uuid,
+    pub plan_id: Option<Uuid>,
+    pub principal: Decimal,
+    pub amount_repaid: Decimal,
+    pub liquidated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KycSubmitted {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KycApproved {
+    pub user_id: Uuid,
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KycRejected {
+    pub user_id: Uuid,
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmergencyAccessGranted {
+    pub access_id: Uuid,
+    pub plan_id: Uuid,
+    pub access_type: String,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmergencyAccessRevoked {
+    pub access_id: Uuid,
+    pub plan_id: Uuid,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WitnessSigned {
+    pub witness_id: Uuid,
+    pub document_id: Uuid,
+    pub plan_id: Uuid,
+    pub signed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InsuranceClaimProcessed {
+    pub claim_id: Uuid,
+    pub plan_id: Option<Uuid>,
+    pub loan_id: Option<Uuid>,
+    pub status: String,
+    pub approved_amount: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContingentBeneficiaryPromoted {
+    pub plan_id: Uuid,
+    pub beneficiary_id: Uuid,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationCreated {
+    pub notification_id: Uuid,
+    pub notif_type: String,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationDigest {
+    pub frequency: String,
+    pub notifications: Vec<NotificationCreated>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookTest {
+    pub webhook_id: Uuid,
+    pub message: String,
+}
+
+macro_rules! webhook_payload {
+    ($($payload:ty => $event_type:ident, $version:literal;)*) => {
+        $(impl WebhookPayload for $payload {
+            const EVENT_TYPE: &'static str = event_types::$event_type;
+            const VERSION: u32 = $version;
+        })*
+    };
+}
+
+webhook_payload! {
+    PlanCreated => PLAN_CREATED, 1;
+    PlanClaimed => PLAN_CLAIMED, 1;
+    LoanDisbursed => LOAN_DISBURSED, 1;
+    LoanRepaymentReceived => LOAN_REPAYMENT_RECEIVED, 1;
+    LoanRepaid => LOAN_REPAID, 1;
+    LoanLiquidated => LOAN_LIQUIDATED, 1;
+    KycSubmitted => KYC_SUBMITTED, 1;
+    KycApproved => KYC_APPROVED, 1;
+    KycRejected => KYC_REJECTED, 1;
+    EmergencyAccessGranted => EMERGENCY_ACCESS_GRANTED, 1;
+    EmergencyAccessRevoked => EMERGENCY_ACCESS_REVOKED, 1;
+    WitnessSigned => WITNESS_SIGNED, 1;
+    InsuranceClaimProcessed => INSURANCE_CLAIM_PROCESSED, 1;
+    ContingentBeneficiaryPromoted => CONTINGENT_BENEFICIARY_PROMOTED, 1;
+    NotificationCreated => NOTIFICATION_CREATED, 1;
+    NotificationDigest => NOTIFICATION_DIGEST, 1;
+    WebhookTest => WEBHOOK_TEST, 1;
+}
+
+/// One entry of the event catalogue.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventTypeInfo {
+    pub event_type: &'static str,
+    pub version: u32,
+    pub description: &'static str,
+    /// JSON Schema of the event's `data` object.
+    pub schema: Value,
+}
+
+fn uuid() -> Value {
+    json!({ "type": "string", "format": "uuid" })
+}
+
+fn text() -> Value {
+    json!({ "type": "string" })
+}
+
+fn decimal() -> Value {
+    json!({ "type": "string", "pattern": "^-?[0-9]+(\\.[0-9]+)?$" })
+}
+
+fn timestamp() -> Value {
+    json!({ "type": "string", "format": "date-time" })
+}
+
+fn array_of(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn nullable(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+fn object(fields: Vec<(&str, Value)>) -> Value {
+    let required: Vec<&str> = fields.iter().map(|(name, _)| *name).collect();
+    let properties: serde_json::Map<String, Value> = fields
+        .into_iter()
+        .map(|(name, schema)| (name.to_string(), schema))
+        .collect();
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+fn entry<P: WebhookPayload>(description: &'static str, schema: Value) -> EventTypeInfo {
+    EventTypeInfo {
+        event_type: P::EVENT_TYPE,
+        version: P::VERSION,
+        description,
+        schema,
+    }
+}
+
+fn notification_schema() -> Value {
+    object(vec![
+        ("notification_id", uuid()),
+        ("notif_type", text()),
+        ("message", text()),
+        ("created_at", timestamp()),
+    ])
+}
+
+/// Every event type webhooks can subscribe to.
+pub fn catalogue() -> Vec<EventTypeInfo> {
+    vec![
+        entry::<PlanCreated>(
+            "A plan was created.",
+            object(vec![
+                ("plan_id", uuid()),
+                ("title", text()),
+                ("net_amount", decimal()),
+                ("status", text()),
+            ]),
+        ),
+        entry::<PlanClaimed>(
+            "A beneficiary claimed a plan.",
+            object(vec![
+                ("plan_id", uuid()),
+                ("title", text()),
+                ("claimed_by", uuid()),
+            ]),
+        ),
+        entry::<LoanDisbursed>(
+            "A loan was opened.",
+            object(vec![
+                ("loan_id", uuid()),
+                ("plan_id", nullable(uuid())),
+                ("borrow_asset", text()),
+                ("principal", decimal()),
+                ("collateral_asset", text()),
+                ("collateral_amount", decimal()),
+                ("due_date", timestamp()),
+            ]),
+        ),
+        entry::<LoanRepaymentReceived>(
+            "A partial repayment was applied to a loan.",
+            object(vec![
+                ("loan_id", uuid()),
+                ("amount", decimal()),
+                ("amount_repaid", decimal()),
+                ("principal", decimal()),
+            ]),
+        ),
+        entry::<LoanRepaid>(
+            "A loan was repaid in full.",
+            object(vec![
+                ("loan_id", uuid()),
+                ("amount_repaid", decimal()),
+                ("repaid_at", nullable(timestamp())),
+            ]),
+        ),
+        entry::<LoanLiquidated>(
+            "A loan was liquidated.",
+            object(vec![
+                ("loan_id", uuid()),
+                ("plan_id", nullable(uuid())),
+                ("principal", decimal()),
+                ("amount_repaid", decimal()),
+                ("liquidated_at", nullable(timestamp())),
+            ]),
+        ),
+        entry::<KycSubmitted>(
+            "The user submitted KYC for review.",
+            object(vec![("user_id", uuid())]),
+        ),
+        entry::<KycApproved>(
+            "The user's KYC was approved.",
+            object(vec![
+                ("user_id", uuid()),
+                ("reviewed_at", nullable(timestamp())),
+            ]),
+        ),
+        entry::<KycRejected>(
+            "The user's KYC was rejected.",
+            object(vec![
+                ("user_id", uuid()),
+                ("reviewed_at", nullable(timestamp())),
+            ]),
+        ),
+        entry::<EmergencyAccessGranted>(
+            "An administrator granted emergency access to one of the user's plans.",
+            object(vec![
+                ("access_id", uuid()),
+                ("plan_id", uuid()),
+                ("access_type", text()),
+                ("expires_at", nullable(timestamp())),
+            ]),
+        ),
+        entry::<EmergencyAccessRevoked>(
+            "Emergency access to one of the user's plans was revoked.",
+            object(vec![
+                ("access_id", uuid()),
+                ("plan_id", uuid()),
+                ("reason", text()),
+            ]),
+        ),
+        entry::<WitnessSigned>(
+            "A witness signed a will document of one of the user's plans.",
+            object(vec![
+                ("witness_id", uuid()),
+                ("document_id", uuid()),
+                ("plan_id", uuid()),
+                ("signed_at", timestamp()),
+            ]),
+        ),
+        entry::<InsuranceClaimProcessed>(
+            "One of the user's insurance claims was approved or rejected.",
+            object(vec![
+                ("claim_id", uuid()),
+                ("plan_id", nullable(uuid())),
+                ("loan_id", nullable(uuid())),
+                ("status", text()),
+                ("approved_amount", nullable(decimal())),
+            ]),
+        ),
+        entry::<ContingentBeneficiaryPromoted>(
+            "A contingent beneficiary was promoted to primary.",
+            object(vec![
+                ("plan_id", uuid()),
+                ("beneficiary_id", uuid()),
+                ("reason", text()),
+            ]),
+        ),
+        entry::<NotificationCreated>(
+            "A notification for a type the user routes to the webhook channel.",
+            notification_schema(),
+        ),
+        entry::<NotificationDigest>(
+            "A daily or weekly digest of notifications routed to the webhook channel.",
+            object(vec![
+                ("frequency", text()),
+                ("notifications", array_of(notification_schema())),
+            ]),
+        ),
+        entry::<WebhookTest>(
+            "A test event sent on request to check an endpoint.",
+            object(vec![("webhook_id", uuid()), ("message", text())]),
+        ),
+    ]
+}
+
+/// Whether `event_type` is in the catalogue.
+pub fn is_known_event_type(event_type: &str) -> bool {
+    catalogue().iter().any(|info| info.event_type == event_type)
+}
+
+/// The envelope delivered to webhooks.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub id: String,
+    pub event_type: String,
+    pub version: u32,
+    pub data: Value,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl WebhookEvent {
+    pub fn new<P: WebhookPayload>(payload: &P) -> Result<Self, ApiError> {
+        Ok(Self {
+            id: Uuid::new_v4().to_string(),
+            event_type: P::EVENT_TYPE.to_string(),
+            version: P::VERSION,
+            data: serde_json::to_value(payload).map_err(|e| ApiError::Internal(e.into()))?,
+            timestamp: Utc::now(),
+        })
+    }
+}
+
+/// Fans events out to webhooks. Domain services do not call this directly:
+/// they record events in the [outbox](crate::outbox), whose relay dispatches
+/// them. The notification dispatcher uses it for the webhook channel.
+pub struct WebhookDispatcher;
+
+impl WebhookDispatcher {
+    /// Queue `event` for every active webhook of `user_id` subscribed to its
+    /// type. Returns how many deliveries were queued.
+    pub async fn dispatch(
+        executor: impl sqlx::PgExecutor<'_>,
+        user_id: Uuid,
+        event_id: &str,
+        event_type: &str,
+        envelope: &Value,
+    ) -> Result<u64, ApiError> {
+        let queued = sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries (webhook_id, event_id, event_type, payload, status, next_attempt_at)
+            SELECT id, $2, $3, $4, 'pending', NOW()
+            FROM webhooks
+            WHERE user_id = $1 AND is_active = true AND $3 = ANY(events)
+            "#,
+        )
+        .bind(user_id)
+        .bind(event_id)
+        .bind(event_type)
+        .bind(envelope)
+        .execute(executor)
+        .await?;
+
+        Ok(queued.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn keys_of(value: &Value) -> BTreeSet<String> {
+        value.as_object().unwrap().keys().cloned().collect()
+    }
+
+    fn schema_keys(event_type: &str) -> BTreeSet<String> {
+        let info = catalogue()
+            .into_iter()
+            .find(|info| info.event_type == event_type)
+            .unwrap();
+        keys_of(&info.schema["properties"])
+    }
+
+    fn assert_matches_schema<P: WebhookPayload>(payload: P) {
+        let data = serde_json::to_value(&payload).unwrap();
+        assert_eq!(
+            keys_of(&data),
+            schema_keys(P::EVENT_TYPE),
+            "{}",
+            P::EVENT_TYPE
+        );
+    }
+
+    #[test]
+    fn catalogue_event_types_are_unique() {
+        let catalogue = catalogue();
+        let unique: BTreeSet<_> = catalogue.iter().map(|info| info.event_type).collect();
+        assert_eq!(unique.len(), catalogue.len());
+        assert!(is_known_event_type(event_types::LOAN_LIQUIDATED));
+        assert!(!is_known_event_type("loan.exploded"));
+    }
+
+    #[test]
+    fn payloads_match_their_schemas() {
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        let amount = Decimal::new(12_345, 2);
+
+        assert_matches_schema(PlanCreated {
+            plan_id: id,
+            title: "Plan".into(),
+            net_amount: amount,
+            status: "pending".into(),
+        });
+        assert_matches_schema(PlanClaimed {
+            plan_id: id,
+            title: "Plan".into(),
+            claimed_by: id,
+        });
+        assert_matches_schema(LoanDisbursed {
+            loan_id: id,
+            plan_id: None,
+            borrow_asset: "USDC".into(),
+            principal: amount,
+            collateral_asset: "XLM".into(),
+            collateral_amount: amount,
+            due_date: now,
+        });
+        assert_matches_schema(LoanRepaymentReceived {
+            loan_id: id,
+            amount,
+            amount_repaid: amount,
+            principal: amount,
+        });
+        assert_matches_schema(LoanRepaid {
+            loan_id: id,
+            amount_repaid: amount,
+            repaid_at: Some(now),
+        });
+        assert_matches_schema(LoanLiquidated {
+            loan_id: id,
+            plan_id: Some(id),
+            principal: amount,
+            amount_repaid: amount,
+            liquidated_at: Some(now),
+        });
+        assert_matches_schema(KycSubmitted { user_id: id });
+        assert_matches_schema(KycApproved {
+            user_id: id,
+            reviewed_at: Some(now),
+        });
+        assert_matches_schema(KycRejected {
+            user_id: id,
+            reviewed_at: None,
+        });
+        assert_matches_schema(EmergencyAccessGranted {
+            access_id: id,
+            plan_id: id,
+            access_type: "temporary_access".into(),
+            expires_at: None,
+        });
+        assert_matches_schema(EmergencyAccessRevoked {
+            access_id: id,
+            plan_id: id,
+            reason: "resolved".into(),
+        });
+        assert_matches_schema(WitnessSigned {
+            witness_id: id,
+            document_id: id,
+            plan_id: id,
+            signed_at: now,
+        });
+        assert_matches_schema(InsuranceClaimProcessed {
+            claim_id: id,
+            plan_id: None,
+            loan_id: Some(id),
+            status: "approved".into(),
+            approved_amount: Some(amount),
+        });
+        assert_matches_schema(ContingentBeneficiaryPromoted {
+            plan_id: id,
+            beneficiary_id: id,
+            reason: "primary deceased".into(),
+        });
+        let notification = NotificationCreated {
+            notification_id: id,
+            notif_type: "yield_update".into(),
+            message: "Your yield was updated".into(),
+            created_at: now,
+        };
+        assert_matches_schema(notification.clone());
+        assert_matches_schema(NotificationDigest {
+            frequency: "weekly".into(),
+            notifications: vec![notification],
+        });
+        assert_matches_schema(WebhookTest {
+            webhook_id: id,
+            message: "ping".into(),
+        });
+    }
+
+    #[test]
+    fn envelope_carries_type_and_version() {
+        let event = WebhookEvent::new(&KycSubmitted {
+            user_id: Uuid::nil(),
+        })
+        .unwrap();
+        assert_eq!(event.event_type, event_types::KYC_SUBMITTED);
+        assert_eq!(event.version, 1);
+        assert_eq!(event.data["user_id"], json!(Uuid::nil()));
+    }
+}
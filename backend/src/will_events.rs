@@ -192,8 +192,13 @@ impl WillEvent {
 pub struct WillEventService;
 
 impl WillEventService {
-    /// Emit a will event and persist it to the database for auditing and indexing
-    pub async fn emit(db: &PgPool, event: WillEvent) -> Result<Uuid, crate::api_error::ApiError> {
+    /// Emit a will event and persist it to the database for auditing and indexing.
+    /// Pass the transaction of the change being recorded so the event is
+    /// written if and only if that change commits.
+    pub async fn emit(
+        executor: impl sqlx::PgExecutor<'_>,
+        event: WillEvent,
+    ) -> Result<Uuid, crate::api_error::ApiError> {
         let event_id = Uuid::new_v4();
         let event_type = event.event_type();
         let document_id = event.document_id();
@@ -221,7 +226,7 @@ impl WillEventService {
         .bind(vault_id)
         .bind(event_data)
         .bind(timestamp)
-        .execute(db)
+        .execute(executor)
         .await?;
 
         tracing::info!(
@@ -4,6 +4,7 @@
 //! Supports multiple templates (simple, formal, jurisdiction-specific).
 
 use crate::api_error::ApiError;
+use crate::pdf_typesetter::{self, Block, PageFooter, PdfMetadata};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use chrono::{DateTime, Utc};
 use ring::digest::{digest, SHA256};
@@ -95,216 +96,235 @@ pub struct GeneratedWillDocument {
     pub filename: String,
 }
 
+/// A witness invited on an earlier version of the plan's will. New versions
+/// carry a signature block for each of them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WitnessSignatory {
+    pub wallet_address: Option<String>,
+    pub email: Option<String>,
+}
+
 // ─── Template Engine ──────────────────────────────────────────────────────────
 
+/// Witnesses always get at least this many signature blocks, blank if nobody
+/// has been invited yet.
+const MIN_WITNESS_BLOCKS: usize = 2;
+
 struct TemplateEngine;
 
 impl TemplateEngine {
-    fn render(input: &WillDocumentInput, generated_at: DateTime<Utc>, version: u32) -> String {
-        match input.template {
-            WillTemplate::Simple => Self::render_simple(input, generated_at, version),
-            WillTemplate::Formal => Self::render_formal(input, generated_at, version),
-            WillTemplate::UsJurisdiction => Self::render_us(input, generated_at, version),
-            WillTemplate::UkJurisdiction => Self::render_uk(input, generated_at, version),
-            WillTemplate::GlobalGeneric => Self::render_global(input, generated_at, version),
-        }
+    fn layout(
+        input: &WillDocumentInput,
+        witnesses: &[WitnessSignatory],
+        generated_at: DateTime<Utc>,
+        version: u32,
+    ) -> Vec<Block> {
+        let mut blocks = match input.template {
+            WillTemplate::Simple => Self::layout_simple(input, generated_at, version),
+            WillTemplate::Formal => Self::layout_formal(input, generated_at, version),
+            WillTemplate::UsJurisdiction => Self::layout_us(input, generated_at, version),
+            WillTemplate::UkJurisdiction => Self::layout_uk(input, generated_at, version),
+            WillTemplate::GlobalGeneric => Self::layout_global(input, generated_at, version),
+        };
+        Self::append_signatures(&mut blocks, input, witnesses);
+        Self::append_references(&mut blocks, input);
+        blocks
     }
 
-    fn header(title: &str, generated_at: DateTime<Utc>, version: u32) -> String {
-        format!(
-            "================================================================\n\
-             {title}\n\
-             ================================================================\n\
-             Generated: {generated_at}\n\
-             Document Version: {version}\n\
-             ----------------------------------------------------------------\n"
-        )
+    fn header(title: &str, generated_at: DateTime<Utc>, version: u32) -> Vec<Block> {
+        vec![
+            Block::Title(title.to_string()),
+            Block::Subtitle(format!(
+                "Generated: {} | Document Version: {version}",
+                generated_at.format("%Y-%m-%d %H:%M:%S UTC")
+            )),
+        ]
     }
 
-    fn beneficiaries_section(beneficiaries: &[BeneficiaryEntry]) -> String {
-        let mut s = String::from("BENEFICIARIES\n-------------\n");
+    fn testator_section(blocks: &mut Vec<Block>, input: &WillDocumentInput) {
+        blocks.push(Block::Heading("TESTATOR".to_string()));
+        blocks.push(field("Name", &input.owner_name));
+        blocks.push(field("Wallet", &input.owner_wallet));
+        blocks.push(field("Vault ID", &input.vault_id));
+    }
+
+    fn beneficiaries_section(blocks: &mut Vec<Block>, beneficiaries: &[BeneficiaryEntry]) {
+        blocks.push(Block::Heading("BENEFICIARIES".to_string()));
         for (i, b) in beneficiaries.iter().enumerate() {
-            s.push_str(&format!(
-                "{}. Name:       {}\n   Wallet:     {}\n   Allocation: {}%\n",
-                i + 1,
-                b.name,
-                b.wallet_address,
-                b.allocation_percent
-            ));
+            blocks.push(Block::Subheading(format!("{}. {}", i + 1, b.name)));
+            blocks.push(field("Wallet", &b.wallet_address));
+            blocks.push(field("Allocation", &format!("{}%", b.allocation_percent)));
             if let Some(rel) = &b.relationship {
-                s.push_str(&format!("   Relation:   {rel}\n"));
+                blocks.push(field("Relation", rel));
             }
         }
-        s
     }
 
-    fn render_simple(input: &WillDocumentInput, ts: DateTime<Utc>, v: u32) -> String {
+    fn layout_simple(input: &WillDocumentInput, ts: DateTime<Utc>, v: u32) -> Vec<Block> {
         let mut doc = Self::header("LAST WILL AND TESTAMENT (SIMPLE)", ts, v);
-        doc.push_str(&format!(
-            "\nI, {}, wallet address {}, hereby declare this my last will.\n\n",
+        doc.push(Block::Paragraph(format!(
+            "I, {}, wallet address {}, hereby declare this my last will.",
             input.owner_name, input.owner_wallet
-        ));
-        doc.push_str(&Self::beneficiaries_section(&input.beneficiaries));
-        Self::append_footer(&mut doc, input);
+        )));
+        Self::beneficiaries_section(&mut doc, &input.beneficiaries);
         doc
     }
 
-    fn render_formal(input: &WillDocumentInput, ts: DateTime<Utc>, v: u32) -> String {
+    fn layout_formal(input: &WillDocumentInput, ts: DateTime<Utc>, v: u32) -> Vec<Block> {
         let mut doc = Self::header("FORMAL LAST WILL AND TESTAMENT", ts, v);
-        doc.push_str(&format!(
-            "\nI, {owner}, residing at blockchain address {wallet}, being of sound mind,\n\
-             do hereby make, publish, and declare this instrument to be my Last Will\n\
-             and Testament, hereby revoking all former wills and codicils.\n\n\
-             VAULT REFERENCE: {vault}\n\n",
+        doc.push(Block::Paragraph(format!(
+            "I, {owner}, residing at blockchain address {wallet}, being of sound mind, \
+             do hereby make, publish, and declare this instrument to be my Last Will \
+             and Testament, hereby revoking all former wills and codicils.",
             owner = input.owner_name,
             wallet = input.owner_wallet,
-            vault = input.vault_id
-        ));
-        doc.push_str(&Self::beneficiaries_section(&input.beneficiaries));
+        )));
+        doc.push(field("Vault Reference", &input.vault_id));
+        Self::beneficiaries_section(&mut doc, &input.beneficiaries);
         if let Some(rules) = &input.execution_rules {
-            doc.push_str(&format!("\nEXECUTION RULES\n---------------\n{rules}\n"));
+            doc.push(Block::Heading("EXECUTION RULES".to_string()));
+            doc.push(Block::Paragraph(rules.clone()));
         }
-        Self::append_footer(&mut doc, input);
         doc
     }
 
-    fn render_us(input: &WillDocumentInput, ts: DateTime<Utc>, v: u32) -> String {
+    fn layout_us(input: &WillDocumentInput, ts: DateTime<Utc>, v: u32) -> Vec<Block> {
         let mut doc = Self::header("LAST WILL AND TESTAMENT — US JURISDICTION", ts, v);
-        doc.push_str(
-            "\nSTATE OF [STATE], COUNTY OF [COUNTY]\n\
-             This Will is executed in accordance with applicable US state law.\n\n",
-        );
-        doc.push_str(&format!(
-            "Testator: {}, Wallet: {}\nVault ID: {}\n\n",
-            input.owner_name, input.owner_wallet, input.vault_id
+        doc.push(Block::Paragraph(
+            "STATE OF [STATE], COUNTY OF [COUNTY]\n\
+             This Will is executed in accordance with applicable US state law."
+                .to_string(),
+        ));
+        Self::testator_section(&mut doc, input);
+        Self::beneficiaries_section(&mut doc, &input.beneficiaries);
+        doc.push(Block::Heading("WITNESS CLAUSE".to_string()));
+        doc.push(Block::Paragraph(
+            "This will requires two witnesses per applicable state law.".to_string(),
         ));
-        doc.push_str(&Self::beneficiaries_section(&input.beneficiaries));
-        doc.push_str(
-            "\nWITNESS CLAUSE\nThis will requires two witnesses per applicable state law.\n",
-        );
-        Self::append_footer(&mut doc, input);
         doc
     }
 
-    fn render_uk(input: &WillDocumentInput, ts: DateTime<Utc>, v: u32) -> String {
+    fn layout_uk(input: &WillDocumentInput, ts: DateTime<Utc>, v: u32) -> Vec<Block> {
         let mut doc = Self::header("LAST WILL AND TESTAMENT — UK JURISDICTION", ts, v);
-        doc.push_str("\nThis Will is made in accordance with the Wills Act 1837 (as amended).\n\n");
-        doc.push_str(&format!(
-            "Testator: {}, Wallet: {}\nVault ID: {}\n\n",
-            input.owner_name, input.owner_wallet, input.vault_id
+        doc.push(Block::Paragraph(
+            "This Will is made in accordance with the Wills Act 1837 (as amended).".to_string(),
+        ));
+        Self::testator_section(&mut doc, input);
+        Self::beneficiaries_section(&mut doc, &input.beneficiaries);
+        doc.push(Block::Heading("ATTESTATION".to_string()));
+        doc.push(Block::Paragraph(
+            "Signed by the above-named Testator in our presence.".to_string(),
         ));
-        doc.push_str(&Self::beneficiaries_section(&input.beneficiaries));
-        doc.push_str("\nATTESTATION\nSigned by the above-named Testator in our presence.\n");
-        Self::append_footer(&mut doc, input);
         doc
     }
 
-    fn render_global(input: &WillDocumentInput, ts: DateTime<Utc>, v: u32) -> String {
+    fn layout_global(input: &WillDocumentInput, ts: DateTime<Utc>, v: u32) -> Vec<Block> {
         let mut doc = Self::header("LAST WILL AND TESTAMENT — GLOBAL GENERIC", ts, v);
         let jurisdiction = input
             .jurisdiction
             .as_deref()
             .unwrap_or("International / Unspecified");
-        doc.push_str(&format!("\nJurisdiction: {jurisdiction}\n\n"));
-        doc.push_str(&format!(
-            "Testator: {}, Wallet: {}\nVault ID: {}\n\n",
-            input.owner_name, input.owner_wallet, input.vault_id
-        ));
-        doc.push_str(&Self::beneficiaries_section(&input.beneficiaries));
-        Self::append_footer(&mut doc, input);
+        doc.push(field("Jurisdiction", jurisdiction));
+        Self::testator_section(&mut doc, input);
+        Self::beneficiaries_section(&mut doc, &input.beneficiaries);
         doc
     }
 
-    fn append_footer(doc: &mut String, input: &WillDocumentInput) {
-        doc.push_str("\n----------------------------------------------------------------\n");
+    fn append_signatures(
+        blocks: &mut Vec<Block>,
+        input: &WillDocumentInput,
+        witnesses: &[WitnessSignatory],
+    ) {
+        blocks.push(Block::Heading("SIGNATURES".to_string()));
+        blocks.push(Block::Signature {
+            role: "Testator".to_string(),
+            name: input.owner_name.clone(),
+            details: vec![format!("Wallet: {}", input.owner_wallet)],
+        });
+        for i in 0..witnesses.len().max(MIN_WITNESS_BLOCKS) {
+            let mut details = vec!["Name: ______________________________".to_string()];
+            if let Some(witness) = witnesses.get(i) {
+                if let Some(wallet) = &witness.wallet_address {
+                    details.push(format!("Wallet: {wallet}"));
+                }
+                if let Some(email) = &witness.email {
+                    details.push(format!("Email: {email}"));
+                }
+            }
+            blocks.push(Block::Signature {
+                role: format!("Witness {}", i + 1),
+                name: witnesses
+                    .get(i)
+                    .and_then(|w| w.wallet_address.clone().or_else(|| w.email.clone()))
+                    .unwrap_or_else(|| "(to be completed)".to_string()),
+                details,
+            });
+        }
+    }
+
+    fn append_references(blocks: &mut Vec<Block>, input: &WillDocumentInput) {
+        blocks.push(Block::Heading("DOCUMENT REFERENCES".to_string()));
         if let Some(hash_ref) = &input.will_hash_reference {
-            doc.push_str(&format!("ON-CHAIN WILL HASH: {hash_ref}\n"));
+            blocks.push(field("On-Chain Will Hash", hash_ref));
         }
-        doc.push_str(&format!("PLAN ID: {}\n", input.plan_id));
-        doc.push_str(
-            "================================================================\n\
-             This document is cryptographically bound to the vault above.\n\
-             ================================================================\n",
-        );
+        blocks.push(field("Plan ID", &input.plan_id.to_string()));
+        blocks.push(Block::Paragraph(
+            "This document is cryptographically bound to the vault above.".to_string(),
+        ));
     }
 }
 
-// ─── PDF Builder ──────────────────────────────────────────────────────────────
-
-/// Builds a minimal valid PDF containing the will text.
-/// Uses raw PDF syntax — no external crate required.
-fn build_pdf(content: &str) -> Vec<u8> {
-    // Escape special PDF string characters
-    let escaped = content
-        .replace('\\', "\\\\")
-        .replace('(', "\\(")
-        .replace(')', "\\)");
-
-    // Split into lines for multi-line text rendering
-    let lines: Vec<&str> = escaped.lines().collect();
-    let mut stream_content = String::new();
-    stream_content.push_str("BT\n/F1 10 Tf\n50 780 Td\n12 TL\n");
-    for line in &lines {
-        stream_content.push_str(&format!("({line}) Tj T*\n"));
-    }
-    stream_content.push_str("ET\n");
-
-    let stream_bytes = stream_content.as_bytes();
-    let stream_len = stream_bytes.len();
-
-    let mut pdf = Vec::new();
-    pdf.extend_from_slice(b"%PDF-1.4\n");
-
-    // Object 1: Catalog
-    let obj1_offset = pdf.len();
-    pdf.extend_from_slice(b"1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n");
-
-    // Object 2: Pages
-    let obj2_offset = pdf.len();
-    pdf.extend_from_slice(b"2 0 obj\n<< /Type /Pages /Kids [3 0 R] /Count 1 >>\nendobj\n");
-
-    // Object 3: Page
-    let obj3_offset = pdf.len();
-    pdf.extend_from_slice(
-        b"3 0 obj\n<< /Type /Page /Parent 2 0 R \
-          /MediaBox [0 0 612 792] \
-          /Contents 4 0 R \
-          /Resources << /Font << /F1 5 0 R >> >> >>\nendobj\n",
-    );
-
-    // Object 4: Content stream
-    let obj4_offset = pdf.len();
-    pdf.extend_from_slice(format!("4 0 obj\n<< /Length {stream_len} >>\nstream\n").as_bytes());
-    pdf.extend_from_slice(stream_bytes);
-    pdf.extend_from_slice(b"\nendstream\nendobj\n");
-
-    // Object 5: Font
-    let obj5_offset = pdf.len();
-    pdf.extend_from_slice(
-        b"5 0 obj\n<< /Type /Font /Subtype /Type1 /BaseFont /Courier >>\nendobj\n",
-    );
-
-    // Cross-reference table
-    let xref_offset = pdf.len();
-    pdf.extend_from_slice(
-        format!(
-            "xref\n0 6\n\
-             0000000000 65535 f \n\
-             {obj1_offset:010} 00000 n \n\
-             {obj2_offset:010} 00000 n \n\
-             {obj3_offset:010} 00000 n \n\
-             {obj4_offset:010} 00000 n \n\
-             {obj5_offset:010} 00000 n \n"
-        )
-        .as_bytes(),
-    );
+fn field(label: &str, value: &str) -> Block {
+    Block::Field {
+        label: label.to_string(),
+        value: value.to_string(),
+    }
+}
 
-    // Trailer
-    pdf.extend_from_slice(
-        format!("trailer\n<< /Size 6 /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n").as_bytes(),
-    );
+// ─── PDF Builder ──────────────────────────────────────────────────────────────
 
-    pdf
+/// Typesets the will. The footer on every page repeats `will_hash` and the
+/// vault id, in text and as a QR code, so a printed copy can be checked
+/// against `DocumentVerificationService::verify_hash`. The footer is not part
+/// of the hashed text.
+fn build_pdf(
+    input: &WillDocumentInput,
+    blocks: &[Block],
+    will_hash: &str,
+    generated_at: DateTime<Utc>,
+    version: u32,
+) -> Result<Vec<u8>, ApiError> {
+    let footer = PageFooter {
+        lines: vec![
+            format!("Will hash (SHA-256): {will_hash}"),
+            format!(
+                "Vault: {} | Plan: {} | Version {version}",
+                input.vault_id, input.plan_id
+            ),
+        ],
+        qr_payload: format!("inheritx-will:v1;vault={};hash={will_hash}", input.vault_id),
+    };
+
+    // Derive the PDF document id from the will hash so it is reproducible.
+    let mut document_id = [0u8; 16];
+    let hash_bytes = hex::decode(will_hash)
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Invalid will hash: {e}")))?;
+    document_id.copy_from_slice(&hash_bytes[..16]);
+
+    let metadata = PdfMetadata {
+        title: format!("{} — {}", input.template.display_name(), input.owner_name),
+        author: input.owner_name.clone(),
+        subject: format!("Last will and testament for vault {}", input.vault_id),
+        keywords: vec![
+            "last will and testament".to_string(),
+            input.vault_id.clone(),
+            will_hash.to_string(),
+        ],
+        created_at: generated_at,
+        document_id,
+    };
+
+    pdf_typesetter::render_pdf(blocks, &footer, &metadata)
 }
 
 // ─── Will PDF Service ─────────────────────────────────────────────────────────
@@ -331,15 +351,18 @@ impl WillPdfService {
         let generated_at = Utc::now();
         let document_id = Uuid::new_v4();
 
-        // Render content via template engine
-        let content = TemplateEngine::render(input, generated_at, version);
+        let witnesses = Self::plan_witnesses(db, input.plan_id).await?;
+
+        // Lay out content via template engine
+        let blocks = TemplateEngine::layout(input, &witnesses, generated_at, version);
+        let content = pdf_typesetter::plain_text(&blocks);
 
-        // Compute document hash (SHA-256 over rendered text)
+        // Compute document hash (SHA-256 over canonical text)
         let hash_bytes = digest(&SHA256, content.as_bytes());
         let will_hash = hex::encode(hash_bytes.as_ref());
 
         // Build PDF bytes
-        let pdf_bytes = build_pdf(&content);
+        let pdf_bytes = build_pdf(input, &blocks, &will_hash, generated_at, version)?;
         let pdf_base64 = BASE64.encode(&pdf_bytes);
 
         let filename = format!(
@@ -349,6 +372,7 @@ impl WillPdfService {
         );
 
         // Persist metadata
+        let mut tx = db.begin().await?;
         sqlx::query(
             r#"
             INSERT INTO will_documents
@@ -365,7 +389,7 @@ impl WillPdfService {
         .bind(&filename)
         .bind(&pdf_base64)
         .bind(generated_at)
-        .execute(db)
+        .execute(&mut *tx)
         .await?;
 
         // Emit WillCreated event
@@ -378,9 +402,8 @@ impl WillPdfService {
             will_hash: will_hash.clone(),
             timestamp: generated_at,
         };
-        if let Err(e) = crate::will_events::WillEventService::emit(db, event).await {
-            tracing::warn!("Failed to emit WillCreated event: {}", e);
-        }
+        crate::will_events::WillEventService::emit(&mut *tx, event).await?;
+        tx.commit().await?;
 
         Ok(GeneratedWillDocument {
             document_id,
@@ -394,6 +417,33 @@ impl WillPdfService {
         })
     }
 
+    /// Witnesses invited on any earlier version of the plan's will, in
+    /// invitation order, excluding those who declined.
+    async fn plan_witnesses(db: &PgPool, plan_id: Uuid) -> Result<Vec<WitnessSignatory>, ApiError> {
+        let mut rows: Vec<(Option<String>, Option<String>, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            SELECT DISTINCT ON (COALESCE(w.wallet_address, w.email))
+                w.wallet_address, w.email, w.invited_at
+            FROM will_witnesses w
+            JOIN will_documents d ON d.id = w.document_id
+            WHERE d.plan_id = $1 AND w.status <> 'declined'
+            ORDER BY COALESCE(w.wallet_address, w.email), w.invited_at
+            "#,
+        )
+        .bind(plan_id)
+        .fetch_all(db)
+        .await?;
+
+        rows.sort_by_key(|(_, _, invited_at)| *invited_at);
+        Ok(rows
+            .into_iter()
+            .map(|(wallet_address, email, _)| WitnessSignatory {
+                wallet_address,
+                email,
+            })
+            .collect())
+    }
+
     /// Retrieve a previously generated will document by ID.
     pub async fn get_document(
         db: &PgPool,
@@ -503,10 +553,15 @@ mod tests {
         }
     }
 
+    /// Canonical text of the will, as hashed by `WillPdfService::generate`.
+    fn content(input: &WillDocumentInput, witnesses: &[WitnessSignatory]) -> String {
+        pdf_typesetter::plain_text(&TemplateEngine::layout(input, witnesses, Utc::now(), 1))
+    }
+
     #[test]
     fn test_template_rendering_simple() {
         let input = sample_input(WillTemplate::Simple);
-        let content = TemplateEngine::render(&input, Utc::now(), 1);
+        let content = content(&input, &[]);
         assert!(content.contains("Alice Testator"));
         assert!(content.contains("Bob Beneficiary"));
         assert!(content.contains("100"));
@@ -515,7 +570,7 @@ mod tests {
     #[test]
     fn test_template_rendering_formal() {
         let input = sample_input(WillTemplate::Formal);
-        let content = TemplateEngine::render(&input, Utc::now(), 1);
+        let content = content(&input, &[]);
         assert!(content.contains("FORMAL LAST WILL"));
         assert!(content.contains("EXECUTION RULES"));
     }
@@ -523,7 +578,7 @@ mod tests {
     #[test]
     fn test_template_rendering_us() {
         let input = sample_input(WillTemplate::UsJurisdiction);
-        let content = TemplateEngine::render(&input, Utc::now(), 1);
+        let content = content(&input, &[]);
         assert!(content.contains("US JURISDICTION"));
         assert!(content.contains("WITNESS CLAUSE"));
     }
@@ -531,26 +586,84 @@ mod tests {
     #[test]
     fn test_template_rendering_uk() {
         let input = sample_input(WillTemplate::UkJurisdiction);
-        let content = TemplateEngine::render(&input, Utc::now(), 1);
+        let content = content(&input, &[]);
         assert!(content.contains("Wills Act 1837"));
     }
 
     #[test]
     fn test_template_rendering_global() {
         let input = sample_input(WillTemplate::GlobalGeneric);
-        let content = TemplateEngine::render(&input, Utc::now(), 1);
+        let content = content(&input, &[]);
         assert!(content.contains("GLOBAL GENERIC"));
     }
 
+    fn sample_pdf(input: &WillDocumentInput, generated_at: DateTime<Utc>) -> (String, Vec<u8>) {
+        let blocks = TemplateEngine::layout(input, &[], generated_at, 1);
+        let content = pdf_typesetter::plain_text(&blocks);
+        let will_hash = hex::encode(digest(&SHA256, content.as_bytes()).as_ref());
+        let pdf = build_pdf(input, &blocks, &will_hash, generated_at, 1).unwrap();
+        (will_hash, pdf)
+    }
+
     #[test]
     fn test_pdf_bytes_start_with_pdf_header() {
         let input = sample_input(WillTemplate::Simple);
-        let content = TemplateEngine::render(&input, Utc::now(), 1);
-        let pdf = build_pdf(&content);
-        assert!(pdf.starts_with(b"%PDF-1.4"));
+        let (_, pdf) = sample_pdf(&input, Utc::now());
+        assert!(pdf.starts_with(b"%PDF-1.7"));
         assert!(pdf.ends_with(b"%%EOF\n"));
     }
 
+    #[test]
+    fn test_pdf_and_hash_are_deterministic() {
+        let input = sample_input(WillTemplate::Formal);
+        let generated_at = Utc::now();
+        let (hash_a, pdf_a) = sample_pdf(&input, generated_at);
+        let (hash_b, pdf_b) = sample_pdf(&input, generated_at);
+        assert_eq!(hash_a, hash_b);
+        assert_eq!(pdf_a, pdf_b);
+
+        let mut changed = input.clone();
+        changed.beneficiaries[0].allocation_percent = dec!(50);
+        let (hash_c, _) = sample_pdf(&changed, generated_at);
+        assert_ne!(hash_a, hash_c);
+    }
+
+    #[test]
+    fn test_pdf_metadata_carries_will_hash() {
+        let input = sample_input(WillTemplate::UkJurisdiction);
+        let (will_hash, pdf) = sample_pdf(&input, Utc::now());
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains(&format!(
+            "<pdf:Keywords>last will and testament, vault-001, {will_hash}"
+        )));
+        assert!(text.contains("<pdfaid:part>2</pdfaid:part>"));
+        let id = hex::encode_upper(&hex::decode(&will_hash).unwrap()[..16]);
+        assert!(text.contains(&format!("/ID [<{id}> <{id}>]")));
+    }
+
+    #[test]
+    fn test_signature_blocks_for_testator_and_witnesses() {
+        let input = sample_input(WillTemplate::UsJurisdiction);
+        let blocks = TemplateEngine::layout(&input, &[], Utc::now(), 1);
+        let roles: Vec<&str> = blocks
+            .iter()
+            .filter_map(|b| match b {
+                Block::Signature { role, .. } => Some(role.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(roles, vec!["Testator", "Witness 1", "Witness 2"]);
+
+        let witnesses: Vec<WitnessSignatory> = (0..3)
+            .map(|i| WitnessSignatory {
+                wallet_address: Some(format!("GWITNESS{i}")),
+                email: None,
+            })
+            .collect();
+        let content = content(&input, &witnesses);
+        assert!(content.contains("Witness 3: GWITNESS2"));
+    }
+
     #[test]
     fn test_will_hash_is_hex_sha256() {
         let data = b"test content";
@@ -572,8 +685,7 @@ mod tests {
     #[test]
     fn test_pdf_base64_roundtrip() {
         let input = sample_input(WillTemplate::Formal);
-        let content = TemplateEngine::render(&input, Utc::now(), 1);
-        let pdf = build_pdf(&content);
+        let (_, pdf) = sample_pdf(&input, Utc::now());
         let encoded = BASE64.encode(&pdf);
         let decoded = BASE64.decode(&encoded).unwrap();
         assert_eq!(pdf, decoded);
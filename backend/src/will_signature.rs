@@ -201,13 +201,11 @@ impl WillSignatureService {
         .execute(&mut *tx)
         .await?;
 
-        tx.commit().await?;
-
         // Fetch plan_id for event
         let plan_id: Option<Uuid> =
             sqlx::query_scalar("SELECT plan_id FROM will_documents WHERE id = $1")
                 .bind(row.document_id)
-                .fetch_optional(db)
+                .fetch_optional(&mut *tx)
                 .await?;
 
         // Emit WillSigned event
@@ -222,11 +220,11 @@ impl WillSignatureService {
                 signature_hash: hex::encode(sig_hash.as_ref()),
                 timestamp: signed_at,
             };
-            if let Err(e) = crate::will_events::WillEventService::emit(db, event).await {
-                tracing::warn!("Failed to emit WillSigned event: {}", e);
-            }
+            crate::will_events::WillEventService::emit(&mut *tx, event).await?;
         }
 
+        tx.commit().await?;
+
         Ok(WillSignatureRecord {
             id: record_id,
             document_id: row.document_id,
@@ -292,6 +290,15 @@ impl WillSignatureService {
             .collect())
     }
 
+    /// True when both addresses decode to the same Ed25519 public key, so a
+    /// G-address and its hex form compare equal.
+    pub fn same_wallet(a: &str, b: &str) -> bool {
+        matches!(
+            (Self::decode_public_key(a), Self::decode_public_key(b)),
+            (Ok(a), Ok(b)) if a == b
+        )
+    }
+
     /// Decode a Stellar G-address or hex string into 32 raw public key bytes.
     fn decode_public_key(wallet_address: &str) -> Result<[u8; 32], ApiError> {
         if wallet_address.starts_with('G') && wallet_address.len() == 56 {
@@ -422,6 +429,25 @@ mod tests {
         assert_eq!(decoded, data);
     }
 
+    #[test]
+    fn test_same_wallet_compares_public_keys() {
+        let (_, pub_bytes) = generate_keypair();
+        let g_address =
+            stellar_strkey::ed25519::PublicKey(pub_bytes.clone().try_into().unwrap()).to_string();
+        let hex_address = hex::encode(&pub_bytes);
+        assert!(WillSignatureService::same_wallet(&g_address, &hex_address));
+
+        let (_, other) = generate_keypair();
+        assert!(!WillSignatureService::same_wallet(
+            &hex_address,
+            &hex::encode(other)
+        ));
+        assert!(!WillSignatureService::same_wallet(
+            "not-a-wallet",
+            "not-a-wallet"
+        ));
+    }
+
     #[test]
     fn test_decode_public_key_wrong_length() {
         // hex of 16 bytes (too short)
@@ -227,6 +227,7 @@ impl WillVersionService {
             ));
         }
 
+        let mut tx = db.begin().await?;
         sqlx::query(
             "UPDATE will_documents SET status = 'finalized' \
              WHERE plan_id = $1 AND user_id = $2 AND version = $3",
@@ -234,14 +235,14 @@ impl WillVersionService {
         .bind(plan_id)
         .bind(user_id)
         .bind(version_number as i32)
-        .execute(db)
+        .execute(&mut *tx)
         .await?;
 
         // Fetch vault_id from plan
         let vault_id: Option<String> =
             sqlx::query_scalar("SELECT COALESCE(title, id::text) FROM plans WHERE id = $1")
                 .bind(plan_id)
-                .fetch_optional(db)
+                .fetch_optional(&mut *tx)
                 .await?;
 
         // Emit WillFinalized event
@@ -254,10 +255,9 @@ impl WillVersionService {
                 will_hash: row.will_hash.clone(),
                 timestamp: chrono::Utc::now(),
             };
-            if let Err(e) = crate::will_events::WillEventService::emit(db, event).await {
-                tracing::warn!("Failed to emit WillFinalized event: {}", e);
-            }
+            crate::will_events::WillEventService::emit(&mut *tx, event).await?;
         }
+        tx.commit().await?;
 
         Ok(WillVersionSummary {
             document_id: row.id,
@@ -5,6 +5,9 @@
 //! Ed25519 signatures, and their status is tracked (pending/signed/declined).
 
 use crate::api_error::ApiError;
+use crate::notifications::notif_type;
+use crate::outbox::{Outbox, OutboxMessage};
+use crate::webhook_events::WitnessSigned;
 use chrono::{DateTime, Utc};
 use ring::signature;
 use serde::{Deserialize, Serialize};
@@ -84,6 +87,7 @@ impl WitnessService {
         let id = Uuid::new_v4();
         let invited_at = Utc::now();
 
+        let mut tx = db.begin().await?;
         sqlx::query(
             r#"
             INSERT INTO will_witnesses
@@ -97,7 +101,7 @@ impl WitnessService {
         .bind(&wallet_address)
         .bind(&email)
         .bind(invited_at)
-        .execute(db)
+        .execute(&mut *tx)
         .await?;
 
         // Emit WitnessInvited event
@@ -108,7 +112,7 @@ impl WitnessService {
              WHERE d.id = $1",
         )
         .bind(document_id)
-        .fetch_optional(db)
+        .fetch_optional(&mut *tx)
         .await?;
 
         if let Some((plan_id, vault_id)) = result {
@@ -125,10 +129,9 @@ impl WitnessService {
                 witness_identifier,
                 timestamp: invited_at,
             };
-            if let Err(e) = crate::will_events::WillEventService::emit(db, event).await {
-                tracing::warn!("Failed to emit WitnessInvited event: {}", e);
-            }
+            crate::will_events::WillEventService::emit(&mut *tx, event).await?;
         }
+        tx.commit().await?;
 
         Ok(WitnessRecord {
             id,
@@ -267,19 +270,29 @@ impl WitnessService {
         .execute(&mut *tx)
         .await?;
 
-        tx.commit().await?;
-
-        // Fetch plan_id and vault_id for event
-        let (plan_id, vault_id): (Uuid, String) = sqlx::query_as(
-            "SELECT p.id, COALESCE(p.title, p.id::text) \
+        // Fetch the plan, its owner and vault_id for the events
+        let (plan_id, owner_id, vault_id): (Uuid, Uuid, String) = sqlx::query_as(
+            "SELECT p.id, p.user_id, COALESCE(p.title, p.id::text) \
              FROM plans p \
              JOIN will_documents d ON d.plan_id = p.id \
              WHERE d.id = $1",
         )
         .bind(witness.document_id)
-        .fetch_one(db)
+        .fetch_one(&mut *tx)
         .await?;
 
+        let signed = WitnessSigned {
+            witness_id,
+            document_id: witness.document_id,
+            plan_id,
+            signed_at,
+        };
+        let message = OutboxMessage::new(owner_id, &signed)?.with_notification(
+            notif_type::WITNESS_SIGNED,
+            "A witness has signed your will document",
+        );
+        Outbox::record(&mut *tx, &message).await?;
+
         // Emit WitnessSigned event
         let sig_hash = ring::digest::digest(&ring::digest::SHA256, signature_hex.as_bytes());
         let event = crate::will_events::WillEvent::WitnessSigned {
@@ -291,9 +304,9 @@ impl WitnessService {
             signature_hash: hex::encode(sig_hash.as_ref()),
             timestamp: signed_at,
         };
-        if let Err(e) = crate::will_events::WillEventService::emit(db, event).await {
-            tracing::warn!("Failed to emit WitnessSigned event: {}", e);
-        }
+        crate::will_events::WillEventService::emit(&mut *tx, event).await?;
+
+        tx.commit().await?;
 
         Ok(WitnessRecord {
             id: witness.id,
@@ -337,9 +350,10 @@ impl WitnessService {
             )));
         }
 
+        let mut tx = db.begin().await?;
         sqlx::query("UPDATE will_witnesses SET status = 'declined' WHERE id = $1")
             .bind(witness_id)
-            .execute(db)
+            .execute(&mut *tx)
             .await?;
 
         // Emit WitnessDeclined event
@@ -350,7 +364,7 @@ impl WitnessService {
              WHERE d.id = $1",
         )
         .bind(witness.document_id)
-        .fetch_optional(db)
+        .fetch_optional(&mut *tx)
         .await?;
 
         if let Some((plan_id, vault_id)) = result {
@@ -361,10 +375,9 @@ impl WitnessService {
                 witness_id,
                 timestamp: chrono::Utc::now(),
             };
-            if let Err(e) = crate::will_events::WillEventService::emit(db, event).await {
-                tracing::warn!("Failed to emit WitnessDeclined event: {}", e);
-            }
+            crate::will_events::WillEventService::emit(&mut *tx, event).await?;
         }
+        tx.commit().await?;
 
         Ok(WitnessRecord {
             id: witness.id,
@@ -265,10 +265,10 @@ async fn log_inserted_on_plan_create_and_claim() {
         .expect("Failed to query action logs");
     assert_eq!(plan_log, 1, "Expected 1 plan_created log");
 
+    helpers::register_claim_wallet(&ctx.pool, user_id).await;
+
     // 3. Claim Plan (and verify plan_claimed log)
-    let claim_req = json!({
-        "beneficiary_email": "bene@example.com"
-    });
+    let claim_req = helpers::signed_claim_request(user_id, plan_id, "bene@example.com");
 
     let claim_res = ctx
         .app
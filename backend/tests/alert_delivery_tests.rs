@@ -0,0 +1,175 @@
+mod helpers;
+
+use async_trait::async_trait;
+use inheritx_backend::alert_delivery::{delivery_status, AlertDeliveryService, AlertRequest};
+use inheritx_backend::alert_provider::{
+    AlertChannel, AlertProvider, AlertReceipt, ConfiguredAlertProvider, LOG_ONLY_PROVIDER,
+};
+use inheritx_backend::alert_templates::TemplateVars;
+use inheritx_backend::notifications::notif_type;
+use inheritx_backend::ApiError;
+use uuid::Uuid;
+
+/// Accepts every SMS with a fresh gateway id, or rejects everything.
+struct FakeGateway {
+    failing: bool,
+}
+
+#[async_trait]
+impl AlertProvider for FakeGateway {
+    fn provider_name(&self, _channel: AlertChannel) -> &'static str {
+        "fake"
+    }
+
+    async fn send_sms(&self, _to: &str, _message: &str) -> anyhow::Result<AlertReceipt> {
+        if self.failing {
+            anyhow::bail!("gateway unavailable");
+        }
+        Ok(AlertReceipt {
+            provider: "fake",
+            message_id: Some(format!("SM{}", Uuid::new_v4().simple())),
+        })
+    }
+
+    async fn send_email(
+        &self,
+        _to: &str,
+        _subject: &str,
+        _body: &str,
+    ) -> anyhow::Result<AlertReceipt> {
+        anyhow::bail!("not an email provider")
+    }
+}
+
+async fn create_user(pool: &sqlx::PgPool, locale: &str) -> Uuid {
+    let email = format!("alerts-{}@example.com", Uuid::new_v4());
+    helpers::create_test_user_with_locale(pool, &email, locale)
+        .await
+        .expect("Failed to create user")
+}
+
+fn otp_request(user_id: Uuid, locale: &str) -> AlertRequest<'_> {
+    AlertRequest {
+        user_id: Some(user_id),
+        channel: AlertChannel::Sms,
+        recipient: "+15551234567",
+        notif_type: notif_type::TWO_FA_SENT,
+        locale,
+        vars: TemplateVars::from([
+            ("otp", "123456".to_string()),
+            ("expires_in_minutes", "5".to_string()),
+        ]),
+    }
+}
+
+#[tokio::test]
+async fn test_sent_sms_tracks_gateway_status_without_downgrading() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id = create_user(&ctx.pool, "es-MX").await;
+    let locale = AlertDeliveryService::user_locale(&ctx.pool, user_id)
+        .await
+        .unwrap();
+
+    let delivery = AlertDeliveryService::send(
+        &ctx.pool,
+        &FakeGateway { failing: false },
+        otp_request(user_id, &locale),
+    )
+    .await
+    .unwrap();
+    assert_eq!(delivery.status, delivery_status::SENT);
+    assert_eq!(delivery.locale, "es");
+    assert_eq!(delivery.subject, None);
+    let message_id = delivery.provider_message_id.expect("gateway id recorded");
+
+    let delivered = AlertDeliveryService::record_status(
+        &ctx.pool,
+        "fake",
+        &message_id,
+        delivery_status::DELIVERED,
+        None,
+    )
+    .await
+    .unwrap()
+    .expect("delivery updated");
+    assert_eq!(delivered.status, delivery_status::DELIVERED);
+    assert!(delivered.delivered_at.is_some());
+
+    // A late "sent" report must not undo the delivery.
+    let late = AlertDeliveryService::record_status(
+        &ctx.pool,
+        "fake",
+        &message_id,
+        delivery_status::SENT,
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(late.is_none());
+}
+
+#[tokio::test]
+async fn test_provider_failure_is_recorded_and_returned() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id = create_user(&ctx.pool, "en").await;
+
+    let result = AlertDeliveryService::send(
+        &ctx.pool,
+        &FakeGateway { failing: true },
+        otp_request(user_id, "en"),
+    )
+    .await;
+    assert!(matches!(result, Err(ApiError::ExternalService(_))));
+
+    let (status, error): (String, Option<String>) =
+        sqlx::query_as("SELECT status, error FROM alert_deliveries WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(status, delivery_status::FAILED);
+    assert_eq!(error.as_deref(), Some("gateway unavailable"));
+}
+
+#[tokio::test]
+async fn test_log_only_fallback_is_not_recorded_as_sent() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id = create_user(&ctx.pool, "en").await;
+
+    let logged = AlertDeliveryService::send(
+        &ctx.pool,
+        &ConfiguredAlertProvider::new(None, None).with_log_only_fallback(true),
+        otp_request(user_id, "en"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(logged.status, delivery_status::LOGGED);
+    assert_eq!(logged.provider, LOG_ONLY_PROVIDER);
+    assert_eq!(logged.sent_at, None);
+
+    // Outside development the OTP is neither logged nor reported as sent.
+    let result = AlertDeliveryService::send(
+        &ctx.pool,
+        &ConfiguredAlertProvider::new(None, None).with_log_only_fallback(false),
+        otp_request(user_id, "en"),
+    )
+    .await;
+    assert!(matches!(result, Err(ApiError::ExternalService(_))));
+    let statuses: Vec<String> = sqlx::query_scalar(
+        "SELECT status FROM alert_deliveries WHERE user_id = $1 ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        statuses,
+        vec![delivery_status::LOGGED, delivery_status::FAILED]
+    );
+}
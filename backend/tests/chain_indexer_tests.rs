@@ -0,0 +1,428 @@
+mod helpers;
+
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use httpmock::prelude::*;
+use inheritx_backend::chain_indexer::{
+    ChainIndexerConfig, ChainIndexerService, DECODE_ERROR_REASON, MAX_PARKED_ATTEMPTS,
+};
+use serde_json::{json, Value};
+use soroban_sdk::xdr::{Hash, Limits, ScAddress, ScMap, ScMapEntry, ScSymbol, ScVal, WriteXdr};
+use uuid::Uuid;
+
+fn sym(s: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+}
+
+fn encode(val: &ScVal) -> String {
+    BASE64.encode(val.to_xdr(Limits::none()).unwrap())
+}
+
+/// A contract address unique to `seed`.
+fn address(seed: Uuid) -> ScAddress {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(seed.as_bytes());
+    bytes[16..].copy_from_slice(seed.as_bytes());
+    ScAddress::Contract(Hash(bytes))
+}
+
+fn borrow_event(id: &str, tx_hash: &str, borrower: &ScAddress, asset: &ScAddress) -> Value {
+    let entries: Vec<ScMapEntry> = [
+        ("amount", ScVal::U64(5_000)),
+        ("asset", ScVal::Address(asset.clone())),
+        ("borrower", ScVal::Address(borrower.clone())),
+        ("collateral_amount", ScVal::U64(7_500)),
+        ("due_date", ScVal::U64(1_700_000_000)),
+        ("loan_id", ScVal::U64(1)),
+    ]
+    .into_iter()
+    .map(|(k, v)| ScMapEntry {
+        key: sym(k),
+        val: v,
+    })
+    .collect();
+    let body = ScVal::Map(Some(ScMap(entries.try_into().unwrap())));
+
+    json!({
+        "type": "contract",
+        "ledger": 120,
+        "ledgerClosedAt": "2026-04-29T10:00:00Z",
+        "contractId": "CCONTRACT",
+        "id": id,
+        "pagingToken": id,
+        "topic": [encode(&sym("POOL")), encode(&sym("BORROW"))],
+        "value": encode(&body),
+        "inSuccessfulContractCall": true,
+        "txHash": tx_hash,
+    })
+}
+
+fn plan_event(id: &str, topic: [&str; 2], fields: Vec<(&str, ScVal)>) -> Value {
+    let entries: Vec<ScMapEntry> = fields
+        .into_iter()
+        .map(|(k, v)| ScMapEntry {
+            key: sym(k),
+            val: v,
+        })
+        .collect();
+    let body = ScVal::Map(Some(ScMap(entries.try_into().unwrap())));
+
+    json!({
+        "type": "contract",
+        "ledger": 120,
+        "ledgerClosedAt": "2026-04-29T10:00:00Z",
+        "contractId": "CCONTRACT",
+        "id": id,
+        "pagingToken": id,
+        "topic": [encode(&sym(topic[0])), encode(&sym(topic[1]))],
+        "value": encode(&body),
+        "inSuccessfulContractCall": true,
+        "txHash": format!("tx-{id}"),
+    })
+}
+
+async fn link_wallet(pool: &sqlx::PgPool, user_id: Uuid, wallet: &ScAddress) {
+    sqlx::query("UPDATE users SET wallet_address = $2 WHERE id = $1")
+        .bind(user_id)
+        .bind(wallet.to_string())
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn indexed_rows(pool: &sqlx::PgPool, tx_hash: &str) -> Vec<(Uuid, String)> {
+    sqlx::query_as("SELECT user_id, asset_code FROM lending_events WHERE transaction_hash = $1")
+        .bind(tx_hash)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+async fn parked_count(pool: &sqlx::PgPool, indexer_name: &str) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM chain_indexer_parked_events WHERE indexer_name = $1")
+        .bind(indexer_name)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+/// Make the indexer's parked events due now instead of waiting out their
+/// back-off.
+async fn make_parked_due(pool: &sqlx::PgPool, indexer_name: &str) {
+    sqlx::query(
+        "UPDATE chain_indexer_parked_events SET next_attempt_at = NOW() WHERE indexer_name = $1",
+    )
+    .bind(indexer_name)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn parked_event(pool: &sqlx::PgPool, indexer_name: &str) -> (String, String, i32) {
+    sqlx::query_as(
+        "SELECT status, reason, attempts FROM chain_indexer_parked_events WHERE indexer_name = $1",
+    )
+    .bind(indexer_name)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_poll_ingests_dedups_and_parks_unattributed_events() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let tag = Uuid::new_v4();
+    let indexer_name = format!("test-{tag}");
+    let asset = address(Uuid::new_v4());
+    let known_wallet = address(Uuid::new_v4());
+    let unknown_wallet = address(Uuid::new_v4());
+
+    let known_user = helpers::create_test_user(&ctx.pool, &format!("indexer-{tag}@example.com"))
+        .await
+        .unwrap();
+    link_wallet(&ctx.pool, known_user, &known_wallet).await;
+
+    let known_tx = format!("known-{tag}");
+    let unknown_tx = format!("unknown-{tag}");
+    let known_event = borrow_event(&format!("{tag}-1"), &known_tx, &known_wallet, &asset);
+    let unknown_event = borrow_event(&format!("{tag}-2"), &unknown_tx, &unknown_wallet, &asset);
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST)
+                .json_body_partial(r#"{"method":"getEvents","params":{"startLedger":100}}"#);
+            then.status(200).json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {
+                    "events": [known_event.clone(), unknown_event],
+                    "latestLedger": 130,
+                    "cursor": "page-1",
+                }
+            }));
+        })
+        .await;
+    // The node serves the first event again after the cursor.
+    server
+        .mock_async(|when, then| {
+            when.method(POST).json_body_partial(
+                r#"{"method":"getEvents","params":{"pagination":{"cursor":"page-1"}}}"#,
+            );
+            then.status(200).json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "events": [known_event], "latestLedger": 131, "cursor": "page-1" }
+            }));
+        })
+        .await;
+
+    let indexer = ChainIndexerService::new(
+        ctx.pool.clone(),
+        ChainIndexerConfig {
+            indexer_name: indexer_name.clone(),
+            rpc_url: server.url("/"),
+            contract_ids: vec!["CCONTRACT".to_string()],
+            poll_interval_secs: 10,
+            page_limit: 10,
+            start_ledger: Some(100),
+            asset_codes: HashMap::from([(asset.to_string(), "USDC".to_string())]),
+        },
+    );
+
+    // The known borrower is ingested; the unknown one is parked, not dropped.
+    assert_eq!(indexer.poll_once().await.unwrap(), 1);
+    assert_eq!(
+        indexed_rows(&ctx.pool, &known_tx).await,
+        vec![(known_user, "USDC".to_string())]
+    );
+    assert!(indexed_rows(&ctx.pool, &unknown_tx).await.is_empty());
+    assert_eq!(parked_count(&ctx.pool, &indexer_name).await, 1);
+
+    // A replayed event is detected and skipped; the parked one is backed off.
+    assert_eq!(indexer.poll_once().await.unwrap(), 0);
+    assert_eq!(indexed_rows(&ctx.pool, &known_tx).await.len(), 1);
+    assert_eq!(parked_count(&ctx.pool, &indexer_name).await, 1);
+
+    // Once the wallet is linked, the parked event is ingested on the next poll.
+    let late_user =
+        helpers::create_test_user(&ctx.pool, &format!("indexer-late-{tag}@example.com"))
+            .await
+            .unwrap();
+    link_wallet(&ctx.pool, late_user, &unknown_wallet).await;
+    make_parked_due(&ctx.pool, &indexer_name).await;
+
+    assert_eq!(indexer.poll_once().await.unwrap(), 1);
+    assert_eq!(
+        indexed_rows(&ctx.pool, &unknown_tx).await,
+        vec![(late_user, "USDC".to_string())]
+    );
+    assert_eq!(parked_count(&ctx.pool, &indexer_name).await, 0);
+}
+
+#[tokio::test]
+async fn test_undecodable_event_is_parked_then_dead_lettered() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let tag = Uuid::new_v4();
+    let indexer_name = format!("test-{tag}");
+    let mut garbled = borrow_event(
+        &format!("{tag}-1"),
+        &format!("garbled-{tag}"),
+        &address(Uuid::new_v4()),
+        &address(Uuid::new_v4()),
+    );
+    garbled["value"] = json!("not-xdr");
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST);
+            then.status(200).json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "events": [garbled], "latestLedger": 130, "cursor": "page-1" }
+            }));
+        })
+        .await;
+
+    let indexer = ChainIndexerService::new(
+        ctx.pool.clone(),
+        ChainIndexerConfig {
+            indexer_name: indexer_name.clone(),
+            rpc_url: server.url("/"),
+            contract_ids: vec!["CCONTRACT".to_string()],
+            poll_interval_secs: 10,
+            page_limit: 10,
+            start_ledger: Some(100),
+            asset_codes: HashMap::new(),
+        },
+    );
+
+    // The cursor moves past the event, but it is kept rather than skipped.
+    assert_eq!(indexer.poll_once().await.unwrap(), 0);
+    let (status, reason, attempts) = parked_event(&ctx.pool, &indexer_name).await;
+    assert_eq!((status.as_str(), attempts), ("parked", 1));
+    assert!(reason.starts_with(DECODE_ERROR_REASON));
+
+    // Not yet due, so the next poll leaves it alone.
+    indexer.poll_once().await.unwrap();
+    assert_eq!(parked_event(&ctx.pool, &indexer_name).await.2, 1);
+
+    // Each due retry counts until the budget runs out; then it stays dead.
+    for attempt in 2..=MAX_PARKED_ATTEMPTS {
+        make_parked_due(&ctx.pool, &indexer_name).await;
+        indexer.poll_once().await.unwrap();
+        assert_eq!(parked_event(&ctx.pool, &indexer_name).await.2, attempt);
+    }
+    make_parked_due(&ctx.pool, &indexer_name).await;
+    indexer.poll_once().await.unwrap();
+    let (status, reason, attempts) = parked_event(&ctx.pool, &indexer_name).await;
+    assert_eq!((status.as_str(), attempts), ("dead", MAX_PARKED_ATTEMPTS));
+    assert!(reason.starts_with(DECODE_ERROR_REASON));
+}
+
+#[tokio::test]
+async fn test_failed_page_does_not_advance_cursor() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let indexer_name = format!("test-{}", Uuid::new_v4());
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST);
+            then.status(200).json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": -32600, "message": "start ledger out of range" }
+            }));
+        })
+        .await;
+
+    let indexer = ChainIndexerService::new(
+        ctx.pool.clone(),
+        ChainIndexerConfig {
+            indexer_name: indexer_name.clone(),
+            rpc_url: server.url("/"),
+            contract_ids: vec!["CCONTRACT".to_string()],
+            poll_interval_secs: 10,
+            page_limit: 10,
+            start_ledger: Some(100),
+            asset_codes: HashMap::new(),
+        },
+    );
+
+    assert!(indexer.poll_once().await.is_err());
+    let cursor: Option<String> =
+        sqlx::query_scalar("SELECT cursor FROM chain_indexer_cursors WHERE indexer_name = $1")
+            .bind(&indexer_name)
+            .fetch_optional(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(cursor, None);
+}
+
+#[tokio::test]
+async fn test_installments_are_tracked_per_beneficiary() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let tag = Uuid::new_v4();
+    let owner = helpers::create_test_user(&ctx.pool, &format!("payout-{tag}@example.com"))
+        .await
+        .unwrap();
+    let plan_id = Uuid::new_v4();
+    let contract_plan_id = i64::from(u32::from_be_bytes(tag.as_bytes()[..4].try_into().unwrap()));
+    sqlx::query(
+        r#"
+        INSERT INTO plans (
+            id, user_id, title, fee, net_amount, status,
+            distribution_method, contract_plan_id, contract_created_at
+        )
+        VALUES ($1, $2, 'Monthly plan', '0.00', '100.00', 'pending', 'Monthly', $3, 1000)
+        "#,
+    )
+    .bind(plan_id)
+    .bind(owner)
+    .bind(contract_plan_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let installment = |id: &str, installment: u32| {
+        plan_event(
+            id,
+            ["PAYOUT", "INSTALL"],
+            vec![
+                ("amount", ScVal::U64(100)),
+                ("beneficiary_index", ScVal::U32(1)),
+                ("installment", ScVal::U32(installment)),
+                ("plan_id", ScVal::U64(contract_plan_id as u64)),
+                ("remaining_installments", ScVal::U32(12 - installment)),
+            ],
+        )
+    };
+    let events = vec![
+        installment(&format!("{tag}-1"), 2),
+        // Replays of an older installment do not move progress back.
+        installment(&format!("{tag}-2"), 1),
+        plan_event(
+            &format!("{tag}-3"),
+            ["INHERIT", "TRIGGER"],
+            vec![
+                ("outstanding_loans", ScVal::U64(0)),
+                ("plan_id", ScVal::U64(contract_plan_id as u64)),
+                ("triggered_at", ScVal::U64(5_000)),
+            ],
+        ),
+    ];
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST);
+            then.status(200).json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "events": events, "latestLedger": 130, "cursor": "page-1" }
+            }));
+        })
+        .await;
+    let indexer = ChainIndexerService::new(
+        ctx.pool.clone(),
+        ChainIndexerConfig {
+            indexer_name: format!("test-{tag}"),
+            rpc_url: server.url("/"),
+            contract_ids: vec!["CCONTRACT".to_string()],
+            poll_interval_secs: 10,
+            page_limit: 10,
+            start_ledger: Some(100),
+            asset_codes: HashMap::new(),
+        },
+    );
+    indexer.poll_once().await.unwrap();
+
+    // Only the beneficiary who was paid advances.
+    let progress: Vec<(i32, i32)> = sqlx::query_as(
+        "SELECT beneficiary_index, paid_installments FROM plan_payout_progress WHERE plan_id = $1",
+    )
+    .bind(plan_id)
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(progress, vec![(1, 2)]);
+
+    let triggered_at: Option<i64> =
+        sqlx::query_scalar("SELECT inheritance_triggered_at FROM plans WHERE id = $1")
+            .bind(plan_id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(triggered_at, Some(5_000));
+}
@@ -8,7 +8,7 @@ use axum::{
 use chrono::Utc;
 use inheritx_backend::auth::UserClaims;
 use jsonwebtoken::{encode, EncodingKey, Header};
-use serde_json::{json, Value};
+use serde_json::Value;
 use std::net::SocketAddr;
 use tokio::net::TcpListener;
 use tower::ServiceExt;
@@ -166,16 +166,13 @@ async fn test_claim_before_maturity_returns_400() {
         .expect("Server failed");
     });
 
-    let otp = test_context.prepare_2fa(user_id, "123456").await;
     let token = generate_test_token(user_id, &email);
     let client = reqwest::Client::new();
+    helpers::register_claim_wallet(&pool, user_id).await;
     let response = client
         .post(format!("http://{addr}/api/plans/{plan_id}/claim"))
         .header("Authorization", format!("Bearer {token}"))
-        .json(&json!({
-            "beneficiary_email": "beneficiary@example.com",
-            "two_fa_code": otp
-        }))
+        .json(&helpers::signed_claim_request(user_id, plan_id, "beneficiary@example.com"))
         .send()
         .await
         .expect("Failed to send request");
@@ -203,11 +200,8 @@ async fn test_claim_plan_is_due() {
     approve_kyc_direct(&ctx.pool, user_id).await;
     let plan_id = insert_due_plan(&ctx.pool, user_id).await;
 
-    let otp = ctx.prepare_2fa(user_id, "123456").await;
-    let body = serde_json::json!({
-        "beneficiary_email": "beneficiary@example.com",
-        "two_fa_code": otp
-    });
+    helpers::register_claim_wallet(&ctx.pool, user_id).await;
+    let body = helpers::signed_claim_request(user_id, plan_id, "beneficiary@example.com");
     let response = ctx
         .app
         .clone()
@@ -233,6 +227,48 @@ async fn test_claim_plan_is_due() {
     assert_eq!(json["status"], "success");
 }
 
+#[tokio::test]
+async fn test_claim_rejects_unregistered_wallet() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+
+    let user_id = helpers::create_test_user(&ctx.pool, &format!("stranger-{}@example.com", Uuid::new_v4()))
+        .await
+        .expect("Failed to create user");
+    let token = generate_user_token(user_id);
+    let plan_id = insert_due_plan(&ctx.pool, user_id).await;
+    helpers::register_claim_wallet(&ctx.pool, user_id).await;
+
+    // Validly signed, but by a wallet the claimant never registered.
+    let stranger = helpers::claim_wallet_key(Uuid::new_v4());
+    let body = helpers::claim_request_signed_by(&stranger, plan_id, "beneficiary@example.com");
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/api/plans/{plan_id}/claim"))
+                .header("Authorization", format!("Bearer {token}"))
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    serde_json::to_string(&body).expect("Failed to serialize request body"),
+                ))
+                .expect("Failed to build request"),
+        )
+        .await
+        .expect("request failed");
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let status: String = sqlx::query_scalar("SELECT status FROM plans WHERE id = $1")
+        .bind(plan_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Failed to read plan");
+    assert_ne!(status, "claimed");
+}
+
 #[tokio::test]
 async fn test_claim_requires_kyc_approved() {
     let Some(ctx) = helpers::TestContext::from_env().await else {
@@ -243,11 +279,8 @@ async fn test_claim_requires_kyc_approved() {
     let token = generate_user_token(user_id);
     let plan_id = insert_due_plan(&ctx.pool, user_id).await;
 
-    let otp = ctx.prepare_2fa(user_id, "111111").await;
-    let body = serde_json::json!({
-        "beneficiary_email": "beneficiary@example.com",
-        "two_fa_code": otp
-    });
+    helpers::register_claim_wallet(&ctx.pool, user_id).await;
+    let body = helpers::signed_claim_request(user_id, plan_id, "beneficiary@example.com");
     let response = ctx
         .app
         .clone()
@@ -280,11 +313,8 @@ async fn test_claim_recorded_on_success() {
     approve_kyc_direct(&ctx.pool, user_id).await;
     let plan_id = insert_due_plan(&ctx.pool, user_id).await;
 
-    let otp = ctx.prepare_2fa(user_id, "123456").await;
-    let body = serde_json::json!({
-        "beneficiary_email": "claim-record@example.com",
-        "two_fa_code": otp
-    });
+    helpers::register_claim_wallet(&ctx.pool, user_id).await;
+    let body = helpers::signed_claim_request(user_id, plan_id, "claim-record@example.com");
     let response = ctx
         .app
         .clone()
@@ -331,11 +361,8 @@ async fn test_claim_audit_log_inserted() {
     approve_kyc_direct(&ctx.pool, user_id).await;
     let plan_id = insert_due_plan(&ctx.pool, user_id).await;
 
-    let otp = ctx.prepare_2fa(user_id, "123456").await;
-    let body = serde_json::json!({
-        "beneficiary_email": "audit-test@example.com",
-        "two_fa_code": otp
-    });
+    helpers::register_claim_wallet(&ctx.pool, user_id).await;
+    let body = helpers::signed_claim_request(user_id, plan_id, "audit-test@example.com");
     let response = ctx
         .app
         .clone()
@@ -393,11 +420,8 @@ async fn test_claim_notification_created() {
     .await
     .expect("Failed to count notifications before claim");
 
-    let otp = ctx.prepare_2fa(user_id, "123456").await;
-    let body = serde_json::json!({
-        "beneficiary_email": "notify-test@example.com",
-        "two_fa_code": otp
-    });
+    helpers::register_claim_wallet(&ctx.pool, user_id).await;
+    let body = helpers::signed_claim_request(user_id, plan_id, "notify-test@example.com");
     let response = ctx
         .app
         .clone()
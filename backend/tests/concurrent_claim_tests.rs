@@ -8,7 +8,6 @@ use axum::{
     http::{Request, StatusCode},
 };
 use chrono::Utc;
-use serde_json::json;
 use tokio::join;
 use tower::ServiceExt;
 use uuid::Uuid;
@@ -80,6 +79,7 @@ async fn test_concurrent_claim_same_email_only_one_succeeds() {
 
     // Prepare two simultaneous claim requests with the SAME email
     let claim_email = "beneficiary@test.com";
+    helpers::register_claim_wallet(&pool, user_id).await;
     let claim_req = || {
         Request::builder()
             .method("POST")
@@ -87,7 +87,7 @@ async fn test_concurrent_claim_same_email_only_one_succeeds() {
             .header("Content-Type", "application/json")
             .header("X-User-Id", user_id.to_string())
             .body(Body::from(
-                serde_json::to_string(&json!({ "beneficiary_email": claim_email })).unwrap(),
+                serde_json::to_string(&helpers::signed_claim_request(user_id, plan_id, claim_email)).unwrap(),
             ))
             .unwrap()
     };
@@ -154,6 +154,8 @@ async fn test_concurrent_claim_different_emails_only_one_succeeds() {
     // Create a due plan
     let plan_id = insert_due_plan(&pool, user_id).await;
 
+    helpers::register_claim_wallet(&pool, user_id).await;
+
     // Prepare two simultaneous claim requests with DIFFERENT emails
     let claim_req_with_email = |email: &str| {
         Request::builder()
@@ -162,7 +164,7 @@ async fn test_concurrent_claim_different_emails_only_one_succeeds() {
             .header("Content-Type", "application/json")
             .header("X-User-Id", user_id.to_string())
             .body(Body::from(
-                serde_json::to_string(&json!({ "beneficiary_email": email })).unwrap(),
+                serde_json::to_string(&helpers::signed_claim_request(user_id, plan_id, email)).unwrap(),
             ))
             .unwrap()
     };
@@ -230,6 +232,8 @@ async fn test_concurrent_claim_updates_plan_status() {
     // Create a due plan
     let plan_id = insert_due_plan(&pool, user_id).await;
 
+    helpers::register_claim_wallet(&pool, user_id).await;
+
     // Submit a claim request
     let claim_req = Request::builder()
         .method("POST")
@@ -237,7 +241,7 @@ async fn test_concurrent_claim_updates_plan_status() {
         .header("Content-Type", "application/json")
         .header("X-User-Id", user_id.to_string())
         .body(Body::from(
-            serde_json::to_string(&json!({ "beneficiary_email": "test@beneficiary.com" })).unwrap(),
+            serde_json::to_string(&helpers::signed_claim_request(user_id, plan_id, "test@beneficiary.com")).unwrap(),
         ))
         .unwrap();
 
@@ -293,6 +297,8 @@ async fn test_claim_after_concurrent_claims_fails() {
     // Create a due plan
     let plan_id = insert_due_plan(&pool, user_id).await;
 
+    helpers::register_claim_wallet(&pool, user_id).await;
+
     // Submit first claim (should succeed)
     let claim_req = Request::builder()
         .method("POST")
@@ -300,7 +306,7 @@ async fn test_claim_after_concurrent_claims_fails() {
         .header("Content-Type", "application/json")
         .header("X-User-Id", user_id.to_string())
         .body(Body::from(
-            serde_json::to_string(&json!({ "beneficiary_email": "first@beneficiary.com" }))
+            serde_json::to_string(&helpers::signed_claim_request(user_id, plan_id, "first@beneficiary.com"))
                 .unwrap(),
         ))
         .unwrap();
@@ -319,7 +325,7 @@ async fn test_claim_after_concurrent_claims_fails() {
         .header("Content-Type", "application/json")
         .header("X-User-Id", user_id.to_string())
         .body(Body::from(
-            serde_json::to_string(&json!({ "beneficiary_email": "second@beneficiary.com" }))
+            serde_json::to_string(&helpers::signed_claim_request(user_id, plan_id, "second@beneficiary.com"))
                 .unwrap(),
         ))
         .unwrap();
@@ -372,6 +378,8 @@ async fn test_concurrent_claim_creates_single_audit_log() {
     // Create a due plan
     let plan_id = insert_due_plan(&pool, user_id).await;
 
+    helpers::register_claim_wallet(&pool, user_id).await;
+
     // Submit two concurrent claim requests with different emails
     let claim_req_with_email = |email: &str| {
         Request::builder()
@@ -380,7 +388,7 @@ async fn test_concurrent_claim_creates_single_audit_log() {
             .header("Content-Type", "application/json")
             .header("X-User-Id", user_id.to_string())
             .body(Body::from(
-                serde_json::to_string(&json!({ "beneficiary_email": email })).unwrap(),
+                serde_json::to_string(&helpers::signed_claim_request(user_id, plan_id, email)).unwrap(),
             ))
             .unwrap()
     };
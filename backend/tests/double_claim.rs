@@ -99,9 +99,8 @@ async fn first_claim_succeeds_second_claim_fails() {
     let plan_id = seed_due_plan(&ctx.pool, user_id).await;
     let token = generate_user_token(user_id, &email);
 
-    let claim_body = serde_json::json!({
-        "beneficiary_email": "beneficiary@example.com"
-    });
+    helpers::register_claim_wallet(&ctx.pool, user_id).await;
+    let claim_body = helpers::signed_claim_request(user_id, plan_id, "beneficiary@example.com");
     let claim_body_str =
         serde_json::to_string(&claim_body).expect("Failed to serialize claim request");
 
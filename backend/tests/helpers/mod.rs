@@ -82,18 +82,34 @@ impl TestContext {
 
 #[allow(dead_code)]
 pub async fn create_test_user(pool: &PgPool, email: &str) -> sqlx::Result<uuid::Uuid> {
+    create_test_user_with_locale(pool, email, "en").await
+}
+
+/// Creates a KYC-approved user with a wallet address and the given locale.
+#[allow(dead_code)]
+pub async fn create_test_user_with_locale(
+    pool: &PgPool,
+    email: &str,
+    locale: &str,
+) -> sqlx::Result<uuid::Uuid> {
     let user_id = uuid::Uuid::new_v4();
-    let wallet = format!("G{}", &user_id.to_string().replace("-", "")[..55]);
+    let wallet = format!("G{}", user_id.simple());
 
     sqlx::query(
-        "INSERT INTO users (id, email, wallet_address, kyc_status) VALUES ($1, $2, $3, 'approved')",
+        "INSERT INTO users (id, email, password_hash, wallet_address, locale) VALUES ($1, $2, 'test_password_hash', $3, $4)",
     )
     .bind(user_id)
     .bind(email)
     .bind(wallet)
+    .bind(locale)
     .execute(pool)
     .await?;
 
+    sqlx::query("INSERT INTO kyc_status (user_id, status) VALUES ($1, 'approved')")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
     Ok(user_id)
 }
 
@@ -103,7 +119,7 @@ pub async fn create_test_admin(pool: &PgPool, email: &str) -> sqlx::Result<uuid:
     let password_hash = bcrypt::hash("test_password", bcrypt::DEFAULT_COST).unwrap();
 
     sqlx::query(
-        "INSERT INTO admins (id, email, password_hash, status) VALUES ($1, $2, $3, 'active')",
+        "INSERT INTO admins (id, email, password_hash, role, status) VALUES ($1, $2, $3, 'admin', 'active')",
     )
     .bind(admin_id)
     .bind(email)
@@ -165,3 +181,84 @@ pub async fn create_admin_and_get_token(ctx: &mut TestContext) -> String {
 
     json["token"].as_str().unwrap().to_string()
 }
+
+/// Ed25519 key of the test wallet registered for `user_id`. Derived from the
+/// user id, so the same user always signs with the same wallet.
+#[allow(dead_code)]
+pub fn claim_wallet_key(user_id: uuid::Uuid) -> ring::signature::Ed25519KeyPair {
+    let seed = ring::digest::digest(&ring::digest::SHA256, user_id.as_bytes());
+    ring::signature::Ed25519KeyPair::from_seed_unchecked(seed.as_ref()).unwrap()
+}
+
+/// Registers `user_id`'s test wallet as the user's wallet address, as a
+/// wallet sign-in would. Claims are only accepted from this wallet.
+#[allow(dead_code)]
+pub async fn register_claim_wallet(pool: &PgPool, user_id: uuid::Uuid) {
+    use ring::signature::KeyPair;
+
+    let wallet_address = hex::encode(claim_wallet_key(user_id).public_key().as_ref());
+    sqlx::query("UPDATE users SET wallet_address = $2 WHERE id = $1")
+        .bind(user_id)
+        .bind(wallet_address)
+        .execute(pool)
+        .await
+        .expect("Failed to register claim wallet");
+}
+
+/// Claim request body for the first installment of `plan_id`, signed by
+/// `key` just now.
+#[allow(dead_code)]
+pub fn claim_request_signed_by(
+    key: &ring::signature::Ed25519KeyPair,
+    plan_id: uuid::Uuid,
+    beneficiary_email: &str,
+) -> serde_json::Value {
+    claim_request_for_installment(
+        key,
+        plan_id,
+        beneficiary_email,
+        1,
+        chrono::Utc::now().timestamp(),
+    )
+}
+
+/// Claim request body for `installment` of `plan_id`, signed by `key` at
+/// `signed_at`.
+#[allow(dead_code)]
+pub fn claim_request_for_installment(
+    key: &ring::signature::Ed25519KeyPair,
+    plan_id: uuid::Uuid,
+    beneficiary_email: &str,
+    installment: u32,
+    signed_at: i64,
+) -> serde_json::Value {
+    use ring::signature::KeyPair;
+
+    let wallet_address = hex::encode(key.public_key().as_ref());
+    let message = inheritx_backend::service::PlanService::claim_message(
+        plan_id,
+        beneficiary_email,
+        &wallet_address,
+        installment,
+        signed_at,
+    );
+
+    json!({
+        "beneficiary_email": beneficiary_email,
+        "wallet_address": wallet_address,
+        "installment": installment,
+        "signed_at": signed_at,
+        "signature_hex": hex::encode(key.sign(message.as_bytes()).as_ref()),
+    })
+}
+
+/// Claim request body for `plan_id`, signed by `user_id`'s test wallet.
+/// Call [`register_claim_wallet`] first.
+#[allow(dead_code)]
+pub fn signed_claim_request(
+    user_id: uuid::Uuid,
+    plan_id: uuid::Uuid,
+    beneficiary_email: &str,
+) -> serde_json::Value {
+    claim_request_signed_by(&claim_wallet_key(user_id), plan_id, beneficiary_email)
+}
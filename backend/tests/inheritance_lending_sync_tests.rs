@@ -108,10 +108,8 @@ async fn claim_is_blocked_when_plan_has_active_lending_utilization() {
     .await
     .expect("Failed to seed lending utilization");
 
-    let claim_body = serde_json::json!({
-        "beneficiary_email": "beneficiary@example.com",
-        "two_fa_code": "123456"
-    });
+    helpers::register_claim_wallet(&ctx.pool, user_id).await;
+    let claim_body = helpers::signed_claim_request(user_id, plan_id, "beneficiary@example.com");
 
     let response = ctx
         .app
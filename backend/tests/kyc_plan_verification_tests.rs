@@ -297,10 +297,10 @@ async fn user_with_pending_kyc_cannot_claim_plan() {
 
     let token = generate_user_token(user_id);
 
+    helpers::register_claim_wallet(&test_context.pool, user_id).await;
+
     // 5. Attempt to claim plan
-    let claim_req = json!({
-        "beneficiary_email": "beneficiary@example.com"
-    });
+    let claim_req = helpers::signed_claim_request(user_id, plan_id, "beneficiary@example.com");
 
     let response = test_context
         .app
@@ -391,10 +391,10 @@ async fn user_with_rejected_kyc_cannot_claim_plan() {
 
     let token = generate_user_token(user_id);
 
+    helpers::register_claim_wallet(&test_context.pool, user_id).await;
+
     // 5. Attempt to claim plan
-    let claim_req = json!({
-        "beneficiary_email": "beneficiary@example.com"
-    });
+    let claim_req = helpers::signed_claim_request(user_id, plan_id, "beneficiary@example.com");
 
     let response = test_context
         .app
@@ -485,10 +485,10 @@ async fn user_with_approved_kyc_can_claim_plan() {
 
     let token = generate_user_token(user_id);
 
+    helpers::register_claim_wallet(&test_context.pool, user_id).await;
+
     // 5. Claim plan
-    let claim_req = json!({
-        "beneficiary_email": "beneficiary@example.com"
-    });
+    let claim_req = helpers::signed_claim_request(user_id, plan_id, "beneficiary@example.com");
 
     let response = test_context
         .app
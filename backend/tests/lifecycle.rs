@@ -186,17 +186,20 @@ async fn test_full_lifecycle_flow() {
         .unwrap();
     let body: Value = serde_json::from_slice(&body).unwrap();
     let plan_id = body["data"]["id"].as_str().unwrap();
+    let plan_uuid = Uuid::parse_str(plan_id).unwrap();
 
     // 6. Simulate maturity
     sqlx::query(
         "UPDATE plans SET status = 'active', distribution_method = 'LumpSum', contract_created_at = $1, contract_plan_id = 1 WHERE id = $2"
     )
     .bind(chrono::Utc::now().timestamp() - 86400) // 1 day ago
-    .bind(Uuid::parse_str(plan_id).unwrap())
+    .bind(plan_uuid)
     .execute(&test_context.pool)
     .await
     .unwrap();
 
+    helpers::register_claim_wallet(&test_context.pool, user_id).await;
+
     // 7. Claim
     let response = test_context
         .app
@@ -208,9 +211,7 @@ async fn test_full_lifecycle_flow() {
                 .header("Authorization", format!("Bearer {user_token}"))
                 .header("Content-Type", "application/json")
                 .body(Body::from(
-                    json!({
-                        "beneficiary_email": "beneficiary@example.com"
-                    })
+                    helpers::signed_claim_request(user_id, plan_uuid, "beneficiary@example.com")
                     .to_string(),
                 ))
                 .unwrap(),
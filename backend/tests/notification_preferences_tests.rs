@@ -0,0 +1,145 @@
+mod helpers;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use inheritx_backend::alert_provider::{AlertChannel, AlertProvider, AlertReceipt};
+use inheritx_backend::notification_preferences::{
+    NotificationChannel, NotificationFrequency, NotificationPreferenceService,
+    UpdatePreferenceRequest,
+};
+use inheritx_backend::notifications::{notif_type, NotificationService};
+use inheritx_backend::{ApiError, NotificationDispatcher};
+use uuid::Uuid;
+
+/// Accepts every email without sending anything.
+struct AcceptingMailer;
+
+#[async_trait]
+impl AlertProvider for AcceptingMailer {
+    fn provider_name(&self, _channel: AlertChannel) -> &'static str {
+        "fake"
+    }
+
+    async fn send_sms(&self, _to: &str, _message: &str) -> anyhow::Result<AlertReceipt> {
+        anyhow::bail!("not an SMS provider")
+    }
+
+    async fn send_email(
+        &self,
+        _to: &str,
+        _subject: &str,
+        _body: &str,
+    ) -> anyhow::Result<AlertReceipt> {
+        Ok(AlertReceipt {
+            provider: "fake",
+            message_id: None,
+        })
+    }
+}
+
+async fn create_user(pool: &sqlx::PgPool) -> Uuid {
+    helpers::create_test_user(pool, &format!("prefs-{}@example.com", Uuid::new_v4()))
+        .await
+        .expect("Failed to create user")
+}
+
+async fn notify(pool: &sqlx::PgPool, user_id: Uuid, notif_type: &str) -> Uuid {
+    let mut conn = pool.acquire().await.unwrap();
+    NotificationService::create(&mut conn, user_id, notif_type, "test notification")
+        .await
+        .unwrap()
+        .id
+}
+
+async fn dispatch_state(pool: &sqlx::PgPool, id: Uuid) -> (String, Option<String>) {
+    sqlx::query_as("SELECT dispatch_status, digest_frequency FROM notifications WHERE id = $1")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_security_notifications_cannot_be_muted() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id = create_user(&ctx.pool).await;
+
+    let result = NotificationPreferenceService::update_preference(
+        &ctx.pool,
+        user_id,
+        notif_type::EMERGENCY_ACCESS_GRANTED,
+        &UpdatePreferenceRequest {
+            channels: vec![NotificationChannel::InApp],
+            frequency: NotificationFrequency::Immediate,
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(ApiError::BadRequest(_))));
+
+    // Muting an ordinary type hides it from the in-app list.
+    NotificationPreferenceService::update_preference(
+        &ctx.pool,
+        user_id,
+        notif_type::YIELD_UPDATE,
+        &UpdatePreferenceRequest {
+            channels: vec![],
+            frequency: NotificationFrequency::Immediate,
+        },
+    )
+    .await
+    .unwrap();
+    notify(&ctx.pool, user_id, notif_type::YIELD_UPDATE).await;
+    notify(&ctx.pool, user_id, notif_type::EMERGENCY_ACCESS_GRANTED).await;
+
+    let listed = NotificationService::list_for_user(&ctx.pool, user_id)
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].notif_type, notif_type::EMERGENCY_ACCESS_GRANTED);
+}
+
+#[tokio::test]
+async fn test_digest_types_are_parked_and_security_types_sent_immediately() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id = create_user(&ctx.pool).await;
+    NotificationPreferenceService::update_preference(
+        &ctx.pool,
+        user_id,
+        notif_type::YIELD_UPDATE,
+        &UpdatePreferenceRequest {
+            channels: vec![NotificationChannel::InApp, NotificationChannel::Email],
+            frequency: NotificationFrequency::Daily,
+        },
+    )
+    .await
+    .unwrap();
+
+    let digested = notify(&ctx.pool, user_id, notif_type::YIELD_UPDATE).await;
+    let urgent = notify(&ctx.pool, user_id, notif_type::EMERGENCY_ACCESS_GRANTED).await;
+
+    let dispatcher = NotificationDispatcher::new(ctx.pool.clone(), Arc::new(AcceptingMailer));
+    dispatcher.dispatch_pending().await.unwrap();
+
+    assert_eq!(
+        dispatch_state(&ctx.pool, digested).await,
+        ("digest".to_string(), Some("daily".to_string()))
+    );
+    assert_eq!(
+        dispatch_state(&ctx.pool, urgent).await,
+        ("dispatched".to_string(), None)
+    );
+
+    let emailed: Vec<String> = sqlx::query_scalar(
+        "SELECT notif_type FROM alert_deliveries WHERE user_id = $1 AND channel = 'email'",
+    )
+    .bind(user_id)
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(emailed, vec![notif_type::EMERGENCY_ACCESS_GRANTED]);
+}
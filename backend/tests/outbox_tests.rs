@@ -0,0 +1,149 @@
+mod helpers;
+
+use inheritx_backend::outbox::{
+    InMemoryMessageBus, Outbox, OutboxMessage, OutboxRelay, MAX_RELAY_ATTEMPTS,
+};
+use inheritx_backend::webhook_events::KycSubmitted;
+use std::sync::Arc;
+use uuid::Uuid;
+
+async fn create_user(pool: &sqlx::PgPool) -> Uuid {
+    helpers::create_test_user(pool, &format!("outbox-{}@example.com", Uuid::new_v4()))
+        .await
+        .expect("Failed to create user")
+}
+
+async fn outbox_status(pool: &sqlx::PgPool, event_id: &str) -> Option<(String, i32)> {
+    sqlx::query_as("SELECT status, attempt_count FROM outbox_events WHERE event_id = $1")
+        .bind(event_id)
+        .fetch_optional(pool)
+        .await
+        .unwrap()
+}
+
+async fn bus_status(pool: &sqlx::PgPool, event_id: &str) -> (Option<String>, i32) {
+    sqlx::query_as("SELECT bus_status, bus_attempt_count FROM outbox_events WHERE event_id = $1")
+        .bind(event_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn notification_count(pool: &sqlx::PgPool, user_id: Uuid) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND type = 'outbox_test'",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_outbox_event_is_dropped_with_rolled_back_transaction() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id = create_user(&ctx.pool).await;
+
+    let message = OutboxMessage::new(user_id, &KycSubmitted { user_id }).unwrap();
+    let mut tx = ctx.pool.begin().await.unwrap();
+    Outbox::record(&mut *tx, &message).await.unwrap();
+    tx.rollback().await.unwrap();
+
+    assert_eq!(outbox_status(&ctx.pool, &message.event.id).await, None);
+}
+
+#[tokio::test]
+async fn test_bus_outage_does_not_hold_back_notifications() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id = create_user(&ctx.pool).await;
+
+    let message = OutboxMessage::new(user_id, &KycSubmitted { user_id })
+        .unwrap()
+        .with_notification("outbox_test", "KYC submitted");
+    let mut tx = ctx.pool.begin().await.unwrap();
+    Outbox::record(&mut *tx, &message).await.unwrap();
+    tx.commit().await.unwrap();
+
+    let bus = Arc::new(InMemoryMessageBus::new());
+    let relay = OutboxRelay::new(ctx.pool.clone()).with_bus(bus.clone(), "test");
+
+    // The notification is committed even though the bus is down; only the
+    // bus publication is rescheduled.
+    bus.set_failing(true);
+    relay.relay_pending().await.unwrap();
+    relay.publish_pending().await.unwrap();
+    assert_eq!(
+        outbox_status(&ctx.pool, &message.event.id).await,
+        Some(("relayed".to_string(), 1))
+    );
+    assert_eq!(notification_count(&ctx.pool, user_id).await, 1);
+    assert_eq!(
+        bus_status(&ctx.pool, &message.event.id).await,
+        (Some("pending".to_string()), 1)
+    );
+
+    // Make the publication due again and retry it with a working bus.
+    bus.set_failing(false);
+    sqlx::query("UPDATE outbox_events SET bus_available_at = NOW() WHERE event_id = $1")
+        .bind(&message.event.id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    relay.relay_pending().await.unwrap();
+    relay.publish_pending().await.unwrap();
+
+    assert_eq!(
+        bus_status(&ctx.pool, &message.event.id).await,
+        (Some("published".to_string()), 2)
+    );
+    assert_eq!(notification_count(&ctx.pool, user_id).await, 1);
+    let published = bus
+        .messages()
+        .into_iter()
+        .find(|m| m.key == message.event.id)
+        .expect("event was not published to the bus");
+    assert_eq!(published.topic, "test.kyc.submitted");
+    assert_eq!(published.payload["data"]["user_id"], user_id.to_string());
+}
+
+#[tokio::test]
+async fn test_failed_events_can_be_requeued() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id = create_user(&ctx.pool).await;
+
+    let message = OutboxMessage::new(user_id, &KycSubmitted { user_id }).unwrap();
+    Outbox::record(&ctx.pool, &message).await.unwrap();
+    sqlx::query(
+        "UPDATE outbox_events SET status = 'failed', attempt_count = $2 WHERE event_id = $1",
+    )
+    .bind(&message.event.id)
+    .bind(MAX_RELAY_ATTEMPTS)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let event_ids = std::slice::from_ref(&message.event.id);
+    assert_eq!(
+        Outbox::requeue_failed(&ctx.pool, Some(event_ids))
+            .await
+            .unwrap(),
+        1
+    );
+    // Requeued with a fresh budget, so there is nothing left to requeue.
+    // (Another test's relay may already have picked the event up.)
+    let (status, attempts) = outbox_status(&ctx.pool, &message.event.id).await.unwrap();
+    assert_ne!(status, "failed");
+    assert!(attempts <= 1);
+    assert_eq!(
+        Outbox::requeue_failed(&ctx.pool, Some(event_ids))
+            .await
+            .unwrap(),
+        0
+    );
+}
@@ -0,0 +1,270 @@
+mod helpers;
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use inheritx_backend::plan_freeze::{
+    ChainFreezeStatus, OnChainPlanFreezer, PlanFreezeRetryWorker, MAX_FREEZE_ATTEMPTS,
+};
+use inheritx_backend::service::{EmergencyAdminService, PausePlanRequest, UnpausePlanRequest};
+use inheritx_backend::ApiError;
+use uuid::Uuid;
+
+/// Records the calls it receives, optionally failing every one of them.
+#[derive(Default)]
+struct RecordingFreezer {
+    failing: bool,
+    calls: Mutex<Vec<String>>,
+}
+
+impl RecordingFreezer {
+    fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn record(&self, call: String) -> Result<(), ApiError> {
+        self.calls.lock().unwrap().push(call);
+        if self.failing {
+            return Err(ApiError::ExternalService("RPC unavailable".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OnChainPlanFreezer for RecordingFreezer {
+    async fn freeze_plan(
+        &self,
+        contract_plan_id: u64,
+        reason: &str,
+        expires_at: Option<u64>,
+    ) -> Result<(), ApiError> {
+        self.record(format!("freeze {contract_plan_id} {reason} {expires_at:?}"))
+    }
+
+    async fn unfreeze_plan(&self, contract_plan_id: u64) -> Result<(), ApiError> {
+        self.record(format!("unfreeze {contract_plan_id}"))
+    }
+}
+
+async fn insert_plan(pool: &sqlx::PgPool, user_id: Uuid, contract_plan_id: Option<i64>) -> Uuid {
+    let plan_id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO plans (
+            id, user_id, title, description, fee, net_amount, status,
+            beneficiary_name, bank_account_number, bank_name, currency_preference,
+            distribution_method, contract_plan_id, is_active
+        )
+        VALUES ($1, $2, 'Freeze Test Plan', 'Plan for freeze tests', '10.00', '490.00', 'pending',
+                'Test Beneficiary', '1234567890', 'Test Bank', 'USDC', 'LumpSum', $3, true)
+        "#,
+    )
+    .bind(plan_id)
+    .bind(user_id)
+    .bind(contract_plan_id)
+    .execute(pool)
+    .await
+    .expect("Failed to insert plan");
+    plan_id
+}
+
+async fn is_paused(pool: &sqlx::PgPool, plan_id: Uuid) -> bool {
+    sqlx::query_scalar::<_, Option<bool>>("SELECT is_paused FROM plans WHERE id = $1")
+        .bind(plan_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .unwrap_or(false)
+}
+
+async fn chain_freeze(pool: &sqlx::PgPool, plan_id: Uuid) -> (Option<String>, i32, bool) {
+    sqlx::query_as(
+        r#"
+        SELECT chain_freeze_status, chain_freeze_attempts,
+               chain_freeze_next_attempt_at > NOW()
+        FROM plans WHERE id = $1
+        "#,
+    )
+    .bind(plan_id)
+    .fetch_one(pool)
+    .await
+    .map(|(status, attempts, backed_off): (_, _, Option<bool>)| {
+        (status, attempts, backed_off.unwrap_or(false))
+    })
+    .unwrap()
+}
+
+/// Make the pending freeze due now instead of waiting out its back-off.
+async fn make_due(pool: &sqlx::PgPool, plan_id: Uuid) {
+    sqlx::query("UPDATE plans SET chain_freeze_next_attempt_at = NOW() WHERE id = $1")
+        .bind(plan_id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_pause_and_unpause_are_mirrored_on_chain() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let tag = Uuid::new_v4();
+    let user_id = helpers::create_test_user(&ctx.pool, &format!("freeze-{tag}@example.com"))
+        .await
+        .unwrap();
+    let admin_id =
+        helpers::create_test_admin(&ctx.pool, &format!("freeze-admin-{tag}@example.com"))
+            .await
+            .unwrap();
+    let plan_id = insert_plan(&ctx.pool, user_id, Some(41)).await;
+    let freezer = RecordingFreezer::default();
+
+    let paused = EmergencyAdminService::pause_plan(
+        &ctx.pool,
+        &freezer,
+        admin_id,
+        &PausePlanRequest {
+            plan_id,
+            reason: "Court order".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    assert!(is_paused(&ctx.pool, plan_id).await);
+    assert_eq!(paused.chain_freeze, Some(ChainFreezeStatus::Synced));
+
+    let unpaused = EmergencyAdminService::unpause_plan(
+        &ctx.pool,
+        &freezer,
+        admin_id,
+        &UnpausePlanRequest { plan_id },
+    )
+    .await
+    .unwrap();
+    assert!(!is_paused(&ctx.pool, plan_id).await);
+    assert_eq!(unpaused.chain_freeze, Some(ChainFreezeStatus::Synced));
+
+    assert_eq!(
+        freezer.calls(),
+        vec![
+            "freeze 41 Court order None".to_string(),
+            "unfreeze 41".to_string()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_failed_chain_freeze_is_kept_pending_and_retried() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let tag = Uuid::new_v4();
+    let user_id = helpers::create_test_user(&ctx.pool, &format!("freeze-{tag}@example.com"))
+        .await
+        .unwrap();
+    let admin_id =
+        helpers::create_test_admin(&ctx.pool, &format!("freeze-admin-{tag}@example.com"))
+            .await
+            .unwrap();
+
+    // The DB pause is authoritative: a failed on-chain freeze does not undo
+    // it, but is reported as pending and backed off.
+    let plan_id = insert_plan(&ctx.pool, user_id, Some(42)).await;
+    let failing = Arc::new(RecordingFreezer {
+        failing: true,
+        ..Default::default()
+    });
+    let paused = EmergencyAdminService::pause_plan(
+        &ctx.pool,
+        failing.as_ref(),
+        admin_id,
+        &PausePlanRequest {
+            plan_id,
+            reason: "Fraud review".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    assert!(is_paused(&ctx.pool, plan_id).await);
+    assert!(paused.is_pending_on_chain());
+    assert_eq!(
+        chain_freeze(&ctx.pool, plan_id).await,
+        (Some("pending_freeze".to_string()), 1, true)
+    );
+
+    // Not yet due, so the worker leaves it alone.
+    let worker = PlanFreezeRetryWorker::new(ctx.pool.clone(), failing.clone());
+    worker.retry_due().await.unwrap();
+    assert_eq!(failing.calls().len(), 1);
+
+    // Each due retry is submitted until the budget runs out.
+    for attempt in 2..=MAX_FREEZE_ATTEMPTS {
+        make_due(&ctx.pool, plan_id).await;
+        worker.retry_due().await.unwrap();
+        assert_eq!(chain_freeze(&ctx.pool, plan_id).await.1, attempt);
+    }
+    assert_eq!(
+        chain_freeze(&ctx.pool, plan_id).await,
+        (Some("failed".to_string()), MAX_FREEZE_ATTEMPTS, false)
+    );
+    assert!(is_paused(&ctx.pool, plan_id).await);
+
+    // Unpausing queues the unfreeze afresh, and a recovered RPC lands it.
+    let unpaused = EmergencyAdminService::unpause_plan(
+        &ctx.pool,
+        failing.as_ref(),
+        admin_id,
+        &UnpausePlanRequest { plan_id },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        unpaused.chain_freeze,
+        Some(ChainFreezeStatus::PendingUnfreeze)
+    );
+    let recovered = Arc::new(RecordingFreezer::default());
+    make_due(&ctx.pool, plan_id).await;
+    PlanFreezeRetryWorker::new(ctx.pool.clone(), recovered.clone())
+        .retry_due()
+        .await
+        .unwrap();
+    assert_eq!(recovered.calls(), vec!["unfreeze 42".to_string()]);
+    assert_eq!(
+        chain_freeze(&ctx.pool, plan_id).await,
+        (Some("synced".to_string()), 2, false)
+    );
+}
+
+#[tokio::test]
+async fn test_off_chain_plan_has_nothing_to_freeze() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let tag = Uuid::new_v4();
+    let user_id = helpers::create_test_user(&ctx.pool, &format!("freeze-{tag}@example.com"))
+        .await
+        .unwrap();
+    let admin_id =
+        helpers::create_test_admin(&ctx.pool, &format!("freeze-admin-{tag}@example.com"))
+            .await
+            .unwrap();
+
+    // Plans that were never created on chain have nothing to freeze there.
+    let plan_id = insert_plan(&ctx.pool, user_id, None).await;
+    let freezer = RecordingFreezer::default();
+    let paused = EmergencyAdminService::pause_plan(
+        &ctx.pool,
+        &freezer,
+        admin_id,
+        &PausePlanRequest {
+            plan_id,
+            reason: "Fraud review".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    assert!(is_paused(&ctx.pool, plan_id).await);
+    assert_eq!(paused.chain_freeze, None);
+    assert!(freezer.calls().is_empty());
+}
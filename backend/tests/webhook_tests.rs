@@ -0,0 +1,152 @@
+mod helpers;
+
+use inheritx_backend::webhook::{
+    CreateWebhookRequest, WebhookDelivery, DISABLE_AFTER_CONSECUTIVE_FAILURES,
+    MAX_DELIVERY_ATTEMPTS,
+};
+use inheritx_backend::{event_types, WebhookService};
+use uuid::Uuid;
+
+/// Nothing listens on the discard port, so every attempt fails fast.
+const UNREACHABLE_URL: &str = "http://127.0.0.1:9/webhook";
+
+async fn delivery(pool: &sqlx::PgPool, delivery_id: Uuid) -> WebhookDelivery {
+    sqlx::query_as("SELECT * FROM webhook_deliveries WHERE id = $1")
+        .bind(delivery_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+/// Make the delivery due now instead of waiting out its back-off.
+async fn make_due(pool: &sqlx::PgPool, delivery_id: Uuid) {
+    sqlx::query("UPDATE webhook_deliveries SET next_attempt_at = NOW() WHERE id = $1")
+        .bind(delivery_id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_failing_webhook_is_retried_dead_lettered_and_disabled() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id = helpers::create_test_user(
+        &ctx.pool,
+        &format!("webhook-{}@example.com", Uuid::new_v4()),
+    )
+    .await
+    .expect("Failed to create user");
+    let service = WebhookService::new(ctx.pool.clone()).with_private_targets(true);
+    let webhook = service
+        .register_webhook(
+            user_id,
+            CreateWebhookRequest {
+                url: UNREACHABLE_URL.to_string(),
+                events: vec![event_types::KYC_SUBMITTED.to_string()],
+            },
+        )
+        .await
+        .unwrap();
+
+    // Enqueue and attempt once: the failure schedules a backed-off retry.
+    let first = service.send_test_event(user_id, webhook.id).await.unwrap();
+    assert_eq!(first.status, "retrying");
+    assert_eq!(first.attempt_count, 1);
+    assert!(first.next_attempt_at.unwrap() > chrono::Utc::now());
+
+    // Not yet due, so the worker leaves it alone.
+    service.retry_failed_deliveries().await.unwrap();
+    assert_eq!(delivery(&ctx.pool, first.id).await.attempt_count, 1);
+
+    // Each due retry is attempted until the budget runs out.
+    for attempt in 2..=MAX_DELIVERY_ATTEMPTS {
+        make_due(&ctx.pool, first.id).await;
+        service.retry_failed_deliveries().await.unwrap();
+        assert_eq!(delivery(&ctx.pool, first.id).await.attempt_count, attempt);
+    }
+    let dead = delivery(&ctx.pool, first.id).await;
+    assert_eq!(dead.status, "dead_lettered");
+    assert_eq!(dead.next_attempt_at, None);
+    assert!(dead.last_error.is_some());
+    let detail = service
+        .get_delivery(user_id, webhook.id, first.id)
+        .await
+        .unwrap();
+    assert_eq!(detail.attempts.len(), MAX_DELIVERY_ATTEMPTS as usize);
+    let (dead_letters, total) = service.list_dead_letters(user_id, 10, 0).await.unwrap();
+    assert_eq!(total, 1);
+    assert_eq!(dead_letters[0].id, first.id);
+
+    // Further consecutive failures disable the webhook and dead-letter what
+    // it still owes.
+    let mut pending = Vec::new();
+    for _ in MAX_DELIVERY_ATTEMPTS..DISABLE_AFTER_CONSECUTIVE_FAILURES {
+        pending.push(
+            service
+                .send_test_event(user_id, webhook.id)
+                .await
+                .unwrap()
+                .id,
+        );
+    }
+    let disabled = service.get_webhooks(user_id).await.unwrap();
+    assert_eq!(disabled.len(), 1);
+    assert!(!disabled[0].is_active);
+    assert!(disabled[0].disabled_at.is_some());
+    assert_eq!(
+        disabled[0].failure_count,
+        DISABLE_AFTER_CONSECUTIVE_FAILURES
+    );
+    for delivery_id in pending {
+        assert_eq!(
+            delivery(&ctx.pool, delivery_id).await.status,
+            "dead_lettered"
+        );
+    }
+    assert!(service.send_test_event(user_id, webhook.id).await.is_err());
+
+    // Re-enabling resets the failure streak.
+    let enabled = service.enable_webhook(user_id, webhook.id).await.unwrap();
+    assert!(enabled.is_active);
+    assert_eq!(enabled.failure_count, 0);
+}
+
+#[tokio::test]
+async fn test_inline_attempt_is_not_picked_up_by_the_worker() {
+    let Some(ctx) = helpers::TestContext::from_env().await else {
+        return;
+    };
+    let user_id = helpers::create_test_user(
+        &ctx.pool,
+        &format!("webhook-{}@example.com", Uuid::new_v4()),
+    )
+    .await
+    .expect("Failed to create user");
+    let service = WebhookService::new(ctx.pool.clone()).with_private_targets(true);
+    let webhook = service
+        .register_webhook(
+            user_id,
+            CreateWebhookRequest {
+                url: UNREACHABLE_URL.to_string(),
+                events: vec![event_types::KYC_SUBMITTED.to_string()],
+            },
+        )
+        .await
+        .unwrap();
+
+    // The worker runs while the test event is being sent inline; the new
+    // delivery is leased from the start, so it is attempted exactly once.
+    let (sent, _) = tokio::join!(
+        service.send_test_event(user_id, webhook.id),
+        service.retry_failed_deliveries()
+    );
+    let sent = sent.unwrap();
+    assert_eq!(delivery(&ctx.pool, sent.id).await.attempt_count, 1);
+    let detail = service
+        .get_delivery(user_id, webhook.id, sent.id)
+        .await
+        .unwrap();
+    assert_eq!(detail.attempts.len(), 1);
+}
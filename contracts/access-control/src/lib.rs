@@ -1,8 +1,13 @@
 #![no_std]
 
-use soroban_sdk::{contracttype, Address, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, IntoVal, Val, Vec};
 
-/// The four roles recognised across all InheritX contracts.
+/// Roles recognised across all InheritX contracts.
+///
+/// `Admin`, `Guardian`, `Beneficiary` and `Owner` are the original roles;
+/// the rest are operational roles that let an admin delegate a single duty
+/// (pausing, price feeds, liquidations, dispute arbitration, KYC review)
+/// without handing out full admin rights.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Role {
@@ -10,40 +15,166 @@ pub enum Role {
     Guardian,
     Beneficiary,
     Owner,
+    Pauser,
+    Oracle,
+    Liquidator,
+    Arbitrator,
+    KycOfficer,
 }
 
-/// Per-address storage key for role lists.
+/// Storage keys for role grants and the per-role member indexes.
 #[contracttype]
 #[derive(Clone)]
 pub enum AccessControlKey {
-    Roles(Address),
+    Roles(Address),                // -> Vec<Role>
+    RoleExpiry(Address, Role),     // -> u64 (absent for grants that never expire)
+    RoleAdmin(Role),               // -> Role allowed to grant it (instance; default Admin)
+    MemberCount(Role),             // -> u32
+    Member(Role, u32),             // position -> Address
+    MemberPosition(Role, Address), // address -> u32 position
+}
+
+/// One holder of a role. `expires_at` is a unix timestamp, 0 for never.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleGrant {
+    pub account: Address,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleGrantedEvent {
+    pub role: Role,
+    pub account: Address,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleRevokedEvent {
+    pub role: Role,
+    pub account: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleAdminChangedEvent {
+    pub role: Role,
+    pub admin_role: Role,
 }
 
-/// Assign `role` to `address`.  Idempotent — does nothing if already assigned.
+/// Upper bound on members returned by one `role_members` page.
+pub const MAX_MEMBERS_PAGE: u32 = 50;
+
+fn stored_roles(env: &Env, address: &Address) -> Vec<Role> {
+    env.storage()
+        .persistent()
+        .get(&AccessControlKey::Roles(address.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn role_expiry(env: &Env, address: &Address, role: &Role) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&AccessControlKey::RoleExpiry(address.clone(), role.clone()))
+        .unwrap_or(0)
+}
+
+fn is_live(env: &Env, expires_at: u64) -> bool {
+    expires_at == 0 || env.ledger().timestamp() < expires_at
+}
+
+fn add_member(env: &Env, role: &Role, address: &Address) {
+    let position_key = AccessControlKey::MemberPosition(role.clone(), address.clone());
+    if env.storage().persistent().has(&position_key) {
+        return;
+    }
+    let count = role_member_count(env, role.clone());
+    let member_key = AccessControlKey::Member(role.clone(), count);
+    env.storage().persistent().set(&member_key, address);
+    extend_persistent_ttl(env, &member_key);
+    env.storage().persistent().set(&position_key, &count);
+    extend_persistent_ttl(env, &position_key);
+    let count_key = AccessControlKey::MemberCount(role.clone());
+    env.storage().persistent().set(&count_key, &(count + 1));
+    extend_persistent_ttl(env, &count_key);
+}
+
+/// Swap-remove `address` from the role's member index.
+fn remove_member(env: &Env, role: &Role, address: &Address) {
+    let position_key = AccessControlKey::MemberPosition(role.clone(), address.clone());
+    let Some(position) = env.storage().persistent().get::<_, u32>(&position_key) else {
+        return;
+    };
+    env.storage().persistent().remove(&position_key);
+
+    let last = role_member_count(env, role.clone()) - 1;
+    let last_key = AccessControlKey::Member(role.clone(), last);
+    if position != last {
+        let moved: Address = env.storage().persistent().get(&last_key).unwrap();
+        let member_key = AccessControlKey::Member(role.clone(), position);
+        env.storage().persistent().set(&member_key, &moved);
+        extend_persistent_ttl(env, &member_key);
+        let moved_key = AccessControlKey::MemberPosition(role.clone(), moved);
+        env.storage().persistent().set(&moved_key, &position);
+        extend_persistent_ttl(env, &moved_key);
+    }
+    env.storage().persistent().remove(&last_key);
+    let count_key = AccessControlKey::MemberCount(role.clone());
+    env.storage().persistent().set(&count_key, &last);
+    extend_persistent_ttl(env, &count_key);
+}
+
+/// Assign `role` to `address` permanently. Idempotent — does nothing if it
+/// is already held without an expiry.
 pub fn assign_role(env: &Env, address: &Address, role: Role) {
+    assign_role_until(env, address, role, 0);
+}
+
+/// Assign `role` to `address` until `expires_at` (unix seconds; 0 for
+/// never), replacing the expiry of an existing grant. Emits
+/// `("ROLE", "GRANTED")`.
+pub fn assign_role_until(env: &Env, address: &Address, role: Role, expires_at: u64) {
     let key = AccessControlKey::Roles(address.clone());
-    let mut roles: Vec<Role> = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .unwrap_or(Vec::new(env));
-    for existing in roles.iter() {
-        if existing == role {
-            return;
-        }
+    let mut roles = stored_roles(env, address);
+    let held = roles.contains(&role);
+    if held && role_expiry(env, address, &role) == expires_at {
+        return;
+    }
+    if !held {
+        roles.push_back(role.clone());
+        env.storage().persistent().set(&key, &roles);
+        extend_persistent_ttl(env, &key);
     }
-    roles.push_back(role);
-    env.storage().persistent().set(&key, &roles);
+
+    let expiry_key = AccessControlKey::RoleExpiry(address.clone(), role.clone());
+    if expires_at == 0 {
+        env.storage().persistent().remove(&expiry_key);
+    } else {
+        env.storage().persistent().set(&expiry_key, &expires_at);
+        extend_persistent_ttl(env, &expiry_key);
+    }
+    add_member(env, &role, address);
+
+    env.events().publish(
+        (symbol_short!("ROLE"), symbol_short!("GRANTED")),
+        RoleGrantedEvent {
+            role,
+            account: address.clone(),
+            expires_at,
+        },
+    );
 }
 
 /// Revoke `role` from `address`.  Idempotent — does nothing if not assigned.
+/// Emits `("ROLE", "REVOKED")`.
 pub fn revoke_role(env: &Env, address: &Address, role: Role) {
     let key = AccessControlKey::Roles(address.clone());
-    let roles: Vec<Role> = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .unwrap_or(Vec::new(env));
+    let roles = stored_roles(env, address);
+    if !roles.contains(&role) {
+        return;
+    }
     let mut updated = Vec::new(env);
     for existing in roles.iter() {
         if existing != role {
@@ -51,22 +182,45 @@ pub fn revoke_role(env: &Env, address: &Address, role: Role) {
         }
     }
     env.storage().persistent().set(&key, &updated);
+    extend_persistent_ttl(env, &key);
+    env.storage()
+        .persistent()
+        .remove(&AccessControlKey::RoleExpiry(address.clone(), role.clone()));
+    remove_member(env, &role, address);
+
+    env.events().publish(
+        (symbol_short!("ROLE"), symbol_short!("REVOKED")),
+        RoleRevokedEvent {
+            role,
+            account: address.clone(),
+        },
+    );
 }
 
-/// Return `true` if `address` currently holds `role`.
+/// Return `true` if `address` currently holds `role` and the grant has not
+/// expired.
 pub fn has_role(env: &Env, address: &Address, role: Role) -> bool {
     let key = AccessControlKey::Roles(address.clone());
-    let roles: Vec<Role> = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .unwrap_or(Vec::new(env));
-    for existing in roles.iter() {
-        if existing == role {
-            return true;
+    extend_persistent_ttl(env, &key);
+    stored_roles(env, address).contains(&role) && is_live(env, role_expiry(env, address, &role))
+}
+
+/// Return `true` if `address` currently holds any of `roles`.
+pub fn has_any_role(env: &Env, address: &Address, roles: &[Role]) -> bool {
+    roles
+        .iter()
+        .any(|role| has_role(env, address, role.clone()))
+}
+
+/// The unexpired roles held by `address`.
+pub fn roles_of(env: &Env, address: &Address) -> Vec<Role> {
+    let mut live = Vec::new(env);
+    for role in stored_roles(env, address).iter() {
+        if is_live(env, role_expiry(env, address, &role)) {
+            live.push_back(role);
         }
     }
-    false
+    live
 }
 
 /// Require that `address` holds `role`; panics with `contract_error` otherwise.
@@ -85,6 +239,199 @@ pub fn require_role<E: Into<soroban_sdk::Error> + Copy>(
     }
 }
 
+/// Require that `address` holds at least one of `roles`.
+pub fn require_any_role<E: Into<soroban_sdk::Error> + Copy>(
+    env: &Env,
+    address: &Address,
+    roles: &[Role],
+    contract_error: E,
+) -> Result<(), E> {
+    if has_any_role(env, address, roles) {
+        Ok(())
+    } else {
+        Err(contract_error)
+    }
+}
+
+// ─── Role Hierarchy ──────────────────────────────
+
+/// The role whose holders may grant and revoke `role`. Every role is
+/// managed by `Admin` unless reconfigured with `set_role_admin`.
+pub fn role_admin(env: &Env, role: Role) -> Role {
+    env.storage()
+        .instance()
+        .get(&AccessControlKey::RoleAdmin(role))
+        .unwrap_or(Role::Admin)
+}
+
+/// Let holders of `admin_role` grant and revoke `role`. Emits
+/// `("ROLE", "ADMIN")`. The Admin role itself is only ever handed over with
+/// the two-step admin transfer, so callers should refuse to reconfigure it.
+pub fn set_role_admin(env: &Env, role: Role, admin_role: Role) {
+    env.storage()
+        .instance()
+        .set(&AccessControlKey::RoleAdmin(role.clone()), &admin_role);
+    env.events().publish(
+        (symbol_short!("ROLE"), symbol_short!("ADMIN")),
+        RoleAdminChangedEvent { role, admin_role },
+    );
+}
+
+/// Require that `caller` may grant and revoke `role`. Admins may always
+/// manage every role.
+pub fn require_role_admin<E: Into<soroban_sdk::Error> + Copy>(
+    env: &Env,
+    caller: &Address,
+    role: Role,
+    contract_error: E,
+) -> Result<(), E> {
+    require_any_role(
+        env,
+        caller,
+        &[Role::Admin, role_admin(env, role)],
+        contract_error,
+    )
+}
+
+// ─── Role Enumeration ────────────────────────────
+
+/// Number of addresses holding `role`, expired grants included until they
+/// are revoked.
+pub fn role_member_count(env: &Env, role: Role) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&AccessControlKey::MemberCount(role))
+        .unwrap_or(0)
+}
+
+/// Up to `limit` (capped at `MAX_MEMBERS_PAGE`) holders of `role` from
+/// position `start`. Positions shift when members are revoked. Grants
+/// recorded before member indexes existed are not listed.
+pub fn role_members(env: &Env, role: Role, start: u32, limit: u32) -> Vec<RoleGrant> {
+    let end = start
+        .saturating_add(limit.min(MAX_MEMBERS_PAGE))
+        .min(role_member_count(env, role.clone()));
+    let mut page = Vec::new(env);
+    for position in start..end {
+        let account: Address = env
+            .storage()
+            .persistent()
+            .get(&AccessControlKey::Member(role.clone(), position))
+            .unwrap();
+        page.push_back(RoleGrant {
+            expires_at: role_expiry(env, &account, &role),
+            account,
+        });
+    }
+    page
+}
+
+/// Whether any holder of `role` has an unexpired grant. Stops at the first
+/// live member found.
+pub fn has_live_member(env: &Env, role: Role) -> bool {
+    (0..role_member_count(env, role.clone())).any(|position| {
+        let account: Address = env
+            .storage()
+            .persistent()
+            .get(&AccessControlKey::Member(role.clone(), position))
+            .unwrap();
+        is_live(env, role_expiry(env, &account, &role))
+    })
+}
+
+/// Whether `address` may act in a role that is open to everyone while the
+/// role has no live members (e.g. `Liquidator`): otherwise only members may.
+/// Expired grants do not close the role.
+pub fn is_open_or_has_role(env: &Env, address: &Address, role: Role) -> bool {
+    has_role(env, address, role.clone()) || !has_live_member(env, role)
+}
+
+// ─── Two-Step Admin Transfer ─────────────────────
+
+/// An admin handover waiting for the new admin to accept it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingAdminTransfer {
+    pub current_admin: Address,
+    pub new_admin: Address,
+    pub proposed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferEvent {
+    pub current_admin: Address,
+    pub new_admin: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum AdminTransferKey {
+    Pending,
+}
+
+/// The admin transfer waiting for acceptance, if any.
+pub fn pending_admin_transfer(env: &Env) -> Option<PendingAdminTransfer> {
+    env.storage().instance().get(&AdminTransferKey::Pending)
+}
+
+/// Record `new_admin` as the proposed successor of `current_admin`,
+/// replacing any earlier proposal. Nothing changes until the new admin
+/// accepts, so a mistyped address can simply be re-proposed or cancelled.
+pub fn propose_admin_transfer(env: &Env, current_admin: &Address, new_admin: &Address) {
+    let pending = PendingAdminTransfer {
+        current_admin: current_admin.clone(),
+        new_admin: new_admin.clone(),
+        proposed_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .instance()
+        .set(&AdminTransferKey::Pending, &pending);
+    env.events().publish(
+        (symbol_short!("ADMIN"), symbol_short!("XFER_PROP")),
+        AdminTransferEvent {
+            current_admin: current_admin.clone(),
+            new_admin: new_admin.clone(),
+        },
+    );
+}
+
+/// Drop the pending admin transfer, returning it if there was one.
+pub fn cancel_admin_transfer(env: &Env) -> Option<PendingAdminTransfer> {
+    let pending = pending_admin_transfer(env)?;
+    env.storage().instance().remove(&AdminTransferKey::Pending);
+    env.events().publish(
+        (symbol_short!("ADMIN"), symbol_short!("XFER_CNCL")),
+        AdminTransferEvent {
+            current_admin: pending.current_admin.clone(),
+            new_admin: pending.new_admin.clone(),
+        },
+    );
+    Some(pending)
+}
+
+/// Complete the pending transfer to `new_admin`: the Admin role moves from
+/// the proposing admin to `new_admin`. Returns `None`, changing nothing, if
+/// no transfer to `new_admin` is pending or its proposer is no longer an
+/// admin. Callers authenticate `new_admin` and update any admin address they
+/// store themselves.
+pub fn accept_admin_transfer(env: &Env, new_admin: &Address) -> Option<PendingAdminTransfer> {
+    let pending = pending_admin_transfer(env)
+        .filter(|p| &p.new_admin == new_admin)
+        .filter(|p| has_role(env, &p.current_admin, Role::Admin))?;
+    env.storage().instance().remove(&AdminTransferKey::Pending);
+    revoke_role(env, &pending.current_admin, Role::Admin);
+    assign_role(env, new_admin, Role::Admin);
+    env.events().publish(
+        (symbol_short!("ADMIN"), symbol_short!("XFER_ACPT")),
+        AdminTransferEvent {
+            current_admin: pending.current_admin.clone(),
+            new_admin: new_admin.clone(),
+        },
+    );
+    Some(pending)
+}
+
 // ─── Reentrancy Guard ────────────────────────────
 
 #[contracttype]
@@ -167,3 +514,55 @@ pub fn require_not_paused_or_panic(env: &Env) {
         panic!("contract paused");
     }
 }
+
+// ─── Storage TTL Policy ──────────────────────────
+
+/// Ledgers closed per day at the ~5 second target close time.
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Instance storage is bumped to this TTL on every contract call.
+pub const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+/// Instance storage is only re-bumped once its TTL drops below this.
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Persistent entries are bumped to this TTL when written or read.
+pub const PERSISTENT_BUMP_AMOUNT: u32 = 120 * DAY_IN_LEDGERS;
+/// Persistent entries are only re-bumped once their TTL drops below this.
+pub const PERSISTENT_LIFETIME_THRESHOLD: u32 = 90 * DAY_IN_LEDGERS;
+
+/// Extend the contract instance (and its code) so it is not archived.
+/// Call this at the top of every public entry point.
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+/// Extend a persistent entry's TTL. Does nothing if the entry does not exist,
+/// so it is safe to call on optional keys.
+pub fn extend_persistent_ttl<K>(env: &Env, key: &K)
+where
+    K: IntoVal<Env, Val>,
+{
+    if env.storage().persistent().has(key) {
+        env.storage().persistent().extend_ttl(
+            key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+}
+
+/// Extend a persistent entry's TTL to the full [`PERSISTENT_BUMP_AMOUNT`]
+/// regardless of how much it has left. Does nothing if the entry does not
+/// exist.
+pub fn renew_persistent_ttl<K>(env: &Env, key: &K)
+where
+    K: IntoVal<Env, Val>,
+{
+    if env.storage().persistent().has(key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, PERSISTENT_BUMP_AMOUNT, PERSISTENT_BUMP_AMOUNT);
+    }
+}
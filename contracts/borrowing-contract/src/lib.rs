@@ -1,11 +1,14 @@
 #![no_std]
-use access_control::{self, Role};
+use access_control::{self, PendingAdminTransfer, Role, RoleGrant};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Vec,
 };
 
+mod oracle;
 mod test;
 
+pub use price_oracle::{Asset, OracleConfig, PriceData, PriceOracleClient, PriceOracleInterface};
+
 #[derive(Clone)]
 #[contracttype]
 pub struct Loan {
@@ -158,16 +161,6 @@ pub struct ContractInitializedEvent {
     pub timestamp: u64,
 }
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct RoleUpdatedEvent {
-    pub admin: Address,
-    pub account: Address,
-    pub role: Role,
-    pub granted: bool,
-    pub timestamp: u64,
-}
-
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CollateralWhitelistUpdatedEvent {
@@ -229,6 +222,11 @@ pub enum BorrowingError {
     ExtensionLimitReached = 14,
     ReentrantCall = 15,
     ContractPaused = 16,
+    PriceUnavailable = 17,
+    StalePrice = 18,
+    NoPendingAdminTransfer = 19,
+    InvalidAdminTransfer = 20,
+    ValuationOverflow = 21,
 }
 
 #[contract]
@@ -243,6 +241,7 @@ impl BorrowingContract {
         liquidation_threshold_bps: u32,
         liquidation_bonus_bps: u32,
     ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         admin.require_auth();
         if env.storage().instance().has(&DataKey::Admin) {
             return Err(BorrowingError::AlreadyInitialized);
@@ -276,66 +275,163 @@ impl BorrowingContract {
         access_control::require_role(env, admin, Role::Admin, BorrowingError::Unauthorized)
     }
 
-    /// Assign a role to an address. Admin-only.
+    /// Require that `caller` is an admin or holds the operational `role`.
+    fn require_admin_or(env: &Env, caller: &Address, role: Role) -> Result<(), BorrowingError> {
+        caller.require_auth();
+        access_control::require_any_role(
+            env,
+            caller,
+            &[Role::Admin, role],
+            BorrowingError::Unauthorized,
+        )
+    }
+
+    /// Assign a role to an address. Callable by admins and by holders of the
+    /// role's admin role (see `set_role_admin`). The Admin role itself is
+    /// handed over with `propose_admin_transfer` / `accept_admin_transfer`.
     pub fn assign_role(
         env: Env,
         admin: Address,
         address: Address,
         role: Role,
     ) -> Result<(), BorrowingError> {
-        Self::require_admin(&env, &admin)?;
-        access_control::assign_role(&env, &address, role.clone());
-        env.events().publish(
-            (symbol_short!("ADMIN"), symbol_short!("R_GRANT")),
-            RoleUpdatedEvent {
-                admin,
-                account: address,
-                role,
-                granted: true,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+        Self::assign_role_until(env, admin, address, role, 0)
+    }
+
+    /// Like `assign_role`, but the grant lapses at `expires_at` (unix
+    /// seconds; 0 for never). Re-assigning replaces the expiry.
+    pub fn assign_role_until(
+        env: Env,
+        admin: Address,
+        address: Address,
+        role: Role,
+        expires_at: u64,
+    ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role_admin(
+            &env,
+            &admin,
+            role.clone(),
+            BorrowingError::Unauthorized,
+        )?;
+        if role == Role::Admin {
+            return Err(BorrowingError::InvalidAdminTransfer);
+        }
+        access_control::assign_role_until(&env, &address, role, expires_at);
         Ok(())
     }
 
-    /// Revoke a role from an address. Admin-only.
+    /// Revoke a role from an address. Callable by admins and by holders of
+    /// the role's admin role. The Admin role only moves with the two-step
+    /// admin transfer, so it cannot be revoked here.
     pub fn revoke_role(
         env: Env,
         admin: Address,
         address: Address,
         role: Role,
     ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role_admin(
+            &env,
+            &admin,
+            role.clone(),
+            BorrowingError::Unauthorized,
+        )?;
+        if role == Role::Admin {
+            return Err(BorrowingError::InvalidAdminTransfer);
+        }
+        access_control::revoke_role(&env, &address, role);
+        Ok(())
+    }
+
+    /// Let holders of `admin_role` grant and revoke `role`. Admin-only; who
+    /// manages the Admin role cannot be changed.
+    pub fn set_role_admin(
+        env: Env,
+        admin: Address,
+        role: Role,
+        admin_role: Role,
+    ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
-        access_control::revoke_role(&env, &address, role.clone());
-        env.events().publish(
-            (symbol_short!("ADMIN"), symbol_short!("R_REVOKE")),
-            RoleUpdatedEvent {
-                admin,
-                account: address,
-                role,
-                granted: false,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+        if role == Role::Admin {
+            return Err(BorrowingError::InvalidAdminTransfer);
+        }
+        access_control::set_role_admin(&env, role, admin_role);
         Ok(())
     }
 
+    /// The role whose holders may grant and revoke `role`.
+    pub fn get_role_admin(env: Env, role: Role) -> Role {
+        access_control::extend_instance_ttl(&env);
+        access_control::role_admin(&env, role)
+    }
+
+    /// Up to `limit` holders of `role`, from position `start`.
+    pub fn get_role_members(env: Env, role: Role, start: u32, limit: u32) -> Vec<RoleGrant> {
+        access_control::extend_instance_ttl(&env);
+        access_control::role_members(&env, role, start, limit)
+    }
+
+    /// Propose `new_admin` as the contract's admin. Nothing changes until they
+    /// call `accept_admin_transfer`; proposing again replaces the proposal.
+    pub fn propose_admin_transfer(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin)?;
+        if new_admin == admin {
+            return Err(BorrowingError::InvalidAdminTransfer);
+        }
+        access_control::propose_admin_transfer(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Accept a pending admin transfer, taking over the Admin role from the
+    /// admin who proposed it.
+    pub fn accept_admin_transfer(env: Env, new_admin: Address) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
+        new_admin.require_auth();
+        access_control::accept_admin_transfer(&env, &new_admin)
+            .ok_or(BorrowingError::NoPendingAdminTransfer)?;
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        Ok(())
+    }
+
+    /// Cancel a pending admin transfer. Admin-only.
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin)?;
+        access_control::cancel_admin_transfer(&env)
+            .ok_or(BorrowingError::NoPendingAdminTransfer)?;
+        Ok(())
+    }
+
+    /// The admin transfer waiting for acceptance, if any.
+    pub fn get_pending_admin_transfer(env: Env) -> Option<PendingAdminTransfer> {
+        access_control::extend_instance_ttl(&env);
+        access_control::pending_admin_transfer(&env)
+    }
+
     /// Check whether an address holds a given role.
     pub fn has_role(env: Env, address: Address, role: Role) -> bool {
+        access_control::extend_instance_ttl(&env);
         access_control::has_role(&env, &address, role)
     }
 
     /// Return all roles held by an address.
     pub fn get_roles(env: Env, address: Address) -> Vec<Role> {
-        use access_control::AccessControlKey;
-        env.storage()
-            .persistent()
-            .get(&AccessControlKey::Roles(address))
-            .unwrap_or(Vec::new(&env))
+        access_control::extend_instance_ttl(&env);
+        access_control::roles_of(&env, &address)
     }
 
     pub fn pause(env: Env, admin: Address) -> Result<(), BorrowingError> {
-        Self::require_admin(&env, &admin)?;
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::Pauser)?;
         access_control::pause_contract(&env);
         env.events().publish(
             (symbol_short!("ADMIN"), symbol_short!("PAUSE")),
@@ -345,7 +441,8 @@ impl BorrowingContract {
     }
 
     pub fn unpause(env: Env, admin: Address) -> Result<(), BorrowingError> {
-        Self::require_admin(&env, &admin)?;
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::Pauser)?;
         access_control::unpause_contract(&env);
         env.events().publish(
             (symbol_short!("ADMIN"), symbol_short!("UNPAUSE")),
@@ -355,6 +452,7 @@ impl BorrowingContract {
     }
 
     pub fn is_paused(env: Env) -> bool {
+        access_control::extend_instance_ttl(&env);
         access_control::is_contract_paused(&env)
     }
 
@@ -371,6 +469,7 @@ impl BorrowingContract {
         collateral_token: Address,
         collateral_amount: i128,
     ) -> Result<u64, BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         borrower.require_auth();
         access_control::reentrancy_enter(&env, BorrowingError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
@@ -411,7 +510,9 @@ impl BorrowingContract {
             .and_then(|v| v.checked_div(10000))
             .unwrap_or(0) as i128;
 
-        if collateral_amount < required_collateral {
+        // Principal is denominated in the quote currency; price the collateral in it
+        let collateral_value = oracle::quote_value(&env, &collateral_token, collateral_amount)?;
+        if collateral_value < required_collateral {
             return Err(BorrowingError::InsufficientCollateral);
         }
 
@@ -440,6 +541,7 @@ impl BorrowingContract {
         env.storage()
             .persistent()
             .set(&DataKey::Loan(loan_id), &loan);
+        access_control::extend_persistent_ttl(&env, &DataKey::Loan(loan_id));
 
         // Emit borrow event
         env.events().publish(
@@ -461,6 +563,7 @@ impl BorrowingContract {
     }
 
     pub fn repay_loan(env: Env, loan_id: u64, amount: i128) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_not_paused(&env)?;
         access_control::reentrancy_enter(&env, BorrowingError::ReentrantCall)?;
         let mut loan: Loan = env
@@ -513,12 +616,14 @@ impl BorrowingContract {
         env.storage()
             .persistent()
             .set(&DataKey::Loan(loan_id), &loan);
+        access_control::extend_persistent_ttl(&env, &DataKey::Loan(loan_id));
 
         access_control::reentrancy_exit(&env);
         Ok(())
     }
 
     pub fn get_loan(env: Env, loan_id: u64) -> Loan {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .persistent()
             .get(&DataKey::Loan(loan_id))
@@ -530,10 +635,12 @@ impl BorrowingContract {
         admin: Address,
         token: Address,
     ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
         env.storage()
             .persistent()
             .set(&DataKey::WhitelistedCollateral(token.clone()), &true);
+        access_control::extend_persistent_ttl(&env, &DataKey::WhitelistedCollateral(token.clone()));
         env.events().publish(
             (symbol_short!("ADMIN"), symbol_short!("WHITELIST")),
             CollateralWhitelistUpdatedEvent {
@@ -547,6 +654,7 @@ impl BorrowingContract {
     }
 
     pub fn is_whitelisted(env: Env, token: Address) -> bool {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .persistent()
             .get(&DataKey::WhitelistedCollateral(token))
@@ -554,6 +662,7 @@ impl BorrowingContract {
     }
 
     pub fn set_global_pause(env: Env, admin: Address, paused: bool) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
         env.storage().instance().set(&DataKey::GlobalPause, &paused);
         env.events().publish(
@@ -568,6 +677,7 @@ impl BorrowingContract {
     }
 
     pub fn is_global_paused(env: Env) -> bool {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::GlobalPause)
@@ -580,10 +690,12 @@ impl BorrowingContract {
         token: Address,
         paused: bool,
     ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
         env.storage()
             .persistent()
             .set(&DataKey::VaultPause(token.clone()), &paused);
+        access_control::extend_persistent_ttl(&env, &DataKey::VaultPause(token.clone()));
         env.events().publish(
             (symbol_short!("ADMIN"), symbol_short!("VPAUSE")),
             VaultPauseUpdatedEvent {
@@ -597,6 +709,7 @@ impl BorrowingContract {
     }
 
     pub fn is_vault_paused(env: Env, token: Address) -> bool {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .persistent()
             .get(&DataKey::VaultPause(token))
@@ -604,19 +717,101 @@ impl BorrowingContract {
     }
 
     pub fn get_collateral_ratio(env: Env) -> u32 {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::CollateralRatio)
             .unwrap_or(15000)
     }
 
+    /// Price `token` collateral with a SEP-40 oracle. Admin or oracle only.
+    /// Prices older than `max_age_secs` are rejected as stale.
+    pub fn set_price_oracle(
+        env: Env,
+        admin: Address,
+        token: Address,
+        oracle: Address,
+        max_age_secs: u64,
+    ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::Oracle)?;
+        if max_age_secs == 0 {
+            return Err(BorrowingError::InvalidAmount);
+        }
+        price_oracle::set_config(
+            &env,
+            &admin,
+            &token,
+            &OracleConfig {
+                oracle,
+                max_age_secs,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stop pricing `token` with an oracle. Unless it is the quote asset,
+    /// loans against it can no longer be valued. Admin or oracle only.
+    pub fn remove_price_oracle(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::Oracle)?;
+        price_oracle::remove_config(&env, &admin, &token);
+        Ok(())
+    }
+
+    pub fn get_price_oracle(env: Env, token: Address) -> Option<OracleConfig> {
+        access_control::extend_instance_ttl(&env);
+        price_oracle::get_config(&env, &token)
+    }
+
+    /// Denominate loan principal in `token`: it is valued one-for-one and
+    /// needs no oracle. Every other collateral token must have one. Until it
+    /// is set no collateral without an oracle can be priced, so contracts
+    /// deployed before quote assets existed must call this. Admin only.
+    pub fn set_quote_asset(env: Env, admin: Address, token: Address) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin)?;
+        price_oracle::set_quote_asset(&env, &token);
+        Ok(())
+    }
+
+    pub fn get_quote_asset(env: Env) -> Option<Address> {
+        access_control::extend_instance_ttl(&env);
+        price_oracle::get_quote_asset(&env)
+    }
+
+    /// Collateral value over outstanding debt in basis points, with the
+    /// collateral priced in the quote currency.
+    fn health_factor(env: &Env, loan: &Loan, debt: i128) -> Result<u32, BorrowingError> {
+        if debt == 0 {
+            return Ok(10000);
+        }
+        let collateral_value =
+            oracle::quote_value(env, &loan.collateral_token, loan.collateral_amount)?;
+        Ok((collateral_value as u128)
+            .checked_mul(10000)
+            .and_then(|v| v.checked_div(debt as u128))
+            .unwrap_or(0) as u32)
+    }
+
+    /// Repay part of an unhealthy loan's debt in exchange for collateral.
+    /// Open to anyone until the first `Liquidator` is assigned; then only
+    /// liquidators may call it.
     pub fn liquidate(
         env: Env,
         liquidator: Address,
         loan_id: u64,
         liquidate_amount: i128,
     ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         liquidator.require_auth();
+        if !access_control::is_open_or_has_role(&env, &liquidator, Role::Liquidator) {
+            return Err(BorrowingError::Unauthorized);
+        }
         access_control::reentrancy_enter(&env, BorrowingError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
 
@@ -637,14 +832,7 @@ impl BorrowingContract {
         }
 
         // Calculate health factor inline – avoids a redundant storage read from get_health_factor
-        let health_factor = if debt == 0 {
-            10000
-        } else {
-            (loan.collateral_amount as u128)
-                .checked_mul(10000)
-                .and_then(|v| v.checked_div(debt as u128))
-                .unwrap_or(0) as u32
-        };
+        let health_factor = Self::health_factor(&env, &loan, debt)?;
 
         // Cache both threshold and bonus in a single pass over instance storage
         let liquidation_threshold: u32 = env
@@ -668,7 +856,11 @@ impl BorrowingContract {
             .checked_mul(liquidation_bonus as u128)
             .and_then(|v| v.checked_div(10000))
             .unwrap_or(0) as i128;
-        let liquidator_reward = liquidate_amount + bonus_amount;
+        let liquidator_reward = oracle::token_amount_for(
+            &env,
+            &loan.collateral_token,
+            liquidate_amount + bonus_amount,
+        )?;
 
         if liquidator_reward > loan.collateral_amount {
             return Err(BorrowingError::InvalidAmount);
@@ -704,6 +896,7 @@ impl BorrowingContract {
         env.storage()
             .persistent()
             .set(&DataKey::Loan(loan_id), &loan);
+        access_control::extend_persistent_ttl(&env, &DataKey::Loan(loan_id));
 
         // Emit liquidation event
         env.events().publish(
@@ -724,6 +917,7 @@ impl BorrowingContract {
     }
 
     pub fn get_health_factor(env: Env, loan_id: u64) -> Result<u32, BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         let loan: Loan = env
             .storage()
             .persistent()
@@ -731,14 +925,7 @@ impl BorrowingContract {
             .ok_or(BorrowingError::LoanNotFound)?;
 
         let debt = loan.principal - loan.amount_repaid;
-        let health_factor = if debt == 0 {
-            10000
-        } else {
-            (loan.collateral_amount as u128)
-                .checked_mul(10000)
-                .and_then(|v| v.checked_div(debt as u128))
-                .unwrap_or(0) as u32
-        };
+        let health_factor = Self::health_factor(&env, &loan, debt)?;
 
         Ok(health_factor)
     }
@@ -750,6 +937,7 @@ impl BorrowingContract {
         initial_discount_bps: u32,
         max_discount_bps: u32,
     ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         // Single storage read for the loan – reuse it instead of calling get_loan + get_health_factor
         let loan: Loan = env
             .storage()
@@ -763,14 +951,7 @@ impl BorrowingContract {
         }
 
         // Compute health factor inline (avoids second storage read via get_health_factor)
-        let health_factor = if debt == 0 {
-            10000u32
-        } else {
-            (loan.collateral_amount as u128)
-                .checked_mul(10000)
-                .and_then(|v| v.checked_div(debt as u128))
-                .unwrap_or(0) as u32
-        };
+        let health_factor = Self::health_factor(&env, &loan, debt)?;
 
         let liquidation_threshold: u32 = env
             .storage()
@@ -806,6 +987,7 @@ impl BorrowingContract {
         env.storage()
             .persistent()
             .set(&DataKey::Auction(loan_id), &auction);
+        access_control::extend_persistent_ttl(&env, &DataKey::Auction(loan_id));
 
         env.events().publish(
             (symbol_short!("AUCTION"), symbol_short!("START")),
@@ -820,6 +1002,7 @@ impl BorrowingContract {
     }
 
     pub fn get_liquidation_discount(env: Env, loan_id: u64) -> Result<u32, BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         let auction: LiquidationAuction = env
             .storage()
             .persistent()
@@ -855,6 +1038,7 @@ impl BorrowingContract {
         loan_id: u64,
         bid_amount: i128,
     ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         bidder.require_auth();
         access_control::reentrancy_enter(&env, BorrowingError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
@@ -910,6 +1094,7 @@ impl BorrowingContract {
         env.storage()
             .persistent()
             .set(&DataKey::Auction(loan_id), &auction);
+        access_control::extend_persistent_ttl(&env, &DataKey::Auction(loan_id));
 
         env.events().publish(
             (symbol_short!("AUCTION"), symbol_short!("BID")),
@@ -926,6 +1111,7 @@ impl BorrowingContract {
     }
 
     pub fn execute_auction(env: Env, loan_id: u64) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         access_control::reentrancy_enter(&env, BorrowingError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
 
@@ -957,7 +1143,8 @@ impl BorrowingContract {
             .and_then(|v| v.checked_div(10000))
             .unwrap_or(0) as i128;
 
-        let liquidator_reward = bid_amount + bonus_amount;
+        let liquidator_reward =
+            oracle::token_amount_for(&env, &loan.collateral_token, bid_amount + bonus_amount)?;
 
         if liquidator_reward > loan.collateral_amount {
             return Err(BorrowingError::InvalidAmount);
@@ -978,9 +1165,11 @@ impl BorrowingContract {
         env.storage()
             .persistent()
             .set(&DataKey::Loan(loan_id), &loan);
+        access_control::extend_persistent_ttl(&env, &DataKey::Loan(loan_id));
         env.storage()
             .persistent()
             .set(&DataKey::Auction(loan_id), &auction);
+        access_control::extend_persistent_ttl(&env, &DataKey::Auction(loan_id));
 
         env.events().publish(
             (symbol_short!("AUCTION"), symbol_short!("EXECUTE")),
@@ -1000,6 +1189,7 @@ impl BorrowingContract {
         env: Env,
         loan_id: u64,
     ) -> Result<LiquidationAuction, BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .persistent()
             .get(&DataKey::Auction(loan_id))
@@ -1007,6 +1197,7 @@ impl BorrowingContract {
     }
 
     pub fn cancel_auction(env: Env, loan_id: u64) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         let mut auction: LiquidationAuction = env
             .storage()
             .persistent()
@@ -1024,14 +1215,7 @@ impl BorrowingContract {
             .get(&DataKey::Loan(loan_id))
             .ok_or(BorrowingError::LoanNotFound)?;
         let debt = loan.principal - loan.amount_repaid;
-        let health_factor = if debt == 0 {
-            10000u32
-        } else {
-            (loan.collateral_amount as u128)
-                .checked_mul(10000)
-                .and_then(|v| v.checked_div(debt as u128))
-                .unwrap_or(0) as u32
-        };
+        let health_factor = Self::health_factor(&env, &loan, debt)?;
         let liquidation_threshold: u32 = env
             .storage()
             .instance()
@@ -1046,6 +1230,7 @@ impl BorrowingContract {
         env.storage()
             .persistent()
             .set(&DataKey::Auction(loan_id), &auction);
+        access_control::extend_persistent_ttl(&env, &DataKey::Auction(loan_id));
 
         env.events().publish(
             (symbol_short!("AUCTION"), symbol_short!("CANCEL")),
@@ -1057,6 +1242,7 @@ impl BorrowingContract {
 
     /// Returns the extension fee for a loan (1% of remaining principal by default).
     pub fn get_extension_fee(env: Env, loan_id: u64) -> Result<i128, BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         let loan: Loan = env
             .storage()
             .persistent()
@@ -1084,6 +1270,7 @@ impl BorrowingContract {
 
     /// Returns the maximum additional amount a borrower can take against existing collateral.
     pub fn get_max_additional_borrow(env: Env, loan_id: u64) -> Result<i128, BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         let loan: Loan = env
             .storage()
             .persistent()
@@ -1095,8 +1282,10 @@ impl BorrowingContract {
         }
 
         let ratio = Self::get_collateral_ratio(env.clone());
-        // max_borrow = collateral * 10000 / ratio
-        let max_borrow = (loan.collateral_amount as u128)
+        // max_borrow = collateral value * 10000 / ratio
+        let collateral_value =
+            oracle::quote_value(&env, &loan.collateral_token, loan.collateral_amount)?;
+        let max_borrow = (collateral_value as u128)
             .checked_mul(10000)
             .and_then(|v| v.checked_div(ratio as u128))
             .unwrap_or(0) as i128;
@@ -1114,6 +1303,7 @@ impl BorrowingContract {
         loan_id: u64,
         extension_seconds: u64,
     ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         access_control::reentrancy_enter(&env, BorrowingError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
 
@@ -1166,6 +1356,7 @@ impl BorrowingContract {
         env.storage()
             .persistent()
             .set(&DataKey::Loan(loan_id), &loan);
+        access_control::extend_persistent_ttl(&env, &DataKey::Loan(loan_id));
 
         env.events().publish(
             (symbol_short!("LOAN"), symbol_short!("EXTEND")),
@@ -1189,6 +1380,7 @@ impl BorrowingContract {
         loan_id: u64,
         additional_amount: i128,
     ) -> Result<(), BorrowingError> {
+        access_control::extend_instance_ttl(&env);
         access_control::reentrancy_enter(&env, BorrowingError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
 
@@ -1214,7 +1406,9 @@ impl BorrowingContract {
             .instance()
             .get(&DataKey::CollateralRatio)
             .unwrap_or(15000);
-        let max_borrow = (loan.collateral_amount as u128)
+        let collateral_value =
+            oracle::quote_value(&env, &loan.collateral_token, loan.collateral_amount)?;
+        let max_borrow = (collateral_value as u128)
             .checked_mul(10000)
             .and_then(|v| v.checked_div(ratio as u128))
             .unwrap_or(0) as i128;
@@ -1232,6 +1426,7 @@ impl BorrowingContract {
         env.storage()
             .persistent()
             .set(&DataKey::Loan(loan_id), &loan);
+        access_control::extend_persistent_ttl(&env, &DataKey::Loan(loan_id));
 
         env.events().publish(
             (symbol_short!("LOAN"), symbol_short!("INCREASE")),
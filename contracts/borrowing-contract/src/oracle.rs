@@ -0,0 +1,37 @@
+//! Wrappers around the shared `price-oracle` crate for the `i128` amounts
+//! used by loans, reporting failures as `BorrowingError`.
+
+use crate::BorrowingError;
+use price_oracle::OracleError;
+use soroban_sdk::{Address, Env};
+
+impl From<OracleError> for BorrowingError {
+    fn from(err: OracleError) -> Self {
+        match err {
+            OracleError::PriceUnavailable => BorrowingError::PriceUnavailable,
+            OracleError::StalePrice => BorrowingError::StalePrice,
+            OracleError::Overflow => BorrowingError::ValuationOverflow,
+        }
+    }
+}
+
+fn to_units(amount: i128) -> u128 {
+    amount.max(0) as u128
+}
+
+fn from_units(value: u128) -> Result<i128, BorrowingError> {
+    i128::try_from(value).map_err(|_| BorrowingError::ValuationOverflow)
+}
+
+/// Value `amount` of collateral `token` in the quote currency.
+pub fn quote_value(env: &Env, token: &Address, amount: i128) -> Result<i128, BorrowingError> {
+    let value = price_oracle::quote_value(env, token, to_units(amount))?;
+    from_units(value)
+}
+
+/// Amount of collateral `token` worth `value` in the quote currency, rounded
+/// down.
+pub fn token_amount_for(env: &Env, token: &Address, value: i128) -> Result<i128, BorrowingError> {
+    let amount = price_oracle::token_amount_for(env, token, to_units(value))?;
+    from_units(amount)
+}
@@ -1,7 +1,11 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, token, Address, Env};
+use mock_oracle::{MockOracle, MockOracleClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
 
 fn create_token_addr(env: &Env) -> Address {
     let admin = Address::generate(env);
@@ -19,6 +23,8 @@ fn setup(env: &Env) -> (BorrowingContractClient<'_>, Address, Address) {
     let client = BorrowingContractClient::new(env, &contract_id);
     client.initialize(&admin, &15000, &12000, &500);
     client.whitelist_collateral(&admin, &collateral_addr);
+    // Principal is denominated in the collateral token, so no oracle is needed
+    client.set_quote_asset(&admin, &collateral_addr);
     (client, collateral_addr, admin)
 }
 
@@ -81,6 +87,7 @@ fn test_liquidation() {
     let client = BorrowingContractClient::new(&env, &contract_id);
     client.initialize(&admin, &12000, &13000, &500);
     client.whitelist_collateral(&admin, &collateral_addr);
+    client.set_quote_asset(&admin, &collateral_addr);
     let borrower = Address::generate(&env);
     let liquidator = Address::generate(&env);
     sac_client(&env, &collateral_addr).mint(&borrower, &1200);
@@ -100,6 +107,7 @@ fn test_partial_liquidation() {
     let client = BorrowingContractClient::new(&env, &contract_id);
     client.initialize(&admin, &12000, &13000, &500);
     client.whitelist_collateral(&admin, &collateral_addr);
+    client.set_quote_asset(&admin, &collateral_addr);
     let borrower = Address::generate(&env);
     let liquidator = Address::generate(&env);
     sac_client(&env, &collateral_addr).mint(&borrower, &1200);
@@ -329,6 +337,7 @@ fn test_liquidation_auction() {
     let client = BorrowingContractClient::new(&env, &contract_id);
     client.initialize(&admin, &12000, &13000, &500);
     client.whitelist_collateral(&admin, &collateral_addr);
+    client.set_quote_asset(&admin, &collateral_addr);
 
     let borrower = Address::generate(&env);
     let liquidator = Address::generate(&env);
@@ -384,6 +393,13 @@ fn test_admin_can_assign_and_revoke_roles() {
 
     client.revoke_role(&admin, &user, &access_control::Role::Owner);
     assert!(!client.has_role(&user, &access_control::Role::Owner));
+
+    // Admin only moves with the two-step transfer, never by revocation.
+    assert_eq!(
+        client.try_revoke_role(&admin, &admin, &access_control::Role::Admin),
+        Err(Ok(BorrowingError::InvalidAdminTransfer))
+    );
+    assert!(client.has_role(&admin, &access_control::Role::Admin));
 }
 
 #[test]
@@ -398,6 +414,121 @@ fn test_non_admin_cannot_assign_roles() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_admin_transfer_requires_acceptance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _collateral_addr, admin) = setup(&env);
+    let new_admin = Address::generate(&env);
+
+    assert_eq!(
+        client.try_assign_role(&admin, &new_admin, &access_control::Role::Admin),
+        Err(Ok(BorrowingError::InvalidAdminTransfer))
+    );
+
+    client.propose_admin_transfer(&admin, &new_admin);
+    assert_eq!(
+        client.get_pending_admin_transfer().unwrap().new_admin,
+        new_admin
+    );
+    assert!(!client.has_role(&new_admin, &access_control::Role::Admin));
+    assert_eq!(
+        client.try_accept_admin_transfer(&Address::generate(&env)),
+        Err(Ok(BorrowingError::NoPendingAdminTransfer))
+    );
+
+    client.accept_admin_transfer(&new_admin);
+    assert!(client.has_role(&new_admin, &access_control::Role::Admin));
+    assert!(!client.has_role(&admin, &access_control::Role::Admin));
+    assert_eq!(client.get_pending_admin_transfer(), None);
+
+    client.propose_admin_transfer(&new_admin, &admin);
+    client.cancel_admin_transfer(&new_admin);
+    assert_eq!(
+        client.try_accept_admin_transfer(&admin),
+        Err(Ok(BorrowingError::NoPendingAdminTransfer))
+    );
+}
+
+#[test]
+fn test_pauser_role_can_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _collateral_addr, admin) = setup(&env);
+    let pauser = Address::generate(&env);
+
+    assert_eq!(
+        client.try_pause(&pauser),
+        Err(Ok(BorrowingError::Unauthorized))
+    );
+    client.assign_role(&admin, &pauser, &access_control::Role::Pauser);
+    client.pause(&pauser);
+    assert!(client.is_paused());
+    client.unpause(&pauser);
+    assert!(!client.is_paused());
+}
+
+#[test]
+fn test_liquidation_restricted_once_liquidators_granted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let collateral_addr = create_token_addr(&env);
+    let contract_id = env.register_contract(None, BorrowingContract);
+    let client = BorrowingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &12000, &13000, &500);
+    client.whitelist_collateral(&admin, &collateral_addr);
+    client.set_quote_asset(&admin, &collateral_addr);
+    let borrower = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &1200);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1200);
+
+    client.assign_role(&admin, &keeper, &access_control::Role::Liquidator);
+    assert_eq!(
+        client.try_liquidate(&outsider, &loan_id, &1000),
+        Err(Ok(BorrowingError::Unauthorized))
+    );
+    client.liquidate(&keeper, &loan_id, &1000);
+    assert!(!client.get_loan(&loan_id).is_active);
+}
+
+#[test]
+fn test_liquidation_reopens_once_liquidator_grants_expire() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let collateral_addr = create_token_addr(&env);
+    let contract_id = env.register_contract(None, BorrowingContract);
+    let client = BorrowingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &12000, &13000, &500);
+    client.whitelist_collateral(&admin, &collateral_addr);
+    client.set_quote_asset(&admin, &collateral_addr);
+    let borrower = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &1200);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1200);
+
+    let expires_at = env.ledger().timestamp() + 3600;
+    client.assign_role_until(
+        &admin,
+        &keeper,
+        &access_control::Role::Liquidator,
+        &expires_at,
+    );
+    assert_eq!(
+        client.try_liquidate(&outsider, &loan_id, &1000),
+        Err(Ok(BorrowingError::Unauthorized))
+    );
+
+    // With every grant expired the role is open again.
+    env.ledger().with_mut(|li| li.timestamp = expires_at);
+    client.liquidate(&outsider, &loan_id, &1000);
+    assert!(!client.get_loan(&loan_id).is_active);
+}
+
 #[test]
 fn test_non_admin_cannot_whitelist_collateral() {
     let env = Env::default();
@@ -470,3 +601,175 @@ fn test_is_paused_reflects_state_borrowing() {
     client.unpause(&admin);
     assert!(!client.is_paused());
 }
+
+// ─── Price oracle ────────────────────────────────
+
+fn setup_oracle(
+    env: &Env,
+    client: &BorrowingContractClient<'_>,
+    admin: &Address,
+    collateral_addr: &Address,
+) -> MockOracleClient<'static> {
+    let oracle_id = env.register_contract(None, MockOracle);
+    let oracle = MockOracleClient::new(env, &oracle_id);
+    client.set_price_oracle(admin, collateral_addr, &oracle_id, &3600);
+    oracle
+}
+
+fn set_collateral_price(env: &Env, oracle: &MockOracleClient<'_>, token: &Address, price: i128) {
+    // 7 decimals: 10_000_000 = 1.0 quote unit per collateral unit
+    oracle.set_price(
+        &mock_oracle::Asset::Stellar(token.clone()),
+        &price,
+        &env.ledger().timestamp(),
+    );
+}
+
+#[test]
+fn test_create_loan_uses_oracle_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, admin) = setup(&env);
+    let oracle = setup_oracle(&env, &client, &admin, &collateral_addr);
+    set_collateral_price(&env, &oracle, &collateral_addr, 5_000_000); // 0.5
+
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &3000);
+
+    // 1500 collateral is only worth 750, below the 150% requirement
+    let result = client.try_create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1500);
+    assert_eq!(result, Err(Ok(BorrowingError::InsufficientCollateral)));
+
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &3000);
+    assert_eq!(client.get_health_factor(&loan_id), 15000);
+    assert_eq!(client.get_max_additional_borrow(&loan_id), 0);
+}
+
+#[test]
+fn test_price_drop_enables_liquidation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, admin) = setup(&env);
+    let oracle = setup_oracle(&env, &client, &admin, &collateral_addr);
+    set_collateral_price(&env, &oracle, &collateral_addr, 20_000_000); // 2.0
+
+    let borrower = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &1000);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1000);
+    assert_eq!(client.get_health_factor(&loan_id), 20000);
+
+    let result = client.try_liquidate(&liquidator, &loan_id, &500);
+    assert_eq!(result, Err(Ok(BorrowingError::LoanHealthy)));
+
+    // At 1.0 the collateral covers only 100% of the debt
+    set_collateral_price(&env, &oracle, &collateral_addr, 10_000_000);
+    assert_eq!(client.get_health_factor(&loan_id), 10000);
+
+    // Repay 500 of debt plus a 5% bonus: 525 quote units = 525 collateral at 1.0
+    client.liquidate(&liquidator, &loan_id, &500);
+    let loan = client.get_loan(&loan_id);
+    assert_eq!(loan.collateral_amount, 475);
+    assert_eq!(loan.amount_repaid, 500);
+}
+
+#[test]
+fn test_stale_or_missing_price_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, admin) = setup(&env);
+    let oracle = setup_oracle(&env, &client, &admin, &collateral_addr);
+
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &3000);
+    let result = client.try_create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &3000);
+    assert_eq!(result, Err(Ok(BorrowingError::PriceUnavailable)));
+
+    set_collateral_price(&env, &oracle, &collateral_addr, 10_000_000);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &3000);
+
+    env.ledger().with_mut(|li| li.timestamp += 3601);
+    let result = client.try_get_health_factor(&loan_id);
+    assert_eq!(result, Err(Ok(BorrowingError::StalePrice)));
+
+    // Without an oracle, the quote asset is valued one-for-one again
+    client.remove_price_oracle(&admin, &collateral_addr);
+    assert!(client.get_price_oracle(&collateral_addr).is_none());
+    assert_eq!(client.get_health_factor(&loan_id), 30000);
+}
+
+#[test]
+fn test_collateral_without_oracle_is_not_priced_at_par() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, quote_addr, admin) = setup(&env);
+    let collateral_addr = create_token_addr(&env);
+    client.whitelist_collateral(&admin, &collateral_addr);
+    assert_eq!(client.get_quote_asset(), Some(quote_addr));
+
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &3000);
+    let result = client.try_create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &3000);
+    assert_eq!(result, Err(Ok(BorrowingError::PriceUnavailable)));
+
+    let oracle = setup_oracle(&env, &client, &admin, &collateral_addr);
+    set_collateral_price(&env, &oracle, &collateral_addr, 10_000_000);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &3000);
+
+    // Dropping the feed leaves the loan unpriceable rather than valued 1:1
+    client.remove_price_oracle(&admin, &collateral_addr);
+    let result = client.try_get_health_factor(&loan_id);
+    assert_eq!(result, Err(Ok(BorrowingError::PriceUnavailable)));
+}
+
+#[test]
+fn test_without_quote_asset_nothing_is_priced_at_par() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let collateral_addr = create_token_addr(&env);
+    let contract_id = env.register_contract(None, BorrowingContract);
+    let client = BorrowingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &15000, &12000, &500);
+    client.whitelist_collateral(&admin, &collateral_addr);
+    assert!(client.get_quote_asset().is_none());
+
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &1500);
+    let result = client.try_create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1500);
+    assert_eq!(result, Err(Ok(BorrowingError::PriceUnavailable)));
+
+    // Setting the quote asset is the migration path for older deployments.
+    client.set_quote_asset(&admin, &collateral_addr);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1500);
+    assert_eq!(client.get_health_factor(&loan_id), 15000);
+}
+
+#[test]
+fn test_overflowing_valuation_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, quote_addr, admin) = setup(&env);
+    let collateral_addr = create_token_addr(&env);
+    client.whitelist_collateral(&admin, &collateral_addr);
+    let oracle = setup_oracle(&env, &client, &admin, &collateral_addr);
+    set_collateral_price(&env, &oracle, &collateral_addr, i128::MAX);
+    assert_eq!(client.get_quote_asset(), Some(quote_addr));
+
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &3000);
+    let result = client.try_create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &3000);
+    assert_eq!(result, Err(Ok(BorrowingError::ValuationOverflow)));
+}
+
+#[test]
+fn test_set_price_oracle_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, _admin) = setup(&env);
+    let oracle_id = env.register_contract(None, MockOracle);
+    let non_admin = Address::generate(&env);
+
+    let result = client.try_set_price_oracle(&non_admin, &collateral_addr, &oracle_id, &3600);
+    assert_eq!(result, Err(Ok(BorrowingError::Unauthorized)));
+}
@@ -1,7 +1,8 @@
 #![no_std]
-use access_control::{self, Role};
+use access_control::{self, PendingAdminTransfer, Role, RoleGrant};
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, Env, IntoVal, String, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, token, Address, Env, IntoVal, String,
+    Symbol, Vec,
 };
 
 mod test;
@@ -12,6 +13,29 @@ mod test;
 
 const PROPOSAL_DURATION: u64 = 604_800; // 7 days in seconds
 const QUORUM_THRESHOLD: i128 = 1; // Minimum total votes required to consider a proposal valid
+const MAX_BPS: u32 = 10_000;
+const MIN_APPROVAL_THRESHOLD_BPS: u32 = 5_000; // Never let a minority approve a proposal
+const DEFAULT_TIMELOCK_DELAY: u64 = 172_800; // 2 days in seconds
+const MIN_TIMELOCK_DELAY: u64 = 86_400; // Token holders always get at least a day to react
+const MAX_PROPOSAL_ACTIONS: u32 = 10;
+
+/// Action functions that replace a controlled contract's code or change who
+/// controls it; proposals calling them must clear the `ContractUpgrade` bar.
+/// Any other action changes configuration and needs at least `ParameterChange`.
+const UPGRADE_ACTION_FUNCTIONS: [&str; 12] = [
+    "upgrade",
+    "upgrade_contract",
+    "upgrade_controlled_contract",
+    "set_admin",
+    "propose_admin_transfer",
+    "accept_admin_transfer",
+    "assign_role",
+    "assign_role_until",
+    "revoke_role",
+    "set_role_admin",
+    "set_governance_contract",
+    "add_controlled_contract",
+];
 
 // ─────────────────────────────────────────────────
 // Storage Keys
@@ -38,6 +62,16 @@ pub enum DataKey {
     NextProposalId,
     Proposal(u32),
     UserVoteChoice(Address, u32),
+    // Token-backed voting power
+    GovernanceToken,
+    CheckpointCount(CheckpointSeries),
+    Checkpoint(CheckpointSeries, u32),
+    ProposalConfig(ProposalType),
+    VotePowerUsed(u32, Address),
+    // Executable proposals
+    ProposalActions(u32),
+    ProposalEta(u32),
+    TimelockDelay,
 }
 
 // ─────────────────────────────────────────────────
@@ -52,6 +86,8 @@ pub enum ProposalStatus {
     Rejected,
     Executed,
     Cancelled,
+    Queued,
+    Vetoed,
 }
 
 #[contracttype]
@@ -62,6 +98,29 @@ pub enum VoteChoice {
     Abstain,
 }
 
+/// Category of a proposal; each category carries its own quorum and
+/// approval threshold (see [`ProposalConfig`]).
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProposalType {
+    General,
+    ParameterChange,
+    ContractUpgrade,
+}
+
+/// Passing requirements for a proposal type, in basis points.
+///
+/// - `quorum_bps`: share of the snapshotted voting supply that must take part
+///   (yes + no + abstain).
+/// - `approval_threshold_bps`: share of the decisive (yes + no) votes that
+///   must be exceeded by yes votes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalConfig {
+    pub quorum_bps: u32,
+    pub approval_threshold_bps: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Proposal {
@@ -75,6 +134,21 @@ pub struct Proposal {
     pub status: ProposalStatus,
     pub created_at: u64,
     pub expires_at: u64,
+    pub proposal_type: ProposalType,
+    /// Ledger sequence whose closing balances determine voting power.
+    pub snapshot_ledger: u32,
+    pub quorum_bps: u32,
+    pub approval_threshold_bps: u32,
+}
+
+/// A call the governance contract makes on a controlled contract once the
+/// proposal carrying it has passed and cleared the timelock.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalAction {
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<soroban_sdk::Val>,
 }
 
 #[contracttype]
@@ -85,6 +159,23 @@ pub struct VoteCount {
     pub abstain_votes: i128,
 }
 
+/// A history of escrowed balances, stored as one entry per checkpoint so it
+/// can grow without hitting the ledger entry size limit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CheckpointSeries {
+    Holder(Address),
+    TotalSupply,
+}
+
+/// Escrowed governance-token balance as of the end of `ledger`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceCheckpoint {
+    pub ledger: u32,
+    pub balance: i128,
+}
+
 // ─────────────────────────────────────────────────
 // Delegation Types
 // ─────────────────────────────────────────────────
@@ -160,6 +251,14 @@ pub struct VoteCastEvent {
     pub voting_power: i128,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VotingBalanceChangedEvent {
+    pub holder: Address,
+    pub amount: i128,
+    pub balance: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalExecutedEvent {
@@ -167,6 +266,20 @@ pub struct ProposalExecutedEvent {
     pub executor: Address,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalQueuedEvent {
+    pub proposal_id: u32,
+    pub eta: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalVetoedEvent {
+    pub proposal_id: u32,
+    pub guardian: Address,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalCancelledEvent {
@@ -200,6 +313,19 @@ pub enum GovernanceError {
     NotProposer = 17,
     ReentrantCall = 18,
     ContractPaused = 19,
+    VotingNotStarted = 20,
+    GovernanceTokenNotSet = 21,
+    InsufficientVotingBalance = 22,
+    InvalidProposalConfig = 23,
+    GovernanceTokenLocked = 24,
+    ProposalNotQueued = 25,
+    TimelockNotElapsed = 26,
+    VetoWindowClosed = 27,
+    InvalidProposalAction = 28,
+    TimelockDelayTooShort = 29,
+    NoPendingAdminTransfer = 30,
+    InvalidAdminTransfer = 31,
+    ProposalTypeTooWeak = 32,
 }
 
 // ─────────────────────────────────────────────────
@@ -220,6 +346,7 @@ impl GovernanceContract {
         collateral_ratio: u32,
         liquidation_bonus: u32,
     ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         if env.storage().instance().has(&DataKey::Admin) {
             return Err(GovernanceError::AlreadyInitialized);
         }
@@ -250,67 +377,187 @@ impl GovernanceContract {
     }
 
     pub fn get_admin(env: Env) -> Address {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::Admin)
             .expect("Not initialized")
     }
 
-    /// Assign a role to an address. Admin-only.
+    /// Assign a role to an address. Callable by admins and by holders of the
+    /// role's admin role (see `set_role_admin`). The Admin role itself is
+    /// handed over with `propose_admin_transfer` / `accept_admin_transfer`.
     pub fn assign_role(
         env: Env,
         admin: Address,
         address: Address,
         role: Role,
     ) -> Result<(), GovernanceError> {
+        Self::assign_role_until(env, admin, address, role, 0)
+    }
+
+    /// Like `assign_role`, but the grant lapses at `expires_at` (unix
+    /// seconds; 0 for never). Re-assigning replaces the expiry.
+    pub fn assign_role_until(
+        env: Env,
+        admin: Address,
+        address: Address,
+        role: Role,
+        expires_at: u64,
+    ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         admin.require_auth();
-        access_control::require_role(&env, &admin, Role::Admin, GovernanceError::Unauthorized)?;
-        access_control::assign_role(&env, &address, role);
+        access_control::require_role_admin(
+            &env,
+            &admin,
+            role.clone(),
+            GovernanceError::Unauthorized,
+        )?;
+        if role == Role::Admin {
+            return Err(GovernanceError::InvalidAdminTransfer);
+        }
+        access_control::assign_role_until(&env, &address, role, expires_at);
         Ok(())
     }
 
-    /// Revoke a role from an address. Admin-only.
+    /// Revoke a role from an address. Callable by admins and by holders of
+    /// the role's admin role. The Admin role only moves with the two-step
+    /// admin transfer, so it cannot be revoked here.
     pub fn revoke_role(
         env: Env,
         admin: Address,
         address: Address,
         role: Role,
     ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         admin.require_auth();
-        access_control::require_role(&env, &admin, Role::Admin, GovernanceError::Unauthorized)?;
+        access_control::require_role_admin(
+            &env,
+            &admin,
+            role.clone(),
+            GovernanceError::Unauthorized,
+        )?;
+        if role == Role::Admin {
+            return Err(GovernanceError::InvalidAdminTransfer);
+        }
         access_control::revoke_role(&env, &address, role);
         Ok(())
     }
 
+    /// Let holders of `admin_role` grant and revoke `role`. Admin-only; who
+    /// manages the Admin role cannot be changed.
+    pub fn set_role_admin(
+        env: Env,
+        admin: Address,
+        role: Role,
+        admin_role: Role,
+    ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role(&env, &admin, Role::Admin, GovernanceError::Unauthorized)?;
+        if role == Role::Admin {
+            return Err(GovernanceError::InvalidAdminTransfer);
+        }
+        access_control::set_role_admin(&env, role, admin_role);
+        Ok(())
+    }
+
+    /// The role whose holders may grant and revoke `role`.
+    pub fn get_role_admin(env: Env, role: Role) -> Role {
+        access_control::extend_instance_ttl(&env);
+        access_control::role_admin(&env, role)
+    }
+
+    /// Up to `limit` holders of `role`, from position `start`.
+    pub fn get_role_members(env: Env, role: Role, start: u32, limit: u32) -> Vec<RoleGrant> {
+        access_control::extend_instance_ttl(&env);
+        access_control::role_members(&env, role, start, limit)
+    }
+
+    /// Propose `new_admin` as the contract's admin. Nothing changes until they
+    /// call `accept_admin_transfer`; proposing again replaces the proposal.
+    pub fn propose_admin_transfer(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role(&env, &admin, Role::Admin, GovernanceError::Unauthorized)?;
+        if new_admin == admin {
+            return Err(GovernanceError::InvalidAdminTransfer);
+        }
+        access_control::propose_admin_transfer(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Accept a pending admin transfer, taking over the Admin role from the
+    /// admin who proposed it.
+    pub fn accept_admin_transfer(env: Env, new_admin: Address) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        new_admin.require_auth();
+        access_control::accept_admin_transfer(&env, &new_admin)
+            .ok_or(GovernanceError::NoPendingAdminTransfer)?;
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        Ok(())
+    }
+
+    /// Cancel a pending admin transfer. Admin-only.
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role(&env, &admin, Role::Admin, GovernanceError::Unauthorized)?;
+        access_control::cancel_admin_transfer(&env)
+            .ok_or(GovernanceError::NoPendingAdminTransfer)?;
+        Ok(())
+    }
+
+    /// The admin transfer waiting for acceptance, if any.
+    pub fn get_pending_admin_transfer(env: Env) -> Option<PendingAdminTransfer> {
+        access_control::extend_instance_ttl(&env);
+        access_control::pending_admin_transfer(&env)
+    }
+
     /// Check whether an address holds a given role.
     pub fn has_role(env: Env, address: Address, role: Role) -> bool {
+        access_control::extend_instance_ttl(&env);
         access_control::has_role(&env, &address, role)
     }
 
     /// Return all roles held by an address.
     pub fn get_roles(env: Env, address: Address) -> Vec<Role> {
-        use access_control::AccessControlKey;
-        env.storage()
-            .persistent()
-            .get(&AccessControlKey::Roles(address))
-            .unwrap_or(Vec::new(&env))
+        access_control::extend_instance_ttl(&env);
+        access_control::roles_of(&env, &address)
     }
 
     pub fn pause(env: Env, admin: Address) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         admin.require_auth();
-        access_control::require_role(&env, &admin, Role::Admin, GovernanceError::Unauthorized)?;
+        access_control::require_any_role(
+            &env,
+            &admin,
+            &[Role::Admin, Role::Pauser],
+            GovernanceError::Unauthorized,
+        )?;
         access_control::pause_contract(&env);
         Ok(())
     }
 
     pub fn unpause(env: Env, admin: Address) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         admin.require_auth();
-        access_control::require_role(&env, &admin, Role::Admin, GovernanceError::Unauthorized)?;
+        access_control::require_any_role(
+            &env,
+            &admin,
+            &[Role::Admin, Role::Pauser],
+            GovernanceError::Unauthorized,
+        )?;
         access_control::unpause_contract(&env);
         Ok(())
     }
 
     pub fn is_paused(env: Env) -> bool {
+        access_control::extend_instance_ttl(&env);
         access_control::is_contract_paused(&env)
     }
 
@@ -319,6 +566,7 @@ impl GovernanceContract {
     }
 
     pub fn update_interest_rate(env: Env, new_rate: u32) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::check_admin(&env)?;
         env.storage()
             .instance()
@@ -327,6 +575,7 @@ impl GovernanceContract {
     }
 
     pub fn update_collateral_ratio(env: Env, new_ratio: u32) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::check_admin(&env)?;
         env.storage()
             .instance()
@@ -335,6 +584,7 @@ impl GovernanceContract {
     }
 
     pub fn update_liquidation_bonus(env: Env, new_bonus: u32) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::check_admin(&env)?;
         env.storage()
             .instance()
@@ -348,6 +598,7 @@ impl GovernanceContract {
         proposer: Address,
         new_rate: u32,
     ) -> Result<u32, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         let mut args = Vec::new(&env);
         args.push_back(new_rate.into_val(&env));
         Self::propose_transaction(
@@ -364,6 +615,7 @@ impl GovernanceContract {
         proposer: Address,
         new_ratio: u32,
     ) -> Result<u32, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         let mut args = Vec::new(&env);
         args.push_back(new_ratio.into_val(&env));
         Self::propose_transaction(
@@ -380,6 +632,7 @@ impl GovernanceContract {
         proposer: Address,
         new_bonus: u32,
     ) -> Result<u32, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         let mut args = Vec::new(&env);
         args.push_back(new_bonus.into_val(&env));
         Self::propose_transaction(
@@ -392,6 +645,7 @@ impl GovernanceContract {
     }
 
     pub fn get_interest_rate(env: Env) -> u32 {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::InterestRate)
@@ -399,6 +653,7 @@ impl GovernanceContract {
     }
 
     pub fn get_collateral_ratio(env: Env) -> u32 {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::CollateralRatio)
@@ -406,6 +661,7 @@ impl GovernanceContract {
     }
 
     pub fn get_liquidation_bonus(env: Env) -> u32 {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::LiquidationBonus)
@@ -413,6 +669,7 @@ impl GovernanceContract {
     }
 
     pub fn get_multi_sig_config(env: Env) -> MultiSig {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::MultiSigConfig)
@@ -425,6 +682,7 @@ impl GovernanceContract {
         signers: Vec<Address>,
         threshold: u32,
     ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         admin.require_auth();
         Self::check_admin(&env)?;
 
@@ -446,6 +704,7 @@ impl GovernanceContract {
         function: Symbol,
         args: Vec<soroban_sdk::Val>,
     ) -> Result<u32, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         proposer.require_auth();
         Self::require_not_paused(&env)?;
 
@@ -477,6 +736,7 @@ impl GovernanceContract {
     }
 
     pub fn sign_transaction(env: Env, signer: Address, tx_id: u32) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         signer.require_auth();
         Self::require_not_paused(&env)?;
 
@@ -510,6 +770,7 @@ impl GovernanceContract {
         executor: Address,
         tx_id: u32,
     ) -> Result<soroban_sdk::Val, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         executor.require_auth();
         access_control::reentrancy_enter(&env, GovernanceError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
@@ -541,6 +802,7 @@ impl GovernanceContract {
     }
 
     pub fn get_pending_transaction(env: Env, tx_id: u32) -> Option<PendingTransaction> {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::PendingTransaction(tx_id))
@@ -558,17 +820,130 @@ impl GovernanceContract {
 
     // ─── Token Balance ───────────────────────────────
 
-    pub fn set_token_balance(env: Env, address: Address, balance: i128) {
+    /// Configure the token whose escrowed balances give voting power. Admin-only.
+    /// The token can only be changed while no voting balance is escrowed.
+    pub fn set_governance_token(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role(&env, &admin, Role::Admin, GovernanceError::Unauthorized)?;
+        if Self::latest_checkpoint(&env, &CheckpointSeries::TotalSupply) != 0 {
+            return Err(GovernanceError::GovernanceTokenLocked);
+        }
         env.storage()
             .instance()
-            .set(&DataKey::TokenBalance(address), &balance);
+            .set(&DataKey::GovernanceToken, &token);
+        Ok(())
     }
 
-    pub fn get_token_balance(env: Env, address: Address) -> i128 {
-        env.storage()
+    pub fn get_governance_token(env: Env) -> Option<Address> {
+        access_control::extend_instance_ttl(&env);
+        env.storage().instance().get(&DataKey::GovernanceToken)
+    }
+
+    /// Escrow `amount` governance tokens from `holder` to gain voting power.
+    /// The new balance counts for proposals created from the current ledger on.
+    pub fn deposit_voting_tokens(
+        env: Env,
+        holder: Address,
+        amount: i128,
+    ) -> Result<i128, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        holder.require_auth();
+        Self::require_not_paused(&env)?;
+        if amount <= 0 {
+            return Err(GovernanceError::ZeroAmount);
+        }
+        let token_address: Address = env
+            .storage()
             .instance()
-            .get(&DataKey::TokenBalance(address))
-            .unwrap_or(0)
+            .get(&DataKey::GovernanceToken)
+            .ok_or(GovernanceError::GovernanceTokenNotSet)?;
+
+        token::Client::new(&env, &token_address).transfer(
+            &holder,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let balance =
+            Self::latest_checkpoint(&env, &CheckpointSeries::Holder(holder.clone())) + amount;
+        Self::write_balance(&env, &holder, balance);
+
+        env.events().publish(
+            (Symbol::new(&env, "VoteDeposit"), holder.clone()),
+            VotingBalanceChangedEvent {
+                holder,
+                amount,
+                balance,
+            },
+        );
+
+        Ok(balance)
+    }
+
+    /// Return `amount` escrowed governance tokens to `holder`. Voting power
+    /// already snapshotted by existing proposals is unaffected.
+    pub fn withdraw_voting_tokens(
+        env: Env,
+        holder: Address,
+        amount: i128,
+    ) -> Result<i128, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        holder.require_auth();
+        if amount <= 0 {
+            return Err(GovernanceError::ZeroAmount);
+        }
+        let token_address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GovernanceToken)
+            .ok_or(GovernanceError::GovernanceTokenNotSet)?;
+
+        let current = Self::latest_checkpoint(&env, &CheckpointSeries::Holder(holder.clone()));
+        if amount > current {
+            return Err(GovernanceError::InsufficientVotingBalance);
+        }
+        let balance = current - amount;
+        Self::write_balance(&env, &holder, balance);
+
+        token::Client::new(&env, &token_address).transfer(
+            &env.current_contract_address(),
+            &holder,
+            &amount,
+        );
+
+        env.events().publish(
+            (Symbol::new(&env, "VoteWithdraw"), holder.clone()),
+            VotingBalanceChangedEvent {
+                holder,
+                amount,
+                balance,
+            },
+        );
+
+        Ok(balance)
+    }
+
+    /// Current escrowed governance-token balance of `address`.
+    pub fn get_token_balance(env: Env, address: Address) -> i128 {
+        access_control::extend_instance_ttl(&env);
+        Self::latest_checkpoint(&env, &CheckpointSeries::Holder(address))
+    }
+
+    /// Escrowed balance of `address` at the end of ledger `ledger`.
+    pub fn get_token_balance_at(env: Env, address: Address, ledger: u32) -> i128 {
+        access_control::extend_instance_ttl(&env);
+        Self::checkpoint_at(&env, &CheckpointSeries::Holder(address), ledger)
+    }
+
+    /// Total escrowed governance-token supply at the end of ledger `ledger`.
+    pub fn get_total_supply_at(env: Env, ledger: u32) -> i128 {
+        access_control::extend_instance_ttl(&env);
+        Self::checkpoint_at(&env, &CheckpointSeries::TotalSupply, ledger)
     }
 
     // ─── Delegation ──────────────────────────────────
@@ -578,6 +953,7 @@ impl GovernanceContract {
         delegator: Address,
         delegate: Address,
     ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         delegator.require_auth();
 
         if delegator == delegate {
@@ -631,6 +1007,7 @@ impl GovernanceContract {
     }
 
     pub fn undelegate_votes(env: Env, delegator: Address) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         delegator.require_auth();
 
         let current_delegate = Self::get_delegate(env.clone(), delegator.clone());
@@ -670,12 +1047,14 @@ impl GovernanceContract {
     }
 
     pub fn get_delegate(env: Env, delegator: Address) -> Option<Address> {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::Delegation(delegator))
     }
 
     pub fn get_delegators(env: Env, delegate: Address) -> Vec<Address> {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::Delegators(delegate))
@@ -683,6 +1062,7 @@ impl GovernanceContract {
     }
 
     pub fn get_voting_power(env: Env, address: Address) -> i128 {
+        access_control::extend_instance_ttl(&env);
         // Delegated accounts have zero direct voting power
         if env
             .storage()
@@ -692,11 +1072,7 @@ impl GovernanceContract {
             return 0;
         }
 
-        let own_balance: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TokenBalance(address.clone()))
-            .unwrap_or(0);
+        let own_balance = Self::latest_checkpoint(&env, &CheckpointSeries::Holder(address.clone()));
 
         let delegators: Vec<Address> = env
             .storage()
@@ -706,18 +1082,15 @@ impl GovernanceContract {
 
         let mut total_delegated: i128 = 0;
         for delegator_addr in delegators.iter() {
-            let bal: i128 = env
-                .storage()
-                .instance()
-                .get(&DataKey::TokenBalance(delegator_addr))
-                .unwrap_or(0);
-            total_delegated += bal;
+            total_delegated +=
+                Self::latest_checkpoint(&env, &CheckpointSeries::Holder(delegator_addr));
         }
 
         own_balance + total_delegated
     }
 
     pub fn get_delegation_history(env: Env) -> Vec<DelegationRecord> {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::DelegationHistory)
@@ -726,16 +1099,216 @@ impl GovernanceContract {
 
     // ─── Proposal Governance ─────────────────────────
 
-    /// Create a new governance proposal. Returns the new proposal ID.
+    /// Create a new general governance proposal. Returns the new proposal ID.
     pub fn create_proposal(
         env: Env,
         proposer: Address,
         title: String,
         description: String,
     ) -> Result<u32, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        Self::create_typed_proposal(env, proposer, ProposalType::General, title, description)
+    }
+
+    /// Create a proposal of the given type. The type's current quorum and
+    /// approval threshold are fixed on the proposal, and voting power is
+    /// snapshotted at the end of the current ledger; voting opens in the next one.
+    pub fn create_typed_proposal(
+        env: Env,
+        proposer: Address,
+        proposal_type: ProposalType,
+        title: String,
+        description: String,
+    ) -> Result<u32, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         proposer.require_auth();
         Self::require_not_paused(&env)?;
+        Self::store_new_proposal(
+            &env,
+            proposer,
+            proposal_type,
+            title,
+            description,
+            Vec::new(&env),
+        )
+    }
+
+    /// Create a proposal that, once passed and queued, calls `actions` in order
+    /// from the governance contract. Every target must be a controlled contract,
+    /// and `proposal_type` must be at least as strict as every action needs
+    /// (see [`UPGRADE_ACTION_FUNCTIONS`]).
+    pub fn create_executable_proposal(
+        env: Env,
+        proposer: Address,
+        proposal_type: ProposalType,
+        title: String,
+        description: String,
+        actions: Vec<ProposalAction>,
+    ) -> Result<u32, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        proposer.require_auth();
+        Self::require_not_paused(&env)?;
+
+        if actions.is_empty() || actions.len() > MAX_PROPOSAL_ACTIONS {
+            return Err(GovernanceError::InvalidProposalAction);
+        }
+        let contracts = Self::get_controlled_contracts(env.clone());
+        for action in actions.iter() {
+            if !contracts.contains(&action.target) {
+                return Err(GovernanceError::InvalidProposalAction);
+            }
+            if (proposal_type as u32)
+                < (Self::required_proposal_type(&env, &action.function) as u32)
+            {
+                return Err(GovernanceError::ProposalTypeTooWeak);
+            }
+        }
+
+        Self::store_new_proposal(&env, proposer, proposal_type, title, description, actions)
+    }
+
+    /// Queue a passed proposal behind the timelock. Anyone can call this once
+    /// voting has ended; guardians may veto it until the returned `eta`.
+    pub fn queue_proposal(env: Env, proposal_id: u32) -> Result<u64, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_not_paused(&env)?;
+
+        let status = Self::evaluate_proposal_status(&env, proposal_id)?;
+        if status != ProposalStatus::Passed {
+            return Err(GovernanceError::ProposalNotPassed);
+        }
+
+        let mut proposal: Proposal = env
+            .storage()
+            .instance()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(GovernanceError::ProposalNotFound)?;
+
+        let eta = env.ledger().timestamp() + Self::get_timelock_delay(env.clone());
+        proposal.status = ProposalStatus::Queued;
+        env.storage()
+            .instance()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+        env.storage()
+            .instance()
+            .set(&DataKey::ProposalEta(proposal_id), &eta);
+
+        env.events().publish(
+            (Symbol::new(&env, "PropQueue"), proposal_id),
+            ProposalQueuedEvent { proposal_id, eta },
+        );
+
+        Ok(eta)
+    }
+
+    /// Veto a queued proposal before its timelock elapses. Guardian-only.
+    pub fn veto_proposal(
+        env: Env,
+        guardian: Address,
+        proposal_id: u32,
+    ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        guardian.require_auth();
+        access_control::require_role(
+            &env,
+            &guardian,
+            Role::Guardian,
+            GovernanceError::Unauthorized,
+        )?;
+
+        let mut proposal: Proposal = env
+            .storage()
+            .instance()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(GovernanceError::ProposalNotFound)?;
+
+        if proposal.status != ProposalStatus::Queued {
+            return Err(GovernanceError::ProposalNotQueued);
+        }
+
+        let eta: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposalEta(proposal_id))
+            .ok_or(GovernanceError::ProposalNotQueued)?;
+        if env.ledger().timestamp() >= eta {
+            return Err(GovernanceError::VetoWindowClosed);
+        }
+
+        proposal.status = ProposalStatus::Vetoed;
+        env.storage()
+            .instance()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+
+        env.events().publish(
+            (Symbol::new(&env, "PropVeto"), guardian.clone()),
+            ProposalVetoedEvent {
+                proposal_id,
+                guardian,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Set the delay between queueing and executing a proposal. Admin-only.
+    pub fn set_timelock_delay(env: Env, admin: Address, delay: u64) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role(&env, &admin, Role::Admin, GovernanceError::Unauthorized)?;
+        if delay < MIN_TIMELOCK_DELAY {
+            return Err(GovernanceError::TimelockDelayTooShort);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::TimelockDelay, &delay);
+        Ok(())
+    }
+
+    pub fn get_timelock_delay(env: Env) -> u64 {
+        access_control::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::TimelockDelay)
+            .unwrap_or(DEFAULT_TIMELOCK_DELAY)
+    }
+
+    pub fn get_proposal_actions(env: Env, proposal_id: u32) -> Vec<ProposalAction> {
+        access_control::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::ProposalActions(proposal_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Earliest execution time of a queued proposal.
+    pub fn get_proposal_eta(env: Env, proposal_id: u32) -> Option<u64> {
+        access_control::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::ProposalEta(proposal_id))
+    }
+
+    /// Weakest proposal type that may carry an action calling `function`.
+    fn required_proposal_type(env: &Env, function: &Symbol) -> ProposalType {
+        if UPGRADE_ACTION_FUNCTIONS
+            .iter()
+            .any(|name| *function == Symbol::new(env, name))
+        {
+            ProposalType::ContractUpgrade
+        } else {
+            ProposalType::ParameterChange
+        }
+    }
 
+    fn store_new_proposal(
+        env: &Env,
+        proposer: Address,
+        proposal_type: ProposalType,
+        title: String,
+        description: String,
+        actions: Vec<ProposalAction>,
+    ) -> Result<u32, GovernanceError> {
         let proposal_id: u32 = env
             .storage()
             .instance()
@@ -744,6 +1317,7 @@ impl GovernanceContract {
 
         let now = env.ledger().timestamp();
         let expires_at = now + PROPOSAL_DURATION;
+        let config = Self::get_proposal_config(env.clone(), proposal_type);
 
         let proposal = Proposal {
             id: proposal_id,
@@ -756,6 +1330,10 @@ impl GovernanceContract {
             status: ProposalStatus::Active,
             created_at: now,
             expires_at,
+            proposal_type,
+            snapshot_ledger: env.ledger().sequence(),
+            quorum_bps: config.quorum_bps,
+            approval_threshold_bps: config.approval_threshold_bps,
         };
 
         env.storage()
@@ -764,9 +1342,14 @@ impl GovernanceContract {
         env.storage()
             .instance()
             .set(&DataKey::NextProposalId, &(proposal_id + 1));
+        if !actions.is_empty() {
+            env.storage()
+                .instance()
+                .set(&DataKey::ProposalActions(proposal_id), &actions);
+        }
 
         env.events().publish(
-            (Symbol::new(&env, "PropCreate"), proposer.clone()),
+            (Symbol::new(env, "PropCreate"), proposer.clone()),
             ProposalCreatedEvent {
                 id: proposal_id,
                 proposer,
@@ -778,13 +1361,16 @@ impl GovernanceContract {
     }
 
     /// Vote on a proposal with yes, no, or abstain.
-    /// Voting power is automatically derived from the voter's token balance plus any delegated balances.
+    /// Voting power is the voter's escrowed balance plus any delegated balances,
+    /// all taken at the proposal's snapshot ledger. Each balance is counted at
+    /// most once per proposal, so re-delegating cannot reuse it.
     pub fn vote(
         env: Env,
         voter: Address,
         proposal_id: u32,
         choice: VoteChoice,
     ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         voter.require_auth();
         Self::require_not_paused(&env)?;
 
@@ -797,31 +1383,6 @@ impl GovernanceContract {
             return Err(GovernanceError::Unauthorized);
         }
 
-        // Compute voting power inline
-        let own_balance: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TokenBalance(voter.clone()))
-            .unwrap_or(0);
-        let delegators: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Delegators(voter.clone()))
-            .unwrap_or_else(|| Vec::new(&env));
-        let mut voting_power = own_balance;
-        for delegator_addr in delegators.iter() {
-            let bal: i128 = env
-                .storage()
-                .instance()
-                .get(&DataKey::TokenBalance(delegator_addr))
-                .unwrap_or(0);
-            voting_power += bal;
-        }
-
-        if voting_power == 0 {
-            return Err(GovernanceError::ZeroAmount);
-        }
-
         // Validate proposal state
         let mut proposal: Proposal = env
             .storage()
@@ -829,6 +1390,11 @@ impl GovernanceContract {
             .get(&DataKey::Proposal(proposal_id))
             .ok_or(GovernanceError::ProposalNotFound)?;
 
+        // Balances in the snapshot ledger are only final once it has closed
+        if env.ledger().sequence() <= proposal.snapshot_ledger {
+            return Err(GovernanceError::VotingNotStarted);
+        }
+
         let current_time = env.ledger().timestamp();
         if current_time > proposal.expires_at {
             return Err(GovernanceError::ProposalExpired);
@@ -844,6 +1410,20 @@ impl GovernanceContract {
             return Err(GovernanceError::AlreadyVoted);
         }
 
+        let delegators: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Delegators(voter.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut voting_power = Self::claim_snapshot_power(&env, &proposal, &voter);
+        for delegator_addr in delegators.iter() {
+            voting_power += Self::claim_snapshot_power(&env, &proposal, &delegator_addr);
+        }
+
+        if voting_power == 0 {
+            return Err(GovernanceError::ZeroAmount);
+        }
+
         // Record vote and tally
         env.storage().instance().set(&vote_choice_key, &choice);
 
@@ -872,19 +1452,36 @@ impl GovernanceContract {
         Ok(())
     }
 
-    /// Execute a passed proposal. Anyone can call this after the voting period ends.
+    /// Execute a proposal. Anyone can call this.
+    ///
+    /// Signalling proposals (no actions) can be executed as soon as they pass.
+    /// Proposals with actions must be queued first and can only be executed
+    /// once the timelock has elapsed; their actions then run in order.
     pub fn execute_proposal(
         env: Env,
         executor: Address,
         proposal_id: u32,
     ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         executor.require_auth();
         access_control::reentrancy_enter(&env, GovernanceError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
 
-        let status = Self::evaluate_proposal_status(&env, proposal_id)?;
-        if status != ProposalStatus::Passed {
-            return Err(GovernanceError::ProposalNotPassed);
+        let actions = Self::get_proposal_actions(env.clone(), proposal_id);
+        match Self::evaluate_proposal_status(&env, proposal_id)? {
+            ProposalStatus::Passed if actions.is_empty() => {}
+            ProposalStatus::Passed => return Err(GovernanceError::ProposalNotQueued),
+            ProposalStatus::Queued => {
+                let eta: u64 = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::ProposalEta(proposal_id))
+                    .ok_or(GovernanceError::ProposalNotQueued)?;
+                if env.ledger().timestamp() < eta {
+                    return Err(GovernanceError::TimelockNotElapsed);
+                }
+            }
+            _ => return Err(GovernanceError::ProposalNotPassed),
         }
 
         let mut proposal: Proposal = env
@@ -893,11 +1490,27 @@ impl GovernanceContract {
             .get(&DataKey::Proposal(proposal_id))
             .ok_or(GovernanceError::ProposalNotFound)?;
 
+        // Mark executed before calling out so a re-entrant call cannot replay the actions
         proposal.status = ProposalStatus::Executed;
         env.storage()
             .instance()
             .set(&DataKey::Proposal(proposal_id), &proposal);
 
+        let contracts = Self::get_controlled_contracts(env.clone());
+        for action in actions.iter() {
+            if !contracts.contains(&action.target) {
+                return Err(GovernanceError::InvalidProposalAction);
+            }
+            env.invoke_contract::<soroban_sdk::Val>(&action.target, &action.function, action.args);
+            env.events().publish(
+                (Symbol::new(&env, "EXECUTE"), action.target.clone()),
+                ContractExecutedEvent {
+                    contract: action.target,
+                    func: action.function,
+                },
+            );
+        }
+
         env.events().publish(
             (Symbol::new(&env, "PropExec"), executor.clone()),
             ProposalExecutedEvent {
@@ -912,6 +1525,7 @@ impl GovernanceContract {
 
     /// Get full proposal details.
     pub fn get_proposal(env: Env, proposal_id: u32) -> Option<Proposal> {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::Proposal(proposal_id))
@@ -922,11 +1536,13 @@ impl GovernanceContract {
         env: Env,
         proposal_id: u32,
     ) -> Result<ProposalStatus, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::evaluate_proposal_status(&env, proposal_id)
     }
 
     /// Get the vote counts (yes, no, abstain) for a proposal.
     pub fn get_vote_count(env: Env, proposal_id: u32) -> Result<VoteCount, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         let proposal: Proposal = env
             .storage()
             .instance()
@@ -941,6 +1557,7 @@ impl GovernanceContract {
 
     /// Get the vote choice of a specific voter on a proposal.
     pub fn get_user_vote(env: Env, voter: Address, proposal_id: u32) -> Option<VoteChoice> {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::UserVoteChoice(voter, proposal_id))
@@ -952,6 +1569,7 @@ impl GovernanceContract {
         caller: Address,
         proposal_id: u32,
     ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         caller.require_auth();
 
         let mut proposal: Proposal = env
@@ -986,6 +1604,7 @@ impl GovernanceContract {
 
     /// Returns yes_votes for a proposal (backward-compatible helper).
     pub fn get_proposal_votes(env: Env, proposal_id: u32) -> i128 {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get::<DataKey, Proposal>(&DataKey::Proposal(proposal_id))
@@ -993,8 +1612,40 @@ impl GovernanceContract {
             .unwrap_or(0)
     }
 
+    /// Set the quorum and approval threshold for a proposal type. Admin-only.
+    /// Proposals that already exist keep the values they were created with.
+    pub fn set_proposal_config(
+        env: Env,
+        admin: Address,
+        proposal_type: ProposalType,
+        config: ProposalConfig,
+    ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role(&env, &admin, Role::Admin, GovernanceError::Unauthorized)?;
+        if config.quorum_bps > MAX_BPS
+            || config.approval_threshold_bps < MIN_APPROVAL_THRESHOLD_BPS
+            || config.approval_threshold_bps >= MAX_BPS
+        {
+            return Err(GovernanceError::InvalidProposalConfig);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ProposalConfig(proposal_type), &config);
+        Ok(())
+    }
+
+    pub fn get_proposal_config(env: Env, proposal_type: ProposalType) -> ProposalConfig {
+        access_control::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::ProposalConfig(proposal_type))
+            .unwrap_or_else(|| Self::default_proposal_config(proposal_type))
+    }
+
     /// Returns true if the voter has already cast a vote on this proposal.
     pub fn has_voted(env: Env, voter: Address, proposal_id: u32) -> bool {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .has(&DataKey::UserVoteChoice(voter, proposal_id))
@@ -1021,15 +1672,124 @@ impl GovernanceContract {
             return Ok(ProposalStatus::Active);
         }
 
-        // Voting period ended — evaluate result against quorum and majority
+        // Voting period ended — evaluate result against quorum and approval threshold
         let total_votes = proposal.yes_votes + proposal.no_votes + proposal.abstain_votes;
-        if total_votes >= QUORUM_THRESHOLD && proposal.yes_votes > proposal.no_votes {
+        let total_supply = Self::checkpoint_at(
+            env,
+            &CheckpointSeries::TotalSupply,
+            proposal.snapshot_ledger,
+        );
+        let quorum_met = total_votes >= QUORUM_THRESHOLD
+            && total_votes * MAX_BPS as i128 >= total_supply * proposal.quorum_bps as i128;
+        let approved = proposal.yes_votes * MAX_BPS as i128
+            > (proposal.yes_votes + proposal.no_votes) * proposal.approval_threshold_bps as i128;
+        if quorum_met && approved {
             Ok(ProposalStatus::Passed)
         } else {
             Ok(ProposalStatus::Rejected)
         }
     }
 
+    fn default_proposal_config(proposal_type: ProposalType) -> ProposalConfig {
+        let (quorum_bps, approval_threshold_bps) = match proposal_type {
+            ProposalType::General => (400, 5_000),
+            ProposalType::ParameterChange => (1_000, 5_000),
+            ProposalType::ContractUpgrade => (2_000, 6_667),
+        };
+        ProposalConfig {
+            quorum_bps,
+            approval_threshold_bps,
+        }
+    }
+
+    fn checkpoint_count(env: &Env, series: &CheckpointSeries) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CheckpointCount(series.clone()))
+            .unwrap_or(0)
+    }
+
+    fn read_checkpoint(env: &Env, series: &CheckpointSeries, index: u32) -> BalanceCheckpoint {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Checkpoint(series.clone(), index))
+            .expect("Checkpoint missing")
+    }
+
+    fn latest_checkpoint(env: &Env, series: &CheckpointSeries) -> i128 {
+        match Self::checkpoint_count(env, series) {
+            0 => 0,
+            count => Self::read_checkpoint(env, series, count - 1).balance,
+        }
+    }
+
+    /// Binary search for the last checkpoint written at or before `ledger`.
+    fn checkpoint_at(env: &Env, series: &CheckpointSeries, ledger: u32) -> i128 {
+        let mut low = 0u32;
+        let mut high = Self::checkpoint_count(env, series);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if Self::read_checkpoint(env, series, mid).ledger <= ledger {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low == 0 {
+            0
+        } else {
+            Self::read_checkpoint(env, series, low - 1).balance
+        }
+    }
+
+    fn push_checkpoint(env: &Env, series: &CheckpointSeries, balance: i128) {
+        let ledger = env.ledger().sequence();
+        let count = Self::checkpoint_count(env, series);
+        // Several updates within one ledger collapse into a single checkpoint
+        let index = if count > 0 && Self::read_checkpoint(env, series, count - 1).ledger == ledger {
+            count - 1
+        } else {
+            env.storage()
+                .persistent()
+                .set(&DataKey::CheckpointCount(series.clone()), &(count + 1));
+            access_control::extend_persistent_ttl(env, &DataKey::CheckpointCount(series.clone()));
+            count
+        };
+        env.storage().persistent().set(
+            &DataKey::Checkpoint(series.clone(), index),
+            &BalanceCheckpoint { ledger, balance },
+        );
+        access_control::extend_persistent_ttl(env, &DataKey::Checkpoint(series.clone(), index));
+    }
+
+    fn write_balance(env: &Env, holder: &Address, balance: i128) {
+        let series = CheckpointSeries::Holder(holder.clone());
+        let previous = Self::latest_checkpoint(env, &series);
+        Self::push_checkpoint(env, &series, balance);
+        let supply =
+            Self::latest_checkpoint(env, &CheckpointSeries::TotalSupply) - previous + balance;
+        Self::push_checkpoint(env, &CheckpointSeries::TotalSupply, supply);
+    }
+
+    /// Snapshot balance of `address` for `proposal`, or 0 if it has already
+    /// been counted towards another vote on the same proposal.
+    fn claim_snapshot_power(env: &Env, proposal: &Proposal, address: &Address) -> i128 {
+        let used_key = DataKey::VotePowerUsed(proposal.id, address.clone());
+        if env.storage().persistent().has(&used_key) {
+            return 0;
+        }
+        let power = Self::checkpoint_at(
+            env,
+            &CheckpointSeries::Holder(address.clone()),
+            proposal.snapshot_ledger,
+        );
+        if power > 0 {
+            env.storage().persistent().set(&used_key, &true);
+            access_control::extend_persistent_ttl(env, &used_key);
+        }
+        power
+    }
+
     fn check_circular_delegation(
         env: &Env,
         delegator: &Address,
@@ -1105,6 +1865,7 @@ impl GovernanceContract {
         _admin: Address,
         contract: Address,
     ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::check_admin(&env)?;
 
         let mut contracts: Vec<Address> = env
@@ -1129,6 +1890,7 @@ impl GovernanceContract {
     }
 
     pub fn get_controlled_contracts(env: Env) -> Vec<Address> {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::ControlledContracts)
@@ -1142,6 +1904,7 @@ impl GovernanceContract {
         func: Symbol,
         args: Vec<soroban_sdk::Val>,
     ) -> Result<soroban_sdk::Val, GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::check_admin(&env)?;
         access_control::reentrancy_enter(&env, GovernanceError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
@@ -1168,6 +1931,7 @@ impl GovernanceContract {
         contract: Address,
         new_wasm_hash: soroban_sdk::BytesN<32>,
     ) -> Result<(), GovernanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::check_admin(&env)?;
         access_control::reentrancy_enter(&env, GovernanceError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
@@ -1191,3 +1955,12 @@ impl GovernanceContract {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[contractimpl]
+impl GovernanceContract {
+    /// Test-only: write an escrowed balance directly, bypassing the token transfer.
+    pub fn set_token_balance(env: Env, address: Address, balance: i128) {
+        Self::write_balance(&env, &address, balance);
+    }
+}
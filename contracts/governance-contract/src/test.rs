@@ -1,7 +1,7 @@
 #![cfg(test)]
 use super::*;
 use soroban_sdk::testutils::{Address as _, Ledger};
-use soroban_sdk::{Env, String};
+use soroban_sdk::{token, Env, String};
 
 // ─────────────────────────────────────────────────
 // Helpers
@@ -16,11 +16,18 @@ fn setup_contract(env: &Env) -> (GovernanceContractClient<'_>, Address) {
 }
 
 fn make_proposal(env: &Env, client: &GovernanceContractClient, proposer: &Address) -> u32 {
-    client.create_proposal(
+    let proposal_id = client.create_proposal(
         proposer,
         &String::from_str(env, "Test Proposal"),
         &String::from_str(env, "A test governance proposal"),
-    )
+    );
+    // Voting opens once the snapshot ledger has closed
+    advance_ledger(env);
+    proposal_id
+}
+
+fn advance_ledger(env: &Env) {
+    env.ledger().with_mut(|li| li.sequence_number += 1);
 }
 
 // ─────────────────────────────────────────────────
@@ -702,6 +709,580 @@ fn test_has_voted_reflects_state() {
     assert!(client.has_voted(&voter, &proposal_id));
 }
 
+// ─────────────────────────────────────────────────
+// Token Voting & Snapshot Tests
+// ─────────────────────────────────────────────────
+
+fn setup_governance_token(
+    env: &Env,
+    client: &GovernanceContractClient,
+    admin: &Address,
+) -> Address {
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    client.set_governance_token(admin, &token);
+    token
+}
+
+#[test]
+fn test_deposit_and_withdraw_voting_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let token = setup_governance_token(&env, &client, &admin);
+    let holder = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&holder, &1000);
+
+    assert_eq!(client.deposit_voting_tokens(&holder, &600), 600);
+    assert_eq!(client.get_token_balance(&holder), 600);
+    assert_eq!(client.get_voting_power(&holder), 600);
+    assert_eq!(token::Client::new(&env, &token).balance(&holder), 400);
+
+    assert_eq!(client.withdraw_voting_tokens(&holder, &200), 400);
+    assert_eq!(client.get_token_balance(&holder), 400);
+    assert_eq!(token::Client::new(&env, &token).balance(&holder), 600);
+
+    assert_eq!(
+        client.try_withdraw_voting_tokens(&holder, &401),
+        Err(Ok(GovernanceError::InsufficientVotingBalance))
+    );
+}
+
+#[test]
+fn test_deposit_without_governance_token_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin) = setup_contract(&env);
+    let holder = Address::generate(&env);
+
+    assert_eq!(
+        client.try_deposit_voting_tokens(&holder, &100),
+        Err(Ok(GovernanceError::GovernanceTokenNotSet))
+    );
+}
+
+#[test]
+fn test_governance_token_locked_while_balances_escrowed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let token = setup_governance_token(&env, &client, &admin);
+    let holder = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&holder, &100);
+    client.deposit_voting_tokens(&holder, &100);
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_set_governance_token(&admin, &other),
+        Err(Ok(GovernanceError::GovernanceTokenLocked))
+    );
+}
+
+#[test]
+fn test_vote_in_snapshot_ledger_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let voter = Address::generate(&env);
+    client.set_token_balance(&voter, &1000);
+
+    let proposal_id = client.create_proposal(
+        &admin,
+        &String::from_str(&env, "Same ledger"),
+        &String::from_str(&env, "Votes must wait for the snapshot ledger to close"),
+    );
+
+    assert_eq!(
+        client.try_vote(&voter, &proposal_id, &VoteChoice::Yes),
+        Err(Ok(GovernanceError::VotingNotStarted))
+    );
+}
+
+#[test]
+fn test_tokens_moved_after_snapshot_cannot_vote_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let token = setup_governance_token(&env, &client, &admin);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&alice, &1000);
+    client.deposit_voting_tokens(&alice, &1000);
+
+    let proposal_id = make_proposal(&env, &client, &admin);
+    client.vote(&alice, &proposal_id, &VoteChoice::Yes);
+
+    // Alice moves her tokens to Bob, who escrows them and tries to vote again
+    client.withdraw_voting_tokens(&alice, &1000);
+    token::Client::new(&env, &token).transfer(&alice, &bob, &1000);
+    client.deposit_voting_tokens(&bob, &1000);
+    assert_eq!(client.get_voting_power(&bob), 1000);
+
+    assert_eq!(
+        client.try_vote(&bob, &proposal_id, &VoteChoice::Yes),
+        Err(Ok(GovernanceError::ZeroAmount))
+    );
+    assert_eq!(client.get_vote_count(&proposal_id).yes_votes, 1000);
+
+    // The new balance counts for proposals created afterwards
+    let next_id = make_proposal(&env, &client, &admin);
+    client.vote(&bob, &next_id, &VoteChoice::Yes);
+    assert_eq!(client.get_vote_count(&next_id).yes_votes, 1000);
+}
+
+#[test]
+fn test_delegated_power_counted_once_per_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let delegator = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    client.set_token_balance(&delegator, &700);
+    client.set_token_balance(&delegate, &300);
+    client.delegate_votes(&delegator, &delegate);
+
+    let proposal_id = make_proposal(&env, &client, &admin);
+    client.vote(&delegate, &proposal_id, &VoteChoice::Yes);
+    assert_eq!(client.get_vote_count(&proposal_id).yes_votes, 1000);
+
+    // Taking the delegation back does not free the already-counted balance
+    client.undelegate_votes(&delegator);
+    assert_eq!(
+        client.try_vote(&delegator, &proposal_id, &VoteChoice::No),
+        Err(Ok(GovernanceError::ZeroAmount))
+    );
+}
+
+#[test]
+fn test_quorum_is_share_of_snapshot_supply() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let small_holder = Address::generate(&env);
+    let whale = Address::generate(&env);
+    client.set_token_balance(&small_holder, &300);
+    client.set_token_balance(&whale, &9_700);
+
+    // 300 of 10_000 is below the 4% general quorum
+    let proposal_id = make_proposal(&env, &client, &admin);
+    client.vote(&small_holder, &proposal_id, &VoteChoice::Yes);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 604_801;
+    });
+    assert_eq!(
+        client.get_proposal_status(&proposal_id),
+        ProposalStatus::Rejected
+    );
+}
+
+#[test]
+fn test_contract_upgrade_requires_supermajority() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let yes_voter = Address::generate(&env);
+    let no_voter = Address::generate(&env);
+    client.set_token_balance(&yes_voter, &600);
+    client.set_token_balance(&no_voter, &400);
+
+    let general_id = make_proposal(&env, &client, &admin);
+    let upgrade_id = client.create_typed_proposal(
+        &admin,
+        &ProposalType::ContractUpgrade,
+        &String::from_str(&env, "Upgrade"),
+        &String::from_str(&env, "Upgrade the lending contract"),
+    );
+    advance_ledger(&env);
+
+    let upgrade = client.get_proposal(&upgrade_id).unwrap();
+    assert_eq!(upgrade.proposal_type, ProposalType::ContractUpgrade);
+    assert_eq!(upgrade.approval_threshold_bps, 6_667);
+
+    for id in [general_id, upgrade_id] {
+        client.vote(&yes_voter, &id, &VoteChoice::Yes);
+        client.vote(&no_voter, &id, &VoteChoice::No);
+    }
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 604_801;
+    });
+    assert_eq!(
+        client.get_proposal_status(&general_id),
+        ProposalStatus::Passed
+    );
+    assert_eq!(
+        client.get_proposal_status(&upgrade_id),
+        ProposalStatus::Rejected
+    );
+}
+
+#[test]
+fn test_set_proposal_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let config = ProposalConfig {
+        quorum_bps: 2_500,
+        approval_threshold_bps: 7_500,
+    };
+
+    client.set_proposal_config(&admin, &ProposalType::ParameterChange, &config);
+    assert_eq!(
+        client.get_proposal_config(&ProposalType::ParameterChange),
+        config
+    );
+
+    let proposal_id = client.create_typed_proposal(
+        &admin,
+        &ProposalType::ParameterChange,
+        &String::from_str(&env, "Rate"),
+        &String::from_str(&env, "Raise the interest rate"),
+    );
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert_eq!(proposal.quorum_bps, 2_500);
+    assert_eq!(proposal.approval_threshold_bps, 7_500);
+}
+
+#[test]
+fn test_set_proposal_config_rejects_invalid_values() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+
+    let minority = ProposalConfig {
+        quorum_bps: 1_000,
+        approval_threshold_bps: 4_999,
+    };
+    assert_eq!(
+        client.try_set_proposal_config(&admin, &ProposalType::General, &minority),
+        Err(Ok(GovernanceError::InvalidProposalConfig))
+    );
+
+    let over_quorum = ProposalConfig {
+        quorum_bps: 10_001,
+        approval_threshold_bps: 5_000,
+    };
+    assert_eq!(
+        client.try_set_proposal_config(&admin, &ProposalType::General, &over_quorum),
+        Err(Ok(GovernanceError::InvalidProposalConfig))
+    );
+
+    let non_admin = Address::generate(&env);
+    let valid = ProposalConfig {
+        quorum_bps: 1_000,
+        approval_threshold_bps: 6_000,
+    };
+    assert_eq!(
+        client.try_set_proposal_config(&non_admin, &ProposalType::General, &valid),
+        Err(Ok(GovernanceError::Unauthorized))
+    );
+}
+
+// ─────────────────────────────────────────────────
+// Executable Proposal & Timelock Tests
+// ─────────────────────────────────────────────────
+
+mod param_target {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    /// Stand-in for a controlled contract with an admin-gated setter.
+    #[contract]
+    pub struct ParamTarget;
+
+    #[contractimpl]
+    impl ParamTarget {
+        pub fn set_reserve_factor(env: Env, admin: Address, reserve_factor_bps: u32) {
+            admin.require_auth();
+            env.storage()
+                .instance()
+                .set(&symbol_short!("admin"), &admin);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("rf"), &reserve_factor_bps);
+        }
+
+        pub fn reserve_factor(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("rf"))
+                .unwrap_or(0)
+        }
+
+        pub fn last_admin(env: Env) -> Option<Address> {
+            env.storage().instance().get(&symbol_short!("admin"))
+        }
+    }
+}
+
+use param_target::{ParamTarget, ParamTargetClient};
+
+/// Registers a controlled target and returns a passed (not yet queued)
+/// proposal that sets its reserve factor to 1500.
+fn setup_passed_executable_proposal<'a>(
+    env: &'a Env,
+    client: &GovernanceContractClient<'a>,
+    admin: &Address,
+) -> (u32, ParamTargetClient<'a>) {
+    let target_id = env.register_contract(None, ParamTarget);
+    client.add_controlled_contract(admin, &target_id);
+
+    let mut args: Vec<soroban_sdk::Val> = Vec::new(env);
+    args.push_back(client.address.clone().into_val(env));
+    args.push_back(1500u32.into_val(env));
+    let mut actions = Vec::new(env);
+    actions.push_back(ProposalAction {
+        target: target_id.clone(),
+        function: Symbol::new(env, "set_reserve_factor"),
+        args,
+    });
+
+    let voter = Address::generate(env);
+    client.set_token_balance(&voter, &1000);
+    let proposal_id = client.create_executable_proposal(
+        admin,
+        &ProposalType::ParameterChange,
+        &String::from_str(env, "Reserve factor"),
+        &String::from_str(env, "Raise the reserve factor to 15%"),
+        &actions,
+    );
+    advance_ledger(env);
+    client.vote(&voter, &proposal_id, &VoteChoice::Yes);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 604_801;
+    });
+
+    (proposal_id, ParamTargetClient::new(env, &target_id))
+}
+
+#[test]
+fn test_executable_proposal_runs_actions_after_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let (proposal_id, target) = setup_passed_executable_proposal(&env, &client, &admin);
+
+    assert_eq!(client.get_proposal_actions(&proposal_id).len(), 1);
+
+    let eta = client.queue_proposal(&proposal_id);
+    assert_eq!(eta, 604_801 + 172_800);
+    assert_eq!(client.get_proposal_eta(&proposal_id), Some(eta));
+    assert_eq!(
+        client.get_proposal_status(&proposal_id),
+        ProposalStatus::Queued
+    );
+
+    assert_eq!(
+        client.try_execute_proposal(&admin, &proposal_id),
+        Err(Ok(GovernanceError::TimelockNotElapsed))
+    );
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = eta;
+    });
+    let executor = Address::generate(&env);
+    client.execute_proposal(&executor, &proposal_id);
+
+    assert_eq!(target.reserve_factor(), 1500);
+    assert_eq!(target.last_admin(), Some(client.address.clone()));
+    assert_eq!(
+        client.get_proposal(&proposal_id).unwrap().status,
+        ProposalStatus::Executed
+    );
+    assert!(client
+        .try_execute_proposal(&executor, &proposal_id)
+        .is_err());
+}
+
+#[test]
+fn test_executable_proposal_must_be_queued() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let (proposal_id, target) = setup_passed_executable_proposal(&env, &client, &admin);
+
+    assert_eq!(
+        client.try_execute_proposal(&admin, &proposal_id),
+        Err(Ok(GovernanceError::ProposalNotQueued))
+    );
+    assert_eq!(target.reserve_factor(), 0);
+}
+
+#[test]
+fn test_queue_requires_passed_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let proposal_id = make_proposal(&env, &client, &admin);
+
+    // Still in the voting period
+    assert_eq!(
+        client.try_queue_proposal(&proposal_id),
+        Err(Ok(GovernanceError::ProposalNotPassed))
+    );
+}
+
+#[test]
+fn test_guardian_can_veto_queued_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let (proposal_id, target) = setup_passed_executable_proposal(&env, &client, &admin);
+    let guardian = Address::generate(&env);
+    client.assign_role(&admin, &guardian, &Role::Guardian);
+
+    let eta = client.queue_proposal(&proposal_id);
+    client.veto_proposal(&guardian, &proposal_id);
+    assert_eq!(
+        client.get_proposal_status(&proposal_id),
+        ProposalStatus::Vetoed
+    );
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = eta;
+    });
+    assert_eq!(
+        client.try_execute_proposal(&admin, &proposal_id),
+        Err(Ok(GovernanceError::ProposalNotPassed))
+    );
+    assert_eq!(target.reserve_factor(), 0);
+}
+
+#[test]
+fn test_veto_requires_guardian_role() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let (proposal_id, _target) = setup_passed_executable_proposal(&env, &client, &admin);
+    client.queue_proposal(&proposal_id);
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_veto_proposal(&outsider, &proposal_id),
+        Err(Ok(GovernanceError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_veto_window_closes_at_eta() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let (proposal_id, _target) = setup_passed_executable_proposal(&env, &client, &admin);
+    let guardian = Address::generate(&env);
+    client.assign_role(&admin, &guardian, &Role::Guardian);
+
+    let eta = client.queue_proposal(&proposal_id);
+    env.ledger().with_mut(|li| {
+        li.timestamp = eta;
+    });
+    assert_eq!(
+        client.try_veto_proposal(&guardian, &proposal_id),
+        Err(Ok(GovernanceError::VetoWindowClosed))
+    );
+}
+
+#[test]
+fn test_set_timelock_delay() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    assert_eq!(client.get_timelock_delay(), 172_800);
+
+    assert_eq!(
+        client.try_set_timelock_delay(&admin, &3_600),
+        Err(Ok(GovernanceError::TimelockDelayTooShort))
+    );
+    client.set_timelock_delay(&admin, &86_400);
+    let (proposal_id, _target) = setup_passed_executable_proposal(&env, &client, &admin);
+    assert_eq!(client.queue_proposal(&proposal_id), 604_801 + 86_400);
+
+    let non_admin = Address::generate(&env);
+    assert_eq!(
+        client.try_set_timelock_delay(&non_admin, &0),
+        Err(Ok(GovernanceError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_executable_proposal_rejects_uncontrolled_target() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+
+    let mut actions = Vec::new(&env);
+    actions.push_back(ProposalAction {
+        target: Address::generate(&env),
+        function: Symbol::new(&env, "set_reserve_factor"),
+        args: Vec::new(&env),
+    });
+    assert_eq!(
+        client.try_create_executable_proposal(
+            &admin,
+            &ProposalType::ParameterChange,
+            &String::from_str(&env, "Bad target"),
+            &String::from_str(&env, "Targets a contract governance does not control"),
+            &actions,
+        ),
+        Err(Ok(GovernanceError::InvalidProposalAction))
+    );
+
+    assert_eq!(
+        client.try_create_executable_proposal(
+            &admin,
+            &ProposalType::ParameterChange,
+            &String::from_str(&env, "No actions"),
+            &String::from_str(&env, "Nothing to execute"),
+            &Vec::new(&env),
+        ),
+        Err(Ok(GovernanceError::InvalidProposalAction))
+    );
+}
+
+#[test]
+fn test_executable_proposal_type_must_match_its_actions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let target_id = env.register_contract(None, ParamTarget);
+    client.add_controlled_contract(&admin, &target_id);
+
+    let action = |function: &str| {
+        let mut actions = Vec::new(&env);
+        actions.push_back(ProposalAction {
+            target: target_id.clone(),
+            function: Symbol::new(&env, function),
+            args: Vec::new(&env),
+        });
+        actions
+    };
+    let propose = |proposal_type: ProposalType, function: &str| {
+        client.try_create_executable_proposal(
+            &admin,
+            &proposal_type,
+            &String::from_str(&env, "Change"),
+            &String::from_str(&env, "Calls a controlled contract"),
+            &action(function),
+        )
+    };
+
+    for proposal_type in [ProposalType::General, ProposalType::ParameterChange] {
+        assert_eq!(
+            propose(proposal_type, "upgrade_contract"),
+            Err(Ok(GovernanceError::ProposalTypeTooWeak))
+        );
+    }
+    assert_eq!(
+        propose(ProposalType::General, "set_reserve_factor"),
+        Err(Ok(GovernanceError::ProposalTypeTooWeak))
+    );
+
+    assert!(propose(ProposalType::ContractUpgrade, "upgrade_contract").is_ok());
+    assert!(propose(ProposalType::ParameterChange, "set_reserve_factor").is_ok());
+    assert!(propose(ProposalType::ContractUpgrade, "set_reserve_factor").is_ok());
+}
+
 // ─────────────────────────────────────────────────
 // Access Control (RBAC) Tests
 // ─────────────────────────────────────────────────
@@ -730,6 +1311,13 @@ fn test_admin_can_assign_and_revoke_roles() {
 
     client.revoke_role(&admin, &user, &access_control::Role::Guardian);
     assert!(!client.has_role(&user, &access_control::Role::Guardian));
+
+    // Admin only moves with the two-step transfer, never by revocation.
+    assert_eq!(
+        client.try_revoke_role(&admin, &admin, &access_control::Role::Admin),
+        Err(Ok(GovernanceError::InvalidAdminTransfer))
+    );
+    assert!(client.has_role(&admin, &access_control::Role::Admin));
 }
 
 #[test]
@@ -744,6 +1332,42 @@ fn test_non_admin_cannot_assign_roles() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_admin_transfer_requires_acceptance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup_contract(&env);
+    let new_admin = Address::generate(&env);
+
+    assert_eq!(
+        client.try_assign_role(&admin, &new_admin, &access_control::Role::Admin),
+        Err(Ok(GovernanceError::InvalidAdminTransfer))
+    );
+
+    client.propose_admin_transfer(&admin, &new_admin);
+    assert_eq!(
+        client.get_pending_admin_transfer().unwrap().new_admin,
+        new_admin
+    );
+    assert!(!client.has_role(&new_admin, &access_control::Role::Admin));
+    assert_eq!(
+        client.try_accept_admin_transfer(&Address::generate(&env)),
+        Err(Ok(GovernanceError::NoPendingAdminTransfer))
+    );
+
+    client.accept_admin_transfer(&new_admin);
+    assert!(client.has_role(&new_admin, &access_control::Role::Admin));
+    assert!(!client.has_role(&admin, &access_control::Role::Admin));
+    assert_eq!(client.get_pending_admin_transfer(), None);
+
+    client.propose_admin_transfer(&new_admin, &admin);
+    client.cancel_admin_transfer(&new_admin);
+    assert_eq!(
+        client.try_accept_admin_transfer(&admin),
+        Err(Ok(GovernanceError::NoPendingAdminTransfer))
+    );
+}
+
 #[test]
 fn test_non_admin_cannot_update_interest_rate() {
     let env = Env::default();
@@ -0,0 +1,265 @@
+use crate::InheritancePlan;
+use soroban_sdk::{contracterror, contracttype, Address, BytesN, Env, Map, Symbol, Vec};
+
+/// Most extra assets a single plan may hold besides its primary token.
+pub const MAX_PLAN_ASSETS: u32 = 10;
+
+/// Errors raised when managing a plan's additional token balances.
+///
+/// Kept separate from `InheritanceError` (at the 50-case spec limit).
+/// Codes start at 600 so they never overlap the other module enums.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AssetError {
+    NotAdmin = 600,
+    Unauthorized = 601,
+    PlanNotFound = 602,
+    TokenNotWhitelisted = 603,
+    InvalidAllocation = 604,
+    AllocationLocked = 605,
+    InvalidBeneficiaryIndex = 606,
+    AssetCodeInUse = 607,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum AssetKey {
+    Whitelist,                 // -> Map<Address, Symbol> (instance; token -> asset code)
+    PlanAssets(u64),           // plan_id -> Vec<Address> (extra tokens ever deposited)
+    Balance(u64, Address),     // (plan_id, token) -> u64
+    Allocation(u64, Address),  // (plan_id, token) -> Map<BytesN<32>, u32> (hashed_email -> bp)
+    Distributed(u64, Address), // (plan_id, token) -> u64 (paid out to beneficiaries so far)
+    PrimaryToken(u64),         // plan_id -> Address (token backing `total_amount`)
+}
+
+/// One line of a plan's holdings. `token` is `None` only for the primary
+/// balance of a plan whose asset code is not on the whitelist.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetAmount {
+    pub asset_code: Symbol,
+    pub token: Option<Address>,
+    pub amount: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetDepositEvent {
+    pub plan_id: u64,
+    pub token: Address,
+    pub amount: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetWithdrawEvent {
+    pub plan_id: u64,
+    pub token: Address,
+    pub amount: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetClaimedEvent {
+    pub plan_id: u64,
+    pub hashed_email: BytesN<32>,
+    pub token: Address,
+    pub amount: u64,
+}
+
+pub fn whitelist(env: &Env) -> Map<Address, Symbol> {
+    env.storage()
+        .instance()
+        .get(&AssetKey::Whitelist)
+        .unwrap_or_else(|| Map::new(env))
+}
+
+pub fn set_whitelist(env: &Env, whitelist: &Map<Address, Symbol>) {
+    env.storage()
+        .instance()
+        .set(&AssetKey::Whitelist, whitelist);
+}
+
+/// The asset code `token` is whitelisted under.
+pub fn asset_code(env: &Env, token: &Address) -> Option<Symbol> {
+    whitelist(env).get(token.clone())
+}
+
+/// The whitelisted token carrying `code`, if any.
+pub fn token_for_code(env: &Env, code: &Symbol) -> Option<Address> {
+    whitelist(env)
+        .iter()
+        .find(|(_, c)| c == code)
+        .map(|(token, _)| token)
+}
+
+/// The token backing the plan's primary balance, recorded at creation.
+/// `None` for older plans until `backfill_primary_tokens` reaches them.
+pub fn primary_token(env: &Env, plan_id: u64) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&AssetKey::PrimaryToken(plan_id))
+}
+
+pub fn set_primary_token(env: &Env, plan_id: u64, token: &Address) {
+    let key = AssetKey::PrimaryToken(plan_id);
+    env.storage().persistent().set(&key, token);
+    access_control::extend_persistent_ttl(env, &key);
+}
+
+/// Whether `token` is held as an extra asset of the plan rather than being
+/// its primary token (tracked in `total_amount`). A token delisted after it
+/// was deposited stays an extra asset so its balance can still leave.
+pub fn is_extra_asset(env: &Env, plan_id: u64, plan: &InheritancePlan, token: &Address) -> bool {
+    if primary_token(env, plan_id).as_ref() == Some(token) {
+        return false;
+    }
+    plan_assets(env, plan_id).contains(token)
+        || asset_code(env, token).is_some_and(|code| code != plan.asset_type)
+}
+
+/// Whether `token` feeds the plan's primary balance. Plans without a
+/// recorded primary token fall back to matching the whitelist entry for
+/// their asset code, or any token while the whitelist is empty.
+pub fn is_primary_token(env: &Env, plan_id: u64, plan: &InheritancePlan, token: &Address) -> bool {
+    match primary_token(env, plan_id) {
+        Some(primary) => primary == *token,
+        None => {
+            let whitelist = whitelist(env);
+            whitelist.is_empty() || whitelist.get(token.clone()) == Some(plan.asset_type.clone())
+        }
+    }
+}
+
+pub fn plan_assets(env: &Env, plan_id: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&AssetKey::PlanAssets(plan_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn balance(env: &Env, plan_id: u64, token: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&AssetKey::Balance(plan_id, token.clone()))
+        .unwrap_or(0)
+}
+
+/// Whether the plan can hold `token` without exceeding `MAX_PLAN_ASSETS`.
+pub fn has_room_for(env: &Env, plan_id: u64, token: &Address) -> bool {
+    let assets = plan_assets(env, plan_id);
+    assets.contains(token) || assets.len() < MAX_PLAN_ASSETS
+}
+
+/// Set the plan's balance of `token`, registering it as a plan asset on
+/// first use. Callers check `has_room_for` first.
+pub fn set_balance(env: &Env, plan_id: u64, token: &Address, amount: u64) {
+    let mut assets = plan_assets(env, plan_id);
+    if !assets.contains(token) {
+        assets.push_back(token.clone());
+        let key = AssetKey::PlanAssets(plan_id);
+        env.storage().persistent().set(&key, &assets);
+        access_control::extend_persistent_ttl(env, &key);
+    }
+    let key = AssetKey::Balance(plan_id, token.clone());
+    env.storage().persistent().set(&key, &amount);
+    access_control::extend_persistent_ttl(env, &key);
+}
+
+pub fn distributed(env: &Env, plan_id: u64, token: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&AssetKey::Distributed(plan_id, token.clone()))
+        .unwrap_or(0)
+}
+
+fn set_distributed(env: &Env, plan_id: u64, token: &Address, amount: u64) {
+    let key = AssetKey::Distributed(plan_id, token.clone());
+    env.storage().persistent().set(&key, &amount);
+    access_control::extend_persistent_ttl(env, &key);
+}
+
+/// Per-asset allocation overrides for `token`, keyed by hashed email.
+/// `None` means the token is split pro-rata by each beneficiary's
+/// `allocation_bp`.
+pub fn allocation(env: &Env, plan_id: u64, token: &Address) -> Option<Map<BytesN<32>, u32>> {
+    env.storage()
+        .persistent()
+        .get(&AssetKey::Allocation(plan_id, token.clone()))
+}
+
+pub fn set_allocation(env: &Env, plan_id: u64, token: &Address, bps: Option<Map<BytesN<32>, u32>>) {
+    let key = AssetKey::Allocation(plan_id, token.clone());
+    match bps {
+        Some(bps) => {
+            env.storage().persistent().set(&key, &bps);
+            access_control::extend_persistent_ttl(env, &key);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Move a beneficiary's per-asset allocation overrides from one hashed email
+/// to another.
+pub fn rekey_allocations(env: &Env, plan_id: u64, from: &BytesN<32>, to: &BytesN<32>) {
+    for token in plan_assets(env, plan_id).iter() {
+        let Some(mut bps) = allocation(env, plan_id, &token) else {
+            continue;
+        };
+        if let Some(bp) = bps.get(from.clone()) {
+            bps.remove(from.clone());
+            bps.set(to.clone(), bp);
+            set_allocation(env, plan_id, &token, Some(bps));
+        }
+    }
+}
+
+/// Basis points of `token` owed to the beneficiary with `hashed_email`.
+pub fn allocation_bp(
+    env: &Env,
+    plan_id: u64,
+    token: &Address,
+    hashed_email: &BytesN<32>,
+    default_bp: u32,
+) -> u32 {
+    match allocation(env, plan_id, token) {
+        Some(bps) => bps.get(hashed_email.clone()).unwrap_or(0),
+        None => default_bp,
+    }
+}
+
+/// A beneficiary's share of `token`: their basis points of everything the
+/// plan has held for distribution (current balance plus what earlier
+/// claimers received), capped to the remaining balance.
+pub fn entitlement(env: &Env, plan_id: u64, token: &Address, bp: u32) -> u64 {
+    let held = balance(env, plan_id, token);
+    let base = held.saturating_add(distributed(env, plan_id, token));
+    let share = (base as u128)
+        .checked_mul(bp as u128)
+        .and_then(|v| v.checked_div(10000))
+        .unwrap_or(0) as u64;
+    share.min(held)
+}
+
+/// Record `amount` of `token` as paid out of the plan.
+pub fn record_payout(env: &Env, plan_id: u64, token: &Address, amount: u64) {
+    let held = balance(env, plan_id, token);
+    set_balance(env, plan_id, token, held.saturating_sub(amount));
+    let paid = distributed(env, plan_id, token);
+    set_distributed(env, plan_id, token, paid.saturating_add(amount));
+}
+
+/// Renew the TTL of every per-asset record of the plan.
+pub fn extend_ttl(env: &Env, plan_id: u64) {
+    access_control::renew_persistent_ttl(env, &AssetKey::PrimaryToken(plan_id));
+    access_control::renew_persistent_ttl(env, &AssetKey::PlanAssets(plan_id));
+    for token in plan_assets(env, plan_id).iter() {
+        for key in [
+            AssetKey::Balance(plan_id, token.clone()),
+            AssetKey::Allocation(plan_id, token.clone()),
+            AssetKey::Distributed(plan_id, token.clone()),
+        ] {
+            access_control::renew_persistent_ttl(env, &key);
+        }
+    }
+}
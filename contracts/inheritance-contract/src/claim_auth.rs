@@ -0,0 +1,191 @@
+use soroban_sdk::{contracterror, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env};
+
+/// Ledgers a sealed claim stays valid before it must be revealed.
+pub const SEAL_TTL_LEDGERS: u32 = access_control::DAY_IN_LEDGERS;
+
+/// Errors raised when binding beneficiaries to a claiming identity.
+///
+/// Kept separate from `InheritanceError` (at the 50-case spec limit).
+/// Codes start at 400 so they never overlap the other module enums.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClaimAuthError {
+    Unauthorized = 400,
+    PlanNotFound = 401,
+    InvalidBeneficiaryIndex = 402,
+    AlreadyClaimed = 403,
+    NotCommitted = 404,
+    CommitmentMismatch = 405,
+    SealNotFound = 406,
+    SealNotMatured = 407,
+    KycNotApproved = 408,
+}
+
+/// How a beneficiary proves their identity when claiming.
+///
+/// Beneficiaries without a binding fall back to the legacy email + claim code
+/// check while it is enabled; once bound, every claim path requires the
+/// bound identity.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BeneficiaryBinding {
+    /// The claimer must be this address (checked with `require_auth`).
+    Address(Address),
+    /// The claimer must present an ed25519 signature from this key over
+    /// `claim_message`.
+    Ed25519(BytesN<32>),
+    /// `commitment(plan_id, hashed_email, salt)` for a beneficiary known only
+    /// by email. The salt is delivered off-chain; revealing it binds the
+    /// beneficiary to the first KYC-approved address that sealed it.
+    Commitment(BytesN<32>),
+}
+
+/// A claimer's sealed salt, waiting to be revealed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SealRecord {
+    pub sealed: BytesN<32>,
+    pub sealed_at_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeneficiaryBoundEvent {
+    pub plan_id: u64,
+    pub hashed_email: BytesN<32>,
+    pub binding: BeneficiaryBinding,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum ClaimAuthKey {
+    Binding(u64, BytesN<32>), // (plan_id, hashed_email) -> BeneficiaryBinding
+    Seal(u64, BytesN<32>, Address), // (plan_id, hashed_email, claimer) -> SealRecord (temporary)
+    LegacyClaimCodesDisabled, // bool (instance)
+}
+
+/// Whether unbound beneficiaries may still claim with their email and claim
+/// code. On until an admin or `migrate` turns it off.
+pub fn legacy_claim_codes_enabled(env: &Env) -> bool {
+    !env.storage()
+        .instance()
+        .get(&ClaimAuthKey::LegacyClaimCodesDisabled)
+        .unwrap_or(false)
+}
+
+pub fn set_legacy_claim_codes_enabled(env: &Env, enabled: bool) {
+    env.storage()
+        .instance()
+        .set(&ClaimAuthKey::LegacyClaimCodesDisabled, &!enabled);
+}
+
+/// Bindings are keyed by hashed email rather than index because removing a
+/// beneficiary swaps the last one into its slot.
+pub fn binding(env: &Env, plan_id: u64, hashed_email: &BytesN<32>) -> Option<BeneficiaryBinding> {
+    env.storage()
+        .persistent()
+        .get(&ClaimAuthKey::Binding(plan_id, hashed_email.clone()))
+}
+
+pub fn set_binding(
+    env: &Env,
+    plan_id: u64,
+    hashed_email: &BytesN<32>,
+    binding: &BeneficiaryBinding,
+) {
+    let key = ClaimAuthKey::Binding(plan_id, hashed_email.clone());
+    env.storage().persistent().set(&key, binding);
+    access_control::extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_binding(env: &Env, plan_id: u64, hashed_email: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .remove(&ClaimAuthKey::Binding(plan_id, hashed_email.clone()));
+}
+
+/// Move the beneficiary's binding from one hashed email to another.
+pub fn rekey_binding(env: &Env, plan_id: u64, from: &BytesN<32>, to: &BytesN<32>) {
+    if let Some(binding) = binding(env, plan_id, from) {
+        set_binding(env, plan_id, to, &binding);
+        remove_binding(env, plan_id, from);
+    }
+}
+
+/// `sha256(plan_id_be ‖ hashed_email ‖ salt)`: what the owner stores for an
+/// email-only beneficiary.
+pub fn commitment(
+    env: &Env,
+    plan_id: u64,
+    hashed_email: &BytesN<32>,
+    salt: &BytesN<32>,
+) -> BytesN<32> {
+    let mut data = Bytes::new(env);
+    data.extend_from_slice(&plan_id.to_be_bytes());
+    data.extend_from_slice(&hashed_email.to_array());
+    data.extend_from_slice(&salt.to_array());
+    env.crypto().sha256(&data).into()
+}
+
+/// `sha256(salt ‖ claimer_xdr)`: what a claimer seals before revealing the
+/// salt, so an observer of the reveal cannot redirect it to another address.
+pub fn seal(env: &Env, salt: &BytesN<32>, claimer: &Address) -> BytesN<32> {
+    let mut data = Bytes::from_array(env, &salt.to_array());
+    data.append(&claimer.clone().to_xdr(env));
+    env.crypto().sha256(&data).into()
+}
+
+pub fn record_seal(
+    env: &Env,
+    plan_id: u64,
+    hashed_email: &BytesN<32>,
+    claimer: &Address,
+    sealed: BytesN<32>,
+) {
+    let key = ClaimAuthKey::Seal(plan_id, hashed_email.clone(), claimer.clone());
+    let record = SealRecord {
+        sealed,
+        sealed_at_ledger: env.ledger().sequence(),
+    };
+    env.storage().temporary().set(&key, &record);
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, SEAL_TTL_LEDGERS, SEAL_TTL_LEDGERS);
+}
+
+/// Remove and return the claimer's seal.
+pub fn take_seal(
+    env: &Env,
+    plan_id: u64,
+    hashed_email: &BytesN<32>,
+    claimer: &Address,
+) -> Option<SealRecord> {
+    let key = ClaimAuthKey::Seal(plan_id, hashed_email.clone(), claimer.clone());
+    let record = env.storage().temporary().get(&key);
+    env.storage().temporary().remove(&key);
+    record
+}
+
+/// The bytes an ed25519-bound beneficiary signs to claim:
+/// `"inheritx:claim" ‖ contract_xdr ‖ plan_id_be ‖ hashed_email ‖ claimer_xdr`.
+pub fn claim_message(
+    env: &Env,
+    plan_id: u64,
+    hashed_email: &BytesN<32>,
+    claimer: &Address,
+) -> Bytes {
+    let mut data = Bytes::from_slice(env, b"inheritx:claim");
+    data.append(&env.current_contract_address().to_xdr(env));
+    data.extend_from_slice(&plan_id.to_be_bytes());
+    data.extend_from_slice(&hashed_email.to_array());
+    data.append(&claimer.clone().to_xdr(env));
+    data
+}
+
+/// Renew the TTL of the beneficiary's binding, if any.
+pub fn extend_binding_ttl(env: &Env, plan_id: u64, hashed_email: &BytesN<32>) {
+    access_control::renew_persistent_ttl(
+        env,
+        &ClaimAuthKey::Binding(plan_id, hashed_email.clone()),
+    );
+}
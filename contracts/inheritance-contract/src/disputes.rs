@@ -1,7 +1,16 @@
-use soroban_sdk::{contracttype, Address, Env, Vec};
+use soroban_sdk::{contracterror, contracttype, Address, Env, Map, Vec};
+
+/// How long an undecided dispute keeps its plan frozen. An arbitrator must
+/// resolve or reject it within this window; after that it lapses.
+pub const DISPUTE_EXPIRY_SECS: u64 = 60 * 24 * 60 * 60;
+
+/// Disputes a party may have filed on one plan that were not upheld
+/// (rejected, lapsed or still open). Bounds how long one party can keep a
+/// plan frozen by refiling, and with it the plan's dispute history.
+pub const MAX_UNUPHELD_DISPUTES_PER_PARTY: u32 = 2;
 
 #[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum DisputeStatus {
     Filed = 0,
     UnderReview = 1,
@@ -20,7 +29,43 @@ pub struct DisputeRecord {
     pub filed_at: u64,
     pub resolved_at: u64,
     pub resolution_notes: soroban_sdk::String,
-    pub arbitrator: Address,
+    pub arbitrator: Option<Address>, // None until an arbitrator is assigned
+    pub expires_at: u64,             // Lapses undecided after this timestamp
+}
+
+/// Errors raised by the dispute workflow.
+///
+/// `InheritanceError` is at the 50-case spec limit, so disputes carry their
+/// own error enum. Codes start at 100 to stay distinguishable from it.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisputeError {
+    NotAdmin = 100,
+    PlanNotFound = 101,
+    PlanNotActive = 102,
+    DisputeNotFound = 103,
+    InvalidDisputeStatus = 104,
+    MissingReason = 105,
+    KycNotApproved = 106,
+    ArbitratorNotRegistered = 107,
+    ArbitratorAlreadyRegistered = 108,
+    NotAssignedArbitrator = 109,
+    NotPlanParty = 110,
+    DisputeAlreadyOpen = 111,
+    DisputeLimitReached = 112,
+}
+
+/// Storage keys for the dispute workflow (kept apart from `DataKey`,
+/// which is close to the spec union-case limit).
+#[contracttype]
+#[derive(Clone)]
+pub enum DisputeKey {
+    NextDisputeId,     // Global next dispute ID counter
+    Dispute(u64),      // dispute_id -> DisputeRecord
+    PlanDisputes(u64), // plan_id -> Vec<u64> (dispute IDs)
+    Arbitrators,       // Vec<Address> of registered arbitrators
+    OpenDisputes(u64), // plan_id -> Vec<u64> (IDs not yet closed; may hold lapsed ones)
+    PartyFilings(u64), // plan_id -> Map<Address, u32> (disputes filed and not upheld)
 }
 
 #[contracttype]
@@ -33,6 +78,22 @@ pub struct DisputeFiledEvent {
     pub filed_at: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbitratorAssignedEvent {
+    pub dispute_id: u64,
+    pub plan_id: u64,
+    pub arbitrator: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeReviewStartedEvent {
+    pub dispute_id: u64,
+    pub plan_id: u64,
+    pub arbitrator: Address,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DisputeResolvedEvent {
@@ -45,7 +106,7 @@ pub struct DisputeResolvedEvent {
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PlanFrozenEvent {
+pub struct DisputePlanFrozenEvent {
     pub plan_id: u64,
     pub dispute_id: u64,
     pub frozen_at: u64,
@@ -53,7 +114,7 @@ pub struct PlanFrozenEvent {
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PlanUnfrozenEvent {
+pub struct DisputePlanUnfrozenEvent {
     pub plan_id: u64,
     pub dispute_id: u64,
     pub unfrozen_at: u64,
@@ -72,3 +133,175 @@ pub struct ArbitratorRemovedEvent {
     pub arbitrator: Address,
     pub removed_at: u64,
 }
+
+// ─── Storage helpers ─────────────────────────────
+
+pub fn next_dispute_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&DisputeKey::NextDisputeId)
+        .unwrap_or(1);
+    env.storage()
+        .instance()
+        .set(&DisputeKey::NextDisputeId, &(id + 1));
+    id
+}
+
+pub fn get_dispute(env: &Env, dispute_id: u64) -> Option<DisputeRecord> {
+    env.storage()
+        .persistent()
+        .get(&DisputeKey::Dispute(dispute_id))
+}
+
+pub fn store_dispute(env: &Env, dispute: &DisputeRecord) {
+    env.storage()
+        .persistent()
+        .set(&DisputeKey::Dispute(dispute.dispute_id), dispute);
+    access_control::extend_persistent_ttl(env, &DisputeKey::Dispute(dispute.dispute_id));
+}
+
+pub fn get_plan_dispute_ids(env: &Env, plan_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DisputeKey::PlanDisputes(plan_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn add_dispute_to_plan(env: &Env, plan_id: u64, dispute_id: u64) {
+    let mut ids = get_plan_dispute_ids(env, plan_id);
+    ids.push_back(dispute_id);
+    env.storage()
+        .persistent()
+        .set(&DisputeKey::PlanDisputes(plan_id), &ids);
+    access_control::extend_persistent_ttl(env, &DisputeKey::PlanDisputes(plan_id));
+}
+
+fn get_open_dispute_ids(env: &Env, plan_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DisputeKey::OpenDisputes(plan_id))
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_open_dispute_ids(env: &Env, plan_id: u64, ids: &Vec<u64>) {
+    let key = DisputeKey::OpenDisputes(plan_id);
+    if ids.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, ids);
+        access_control::extend_persistent_ttl(env, &key);
+    }
+}
+
+/// Track a newly filed dispute as open, dropping any that have lapsed.
+pub fn add_open_dispute(env: &Env, plan_id: u64, dispute_id: u64) {
+    let mut ids = Vec::new(env);
+    for id in get_open_dispute_ids(env, plan_id).iter() {
+        if get_dispute(env, id).is_some_and(|dispute| is_open(env, &dispute)) {
+            ids.push_back(id);
+        }
+    }
+    ids.push_back(dispute_id);
+    set_open_dispute_ids(env, plan_id, &ids);
+}
+
+/// Stop tracking a dispute that has been resolved or rejected.
+pub fn remove_open_dispute(env: &Env, plan_id: u64, dispute_id: u64) {
+    let mut ids = get_open_dispute_ids(env, plan_id);
+    if let Some(index) = ids.first_index_of(dispute_id) {
+        ids.remove(index);
+        set_open_dispute_ids(env, plan_id, &ids);
+    }
+}
+
+fn get_party_filings(env: &Env, plan_id: u64) -> Map<Address, u32> {
+    env.storage()
+        .persistent()
+        .get(&DisputeKey::PartyFilings(plan_id))
+        .unwrap_or(Map::new(env))
+}
+
+/// Number of disputes `disputer` filed on `plan_id` that were not upheld.
+pub fn unupheld_filings(env: &Env, plan_id: u64, disputer: &Address) -> u32 {
+    get_party_filings(env, plan_id)
+        .get(disputer.clone())
+        .unwrap_or(0)
+}
+
+/// Count a filing against `disputer` (`upheld = false`) or give it back once
+/// the dispute is upheld (`upheld = true`).
+pub fn record_filing(env: &Env, plan_id: u64, disputer: &Address, upheld: bool) {
+    let mut filings = get_party_filings(env, plan_id);
+    let count = filings.get(disputer.clone()).unwrap_or(0);
+    let count = if upheld {
+        count.saturating_sub(1)
+    } else {
+        count + 1
+    };
+    filings.set(disputer.clone(), count);
+    let key = DisputeKey::PartyFilings(plan_id);
+    env.storage().persistent().set(&key, &filings);
+    access_control::extend_persistent_ttl(env, &key);
+}
+
+/// Renew the TTL of the plan's dispute indexes and every dispute they reference.
+pub fn extend_plan_ttl(env: &Env, plan_id: u64) {
+    access_control::renew_persistent_ttl(env, &DisputeKey::PlanDisputes(plan_id));
+    access_control::renew_persistent_ttl(env, &DisputeKey::OpenDisputes(plan_id));
+    access_control::renew_persistent_ttl(env, &DisputeKey::PartyFilings(plan_id));
+    for id in get_plan_dispute_ids(env, plan_id).iter() {
+        access_control::renew_persistent_ttl(env, &DisputeKey::Dispute(id));
+    }
+}
+
+/// Filed or under review, and not yet lapsed.
+pub fn is_open(env: &Env, dispute: &DisputeRecord) -> bool {
+    matches!(
+        dispute.status,
+        DisputeStatus::Filed | DisputeStatus::UnderReview
+    ) && env.ledger().timestamp() < dispute.expires_at
+}
+
+/// Number of disputes on `plan_id` that are still open. Reads only the open
+/// index, so its cost does not grow with the plan's dispute history.
+pub fn open_dispute_count(env: &Env, plan_id: u64) -> u32 {
+    let mut count = 0;
+    for id in get_open_dispute_ids(env, plan_id).iter() {
+        if let Some(dispute) = get_dispute(env, id) {
+            if is_open(env, &dispute) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+/// Whether `disputer` already has an open dispute on `plan_id`.
+pub fn has_open_dispute_by(env: &Env, plan_id: u64, disputer: &Address) -> bool {
+    get_open_dispute_ids(env, plan_id).iter().any(|id| {
+        get_dispute(env, id)
+            .is_some_and(|dispute| dispute.disputer == *disputer && is_open(env, &dispute))
+    })
+}
+
+pub fn get_arbitrators(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DisputeKey::Arbitrators)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_arbitrators(env: &Env, arbitrators: &Vec<Address>) {
+    env.storage()
+        .persistent()
+        .set(&DisputeKey::Arbitrators, arbitrators);
+    access_control::extend_persistent_ttl(env, &DisputeKey::Arbitrators);
+}
+
+/// Registered arbitrators and holders of the shared `Arbitrator` role both
+/// qualify.
+pub fn is_arbitrator(env: &Env, address: &Address) -> bool {
+    get_arbitrators(env).contains(address)
+        || access_control::has_role(env, address, access_control::Role::Arbitrator)
+}
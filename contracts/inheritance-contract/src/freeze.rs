@@ -0,0 +1,49 @@
+use crate::{DataKey, FreezeRecord, LegalHold};
+use soroban_sdk::{contracterror, Env};
+
+/// Errors raised by the freeze / legal-hold API.
+///
+/// Kept separate from `InheritanceError` (at the 50-case spec limit).
+/// Codes start at 200 so they never overlap `DisputeError`.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FreezeError {
+    Unauthorized = 200,
+    PlanNotFound = 201,
+    MissingReason = 202,
+    InvalidExpiry = 203,
+    AlreadyFrozen = 204,
+    NotFrozen = 205,
+    LegalHoldExists = 206,
+    LegalHoldNotFound = 207,
+    DisputeOpen = 208,
+}
+
+/// `expires_at == 0` means the restriction has no expiry.
+fn is_live(env: &Env, expires_at: u64) -> bool {
+    expires_at == 0 || env.ledger().timestamp() < expires_at
+}
+
+/// The plan's freeze record, if one is stored and has not expired.
+pub fn active_freeze(env: &Env, plan_id: u64) -> Option<FreezeRecord> {
+    env.storage()
+        .persistent()
+        .get::<_, FreezeRecord>(&DataKey::FreezePlan(plan_id))
+        .filter(|record| is_live(env, record.expires_at))
+}
+
+/// The plan's legal hold, if one is stored and has not expired.
+pub fn active_legal_hold(env: &Env, plan_id: u64) -> Option<LegalHold> {
+    env.storage()
+        .persistent()
+        .get::<_, LegalHold>(&DataKey::LegalHold(plan_id))
+        .filter(|hold| is_live(env, hold.expires_at))
+}
+
+/// Validate a caller-supplied expiry: zero (no expiry) or strictly in the future.
+pub fn validate_expiry(env: &Env, expires_at: u64) -> Result<(), FreezeError> {
+    if expires_at != 0 && expires_at <= env.ledger().timestamp() {
+        return Err(FreezeError::InvalidExpiry);
+    }
+    Ok(())
+}
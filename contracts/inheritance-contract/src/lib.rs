@@ -1,12 +1,33 @@
 #![no_std]
-use access_control::{self, Role};
+use access_control::{self, PendingAdminTransfer, Role, RoleGrant};
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, log, symbol_short, token, vec, Address,
-    Bytes, BytesN, Env, FromVal, IntoVal, InvokeError, String, Symbol, Val, Vec,
+    contract, contracterror, contractimpl, contracttype, log, symbol_short, token, vec, xdr::ToXdr,
+    Address, Bytes, BytesN, Env, FromVal, IntoVal, InvokeError, Map, String, Symbol, Val, Vec,
 };
 
+mod assets;
+mod claim_auth;
+mod disputes;
+mod freeze;
+mod ownership;
+mod payout_details;
+mod payouts;
+mod plan_index;
+mod recovery;
+mod vesting;
+pub use assets::{AssetAmount, AssetError};
+pub use claim_auth::{BeneficiaryBinding, ClaimAuthError, SealRecord};
+pub use disputes::{DisputeError, DisputeRecord, DisputeStatus};
+pub use freeze::FreezeError;
+pub use ownership::{OwnershipError, PendingPlanTransfer};
+pub use payout_details::PayoutDetailsError;
+pub use payouts::{InstallmentProgress, InstallmentStatus, PayoutError, PayoutSchedule};
+pub use plan_index::{PlanEntry, PlanIndex, PlanPage};
+pub use recovery::{RecoveryError, RecoveryProposal};
+pub use vesting::{VestingError, VestingProgress, VestingRelease, VestingStage, VestingStatus};
+
 /// Current contract version - bump this on each upgrade
-const CONTRACT_VERSION: u32 = 1;
+const CONTRACT_VERSION: u32 = 4;
 
 /// Emergency transfer limit in basis points (10% = 1000 bp)
 const EMERGENCY_TRANSFER_LIMIT_BP: u32 = 1000;
@@ -29,7 +50,7 @@ pub struct Beneficiary {
     pub hashed_full_name: BytesN<32>,
     pub hashed_email: BytesN<32>,
     pub hashed_claim_code: BytesN<32>,
-    pub bank_account: Bytes, // Plain text for fiat settlement (MVP trade-off)
+    pub bank_account: Bytes, // Payout details sealed to the settlement key (see `payout_details`)
     pub allocation_bp: u32,  // Allocation in basis points (0-10000, where 10000 = 100%)
     pub priority: u32,       // Priority level (1=highest)
     pub is_claimed: bool,    // Whether the beneficiary has already claimed their portion
@@ -44,6 +65,7 @@ pub struct BeneficiaryInput {
     pub bank_account: Bytes,
     pub allocation_bp: u32,
     pub priority: u32,
+    pub vesting: VestingRelease, // Release condition, e.g. for a minor
 }
 
 #[contracttype]
@@ -51,7 +73,7 @@ pub struct BeneficiaryInput {
 pub struct InheritancePlan {
     pub plan_name: String,
     pub description: String,
-    pub asset_type: Symbol, // Only USDC allowed
+    pub asset_type: Symbol, // Asset code of the primary balance (see `assets::primary_token`)
     pub total_amount: u64,
     pub distribution_method: DistributionMethod,
     pub beneficiaries: Vec<Beneficiary>,
@@ -99,6 +121,24 @@ pub enum InheritanceError {
     FeeTransferFailed = 30,
     InsufficientLiquidity = 31,
     InheritanceAlreadyTriggered = 32,
+    AlreadyApproved = 33,
+    EmergencyAccessAlreadyActive = 34,
+    EmergencyContactAlreadyExists = 35,
+    EmergencyContactNotFound = 36,
+    EmergencyCooldownActive = 37,
+    GuardianNotFound = 38,
+    InheritanceNotTriggered = 39,
+    InvalidGuardianThreshold = 40,
+    LoanRecallFailed = 41,
+    NoOutstandingLoans = 42,
+    NothingToClaim = 43,
+    TooManyEmergencyContacts = 44,
+    VestingScheduleActive = 45,
+    WillAlreadyFinalized = 46,
+    WillAlreadyLinked = 47,
+    WillHashAlreadyStored = 48,
+    WillNotVerified = 49,
+    WillVersionNotFound = 50,
 }
 
 #[contracttype]
@@ -106,7 +146,8 @@ pub enum InheritanceError {
 pub enum DataKey {
     NextPlanId,
     Plan(u64),
-    Claim(BytesN<32>),         // keyed by hashed_email
+    Claim(BytesN<32>), // keyed by hashed_email
+    // Legacy plan lists, superseded by `plan_index`; only read by `backfill_plan_indexes`.
     UserPlans(Address),        // keyed by owner Address, value is Vec<u64>
     UserClaimedPlans(Address), // keyed by owner Address, value is Vec<u64>
     DeactivatedPlans,          // value is Vec<u64> of all deactivated plan IDs
@@ -642,6 +683,8 @@ pub struct FreezeRecord {
     pub frozen_at: u64,
     pub reason: String,
     pub frozen_by: Address,
+    pub expires_at: u64, // 0 = no expiry
+    pub placed_as: Role, // Role frozen_by acted under; lifting needs it or Admin
 }
 
 #[contracttype]
@@ -651,6 +694,8 @@ pub struct LegalHold {
     pub added_at: u64,
     pub reason: String,
     pub added_by: Address,
+    pub expires_at: u64, // 0 = no expiry
+    pub placed_as: Role, // Role added_by acted under; lifting needs it or Admin
 }
 
 #[contracttype]
@@ -728,19 +773,27 @@ impl InheritanceContract {
     const EMERGENCY_EXPIRATION_PERIOD: u64 = 604800; // 7 days in seconds
 
     pub fn hello(env: Env, to: Symbol) -> Vec<Symbol> {
+        access_control::extend_instance_ttl(&env);
         vec![&env, symbol_short!("Hello"), to]
     }
 
     // Hash utility functions
+    /// Hashes the XDR encoding of `input` (an `ScVal::String`), so off-chain
+    /// services can reproduce the hash from the plain text.
     pub fn hash_string(env: &Env, input: String) -> BytesN<32> {
-        // Convert string to bytes for hashing
-        let mut data = Bytes::new(env);
+        let data = input.to_xdr(env);
+        env.crypto().sha256(&data).into()
+    }
 
-        // Simple conversion - in production, use proper string-to-bytes conversion
+    /// The hash plans stored before `hash_string` switched to the XDR
+    /// encoding. It only depends on the string's length, so it is never
+    /// accepted at claim time; `rehash_beneficiary_email` uses it to check
+    /// the email an admin migrates a beneficiary to.
+    fn legacy_hash_string(env: &Env, input: &String) -> BytesN<32> {
+        let mut data = Bytes::new(env);
         for i in 0..input.len() {
             data.push_back((i % 256) as u8);
         }
-
         env.crypto().sha256(&data).into()
     }
 
@@ -776,6 +829,17 @@ impl InheritanceContract {
         access_control::require_role(env, admin, Role::Admin, InheritanceError::NotAdmin)
     }
 
+    /// Like `require_admin`, but holders of the operational `role` pass too.
+    fn require_admin_or(env: &Env, caller: &Address, role: Role) -> Result<(), InheritanceError> {
+        caller.require_auth();
+        access_control::require_any_role(
+            env,
+            caller,
+            &[Role::Admin, role],
+            InheritanceError::NotAdmin,
+        )
+    }
+
     fn enter_guard(env: &Env) {
         access_control::reentrancy_enter_or_panic(env);
     }
@@ -788,8 +852,10 @@ impl InheritanceContract {
         access_control::require_not_paused_or_panic(env);
     }
 
+    /// Admin or pauser only.
     pub fn pause(env: Env, admin: Address) -> Result<(), InheritanceError> {
-        Self::require_admin(&env, &admin)?;
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::Pauser)?;
         access_control::pause_contract(&env);
         env.events().publish(
             (symbol_short!("ADMIN"), symbol_short!("PAUSE")),
@@ -798,8 +864,10 @@ impl InheritanceContract {
         Ok(())
     }
 
+    /// Admin or pauser only.
     pub fn unpause(env: Env, admin: Address) -> Result<(), InheritanceError> {
-        Self::require_admin(&env, &admin)?;
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::Pauser)?;
         access_control::unpause_contract(&env);
         env.events().publish(
             (symbol_short!("ADMIN"), symbol_short!("UNPAUSE")),
@@ -809,10 +877,12 @@ impl InheritanceContract {
     }
 
     pub fn is_paused(env: Env) -> bool {
+        access_control::extend_instance_ttl(&env);
         access_control::is_contract_paused(&env)
     }
 
     pub fn initialize_admin(env: Env, admin: Address) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         admin.require_auth();
         if Self::get_admin(&env).is_some() {
             return Err(InheritanceError::AdminAlreadyInitialized);
@@ -824,42 +894,138 @@ impl InheritanceContract {
         Ok(())
     }
 
-    /// Assign a role to an address. Admin-only.
+    /// Assign a role to an address. Callable by admins and by holders of the
+    /// role's admin role (see `set_role_admin`). The Admin role itself is
+    /// handed over with `propose_admin_transfer` / `accept_admin_transfer`.
     pub fn assign_role(
         env: Env,
         admin: Address,
         address: Address,
         role: Role,
     ) -> Result<(), InheritanceError> {
-        Self::require_admin(&env, &admin)?;
-        access_control::assign_role(&env, &address, role);
+        Self::assign_role_until(env, admin, address, role, 0)
+    }
+
+    /// Like `assign_role`, but the grant lapses at `expires_at` (unix
+    /// seconds; 0 for never). Re-assigning replaces the expiry.
+    pub fn assign_role_until(
+        env: Env,
+        admin: Address,
+        address: Address,
+        role: Role,
+        expires_at: u64,
+    ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role_admin(&env, &admin, role.clone(), InheritanceError::NotAdmin)?;
+        if role == Role::Admin {
+            return Err(InheritanceError::Unauthorized);
+        }
+        access_control::assign_role_until(&env, &address, role, expires_at);
         Ok(())
     }
 
-    /// Revoke a role from an address. Admin-only.
+    /// Revoke a role from an address. Callable by admins and by holders of
+    /// the role's admin role. The Admin role only moves with the two-step
+    /// admin transfer, so it cannot be revoked here.
     pub fn revoke_role(
         env: Env,
         admin: Address,
         address: Address,
         role: Role,
     ) -> Result<(), InheritanceError> {
-        Self::require_admin(&env, &admin)?;
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role_admin(&env, &admin, role.clone(), InheritanceError::NotAdmin)?;
+        if role == Role::Admin {
+            return Err(InheritanceError::Unauthorized);
+        }
         access_control::revoke_role(&env, &address, role);
         Ok(())
     }
 
+    /// Let holders of `admin_role` grant and revoke `role`. Admin-only; who
+    /// manages the Admin role cannot be changed.
+    pub fn set_role_admin(
+        env: Env,
+        admin: Address,
+        role: Role,
+        admin_role: Role,
+    ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin)?;
+        if role == Role::Admin {
+            return Err(InheritanceError::Unauthorized);
+        }
+        access_control::set_role_admin(&env, role, admin_role);
+        Ok(())
+    }
+
+    /// The role whose holders may grant and revoke `role`.
+    pub fn get_role_admin(env: Env, role: Role) -> Role {
+        access_control::extend_instance_ttl(&env);
+        access_control::role_admin(&env, role)
+    }
+
+    /// Up to `limit` holders of `role`, from position `start`.
+    pub fn get_role_members(env: Env, role: Role, start: u32, limit: u32) -> Vec<RoleGrant> {
+        access_control::extend_instance_ttl(&env);
+        access_control::role_members(&env, role, start, limit)
+    }
+
+    /// Propose `new_admin` as the contract admin. Nothing changes until they
+    /// call `accept_admin_transfer`; proposing again replaces the proposal.
+    pub fn propose_admin_transfer(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), OwnershipError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role(&env, &admin, Role::Admin, OwnershipError::NotAdmin)?;
+        if new_admin == admin {
+            return Err(OwnershipError::InvalidNewOwner);
+        }
+        access_control::propose_admin_transfer(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Accept a pending admin transfer, taking over the Admin role and the
+    /// admin fee wallet from the admin who proposed it.
+    pub fn accept_admin_transfer(env: Env, new_admin: Address) -> Result<(), OwnershipError> {
+        access_control::extend_instance_ttl(&env);
+        new_admin.require_auth();
+        access_control::accept_admin_transfer(&env, &new_admin)
+            .ok_or(OwnershipError::NoPendingTransfer)?;
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        Ok(())
+    }
+
+    /// Cancel a pending admin transfer. Admin-only.
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), OwnershipError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role(&env, &admin, Role::Admin, OwnershipError::NotAdmin)?;
+        access_control::cancel_admin_transfer(&env).ok_or(OwnershipError::NoPendingTransfer)?;
+        Ok(())
+    }
+
+    /// The admin transfer waiting for acceptance, if any.
+    pub fn get_pending_admin_transfer(env: Env) -> Option<PendingAdminTransfer> {
+        access_control::extend_instance_ttl(&env);
+        access_control::pending_admin_transfer(&env)
+    }
+
     /// Check whether an address holds a given role.
     pub fn has_role(env: Env, address: Address, role: Role) -> bool {
+        access_control::extend_instance_ttl(&env);
         access_control::has_role(&env, &address, role)
     }
 
     /// Return all roles held by an address.
     pub fn get_roles(env: Env, address: Address) -> Vec<Role> {
-        use access_control::AccessControlKey;
-        env.storage()
-            .persistent()
-            .get(&AccessControlKey::Roles(address))
-            .unwrap_or(Vec::new(&env))
+        access_control::extend_instance_ttl(&env);
+        access_control::roles_of(&env, &address)
     }
 
     fn create_beneficiary(
@@ -913,8 +1079,10 @@ impl InheritanceContract {
             return Err(InheritanceError::DescriptionTooLong);
         }
 
-        // Validate asset type (only USDC allowed)
-        if asset_type != Symbol::new(env, "USDC") {
+        // Validate asset type (USDC, or any whitelisted asset code)
+        if asset_type != Symbol::new(env, "USDC")
+            && assets::token_for_code(env, &asset_type).is_none()
+        {
             return Err(InheritanceError::InvalidAssetType);
         }
 
@@ -1003,84 +1171,67 @@ impl InheritanceContract {
         current_id
     }
 
+    /// Writes the plan record and extends its TTL, so a plan that is in use
+    /// never drifts towards archival. The other per-plan entries are renewed
+    /// by `bump_plan`.
     fn store_plan(env: &Env, plan_id: u64, plan: &InheritancePlan) {
         let key = DataKey::Plan(plan_id);
         env.storage().persistent().set(&key, plan);
+        access_control::extend_persistent_ttl(env, &key);
     }
 
+    /// Reads the plan record, extending its TTL when it is found.
     fn get_plan(env: &Env, plan_id: u64) -> Option<InheritancePlan> {
         let key = DataKey::Plan(plan_id);
-        env.storage().persistent().get(&key)
+        let plan = env.storage().persistent().get(&key);
+        if plan.is_some() {
+            access_control::extend_persistent_ttl(env, &key);
+        }
+        plan
     }
 
-    fn add_plan_to_user(env: &Env, owner: Address, plan_id: u64) {
-        let key = DataKey::UserPlans(owner.clone());
-        let mut plans: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
+    /// True while the plan has an open dispute, an unexpired freeze or a legal hold.
+    ///
+    /// Open disputes are checked directly because an admin freeze can replace
+    /// the dispute's freeze record and lapse before the dispute closes.
+    fn is_plan_frozen(env: &Env, plan_id: u64) -> bool {
+        disputes::open_dispute_count(env, plan_id) > 0
+            || freeze::active_freeze(env, plan_id).is_some()
+            || freeze::active_legal_hold(env, plan_id).is_some()
+    }
 
-        plans.push_back(plan_id);
-        env.storage().persistent().set(&key, &plans);
+    fn add_plan_to_user(env: &Env, owner: Address, plan_id: u64) {
+        plan_index::insert(env, PlanIndex::Owner(owner), plan_id);
     }
 
     fn remove_plan_from_user(env: &Env, owner: Address, plan_id: u64) {
-        let key = DataKey::UserPlans(owner);
-        let mut plans: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
-
-        for i in 0..plans.len() {
-            if plans.get(i).unwrap() == plan_id {
-                plans.remove(i);
-                break;
-            }
-        }
-        env.storage().persistent().set(&key, &plans);
+        plan_index::remove(env, PlanIndex::Owner(owner), plan_id);
     }
 
     fn add_plan_to_deactivated(env: &Env, plan_id: u64) {
-        let key = DataKey::DeactivatedPlans;
-        let mut plans: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
-
-        // Avoid duplicates if called multiple times (though logic should prevent this)
-        if !plans.contains(plan_id) {
-            plans.push_back(plan_id);
-            env.storage().persistent().set(&key, &plans);
-        }
+        plan_index::remove(env, PlanIndex::Pending, plan_id);
+        plan_index::insert(env, PlanIndex::Deactivated, plan_id);
     }
 
     fn add_plan_to_claimed(env: &Env, owner: Address, plan_id: u64) {
-        let key_user = DataKey::UserClaimedPlans(owner);
-        let mut user_plans: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key_user)
-            .unwrap_or(Vec::new(env));
-
-        if !user_plans.contains(plan_id) {
-            user_plans.push_back(plan_id);
-            env.storage().persistent().set(&key_user, &user_plans);
-        }
-
-        let key_all = DataKey::AllClaimedPlans;
-        let mut all_plans: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key_all)
-            .unwrap_or(Vec::new(env));
+        plan_index::insert(env, PlanIndex::Claimed, plan_id);
+        plan_index::insert(env, PlanIndex::OwnerClaimed(owner), plan_id);
+    }
 
-        if !all_plans.contains(plan_id) {
-            all_plans.push_back(plan_id);
-            env.storage().persistent().set(&key_all, &all_plans);
-        }
+    fn load_plan_page(
+        env: &Env,
+        index: PlanIndex,
+        start_after: Option<u64>,
+        limit: u32,
+    ) -> PlanPage {
+        plan_index::page(
+            env,
+            &index,
+            Self::get_next_plan_id(env),
+            start_after,
+            limit,
+            |plan_id| Self::get_plan(env, plan_id),
+        )
     }
 
     /// Get plan details
@@ -1092,14 +1243,105 @@ impl InheritanceContract {
     /// # Returns
     /// The InheritancePlan if found, None otherwise
     pub fn get_plan_details(env: Env, plan_id: u64) -> Option<InheritancePlan> {
+        access_control::extend_instance_ttl(&env);
         Self::get_plan(&env, plan_id)
     }
 
+    /// Renew the TTL of a plan and every per-plan storage entry so that a
+    /// dormant plan is not archived before it is claimed. Every entry is
+    /// extended to the full bump amount. Reads and writes keep the plan
+    /// record itself alive, so keepers should bump on a schedule rather than
+    /// waiting for the record's TTL to run low.
+    ///
+    /// Permissionless: anyone (typically an off-chain keeper) may pay the
+    /// rent. Covers the plan record, the owner's plan index, beneficiary
+    /// claim/notification records, will versions, signatures and witnesses,
+    /// trigger/guardian/emergency configuration, freezes, disputes and
+    /// payout progress.
+    ///
+    /// # Errors
+    /// - PlanNotFound: If plan_id doesn't exist
+    pub fn bump_plan(env: Env, plan_id: u64) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+
+        for key in [
+            DataKey::InheritanceTrigger(plan_id),
+            DataKey::EmergencyAccess(plan_id),
+            DataKey::Guardians(plan_id),
+            DataKey::EmergencyContacts(plan_id),
+            DataKey::WillHash(plan_id),
+            DataKey::VaultWill(plan_id),
+            DataKey::BeneficiaryVerification(plan_id),
+            DataKey::WillVersionCount(plan_id),
+            DataKey::ActiveWillVersion(plan_id),
+            DataKey::WillSignature(plan_id),
+            DataKey::VaultMessages(plan_id),
+            DataKey::WillWitnesses(plan_id),
+            DataKey::RequiresAcknowledgment(plan_id),
+            DataKey::FreezePlan(plan_id),
+            DataKey::LegalHold(plan_id),
+            DataKey::TriggerConditions(plan_id),
+        ] {
+            access_control::renew_persistent_ttl(&env, &key);
+        }
+
+        let version_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WillVersionCount(plan_id))
+            .unwrap_or(0);
+        for version in 1..=version_count {
+            access_control::renew_persistent_ttl(&env, &DataKey::WillVersion(plan_id, version));
+            access_control::renew_persistent_ttl(&env, &DataKey::WillFinalized(plan_id, version));
+            access_control::renew_persistent_ttl(&env, &DataKey::WillFinalizedAt(plan_id, version));
+        }
+
+        let witnesses: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WillWitnesses(plan_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        for witness in witnesses.iter() {
+            access_control::renew_persistent_ttl(
+                &env,
+                &DataKey::WitnessSignature(plan_id, witness),
+            );
+        }
+
+        assets::extend_ttl(&env, plan_id);
+        recovery::extend_ttl(&env, plan_id);
+        ownership::extend_ttl(&env, plan_id);
+
+        for (index, beneficiary) in plan.beneficiaries.iter().enumerate() {
+            let index = index as u32;
+            claim_auth::extend_binding_ttl(&env, plan_id, &beneficiary.hashed_email);
+            vesting::extend_ttl(&env, plan_id, &beneficiary.hashed_email);
+            for key in [
+                Self::claim_key(&env, plan_id, &beneficiary.hashed_email),
+                DataKey::BeneficiaryNotifiedAt(plan_id, index),
+                DataKey::BeneficiaryAcknowledgedAt(plan_id, index),
+                DataKey::FrozenBeneficiary(plan_id, index),
+                DataKey::VestingExitSettlement(plan_id, index),
+            ] {
+                access_control::renew_persistent_ttl(&env, &key);
+            }
+        }
+
+        plan_index::extend_plan_ttl(&env, plan_id, &plan.owner);
+        disputes::extend_plan_ttl(&env, plan_id);
+        payouts::extend_plan_ttl(&env, plan_id, plan.beneficiaries.len());
+        access_control::renew_persistent_ttl(&env, &DataKey::Plan(plan_id));
+
+        Ok(())
+    }
+
     pub fn get_user_plan(
         env: Env,
         caller: Address,
         plan_id: u64,
     ) -> Result<InheritancePlan, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         caller.require_auth();
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
 
@@ -1146,13 +1388,9 @@ impl InheritanceContract {
     }
 
     pub fn get_user_plans(env: Env, user: Address) -> Vec<InheritancePlan> {
+        access_control::extend_instance_ttl(&env);
         user.require_auth();
-        let key = DataKey::UserPlans(user);
-        let plan_ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
+        let plan_ids = plan_index::ids(&env, &PlanIndex::Owner(user));
 
         let mut plans = Vec::new(&env);
         for plan_id in plan_ids.iter() {
@@ -1163,23 +1401,82 @@ impl InheritanceContract {
         plans
     }
 
+    /// Page through every plan in creation order (Admin only).
+    ///
+    /// Pass `None` as `start_after` for the first page and the returned
+    /// `next_cursor` for each following page. `limit` is clamped to 1..=50.
     pub fn get_all_plans(
         env: Env,
         admin: Address,
-    ) -> Result<Vec<InheritancePlan>, InheritanceError> {
+        start_after: Option<u64>,
+        limit: u32,
+    ) -> Result<PlanPage, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
+        Ok(Self::load_plan_page(
+            &env,
+            PlanIndex::All,
+            start_after,
+            limit,
+        ))
+    }
 
-        let mut plans = Vec::new(&env);
-        let next_plan_id = Self::get_next_plan_id(&env);
-        for plan_id in 1..next_plan_id {
-            if let Some(plan) = Self::get_plan(&env, plan_id) {
-                plans.push_back(plan);
+    /// Page through the plans currently owned by `user`.
+    pub fn get_owner_plans(
+        env: Env,
+        user: Address,
+        start_after: Option<u64>,
+        limit: u32,
+    ) -> PlanPage {
+        access_control::extend_instance_ttl(&env);
+        user.require_auth();
+        Self::load_plan_page(&env, PlanIndex::Owner(user), start_after, limit)
+    }
+
+    /// Number of plans in `index`.
+    pub fn get_plan_count(env: Env, index: PlanIndex) -> u32 {
+        access_control::extend_instance_ttl(&env);
+        plan_index::count(&env, &index, Self::get_next_plan_id(&env))
+    }
+
+    /// Index up to `limit` plans after `start_after` (a plan id) into the
+    /// status and owner indexes, for plans created before the indexes existed.
+    /// Safe to re-run. Returns the cursor for the next call, or `None` once
+    /// every plan has been indexed.
+    pub fn backfill_plan_indexes(
+        env: Env,
+        admin: Address,
+        start_after: Option<u64>,
+        limit: u32,
+    ) -> Result<Option<u64>, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin)?;
+
+        let page = Self::load_plan_page(&env, PlanIndex::All, start_after, limit);
+        for entry in page.plans.iter() {
+            let plan_id = entry.plan_id;
+            let owner = entry.plan.owner.clone();
+            Self::add_plan_to_user(&env, owner.clone(), plan_id);
+            if entry.plan.is_active {
+                plan_index::insert(&env, PlanIndex::Pending, plan_id);
+            } else {
+                Self::add_plan_to_deactivated(&env, plan_id);
+            }
+
+            let legacy_claimed: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::UserClaimedPlans(owner.clone()))
+                .unwrap_or(Vec::new(&env));
+            if legacy_claimed.contains(plan_id) {
+                Self::add_plan_to_claimed(&env, owner, plan_id);
             }
         }
-        Ok(plans)
+        Ok(page.next_cursor)
     }
 
     pub fn get_user_pending_plans(env: Env, user: Address) -> Vec<InheritancePlan> {
+        access_control::extend_instance_ttl(&env);
         let all_user_plans = Self::get_user_plans(env.clone(), user);
         let mut pending = Vec::new(&env);
         for plan in all_user_plans.iter() {
@@ -1190,18 +1487,21 @@ impl InheritanceContract {
         pending
     }
 
+    /// Page through active plans (Admin only). See `get_all_plans`.
     pub fn get_all_pending_plans(
         env: Env,
         admin: Address,
-    ) -> Result<Vec<InheritancePlan>, InheritanceError> {
-        let all_plans = Self::get_all_plans(env.clone(), admin)?;
-        let mut pending = Vec::new(&env);
-        for plan in all_plans.iter() {
-            if plan.is_active {
-                pending.push_back(plan);
-            }
-        }
-        Ok(pending)
+        start_after: Option<u64>,
+        limit: u32,
+    ) -> Result<PlanPage, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin)?;
+        Ok(Self::load_plan_page(
+            &env,
+            PlanIndex::Pending,
+            start_after,
+            limit,
+        ))
     }
 
     /// Add a beneficiary to an existing inheritance plan
@@ -1229,6 +1529,7 @@ impl InheritanceContract {
         plan_id: u64,
         beneficiary_input: BeneficiaryInput,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         // Require owner authorization
         owner.require_auth();
         Self::check_not_paused(&env);
@@ -1258,6 +1559,13 @@ impl InheritanceContract {
             return Err(InheritanceError::AllocationExceedsLimit);
         }
 
+        if !payout_details::accepts_new(&env, &beneficiary_input.bank_account) {
+            return Err(InheritanceError::InvalidBeneficiaryData);
+        }
+        if !vesting::is_valid(&beneficiary_input.vesting) {
+            return Err(InheritanceError::InvalidBeneficiaryData);
+        }
+
         // Create the beneficiary (validates inputs and hashes sensitive data)
         let beneficiary = Self::create_beneficiary(
             &env,
@@ -1272,6 +1580,12 @@ impl InheritanceContract {
         // Add beneficiary to plan
         plan.beneficiaries.push_back(beneficiary.clone());
         plan.total_allocation_bp = new_total;
+        vesting::set_release(
+            &env,
+            plan_id,
+            &beneficiary.hashed_email,
+            &beneficiary_input.vesting,
+        );
 
         // Store updated plan
         Self::store_plan(&env, plan_id, &plan);
@@ -1313,6 +1627,7 @@ impl InheritanceContract {
         plan_id: u64,
         index: u32,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         // Require owner authorization
         owner.require_auth();
         Self::check_not_paused(&env);
@@ -1346,6 +1661,8 @@ impl InheritanceContract {
 
         // Update total allocation
         plan.total_allocation_bp -= removed_allocation;
+        claim_auth::remove_binding(&env, plan_id, &removed_beneficiary.hashed_email);
+        vesting::clear(&env, plan_id, &removed_beneficiary.hashed_email);
 
         // Store updated plan
         Self::store_plan(&env, plan_id, &plan);
@@ -1396,6 +1713,7 @@ impl InheritanceContract {
         env: Env,
         params: CreateInheritancePlanParams,
     ) -> Result<u64, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         let CreateInheritancePlanParams {
             owner,
             token,
@@ -1431,13 +1749,18 @@ impl InheritanceContract {
             return Err(InheritanceError::InvalidTotalAmount);
         }
 
-        // Validate plan inputs using user input for "full amount" validation
-        let usdc_symbol = Symbol::new(&env, "USDC");
+        // Validate plan inputs using user input for "full amount" validation.
+        // Until the admin whitelists assets every plan is labelled USDC.
+        let asset_type = if assets::whitelist(&env).is_empty() {
+            Symbol::new(&env, "USDC")
+        } else {
+            assets::asset_code(&env, &token).ok_or(InheritanceError::InvalidAssetType)?
+        };
         Self::validate_plan_inputs(
             &env,
             plan_name.clone(),
             description.clone(),
-            usdc_symbol.clone(),
+            asset_type.clone(),
             total_amount,
         )?;
 
@@ -1496,6 +1819,9 @@ impl InheritanceContract {
         let mut total_allocation_bp = 0u32;
 
         for beneficiary_data in beneficiaries_data.iter() {
+            if !payout_details::accepts_new(&env, &beneficiary_data.3) {
+                return Err(InheritanceError::InvalidBeneficiaryData);
+            }
             let beneficiary = Self::create_beneficiary(
                 &env,
                 beneficiary_data.0.clone(),
@@ -1513,7 +1839,7 @@ impl InheritanceContract {
         let plan = InheritancePlan {
             plan_name,
             description,
-            asset_type: Symbol::new(&env, "USDC"),
+            asset_type,
             total_amount: net_amount,
             distribution_method,
             beneficiaries,
@@ -1529,9 +1855,11 @@ impl InheritanceContract {
         // Store the plan and get the plan ID
         let plan_id = Self::increment_plan_id(&env);
         Self::store_plan(&env, plan_id, &plan);
+        assets::set_primary_token(&env, plan_id, &token);
 
-        // Add to user's plan list
+        // Index the plan by owner and status
         Self::add_plan_to_user(&env, owner.clone(), plan_id);
+        plan_index::insert(&env, PlanIndex::Pending, plan_id);
 
         // Grant Owner role so RBAC checks recognise this address as a plan owner
         access_control::assign_role(&env, &owner, Role::Owner);
@@ -1548,6 +1876,7 @@ impl InheritanceContract {
         plan_id: u64,
         is_lendable: bool,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != owner {
@@ -1575,6 +1904,7 @@ impl InheritanceContract {
         plan_id: u64,
         amount: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         caller.require_auth();
         Self::check_not_paused(&env);
         Self::enter_guard(&env);
@@ -1593,6 +1923,19 @@ impl InheritanceContract {
             return Err(InheritanceError::PlanNotActive);
         }
 
+        // Whitelisted tokens other than the plan's own asset are held as
+        // separate balances. Delisted tokens take no new deposits.
+        let extra_asset = assets::is_extra_asset(&env, plan_id, &plan, &token);
+        if extra_asset {
+            if assets::asset_code(&env, &token).is_none()
+                || !assets::has_room_for(&env, plan_id, &token)
+            {
+                return Err(InheritanceError::InvalidAssetType);
+            }
+        } else if !assets::is_primary_token(&env, plan_id, &plan, &token) {
+            return Err(InheritanceError::InvalidAssetType);
+        }
+
         let token_client = token::Client::new(&env, &token);
         let balance = token_client.balance(&caller);
         let required = amount as i128;
@@ -1613,13 +1956,26 @@ impl InheritanceContract {
             return Err(InheritanceError::FeeTransferFailed);
         }
 
-        plan.total_amount += amount;
-        Self::store_plan(&env, plan_id, &plan);
+        if extra_asset {
+            let held = assets::balance(&env, plan_id, &token);
+            assets::set_balance(&env, plan_id, &token, held.saturating_add(amount));
+            env.events().publish(
+                (symbol_short!("ASSET"), symbol_short!("DEPOSIT")),
+                assets::AssetDepositEvent {
+                    plan_id,
+                    token,
+                    amount,
+                },
+            );
+        } else {
+            plan.total_amount += amount;
+            Self::store_plan(&env, plan_id, &plan);
 
-        env.events().publish(
-            (symbol_short!("VAULT"), symbol_short!("DEPOSIT")),
-            VaultDepositEvent { plan_id, amount },
-        );
+            env.events().publish(
+                (symbol_short!("VAULT"), symbol_short!("DEPOSIT")),
+                VaultDepositEvent { plan_id, amount },
+            );
+        }
         log!(&env, "Deposited {} into plan {}", amount, plan_id);
         Self::exit_guard(&env);
         Ok(())
@@ -1632,6 +1988,7 @@ impl InheritanceContract {
         plan_id: u64,
         amount: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         caller.require_auth();
         Self::check_not_paused(&env);
         Self::enter_guard(&env);
@@ -1646,32 +2003,23 @@ impl InheritanceContract {
         }
 
         // Freeze/legal hold check
-        if env
-            .storage()
-            .persistent()
-            .has(&DataKey::FreezePlan(plan_id))
-        {
-            return Err(InheritanceError::PlanNotActive);
-        }
-        if env.storage().persistent().has(&DataKey::LegalHold(plan_id)) {
+        if Self::is_plan_frozen(&env, plan_id) {
             return Err(InheritanceError::PlanNotActive);
         }
 
-        // Emergency Guard: Limit withdrawal if emergency access was recently activated
-        if Self::is_emergency_active(&env, plan_id) {
-            let limit = (plan.total_amount as u128)
-                .checked_mul(EMERGENCY_TRANSFER_LIMIT_BP as u128)
-                .and_then(|v| v.checked_div(10000))
-                .unwrap_or(0) as u64;
-
-            if amount > limit {
-                return Err(InheritanceError::EmergencyCooldownActive);
-            }
+        let extra_asset = assets::is_extra_asset(&env, plan_id, &plan, &token);
+        if !extra_asset && !assets::is_primary_token(&env, plan_id, &plan, &token) {
+            return Err(InheritanceError::InvalidAssetType);
         }
+        let held = if extra_asset {
+            assets::balance(&env, plan_id, &token)
+        } else {
+            plan.total_amount
+        };
 
         // Emergency Guard: Limit withdrawal if emergency access was recently activated
         if Self::is_emergency_active(&env, plan_id) {
-            let limit = (plan.total_amount as u128)
+            let limit = (held as u128)
                 .checked_mul(EMERGENCY_TRANSFER_LIMIT_BP as u128)
                 .and_then(|v| v.checked_div(10000))
                 .unwrap_or(0) as u64;
@@ -1681,7 +2029,15 @@ impl InheritanceContract {
             }
         }
 
-        let available = plan.total_amount.saturating_sub(plan.total_loaned);
+        // Loans are only ever drawn from the primary balance, and vesting
+        // entitlements are fixed in it.
+        let available = if extra_asset {
+            held
+        } else {
+            plan.total_amount
+                .saturating_sub(plan.total_loaned)
+                .saturating_sub(Self::vesting_outstanding(&env, plan_id, &plan))
+        };
         if amount > available {
             return Err(InheritanceError::InsufficientLiquidity);
         }
@@ -1700,13 +2056,25 @@ impl InheritanceContract {
             return Err(InheritanceError::FeeTransferFailed);
         }
 
-        plan.total_amount -= amount;
-        Self::store_plan(&env, plan_id, &plan);
+        if extra_asset {
+            assets::set_balance(&env, plan_id, &token, held - amount);
+            env.events().publish(
+                (symbol_short!("ASSET"), symbol_short!("WITHDRAW")),
+                assets::AssetWithdrawEvent {
+                    plan_id,
+                    token,
+                    amount,
+                },
+            );
+        } else {
+            plan.total_amount -= amount;
+            Self::store_plan(&env, plan_id, &plan);
 
-        env.events().publish(
-            (symbol_short!("VAULT"), symbol_short!("WITHDRAW")),
-            VaultWithdrawEvent { plan_id, amount },
-        );
+            env.events().publish(
+                (symbol_short!("VAULT"), symbol_short!("WITHDRAW")),
+                VaultWithdrawEvent { plan_id, amount },
+            );
+        }
         log!(&env, "Withdrew {} from plan {}", amount, plan_id);
         Self::exit_guard(&env);
         Ok(())
@@ -1719,6 +2087,7 @@ impl InheritanceContract {
         beneficiary_index: u32,
         priority: u32,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != owner {
@@ -1765,6 +2134,7 @@ impl InheritanceContract {
         plan_id: u64,
         beneficiary_index: u32,
     ) -> Result<u32, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if beneficiary_index >= plan.beneficiaries.len() {
             return Err(InheritanceError::InvalidBeneficiaryIndex);
@@ -1777,6 +2147,7 @@ impl InheritanceContract {
         owner: Address,
         plan_id: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != owner {
@@ -1825,20 +2196,71 @@ impl InheritanceContract {
         entitlement.min(plan.total_amount)
     }
 
-    /// Check if a beneficiary has an active vesting schedule
-    fn has_active_vesting_schedule(_env: &Env, _plan_id: u64, _beneficiary_index: u32) -> bool {
-        // For MVP, we don't implement vesting schedules yet
-        // This is a placeholder that always returns false
-        false
-    }
-
     /// Get vesting exit settlement amount for a beneficiary
     fn get_vesting_exit_settlement(env: &Env, plan_id: u64, beneficiary_index: u32) -> u64 {
         let settle_key = DataKey::VestingExitSettlement(plan_id, beneficiary_index);
-        env.storage()
-            .persistent()
-            .get(&settle_key)
-            .unwrap_or(0u64)
+        env.storage().persistent().get(&settle_key).unwrap_or(0u64)
+    }
+
+    /// Deduct `payout` from a beneficiary's exit settlement, if they have one.
+    /// Returns whether the settlement is now fully paid (always true without
+    /// one).
+    fn record_exit_settlement_payout(
+        env: &Env,
+        plan_id: u64,
+        beneficiary_index: u32,
+        exit_settlement: u64,
+        payout: u64,
+    ) -> bool {
+        if exit_settlement == 0 {
+            return true;
+        }
+        let settle_key = DataKey::VestingExitSettlement(plan_id, beneficiary_index);
+        let remaining = exit_settlement.saturating_sub(payout);
+        if remaining == 0 {
+            env.storage().persistent().remove(&settle_key);
+        } else {
+            env.storage().persistent().set(&settle_key, &remaining);
+            access_control::extend_persistent_ttl(env, &settle_key);
+        }
+        remaining == 0
+    }
+
+    /// How much of `entitlement` a vesting beneficiary may still be paid,
+    /// with the progress to record the payment against. The entitlement is
+    /// fixed at their first release. None when the beneficiary does not vest.
+    fn vesting_allowance(
+        env: &Env,
+        plan_id: u64,
+        hashed_email: &BytesN<32>,
+        entitlement: u64,
+    ) -> Option<(u64, VestingProgress)> {
+        let release = vesting::release(env, plan_id, hashed_email)?;
+        let progress = vesting::progress(env, plan_id, hashed_email).unwrap_or(VestingProgress {
+            entitlement,
+            released: 0,
+        });
+        let bp = vesting::unlocked_bp(
+            env,
+            plan_id,
+            hashed_email,
+            &release,
+            env.ledger().timestamp(),
+        );
+        let allowance =
+            vesting::unlocked_amount(progress.entitlement, bp).saturating_sub(progress.released);
+        Some((allowance, progress))
+    }
+
+    /// What vesting beneficiaries are still owed out of the entitlements
+    /// fixed at their first release.
+    fn vesting_outstanding(env: &Env, plan_id: u64, plan: &InheritancePlan) -> u64 {
+        plan.beneficiaries
+            .iter()
+            .filter_map(|b| vesting::progress(env, plan_id, &b.hashed_email))
+            .fold(0u64, |total, progress| {
+                total.saturating_add(progress.entitlement.saturating_sub(progress.released))
+            })
     }
 
     pub fn get_claimable_by_priority(
@@ -1846,6 +2268,7 @@ impl InheritanceContract {
         plan_id: u64,
         beneficiary_index: u32,
     ) -> Result<u64, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if beneficiary_index >= plan.beneficiaries.len() {
             return Err(InheritanceError::InvalidBeneficiaryIndex);
@@ -1857,16 +2280,19 @@ impl InheritanceContract {
         ))
     }
 
-    fn is_claim_time_valid(env: &Env, plan: &InheritancePlan) -> bool {
-        let now = env.ledger().timestamp();
-        let elapsed = now - plan.created_at;
-
-        match plan.distribution_method {
-            DistributionMethod::LumpSum => true, // always claimable
-            DistributionMethod::Monthly => elapsed >= 30 * 24 * 60 * 60,
-            DistributionMethod::Quarterly => elapsed >= 90 * 24 * 60 * 60,
-            DistributionMethod::Yearly => elapsed >= 365 * 24 * 60 * 60,
+    /// Locate the beneficiary matching the hashed email and claim code.
+    fn find_beneficiary_index(
+        plan: &InheritancePlan,
+        hashed_email: &BytesN<32>,
+        hashed_claim_code: &BytesN<32>,
+    ) -> Option<u32> {
+        for i in 0..plan.beneficiaries.len() {
+            let b = plan.beneficiaries.get(i).unwrap();
+            if &b.hashed_email == hashed_email && &b.hashed_claim_code == hashed_claim_code {
+                return Some(i);
+            }
         }
+        None
     }
 
     pub fn claim_inheritance_plan(
@@ -1876,6 +2302,7 @@ impl InheritanceContract {
         email: String,
         claim_code: u32,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         // Require claimer authorization
         claimer.require_auth();
         Self::check_not_paused(&env);
@@ -1884,64 +2311,151 @@ impl InheritanceContract {
         // Check KYC approval - only approved users can claim plans
         Self::check_kyc_approved(&env, &claimer)?;
 
-        // Fetch the plan
-        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        let plan = Self::claimable_plan(&env, plan_id)?;
 
-        // Check if plan is active
-        if !plan.is_active {
-            return Err(InheritanceError::PlanNotActive);
-        }
+        // Hash email and claim code
+        let hashed_email = Self::hash_string(&env, email.clone());
+        let hashed_claim_code = Self::hash_claim_code(&env, claim_code)?;
 
-        // Freeze/legal hold check
+        // Check if already claimed for this plan
         if env
             .storage()
             .persistent()
-            .has(&DataKey::FreezePlan(plan_id))
+            .has(&Self::claim_key(&env, plan_id, &hashed_email))
         {
-            return Err(InheritanceError::PlanNotActive);
+            return Err(InheritanceError::AlreadyClaimed);
+        }
+
+        // Find beneficiary
+        let index = Self::find_beneficiary_index(&plan, &hashed_email, &hashed_claim_code)
+            .ok_or(InheritanceError::BeneficiaryNotFound)?;
+        Self::require_claim_code_claimer(&env, plan_id, &plan, index, &claimer)?;
+
+        Self::settle_claim(&env, plan_id, plan, index, &claimer)
+    }
+
+    /// Claim as a beneficiary bound with `bind_beneficiary` (or bound by
+    /// revealing a commitment). Address bindings are proven by the claimer's
+    /// own authorization; ed25519 bindings additionally need `signature` over
+    /// the claim message described in `claim_auth::claim_message`.
+    pub fn claim_as_beneficiary(
+        env: Env,
+        plan_id: u64,
+        beneficiary_index: u32,
+        claimer: Address,
+        signature: Option<BytesN<64>>,
+    ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
+        claimer.require_auth();
+        Self::check_not_paused(&env);
+        Self::enter_guard(&env);
+
+        Self::check_kyc_approved(&env, &claimer)?;
+
+        let plan = Self::claimable_plan(&env, plan_id)?;
+        Self::require_bound_claimer(&env, plan_id, &plan, beneficiary_index, &claimer, signature)?;
+
+        Self::settle_claim(&env, plan_id, plan, beneficiary_index, &claimer)
+    }
+
+    /// Fetch a plan that can currently be claimed in a lump sum.
+    fn claimable_plan(env: &Env, plan_id: u64) -> Result<InheritancePlan, InheritanceError> {
+        let plan = Self::get_plan(env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+
+        // Check if plan is active
+        if !plan.is_active {
+            return Err(InheritanceError::PlanNotActive);
         }
-        if env.storage().persistent().has(&DataKey::LegalHold(plan_id)) {
+
+        // Freeze/legal hold check
+        if Self::is_plan_frozen(env, plan_id) {
             return Err(InheritanceError::PlanNotActive);
         }
 
-        // When inheritance is triggered, bypass the time-based check so
-        // that inheritance execution cannot be blocked.
-        let triggered = Self::get_trigger_info(&env, plan_id).is_some();
-        if !triggered && !Self::is_claim_time_valid(&env, &plan) {
+        // Periodic plans pay out in installments via `claim_next_installment`.
+        if payouts::is_periodic(&plan.distribution_method) {
             return Err(InheritanceError::ClaimNotAllowedYet);
         }
 
-        // Hash email and claim code
-        let hashed_email = Self::hash_string(&env, email.clone());
-        let hashed_claim_code = Self::hash_claim_code(&env, claim_code)?;
+        Ok(plan)
+    }
 
-        // Build claim key including plan ID
-        let claim_key = {
-            let mut data = Bytes::new(&env);
-            data.extend_from_slice(&plan_id.to_be_bytes()); // plan ID as bytes
-            data.extend_from_slice(&hashed_email.to_array()); // convert BytesN<32> to [u8;32]
-            DataKey::Claim(env.crypto().sha256(&data).into())
-        };
+    /// Claim record key: `sha256(plan_id_be ‖ hashed_email)`.
+    fn claim_key(env: &Env, plan_id: u64, hashed_email: &BytesN<32>) -> DataKey {
+        let mut data = Bytes::new(env);
+        data.extend_from_slice(&plan_id.to_be_bytes());
+        data.extend_from_slice(&hashed_email.to_array());
+        DataKey::Claim(env.crypto().sha256(&data).into())
+    }
 
-        // Check if already claimed for this plan
-        if env.storage().persistent().has(&claim_key) {
-            return Err(InheritanceError::AlreadyClaimed);
+    /// Check that `claimer` is the identity beneficiary `index` is bound to.
+    fn require_bound_claimer(
+        env: &Env,
+        plan_id: u64,
+        plan: &InheritancePlan,
+        index: u32,
+        claimer: &Address,
+        signature: Option<BytesN<64>>,
+    ) -> Result<(), InheritanceError> {
+        let beneficiary = plan
+            .beneficiaries
+            .get(index)
+            .ok_or(InheritanceError::InvalidBeneficiaryIndex)?;
+        match claim_auth::binding(env, plan_id, &beneficiary.hashed_email) {
+            Some(BeneficiaryBinding::Address(bound)) if &bound == claimer => Ok(()),
+            Some(BeneficiaryBinding::Ed25519(public_key)) => {
+                let signature = signature.ok_or(InheritanceError::Unauthorized)?;
+                let message =
+                    claim_auth::claim_message(env, plan_id, &beneficiary.hashed_email, claimer);
+                // Traps on an invalid signature.
+                env.crypto()
+                    .ed25519_verify(&public_key, &message, &signature);
+                Ok(())
+            }
+            // Unbound, bound elsewhere, or a commitment not yet revealed.
+            _ => Err(InheritanceError::Unauthorized),
         }
+    }
 
-        // Find beneficiary
-        let mut beneficiary_index: Option<u32> = None;
-        for i in 0..plan.beneficiaries.len() {
-            let b = plan.beneficiaries.get(i).unwrap();
-            if b.hashed_email == hashed_email && b.hashed_claim_code == hashed_claim_code {
-                beneficiary_index = Some(i);
-                break;
-            }
+    /// Check a claim made with an email and claim code. The code only locates
+    /// the beneficiary: a bound beneficiary must still be the identity they
+    /// are bound to, and an unbound one is only accepted while legacy claim
+    /// codes are enabled.
+    fn require_claim_code_claimer(
+        env: &Env,
+        plan_id: u64,
+        plan: &InheritancePlan,
+        index: u32,
+        claimer: &Address,
+    ) -> Result<(), InheritanceError> {
+        let hashed_email = plan
+            .beneficiaries
+            .get(index)
+            .ok_or(InheritanceError::InvalidBeneficiaryIndex)?
+            .hashed_email;
+        if claim_auth::binding(env, plan_id, &hashed_email).is_none()
+            && claim_auth::legacy_claim_codes_enabled(env)
+        {
+            return Ok(());
         }
+        Self::require_bound_claimer(env, plan_id, plan, index, claimer, None)
+    }
 
-        let index = beneficiary_index.ok_or(InheritanceError::BeneficiaryNotFound)?;
+    /// Pay beneficiary `index` their lump-sum share. Callers must already
+    /// have authenticated `claimer` as that beneficiary.
+    fn settle_claim(
+        env: &Env,
+        plan_id: u64,
+        plan: InheritancePlan,
+        index: u32,
+        claimer: &Address,
+    ) -> Result<(), InheritanceError> {
+        let triggered = Self::get_trigger_info(env, plan_id).is_some();
 
-        if Self::has_active_vesting_schedule(&env, plan_id, index) {
-            return Err(InheritanceError::VestingScheduleActive);
+        let hashed_email = plan.beneficiaries.get(index).unwrap().hashed_email;
+        let claim_key = Self::claim_key(env, plan_id, &hashed_email);
+        if env.storage().persistent().has(&claim_key) {
+            return Err(InheritanceError::AlreadyClaimed);
         }
 
         // Waterfall ordering: if enabled, every strictly higher-priority
@@ -1957,15 +2471,28 @@ impl InheritanceContract {
         }
 
         // --- Payout Logic ---
-        let mut payout = Self::calculate_waterfall_payout(&env, &plan, index);
+        let mut payout = Self::calculate_waterfall_payout(env, &plan, index);
 
-        let exit_settlement = Self::get_vesting_exit_settlement(&env, plan_id, index);
+        // A vesting beneficiary is paid what their schedule has unlocked so
+        // far, out of an entitlement fixed at their first release.
+        let mut vesting_progress = None;
+        if let Some((allowance, progress)) =
+            Self::vesting_allowance(env, plan_id, &hashed_email, payout)
+        {
+            payout = allowance.min(plan.total_amount);
+            if payout == 0 && progress.released < progress.entitlement {
+                return Err(InheritanceError::VestingScheduleActive);
+            }
+            vesting_progress = Some(progress);
+        }
+
+        let exit_settlement = Self::get_vesting_exit_settlement(env, plan_id, index);
         if exit_settlement > 0 {
             payout = payout.min(exit_settlement);
         }
 
         // Emergency Guard: Limit claim if emergency access was recently activated
-        if Self::is_emergency_active(&env, plan_id) {
+        if Self::is_emergency_active(env, plan_id) {
             let limit = (plan.total_amount as u128)
                 .checked_mul(EMERGENCY_TRANSFER_LIMIT_BP as u128)
                 .and_then(|v| v.checked_div(10000))
@@ -2005,42 +2532,42 @@ impl InheritanceContract {
         // Update plan balances and mark beneficiary as claimed when fully finalized
         let mut updated_plan = plan.clone();
 
-        let exit_remaining_after = exit_settlement.saturating_sub(payout);
-        let exit_finalized = exit_settlement == 0 || exit_remaining_after == 0;
+        let exit_finalized =
+            Self::record_exit_settlement_payout(env, plan_id, index, exit_settlement, payout);
+        let vesting_finalized = match vesting_progress.as_mut() {
+            Some(progress) => {
+                progress.released += payout;
+                vesting::set_progress(env, plan_id, &hashed_email, progress);
+                progress.released >= progress.entitlement
+            }
+            None => true,
+        };
+        let finalized = exit_finalized && vesting_finalized;
 
         // Update the specific beneficiary in the vector
         let mut b = updated_plan.beneficiaries.get(index).unwrap();
-        if exit_finalized {
+        if finalized {
             b.is_claimed = true;
         }
         updated_plan.beneficiaries.set(index, b);
 
         updated_plan.total_amount = updated_plan.total_amount.saturating_sub(payout);
-        Self::store_plan(&env, plan_id, &updated_plan);
-
-        if exit_settlement > 0 {
-            let settle_key = DataKey::VestingExitSettlement(plan_id, index);
-            if exit_remaining_after == 0 {
-                env.storage().persistent().remove(&settle_key);
-            } else {
-                env.storage()
-                    .persistent()
-                    .set(&settle_key, &exit_remaining_after);
-            }
-        }
+        Self::store_plan(env, plan_id, &updated_plan);
 
-        if exit_finalized {
+        if finalized {
             let claim = ClaimRecord {
                 plan_id,
                 beneficiary_index: index,
                 claimed_at: env.ledger().timestamp(),
             };
             env.storage().persistent().set(&claim_key, &claim);
-            Self::add_plan_to_claimed(&env, plan.owner.clone(), plan_id);
+            access_control::extend_persistent_ttl(env, &claim_key);
+            Self::add_plan_to_claimed(env, plan.owner.clone(), plan_id);
+            Self::pay_out_assets(env, plan_id, &plan, index, claimer)?;
         }
 
         // Grant Beneficiary role to the claimer as an on-chain record of a successful claim
-        access_control::assign_role(&env, &claimer, Role::Beneficiary);
+        access_control::assign_role(env, claimer, Role::Beneficiary);
 
         // Emit claim event
         env.events().publish(
@@ -2049,18 +2576,229 @@ impl InheritanceContract {
         );
 
         log!(
-            &env,
+            env,
             "Inheritance claimed for plan {} by {}",
             plan_id,
-            email
+            claimer.clone()
         );
 
-        Self::exit_guard(&env);
+        Self::exit_guard(env);
+        Ok(())
+    }
+
+    /// Transfer beneficiary `index`'s share of each of the plan's extra
+    /// assets to `claimer`. Called once, when their claim is finalized.
+    fn pay_out_assets(
+        env: &Env,
+        plan_id: u64,
+        plan: &InheritancePlan,
+        index: u32,
+        claimer: &Address,
+    ) -> Result<(), InheritanceError> {
+        let beneficiary = plan.beneficiaries.get(index).unwrap();
+        let contract_id = env.current_contract_address();
+        for token in assets::plan_assets(env, plan_id).iter() {
+            let bp = assets::allocation_bp(
+                env,
+                plan_id,
+                &token,
+                &beneficiary.hashed_email,
+                beneficiary.allocation_bp,
+            );
+            let amount = assets::entitlement(env, plan_id, &token, bp);
+            if amount == 0 {
+                continue;
+            }
+
+            let args: Vec<Val> = vec![
+                env,
+                contract_id.clone().into_val(env),
+                claimer.clone().into_val(env),
+                (amount as i128).into_val(env),
+            ];
+            let res = env.try_invoke_contract::<(), InvokeError>(
+                &token,
+                &symbol_short!("transfer"),
+                args,
+            );
+            if res.is_err() {
+                return Err(InheritanceError::FeeTransferFailed);
+            }
+            assets::record_payout(env, plan_id, &token, amount);
+
+            env.events().publish(
+                (symbol_short!("ASSET"), symbol_short!("CLAIM")),
+                assets::AssetClaimedEvent {
+                    plan_id,
+                    hashed_email: beneficiary.hashed_email.clone(),
+                    token,
+                    amount,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Turn the legacy email + claim code path on or off for unbound
+    /// beneficiaries. `migrate` turns it off; bound beneficiaries are not
+    /// affected either way. Admin only.
+    pub fn set_legacy_claim_codes_enabled(
+        env: Env,
+        admin: Address,
+        enabled: bool,
+    ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin)?;
+        claim_auth::set_legacy_claim_codes_enabled(&env, enabled);
+        env.events()
+            .publish((symbol_short!("CLAIM"), symbol_short!("LEGACY")), enabled);
+        Ok(())
+    }
+
+    pub fn legacy_claim_codes_enabled(env: Env) -> bool {
+        access_control::extend_instance_ttl(&env);
+        claim_auth::legacy_claim_codes_enabled(&env)
+    }
+
+    /// Bind a beneficiary to the identity that may claim for them. Replaces
+    /// any earlier binding; once bound, the claim code alone no longer
+    /// authorizes a claim.
+    pub fn bind_beneficiary(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        beneficiary_index: u32,
+        binding: BeneficiaryBinding,
+    ) -> Result<(), ClaimAuthError> {
+        access_control::extend_instance_ttl(&env);
+        owner.require_auth();
+        Self::check_not_paused(&env);
+
+        let plan = Self::get_plan(&env, plan_id).ok_or(ClaimAuthError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(ClaimAuthError::Unauthorized);
+        }
+        let beneficiary = plan
+            .beneficiaries
+            .get(beneficiary_index)
+            .ok_or(ClaimAuthError::InvalidBeneficiaryIndex)?;
+        if beneficiary.is_claimed {
+            return Err(ClaimAuthError::AlreadyClaimed);
+        }
+
+        claim_auth::set_binding(&env, plan_id, &beneficiary.hashed_email, &binding);
+        env.events().publish(
+            (symbol_short!("BENEFIC"), symbol_short!("BIND")),
+            claim_auth::BeneficiaryBoundEvent {
+                plan_id,
+                hashed_email: beneficiary.hashed_email,
+                binding,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_beneficiary_binding(
+        env: Env,
+        plan_id: u64,
+        beneficiary_index: u32,
+    ) -> Option<BeneficiaryBinding> {
+        access_control::extend_instance_ttl(&env);
+        let beneficiary = Self::get_plan(&env, plan_id)?
+            .beneficiaries
+            .get(beneficiary_index)?;
+        claim_auth::binding(&env, plan_id, &beneficiary.hashed_email)
+    }
+
+    /// First half of binding an email-only beneficiary: record
+    /// `sha256(salt ‖ claimer_xdr)` without revealing the salt.
+    pub fn seal_beneficiary_claim(
+        env: Env,
+        plan_id: u64,
+        beneficiary_index: u32,
+        claimer: Address,
+        sealed: BytesN<32>,
+    ) -> Result<(), ClaimAuthError> {
+        access_control::extend_instance_ttl(&env);
+        claimer.require_auth();
+        Self::check_not_paused(&env);
+
+        let hashed_email = Self::committed_beneficiary(&env, plan_id, beneficiary_index)?.0;
+        claim_auth::record_seal(&env, plan_id, &hashed_email, &claimer, sealed);
+        Ok(())
+    }
+
+    /// Second half: reveal the salt in a later ledger than the seal. If it
+    /// opens the owner's commitment, the beneficiary is bound to `claimer`,
+    /// which must have passed KYC.
+    pub fn reveal_beneficiary_claim(
+        env: Env,
+        plan_id: u64,
+        beneficiary_index: u32,
+        claimer: Address,
+        salt: BytesN<32>,
+    ) -> Result<(), ClaimAuthError> {
+        access_control::extend_instance_ttl(&env);
+        claimer.require_auth();
+        Self::check_not_paused(&env);
+        Self::check_kyc_approved(&env, &claimer).map_err(|_| ClaimAuthError::KycNotApproved)?;
+
+        let (hashed_email, committed) =
+            Self::committed_beneficiary(&env, plan_id, beneficiary_index)?;
+        if claim_auth::commitment(&env, plan_id, &hashed_email, &salt) != committed {
+            return Err(ClaimAuthError::CommitmentMismatch);
+        }
+
+        let seal = claim_auth::take_seal(&env, plan_id, &hashed_email, &claimer)
+            .ok_or(ClaimAuthError::SealNotFound)?;
+        // Sealing and revealing in the same ledger would let anyone who sees
+        // the reveal front-run it with a seal of their own.
+        if seal.sealed_at_ledger >= env.ledger().sequence() {
+            return Err(ClaimAuthError::SealNotMatured);
+        }
+        if seal.sealed != claim_auth::seal(&env, &salt, &claimer) {
+            return Err(ClaimAuthError::CommitmentMismatch);
+        }
+
+        let binding = BeneficiaryBinding::Address(claimer);
+        claim_auth::set_binding(&env, plan_id, &hashed_email, &binding);
+        env.events().publish(
+            (symbol_short!("BENEFIC"), symbol_short!("BIND")),
+            claim_auth::BeneficiaryBoundEvent {
+                plan_id,
+                hashed_email,
+                binding,
+            },
+        );
         Ok(())
     }
 
+    /// The hashed email and commitment of an unclaimed beneficiary that is
+    /// still waiting to be bound by reveal.
+    fn committed_beneficiary(
+        env: &Env,
+        plan_id: u64,
+        index: u32,
+    ) -> Result<(BytesN<32>, BytesN<32>), ClaimAuthError> {
+        let plan = Self::get_plan(env, plan_id).ok_or(ClaimAuthError::PlanNotFound)?;
+        let beneficiary = plan
+            .beneficiaries
+            .get(index)
+            .ok_or(ClaimAuthError::InvalidBeneficiaryIndex)?;
+        if beneficiary.is_claimed {
+            return Err(ClaimAuthError::AlreadyClaimed);
+        }
+        match claim_auth::binding(env, plan_id, &beneficiary.hashed_email) {
+            Some(BeneficiaryBinding::Commitment(committed)) => {
+                Ok((beneficiary.hashed_email, committed))
+            }
+            _ => Err(ClaimAuthError::NotCommitted),
+        }
+    }
+
     /// Record KYC submission on-chain (called after off-chain submission).
     pub fn submit_kyc(env: Env, user: Address) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         user.require_auth();
 
         let key = DataKey::Kyc(user.clone());
@@ -2080,13 +2818,15 @@ impl InheritanceContract {
         status.submitted = true;
         status.submitted_at = env.ledger().timestamp();
         env.storage().persistent().set(&key, &status);
+        access_control::extend_persistent_ttl(&env, &key);
 
         Ok(())
     }
 
-    /// Approve a user's KYC after off-chain verification (admin-only).
+    /// Approve a user's KYC after off-chain verification (admin or KYC officer).
     pub fn approve_kyc(env: Env, admin: Address, user: Address) -> Result<(), InheritanceError> {
-        Self::require_admin(&env, &admin)?;
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::KycOfficer)?;
 
         let key = DataKey::Kyc(user.clone());
         let mut status: KycStatus = env
@@ -2106,6 +2846,7 @@ impl InheritanceContract {
         status.approved = true;
         status.approved_at = env.ledger().timestamp();
         env.storage().persistent().set(&key, &status);
+        access_control::extend_persistent_ttl(&env, &key);
 
         env.events().publish(
             (symbol_short!("KYC"), symbol_short!("APPROV")),
@@ -2118,19 +2859,20 @@ impl InheritanceContract {
         Ok(())
     }
 
-    /// Reject a user's KYC after off-chain review (admin-only).
+    /// Reject a user's KYC after off-chain review (admin or KYC officer).
     ///
     /// # Arguments
     /// * `env` - The environment
-    /// * `admin` - The admin address (must be the initialized admin)
+    /// * `admin` - The admin or a KYC officer
     /// * `user` - The user address whose KYC is being rejected
     ///
     /// # Errors
-    /// - `AdminNotSet` / `NotAdmin` if caller is not the admin
+    /// - `NotAdmin` if caller is neither the admin nor a KYC officer
     /// - `KycNotSubmitted` if user has no submitted KYC data
     /// - `KycAlreadyRejected` if the KYC was already rejected
     pub fn reject_kyc(env: Env, admin: Address, user: Address) -> Result<(), InheritanceError> {
-        Self::require_admin(&env, &admin)?;
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::KycOfficer)?;
 
         let key = DataKey::Kyc(user.clone());
         let mut status: KycStatus = env
@@ -2150,6 +2892,7 @@ impl InheritanceContract {
         status.rejected = true;
         status.rejected_at = env.ledger().timestamp();
         env.storage().persistent().set(&key, &status);
+        access_control::extend_persistent_ttl(&env, &key);
 
         env.events().publish(
             (symbol_short!("KYC"), symbol_short!("REJECT")),
@@ -2186,6 +2929,7 @@ impl InheritanceContract {
         owner: Address,
         plan_id: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         // Require owner authorization
         owner.require_auth();
 
@@ -2233,6 +2977,7 @@ impl InheritanceContract {
         plan_id: u64,
         trusted_contact: Address,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         // Require owner authorization
         owner.require_auth();
 
@@ -2262,6 +3007,7 @@ impl InheritanceContract {
 
         // Store the emergency access record
         env.storage().persistent().set(&key, &emergency_access);
+        access_control::extend_persistent_ttl(&env, &key);
 
         // Emit event
         env.events().publish(
@@ -2290,6 +3036,7 @@ impl InheritanceContract {
         guardians: Vec<Address>,
         threshold: u32,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != owner {
@@ -2305,10 +3052,22 @@ impl InheritanceContract {
         env.storage()
             .persistent()
             .set(&DataKey::Guardians(plan_id), &config);
+        access_control::extend_persistent_ttl(&env, &DataKey::Guardians(plan_id));
         // Grant Guardian role to each guardian address for RBAC checks
         for g in guardians.iter() {
             access_control::assign_role(&env, &g, Role::Guardian);
         }
+        // Approvals from the old guardian set no longer count
+        if let Some(pending) = recovery::clear(&env, plan_id) {
+            env.events().publish(
+                (symbol_short!("RECOVERY"), symbol_short!("CANCEL")),
+                recovery::RecoveryCancelledEvent {
+                    plan_id,
+                    new_owner: pending.new_owner,
+                    cancelled_by: owner,
+                },
+            );
+        }
         Ok(())
     }
 
@@ -2320,6 +3079,7 @@ impl InheritanceContract {
         plan_id: u64,
         contact: Address,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
 
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -2348,6 +3108,7 @@ impl InheritanceContract {
 
         contacts.push_back(contact.clone());
         env.storage().persistent().set(&key, &contacts);
+        access_control::extend_persistent_ttl(&env, &key);
 
         env.events().publish(
             (symbol_short!("EMERG"), symbol_short!("CON_ADD")),
@@ -2369,6 +3130,7 @@ impl InheritanceContract {
         plan_id: u64,
         contact: Address,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
 
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -2403,6 +3165,7 @@ impl InheritanceContract {
         contacts.pop_back();
 
         env.storage().persistent().set(&key, &contacts);
+        access_control::extend_persistent_ttl(&env, &key);
 
         env.events().publish(
             (symbol_short!("EMERG"), symbol_short!("CON_REM")),
@@ -2419,6 +3182,7 @@ impl InheritanceContract {
 
     /// Get all emergency contacts for a vault/plan.
     pub fn get_emergency_contacts(env: Env, plan_id: u64) -> Vec<Address> {
+        access_control::extend_instance_ttl(&env);
         let key = DataKey::EmergencyContacts(plan_id);
         env.storage()
             .persistent()
@@ -2432,6 +3196,7 @@ impl InheritanceContract {
         plan_id: u64,
         trusted_contact: Address,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         guardian.require_auth();
         access_control::require_role(
             &env,
@@ -2484,6 +3249,7 @@ impl InheritanceContract {
 
         approvals.push_back(guardian.clone());
         env.storage().persistent().set(&key_approvals, &approvals);
+        access_control::extend_persistent_ttl(&env, &key_approvals);
 
         env.events().publish(
             (symbol_short!("EMERG"), symbol_short!("APPROVE")),
@@ -2505,6 +3271,7 @@ impl InheritanceContract {
             env.storage()
                 .persistent()
                 .set(&key_access, &emergency_access);
+            access_control::extend_persistent_ttl(&env, &key_access);
 
             env.events().publish(
                 (symbol_short!("EMERG"), symbol_short!("ACTIV")),
@@ -2533,6 +3300,7 @@ impl InheritanceContract {
     /// # Returns
     /// The EmergencyAccessRecord if emergency access is active, None otherwise
     pub fn get_emergency_access(env: Env, plan_id: u64) -> Option<EmergencyAccessRecord> {
+        access_control::extend_instance_ttl(&env);
         if Self::check_and_expire_emergency_access(&env, plan_id) {
             let key = DataKey::EmergencyAccess(plan_id);
             env.storage().persistent().get(&key)
@@ -2581,6 +3349,7 @@ impl InheritanceContract {
         owner: Address,
         plan_id: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         // Require owner authorization
         owner.require_auth();
 
@@ -2623,6 +3392,7 @@ impl InheritanceContract {
         user: Address,
         plan_id: u64,
     ) -> Result<InheritancePlan, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         user.require_auth();
 
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -2642,14 +3412,10 @@ impl InheritanceContract {
 
     /// Retrieve all deactivated plans for a user
     pub fn get_user_deactivated_plans(env: Env, user: Address) -> Vec<InheritancePlan> {
+        access_control::extend_instance_ttl(&env);
         user.require_auth();
 
-        let key = DataKey::UserPlans(user.clone());
-        let user_plan_ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
+        let user_plan_ids = plan_index::ids(&env, &PlanIndex::Owner(user));
 
         let mut deactivated_plans = Vec::new(&env);
 
@@ -2664,11 +3430,14 @@ impl InheritanceContract {
         deactivated_plans
     }
 
-    /// Retrieve all deactivated plans (Admin only)
+    /// Page through deactivated plans (Admin only). See `get_all_plans`.
     pub fn get_all_deactivated_plans(
         env: Env,
         admin: Address,
-    ) -> Result<Vec<InheritancePlan>, InheritanceError> {
+        start_after: Option<u64>,
+        limit: u32,
+    ) -> Result<PlanPage, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         admin.require_auth();
 
         // Verify admin
@@ -2681,24 +3450,12 @@ impl InheritanceContract {
             return Err(InheritanceError::Unauthorized);
         }
 
-        let key = DataKey::DeactivatedPlans;
-        let deactivated_ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
-
-        let mut plans = Vec::new(&env);
-        for plan_id in deactivated_ids.iter() {
-            if let Some(plan) = Self::get_plan(&env, plan_id) {
-                // Double check it's inactive just in case
-                if !plan.is_active {
-                    plans.push_back(plan);
-                }
-            }
-        }
-
-        Ok(plans)
+        Ok(Self::load_plan_page(
+            &env,
+            PlanIndex::Deactivated,
+            start_after,
+            limit,
+        ))
     }
 
     /// Retrieve a specific claimed plan belonging to the authenticated user
@@ -2707,6 +3464,7 @@ impl InheritanceContract {
         user: Address,
         plan_id: u64,
     ) -> Result<InheritancePlan, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         user.require_auth();
 
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -2715,14 +3473,7 @@ impl InheritanceContract {
             return Err(InheritanceError::Unauthorized);
         }
 
-        let key = DataKey::UserClaimedPlans(user);
-        let user_plans: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
-
-        if !user_plans.contains(plan_id) {
+        if !plan_index::contains(&env, &PlanIndex::OwnerClaimed(user), plan_id) {
             return Err(InheritanceError::PlanNotClaimed);
         }
 
@@ -2731,14 +3482,10 @@ impl InheritanceContract {
 
     /// Retrieve all claimed plans for the authenticated user
     pub fn get_user_claimed_plans(env: Env, user: Address) -> Vec<InheritancePlan> {
+        access_control::extend_instance_ttl(&env);
         user.require_auth();
 
-        let key = DataKey::UserClaimedPlans(user);
-        let user_plan_ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
+        let user_plan_ids = plan_index::ids(&env, &PlanIndex::OwnerClaimed(user));
 
         let mut plans = Vec::new(&env);
         for id in user_plan_ids.iter() {
@@ -2749,27 +3496,22 @@ impl InheritanceContract {
         plans
     }
 
-    /// Retrieve all claimed plans across all users; accessible only by administrators
+    /// Page through plans with at least one claim across all users;
+    /// accessible only by administrators. See `get_all_plans`.
     pub fn get_all_claimed_plans(
         env: Env,
         admin: Address,
-    ) -> Result<Vec<InheritancePlan>, InheritanceError> {
+        start_after: Option<u64>,
+        limit: u32,
+    ) -> Result<PlanPage, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
-
-        let key = DataKey::AllClaimedPlans;
-        let all_plan_ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
-
-        let mut plans = Vec::new(&env);
-        for id in all_plan_ids.iter() {
-            if let Some(plan) = Self::get_plan(&env, id) {
-                plans.push_back(plan);
-            }
-        }
-        Ok(plans)
+        Ok(Self::load_plan_page(
+            &env,
+            PlanIndex::Claimed,
+            start_after,
+            limit,
+        ))
     }
 
     // ───────────────────────────────────────────
@@ -2784,6 +3526,7 @@ impl InheritanceContract {
     fn set_trigger_info(env: &Env, plan_id: u64, info: &InheritanceTriggerInfo) {
         let key = DataKey::InheritanceTrigger(plan_id);
         env.storage().persistent().set(&key, info);
+        access_control::extend_persistent_ttl(env, &key);
     }
 
     fn get_trigger_config(env: &Env, plan_id: u64) -> Option<TriggerConfig> {
@@ -2796,9 +3539,11 @@ impl InheritanceContract {
         env.storage()
             .persistent()
             .set(&DataKey::TriggerConditions(plan_id), config);
+        access_control::extend_persistent_ttl(env, &DataKey::TriggerConditions(plan_id));
     }
 
     pub fn check_trigger_conditions(env: Env, plan_id: u64) -> bool {
+        access_control::extend_instance_ttl(&env);
         let config = match Self::get_trigger_config(&env, plan_id) {
             Some(c) => c,
             None => return false,
@@ -2836,6 +3581,7 @@ impl InheritanceContract {
     }
 
     pub fn get_trigger_conditions(env: Env, plan_id: u64) -> Option<TriggerConfig> {
+        access_control::extend_instance_ttl(&env);
         Self::get_trigger_config(&env, plan_id)
     }
 
@@ -2848,6 +3594,7 @@ impl InheritanceContract {
         inactivity_period: u64,
         oracle_address: Option<Address>,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != owner {
@@ -2882,6 +3629,7 @@ impl InheritanceContract {
         plan_id: u64,
         trigger_date: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != owner {
@@ -2923,6 +3671,7 @@ impl InheritanceContract {
         plan_id: u64,
         period_seconds: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != owner {
@@ -2966,6 +3715,7 @@ impl InheritanceContract {
         plan_id: u64,
         oracle_address: Address,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != owner {
@@ -3001,6 +3751,7 @@ impl InheritanceContract {
         plan_id: u64,
         oracle_address: Address,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != owner {
@@ -3031,6 +3782,7 @@ impl InheritanceContract {
     }
 
     pub fn record_activity(env: Env, owner: Address, plan_id: u64) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != owner {
@@ -3048,6 +3800,7 @@ impl InheritanceContract {
         oracle: Address,
         plan_id: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         oracle.require_auth();
         let mut config =
             Self::get_trigger_config(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -3065,6 +3818,7 @@ impl InheritanceContract {
         oracle: Address,
         plan_id: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         oracle.require_auth();
         let mut config =
             Self::get_trigger_config(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -3078,6 +3832,7 @@ impl InheritanceContract {
     }
 
     pub fn auto_trigger_check(env: Env, plan_id: u64) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         if !Self::check_trigger_conditions(env.clone(), plan_id) {
             return Ok(());
         }
@@ -3141,6 +3896,7 @@ impl InheritanceContract {
         caller: Address,
         plan_id: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::check_not_paused(&env);
         Self::enter_guard(&env);
         // Authorization check: Admin OR Owner OR Trusted Contact with active emergency access
@@ -3255,6 +4011,7 @@ impl InheritanceContract {
         plan_id: u64,
         recall_amount: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
 
         let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -3322,6 +4079,7 @@ impl InheritanceContract {
         admin: Address,
         plan_id: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
 
         let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -3367,43 +4125,374 @@ impl InheritanceContract {
 
     /// Query the inheritance trigger status for a plan.
     pub fn get_inheritance_trigger(env: Env, plan_id: u64) -> Option<InheritanceTriggerInfo> {
+        access_control::extend_instance_ttl(&env);
         Self::get_trigger_info(&env, plan_id)
     }
 
-    /// Calculate the claimable amount for a plan, accounting for outstanding loans.
-    /// Returns the amount available to beneficiaries after any loan deductions.
-    pub fn get_claimable_amount(env: Env, plan_id: u64) -> Result<u64, InheritanceError> {
+    /// Per-asset breakdown of what a plan holds for its beneficiaries: the
+    /// primary balance net of outstanding loans first, then each extra asset.
+    pub fn get_claimable_amount(
+        env: Env,
+        plan_id: u64,
+    ) -> Result<Vec<AssetAmount>, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
-        Ok(plan.total_amount.saturating_sub(plan.total_loaned))
-    }
-
-    // ───────────────────────────────────────────
-    // Contract Upgrade Functions
-    // ───────────────────────────────────────────
 
-    /// Get the current contract version.
-    pub fn version(env: Env) -> u32 {
-        env.storage()
-            .instance()
-            .get(&DataKey::Version)
-            .unwrap_or(CONTRACT_VERSION)
+        let mut breakdown = Vec::new(&env);
+        breakdown.push_back(AssetAmount {
+            asset_code: plan.asset_type.clone(),
+            token: assets::primary_token(&env, plan_id)
+                .or_else(|| assets::token_for_code(&env, &plan.asset_type)),
+            amount: plan.total_amount.saturating_sub(plan.total_loaned),
+        });
+        for token in assets::plan_assets(&env, plan_id).iter() {
+            breakdown.push_back(AssetAmount {
+                asset_code: assets::asset_code(&env, &token)
+                    .unwrap_or_else(|| Symbol::new(&env, "UNLISTED")),
+                amount: assets::balance(&env, plan_id, &token),
+                token: Some(token),
+            });
+        }
+        Ok(breakdown)
     }
 
-    /// Upgrade the contract to a new WASM binary.
-    ///
-    /// # Arguments
-    /// * `env` - The environment
-    /// * `admin` - The admin address (must be the initialized admin)
-    /// * `new_wasm_hash` - The hash of the new WASM binary to deploy
-    ///
-    /// # Errors
-    /// - `AdminNotSet` if admin has not been initialized
-    /// - `NotAdmin` if the caller is not the admin
-    pub fn upgrade(
+    /// Per-asset breakdown of what beneficiary `beneficiary_index` would
+    /// receive of the plan's extra assets if they claimed now.
+    pub fn get_beneficiary_assets(
         env: Env,
-        admin: Address,
-        new_wasm_hash: BytesN<32>,
+        plan_id: u64,
+        beneficiary_index: u32,
+    ) -> Result<Vec<AssetAmount>, AssetError> {
+        access_control::extend_instance_ttl(&env);
+        let plan = Self::get_plan(&env, plan_id).ok_or(AssetError::PlanNotFound)?;
+        let beneficiary = plan
+            .beneficiaries
+            .get(beneficiary_index)
+            .ok_or(AssetError::InvalidBeneficiaryIndex)?;
+
+        let mut breakdown = Vec::new(&env);
+        for token in assets::plan_assets(&env, plan_id).iter() {
+            let amount = if beneficiary.is_claimed {
+                0
+            } else {
+                let bp = assets::allocation_bp(
+                    &env,
+                    plan_id,
+                    &token,
+                    &beneficiary.hashed_email,
+                    beneficiary.allocation_bp,
+                );
+                assets::entitlement(&env, plan_id, &token, bp)
+            };
+            breakdown.push_back(AssetAmount {
+                asset_code: assets::asset_code(&env, &token)
+                    .unwrap_or_else(|| Symbol::new(&env, "UNLISTED")),
+                token: Some(token),
+                amount,
+            });
+        }
+        Ok(breakdown)
+    }
+
+    /// Add `token` to the asset whitelist under `asset_code`, or delist it
+    /// when `asset_code` is `None` (admin-only). Delisted tokens take no new
+    /// deposits; balances already held can still be withdrawn and claimed.
+    pub fn set_whitelisted_asset(
+        env: Env,
+        admin: Address,
+        token: Address,
+        asset_code: Option<Symbol>,
+    ) -> Result<(), AssetError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role(&env, &admin, Role::Admin, AssetError::NotAdmin)?;
+
+        let mut whitelist = assets::whitelist(&env);
+        match asset_code.clone() {
+            Some(code) => {
+                if whitelist.iter().any(|(t, c)| c == code && t != token) {
+                    return Err(AssetError::AssetCodeInUse);
+                }
+                whitelist.set(token.clone(), code);
+            }
+            None => {
+                whitelist.remove(token.clone());
+            }
+        }
+        assets::set_whitelist(&env, &whitelist);
+
+        env.events().publish(
+            (symbol_short!("ASSET"), symbol_short!("WHITELIST")),
+            (token, asset_code),
+        );
+        Ok(())
+    }
+
+    /// Record `token` as the primary token of plans created before primary
+    /// tokens were stored (admin-only). Plans in the page with no recorded
+    /// token whose asset code is the one `token` is listed under ("USDC" if
+    /// it is unlisted) get `token`. Returns the cursor for the next call, or
+    /// `None` once every plan has been visited.
+    pub fn backfill_primary_tokens(
+        env: Env,
+        admin: Address,
+        token: Address,
+        start_after: Option<u64>,
+        limit: u32,
+    ) -> Result<Option<u64>, AssetError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role(&env, &admin, Role::Admin, AssetError::NotAdmin)?;
+
+        let asset_code =
+            assets::asset_code(&env, &token).unwrap_or_else(|| Symbol::new(&env, "USDC"));
+        let page = plan_index::page(
+            &env,
+            &PlanIndex::All,
+            Self::get_next_plan_id(&env),
+            start_after,
+            limit,
+            |plan_id| {
+                Self::get_plan(&env, plan_id).filter(|plan| {
+                    plan.asset_type == asset_code && assets::primary_token(&env, plan_id).is_none()
+                })
+            },
+        );
+        for entry in page.plans.iter() {
+            assets::set_primary_token(&env, entry.plan_id, &token);
+        }
+        Ok(page.next_cursor)
+    }
+
+    pub fn get_whitelisted_assets(env: Env) -> Map<Address, Symbol> {
+        access_control::extend_instance_ttl(&env);
+        assets::whitelist(&env)
+    }
+
+    /// Override how `token` is split between the plan's beneficiaries:
+    /// `allocation_bps` holds one entry per beneficiary in index order and
+    /// may total at most 10000. An empty list restores the pro-rata split by
+    /// each beneficiary's `allocation_bp`. Locked once any of the token has
+    /// been paid out.
+    pub fn set_asset_allocation(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        token: Address,
+        allocation_bps: Vec<u32>,
+    ) -> Result<(), AssetError> {
+        access_control::extend_instance_ttl(&env);
+        owner.require_auth();
+        Self::check_not_paused(&env);
+
+        let plan = Self::get_plan(&env, plan_id).ok_or(AssetError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(AssetError::Unauthorized);
+        }
+        if !assets::is_extra_asset(&env, plan_id, &plan, &token) {
+            return Err(AssetError::TokenNotWhitelisted);
+        }
+        if assets::distributed(&env, plan_id, &token) > 0 {
+            return Err(AssetError::AllocationLocked);
+        }
+
+        if allocation_bps.is_empty() {
+            assets::set_allocation(&env, plan_id, &token, None);
+        } else {
+            if allocation_bps.len() != plan.beneficiaries.len() {
+                return Err(AssetError::InvalidAllocation);
+            }
+            let mut bps = Map::new(&env);
+            let mut total = 0u32;
+            for (beneficiary, bp) in plan.beneficiaries.iter().zip(allocation_bps.iter()) {
+                total = total.saturating_add(bp);
+                bps.set(beneficiary.hashed_email, bp);
+            }
+            if total > 10000 {
+                return Err(AssetError::InvalidAllocation);
+            }
+            assets::set_allocation(&env, plan_id, &token, Some(bps));
+        }
+
+        env.events().publish(
+            (symbol_short!("ASSET"), symbol_short!("ALLOC")),
+            (plan_id, token, allocation_bps),
+        );
+        Ok(())
+    }
+
+    /// The basis points of `token` each beneficiary is owed, in index order.
+    pub fn get_asset_allocation(
+        env: Env,
+        plan_id: u64,
+        token: Address,
+    ) -> Result<Vec<u32>, AssetError> {
+        access_control::extend_instance_ttl(&env);
+        let plan = Self::get_plan(&env, plan_id).ok_or(AssetError::PlanNotFound)?;
+        let mut bps = Vec::new(&env);
+        for beneficiary in plan.beneficiaries.iter() {
+            bps.push_back(assets::allocation_bp(
+                &env,
+                plan_id,
+                &token,
+                &beneficiary.hashed_email,
+                beneficiary.allocation_bp,
+            ));
+        }
+        Ok(bps)
+    }
+
+    /// Set the release condition on a beneficiary, e.g. an unlock date or
+    /// staged releases for a minor, or release on a trustee's attestation
+    /// (`Immediate` clears it). Locked once anything has been released to them.
+    pub fn set_beneficiary_vesting(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        beneficiary_index: u32,
+        release: VestingRelease,
+    ) -> Result<(), VestingError> {
+        access_control::extend_instance_ttl(&env);
+        owner.require_auth();
+        Self::check_not_paused(&env);
+
+        let plan = Self::get_plan(&env, plan_id).ok_or(VestingError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(VestingError::Unauthorized);
+        }
+        let beneficiary = plan
+            .beneficiaries
+            .get(beneficiary_index)
+            .ok_or(VestingError::InvalidBeneficiaryIndex)?;
+        if beneficiary.is_claimed
+            || vesting::progress(&env, plan_id, &beneficiary.hashed_email).is_some()
+        {
+            return Err(VestingError::ScheduleLocked);
+        }
+        if !vesting::is_valid(&release) {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        vesting::set_release(&env, plan_id, &beneficiary.hashed_email, &release);
+        env.events().publish(
+            (symbol_short!("VESTING"), symbol_short!("SET")),
+            (plan_id, beneficiary.hashed_email, release),
+        );
+        Ok(())
+    }
+
+    /// Release a trustee-gated beneficiary's entitlement. Only the trustee
+    /// named in their release condition may attest.
+    pub fn attest_vesting_release(
+        env: Env,
+        trustee: Address,
+        plan_id: u64,
+        beneficiary_index: u32,
+    ) -> Result<(), VestingError> {
+        access_control::extend_instance_ttl(&env);
+        trustee.require_auth();
+        Self::check_not_paused(&env);
+
+        let plan = Self::get_plan(&env, plan_id).ok_or(VestingError::PlanNotFound)?;
+        let beneficiary = plan
+            .beneficiaries
+            .get(beneficiary_index)
+            .ok_or(VestingError::InvalidBeneficiaryIndex)?;
+        match vesting::release(&env, plan_id, &beneficiary.hashed_email) {
+            Some(VestingRelease::Trustee(named)) if named == trustee => {}
+            _ => return Err(VestingError::NotTrustee),
+        }
+        if vesting::attested_at(&env, plan_id, &beneficiary.hashed_email).is_some() {
+            return Err(VestingError::AlreadyAttested);
+        }
+
+        vesting::record_attestation(&env, plan_id, &beneficiary.hashed_email);
+        env.events().publish(
+            (symbol_short!("VESTING"), symbol_short!("ATTEST")),
+            vesting::VestingAttestedEvent {
+                plan_id,
+                hashed_email: beneficiary.hashed_email,
+                trustee,
+            },
+        );
+        Ok(())
+    }
+
+    /// Locked vs unlocked amounts for a beneficiary. Until their first
+    /// release the entitlement is estimated from the current plan balance.
+    pub fn get_beneficiary_vesting(
+        env: Env,
+        plan_id: u64,
+        beneficiary_index: u32,
+    ) -> Result<VestingStatus, VestingError> {
+        access_control::extend_instance_ttl(&env);
+        let plan = Self::get_plan(&env, plan_id).ok_or(VestingError::PlanNotFound)?;
+        let beneficiary = plan
+            .beneficiaries
+            .get(beneficiary_index)
+            .ok_or(VestingError::InvalidBeneficiaryIndex)?;
+        let hashed_email = beneficiary.hashed_email.clone();
+        let now = env.ledger().timestamp();
+
+        let release =
+            vesting::release(&env, plan_id, &hashed_email).unwrap_or(VestingRelease::Immediate);
+        let progress = vesting::progress(&env, plan_id, &hashed_email).unwrap_or_else(|| {
+            let entitlement = if beneficiary.is_claimed {
+                0
+            } else {
+                vesting::unlocked_amount(plan.total_amount, beneficiary.allocation_bp)
+            };
+            VestingProgress {
+                entitlement,
+                released: 0,
+            }
+        });
+        let unlocked_total = vesting::unlocked_amount(
+            progress.entitlement,
+            vesting::unlocked_bp(&env, plan_id, &hashed_email, &release, now),
+        );
+
+        Ok(VestingStatus {
+            plan_id,
+            beneficiary_index,
+            next_unlock_at: vesting::next_unlock_at(&release, now),
+            release,
+            entitlement: progress.entitlement,
+            released: progress.released,
+            unlocked: unlocked_total.saturating_sub(progress.released),
+            locked: progress.entitlement.saturating_sub(unlocked_total),
+        })
+    }
+
+    // ───────────────────────────────────────────
+    // Contract Upgrade Functions
+    // ───────────────────────────────────────────
+
+    /// Get the current contract version.
+    pub fn version(env: Env) -> u32 {
+        access_control::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .get(&DataKey::Version)
+            .unwrap_or(CONTRACT_VERSION)
+    }
+
+    /// Upgrade the contract to a new WASM binary.
+    ///
+    /// # Arguments
+    /// * `env` - The environment
+    /// * `admin` - The admin address (must be the initialized admin)
+    /// * `new_wasm_hash` - The hash of the new WASM binary to deploy
+    ///
+    /// # Errors
+    /// - `AdminNotSet` if admin has not been initialized
+    /// - `NotAdmin` if the caller is not the admin
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         // Only the contract admin can trigger an upgrade
         Self::require_admin(&env, &admin)?;
 
@@ -3451,6 +4540,7 @@ impl InheritanceContract {
     /// * `env` - The environment
     /// * `admin` - The admin address (must be the initialized admin)
     pub fn migrate(env: Env, admin: Address) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
 
         let stored_version: u32 = env.storage().instance().get(&DataKey::Version).unwrap_or(0);
@@ -3461,10 +4551,32 @@ impl InheritanceContract {
         }
 
         // ── Version-specific migrations go here ──
-        // Example for a future migration:
-        // if stored_version < 2 {
-        //     // migrate from v1 → v2 schema changes
-        // }
+        if stored_version < 2 {
+            // v1 → v2: claims only accept the XDR-based email hash. The
+            // contract never sees plaintext emails, so beneficiaries still
+            // carrying the legacy hash are rehashed by the backend through
+            // `rehash_beneficiary_email`.
+            env.events().publish(
+                (symbol_short!("BENEFIC"), symbol_short!("MIGRATE")),
+                Self::get_next_plan_id(&env).saturating_sub(1),
+            );
+        }
+        if stored_version < 3 {
+            // v2 → v3: payout details move from plaintext to envelopes sealed
+            // to the settlement key. The contract cannot encrypt, so existing
+            // plans are resealed off-chain: the backend pages through
+            // `get_unsealed_plans` and submits `reseal_payout_details`.
+            env.events().publish(
+                (symbol_short!("PAYOUT"), symbol_short!("MIGRATE")),
+                Self::get_next_plan_id(&env).saturating_sub(1),
+            );
+        }
+
+        if stored_version < 4 {
+            // v3 → v4: claims must come from a bound identity. Unbound
+            // beneficiaries are bound by their owners before claiming.
+            claim_auth::set_legacy_claim_codes_enabled(&env, false);
+        }
 
         // Update stored version to current
         env.storage()
@@ -3481,6 +4593,176 @@ impl InheritanceContract {
         Ok(())
     }
 
+    /// Replace a beneficiary's legacy email hash with `hash_string(email)`,
+    /// moving the claim record, binding, vesting records and asset
+    /// allocations keyed by it. A no-op if the beneficiary already carries
+    /// the current hash.
+    ///
+    /// # Errors
+    /// - `NotAdmin` if the caller is not the admin
+    /// - `PlanNotFound` / `InvalidBeneficiaryIndex` if there is no such beneficiary
+    /// - `InvalidBeneficiaryData` if `email` does not match the stored legacy
+    ///   hash, another beneficiary already has the new hash, or the
+    ///   beneficiary has an email commitment (which covers the old hash and
+    ///   must be re-issued by the owner)
+    pub fn rehash_beneficiary_email(
+        env: Env,
+        admin: Address,
+        plan_id: u64,
+        beneficiary_index: u32,
+        email: String,
+    ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin)?;
+
+        let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        let mut beneficiary = plan
+            .beneficiaries
+            .get(beneficiary_index)
+            .ok_or(InheritanceError::InvalidBeneficiaryIndex)?;
+        let legacy = beneficiary.hashed_email.clone();
+        let hashed = Self::hash_string(&env, email.clone());
+        if legacy == hashed {
+            return Ok(());
+        }
+        if legacy != Self::legacy_hash_string(&env, &email)
+            || plan.beneficiaries.iter().any(|b| b.hashed_email == hashed)
+            || matches!(
+                claim_auth::binding(&env, plan_id, &legacy),
+                Some(BeneficiaryBinding::Commitment(_))
+            )
+        {
+            return Err(InheritanceError::InvalidBeneficiaryData);
+        }
+
+        let legacy_claim = Self::claim_key(&env, plan_id, &legacy);
+        if let Some(claim) = env
+            .storage()
+            .persistent()
+            .get::<_, ClaimRecord>(&legacy_claim)
+        {
+            let claim_key = Self::claim_key(&env, plan_id, &hashed);
+            env.storage().persistent().set(&claim_key, &claim);
+            access_control::extend_persistent_ttl(&env, &claim_key);
+            env.storage().persistent().remove(&legacy_claim);
+        }
+        claim_auth::rekey_binding(&env, plan_id, &legacy, &hashed);
+        vesting::rekey(&env, plan_id, &legacy, &hashed);
+        assets::rekey_allocations(&env, plan_id, &legacy, &hashed);
+
+        beneficiary.hashed_email = hashed.clone();
+        plan.beneficiaries.set(beneficiary_index, beneficiary);
+        Self::store_plan(&env, plan_id, &plan);
+
+        env.events().publish(
+            (symbol_short!("BENEFIC"), symbol_short!("REHASH")),
+            (plan_id, beneficiary_index, hashed),
+        );
+        Ok(())
+    }
+
+    fn require_payout_details_admin(env: &Env, admin: &Address) -> Result<(), PayoutDetailsError> {
+        admin.require_auth();
+        access_control::require_role(env, admin, Role::Admin, PayoutDetailsError::NotAdmin)
+    }
+
+    /// Register (or rotate to) the settlement key that payout details must
+    /// be sealed to. Key ids only move forward, so a retired key can never
+    /// be made current again.
+    pub fn set_settlement_key(
+        env: Env,
+        admin: Address,
+        key_id: u32,
+    ) -> Result<(), PayoutDetailsError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_payout_details_admin(&env, &admin)?;
+        if payout_details::settlement_key_id(&env).is_some_and(|current| key_id <= current) {
+            return Err(PayoutDetailsError::StaleSettlementKey);
+        }
+        payout_details::set_settlement_key_id(&env, key_id);
+        env.events()
+            .publish((symbol_short!("PAYOUT"), symbol_short!("KEY")), key_id);
+        Ok(())
+    }
+
+    pub fn get_settlement_key_id(env: Env) -> Option<u32> {
+        access_control::extend_instance_ttl(&env);
+        payout_details::settlement_key_id(&env)
+    }
+
+    /// Page through plans whose payout details are plaintext or sealed to a
+    /// retired key (admin-only). Cursor semantics match `get_all_plans`.
+    pub fn get_unsealed_plans(
+        env: Env,
+        admin: Address,
+        start_after: Option<u64>,
+        limit: u32,
+    ) -> Result<PlanPage, PayoutDetailsError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_payout_details_admin(&env, &admin)?;
+        Ok(plan_index::page(
+            &env,
+            &PlanIndex::All,
+            Self::get_next_plan_id(&env),
+            start_after,
+            limit,
+            |plan_id| {
+                Self::get_plan(&env, plan_id)
+                    .filter(|plan| payout_details::needs_reseal(&env, plan))
+            },
+        ))
+    }
+
+    /// Replace a plan's payout details with envelopes sealed to the current
+    /// settlement key, one per beneficiary in order (admin-only). Details
+    /// already sealed to the current key must be passed back unchanged.
+    pub fn reseal_payout_details(
+        env: Env,
+        admin: Address,
+        plan_id: u64,
+        sealed: Vec<Bytes>,
+    ) -> Result<(), PayoutDetailsError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_payout_details_admin(&env, &admin)?;
+        let key_id = payout_details::settlement_key_id(&env)
+            .ok_or(PayoutDetailsError::SettlementKeyNotSet)?;
+
+        let mut plan = Self::get_plan(&env, plan_id).ok_or(PayoutDetailsError::PlanNotFound)?;
+        if sealed.len() != plan.beneficiaries.len() {
+            return Err(PayoutDetailsError::LengthMismatch);
+        }
+        if !payout_details::needs_reseal(&env, &plan) {
+            return Err(PayoutDetailsError::AlreadySealed);
+        }
+
+        for (index, details) in sealed.iter().enumerate() {
+            let index = index as u32;
+            if !payout_details::is_sealed_to_current(&env, &details) {
+                return Err(PayoutDetailsError::InvalidEnvelope);
+            }
+            let mut beneficiary = plan.beneficiaries.get(index).unwrap();
+            if payout_details::is_sealed_to_current(&env, &beneficiary.bank_account) {
+                if beneficiary.bank_account != details {
+                    return Err(PayoutDetailsError::AlreadySealed);
+                }
+                continue;
+            }
+            beneficiary.bank_account = details;
+            plan.beneficiaries.set(index, beneficiary);
+        }
+        Self::store_plan(&env, plan_id, &plan);
+
+        env.events().publish(
+            (symbol_short!("PAYOUT"), symbol_short!("RESEAL")),
+            payout_details::PayoutDetailsResealedEvent {
+                plan_id,
+                key_id,
+                beneficiary_count: plan.beneficiaries.len(),
+            },
+        );
+        Ok(())
+    }
+
     // ── Will Management System (Issues #314–#317) ──
 
     /// Store a SHA-256 hash of a will document on-chain, mapped to a plan_id.
@@ -3490,6 +4772,7 @@ impl InheritanceContract {
         plan_id: u64,
         will_hash: BytesN<32>,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
 
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -3508,6 +4791,7 @@ impl InheritanceContract {
         }
 
         env.storage().persistent().set(&key, &will_hash);
+        access_control::extend_persistent_ttl(&env, &key);
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("STORED")),
@@ -3519,7 +4803,9 @@ impl InheritanceContract {
 
     /// Retrieve the stored will hash for a plan.
     pub fn get_will_hash(env: Env, plan_id: u64) -> Option<BytesN<32>> {
+        access_control::extend_instance_ttl(&env);
         let key = DataKey::WillHash(plan_id);
+        access_control::extend_persistent_ttl(&env, &key);
         env.storage().persistent().get(&key)
     }
 
@@ -3531,9 +4817,10 @@ impl InheritanceContract {
         plan_id: u64,
         will_hash: BytesN<32>,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
 
-        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::VaultNotFound)?;
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != owner {
             return Err(InheritanceError::Unauthorized);
         }
@@ -3549,6 +4836,7 @@ impl InheritanceContract {
         }
 
         env.storage().persistent().set(&key, &will_hash);
+        access_control::extend_persistent_ttl(&env, &key);
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("LINKED")),
@@ -3560,7 +4848,9 @@ impl InheritanceContract {
 
     /// Retrieve the will hash linked to a vault.
     pub fn get_vault_will(env: Env, plan_id: u64) -> Option<BytesN<32>> {
+        access_control::extend_instance_ttl(&env);
         let key = DataKey::VaultWill(plan_id);
+        access_control::extend_persistent_ttl(&env, &key);
         env.storage().persistent().get(&key)
     }
 
@@ -3571,6 +4861,7 @@ impl InheritanceContract {
         plan_id: u64,
         will_beneficiaries: Vec<(BytesN<32>, u32)>,
     ) -> Result<bool, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
 
         let plan_bens = &plan.beneficiaries;
@@ -3601,6 +4892,7 @@ impl InheritanceContract {
         // Store verification result
         let ver_key = DataKey::BeneficiaryVerification(plan_id);
         env.storage().persistent().set(&ver_key, &status);
+        access_control::extend_persistent_ttl(&env, &ver_key);
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("VERIFY")),
@@ -3612,6 +4904,7 @@ impl InheritanceContract {
 
     /// Get the last beneficiary verification status for a plan.
     pub fn get_verification_status(env: Env, plan_id: u64) -> Option<bool> {
+        access_control::extend_instance_ttl(&env);
         let key = DataKey::BeneficiaryVerification(plan_id);
         env.storage().persistent().get(&key)
     }
@@ -3624,6 +4917,7 @@ impl InheritanceContract {
         plan_id: u64,
         will_hash: BytesN<32>,
     ) -> Result<u32, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
 
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -3650,6 +4944,7 @@ impl InheritanceContract {
         let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
         let new_version = current_count + 1;
         env.storage().persistent().set(&count_key, &new_version);
+        access_control::extend_persistent_ttl(&env, &count_key);
 
         // Deactivate previously active version if any
         let active_key = DataKey::ActiveWillVersion(plan_id);
@@ -3662,6 +4957,7 @@ impl InheritanceContract {
             {
                 prev_ver.is_active = false;
                 env.storage().persistent().set(&prev_key, &prev_ver);
+                access_control::extend_persistent_ttl(&env, &prev_key);
             }
         }
 
@@ -3674,13 +4970,16 @@ impl InheritanceContract {
         };
         let ver_key = DataKey::WillVersion(plan_id, new_version);
         env.storage().persistent().set(&ver_key, &version_info);
+        access_control::extend_persistent_ttl(&env, &ver_key);
 
         // Set as active
         env.storage().persistent().set(&active_key, &new_version);
+        access_control::extend_persistent_ttl(&env, &active_key);
 
         // Update VaultWill link to point to latest will hash
         let vault_will_key = DataKey::VaultWill(plan_id);
         env.storage().persistent().set(&vault_will_key, &will_hash);
+        access_control::extend_persistent_ttl(&env, &vault_will_key);
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("VERSION")),
@@ -3703,15 +5002,20 @@ impl InheritanceContract {
 
     /// Get a specific will version for a plan.
     pub fn get_will_version(env: Env, plan_id: u64, version: u32) -> Option<WillVersionInfo> {
+        access_control::extend_instance_ttl(&env);
         let key = DataKey::WillVersion(plan_id, version);
+        access_control::extend_persistent_ttl(&env, &key);
         env.storage().persistent().get(&key)
     }
 
     /// Get the currently active will version for a plan.
     pub fn get_active_will_version(env: Env, plan_id: u64) -> Option<WillVersionInfo> {
+        access_control::extend_instance_ttl(&env);
         let active_key = DataKey::ActiveWillVersion(plan_id);
         if let Some(active_ver) = env.storage().persistent().get::<_, u32>(&active_key) {
             let key = DataKey::WillVersion(plan_id, active_ver);
+            access_control::extend_persistent_ttl(&env, &active_key);
+            access_control::extend_persistent_ttl(&env, &key);
             env.storage().persistent().get(&key)
         } else {
             None
@@ -3720,6 +5024,7 @@ impl InheritanceContract {
 
     /// Get the total number of will versions for a plan.
     pub fn get_will_version_count(env: Env, plan_id: u64) -> u32 {
+        access_control::extend_instance_ttl(&env);
         let key = DataKey::WillVersionCount(plan_id);
         env.storage().persistent().get(&key).unwrap_or(0)
     }
@@ -3737,6 +5042,7 @@ impl InheritanceContract {
         vault_id: u64,
         will_hash: BytesN<32>,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
 
         // Verify the plan exists and caller is the owner
@@ -3768,6 +5074,7 @@ impl InheritanceContract {
 
         // Mark signature as used
         env.storage().persistent().set(&used_key, &true);
+        access_control::extend_persistent_ttl(&env, &used_key);
 
         // Store the signature proof
         let proof = WillSignatureProof {
@@ -3780,6 +5087,7 @@ impl InheritanceContract {
         env.storage()
             .persistent()
             .set(&DataKey::WillSignature(vault_id), &proof);
+        access_control::extend_persistent_ttl(&env, &DataKey::WillSignature(vault_id));
 
         // Emit WillSigned event
         env.events().publish(
@@ -3795,9 +5103,10 @@ impl InheritanceContract {
 
     /// Retrieve the stored will signature proof for a vault.
     pub fn get_will_signature(env: Env, vault_id: u64) -> Option<WillSignatureProof> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::WillSignature(vault_id))
+        access_control::extend_instance_ttl(&env);
+        let key = DataKey::WillSignature(vault_id);
+        access_control::extend_persistent_ttl(&env, &key);
+        env.storage().persistent().get(&key)
     }
 
     /// Create a new legacy message with metadata stored on-chain
@@ -3816,6 +5125,7 @@ impl InheritanceContract {
         creator: Address,
         params: CreateLegacyMessageParams,
     ) -> Result<u64, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         // Verify vault/plan exists and creator is the owner
         let plan = Self::get_plan(&env, params.vault_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != creator {
@@ -3852,6 +5162,7 @@ impl InheritanceContract {
         env.storage()
             .persistent()
             .set(&DataKey::LegacyMessage(message_id), &message);
+        access_control::extend_persistent_ttl(&env, &DataKey::LegacyMessage(message_id));
 
         // Add message to vault's message list
         let mut vault_messages: Vec<u64> = env
@@ -3863,11 +5174,13 @@ impl InheritanceContract {
         env.storage()
             .persistent()
             .set(&DataKey::VaultMessages(params.vault_id), &vault_messages);
+        access_control::extend_persistent_ttl(&env, &DataKey::VaultMessages(params.vault_id));
 
         // Increment next message ID
         env.storage()
             .persistent()
             .set(&DataKey::NextMessageId, &(message_id + 1));
+        access_control::extend_persistent_ttl(&env, &DataKey::NextMessageId);
 
         // Emit event
         env.events().publish(
@@ -3888,6 +5201,7 @@ impl InheritanceContract {
         message_id: u64,
         params: CreateLegacyMessageParams,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         creator.require_auth();
 
         let mut message = env
@@ -3915,6 +5229,7 @@ impl InheritanceContract {
         env.storage()
             .persistent()
             .set(&DataKey::LegacyMessage(message_id), &message);
+        access_control::extend_persistent_ttl(&env, &DataKey::LegacyMessage(message_id));
 
         env.events().publish(
             (Symbol::new(&env, "message_updated"), message.vault_id),
@@ -3932,6 +5247,7 @@ impl InheritanceContract {
         creator: Address,
         message_id: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         creator.require_auth();
 
         let mut message = env
@@ -3953,6 +5269,7 @@ impl InheritanceContract {
         env.storage()
             .persistent()
             .set(&DataKey::LegacyMessage(message_id), &message);
+        access_control::extend_persistent_ttl(&env, &DataKey::LegacyMessage(message_id));
 
         env.events().publish(
             (Symbol::new(&env, "message_finalized"), message.vault_id),
@@ -3971,6 +5288,7 @@ impl InheritanceContract {
     /// * `env` - The Soroban environment
     /// * `message_id` - The unique message identifier
     pub fn get_legacy_message(env: Env, message_id: u64) -> Option<LegacyMessageMetadata> {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .persistent()
             .get(&DataKey::LegacyMessage(message_id))
@@ -3982,6 +5300,7 @@ impl InheritanceContract {
     /// * `env` - The Soroban environment
     /// * `vault_id` - The vault/plan ID
     pub fn get_vault_messages(env: Env, vault_id: u64) -> Vec<u64> {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .persistent()
             .get(&DataKey::VaultMessages(vault_id))
@@ -4004,6 +5323,7 @@ impl InheritanceContract {
         owner: Address,
         message_id: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
 
         let message: LegacyMessageMetadata = env
@@ -4040,6 +5360,7 @@ impl InheritanceContract {
         env.storage()
             .persistent()
             .set(&DataKey::VaultMessages(message.vault_id), &updated);
+        access_control::extend_persistent_ttl(&env, &DataKey::VaultMessages(message.vault_id));
 
         env.events().publish(
             (Symbol::new(&env, "message_deleted"), message.vault_id),
@@ -4068,6 +5389,7 @@ impl InheritanceContract {
         caller: Address,
         message_id: u64,
     ) -> Result<LegacyMessageMetadata, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         // Get message metadata
         let mut message: LegacyMessageMetadata = env
             .storage()
@@ -4086,6 +5408,7 @@ impl InheritanceContract {
                 env.storage()
                     .persistent()
                     .set(&DataKey::LegacyMessage(message_id), &message);
+                access_control::extend_persistent_ttl(&env, &DataKey::LegacyMessage(message_id));
 
                 // Emit unlock event
                 env.events().publish(
@@ -4111,6 +5434,10 @@ impl InheritanceContract {
                     env.storage()
                         .persistent()
                         .set(&DataKey::LegacyMessage(message_id), &message);
+                    access_control::extend_persistent_ttl(
+                        &env,
+                        &DataKey::LegacyMessage(message_id),
+                    );
 
                     // Emit unlock event
                     env.events().publish(
@@ -4172,6 +5499,7 @@ impl InheritanceContract {
     /// * `env` - The Soroban environment
     /// * `vault_id` - The vault/plan ID for which inheritance was triggered
     pub fn unlock_messages_on_inheritance(env: Env, vault_id: u64) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         // Verify inheritance was triggered
         let trigger_info: InheritanceTriggerInfo = env
             .storage()
@@ -4203,6 +5531,7 @@ impl InheritanceContract {
                 env.storage()
                     .persistent()
                     .set(&DataKey::LegacyMessage(message_id), &message);
+                access_control::extend_persistent_ttl(&env, &DataKey::LegacyMessage(message_id));
 
                 // Emit unlock event
                 env.events().publish(
@@ -4235,6 +5564,7 @@ impl InheritanceContract {
         vault_id: u64,
         version: u32,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
 
         let plan = Self::get_plan(&env, vault_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -4293,9 +5623,11 @@ impl InheritanceContract {
 
         let finalized_at = env.ledger().timestamp();
         env.storage().persistent().set(&fin_key, &true);
+        access_control::extend_persistent_ttl(&env, &fin_key);
         env.storage()
             .persistent()
             .set(&DataKey::WillFinalizedAt(vault_id, version), &finalized_at);
+        access_control::extend_persistent_ttl(&env, &DataKey::WillFinalizedAt(vault_id, version));
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("FINAL")),
@@ -4311,17 +5643,21 @@ impl InheritanceContract {
 
     /// Check whether a specific will version is finalized.
     pub fn is_will_finalized(env: Env, vault_id: u64, version: u32) -> bool {
+        access_control::extend_instance_ttl(&env);
+        let key = DataKey::WillFinalized(vault_id, version);
+        access_control::extend_persistent_ttl(&env, &key);
         env.storage()
             .persistent()
-            .get::<_, bool>(&DataKey::WillFinalized(vault_id, version))
+            .get::<_, bool>(&key)
             .unwrap_or(false)
     }
 
     /// Get the finalization timestamp for a will version (None if not finalized).
     pub fn get_will_finalized_at(env: Env, vault_id: u64, version: u32) -> Option<u64> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::WillFinalizedAt(vault_id, version))
+        access_control::extend_instance_ttl(&env);
+        let key = DataKey::WillFinalizedAt(vault_id, version);
+        access_control::extend_persistent_ttl(&env, &key);
+        env.storage().persistent().get(&key)
     }
 
     // ── Legal Witness Verification (Issue #320) ──
@@ -4333,6 +5669,7 @@ impl InheritanceContract {
         vault_id: u64,
         witness: Address,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
 
         let plan = Self::get_plan(&env, vault_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -4356,6 +5693,7 @@ impl InheritanceContract {
 
         witnesses.push_back(witness.clone());
         env.storage().persistent().set(&key, &witnesses);
+        access_control::extend_persistent_ttl(&env, &key);
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("WITNESS")),
@@ -4373,6 +5711,7 @@ impl InheritanceContract {
         witness: Address,
         vault_id: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         witness.require_auth();
 
         // Vault must exist
@@ -4410,6 +5749,7 @@ impl InheritanceContract {
 
         let signed_at = env.ledger().timestamp();
         env.storage().persistent().set(&wsig_key, &signed_at);
+        access_control::extend_persistent_ttl(&env, &wsig_key);
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("WSIGN")),
@@ -4421,17 +5761,21 @@ impl InheritanceContract {
 
     /// Get all registered witnesses for a vault.
     pub fn get_witnesses(env: Env, vault_id: u64) -> Vec<Address> {
+        access_control::extend_instance_ttl(&env);
+        let key = DataKey::WillWitnesses(vault_id);
+        access_control::extend_persistent_ttl(&env, &key);
         env.storage()
             .persistent()
-            .get(&DataKey::WillWitnesses(vault_id))
+            .get(&key)
             .unwrap_or_else(|| Vec::new(&env))
     }
 
     /// Get the timestamp at which a witness signed, or None if not yet signed.
     pub fn get_witness_signature(env: Env, vault_id: u64, witness: Address) -> Option<u64> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::WitnessSignature(vault_id, witness))
+        access_control::extend_instance_ttl(&env);
+        let key = DataKey::WitnessSignature(vault_id, witness);
+        access_control::extend_persistent_ttl(&env, &key);
+        env.storage().persistent().get(&key)
     }
     // ── Batch Operations (Issue #483) ──
 
@@ -4446,6 +5790,7 @@ impl InheritanceContract {
         plan_id: u64,
         inputs: Vec<BeneficiaryInput>,
     ) -> Result<(u32, u32), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         if inputs.len() > Self::BATCH_LIMIT {
             return Err(InheritanceError::TooManyBeneficiaries);
@@ -4470,6 +5815,14 @@ impl InheritanceContract {
                 fail += 1;
                 continue;
             }
+            if !payout_details::accepts_new(&env, &input.bank_account) {
+                fail += 1;
+                continue;
+            }
+            if !vesting::is_valid(&input.vesting) {
+                fail += 1;
+                continue;
+            }
             match Self::create_beneficiary(
                 &env,
                 input.name.clone(),
@@ -4481,6 +5834,7 @@ impl InheritanceContract {
             ) {
                 Ok(beneficiary) => {
                     plan.total_allocation_bp = new_total;
+                    vesting::set_release(&env, plan_id, &beneficiary.hashed_email, &input.vesting);
                     plan.beneficiaries.push_back(beneficiary);
                     success += 1;
                 }
@@ -4514,6 +5868,7 @@ impl InheritanceContract {
         plan_id: u64,
         indices: Vec<u32>,
     ) -> Result<(u32, u32), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         if indices.len() > Self::BATCH_LIMIT {
             return Err(InheritanceError::TooManyBeneficiaries);
@@ -4565,6 +5920,7 @@ impl InheritanceContract {
             plan.total_allocation_bp = plan
                 .total_allocation_bp
                 .saturating_sub(removed.allocation_bp);
+            vesting::clear(&env, plan_id, &removed.hashed_email);
             let last = plan.beneficiaries.len() - 1;
             if idx != last {
                 let last_ben = plan.beneficiaries.get(last).unwrap();
@@ -4598,6 +5954,7 @@ impl InheritanceContract {
         plan_id: u64,
         new_allocations: Vec<u32>,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
         if new_allocations.len() > Self::BATCH_LIMIT {
             return Err(InheritanceError::TooManyBeneficiaries);
@@ -4641,7 +5998,8 @@ impl InheritanceContract {
         admin: Address,
         users: Vec<Address>,
     ) -> Result<(u32, u32), InheritanceError> {
-        Self::require_admin(&env, &admin)?;
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::KycOfficer)?;
         if users.len() > Self::BATCH_LIMIT {
             return Err(InheritanceError::TooManyBeneficiaries);
         }
@@ -4663,6 +6021,7 @@ impl InheritanceContract {
                     status.approved = true;
                     status.approved_at = now;
                     env.storage().persistent().set(&key, &status);
+                    access_control::extend_persistent_ttl(&env, &key);
                     env.events().publish(
                         (symbol_short!("KYC"), symbol_short!("APPROV")),
                         KycApprovedEvent {
@@ -4695,6 +6054,7 @@ impl InheritanceContract {
         creator: Address,
         params_list: Vec<CreateLegacyMessageParams>,
     ) -> Result<(Vec<u64>, u32), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         creator.require_auth();
         if params_list.len() > Self::BATCH_MESSAGE_LIMIT {
             return Err(InheritanceError::TooManyBeneficiaries);
@@ -4738,6 +6098,7 @@ impl InheritanceContract {
             env.storage()
                 .persistent()
                 .set(&DataKey::LegacyMessage(message_id), &message);
+            access_control::extend_persistent_ttl(&env, &DataKey::LegacyMessage(message_id));
             let mut vault_msgs: Vec<u64> = env
                 .storage()
                 .persistent()
@@ -4747,9 +6108,11 @@ impl InheritanceContract {
             env.storage()
                 .persistent()
                 .set(&DataKey::VaultMessages(params.vault_id), &vault_msgs);
+            access_control::extend_persistent_ttl(&env, &DataKey::VaultMessages(params.vault_id));
             env.storage()
                 .persistent()
                 .set(&DataKey::NextMessageId, &(message_id + 1));
+            access_control::extend_persistent_ttl(&env, &DataKey::NextMessageId);
             env.events().publish(
                 (Symbol::new(&env, "message_created"), params.vault_id),
                 MessageCreatedEvent {
@@ -4786,17 +6149,17 @@ impl InheritanceContract {
         plan_id: u64,
         claimers: Vec<(Address, String, u32)>,
     ) -> Result<(u32, u32), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::check_not_paused(&env);
         Self::enter_guard(&env);
         if claimers.len() > Self::BATCH_LIMIT {
             return Err(InheritanceError::TooManyBeneficiaries);
         }
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
-        let triggered = Self::get_trigger_info(&env, plan_id).is_some();
-        if !plan.is_active {
+        if !plan.is_active || Self::is_plan_frozen(&env, plan_id) {
             return Err(InheritanceError::PlanNotActive);
         }
-        if !triggered && !Self::is_claim_time_valid(&env, &plan) {
+        if payouts::is_periodic(&plan.distribution_method) {
             return Err(InheritanceError::ClaimNotAllowedYet);
         }
         let mut success: u32 = 0;
@@ -4833,21 +6196,24 @@ impl InheritanceContract {
                     continue;
                 }
             };
-            let mut beneficiary_index: Option<u32> = None;
-            for i in 0..current_plan.beneficiaries.len() {
-                let b = current_plan.beneficiaries.get(i).unwrap();
-                if b.hashed_email == hashed_email && b.hashed_claim_code == hashed_claim_code {
-                    beneficiary_index = Some(i);
-                    break;
-                }
-            }
-            let index = match beneficiary_index {
+            let index = match Self::find_beneficiary_index(
+                &current_plan,
+                &hashed_email,
+                &hashed_claim_code,
+            ) {
                 Some(i) => i,
                 None => {
                     fail += 1;
                     continue;
                 }
             };
+            // Bound and vesting beneficiaries claim individually.
+            if claim_auth::binding(&env, plan_id, &hashed_email).is_some()
+                || vesting::release(&env, plan_id, &hashed_email).is_some()
+            {
+                fail += 1;
+                continue;
+            }
             let beneficiary = current_plan.beneficiaries.get(index).unwrap();
             let base_payout = (current_plan.total_amount as u128)
                 .checked_mul(beneficiary.allocation_bp as u128)
@@ -4872,10 +6238,12 @@ impl InheritanceContract {
                 claimed_at: env.ledger().timestamp(),
             };
             env.storage().persistent().set(&claim_key, &claim);
+            access_control::extend_persistent_ttl(&env, &claim_key);
             let mut updated = current_plan.clone();
             updated.total_amount = updated.total_amount.saturating_sub(base_payout);
             Self::store_plan(&env, plan_id, &updated);
             Self::add_plan_to_claimed(&env, current_plan.owner.clone(), plan_id);
+            Self::pay_out_assets(&env, plan_id, &current_plan, index, &claimer)?;
             env.events().publish(
                 (symbol_short!("CLAIM"), symbol_short!("SUCCESS")),
                 (plan_id, hashed_email, base_payout),
@@ -4908,6 +6276,7 @@ impl InheritanceContract {
         admin: Address,
         contract: Address,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
         env.storage()
             .instance()
@@ -4923,6 +6292,7 @@ impl InheritanceContract {
     }
 
     pub fn get_lending_contract(env: Env) -> Option<Address> {
+        access_control::extend_instance_ttl(&env);
         env.storage().instance().get(&DataKey::LendingContract)
     }
 
@@ -4931,6 +6301,7 @@ impl InheritanceContract {
         admin: Address,
         contract: Address,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
         env.storage()
             .instance()
@@ -4946,10 +6317,12 @@ impl InheritanceContract {
     }
 
     pub fn get_governance_contract(env: Env) -> Option<Address> {
+        access_control::extend_instance_ttl(&env);
         env.storage().instance().get(&DataKey::GovernanceContract)
     }
 
     pub fn verify_plan_ownership(env: Env, plan_id: u64, user: Address) -> bool {
+        access_control::extend_instance_ttl(&env);
         if let Some(plan) = Self::get_plan(&env, plan_id) {
             return plan.owner == user;
         }
@@ -4966,6 +6339,7 @@ impl InheritanceContract {
         plan_id: u64,
         beneficiary_index: u32,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         caller.require_auth();
 
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -5007,6 +6381,7 @@ impl InheritanceContract {
         plan_id: u64,
         beneficiary_index: u32,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         beneficiary_caller.require_auth();
 
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -5047,6 +6422,7 @@ impl InheritanceContract {
         plan_id: u64,
         beneficiary_index: u32,
     ) -> Option<BeneficiaryAcknowledgment> {
+        access_control::extend_instance_ttl(&env);
         let notif_key = DataKey::BeneficiaryNotifiedAt(plan_id, beneficiary_index);
         let notification_sent_at: u64 = env.storage().instance().get(&notif_key)?;
 
@@ -5070,6 +6446,7 @@ impl InheritanceContract {
         plan_id: u64,
         required: bool,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         caller.require_auth();
 
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
@@ -5090,6 +6467,7 @@ impl InheritanceContract {
         env: Env,
         plan_id: u64,
     ) -> Result<Vec<u32>, InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         let mut unacknowledged: Vec<u32> = Vec::new(&env);
 
@@ -5111,6 +6489,7 @@ impl InheritanceContract {
         admin: Address,
         new_wasm_hash: BytesN<32>,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
         env.deployer()
             .update_current_contract_wasm(new_wasm_hash.clone());
@@ -5134,7 +6513,10 @@ impl InheritanceContract {
         Ok(())
     }
 
-    /// Transfer ownership of an inheritance plan to another address.
+    /// Propose transferring an inheritance plan to another address. The plan
+    /// only moves once `new_owner` calls `accept_plan_ownership`, so a
+    /// mistyped address can be re-proposed or cancelled; proposing again
+    /// replaces the pending transfer.
     ///
     /// # Arguments
     /// * `owner` - The current plan owner (must authorize)
@@ -5142,7 +6524,7 @@ impl InheritanceContract {
     /// * `plan_id` - The ID of the plan to transfer
     ///
     /// # Errors
-    /// - `Unauthorized`: Not the current plan owner
+    /// - `Unauthorized`: Not the current plan owner, or `new_owner` is the owner
     /// - `PlanNotFound`: Plan does not exist
     /// - `PlanNotActive`: Plan is inactive or inheritance already triggered
     pub fn transfer_plan_ownership(
@@ -5151,11 +6533,12 @@ impl InheritanceContract {
         new_owner: Address,
         plan_id: u64,
     ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
         owner.require_auth();
 
-        let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
 
-        if plan.owner != owner {
+        if plan.owner != owner || new_owner == owner {
             return Err(InheritanceError::Unauthorized);
         }
 
@@ -5164,33 +6547,1240 @@ impl InheritanceContract {
             return Err(InheritanceError::PlanNotActive);
         }
 
-        // Update user registries
-        Self::remove_plan_from_user(&env, owner.clone(), plan_id);
-        Self::add_plan_to_user(&env, new_owner.clone(), plan_id);
-
-        let old_owner = plan.owner.clone();
+        ownership::set_pending_transfer(
+            &env,
+            &PendingPlanTransfer {
+                plan_id,
+                current_owner: owner.clone(),
+                new_owner: new_owner.clone(),
+                proposed_at: env.ledger().timestamp(),
+            },
+        );
+        env.events().publish(
+            (symbol_short!("PLAN"), symbol_short!("XFER_PROP")),
+            ownership::PlanTransferEvent {
+                plan_id,
+                current_owner: owner,
+                new_owner,
+            },
+        );
+        Ok(())
+    }
+
+    /// Accept a pending plan transfer, becoming the plan's owner.
+    ///
+    /// # Errors
+    /// - `NoPendingTransfer`: No transfer to `new_owner` is pending, or the
+    ///   plan changed hands since it was proposed
+    /// - `PlanNotActive`: Inheritance was triggered in the meantime
+    pub fn accept_plan_ownership(
+        env: Env,
+        new_owner: Address,
+        plan_id: u64,
+    ) -> Result<(), OwnershipError> {
+        access_control::extend_instance_ttl(&env);
+        new_owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(OwnershipError::PlanNotFound)?;
+        ownership::pending_transfer(&env, plan_id)
+            .filter(|p| p.new_owner == new_owner && p.current_owner == plan.owner)
+            .ok_or(OwnershipError::NoPendingTransfer)?;
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(OwnershipError::PlanNotActive);
+        }
+
+        Self::move_plan_ownership(&env, plan_id, plan, new_owner);
+        Ok(())
+    }
+
+    /// Cancel a pending plan transfer. Owner only.
+    pub fn cancel_plan_ownership_transfer(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+    ) -> Result<(), OwnershipError> {
+        access_control::extend_instance_ttl(&env);
+        owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(OwnershipError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(OwnershipError::Unauthorized);
+        }
+        let pending = ownership::clear(&env, plan_id).ok_or(OwnershipError::NoPendingTransfer)?;
+
+        env.events().publish(
+            (symbol_short!("PLAN"), symbol_short!("XFER_CNCL")),
+            ownership::PlanTransferEvent {
+                plan_id,
+                current_owner: owner,
+                new_owner: pending.new_owner,
+            },
+        );
+        Ok(())
+    }
+
+    /// The plan transfer waiting for acceptance, if any.
+    pub fn get_pending_plan_transfer(env: Env, plan_id: u64) -> Option<PendingPlanTransfer> {
+        access_control::extend_instance_ttl(&env);
+        ownership::pending_transfer(&env, plan_id)
+    }
+
+    /// Hand `plan` to `new_owner`, updating the owner indexes and dropping
+    /// any pending transfer or guardian recovery. Shared by accepted owner
+    /// transfers and guardian recovery.
+    fn move_plan_ownership(env: &Env, plan_id: u64, mut plan: InheritancePlan, new_owner: Address) {
+        let old_owner = plan.owner.clone();
+        Self::remove_plan_from_user(env, old_owner.clone(), plan_id);
+        Self::add_plan_to_user(env, new_owner.clone(), plan_id);
+        let claimed_by_old = PlanIndex::OwnerClaimed(old_owner.clone());
+        if plan_index::contains(env, &claimed_by_old, plan_id) {
+            plan_index::remove(env, claimed_by_old, plan_id);
+            plan_index::insert(env, PlanIndex::OwnerClaimed(new_owner.clone()), plan_id);
+        }
+
         plan.owner = new_owner.clone();
-        Self::store_plan(&env, plan_id, &plan);
+        Self::store_plan(env, plan_id, &plan);
+        ownership::clear(env, plan_id);
+        recovery::clear(env, plan_id);
 
-        // Emit event
         env.events().publish(
             (symbol_short!("PLAN"), symbol_short!("TRANSFER")),
             PlanOwnershipTransferredEvent {
                 plan_id,
-                old_owner,
+                old_owner: old_owner.clone(),
                 new_owner: new_owner.clone(),
                 transferred_at: env.ledger().timestamp(),
             },
         );
 
         log!(
-            &env,
+            env,
             "Plan {} ownership transferred from {} to {}",
             plan_id,
-            owner,
+            old_owner,
             new_owner
         );
+    }
+
+    // ─── Guardian Recovery ───────────────────────────
+
+    /// Load the plan's guardian set and check `guardian` belongs to it.
+    fn require_plan_guardian(
+        env: &Env,
+        guardian: &Address,
+        plan_id: u64,
+    ) -> Result<GuardianConfig, RecoveryError> {
+        guardian.require_auth();
+        if Self::get_plan(env, plan_id).is_none() {
+            return Err(RecoveryError::PlanNotFound);
+        }
+        Self::plan_guardian_config(env, guardian, plan_id)
+    }
+
+    /// The plan's guardian set, if `guardian` belongs to it.
+    fn plan_guardian_config(
+        env: &Env,
+        guardian: &Address,
+        plan_id: u64,
+    ) -> Result<GuardianConfig, RecoveryError> {
+        let config: GuardianConfig = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Guardians(plan_id))
+            .ok_or(RecoveryError::GuardiansNotSet)?;
+        if !config.guardians.contains(guardian) {
+            return Err(RecoveryError::NotGuardian);
+        }
+        Ok(config)
+    }
+
+    /// Propose moving a plan to `new_owner`, e.g. after the owner lost their
+    /// wallet. Counts as the proposing guardian's approval. Once the
+    /// guardian threshold is met the owner has `RECOVERY_DELAY_SECS` to
+    /// `cancel_recovery` before `execute_recovery` can move the plan. A
+    /// proposal lapses after `RECOVERY_EXPIRY_SECS` without progress and
+    /// can then be replaced.
+    ///
+    /// # Errors
+    /// - GuardiansNotSet / NotGuardian: `guardian` is not one of the plan's guardians
+    /// - InvalidNewOwner: `new_owner` already owns the plan
+    /// - RecoveryPending: another recovery is in progress and has not lapsed
+    pub fn propose_recovery(
+        env: Env,
+        guardian: Address,
+        plan_id: u64,
+        new_owner: Address,
+    ) -> Result<RecoveryProposal, RecoveryError> {
+        access_control::extend_instance_ttl(&env);
+        let config = Self::require_plan_guardian(&env, &guardian, plan_id)?;
+        let plan = Self::get_plan(&env, plan_id).ok_or(RecoveryError::PlanNotFound)?;
+        if plan.owner == new_owner {
+            return Err(RecoveryError::InvalidNewOwner);
+        }
+        if recovery::proposal(&env, plan_id).is_some_and(|p| !recovery::is_expired(&env, &p)) {
+            return Err(RecoveryError::RecoveryPending);
+        }
+
+        let mut proposal = RecoveryProposal {
+            plan_id,
+            new_owner: new_owner.clone(),
+            approvals: Vec::new(&env),
+            proposed_at: env.ledger().timestamp(),
+            executable_at: 0,
+            cancel_votes: Vec::new(&env),
+        };
+        recovery::add_approval(&env, &mut proposal, &guardian, config.threshold)?;
+        recovery::set_proposal(&env, &proposal);
+
+        env.events().publish(
+            (symbol_short!("RECOVERY"), symbol_short!("PROPOSE")),
+            recovery::RecoveryProposedEvent {
+                plan_id,
+                new_owner,
+                guardian,
+            },
+        );
+        Ok(proposal)
+    }
+
+    /// Add a guardian's approval to the plan's pending recovery.
+    ///
+    /// # Errors
+    /// - GuardiansNotSet / NotGuardian: `guardian` is not one of the plan's guardians
+    /// - RecoveryNotFound: no recovery is pending
+    /// - RecoveryExpired: the pending recovery has lapsed
+    /// - AlreadyApproved: `guardian` has already approved it
+    pub fn approve_recovery(
+        env: Env,
+        guardian: Address,
+        plan_id: u64,
+    ) -> Result<RecoveryProposal, RecoveryError> {
+        access_control::extend_instance_ttl(&env);
+        let config = Self::require_plan_guardian(&env, &guardian, plan_id)?;
+        let mut proposal =
+            recovery::proposal(&env, plan_id).ok_or(RecoveryError::RecoveryNotFound)?;
+        if recovery::is_expired(&env, &proposal) {
+            return Err(RecoveryError::RecoveryExpired);
+        }
+        recovery::add_approval(&env, &mut proposal, &guardian, config.threshold)?;
+        recovery::set_proposal(&env, &proposal);
+
+        env.events().publish(
+            (symbol_short!("RECOVERY"), symbol_short!("APPROVE")),
+            recovery::RecoveryApprovedEvent {
+                plan_id,
+                new_owner: proposal.new_owner.clone(),
+                guardian,
+                approvals_count: proposal.approvals.len(),
+                executable_at: proposal.executable_at,
+            },
+        );
+        Ok(proposal)
+    }
+
+    /// Cancel the plan's pending recovery at any point before it is
+    /// executed. The current owner cancels it outright; a guardian casts a
+    /// vote, and the proposal is withdrawn once the guardian threshold has
+    /// voted, so guardians can replace a proposal they no longer back.
+    ///
+    /// # Errors
+    /// - Unauthorized: `caller` is neither the owner nor one of the plan's guardians
+    /// - RecoveryNotFound: no recovery is pending
+    /// - AlreadyApproved: `caller` has already voted to cancel it
+    pub fn cancel_recovery(env: Env, caller: Address, plan_id: u64) -> Result<(), RecoveryError> {
+        access_control::extend_instance_ttl(&env);
+        caller.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(RecoveryError::PlanNotFound)?;
+        let mut pending =
+            recovery::proposal(&env, plan_id).ok_or(RecoveryError::RecoveryNotFound)?;
+
+        if plan.owner != caller {
+            let config = Self::plan_guardian_config(&env, &caller, plan_id)
+                .map_err(|_| RecoveryError::Unauthorized)?;
+            if !recovery::add_cancel_vote(&mut pending, &caller, config.threshold)? {
+                recovery::set_proposal(&env, &pending);
+                env.events().publish(
+                    (symbol_short!("RECOVERY"), symbol_short!("VETO")),
+                    recovery::RecoveryCancelVotedEvent {
+                        plan_id,
+                        new_owner: pending.new_owner,
+                        guardian: caller,
+                        votes_count: pending.cancel_votes.len(),
+                    },
+                );
+                return Ok(());
+            }
+        }
+        recovery::clear(&env, plan_id);
+
+        env.events().publish(
+            (symbol_short!("RECOVERY"), symbol_short!("CANCEL")),
+            recovery::RecoveryCancelledEvent {
+                plan_id,
+                new_owner: pending.new_owner,
+                cancelled_by: caller,
+            },
+        );
+        Ok(())
+    }
+
+    /// Move the plan to the recovered owner once the guardian threshold is
+    /// met and the cancellation window has passed. The new owner must
+    /// authorize, which proves the guardians picked an address they control.
+    ///
+    /// # Errors
+    /// - RecoveryNotFound: no recovery is pending for `new_owner`
+    /// - RecoveryExpired: the recovery lapsed before it was executed
+    /// - ThresholdNotMet: not enough guardians have approved
+    /// - DelayNotElapsed: the owner's cancellation window is still open
+    /// - PlanTriggered: inheritance has been triggered; ownership is fixed
+    /// - PlanFrozen: the plan is frozen or under legal hold
+    pub fn execute_recovery(
+        env: Env,
+        new_owner: Address,
+        plan_id: u64,
+    ) -> Result<(), RecoveryError> {
+        access_control::extend_instance_ttl(&env);
+        new_owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(RecoveryError::PlanNotFound)?;
+        let proposal = recovery::proposal(&env, plan_id)
+            .filter(|p| p.new_owner == new_owner)
+            .ok_or(RecoveryError::RecoveryNotFound)?;
+        if recovery::is_expired(&env, &proposal) {
+            return Err(RecoveryError::RecoveryExpired);
+        }
+        if proposal.executable_at == 0 {
+            return Err(RecoveryError::ThresholdNotMet);
+        }
+        if env.ledger().timestamp() < proposal.executable_at {
+            return Err(RecoveryError::DelayNotElapsed);
+        }
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(RecoveryError::PlanTriggered);
+        }
+        if Self::is_plan_frozen(&env, plan_id) {
+            return Err(RecoveryError::PlanFrozen);
+        }
+
+        Self::move_plan_ownership(&env, plan_id, plan, new_owner);
+        Ok(())
+    }
+
+    /// The plan's pending guardian recovery, if any has not lapsed.
+    pub fn get_recovery_proposal(env: Env, plan_id: u64) -> Option<RecoveryProposal> {
+        access_control::extend_instance_ttl(&env);
+        recovery::proposal(&env, plan_id).filter(|p| !recovery::is_expired(&env, p))
+    }
+
+    // ─── Dispute Resolution ──────────────────────────
+
+    fn require_dispute_admin(env: &Env, admin: &Address) -> Result<(), DisputeError> {
+        admin.require_auth();
+        access_control::require_role(env, admin, Role::Admin, DisputeError::NotAdmin)
+    }
+
+    /// Load a dispute and check that `arbitrator` is the one assigned to it.
+    fn load_assigned_dispute(
+        env: &Env,
+        arbitrator: &Address,
+        dispute_id: u64,
+    ) -> Result<DisputeRecord, DisputeError> {
+        let dispute =
+            disputes::get_dispute(env, dispute_id).ok_or(DisputeError::DisputeNotFound)?;
+        if dispute.arbitrator.as_ref() != Some(arbitrator) {
+            return Err(DisputeError::NotAssignedArbitrator);
+        }
+        if !disputes::is_arbitrator(env, arbitrator) {
+            return Err(DisputeError::ArbitratorNotRegistered);
+        }
+        Ok(dispute)
+    }
+
+    /// Whether `address` has a stake in the plan: its owner, one of its
+    /// guardians, or a beneficiary bound to that address. Beneficiaries
+    /// known only by email or key cannot be told apart by address until
+    /// they are bound.
+    fn is_plan_party(env: &Env, plan_id: u64, plan: &InheritancePlan, address: &Address) -> bool {
+        if plan.owner == *address {
+            return true;
+        }
+        let guardians: Option<GuardianConfig> =
+            env.storage().persistent().get(&DataKey::Guardians(plan_id));
+        if guardians.is_some_and(|config| config.guardians.contains(address)) {
+            return true;
+        }
+        plan.beneficiaries.iter().any(|beneficiary| {
+            matches!(
+                claim_auth::binding(env, plan_id, &beneficiary.hashed_email),
+                Some(BeneficiaryBinding::Address(bound)) if bound == *address
+            )
+        })
+    }
+
+    /// Close a dispute as resolved or rejected and lift the dispute freeze
+    /// once no other dispute on the plan remains open.
+    fn close_dispute(
+        env: &Env,
+        arbitrator: Address,
+        dispute_id: u64,
+        status: DisputeStatus,
+        resolution_notes: String,
+    ) -> Result<(), DisputeError> {
+        arbitrator.require_auth();
+        let mut dispute = Self::load_assigned_dispute(env, &arbitrator, dispute_id)?;
+
+        let allowed = match status {
+            DisputeStatus::Resolved => {
+                dispute.status == DisputeStatus::UnderReview && disputes::is_open(env, &dispute)
+            }
+            _ => disputes::is_open(env, &dispute),
+        };
+        if !allowed {
+            return Err(DisputeError::InvalidDisputeStatus);
+        }
+
+        let now = env.ledger().timestamp();
+        dispute.status = status;
+        dispute.resolved_at = now;
+        dispute.resolution_notes = resolution_notes;
+        disputes::store_dispute(env, &dispute);
+        disputes::remove_open_dispute(env, dispute.plan_id, dispute_id);
+        if status == DisputeStatus::Resolved {
+            disputes::record_filing(env, dispute.plan_id, &dispute.disputer, true);
+        }
+
+        env.events().publish(
+            (symbol_short!("DISPUTE"), symbol_short!("CLOSED")),
+            disputes::DisputeResolvedEvent {
+                dispute_id,
+                plan_id: dispute.plan_id,
+                status,
+                arbitrator,
+                resolved_at: now,
+            },
+        );
+
+        // Only lift freezes this workflow placed; an admin freeze stays put.
+        let plan_id = dispute.plan_id;
+        let freeze_key = DataKey::FreezePlan(plan_id);
+        if disputes::open_dispute_count(env, plan_id) == 0 {
+            if let Some(record) = env
+                .storage()
+                .persistent()
+                .get::<_, FreezeRecord>(&freeze_key)
+            {
+                if record.frozen_by == env.current_contract_address() {
+                    env.storage().persistent().remove(&freeze_key);
+                    env.events().publish(
+                        (symbol_short!("DISPUTE"), symbol_short!("UNFREEZE")),
+                        disputes::DisputePlanUnfrozenEvent {
+                            plan_id,
+                            dispute_id,
+                            unfrozen_at: now,
+                        },
+                    );
+                }
+            }
+        }
+
+        log!(env, "Dispute {} on plan {} closed", dispute_id, plan_id);
+        Ok(())
+    }
+
+    /// Register an address as an arbitrator eligible for dispute assignment. Admin-only.
+    pub fn add_arbitrator(
+        env: Env,
+        admin: Address,
+        arbitrator: Address,
+    ) -> Result<(), DisputeError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_dispute_admin(&env, &admin)?;
+        let mut arbitrators = disputes::get_arbitrators(&env);
+        if arbitrators.contains(&arbitrator) {
+            return Err(DisputeError::ArbitratorAlreadyRegistered);
+        }
+        arbitrators.push_back(arbitrator.clone());
+        disputes::set_arbitrators(&env, &arbitrators);
+
+        env.events().publish(
+            (symbol_short!("DISPUTE"), symbol_short!("ARB_ADD")),
+            disputes::ArbitratorAddedEvent {
+                arbitrator,
+                added_at: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Remove an arbitrator from the registry. Admin-only.
+    ///
+    /// Disputes already assigned to the arbitrator can no longer be progressed
+    /// by them; the admin should reassign those disputes.
+    pub fn remove_arbitrator(
+        env: Env,
+        admin: Address,
+        arbitrator: Address,
+    ) -> Result<(), DisputeError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_dispute_admin(&env, &admin)?;
+        let mut arbitrators = disputes::get_arbitrators(&env);
+        let index = arbitrators
+            .first_index_of(&arbitrator)
+            .ok_or(DisputeError::ArbitratorNotRegistered)?;
+        arbitrators.remove(index);
+        disputes::set_arbitrators(&env, &arbitrators);
+
+        env.events().publish(
+            (symbol_short!("DISPUTE"), symbol_short!("ARB_REM")),
+            disputes::ArbitratorRemovedEvent {
+                arbitrator,
+                removed_at: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn is_arbitrator(env: Env, address: Address) -> bool {
+        access_control::extend_instance_ttl(&env);
+        disputes::is_arbitrator(&env, &address)
+    }
+
+    pub fn get_arbitrators(env: Env) -> Vec<Address> {
+        access_control::extend_instance_ttl(&env);
+        disputes::get_arbitrators(&env)
+    }
+
+    /// File a dispute against a plan. The plan is frozen until every open
+    /// dispute on it has been resolved or rejected, or has lapsed
+    /// `DISPUTE_EXPIRY_SECS` after filing.
+    ///
+    /// # Arguments
+    /// * `disputer` - The plan's owner, a guardian or a bound beneficiary,
+    ///   with approved KYC (must authorize)
+    /// * `plan_id` - The contested plan
+    /// * `reason` - Free-text grounds for the dispute
+    ///
+    /// # Errors
+    /// - `KycNotApproved`: Disputer has no approved KYC
+    /// - `MissingReason`: `reason` is empty
+    /// - `PlanNotFound` / `PlanNotActive`: Plan missing or deactivated
+    /// - `NotPlanParty`: Disputer has no stake in the plan
+    /// - `DisputeAlreadyOpen`: Disputer already has an open dispute on the plan
+    /// - `DisputeLimitReached`: Disputer has filed `MAX_UNUPHELD_DISPUTES_PER_PARTY`
+    ///   disputes on the plan that were not upheld
+    pub fn file_dispute(
+        env: Env,
+        disputer: Address,
+        plan_id: u64,
+        reason: String,
+    ) -> Result<u64, DisputeError> {
+        access_control::extend_instance_ttl(&env);
+        disputer.require_auth();
+        Self::check_kyc_approved(&env, &disputer).map_err(|_| DisputeError::KycNotApproved)?;
+
+        if reason.is_empty() {
+            return Err(DisputeError::MissingReason);
+        }
+
+        let plan = Self::get_plan(&env, plan_id).ok_or(DisputeError::PlanNotFound)?;
+        if !plan.is_active {
+            return Err(DisputeError::PlanNotActive);
+        }
+        if !Self::is_plan_party(&env, plan_id, &plan, &disputer) {
+            return Err(DisputeError::NotPlanParty);
+        }
+        if disputes::has_open_dispute_by(&env, plan_id, &disputer) {
+            return Err(DisputeError::DisputeAlreadyOpen);
+        }
+        if disputes::unupheld_filings(&env, plan_id, &disputer)
+            >= disputes::MAX_UNUPHELD_DISPUTES_PER_PARTY
+        {
+            return Err(DisputeError::DisputeLimitReached);
+        }
+
+        let now = env.ledger().timestamp();
+        let expires_at = now.saturating_add(disputes::DISPUTE_EXPIRY_SECS);
+        let dispute_id = disputes::next_dispute_id(&env);
+        let dispute = DisputeRecord {
+            dispute_id,
+            plan_id,
+            disputer: disputer.clone(),
+            reason: reason.clone(),
+            status: DisputeStatus::Filed,
+            filed_at: now,
+            resolved_at: 0,
+            resolution_notes: String::from_str(&env, ""),
+            arbitrator: None,
+            expires_at,
+        };
+        disputes::store_dispute(&env, &dispute);
+        disputes::add_dispute_to_plan(&env, plan_id, dispute_id);
+        disputes::add_open_dispute(&env, plan_id, dispute_id);
+        disputes::record_filing(&env, plan_id, &disputer, false);
+
+        env.events().publish(
+            (symbol_short!("DISPUTE"), symbol_short!("FILED")),
+            disputes::DisputeFiledEvent {
+                dispute_id,
+                plan_id,
+                disputer,
+                reason: reason.clone(),
+                filed_at: now,
+            },
+        );
+
+        // Freeze the plan unless an admin freeze is already in place. A
+        // freeze from an earlier dispute is kept until the latest one lapses.
+        let existing = freeze::active_freeze(&env, plan_id);
+        let contract = env.current_contract_address();
+        match &existing {
+            Some(record) if record.frozen_by != contract => {}
+            Some(record) if record.expires_at >= expires_at => {}
+            _ => {
+                env.storage().persistent().set(
+                    &DataKey::FreezePlan(plan_id),
+                    &FreezeRecord {
+                        plan_id,
+                        frozen_at: existing.as_ref().map_or(now, |record| record.frozen_at),
+                        reason,
+                        frozen_by: contract,
+                        expires_at,
+                        // Any compliance role may lift it once no dispute is open.
+                        placed_as: Role::Guardian,
+                    },
+                );
+                access_control::extend_persistent_ttl(&env, &DataKey::FreezePlan(plan_id));
+            }
+        }
+        if existing.is_none() {
+            env.events().publish(
+                (symbol_short!("DISPUTE"), symbol_short!("FREEZE")),
+                disputes::DisputePlanFrozenEvent {
+                    plan_id,
+                    dispute_id,
+                    frozen_at: now,
+                },
+            );
+        }
+
+        log!(
+            &env,
+            "Dispute {} filed against plan {}",
+            dispute_id,
+            plan_id
+        );
+        Ok(dispute_id)
+    }
+
+    /// Assign (or reassign) a registered arbitrator to an open dispute. Admin-only.
+    pub fn assign_arbitrator(
+        env: Env,
+        admin: Address,
+        dispute_id: u64,
+        arbitrator: Address,
+    ) -> Result<(), DisputeError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_dispute_admin(&env, &admin)?;
+        if !disputes::is_arbitrator(&env, &arbitrator) {
+            return Err(DisputeError::ArbitratorNotRegistered);
+        }
+
+        let mut dispute =
+            disputes::get_dispute(&env, dispute_id).ok_or(DisputeError::DisputeNotFound)?;
+        if !disputes::is_open(&env, &dispute) {
+            return Err(DisputeError::InvalidDisputeStatus);
+        }
+
+        dispute.arbitrator = Some(arbitrator.clone());
+        disputes::store_dispute(&env, &dispute);
+
+        env.events().publish(
+            (symbol_short!("DISPUTE"), symbol_short!("ASSIGN")),
+            disputes::ArbitratorAssignedEvent {
+                dispute_id,
+                plan_id: dispute.plan_id,
+                arbitrator,
+            },
+        );
+        Ok(())
+    }
+
+    /// Move a filed dispute under review. Only the assigned arbitrator may call this.
+    pub fn move_to_review(
+        env: Env,
+        arbitrator: Address,
+        dispute_id: u64,
+    ) -> Result<(), DisputeError> {
+        access_control::extend_instance_ttl(&env);
+        arbitrator.require_auth();
+        let mut dispute = Self::load_assigned_dispute(&env, &arbitrator, dispute_id)?;
+        if dispute.status != DisputeStatus::Filed || !disputes::is_open(&env, &dispute) {
+            return Err(DisputeError::InvalidDisputeStatus);
+        }
+
+        dispute.status = DisputeStatus::UnderReview;
+        disputes::store_dispute(&env, &dispute);
+
+        env.events().publish(
+            (symbol_short!("DISPUTE"), symbol_short!("REVIEW")),
+            disputes::DisputeReviewStartedEvent {
+                dispute_id,
+                plan_id: dispute.plan_id,
+                arbitrator,
+            },
+        );
+        Ok(())
+    }
+
+    /// Uphold a dispute under review. Only the assigned arbitrator may call this.
+    ///
+    /// The on-chain record only captures the outcome; any remedy (e.g. owner
+    /// edits to beneficiaries) is applied through the regular plan entry points.
+    pub fn resolve_dispute(
+        env: Env,
+        arbitrator: Address,
+        dispute_id: u64,
+        resolution_notes: String,
+    ) -> Result<(), DisputeError> {
+        access_control::extend_instance_ttl(&env);
+        Self::close_dispute(
+            &env,
+            arbitrator,
+            dispute_id,
+            DisputeStatus::Resolved,
+            resolution_notes,
+        )
+    }
+
+    /// Dismiss a filed or under-review dispute. Only the assigned arbitrator may call this.
+    pub fn reject_dispute(
+        env: Env,
+        arbitrator: Address,
+        dispute_id: u64,
+        resolution_notes: String,
+    ) -> Result<(), DisputeError> {
+        access_control::extend_instance_ttl(&env);
+        Self::close_dispute(
+            &env,
+            arbitrator,
+            dispute_id,
+            DisputeStatus::Rejected,
+            resolution_notes,
+        )
+    }
+
+    pub fn get_dispute(env: Env, dispute_id: u64) -> Option<DisputeRecord> {
+        access_control::extend_instance_ttl(&env);
+        disputes::get_dispute(&env, dispute_id)
+    }
+
+    /// Return every dispute ever filed against a plan, oldest first.
+    pub fn get_plan_disputes(env: Env, plan_id: u64) -> Vec<DisputeRecord> {
+        access_control::extend_instance_ttl(&env);
+        let mut result = Vec::new(&env);
+        for id in disputes::get_plan_dispute_ids(&env, plan_id).iter() {
+            if let Some(dispute) = disputes::get_dispute(&env, id) {
+                result.push_back(dispute);
+            }
+        }
+        result
+    }
+
+    pub fn has_open_dispute(env: Env, plan_id: u64) -> bool {
+        access_control::extend_instance_ttl(&env);
+        disputes::open_dispute_count(&env, plan_id) > 0
+    }
+
+    // ─── Plan Freezes & Legal Holds ──────────────────
+
+    /// Compliance actions may be taken by any Admin or Guardian. Returns the
+    /// role the caller acts under, Admin when they hold both.
+    fn require_compliance_role(env: &Env, caller: &Address) -> Result<Role, FreezeError> {
+        caller.require_auth();
+        if access_control::has_role(env, caller, Role::Admin) {
+            Ok(Role::Admin)
+        } else if access_control::has_role(env, caller, Role::Guardian) {
+            Ok(Role::Guardian)
+        } else {
+            Err(FreezeError::Unauthorized)
+        }
+    }
+
+    /// A freeze or hold may be lifted by an Admin, or by a caller acting
+    /// under the same role it was placed with, so Guardians cannot lift
+    /// what an Admin placed.
+    fn require_can_lift(caller_role: Role, placed_as: Role) -> Result<(), FreezeError> {
+        if caller_role == Role::Admin || caller_role == placed_as {
+            Ok(())
+        } else {
+            Err(FreezeError::Unauthorized)
+        }
+    }
+
+    /// Freeze a plan, blocking withdrawals and claims. Admin/Guardian only.
+    ///
+    /// # Arguments
+    /// * `caller` - Admin or Guardian (must authorize)
+    /// * `plan_id` - The plan to freeze
+    /// * `reason` - Why the plan is being frozen
+    /// * `expires_at` - Ledger timestamp at which the freeze lapses, or 0 for none
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller holds neither role
+    /// - `PlanNotFound`, `MissingReason`, `InvalidExpiry`
+    /// - `AlreadyFrozen`: An unexpired manual freeze is already in place
+    ///
+    /// A freeze placed automatically by an open dispute is taken over by this
+    /// call, so it is no longer lifted when the dispute closes. The plan stays
+    /// frozen while any dispute is open, even if this freeze expires first.
+    pub fn freeze_plan(
+        env: Env,
+        caller: Address,
+        plan_id: u64,
+        reason: String,
+        expires_at: u64,
+    ) -> Result<(), FreezeError> {
+        access_control::extend_instance_ttl(&env);
+        let role = Self::require_compliance_role(&env, &caller)?;
+        Self::get_plan(&env, plan_id).ok_or(FreezeError::PlanNotFound)?;
+        if reason.is_empty() {
+            return Err(FreezeError::MissingReason);
+        }
+        freeze::validate_expiry(&env, expires_at)?;
+
+        if let Some(existing) = freeze::active_freeze(&env, plan_id) {
+            if existing.frozen_by != env.current_contract_address() {
+                return Err(FreezeError::AlreadyFrozen);
+            }
+        }
+
+        let now = env.ledger().timestamp();
+        env.storage().persistent().set(
+            &DataKey::FreezePlan(plan_id),
+            &FreezeRecord {
+                plan_id,
+                frozen_at: now,
+                reason: reason.clone(),
+                frozen_by: caller.clone(),
+                expires_at,
+                placed_as: role,
+            },
+        );
+        access_control::extend_persistent_ttl(&env, &DataKey::FreezePlan(plan_id));
+
+        env.events().publish(
+            (symbol_short!("PLAN"), symbol_short!("FREEZE")),
+            PlanFrozenEvent {
+                plan_id,
+                frozen_by: caller,
+                frozen_at: now,
+                reason,
+            },
+        );
+        log!(&env, "Plan {} frozen", plan_id);
+        Ok(())
+    }
+
+    /// Lift a plan freeze. Admin, or the role that placed the freeze.
+    ///
+    /// # Errors
+    /// - `NotFrozen`: No freeze record is stored for the plan
+    /// - `Unauthorized`: A Guardian tried to lift an Admin-placed freeze
+    /// - `DisputeOpen`: The plan still has an open dispute
+    pub fn unfreeze_plan(env: Env, caller: Address, plan_id: u64) -> Result<(), FreezeError> {
+        access_control::extend_instance_ttl(&env);
+        let role = Self::require_compliance_role(&env, &caller)?;
+        let key = DataKey::FreezePlan(plan_id);
+        let record: FreezeRecord = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(FreezeError::NotFrozen)?;
+        Self::require_can_lift(role, record.placed_as)?;
+        if disputes::open_dispute_count(&env, plan_id) > 0 {
+            return Err(FreezeError::DisputeOpen);
+        }
+
+        env.storage().persistent().remove(&key);
+
+        env.events().publish(
+            (symbol_short!("PLAN"), symbol_short!("UNFREEZE")),
+            PlanUnfrozenEvent {
+                plan_id,
+                unfrozen_by: caller,
+                unfrozen_at: env.ledger().timestamp(),
+            },
+        );
+        log!(&env, "Plan {} unfrozen", plan_id);
+        Ok(())
+    }
+
+    /// Place a legal hold on a plan, blocking withdrawals and claims. Admin/Guardian only.
+    ///
+    /// Takes the same arguments and validation as `freeze_plan`; fails with
+    /// `LegalHoldExists` if an unexpired hold is already in place.
+    pub fn add_legal_hold(
+        env: Env,
+        caller: Address,
+        plan_id: u64,
+        reason: String,
+        expires_at: u64,
+    ) -> Result<(), FreezeError> {
+        access_control::extend_instance_ttl(&env);
+        let role = Self::require_compliance_role(&env, &caller)?;
+        Self::get_plan(&env, plan_id).ok_or(FreezeError::PlanNotFound)?;
+        if reason.is_empty() {
+            return Err(FreezeError::MissingReason);
+        }
+        freeze::validate_expiry(&env, expires_at)?;
+        if freeze::active_legal_hold(&env, plan_id).is_some() {
+            return Err(FreezeError::LegalHoldExists);
+        }
+
+        let now = env.ledger().timestamp();
+        env.storage().persistent().set(
+            &DataKey::LegalHold(plan_id),
+            &LegalHold {
+                plan_id,
+                added_at: now,
+                reason,
+                added_by: caller.clone(),
+                expires_at,
+                placed_as: role,
+            },
+        );
+        access_control::extend_persistent_ttl(&env, &DataKey::LegalHold(plan_id));
+
+        env.events().publish(
+            (symbol_short!("PLAN"), symbol_short!("HOLD_ADD")),
+            LegalHoldAddedEvent {
+                plan_id,
+                added_by: caller,
+                added_at: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Remove a plan's legal hold. Admin, or the role that placed the hold.
+    pub fn remove_legal_hold(env: Env, caller: Address, plan_id: u64) -> Result<(), FreezeError> {
+        access_control::extend_instance_ttl(&env);
+        let role = Self::require_compliance_role(&env, &caller)?;
+        let key = DataKey::LegalHold(plan_id);
+        let hold: LegalHold = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(FreezeError::LegalHoldNotFound)?;
+        Self::require_can_lift(role, hold.placed_as)?;
+
+        env.storage().persistent().remove(&key);
+
+        env.events().publish(
+            (symbol_short!("PLAN"), symbol_short!("HOLD_REM")),
+            LegalHoldRemovedEvent {
+                plan_id,
+                removed_by: caller,
+                removed_at: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Return the plan's freeze record, or None if unfrozen or the freeze has expired.
+    pub fn get_plan_freeze(env: Env, plan_id: u64) -> Option<FreezeRecord> {
+        access_control::extend_instance_ttl(&env);
+        freeze::active_freeze(&env, plan_id)
+    }
+
+    /// Return the plan's legal hold, or None if there is none or it has expired.
+    pub fn get_legal_hold(env: Env, plan_id: u64) -> Option<LegalHold> {
+        access_control::extend_instance_ttl(&env);
+        freeze::active_legal_hold(&env, plan_id)
+    }
+
+    // ─── Periodic Payouts ────────────────────────────
+
+    /// Set how many installments a Monthly/Quarterly/Yearly plan pays each
+    /// beneficiary. Owner only, and only before the first installment is paid.
+    pub fn set_payout_schedule(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        installment_count: u32,
+    ) -> Result<(), PayoutError> {
+        access_control::extend_instance_ttl(&env);
+        owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(PayoutError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(PayoutError::Unauthorized);
+        }
+        if !payouts::is_periodic(&plan.distribution_method) {
+            return Err(PayoutError::NotPeriodicPlan);
+        }
+        if installment_count == 0 || installment_count > payouts::MAX_INSTALLMENTS {
+            return Err(PayoutError::InvalidInstallmentCount);
+        }
+        if payouts::is_locked(&env, plan_id) {
+            return Err(PayoutError::ScheduleLocked);
+        }
+
+        payouts::set_installment_count(&env, plan_id, installment_count);
+
+        env.events().publish(
+            (symbol_short!("PAYOUT"), symbol_short!("SCHEDULE")),
+            payouts::PayoutScheduleSetEvent {
+                plan_id,
+                installment_count,
+            },
+        );
+        Ok(())
+    }
+
+    /// Return the installment schedule of a periodic plan, or None for
+    /// lump-sum or unknown plans.
+    pub fn get_payout_schedule(env: Env, plan_id: u64) -> Option<PayoutSchedule> {
+        access_control::extend_instance_ttl(&env);
+        let plan = Self::get_plan(&env, plan_id)?;
+        let triggered_at = Self::get_trigger_info(&env, plan_id).map(|t| t.triggered_at);
+        payouts::schedule_for(&env, plan_id, &plan, triggered_at)
+    }
+
+    /// Return a beneficiary's progress through a periodic plan's payouts.
+    pub fn get_installment_status(
+        env: Env,
+        plan_id: u64,
+        beneficiary_index: u32,
+    ) -> Result<InstallmentStatus, PayoutError> {
+        access_control::extend_instance_ttl(&env);
+        let plan = Self::get_plan(&env, plan_id).ok_or(PayoutError::PlanNotFound)?;
+        if beneficiary_index >= plan.beneficiaries.len() {
+            return Err(PayoutError::InvalidBeneficiaryIndex);
+        }
+        let triggered_at = Self::get_trigger_info(&env, plan_id).map(|t| t.triggered_at);
+        let schedule = payouts::schedule_for(&env, plan_id, &plan, triggered_at)
+            .ok_or(PayoutError::NotPeriodicPlan)?;
+
+        let beneficiary = plan.beneficiaries.get(beneficiary_index).unwrap();
+        let entitlement = payouts::entitlement(&schedule, &plan, beneficiary.allocation_bp);
+        let progress = payouts::get_progress(&env, plan_id, beneficiary_index);
+        let finished = progress.paid_installments >= schedule.installment_count;
+        let next = progress.paid_installments + 1;
+
+        Ok(InstallmentStatus {
+            plan_id,
+            beneficiary_index,
+            entitlement,
+            paid_installments: progress.paid_installments,
+            paid_amount: progress.paid_amount,
+            due_installments: payouts::installments_due(&schedule, env.ledger().timestamp()),
+            next_due_at: if finished {
+                0
+            } else {
+                payouts::due_at(&schedule, next)
+            },
+            next_amount: if finished {
+                0
+            } else {
+                payouts::installment_amount(&schedule, entitlement, next)
+            },
+        })
+    }
+
+    /// Claim the next due installment of a Monthly/Quarterly/Yearly plan.
+    ///
+    /// Each call pays exactly one tranche. The plan balance is locked as the
+    /// payout base when the first tranche is paid. Triggering inheritance
+    /// brings the first tranche forward but keeps the stream. With waterfall
+    /// enabled, higher-priority beneficiaries must take each tranche first.
+    pub fn claim_next_installment(
+        env: Env,
+        plan_id: u64,
+        claimer: Address,
+        email: String,
+        claim_code: u32,
+    ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
+        claimer.require_auth();
+        Self::check_not_paused(&env);
+        Self::enter_guard(&env);
+
+        Self::check_kyc_approved(&env, &claimer)?;
+
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if !plan.is_active || Self::is_plan_frozen(&env, plan_id) {
+            return Err(InheritanceError::PlanNotActive);
+        }
+
+        let hashed_email = Self::hash_string(&env, email.clone());
+        let hashed_claim_code = Self::hash_claim_code(&env, claim_code)?;
+        let index = Self::find_beneficiary_index(&plan, &hashed_email, &hashed_claim_code)
+            .ok_or(InheritanceError::BeneficiaryNotFound)?;
+        Self::require_claim_code_claimer(&env, plan_id, &plan, index, &claimer)?;
+
+        Self::settle_installment(&env, plan_id, plan, index, &claimer)
+    }
+
+    /// Installment counterpart of `claim_as_beneficiary`.
+    pub fn claim_installment_as_beneficiary(
+        env: Env,
+        plan_id: u64,
+        beneficiary_index: u32,
+        claimer: Address,
+        signature: Option<BytesN<64>>,
+    ) -> Result<(), InheritanceError> {
+        access_control::extend_instance_ttl(&env);
+        claimer.require_auth();
+        Self::check_not_paused(&env);
+        Self::enter_guard(&env);
+
+        Self::check_kyc_approved(&env, &claimer)?;
+
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if !plan.is_active || Self::is_plan_frozen(&env, plan_id) {
+            return Err(InheritanceError::PlanNotActive);
+        }
+        Self::require_bound_claimer(&env, plan_id, &plan, beneficiary_index, &claimer, signature)?;
+
+        Self::settle_installment(&env, plan_id, plan, beneficiary_index, &claimer)
+    }
+
+    /// Pay beneficiary `index` their next due installment. Callers must
+    /// already have authenticated `claimer` as that beneficiary.
+    fn settle_installment(
+        env: &Env,
+        plan_id: u64,
+        plan: InheritancePlan,
+        index: u32,
+        claimer: &Address,
+    ) -> Result<(), InheritanceError> {
+        let trigger = Self::get_trigger_info(env, plan_id);
+        let mut schedule = payouts::schedule_for(
+            env,
+            plan_id,
+            &plan,
+            trigger.as_ref().map(|t| t.triggered_at),
+        )
+        .ok_or(InheritanceError::ClaimNotAllowedYet)?;
+
+        let hashed_email = plan.beneficiaries.get(index).unwrap().hashed_email;
+        let mut progress = payouts::get_progress(env, plan_id, index);
+        if progress.paid_installments >= schedule.installment_count
+            || plan.beneficiaries.get(index).unwrap().is_claimed
+        {
+            return Err(InheritanceError::AlreadyClaimed);
+        }
+        let installment = progress.paid_installments + 1;
+        if installment > payouts::installments_due(&schedule, env.ledger().timestamp()) {
+            return Err(InheritanceError::ClaimNotAllowedYet);
+        }
+
+        if plan.waterfall_enabled {
+            let this = plan.beneficiaries.get(index).unwrap();
+            for i in 0..plan.beneficiaries.len() {
+                let b = plan.beneficiaries.get(i).unwrap();
+                if b.priority != 0
+                    && b.priority < this.priority
+                    && !b.is_claimed
+                    && payouts::get_progress(env, plan_id, i).paid_installments < installment
+                {
+                    return Err(InheritanceError::ClaimNotAllowedYet);
+                }
+            }
+        }
+
+        payouts::lock_base_amount(env, &mut schedule, &plan);
+        let beneficiary = plan.beneficiaries.get(index).unwrap();
+        let entitlement = payouts::entitlement(&schedule, &plan, beneficiary.allocation_bp);
+        let mut payout =
+            payouts::installment_amount(&schedule, entitlement, installment).min(plan.total_amount);
+
+        // Same gates as a one-shot claim: a vesting beneficiary only takes a
+        // tranche once enough has unlocked to cover it, and an exit
+        // settlement caps what is left to pay.
+        let mut vesting_progress = None;
+        if let Some((allowance, progress)) =
+            Self::vesting_allowance(env, plan_id, &hashed_email, entitlement)
+        {
+            if payout > allowance {
+                return Err(InheritanceError::VestingScheduleActive);
+            }
+            vesting_progress = Some(progress);
+        }
+        let exit_settlement = Self::get_vesting_exit_settlement(env, plan_id, index);
+        if exit_settlement > 0 {
+            payout = payout.min(exit_settlement);
+        }
+
+        if Self::is_emergency_active(env, plan_id) {
+            let limit = (plan.total_amount as u128)
+                .checked_mul(EMERGENCY_TRANSFER_LIMIT_BP as u128)
+                .and_then(|v| v.checked_div(10000))
+                .unwrap_or(0) as u64;
+            if payout > limit {
+                return Err(InheritanceError::EmergencyCooldownActive);
+            }
+        }
+
+        let available_liquidity = plan.total_amount.saturating_sub(plan.total_loaned);
+        if trigger.is_none() && payout > available_liquidity {
+            return Err(InheritanceError::InsufficientLiquidity);
+        }
+
+        if payout == 0 {
+            return Err(InheritanceError::NothingToClaim);
+        }
+
+        progress.paid_installments = installment;
+        progress.paid_amount += payout;
+        payouts::set_progress(env, plan_id, index, &progress);
+        if let Some(vesting_progress) = vesting_progress.as_mut() {
+            vesting_progress.released += payout;
+            vesting::set_progress(env, plan_id, &hashed_email, vesting_progress);
+        }
+        let settled =
+            Self::record_exit_settlement_payout(env, plan_id, index, exit_settlement, payout);
+
+        let finished =
+            installment == schedule.installment_count || (exit_settlement > 0 && settled);
+        let mut updated_plan = plan.clone();
+        if finished {
+            let mut b = updated_plan.beneficiaries.get(index).unwrap();
+            b.is_claimed = true;
+            updated_plan.beneficiaries.set(index, b);
+        }
+        updated_plan.total_amount = updated_plan.total_amount.saturating_sub(payout);
+        Self::store_plan(env, plan_id, &updated_plan);
+
+        if finished {
+            let claim_key = Self::claim_key(env, plan_id, &hashed_email);
+            let claim = ClaimRecord {
+                plan_id,
+                beneficiary_index: index,
+                claimed_at: env.ledger().timestamp(),
+            };
+            env.storage().persistent().set(&claim_key, &claim);
+            access_control::extend_persistent_ttl(env, &claim_key);
+            Self::add_plan_to_claimed(env, plan.owner.clone(), plan_id);
+            Self::pay_out_assets(env, plan_id, &plan, index, claimer)?;
+        }
+
+        access_control::assign_role(env, claimer, Role::Beneficiary);
+
+        env.events().publish(
+            (symbol_short!("PAYOUT"), symbol_short!("INSTALL")),
+            payouts::InstallmentClaimedEvent {
+                plan_id,
+                beneficiary_index: index,
+                installment,
+                amount: payout,
+                remaining_installments: if finished {
+                    0
+                } else {
+                    schedule.installment_count - installment
+                },
+            },
+        );
 
+        Self::exit_guard(env);
         Ok(())
     }
 }
@@ -0,0 +1,68 @@
+use soroban_sdk::{contracterror, contracttype, Address, Env};
+
+/// Errors raised when accepting or cancelling two-step ownership and admin
+/// transfers.
+///
+/// Kept separate from `InheritanceError` (at the 50-case spec limit).
+/// Codes start at 900 so they never overlap the other module enums.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OwnershipError {
+    NotAdmin = 900,
+    Unauthorized = 901,
+    PlanNotFound = 902,
+    InvalidNewOwner = 903,
+    NoPendingTransfer = 904,
+    PlanNotActive = 905,
+}
+
+/// A plan handover waiting for the new owner to accept it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingPlanTransfer {
+    pub plan_id: u64,
+    pub current_owner: Address,
+    pub new_owner: Address,
+    pub proposed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanTransferEvent {
+    pub plan_id: u64,
+    pub current_owner: Address,
+    pub new_owner: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum OwnershipKey {
+    PendingTransfer(u64), // plan_id -> PendingPlanTransfer
+}
+
+pub fn pending_transfer(env: &Env, plan_id: u64) -> Option<PendingPlanTransfer> {
+    env.storage()
+        .persistent()
+        .get(&OwnershipKey::PendingTransfer(plan_id))
+}
+
+pub fn set_pending_transfer(env: &Env, pending: &PendingPlanTransfer) {
+    let key = OwnershipKey::PendingTransfer(pending.plan_id);
+    env.storage().persistent().set(&key, pending);
+    access_control::extend_persistent_ttl(env, &key);
+}
+
+/// Drop the plan's pending transfer, returning it if there was one.
+pub fn clear(env: &Env, plan_id: u64) -> Option<PendingPlanTransfer> {
+    let pending = pending_transfer(env, plan_id);
+    if pending.is_some() {
+        env.storage()
+            .persistent()
+            .remove(&OwnershipKey::PendingTransfer(plan_id));
+    }
+    pending
+}
+
+pub fn extend_ttl(env: &Env, plan_id: u64) {
+    access_control::renew_persistent_ttl(env, &OwnershipKey::PendingTransfer(plan_id));
+}
@@ -0,0 +1,92 @@
+use crate::InheritancePlan;
+use soroban_sdk::{contracterror, contracttype, Bytes, Env};
+
+/// Layout version of a sealed payout-details envelope.
+pub const ENVELOPE_VERSION: u8 = 1;
+
+/// `version (1) ‖ key_id (4, big-endian)`; also the AEAD associated data.
+pub const HEADER_LEN: u32 = 5;
+const NONCE_LEN: u32 = 12;
+const TAG_LEN: u32 = 16;
+
+/// Shortest well-formed envelope: header, nonce, tag and one byte of data.
+pub const MIN_ENVELOPE_LEN: u32 = HEADER_LEN + NONCE_LEN + TAG_LEN + 1;
+
+/// Errors raised when managing sealed payout details.
+///
+/// Kept separate from `InheritanceError` (at the 50-case spec limit).
+/// Codes start at 500 so they never overlap the other module enums.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PayoutDetailsError {
+    NotAdmin = 500,
+    SettlementKeyNotSet = 501,
+    StaleSettlementKey = 502,
+    PlanNotFound = 503,
+    InvalidEnvelope = 504,
+    LengthMismatch = 505,
+    AlreadySealed = 506,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum PayoutDetailsKey {
+    SettlementKeyId, // -> u32 (instance; key new envelopes must be sealed to)
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutDetailsResealedEvent {
+    pub plan_id: u64,
+    pub key_id: u32,
+    pub beneficiary_count: u32,
+}
+
+/// The settlement key new payout details must be sealed to, once the admin
+/// has registered one.
+pub fn settlement_key_id(env: &Env) -> Option<u32> {
+    env.storage()
+        .instance()
+        .get(&PayoutDetailsKey::SettlementKeyId)
+}
+
+pub fn set_settlement_key_id(env: &Env, key_id: u32) {
+    env.storage()
+        .instance()
+        .set(&PayoutDetailsKey::SettlementKeyId, &key_id);
+}
+
+/// The key id in `details`' header, or `None` if it is not a well-formed
+/// envelope (legacy plaintext, or garbage).
+pub fn envelope_key_id(details: &Bytes) -> Option<u32> {
+    if details.len() < MIN_ENVELOPE_LEN || details.get(0) != Some(ENVELOPE_VERSION) {
+        return None;
+    }
+    let mut key_id = [0u8; 4];
+    details.slice(1..HEADER_LEN).copy_into_slice(&mut key_id);
+    Some(u32::from_be_bytes(key_id))
+}
+
+/// Whether `details` is an envelope sealed to the current settlement key.
+pub fn is_sealed_to_current(env: &Env, details: &Bytes) -> bool {
+    settlement_key_id(env).is_some_and(|current| envelope_key_id(details) == Some(current))
+}
+
+/// Whether payout details supplied for a new beneficiary are acceptable.
+/// Until the admin registers a settlement key anything is accepted (the
+/// emptiness check stays in `create_beneficiary`), so existing integrations
+/// keep working during rollout.
+pub fn accepts_new(env: &Env, details: &Bytes) -> bool {
+    match settlement_key_id(env) {
+        Some(current) => envelope_key_id(details) == Some(current),
+        None => true,
+    }
+}
+
+/// Whether any beneficiary of `plan` still holds plaintext details or an
+/// envelope sealed to a retired key.
+pub fn needs_reseal(env: &Env, plan: &InheritancePlan) -> bool {
+    plan.beneficiaries
+        .iter()
+        .any(|b| !is_sealed_to_current(env, &b.bank_account))
+}
@@ -0,0 +1,234 @@
+use crate::{DistributionMethod, InheritancePlan};
+use soroban_sdk::{contracterror, contracttype, Env};
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+/// Upper bound on installments per beneficiary (ten years of monthly payouts).
+pub const MAX_INSTALLMENTS: u32 = 120;
+
+/// Errors raised when configuring payout schedules.
+///
+/// Kept separate from `InheritanceError` (at the 50-case spec limit).
+/// Codes start at 300 so they never overlap the other module enums.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PayoutError {
+    Unauthorized = 300,
+    PlanNotFound = 301,
+    NotPeriodicPlan = 302,
+    InvalidInstallmentCount = 303,
+    ScheduleLocked = 304,
+    InvalidBeneficiaryIndex = 305,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum PayoutKey {
+    InstallmentCount(u64), // plan_id -> u32 (owner override of the default count)
+    BaseAmount(u64),       // plan_id -> u64 (plan balance locked at the first payout)
+    Progress(u64, u32),    // (plan_id, beneficiary_index) -> InstallmentProgress
+}
+
+/// Plan-level payout schedule. Installment `n` (1-based) falls due at
+/// `first_due_at + (n - 1) * period_secs`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutSchedule {
+    pub plan_id: u64,
+    pub period_secs: u64,
+    pub installment_count: u32,
+    pub first_due_at: u64,
+    pub base_amount: u64, // 0 until the first installment is paid
+}
+
+/// Installments already paid to one beneficiary.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct InstallmentProgress {
+    pub paid_installments: u32,
+    pub paid_amount: u64,
+}
+
+/// Per-beneficiary view of the payout stream.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallmentStatus {
+    pub plan_id: u64,
+    pub beneficiary_index: u32,
+    pub entitlement: u64,
+    pub paid_installments: u32,
+    pub paid_amount: u64,
+    pub due_installments: u32,
+    pub next_due_at: u64, // 0 once every installment has been paid
+    pub next_amount: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallmentClaimedEvent {
+    pub plan_id: u64,
+    pub beneficiary_index: u32,
+    pub installment: u32,
+    pub amount: u64,
+    pub remaining_installments: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutScheduleSetEvent {
+    pub plan_id: u64,
+    pub installment_count: u32,
+}
+
+/// Length of one payout period, or None for lump-sum plans.
+pub fn period_secs(method: &DistributionMethod) -> Option<u64> {
+    match method {
+        DistributionMethod::LumpSum => None,
+        DistributionMethod::Monthly => Some(30 * DAY_SECS),
+        DistributionMethod::Quarterly => Some(90 * DAY_SECS),
+        DistributionMethod::Yearly => Some(365 * DAY_SECS),
+    }
+}
+
+/// Installment count used when the owner has not configured one:
+/// one year of payouts for monthly/quarterly plans, three for yearly plans.
+pub fn default_installment_count(method: &DistributionMethod) -> u32 {
+    match method {
+        DistributionMethod::LumpSum => 1,
+        DistributionMethod::Monthly => 12,
+        DistributionMethod::Quarterly => 4,
+        DistributionMethod::Yearly => 3,
+    }
+}
+
+pub fn is_periodic(method: &DistributionMethod) -> bool {
+    period_secs(method).is_some()
+}
+
+/// Build the schedule for a periodic plan.
+///
+/// The first installment falls due one period after plan creation, or at the
+/// inheritance trigger if that comes earlier: a trigger brings the stream
+/// forward but never collapses it into a lump sum.
+pub fn schedule_for(
+    env: &Env,
+    plan_id: u64,
+    plan: &InheritancePlan,
+    triggered_at: Option<u64>,
+) -> Option<PayoutSchedule> {
+    let period = period_secs(&plan.distribution_method)?;
+    let mut first_due_at = plan.created_at + period;
+    if let Some(triggered_at) = triggered_at {
+        first_due_at = first_due_at.min(triggered_at);
+    }
+    let installment_count = env
+        .storage()
+        .persistent()
+        .get(&PayoutKey::InstallmentCount(plan_id))
+        .unwrap_or(default_installment_count(&plan.distribution_method));
+    let base_amount = env
+        .storage()
+        .persistent()
+        .get(&PayoutKey::BaseAmount(plan_id))
+        .unwrap_or(0);
+    Some(PayoutSchedule {
+        plan_id,
+        period_secs: period,
+        installment_count,
+        first_due_at,
+        base_amount,
+    })
+}
+
+/// Number of installments that have fallen due by `now`.
+pub fn installments_due(schedule: &PayoutSchedule, now: u64) -> u32 {
+    if now < schedule.first_due_at {
+        return 0;
+    }
+    let elapsed_periods = (now - schedule.first_due_at) / schedule.period_secs;
+    let due = elapsed_periods.saturating_add(1);
+    due.min(schedule.installment_count as u64) as u32
+}
+
+/// Due date of installment `n` (1-based).
+pub fn due_at(schedule: &PayoutSchedule, n: u32) -> u64 {
+    schedule.first_due_at + (n.saturating_sub(1) as u64) * schedule.period_secs
+}
+
+/// Amount of installment `n` (1-based) out of `entitlement`; the last one
+/// absorbs the rounding remainder.
+pub fn installment_amount(schedule: &PayoutSchedule, entitlement: u64, n: u32) -> u64 {
+    let count = schedule.installment_count as u64;
+    let regular = entitlement / count;
+    if n as u64 == count {
+        entitlement - regular * (count - 1)
+    } else {
+        regular
+    }
+}
+
+/// The beneficiary's total stream: their share of the base amount, or of the
+/// current plan balance while the schedule is not yet locked.
+pub fn entitlement(schedule: &PayoutSchedule, plan: &InheritancePlan, allocation_bp: u32) -> u64 {
+    let base = if schedule.base_amount > 0 {
+        schedule.base_amount
+    } else {
+        plan.total_amount
+    };
+    (base as u128)
+        .checked_mul(allocation_bp as u128)
+        .and_then(|v| v.checked_div(10000))
+        .unwrap_or(0) as u64
+}
+
+pub fn get_progress(env: &Env, plan_id: u64, beneficiary_index: u32) -> InstallmentProgress {
+    env.storage()
+        .persistent()
+        .get(&PayoutKey::Progress(plan_id, beneficiary_index))
+        .unwrap_or_default()
+}
+
+pub fn set_progress(
+    env: &Env,
+    plan_id: u64,
+    beneficiary_index: u32,
+    progress: &InstallmentProgress,
+) {
+    env.storage()
+        .persistent()
+        .set(&PayoutKey::Progress(plan_id, beneficiary_index), progress);
+    access_control::extend_persistent_ttl(env, &PayoutKey::Progress(plan_id, beneficiary_index));
+}
+
+/// Lock the base amount the first time any installment is paid.
+pub fn lock_base_amount(env: &Env, schedule: &mut PayoutSchedule, plan: &InheritancePlan) {
+    if schedule.base_amount == 0 {
+        schedule.base_amount = plan.total_amount;
+        env.storage()
+            .persistent()
+            .set(&PayoutKey::BaseAmount(schedule.plan_id), &plan.total_amount);
+        access_control::extend_persistent_ttl(env, &PayoutKey::BaseAmount(schedule.plan_id));
+    }
+}
+
+pub fn set_installment_count(env: &Env, plan_id: u64, count: u32) {
+    env.storage()
+        .persistent()
+        .set(&PayoutKey::InstallmentCount(plan_id), &count);
+    access_control::extend_persistent_ttl(env, &PayoutKey::InstallmentCount(plan_id));
+}
+
+/// Renew the TTL of the plan's payout entries, including per-beneficiary progress.
+pub fn extend_plan_ttl(env: &Env, plan_id: u64, beneficiary_count: u32) {
+    access_control::renew_persistent_ttl(env, &PayoutKey::InstallmentCount(plan_id));
+    access_control::renew_persistent_ttl(env, &PayoutKey::BaseAmount(plan_id));
+    for index in 0..beneficiary_count {
+        access_control::renew_persistent_ttl(env, &PayoutKey::Progress(plan_id, index));
+    }
+}
+
+pub fn is_locked(env: &Env, plan_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&PayoutKey::BaseAmount(plan_id))
+}
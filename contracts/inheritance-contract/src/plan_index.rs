@@ -0,0 +1,205 @@
+use crate::InheritancePlan;
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+/// Upper bound on plans returned by one page.
+pub const MAX_PAGE_LIMIT: u32 = 50;
+
+/// Upper bound on index slots inspected by one page, so a long run of
+/// removed entries cannot exhaust the read budget.
+pub const MAX_PAGE_SCAN: u32 = 200;
+
+/// A set of plan ids that can be counted and paged through.
+///
+/// `All` is every plan ever created and needs no storage (plan ids are
+/// sequential). The other indexes are append-only slot lists: removing a plan
+/// empties its slot, so positions — and therefore page cursors — never shift.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PlanIndex {
+    All,
+    Pending,               // plans that are still active
+    Claimed,               // plans with at least one claim
+    Deactivated,           // plans deactivated by their owner
+    Owner(Address),        // plans currently owned by the address
+    OwnerClaimed(Address), // claimed plans owned by the address
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum PlanIndexKey {
+    Len(PlanIndex),           // -> u32 (slots ever used)
+    Count(PlanIndex),         // -> u32 (live entries)
+    Slot(PlanIndex, u32),     // position -> u64 plan_id (absent once removed)
+    Position(PlanIndex, u64), // plan_id -> u32 position (absent once removed)
+}
+
+/// One plan in a page, with the id needed to act on it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanEntry {
+    pub plan_id: u64,
+    pub plan: InheritancePlan,
+}
+
+/// A page of plans. Pass `next_cursor` as `start_after` to fetch the next
+/// page; `None` means the index has been read to the end.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanPage {
+    pub plans: Vec<PlanEntry>,
+    pub next_cursor: Option<u64>,
+}
+
+fn read_u32(env: &Env, key: &PlanIndexKey) -> u32 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn write_u32(env: &Env, key: &PlanIndexKey, value: u32) {
+    env.storage().persistent().set(key, &value);
+    access_control::extend_persistent_ttl(env, key);
+}
+
+pub fn contains(env: &Env, index: &PlanIndex, plan_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&PlanIndexKey::Position(index.clone(), plan_id))
+}
+
+/// Append `plan_id` to `index`. Does nothing if it is already present.
+pub fn insert(env: &Env, index: PlanIndex, plan_id: u64) {
+    if contains(env, &index, plan_id) {
+        return;
+    }
+    let len_key = PlanIndexKey::Len(index.clone());
+    let position = read_u32(env, &len_key);
+
+    let slot_key = PlanIndexKey::Slot(index.clone(), position);
+    env.storage().persistent().set(&slot_key, &plan_id);
+    access_control::extend_persistent_ttl(env, &slot_key);
+    let position_key = PlanIndexKey::Position(index.clone(), plan_id);
+    env.storage().persistent().set(&position_key, &position);
+    access_control::extend_persistent_ttl(env, &position_key);
+
+    write_u32(env, &len_key, position + 1);
+    let count_key = PlanIndexKey::Count(index);
+    write_u32(env, &count_key, read_u32(env, &count_key) + 1);
+}
+
+/// Remove `plan_id` from `index`, leaving its slot empty. Does nothing if it
+/// is not present.
+pub fn remove(env: &Env, index: PlanIndex, plan_id: u64) {
+    let position_key = PlanIndexKey::Position(index.clone(), plan_id);
+    let Some(position) = env.storage().persistent().get::<_, u32>(&position_key) else {
+        return;
+    };
+    env.storage().persistent().remove(&position_key);
+    env.storage()
+        .persistent()
+        .remove(&PlanIndexKey::Slot(index.clone(), position));
+
+    let count_key = PlanIndexKey::Count(index);
+    write_u32(env, &count_key, read_u32(env, &count_key).saturating_sub(1));
+}
+
+/// Number of live plans in `index`. `All` is derived from the next plan id.
+pub fn count(env: &Env, index: &PlanIndex, next_plan_id: u64) -> u32 {
+    match index {
+        PlanIndex::All => next_plan_id.saturating_sub(1) as u32,
+        _ => read_u32(env, &PlanIndexKey::Count(index.clone())),
+    }
+}
+
+/// Every live plan id in `index`, in insertion order. Only for indexes
+/// bounded by a single owner; global indexes must be paged.
+pub fn ids(env: &Env, index: &PlanIndex) -> Vec<u64> {
+    let mut ids = Vec::new(env);
+    let len = read_u32(env, &PlanIndexKey::Len(index.clone()));
+    for position in 0..len {
+        if let Some(plan_id) = env
+            .storage()
+            .persistent()
+            .get::<_, u64>(&PlanIndexKey::Slot(index.clone(), position))
+        {
+            ids.push_back(plan_id);
+        }
+    }
+    ids
+}
+
+/// Read up to `limit` plans after the cursor `start_after`.
+///
+/// For `All` the cursor is a plan id; for the other indexes it is a slot
+/// position. Callers should treat it as opaque and pass back `next_cursor`.
+/// `limit` is clamped to `1..=MAX_PAGE_LIMIT`, and at most `MAX_PAGE_SCAN`
+/// slots are inspected, so a page may hold fewer than `limit` plans while
+/// `next_cursor` is still set.
+pub fn page<F>(
+    env: &Env,
+    index: &PlanIndex,
+    next_plan_id: u64,
+    start_after: Option<u64>,
+    limit: u32,
+    load: F,
+) -> PlanPage
+where
+    F: Fn(u64) -> Option<InheritancePlan>,
+{
+    let limit = limit.clamp(1, MAX_PAGE_LIMIT);
+    let (default_first, end) = match index {
+        // Plan ids start at 1.
+        PlanIndex::All => (1, next_plan_id),
+        _ => (0, read_u32(env, &PlanIndexKey::Len(index.clone())) as u64),
+    };
+    // A cursor at u64::MAX has nothing after it.
+    let Some(first) = start_after.map_or(Some(default_first), |cursor| cursor.checked_add(1))
+    else {
+        return PlanPage {
+            plans: Vec::new(env),
+            next_cursor: None,
+        };
+    };
+
+    let mut plans = Vec::new(env);
+    let mut cursor = first;
+    let mut scanned = 0;
+    while cursor < end && plans.len() < limit && scanned < MAX_PAGE_SCAN {
+        let plan_id = match index {
+            PlanIndex::All => Some(cursor),
+            _ => env
+                .storage()
+                .persistent()
+                .get::<_, u64>(&PlanIndexKey::Slot(index.clone(), cursor as u32)),
+        };
+        if let Some(plan_id) = plan_id {
+            if let Some(plan) = load(plan_id) {
+                plans.push_back(PlanEntry { plan_id, plan });
+            }
+        }
+        cursor += 1;
+        scanned += 1;
+    }
+
+    PlanPage {
+        plans,
+        next_cursor: if cursor < end { Some(cursor - 1) } else { None },
+    }
+}
+
+/// Renew the TTL of every index entry that refers to `plan_id`.
+pub fn extend_plan_ttl(env: &Env, plan_id: u64, owner: &Address) {
+    for index in [
+        PlanIndex::Pending,
+        PlanIndex::Claimed,
+        PlanIndex::Deactivated,
+        PlanIndex::Owner(owner.clone()),
+        PlanIndex::OwnerClaimed(owner.clone()),
+    ] {
+        let position_key = PlanIndexKey::Position(index.clone(), plan_id);
+        if let Some(position) = env.storage().persistent().get::<_, u32>(&position_key) {
+            access_control::renew_persistent_ttl(env, &position_key);
+            access_control::renew_persistent_ttl(env, &PlanIndexKey::Slot(index.clone(), position));
+            access_control::renew_persistent_ttl(env, &PlanIndexKey::Len(index.clone()));
+            access_control::renew_persistent_ttl(env, &PlanIndexKey::Count(index));
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use soroban_sdk::{contracterror, contracttype, Address, Env, Vec};
+
+/// Seconds the current owner has to cancel a recovery once enough guardians
+/// have approved it.
+pub const RECOVERY_DELAY_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Seconds a proposal has to reach the guardian threshold, and then to be
+/// executed once its cancellation window ends. A lapsed proposal no longer
+/// blocks a new one.
+pub const RECOVERY_EXPIRY_SECS: u64 = 14 * 24 * 60 * 60;
+
+/// Errors raised by guardian social recovery of plan ownership.
+///
+/// Kept separate from `InheritanceError` (at the 50-case spec limit).
+/// Codes start at 800 so they never overlap the other module enums.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecoveryError {
+    Unauthorized = 800,
+    PlanNotFound = 801,
+    GuardiansNotSet = 802,
+    NotGuardian = 803,
+    InvalidNewOwner = 804,
+    RecoveryPending = 805,
+    RecoveryNotFound = 806,
+    AlreadyApproved = 807,
+    ThresholdNotMet = 808,
+    DelayNotElapsed = 809,
+    PlanTriggered = 810,
+    PlanFrozen = 811,
+    RecoveryExpired = 812,
+}
+
+/// A guardian proposal to move a plan to `new_owner`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryProposal {
+    pub plan_id: u64,
+    pub new_owner: Address,
+    pub approvals: Vec<Address>,
+    pub proposed_at: u64,
+    pub executable_at: u64,         // 0 until the guardian threshold is met
+    pub cancel_votes: Vec<Address>, // Guardians voting to withdraw the proposal
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryProposedEvent {
+    pub plan_id: u64,
+    pub new_owner: Address,
+    pub guardian: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryApprovedEvent {
+    pub plan_id: u64,
+    pub new_owner: Address,
+    pub guardian: Address,
+    pub approvals_count: u32,
+    pub executable_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryCancelledEvent {
+    pub plan_id: u64,
+    pub new_owner: Address,
+    pub cancelled_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryCancelVotedEvent {
+    pub plan_id: u64,
+    pub new_owner: Address,
+    pub guardian: Address,
+    pub votes_count: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum RecoveryKey {
+    Proposal(u64), // plan_id -> RecoveryProposal
+}
+
+pub fn proposal(env: &Env, plan_id: u64) -> Option<RecoveryProposal> {
+    env.storage()
+        .persistent()
+        .get(&RecoveryKey::Proposal(plan_id))
+}
+
+pub fn set_proposal(env: &Env, proposal: &RecoveryProposal) {
+    let key = RecoveryKey::Proposal(proposal.plan_id);
+    env.storage().persistent().set(&key, proposal);
+    access_control::extend_persistent_ttl(env, &key);
+}
+
+/// Drop the plan's pending recovery, returning it if there was one.
+pub fn clear(env: &Env, plan_id: u64) -> Option<RecoveryProposal> {
+    let pending = proposal(env, plan_id);
+    if pending.is_some() {
+        env.storage()
+            .persistent()
+            .remove(&RecoveryKey::Proposal(plan_id));
+    }
+    pending
+}
+
+/// Record `guardian`'s approval, starting the cancellation window once
+/// `threshold` approvals are in.
+pub fn add_approval(
+    env: &Env,
+    proposal: &mut RecoveryProposal,
+    guardian: &Address,
+    threshold: u32,
+) -> Result<(), RecoveryError> {
+    if proposal.approvals.contains(guardian) {
+        return Err(RecoveryError::AlreadyApproved);
+    }
+    proposal.approvals.push_back(guardian.clone());
+    if proposal.executable_at == 0 && proposal.approvals.len() >= threshold {
+        proposal.executable_at = env.ledger().timestamp().saturating_add(RECOVERY_DELAY_SECS);
+    }
+    Ok(())
+}
+
+/// Record `guardian`'s vote to withdraw the proposal. Returns whether
+/// `threshold` guardians have now voted to withdraw it.
+pub fn add_cancel_vote(
+    proposal: &mut RecoveryProposal,
+    guardian: &Address,
+    threshold: u32,
+) -> Result<bool, RecoveryError> {
+    if proposal.cancel_votes.contains(guardian) {
+        return Err(RecoveryError::AlreadyApproved);
+    }
+    proposal.cancel_votes.push_back(guardian.clone());
+    Ok(proposal.cancel_votes.len() >= threshold)
+}
+
+/// When the proposal lapses: `RECOVERY_EXPIRY_SECS` after it was proposed
+/// while the threshold is unmet, or after it became executable.
+pub fn expires_at(proposal: &RecoveryProposal) -> u64 {
+    let since = if proposal.executable_at == 0 {
+        proposal.proposed_at
+    } else {
+        proposal.executable_at
+    };
+    since.saturating_add(RECOVERY_EXPIRY_SECS)
+}
+
+pub fn is_expired(env: &Env, proposal: &RecoveryProposal) -> bool {
+    env.ledger().timestamp() >= expires_at(proposal)
+}
+
+pub fn extend_ttl(env: &Env, plan_id: u64) {
+    access_control::renew_persistent_ttl(env, &RecoveryKey::Proposal(plan_id));
+}
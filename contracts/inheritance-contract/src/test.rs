@@ -4,8 +4,11 @@ use super::*;
 use mock_token::MockToken;
 use mock_token::MockTokenClient;
 use soroban_sdk::{
-    testutils::Address as _, testutils::Events, testutils::Ledger, token, vec, Address, Bytes, Env,
-    String, Vec,
+    testutils::storage::{Instance as _, Persistent as _},
+    testutils::Address as _,
+    testutils::Events,
+    testutils::Ledger,
+    token, vec, Address, Bytes, Env, String, Vec,
 };
 
 /// Test helper for balance and mint (uses mock-token crate client).
@@ -156,6 +159,16 @@ fn test_hash_string() {
     assert_ne!(hash1, hash3);
 }
 
+#[test]
+fn test_hash_string_depends_on_content_not_length() {
+    let env = Env::default();
+
+    let alice = InheritanceContract::hash_string(&env, String::from_str(&env, "alice"));
+    let bobby = InheritanceContract::hash_string(&env, String::from_str(&env, "bobby"));
+
+    assert_ne!(alice, bobby);
+}
+
 #[test]
 fn test_hash_claim_code_valid() {
     let env = Env::default();
@@ -459,6 +472,7 @@ fn test_add_beneficiary_allocation_exceeds_limit() {
             bank_account: create_test_bytes(&env, "3333333333333333"),
             allocation_bp: 2000,
             priority: 1,
+            vesting: VestingRelease::Immediate,
         },
     );
 
@@ -516,6 +530,7 @@ fn test_remove_beneficiary_success() {
             bank_account: create_test_bytes(&env, "3333333333333333"),
             allocation_bp: 2000,
             priority: 1,
+            vesting: VestingRelease::Immediate,
         },
     );
     assert!(add_result.is_ok());
@@ -646,6 +661,7 @@ fn test_beneficiary_allocation_tracking() {
             bank_account: create_test_bytes(&env, "3333333333333333"),
             allocation_bp: 2000,
             priority: 1,
+            vesting: VestingRelease::Immediate,
         },
     );
     assert!(result.is_ok());
@@ -661,6 +677,7 @@ fn test_beneficiary_allocation_tracking() {
             bank_account: create_test_bytes(&env, "3333333333333333"),
             allocation_bp: 2000,
             priority: 1,
+            vesting: VestingRelease::Immediate,
         },
     );
     assert!(result2.is_err());
@@ -711,6 +728,87 @@ fn test_claim_success() {
     );
 }
 
+#[test]
+fn test_legacy_email_hash_is_rehashed_before_claim() {
+    let env = Env::default();
+    let (client, token, admin, owner) = setup_with_token_and_admin(&env);
+    let beneficiary = create_test_address(&env, 101);
+
+    let beneficiaries = vec![
+        &env,
+        (
+            String::from_str(&env, "Alice"),
+            String::from_str(&env, "alice@example.com"),
+            123456u32,
+            create_test_bytes(&env, "1111"),
+            10000u32,
+            1u32,
+        ),
+    ];
+
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token,
+        "Will",
+        "Inheritance Plan",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &beneficiaries,
+    ));
+
+    // Plans created before the XDR-based hash carry the legacy email hash.
+    let email = String::from_str(&env, "alice@example.com");
+    let mut plan = client.get_plan_details(&plan_id).unwrap();
+    let mut stored = plan.beneficiaries.get(0).unwrap();
+    env.as_contract(&client.address, || {
+        stored.hashed_email = InheritanceContract::legacy_hash_string(&env, &email);
+    });
+    plan.beneficiaries.set(0, stored);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Plan(plan_id), &plan);
+    });
+
+    client.submit_kyc(&beneficiary);
+    client.approve_kyc(&admin, &beneficiary);
+
+    // The length-only legacy hash is no longer accepted at claim time.
+    assert!(client
+        .try_claim_inheritance_plan(&plan_id, &beneficiary, &email, &123456u32)
+        .is_err());
+
+    // Only the admin can rehash, and only to an email matching the stored hash.
+    assert_eq!(
+        client.try_rehash_beneficiary_email(&owner, &plan_id, &0u32, &email),
+        Err(Ok(InheritanceError::NotAdmin))
+    );
+    assert_eq!(
+        client.try_rehash_beneficiary_email(
+            &admin,
+            &plan_id,
+            &0u32,
+            &String::from_str(&env, "bob@example.com")
+        ),
+        Err(Ok(InheritanceError::InvalidBeneficiaryData))
+    );
+
+    client.rehash_beneficiary_email(&admin, &plan_id, &0u32, &email);
+    let rehashed = client.get_plan_details(&plan_id).unwrap();
+    assert_eq!(
+        rehashed.beneficiaries.get(0).unwrap().hashed_email,
+        InheritanceContract::hash_string(&env, email.clone())
+    );
+    // Rehashing again is a no-op.
+    client.rehash_beneficiary_email(&admin, &plan_id, &0u32, &email);
+
+    client.claim_inheritance_plan(&plan_id, &beneficiary, &email, &123456u32);
+    assert!(client
+        .try_claim_inheritance_plan(&plan_id, &beneficiary, &email, &123456u32)
+        .is_err());
+}
+
 #[test]
 #[should_panic]
 fn test_double_claim_fails() {
@@ -1368,7 +1466,7 @@ fn test_version_returns_default() {
     let client = InheritanceContractClient::new(&env, &contract_id);
 
     let version = client.version();
-    assert_eq!(version, 1);
+    assert_eq!(version, CONTRACT_VERSION);
 }
 
 #[test]
@@ -1472,7 +1570,7 @@ fn test_migrate_runs_when_version_outdated() {
 
     // After migration, version should be CONTRACT_VERSION
     let version = client.version();
-    assert_eq!(version, 1);
+    assert_eq!(version, CONTRACT_VERSION);
 }
 
 #[test]
@@ -1672,8 +1770,9 @@ fn test_admin_retrieval() {
     client.deactivate_inheritance_plan(&owner2, &plan2);
 
     // Admin retrieves all
-    let all_deactivated = client.get_all_deactivated_plans(&admin);
-    assert_eq!(all_deactivated.len(), 2);
+    let all_deactivated = client.get_all_deactivated_plans(&admin, &None, &10);
+    assert_eq!(all_deactivated.plans.len(), 2);
+    assert_eq!(all_deactivated.next_cursor, None);
 }
 
 #[test]
@@ -1827,11 +1926,11 @@ fn test_get_all_claimed_plans() {
         &123456u32,
     );
 
-    let plans = client.get_all_claimed_plans(&admin);
-    assert_eq!(plans.len(), 1);
+    let plans = client.get_all_claimed_plans(&admin, &None, &10);
+    assert_eq!(plans.plans.len(), 1);
 
     let non_admin = create_test_address(&env, 2);
-    let result = client.try_get_all_claimed_plans(&non_admin);
+    let result = client.try_get_all_claimed_plans(&non_admin, &None, &10);
     assert!(result.is_err());
 }
 
@@ -1946,11 +2045,11 @@ fn test_get_all_plans_admin_only_and_includes_active_inactive() {
 
     client.deactivate_inheritance_plan(&user_a, &plan_a1);
 
-    let all_plans = client.get_all_plans(&admin);
-    assert_eq!(all_plans.len(), 3);
+    let all_plans = client.get_all_plans(&admin, &None, &10);
+    assert_eq!(all_plans.plans.len(), 3);
 
     let non_admin = create_test_address(&env, 999);
-    let unauthorized = client.try_get_all_plans(&non_admin);
+    let unauthorized = client.try_get_all_plans(&non_admin, &None, &10);
     assert!(unauthorized.is_err());
 }
 
@@ -2027,12 +2126,12 @@ fn test_get_all_pending_plans_admin_only() {
 
     client.deactivate_inheritance_plan(&user_a, &plan_a);
 
-    let pending = client.get_all_pending_plans(&admin);
-    assert_eq!(pending.len(), 1);
-    assert!(pending.get(0).unwrap().is_active);
+    let pending = client.get_all_pending_plans(&admin, &None, &10);
+    assert_eq!(pending.plans.len(), 1);
+    assert!(pending.plans.get(0).unwrap().plan.is_active);
 
     let not_admin = create_test_address(&env, 999);
-    let unauthorized = client.try_get_all_pending_plans(&not_admin);
+    let unauthorized = client.try_get_all_pending_plans(&not_admin, &None, &10);
     assert!(unauthorized.is_err());
 }
 
@@ -2559,7 +2658,7 @@ fn test_inheritance_claim_not_blocked_by_loans() {
     );
 
     // After claiming, total_amount is reduced by base_payout so claimable is 0
-    let claimable = client.get_claimable_amount(&plan_id);
+    let claimable = client.get_claimable_amount(&plan_id).get(0).unwrap().amount;
     assert_eq!(claimable, 0);
 }
 
@@ -2585,25 +2684,27 @@ fn test_inheritance_claim_bypasses_time_check_when_triggered() {
     client.submit_kyc(&beneficiary);
     client.approve_kyc(&admin, &beneficiary);
 
-    // Without trigger, claim should fail (time not met)
-    let result = client.try_claim_inheritance_plan(
+    // Without trigger, the first installment is not due yet
+    let result = client.try_claim_next_installment(
         &plan_id,
         &beneficiary,
         &String::from_str(&env, "alice@example.com"),
         &123456u32,
     );
-    assert!(result.is_err());
+    assert_eq!(result, Err(Ok(InheritanceError::ClaimNotAllowedYet)));
 
     // Trigger inheritance
     client.trigger_inheritance(&admin, &plan_id);
 
-    // Now claim should succeed despite time not elapsed
-    client.claim_inheritance_plan(
+    // Now the first installment is claimable despite time not elapsed
+    client.claim_next_installment(
         &plan_id,
         &beneficiary,
         &String::from_str(&env, "alice@example.com"),
         &123456u32,
     );
+    let status = client.get_installment_status(&plan_id, &0u32);
+    assert_eq!(status.paid_installments, 1);
 }
 
 #[test]
@@ -2623,7 +2724,7 @@ fn test_get_claimable_amount() {
     ));
 
     // No loans — full amount claimable (98,000 after 2% fee)
-    let claimable = client.get_claimable_amount(&plan_id);
+    let claimable = client.get_claimable_amount(&plan_id).get(0).unwrap().amount;
     assert_eq!(claimable, 98_000);
 
     // Simulate loans
@@ -2635,7 +2736,7 @@ fn test_get_claimable_amount() {
             .set(&DataKey::Plan(plan_id), &plan);
     });
 
-    let claimable = client.get_claimable_amount(&plan_id);
+    let claimable = client.get_claimable_amount(&plan_id).get(0).unwrap().amount;
     assert_eq!(claimable, 78_000);
 }
 
@@ -2700,7 +2801,7 @@ fn test_full_loan_recall_workflow() {
     );
 
     // After claiming, total_amount is reduced by base_payout so claimable is 0
-    let claimable = client.get_claimable_amount(&plan_id);
+    let claimable = client.get_claimable_amount(&plan_id).get(0).unwrap().amount;
     assert_eq!(claimable, 0);
 
     // Verify full trigger info
@@ -4920,6 +5021,7 @@ fn test_batch_add_beneficiaries_success() {
             bank_account: create_test_bytes(&env, "3333333333333333"),
             allocation_bp: 6000u32,
             priority: 1u32,
+            vesting: VestingRelease::Immediate,
         },
         BeneficiaryInput {
             name: String::from_str(&env, "Dave"),
@@ -4928,6 +5030,7 @@ fn test_batch_add_beneficiaries_success() {
             bank_account: create_test_bytes(&env, "4444444444444444"),
             allocation_bp: 4000u32,
             priority: 2u32,
+            vesting: VestingRelease::Immediate,
         },
     ];
     let (success, fail) = client.batch_add_beneficiaries(&owner, &plan_id, &inputs);
@@ -4952,6 +5055,7 @@ fn test_batch_add_beneficiaries_partial_fail_over_allocation() {
             bank_account: create_test_bytes(&env, "5555555555555555"),
             allocation_bp: 1000u32,
             priority: 1u32,
+            vesting: VestingRelease::Immediate,
         },
     ];
     let (success, fail) = client.batch_add_beneficiaries(&owner, &plan_id, &inputs);
@@ -4973,6 +5077,7 @@ fn test_batch_add_beneficiaries_limit_exceeded() {
             bank_account: create_test_bytes(&env, "1234"),
             allocation_bp: 100u32,
             priority: i,
+            vesting: VestingRelease::Immediate,
         });
     }
     let result = client.try_batch_add_beneficiaries(&owner, &plan_id, &inputs);
@@ -4994,6 +5099,7 @@ fn test_batch_add_beneficiaries_unauthorized() {
             bank_account: create_test_bytes(&env, "1234"),
             allocation_bp: 1000u32,
             priority: 1u32,
+            vesting: VestingRelease::Immediate,
         },
     ];
     let result = client.try_batch_add_beneficiaries(&stranger, &plan_id, &inputs);
@@ -5868,6 +5974,13 @@ fn test_admin_can_assign_and_revoke_roles() {
 
     client.revoke_role(&admin, &target, &access_control::Role::Guardian);
     assert!(!client.has_role(&target, &access_control::Role::Guardian));
+
+    // Admin only moves with the two-step transfer, never by revocation.
+    assert_eq!(
+        client.try_revoke_role(&admin, &admin, &access_control::Role::Admin),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+    assert!(client.has_role(&admin, &access_control::Role::Admin));
 }
 
 #[test]
@@ -5918,6 +6031,138 @@ fn test_get_roles_returns_all_assigned_roles() {
     assert_eq!(roles.len(), 2);
 }
 
+#[test]
+fn test_role_grant_expires() {
+    let env = Env::default();
+    let (client, _token_id, admin, _owner) = setup_with_token_and_admin(&env);
+    let officer = Address::generate(&env);
+    let expires_at = env.ledger().timestamp() + 3600;
+
+    client.assign_role_until(
+        &admin,
+        &officer,
+        &access_control::Role::KycOfficer,
+        &expires_at,
+    );
+    assert!(client.has_role(&officer, &access_control::Role::KycOfficer));
+
+    let members = client.get_role_members(&access_control::Role::KycOfficer, &0u32, &10u32);
+    assert_eq!(members.len(), 1);
+    assert_eq!(members.get(0).unwrap().account, officer);
+    assert_eq!(members.get(0).unwrap().expires_at, expires_at);
+
+    env.ledger().with_mut(|li| li.timestamp = expires_at);
+    assert!(!client.has_role(&officer, &access_control::Role::KycOfficer));
+    assert_eq!(client.get_roles(&officer).len(), 0);
+}
+
+#[test]
+fn test_role_admin_can_manage_delegated_role() {
+    let env = Env::default();
+    let (client, _token_id, admin, owner) = setup_with_token_and_admin_no_kyc(&env);
+    let compliance_lead = Address::generate(&env);
+    let officer = Address::generate(&env);
+
+    // Only the admin may grant KYC officers until the role is delegated.
+    client.assign_role(&admin, &compliance_lead, &access_control::Role::Guardian);
+    assert_eq!(
+        client.try_assign_role(
+            &compliance_lead,
+            &officer,
+            &access_control::Role::KycOfficer
+        ),
+        Err(Ok(InheritanceError::NotAdmin))
+    );
+
+    client.set_role_admin(
+        &admin,
+        &access_control::Role::KycOfficer,
+        &access_control::Role::Guardian,
+    );
+    assert_eq!(
+        client.get_role_admin(&access_control::Role::KycOfficer),
+        access_control::Role::Guardian
+    );
+    client.assign_role(
+        &compliance_lead,
+        &officer,
+        &access_control::Role::KycOfficer,
+    );
+
+    // The officer can review KYC but not pause the contract.
+    client.submit_kyc(&owner);
+    client.approve_kyc(&officer, &owner);
+    assert_eq!(
+        client.try_pause(&officer),
+        Err(Ok(InheritanceError::NotAdmin))
+    );
+
+    // The admin role itself cannot be re-parented or granted directly.
+    assert!(client
+        .try_set_role_admin(
+            &admin,
+            &access_control::Role::Admin,
+            &access_control::Role::Guardian
+        )
+        .is_err());
+    assert!(client
+        .try_assign_role(&compliance_lead, &officer, &access_control::Role::Admin)
+        .is_err());
+
+    client.revoke_role(
+        &compliance_lead,
+        &officer,
+        &access_control::Role::KycOfficer,
+    );
+    assert_eq!(
+        client
+            .get_role_members(&access_control::Role::KycOfficer, &0u32, &10u32)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_pauser_and_arbitrator_roles() {
+    let env = Env::default();
+    let (client, _token_id, admin, _owner) = setup_with_token_and_admin(&env);
+    let pauser = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    client.assign_role(&admin, &pauser, &access_control::Role::Pauser);
+    client.pause(&pauser);
+    assert!(client.is_paused());
+    client.unpause(&pauser);
+    assert!(!client.is_paused());
+
+    assert!(!client.is_arbitrator(&arbitrator));
+    client.assign_role(&admin, &arbitrator, &access_control::Role::Arbitrator);
+    assert!(client.is_arbitrator(&arbitrator));
+}
+
+#[test]
+fn test_role_changes_emit_events() {
+    let env = Env::default();
+    let (client, _token_id, admin, _owner) = setup_with_token_and_admin(&env);
+    let pauser = Address::generate(&env);
+
+    client.assign_role(&admin, &pauser, &access_control::Role::Pauser);
+    let events = env.events().all();
+    let granted = events.get(events.len() - 1).unwrap();
+    assert_eq!(
+        granted.1,
+        (symbol_short!("ROLE"), symbol_short!("GRANTED")).into_val(&env)
+    );
+
+    client.revoke_role(&admin, &pauser, &access_control::Role::Pauser);
+    let events = env.events().all();
+    let revoked = events.get(events.len() - 1).unwrap();
+    assert_eq!(
+        revoked.1,
+        (symbol_short!("ROLE"), symbol_short!("REVOKED")).into_val(&env)
+    );
+}
+
 #[test]
 fn test_unauthorized_approve_kyc_rejected() {
     let env = Env::default();
@@ -6153,3 +6398,2397 @@ fn test_set_conditions_blocked_after_trigger() {
     let result = client.try_add_time_trigger(&owner, &plan_id, &9999u64);
     assert!(result.is_err());
 }
+
+// ─── Dispute Resolution Tests ─────────────────────────────────────────────────
+
+/// Creates a LumpSum plan with a KYC-approved beneficiary bound to their
+/// address, and a registered arbitrator.
+/// Returns (client, admin, beneficiary, arbitrator, plan_id).
+fn setup_plan_for_disputes(
+    env: &Env,
+) -> (
+    InheritanceContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    u64,
+) {
+    let (client, token, admin, owner) = setup_with_token_and_admin(env);
+    let beneficiary = create_test_address(env, 300);
+    let arbitrator = create_test_address(env, 301);
+
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        env,
+        &owner,
+        &token,
+        "Will",
+        "My will",
+        100_000u64,
+        DistributionMethod::LumpSum,
+        &one_beneficiary(env, "Alice", "alice@example.com", 123456),
+    ));
+
+    client.bind_beneficiary(
+        &owner,
+        &plan_id,
+        &0u32,
+        &BeneficiaryBinding::Address(beneficiary.clone()),
+    );
+    client.submit_kyc(&beneficiary);
+    client.approve_kyc(&admin, &beneficiary);
+    client.add_arbitrator(&admin, &arbitrator);
+
+    (client, admin, beneficiary, arbitrator, plan_id)
+}
+
+#[test]
+fn test_file_dispute_freezes_plan_and_blocks_claim() {
+    let env = Env::default();
+    let (client, _admin, beneficiary, _arbitrator, plan_id) = setup_plan_for_disputes(&env);
+
+    let dispute_id = client.file_dispute(
+        &beneficiary,
+        &plan_id,
+        &String::from_str(&env, "Will was signed under duress"),
+    );
+
+    let dispute = client.get_dispute(&dispute_id).unwrap();
+    assert_eq!(dispute.status, DisputeStatus::Filed);
+    assert_eq!(dispute.arbitrator, None);
+    assert!(client.has_open_dispute(&plan_id));
+
+    let result = client.try_claim_as_beneficiary(&plan_id, &0u32, &beneficiary, &None);
+    assert_eq!(result, Err(Ok(InheritanceError::PlanNotActive)));
+}
+
+#[test]
+fn test_resolved_dispute_unfreezes_plan_and_allows_claim() {
+    let env = Env::default();
+    let (client, admin, beneficiary, arbitrator, plan_id) = setup_plan_for_disputes(&env);
+
+    let dispute_id = client.file_dispute(
+        &beneficiary,
+        &plan_id,
+        &String::from_str(&env, "Contested allocation"),
+    );
+    client.assign_arbitrator(&admin, &dispute_id, &arbitrator);
+    client.move_to_review(&arbitrator, &dispute_id);
+    client.resolve_dispute(
+        &arbitrator,
+        &dispute_id,
+        &String::from_str(&env, "Allocation confirmed"),
+    );
+
+    let dispute = client.get_dispute(&dispute_id).unwrap();
+    assert_eq!(dispute.status, DisputeStatus::Resolved);
+    assert_eq!(dispute.arbitrator, Some(arbitrator));
+    assert!(!client.has_open_dispute(&plan_id));
+
+    client.claim_as_beneficiary(&plan_id, &0u32, &beneficiary, &None);
+}
+
+#[test]
+fn test_plan_stays_frozen_until_all_disputes_closed() {
+    let env = Env::default();
+    let (client, admin, beneficiary, arbitrator, plan_id) = setup_plan_for_disputes(&env);
+
+    let owner = client.get_plan_details(&plan_id).unwrap().owner;
+
+    let first = client.file_dispute(&beneficiary, &plan_id, &String::from_str(&env, "First"));
+    let second = client.file_dispute(&owner, &plan_id, &String::from_str(&env, "Second"));
+    client.assign_arbitrator(&admin, &first, &arbitrator);
+    client.assign_arbitrator(&admin, &second, &arbitrator);
+
+    client.reject_dispute(&arbitrator, &first, &String::from_str(&env, "No merit"));
+    assert!(client.has_open_dispute(&plan_id));
+    let result = client.try_claim_as_beneficiary(&plan_id, &0u32, &beneficiary, &None);
+    assert_eq!(result, Err(Ok(InheritanceError::PlanNotActive)));
+
+    client.reject_dispute(&arbitrator, &second, &String::from_str(&env, "No merit"));
+    assert!(!client.has_open_dispute(&plan_id));
+    client.claim_as_beneficiary(&plan_id, &0u32, &beneficiary, &None);
+
+    let disputes = client.get_plan_disputes(&plan_id);
+    assert_eq!(disputes.len(), 2);
+    assert_eq!(disputes.get(0).unwrap().status, DisputeStatus::Rejected);
+    assert_eq!(disputes.get(1).unwrap().status, DisputeStatus::Rejected);
+}
+
+#[test]
+fn test_dispute_workflow_requires_assigned_arbitrator() {
+    let env = Env::default();
+    let (client, admin, beneficiary, arbitrator, plan_id) = setup_plan_for_disputes(&env);
+    let other = create_test_address(&env, 302);
+    client.add_arbitrator(&admin, &other);
+
+    let dispute_id = client.file_dispute(&beneficiary, &plan_id, &String::from_str(&env, "Why"));
+
+    // Nobody assigned yet
+    let result = client.try_move_to_review(&arbitrator, &dispute_id);
+    assert_eq!(result, Err(Ok(DisputeError::NotAssignedArbitrator)));
+
+    client.assign_arbitrator(&admin, &dispute_id, &arbitrator);
+    let result = client.try_move_to_review(&other, &dispute_id);
+    assert_eq!(result, Err(Ok(DisputeError::NotAssignedArbitrator)));
+
+    // Resolution requires review first
+    let result =
+        client.try_resolve_dispute(&arbitrator, &dispute_id, &String::from_str(&env, "Done"));
+    assert_eq!(result, Err(Ok(DisputeError::InvalidDisputeStatus)));
+
+    client.move_to_review(&arbitrator, &dispute_id);
+    let result = client.try_move_to_review(&arbitrator, &dispute_id);
+    assert_eq!(result, Err(Ok(DisputeError::InvalidDisputeStatus)));
+}
+
+#[test]
+fn test_assign_unregistered_arbitrator_fails() {
+    let env = Env::default();
+    let (client, admin, beneficiary, arbitrator, plan_id) = setup_plan_for_disputes(&env);
+    let stranger = create_test_address(&env, 303);
+
+    let dispute_id = client.file_dispute(&beneficiary, &plan_id, &String::from_str(&env, "Why"));
+    let result = client.try_assign_arbitrator(&admin, &dispute_id, &stranger);
+    assert_eq!(result, Err(Ok(DisputeError::ArbitratorNotRegistered)));
+
+    client.remove_arbitrator(&admin, &arbitrator);
+    assert!(!client.is_arbitrator(&arbitrator));
+    let result = client.try_assign_arbitrator(&admin, &dispute_id, &arbitrator);
+    assert_eq!(result, Err(Ok(DisputeError::ArbitratorNotRegistered)));
+}
+
+#[test]
+fn test_file_dispute_validation() {
+    let env = Env::default();
+    let (client, _admin, beneficiary, _arbitrator, plan_id) = setup_plan_for_disputes(&env);
+    let no_kyc = create_test_address(&env, 304);
+
+    let result = client.try_file_dispute(&no_kyc, &plan_id, &String::from_str(&env, "Why"));
+    assert_eq!(result, Err(Ok(DisputeError::KycNotApproved)));
+
+    let result = client.try_file_dispute(&beneficiary, &plan_id, &String::from_str(&env, ""));
+    assert_eq!(result, Err(Ok(DisputeError::MissingReason)));
+
+    let result = client.try_file_dispute(&beneficiary, &999u64, &String::from_str(&env, "Why"));
+    assert_eq!(result, Err(Ok(DisputeError::PlanNotFound)));
+
+    client.file_dispute(&beneficiary, &plan_id, &String::from_str(&env, "Why"));
+    let result = client.try_file_dispute(&beneficiary, &plan_id, &String::from_str(&env, "Again"));
+    assert_eq!(result, Err(Ok(DisputeError::DisputeAlreadyOpen)));
+}
+
+#[test]
+fn test_file_dispute_requires_plan_party() {
+    let env = Env::default();
+    let (client, admin, _beneficiary, _arbitrator, plan_id) = setup_plan_for_disputes(&env);
+    let owner = client.get_plan_details(&plan_id).unwrap().owner;
+    let guardian = create_test_address(&env, 305);
+    let stranger = create_test_address(&env, 306);
+    for address in [&guardian, &stranger] {
+        client.submit_kyc(address);
+        client.approve_kyc(&admin, address);
+    }
+
+    let result = client.try_file_dispute(&stranger, &plan_id, &String::from_str(&env, "Why"));
+    assert_eq!(result, Err(Ok(DisputeError::NotPlanParty)));
+    assert!(!client.has_open_dispute(&plan_id));
+
+    client.file_dispute(&owner, &plan_id, &String::from_str(&env, "Why"));
+    client.set_guardians(&owner, &plan_id, &vec![&env, guardian.clone()], &1u32);
+    client.file_dispute(&guardian, &plan_id, &String::from_str(&env, "Why"));
+    assert_eq!(client.get_plan_disputes(&plan_id).len(), 2);
+}
+
+#[test]
+fn test_undecided_dispute_lapses_and_releases_plan() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, admin, beneficiary, arbitrator, plan_id) = setup_plan_for_disputes(&env);
+
+    let dispute_id = client.file_dispute(&beneficiary, &plan_id, &String::from_str(&env, "Why"));
+    client.assign_arbitrator(&admin, &dispute_id, &arbitrator);
+    let dispute = client.get_dispute(&dispute_id).unwrap();
+    assert_eq!(dispute.expires_at, 1000 + disputes::DISPUTE_EXPIRY_SECS);
+
+    env.ledger().set_timestamp(dispute.expires_at - 1);
+    assert!(client.has_open_dispute(&plan_id));
+    assert!(client.get_plan_freeze(&plan_id).is_some());
+
+    env.ledger().set_timestamp(dispute.expires_at);
+    assert!(!client.has_open_dispute(&plan_id));
+    assert!(client.get_plan_freeze(&plan_id).is_none());
+    let result = client.try_move_to_review(&arbitrator, &dispute_id);
+    assert_eq!(result, Err(Ok(DisputeError::InvalidDisputeStatus)));
+    let result =
+        client.try_reject_dispute(&arbitrator, &dispute_id, &String::from_str(&env, "Late"));
+    assert_eq!(result, Err(Ok(DisputeError::InvalidDisputeStatus)));
+
+    client.claim_as_beneficiary(&plan_id, &0u32, &beneficiary, &None);
+}
+
+#[test]
+fn test_party_cannot_refile_disputes_indefinitely() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, admin, beneficiary, arbitrator, plan_id) = setup_plan_for_disputes(&env);
+    let reason = String::from_str(&env, "Why");
+
+    // An upheld dispute does not count against the party.
+    let upheld = client.file_dispute(&beneficiary, &plan_id, &reason);
+    client.assign_arbitrator(&admin, &upheld, &arbitrator);
+    client.move_to_review(&arbitrator, &upheld);
+    client.resolve_dispute(&arbitrator, &upheld, &String::from_str(&env, "Fixed"));
+
+    let rejected = client.file_dispute(&beneficiary, &plan_id, &reason);
+    client.assign_arbitrator(&admin, &rejected, &arbitrator);
+    client.reject_dispute(&arbitrator, &rejected, &String::from_str(&env, "No merit"));
+
+    let lapsed = client.file_dispute(&beneficiary, &plan_id, &reason);
+    env.ledger()
+        .set_timestamp(client.get_dispute(&lapsed).unwrap().expires_at);
+    assert!(!client.has_open_dispute(&plan_id));
+
+    let result = client.try_file_dispute(&beneficiary, &plan_id, &reason);
+    assert_eq!(result, Err(Ok(DisputeError::DisputeLimitReached)));
+    assert!(client.get_plan_freeze(&plan_id).is_none());
+    client.claim_as_beneficiary(&plan_id, &0u32, &beneficiary, &None);
+}
+
+// ─── Plan Freeze & Legal Hold Tests ───────────────────────────────────────────
+
+#[test]
+fn test_freeze_plan_blocks_withdraw_until_unfrozen() {
+    let env = Env::default();
+    let (client, token, admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token,
+        "Will",
+        "My will",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+
+    client.freeze_plan(
+        &admin,
+        &plan_id,
+        &String::from_str(&env, "Suspicious activity"),
+        &0u64,
+    );
+    let record = client.get_plan_freeze(&plan_id).unwrap();
+    assert_eq!(record.frozen_by, admin);
+
+    let result = client.try_withdraw(&owner, &token, &plan_id, &100u64);
+    assert_eq!(result, Err(Ok(InheritanceError::PlanNotActive)));
+
+    let result = client.try_freeze_plan(&admin, &plan_id, &String::from_str(&env, "Again"), &0u64);
+    assert_eq!(result, Err(Ok(FreezeError::AlreadyFrozen)));
+
+    client.unfreeze_plan(&admin, &plan_id);
+    assert!(client.get_plan_freeze(&plan_id).is_none());
+    client.withdraw(&owner, &token, &plan_id, &100u64);
+}
+
+#[test]
+fn test_freeze_plan_expires() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, token, admin, owner) = setup_with_token_and_admin(&env);
+    let guardian = create_test_address(&env, 310);
+    client.assign_role(&admin, &guardian, &Role::Guardian);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token,
+        "Will",
+        "My will",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+
+    let result =
+        client.try_freeze_plan(&guardian, &plan_id, &String::from_str(&env, "x"), &1000u64);
+    assert_eq!(result, Err(Ok(FreezeError::InvalidExpiry)));
+
+    client.freeze_plan(&guardian, &plan_id, &String::from_str(&env, "x"), &2000u64);
+    assert!(client
+        .try_withdraw(&owner, &token, &plan_id, &100u64)
+        .is_err());
+
+    env.ledger().set_timestamp(2000);
+    assert!(client.get_plan_freeze(&plan_id).is_none());
+    client.withdraw(&owner, &token, &plan_id, &100u64);
+}
+
+#[test]
+fn test_freeze_requires_admin_or_guardian() {
+    let env = Env::default();
+    let (client, token, _admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token,
+        "Will",
+        "My will",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+
+    let result = client.try_freeze_plan(&owner, &plan_id, &String::from_str(&env, "x"), &0u64);
+    assert_eq!(result, Err(Ok(FreezeError::Unauthorized)));
+    let result = client.try_add_legal_hold(&owner, &plan_id, &String::from_str(&env, "x"), &0u64);
+    assert_eq!(result, Err(Ok(FreezeError::Unauthorized)));
+}
+
+#[test]
+fn test_unfreeze_refused_while_dispute_open() {
+    let env = Env::default();
+    let (client, admin, beneficiary, arbitrator, plan_id) = setup_plan_for_disputes(&env);
+
+    let dispute_id = client.file_dispute(&beneficiary, &plan_id, &String::from_str(&env, "Why"));
+    let result = client.try_unfreeze_plan(&admin, &plan_id);
+    assert_eq!(result, Err(Ok(FreezeError::DisputeOpen)));
+
+    // An admin freeze takes over the dispute freeze and outlives the dispute.
+    client.freeze_plan(
+        &admin,
+        &plan_id,
+        &String::from_str(&env, "Court order"),
+        &0u64,
+    );
+    client.assign_arbitrator(&admin, &dispute_id, &arbitrator);
+    client.reject_dispute(
+        &arbitrator,
+        &dispute_id,
+        &String::from_str(&env, "No merit"),
+    );
+    assert_eq!(client.get_plan_freeze(&plan_id).unwrap().frozen_by, admin);
+
+    client.unfreeze_plan(&admin, &plan_id);
+    assert!(client.get_plan_freeze(&plan_id).is_none());
+}
+
+#[test]
+fn test_expired_admin_freeze_does_not_release_disputed_plan() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, admin, beneficiary, _arbitrator, plan_id) = setup_plan_for_disputes(&env);
+
+    client.file_dispute(&beneficiary, &plan_id, &String::from_str(&env, "Why"));
+    client.freeze_plan(
+        &admin,
+        &plan_id,
+        &String::from_str(&env, "Review"),
+        &2000u64,
+    );
+
+    env.ledger().set_timestamp(2000);
+    assert!(client.get_plan_freeze(&plan_id).is_none());
+    assert!(client.has_open_dispute(&plan_id));
+    let result = client.try_claim_as_beneficiary(&plan_id, &0u32, &beneficiary, &None);
+    assert_eq!(result, Err(Ok(InheritanceError::PlanNotActive)));
+}
+
+#[test]
+fn test_legal_hold_blocks_claim_until_removed() {
+    let env = Env::default();
+    let (client, admin, beneficiary, _arbitrator, plan_id) = setup_plan_for_disputes(&env);
+
+    client.add_legal_hold(&admin, &plan_id, &String::from_str(&env, "Probate"), &0u64);
+    let hold = client.get_legal_hold(&plan_id).unwrap();
+    assert_eq!(hold.added_by, admin);
+
+    let result = client.try_add_legal_hold(&admin, &plan_id, &String::from_str(&env, "x"), &0u64);
+    assert_eq!(result, Err(Ok(FreezeError::LegalHoldExists)));
+
+    let result = client.try_claim_as_beneficiary(&plan_id, &0u32, &beneficiary, &None);
+    assert_eq!(result, Err(Ok(InheritanceError::PlanNotActive)));
+
+    client.remove_legal_hold(&admin, &plan_id);
+    let result = client.try_remove_legal_hold(&admin, &plan_id);
+    assert_eq!(result, Err(Ok(FreezeError::LegalHoldNotFound)));
+
+    client.claim_as_beneficiary(&plan_id, &0u32, &beneficiary, &None);
+}
+
+#[test]
+fn test_guardian_cannot_lift_admin_placed_freeze_or_hold() {
+    let env = Env::default();
+    let (client, admin, _beneficiary, _arbitrator, plan_id) = setup_plan_for_disputes(&env);
+    let guardian = create_test_address(&env, 302);
+    client.assign_role(&admin, &guardian, &Role::Guardian);
+
+    client.freeze_plan(
+        &admin,
+        &plan_id,
+        &String::from_str(&env, "Court order"),
+        &0u64,
+    );
+    client.add_legal_hold(&admin, &plan_id, &String::from_str(&env, "Probate"), &0u64);
+    assert_eq!(
+        client.get_plan_freeze(&plan_id).unwrap().placed_as,
+        Role::Admin
+    );
+
+    let result = client.try_unfreeze_plan(&guardian, &plan_id);
+    assert_eq!(result, Err(Ok(FreezeError::Unauthorized)));
+    let result = client.try_remove_legal_hold(&guardian, &plan_id);
+    assert_eq!(result, Err(Ok(FreezeError::Unauthorized)));
+
+    client.unfreeze_plan(&admin, &plan_id);
+    client.remove_legal_hold(&admin, &plan_id);
+
+    // Guardian-placed measures can be lifted by the guardian or by an admin.
+    client.freeze_plan(
+        &guardian,
+        &plan_id,
+        &String::from_str(&env, "Review"),
+        &0u64,
+    );
+    client.add_legal_hold(
+        &guardian,
+        &plan_id,
+        &String::from_str(&env, "Probate"),
+        &0u64,
+    );
+    assert_eq!(
+        client.get_legal_hold(&plan_id).unwrap().placed_as,
+        Role::Guardian
+    );
+    client.unfreeze_plan(&guardian, &plan_id);
+    client.remove_legal_hold(&admin, &plan_id);
+}
+
+// ─── Periodic Payouts ────────────────────────────
+
+const MONTH_SECS: u64 = 30 * 24 * 60 * 60;
+
+fn setup_periodic_plan(
+    env: &Env,
+    method: DistributionMethod,
+) -> (
+    InheritanceContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    u64,
+) {
+    let (client, token, admin, owner) = setup_with_token_and_admin(env);
+    let beneficiary = create_test_address(env, 300);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        env,
+        &owner,
+        &token,
+        "Will",
+        "Monthly payouts",
+        100_000u64,
+        method,
+        &one_beneficiary(env, "Alice", "alice@example.com", 123456),
+    ));
+    client.submit_kyc(&beneficiary);
+    client.approve_kyc(&admin, &beneficiary);
+    (client, admin, owner, beneficiary, plan_id)
+}
+
+fn claim_installment(
+    env: &Env,
+    client: &InheritanceContractClient<'_>,
+    plan_id: u64,
+    beneficiary: &Address,
+) -> Result<(), Result<InheritanceError, soroban_sdk::InvokeError>> {
+    client
+        .try_claim_next_installment(
+            &plan_id,
+            beneficiary,
+            &String::from_str(env, "alice@example.com"),
+            &123456u32,
+        )
+        .map(|_| ())
+}
+
+#[test]
+fn test_payout_schedule_defaults() {
+    let env = Env::default();
+    let (client, _admin, _owner, _beneficiary, plan_id) =
+        setup_periodic_plan(&env, DistributionMethod::Quarterly);
+
+    let schedule = client.get_payout_schedule(&plan_id).unwrap();
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert_eq!(schedule.installment_count, 4);
+    assert_eq!(schedule.period_secs, 90 * 24 * 60 * 60);
+    assert_eq!(
+        schedule.first_due_at,
+        plan.created_at + schedule.period_secs
+    );
+    assert_eq!(schedule.base_amount, 0);
+}
+
+#[test]
+fn test_payout_schedule_none_for_lump_sum() {
+    let env = Env::default();
+    let (client, _admin, owner, _beneficiary, plan_id) =
+        setup_periodic_plan(&env, DistributionMethod::LumpSum);
+
+    assert!(client.get_payout_schedule(&plan_id).is_none());
+    let result = client.try_set_payout_schedule(&owner, &plan_id, &6u32);
+    assert_eq!(result, Err(Ok(PayoutError::NotPeriodicPlan)));
+}
+
+#[test]
+fn test_claim_inheritance_plan_rejects_periodic_plan() {
+    let env = Env::default();
+    let (client, _admin, _owner, beneficiary, plan_id) =
+        setup_periodic_plan(&env, DistributionMethod::Monthly);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += 400 * 24 * 60 * 60);
+    let result = client.try_claim_inheritance_plan(
+        &plan_id,
+        &beneficiary,
+        &String::from_str(&env, "alice@example.com"),
+        &123456u32,
+    );
+    assert_eq!(result, Err(Ok(InheritanceError::ClaimNotAllowedYet)));
+}
+
+#[test]
+fn test_claim_next_installment_pays_one_tranche_per_period() {
+    let env = Env::default();
+    let (client, _admin, owner, beneficiary, plan_id) =
+        setup_periodic_plan(&env, DistributionMethod::Monthly);
+    client.set_payout_schedule(&owner, &plan_id, &3u32);
+
+    // Nothing due before the first period has elapsed
+    let result = claim_installment(&env, &client, plan_id, &beneficiary);
+    assert_eq!(result, Err(Ok(InheritanceError::ClaimNotAllowedYet)));
+
+    env.ledger().with_mut(|li| li.timestamp += MONTH_SECS);
+    claim_installment(&env, &client, plan_id, &beneficiary).unwrap();
+
+    // Only one tranche per period
+    let result = claim_installment(&env, &client, plan_id, &beneficiary);
+    assert_eq!(result, Err(Ok(InheritanceError::ClaimNotAllowedYet)));
+
+    // 98,000 after the 2% fee, split into 32,666 + 32,666 + 32,668
+    let status = client.get_installment_status(&plan_id, &0u32);
+    assert_eq!(status.entitlement, 98_000);
+    assert_eq!(status.paid_amount, 32_666);
+    assert_eq!(status.next_amount, 32_666);
+    assert!(
+        !client
+            .get_plan_details(&plan_id)
+            .unwrap()
+            .beneficiaries
+            .get(0)
+            .unwrap()
+            .is_claimed
+    );
+
+    // Schedule is locked once a tranche has been paid
+    let result = client.try_set_payout_schedule(&owner, &plan_id, &6u32);
+    assert_eq!(result, Err(Ok(PayoutError::ScheduleLocked)));
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * MONTH_SECS);
+    claim_installment(&env, &client, plan_id, &beneficiary).unwrap();
+    claim_installment(&env, &client, plan_id, &beneficiary).unwrap();
+
+    let status = client.get_installment_status(&plan_id, &0u32);
+    assert_eq!(status.paid_installments, 3);
+    assert_eq!(status.paid_amount, 98_000);
+    assert_eq!(status.next_due_at, 0);
+
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert_eq!(plan.total_amount, 0);
+    assert!(plan.beneficiaries.get(0).unwrap().is_claimed);
+
+    let result = claim_installment(&env, &client, plan_id, &beneficiary);
+    assert_eq!(result, Err(Ok(InheritanceError::AlreadyClaimed)));
+}
+
+#[test]
+fn test_claim_next_installment_respects_vesting() {
+    let env = Env::default();
+    let (client, _admin, owner, beneficiary, plan_id) =
+        setup_periodic_plan(&env, DistributionMethod::Monthly);
+    client.set_payout_schedule(&owner, &plan_id, &2u32);
+    let start = env.ledger().timestamp();
+    client.set_beneficiary_vesting(
+        &owner,
+        &plan_id,
+        &0u32,
+        &VestingRelease::Staged(vec![
+            &env,
+            VestingStage {
+                unlock_at: start + MONTH_SECS,
+                cumulative_bp: 5000,
+            },
+            VestingStage {
+                unlock_at: start + 3 * MONTH_SECS,
+                cumulative_bp: 10000,
+            },
+        ]),
+    );
+
+    // The first tranche (49,000) is covered by the first stage
+    env.ledger()
+        .with_mut(|li| li.timestamp = start + MONTH_SECS);
+    claim_installment(&env, &client, plan_id, &beneficiary).unwrap();
+    assert_eq!(
+        client.get_beneficiary_vesting(&plan_id, &0u32).released,
+        49_000
+    );
+
+    // The second is due but not yet vested
+    env.ledger()
+        .with_mut(|li| li.timestamp = start + 2 * MONTH_SECS);
+    let result = claim_installment(&env, &client, plan_id, &beneficiary);
+    assert_eq!(result, Err(Ok(InheritanceError::VestingScheduleActive)));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = start + 3 * MONTH_SECS);
+    claim_installment(&env, &client, plan_id, &beneficiary).unwrap();
+    let status = client.get_installment_status(&plan_id, &0u32);
+    assert_eq!(status.paid_amount, 98_000);
+    assert!(
+        client
+            .get_plan_details(&plan_id)
+            .unwrap()
+            .beneficiaries
+            .get(0)
+            .unwrap()
+            .is_claimed
+    );
+}
+
+#[test]
+fn test_set_payout_schedule_validation() {
+    let env = Env::default();
+    let (client, _admin, owner, _beneficiary, plan_id) =
+        setup_periodic_plan(&env, DistributionMethod::Yearly);
+
+    let stranger = create_test_address(&env, 301);
+    let result = client.try_set_payout_schedule(&stranger, &plan_id, &5u32);
+    assert_eq!(result, Err(Ok(PayoutError::Unauthorized)));
+
+    let result = client.try_set_payout_schedule(&owner, &plan_id, &0u32);
+    assert_eq!(result, Err(Ok(PayoutError::InvalidInstallmentCount)));
+
+    let result = client.try_set_payout_schedule(&owner, &plan_id, &121u32);
+    assert_eq!(result, Err(Ok(PayoutError::InvalidInstallmentCount)));
+
+    client.set_payout_schedule(&owner, &plan_id, &5u32);
+    assert_eq!(
+        client
+            .get_payout_schedule(&plan_id)
+            .unwrap()
+            .installment_count,
+        5
+    );
+}
+
+#[test]
+fn test_claim_next_installment_blocked_while_frozen() {
+    let env = Env::default();
+    let (client, admin, _owner, beneficiary, plan_id) =
+        setup_periodic_plan(&env, DistributionMethod::Monthly);
+
+    env.ledger().with_mut(|li| li.timestamp += MONTH_SECS);
+    client.freeze_plan(&admin, &plan_id, &String::from_str(&env, "Review"), &0u64);
+    let result = claim_installment(&env, &client, plan_id, &beneficiary);
+    assert_eq!(result, Err(Ok(InheritanceError::PlanNotActive)));
+
+    client.unfreeze_plan(&admin, &plan_id);
+    claim_installment(&env, &client, plan_id, &beneficiary).unwrap();
+}
+
+// ─── Storage TTL Tests ───────────────────────────
+
+fn plan_ttl(env: &Env, client: &InheritanceContractClient, plan_id: u64) -> u32 {
+    env.as_contract(&client.address, || {
+        env.storage().persistent().get_ttl(&DataKey::Plan(plan_id))
+    })
+}
+
+fn advance_days(env: &Env, days: u32) {
+    env.ledger()
+        .with_mut(|li| li.sequence_number += days * access_control::DAY_IN_LEDGERS);
+}
+
+#[test]
+fn test_plan_ttl_extended_on_create() {
+    let env = Env::default();
+    let (client, token, _admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token,
+        "TTL Plan",
+        "Plan kept alive",
+        1_000_000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+
+    assert_eq!(
+        plan_ttl(&env, &client, plan_id),
+        access_control::PERSISTENT_BUMP_AMOUNT
+    );
+    let instance_ttl = env.as_contract(&client.address, || env.storage().instance().get_ttl());
+    assert_eq!(instance_ttl, access_control::INSTANCE_BUMP_AMOUNT);
+}
+
+#[test]
+fn test_bump_plan_restores_ttl_below_threshold() {
+    let env = Env::default();
+    let (client, token, _admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token,
+        "TTL Plan",
+        "Plan kept alive",
+        1_000_000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+
+    // The keeper bump renews the plan and every per-plan entry.
+    advance_days(&env, 20);
+    client.get_plan_details(&plan_id);
+    advance_days(&env, 15);
+    client.bump_plan(&plan_id);
+    assert_eq!(
+        plan_ttl(&env, &client, plan_id),
+        access_control::PERSISTENT_BUMP_AMOUNT
+    );
+    let owner_index_ttl = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&crate::plan_index::PlanIndexKey::Position(
+                PlanIndex::Owner(owner.clone()),
+                plan_id,
+            ))
+    });
+    assert_eq!(owner_index_ttl, access_control::PERSISTENT_BUMP_AMOUNT);
+}
+
+#[test]
+fn test_plan_ttl_extended_on_read_and_write() {
+    let env = Env::default();
+    let (client, token, _admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token,
+        "TTL Plan",
+        "Plan kept alive",
+        1_000_000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+
+    // Above the threshold a read leaves the TTL alone.
+    advance_days(&env, 20);
+    client.get_plan_details(&plan_id);
+    assert_eq!(
+        plan_ttl(&env, &client, plan_id),
+        access_control::PERSISTENT_BUMP_AMOUNT - 20 * access_control::DAY_IN_LEDGERS
+    );
+
+    // Once below it, reading the plan renews the record.
+    advance_days(&env, 15);
+    client.get_plan_details(&plan_id);
+    assert_eq!(
+        plan_ttl(&env, &client, plan_id),
+        access_control::PERSISTENT_BUMP_AMOUNT
+    );
+
+    // Calls that update the plan keep it alive as well.
+    advance_days(&env, 20);
+    client.get_plan_details(&plan_id);
+    advance_days(&env, 15);
+    client.deactivate_inheritance_plan(&owner, &plan_id);
+    assert_eq!(
+        plan_ttl(&env, &client, plan_id),
+        access_control::PERSISTENT_BUMP_AMOUNT
+    );
+}
+
+#[test]
+fn test_bump_plan_not_found() {
+    let env = Env::default();
+    let (client, _token, _admin, _owner) = setup_with_token_and_admin(&env);
+    let result = client.try_bump_plan(&999u64);
+    assert_eq!(result, Err(Ok(InheritanceError::PlanNotFound)));
+}
+
+// ─── Plan Enumeration Tests ──────────────────────
+
+fn create_plans(
+    env: &Env,
+    client: &InheritanceContractClient,
+    token: &Address,
+    owner: &Address,
+    count: u32,
+) -> Vec<u64> {
+    let mut ids = Vec::new(env);
+    for i in 0..count {
+        ids.push_back(client.create_inheritance_plan(&plan_params(
+            env,
+            owner,
+            token,
+            "Paged Plan",
+            "Plan for pagination",
+            10_000u64,
+            DistributionMethod::LumpSum,
+            &one_beneficiary(env, "B", "paged@example.com", 100_000 + i),
+        )));
+    }
+    ids
+}
+
+#[test]
+fn test_get_all_plans_pages_in_creation_order() {
+    let env = Env::default();
+    let (client, token, admin, owner) = setup_with_token_and_admin(&env);
+    let ids = create_plans(&env, &client, &token, &owner, 5);
+
+    let first = client.get_all_plans(&admin, &None, &2);
+    assert_eq!(first.plans.len(), 2);
+    assert_eq!(first.plans.get(0).unwrap().plan_id, ids.get(0).unwrap());
+    assert_eq!(first.plans.get(1).unwrap().plan_id, ids.get(1).unwrap());
+
+    let second = client.get_all_plans(&admin, &first.next_cursor, &2);
+    assert_eq!(second.plans.get(0).unwrap().plan_id, ids.get(2).unwrap());
+
+    let last = client.get_all_plans(&admin, &second.next_cursor, &2);
+    assert_eq!(last.plans.len(), 1);
+    assert_eq!(last.plans.get(0).unwrap().plan_id, ids.get(4).unwrap());
+    assert_eq!(last.next_cursor, None);
+
+    // Limit is clamped to the page maximum.
+    let capped = client.get_all_plans(&admin, &None, &1_000);
+    assert_eq!(capped.plans.len(), 5);
+
+    // A cursor at the end of the id space yields an empty page.
+    let past_end = client.get_all_plans(&admin, &Some(u64::MAX), &2);
+    assert_eq!(past_end.plans.len(), 0);
+    assert_eq!(past_end.next_cursor, None);
+    let past_end = client.get_all_pending_plans(&admin, &Some(u64::MAX), &2);
+    assert_eq!(past_end.plans.len(), 0);
+}
+
+#[test]
+fn test_status_indexes_track_deactivation_and_claims() {
+    let env = Env::default();
+    let (client, token, admin, owner) = setup_with_token_and_admin(&env);
+    let ids = create_plans(&env, &client, &token, &owner, 3);
+
+    client.deactivate_inheritance_plan(&owner, &ids.get(1).unwrap());
+
+    assert_eq!(client.get_plan_count(&PlanIndex::All), 3);
+    assert_eq!(client.get_plan_count(&PlanIndex::Pending), 2);
+    assert_eq!(client.get_plan_count(&PlanIndex::Deactivated), 1);
+    assert_eq!(client.get_plan_count(&PlanIndex::Owner(owner.clone())), 3);
+
+    // The removed slot is skipped and pages stay in order.
+    let pending = client.get_all_pending_plans(&admin, &None, &1);
+    assert_eq!(pending.plans.get(0).unwrap().plan_id, ids.get(0).unwrap());
+    let pending = client.get_all_pending_plans(&admin, &pending.next_cursor, &1);
+    assert_eq!(pending.plans.get(0).unwrap().plan_id, ids.get(2).unwrap());
+    assert_eq!(pending.next_cursor, None);
+
+    let deactivated = client.get_all_deactivated_plans(&admin, &None, &10);
+    assert_eq!(deactivated.plans.len(), 1);
+    assert_eq!(
+        deactivated.plans.get(0).unwrap().plan_id,
+        ids.get(1).unwrap()
+    );
+}
+
+#[test]
+fn test_owner_index_follows_ownership_transfer() {
+    let env = Env::default();
+    let (client, token, _admin, owner) = setup_with_token_and_admin(&env);
+    let ids = create_plans(&env, &client, &token, &owner, 2);
+    let new_owner = create_test_address(&env, 77);
+
+    client.transfer_plan_ownership(&owner, &new_owner, &ids.get(0).unwrap());
+    client.accept_plan_ownership(&new_owner, &ids.get(0).unwrap());
+
+    assert_eq!(client.get_plan_count(&PlanIndex::Owner(owner.clone())), 1);
+    assert_eq!(
+        client.get_plan_count(&PlanIndex::Owner(new_owner.clone())),
+        1
+    );
+
+    let page = client.get_owner_plans(&new_owner, &None, &10);
+    assert_eq!(page.plans.len(), 1);
+    assert_eq!(page.plans.get(0).unwrap().plan_id, ids.get(0).unwrap());
+    assert_eq!(client.get_user_plans(&owner).len(), 1);
+}
+
+#[test]
+fn test_claimed_index_follows_ownership_transfer() {
+    let env = Env::default();
+    let (client, _admin, beneficiary, _arbitrator, plan_id) = setup_plan_for_disputes(&env);
+    let owner = client.get_plan_details(&plan_id).unwrap().owner;
+    let new_owner = create_test_address(&env, 77);
+    client.claim_as_beneficiary(&plan_id, &0u32, &beneficiary, &None);
+    assert_eq!(
+        client.get_plan_count(&PlanIndex::OwnerClaimed(owner.clone())),
+        1
+    );
+
+    client.transfer_plan_ownership(&owner, &new_owner, &plan_id);
+    client.accept_plan_ownership(&new_owner, &plan_id);
+
+    assert_eq!(
+        client.get_plan_count(&PlanIndex::OwnerClaimed(owner.clone())),
+        0
+    );
+    assert_eq!(
+        client.get_plan_count(&PlanIndex::OwnerClaimed(new_owner.clone())),
+        1
+    );
+    assert_eq!(client.get_user_claimed_plans(&new_owner).len(), 1);
+    assert!(client.get_user_claimed_plans(&owner).is_empty());
+    client.get_claimed_plan(&new_owner, &plan_id);
+}
+
+#[test]
+fn test_backfill_plan_indexes_is_idempotent() {
+    let env = Env::default();
+    let (client, token, admin, owner) = setup_with_token_and_admin(&env);
+    create_plans(&env, &client, &token, &owner, 3);
+
+    let cursor = client.backfill_plan_indexes(&admin, &None, &2);
+    assert_eq!(cursor, Some(2));
+    assert_eq!(client.backfill_plan_indexes(&admin, &cursor, &2), None);
+
+    assert_eq!(client.get_plan_count(&PlanIndex::Pending), 3);
+    assert_eq!(client.get_plan_count(&PlanIndex::Owner(owner)), 3);
+
+    let not_admin = create_test_address(&env, 999);
+    assert!(client
+        .try_backfill_plan_indexes(&not_admin, &None, &2)
+        .is_err());
+}
+
+// ─── Beneficiary Binding Tests ───────────────────
+
+fn bound_plan(env: &Env) -> (InheritanceContractClient<'_>, Address, Address, u64) {
+    let (client, token, admin, owner) = setup_with_token_and_admin(env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        env,
+        &owner,
+        &token,
+        "Will",
+        "Inheritance Plan",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(env),
+    ));
+    (client, admin, owner, plan_id)
+}
+
+fn kyc_approved(env: &Env, client: &InheritanceContractClient, admin: &Address) -> Address {
+    let user = create_test_address(env, 0);
+    client.submit_kyc(&user);
+    client.approve_kyc(admin, &user);
+    user
+}
+
+#[test]
+fn test_address_bound_beneficiary_claims_with_auth() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = bound_plan(&env);
+    let beneficiary = kyc_approved(&env, &client, &admin);
+
+    client.bind_beneficiary(
+        &owner,
+        &plan_id,
+        &0,
+        &BeneficiaryBinding::Address(beneficiary.clone()),
+    );
+    assert_eq!(
+        client.get_beneficiary_binding(&plan_id, &0),
+        Some(BeneficiaryBinding::Address(beneficiary.clone()))
+    );
+
+    // The claim code no longer proves identity once the beneficiary is bound.
+    let impostor = kyc_approved(&env, &client, &admin);
+    let by_code = client.try_claim_inheritance_plan(
+        &plan_id,
+        &impostor,
+        &String::from_str(&env, "alice@example.com"),
+        &111111u32,
+    );
+    assert_eq!(by_code, Err(Ok(InheritanceError::Unauthorized)));
+
+    assert_eq!(
+        client.try_claim_as_beneficiary(&plan_id, &0, &impostor, &None),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+
+    client.claim_as_beneficiary(&plan_id, &0, &beneficiary, &None);
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert!(plan.beneficiaries.get(0).unwrap().is_claimed);
+    assert_eq!(
+        client.try_claim_as_beneficiary(&plan_id, &0, &beneficiary, &None),
+        Err(Ok(InheritanceError::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_installment_claim_code_requires_bound_claimer() {
+    let env = Env::default();
+    let (client, admin, owner, beneficiary, plan_id) =
+        setup_periodic_plan(&env, DistributionMethod::Monthly);
+    client.set_payout_schedule(&owner, &plan_id, &3u32);
+    client.bind_beneficiary(
+        &owner,
+        &plan_id,
+        &0,
+        &BeneficiaryBinding::Address(beneficiary.clone()),
+    );
+    env.ledger().with_mut(|li| li.timestamp += MONTH_SECS);
+
+    let impostor = kyc_approved(&env, &client, &admin);
+    assert_eq!(
+        claim_installment(&env, &client, plan_id, &impostor),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+
+    claim_installment(&env, &client, plan_id, &beneficiary).unwrap();
+    assert_eq!(
+        client
+            .get_installment_status(&plan_id, &0)
+            .paid_installments,
+        1
+    );
+}
+
+#[test]
+fn test_migrate_turns_off_legacy_claim_codes() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = bound_plan(&env);
+    let beneficiary = kyc_approved(&env, &client, &admin);
+    assert!(client.legacy_claim_codes_enabled());
+
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&DataKey::Version, &3u32);
+    });
+    client.migrate(&admin);
+    assert!(!client.legacy_claim_codes_enabled());
+
+    let by_code = client.try_claim_inheritance_plan(
+        &plan_id,
+        &beneficiary,
+        &String::from_str(&env, "alice@example.com"),
+        &111111u32,
+    );
+    assert_eq!(by_code, Err(Ok(InheritanceError::Unauthorized)));
+
+    // Only the admin can turn the path back on.
+    assert!(client
+        .try_set_legacy_claim_codes_enabled(&owner, &true)
+        .is_err());
+
+    // A bound beneficiary can still claim.
+    client.bind_beneficiary(
+        &owner,
+        &plan_id,
+        &0,
+        &BeneficiaryBinding::Address(beneficiary.clone()),
+    );
+    client.claim_as_beneficiary(&plan_id, &0, &beneficiary, &None);
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert!(plan.beneficiaries.get(0).unwrap().is_claimed);
+}
+
+#[test]
+fn test_admin_toggles_legacy_claim_codes() {
+    let env = Env::default();
+    let (client, admin, _owner, plan_id) = bound_plan(&env);
+    let beneficiary = kyc_approved(&env, &client, &admin);
+    let claim = |client: &InheritanceContractClient| {
+        client.try_claim_inheritance_plan(
+            &plan_id,
+            &beneficiary,
+            &String::from_str(&env, "alice@example.com"),
+            &111111u32,
+        )
+    };
+
+    client.set_legacy_claim_codes_enabled(&admin, &false);
+    assert_eq!(claim(&client), Err(Ok(InheritanceError::Unauthorized)));
+
+    client.set_legacy_claim_codes_enabled(&admin, &true);
+    assert!(claim(&client).is_ok());
+}
+
+#[test]
+fn test_unbound_beneficiary_cannot_claim_as_beneficiary() {
+    let env = Env::default();
+    let (client, admin, _owner, plan_id) = bound_plan(&env);
+    let claimer = kyc_approved(&env, &client, &admin);
+
+    assert_eq!(
+        client.try_claim_as_beneficiary(&plan_id, &0, &claimer, &None),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_bind_beneficiary_requires_owner() {
+    let env = Env::default();
+    let (client, admin, _owner, plan_id) = bound_plan(&env);
+    let other = kyc_approved(&env, &client, &admin);
+
+    assert_eq!(
+        client.try_bind_beneficiary(
+            &other,
+            &plan_id,
+            &0,
+            &BeneficiaryBinding::Address(other.clone())
+        ),
+        Err(Ok(ClaimAuthError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_ed25519_bound_beneficiary_claims_with_signature() {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = bound_plan(&env);
+    let claimer = kyc_approved(&env, &client, &admin);
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+
+    client.bind_beneficiary(
+        &owner,
+        &plan_id,
+        &0,
+        &BeneficiaryBinding::Ed25519(BytesN::from_array(&env, &key.verifying_key().to_bytes())),
+    );
+
+    assert_eq!(
+        client.try_claim_as_beneficiary(&plan_id, &0, &claimer, &None),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+
+    let hashed_email =
+        InheritanceContract::hash_string(&env, String::from_str(&env, "alice@example.com"));
+    let message = env.as_contract(&client.address, || {
+        claim_auth::claim_message(&env, plan_id, &hashed_email, &claimer)
+    });
+    let mut raw = [0u8; 256];
+    let len = message.len() as usize;
+    message.copy_into_slice(&mut raw[..len]);
+    let signature = BytesN::from_array(&env, &key.sign(&raw[..len]).to_bytes());
+
+    // A signature only authorizes the address it names.
+    let other = kyc_approved(&env, &client, &admin);
+    assert!(client
+        .try_claim_as_beneficiary(&plan_id, &0, &other, &Some(signature.clone()))
+        .is_err());
+
+    client.claim_as_beneficiary(&plan_id, &0, &claimer, &Some(signature));
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert!(plan.beneficiaries.get(0).unwrap().is_claimed);
+}
+
+#[test]
+fn test_commitment_binds_first_revealing_kyc_address() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = bound_plan(&env);
+    let claimer = kyc_approved(&env, &client, &admin);
+    let salt = BytesN::from_array(&env, &[42u8; 32]);
+    let hashed_email =
+        InheritanceContract::hash_string(&env, String::from_str(&env, "alice@example.com"));
+
+    client.bind_beneficiary(
+        &owner,
+        &plan_id,
+        &0,
+        &BeneficiaryBinding::Commitment(claim_auth::commitment(
+            &env,
+            plan_id,
+            &hashed_email,
+            &salt,
+        )),
+    );
+
+    let sealed = claim_auth::seal(&env, &salt, &claimer);
+    client.seal_beneficiary_claim(&plan_id, &0, &claimer, &sealed);
+    assert_eq!(
+        client.try_reveal_beneficiary_claim(&plan_id, &0, &claimer, &salt),
+        Err(Ok(ClaimAuthError::SealNotMatured))
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    let wrong_salt = BytesN::from_array(&env, &[1u8; 32]);
+    assert_eq!(
+        client.try_reveal_beneficiary_claim(&plan_id, &0, &claimer, &wrong_salt),
+        Err(Ok(ClaimAuthError::CommitmentMismatch))
+    );
+
+    // Someone who learns the salt but sealed nothing cannot take the binding.
+    let front_runner = kyc_approved(&env, &client, &admin);
+    assert_eq!(
+        client.try_reveal_beneficiary_claim(&plan_id, &0, &front_runner, &salt),
+        Err(Ok(ClaimAuthError::SealNotFound))
+    );
+
+    client.reveal_beneficiary_claim(&plan_id, &0, &claimer, &salt);
+    assert_eq!(
+        client.get_beneficiary_binding(&plan_id, &0),
+        Some(BeneficiaryBinding::Address(claimer.clone()))
+    );
+    assert_eq!(
+        client.try_reveal_beneficiary_claim(&plan_id, &0, &claimer, &salt),
+        Err(Ok(ClaimAuthError::NotCommitted))
+    );
+
+    client.claim_as_beneficiary(&plan_id, &0, &claimer, &None);
+}
+
+#[test]
+fn test_reveal_requires_kyc() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id) = bound_plan(&env);
+    let claimer = create_test_address(&env, 0);
+    let salt = BytesN::from_array(&env, &[42u8; 32]);
+    let hashed_email =
+        InheritanceContract::hash_string(&env, String::from_str(&env, "alice@example.com"));
+
+    client.bind_beneficiary(
+        &owner,
+        &plan_id,
+        &0,
+        &BeneficiaryBinding::Commitment(claim_auth::commitment(
+            &env,
+            plan_id,
+            &hashed_email,
+            &salt,
+        )),
+    );
+    client.seal_beneficiary_claim(
+        &plan_id,
+        &0,
+        &claimer,
+        &claim_auth::seal(&env, &salt, &claimer),
+    );
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+
+    assert_eq!(
+        client.try_reveal_beneficiary_claim(&plan_id, &0, &claimer, &salt),
+        Err(Ok(ClaimAuthError::KycNotApproved))
+    );
+}
+
+#[test]
+fn test_remove_beneficiary_clears_binding() {
+    let env = Env::default();
+    let (client, token, admin, owner) = setup_with_token_and_admin(&env);
+    let mut beneficiaries = default_beneficiaries(&env);
+    beneficiaries.set(
+        0,
+        (
+            String::from_str(&env, "Alice"),
+            String::from_str(&env, "alice@example.com"),
+            111111u32,
+            create_test_bytes(&env, "1111111111111111"),
+            5000u32,
+            1u32,
+        ),
+    );
+    beneficiaries.push_back((
+        String::from_str(&env, "Bob"),
+        String::from_str(&env, "bob@example.com"),
+        222222u32,
+        create_test_bytes(&env, "2222222222222222"),
+        5000u32,
+        2u32,
+    ));
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token,
+        "Will",
+        "Inheritance Plan",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &beneficiaries,
+    ));
+    let beneficiary = kyc_approved(&env, &client, &admin);
+
+    client.bind_beneficiary(
+        &owner,
+        &plan_id,
+        &0,
+        &BeneficiaryBinding::Address(beneficiary),
+    );
+    client.remove_beneficiary(&owner, &plan_id, &0);
+
+    // Bob moved into slot 0 and carries no binding.
+    assert_eq!(client.get_beneficiary_binding(&plan_id, &0), None);
+}
+
+// ─── Payout Details Tests ────────────────────────
+
+fn sealed_details(env: &Env, key_id: u32) -> Bytes {
+    let mut details = Bytes::from_array(env, &[payout_details::ENVELOPE_VERSION]);
+    details.extend_from_array(&key_id.to_be_bytes());
+    details.extend_from_array(&[9u8; 29]); // nonce, tag and one byte of data
+    details
+}
+
+fn beneficiary_with_details(
+    env: &Env,
+    details: Bytes,
+) -> Vec<(String, String, u32, Bytes, u32, u32)> {
+    vec![
+        env,
+        (
+            String::from_str(env, "Alice"),
+            String::from_str(env, "alice@example.com"),
+            111111u32,
+            details,
+            10000u32,
+            1u32,
+        ),
+    ]
+}
+
+#[test]
+fn test_settlement_key_requires_sealed_details() {
+    let env = Env::default();
+    let (client, token, admin, owner) = setup_with_token_and_admin(&env);
+    client.set_settlement_key(&admin, &1);
+    assert_eq!(client.get_settlement_key_id(), Some(1));
+
+    for details in [
+        create_test_bytes(&env, "1111111111111111"),
+        sealed_details(&env, 2),
+    ] {
+        let params = plan_params(
+            &env,
+            &owner,
+            &token,
+            "Will",
+            "Inheritance Plan",
+            1000u64,
+            DistributionMethod::LumpSum,
+            &beneficiary_with_details(&env, details),
+        );
+        assert_eq!(
+            client.try_create_inheritance_plan(&params),
+            Err(Ok(InheritanceError::InvalidBeneficiaryData))
+        );
+    }
+
+    client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token,
+        "Will",
+        "Inheritance Plan",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &beneficiary_with_details(&env, sealed_details(&env, 1)),
+    ));
+}
+
+#[test]
+fn test_settlement_key_only_moves_forward() {
+    let env = Env::default();
+    let (client, _token, admin, owner) = setup_with_token_and_admin(&env);
+
+    assert_eq!(
+        client.try_set_settlement_key(&owner, &1),
+        Err(Ok(PayoutDetailsError::NotAdmin))
+    );
+    client.set_settlement_key(&admin, &2);
+    assert_eq!(
+        client.try_set_settlement_key(&admin, &2),
+        Err(Ok(PayoutDetailsError::StaleSettlementKey))
+    );
+    assert_eq!(
+        client.try_set_settlement_key(&admin, &1),
+        Err(Ok(PayoutDetailsError::StaleSettlementKey))
+    );
+}
+
+#[test]
+fn test_reseal_migrates_plaintext_plans() {
+    let env = Env::default();
+    let (client, token, admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token,
+        "Will",
+        "Inheritance Plan",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+
+    assert_eq!(
+        client.try_reseal_payout_details(&admin, &plan_id, &vec![&env, sealed_details(&env, 1)]),
+        Err(Ok(PayoutDetailsError::SettlementKeyNotSet))
+    );
+    client.set_settlement_key(&admin, &1);
+
+    let page = client.get_unsealed_plans(&admin, &None, &10);
+    assert_eq!(page.plans.len(), 1);
+    assert_eq!(page.plans.get(0).unwrap().plan_id, plan_id);
+
+    assert_eq!(
+        client.try_reseal_payout_details(&admin, &plan_id, &Vec::new(&env)),
+        Err(Ok(PayoutDetailsError::LengthMismatch))
+    );
+    assert_eq!(
+        client.try_reseal_payout_details(
+            &admin,
+            &plan_id,
+            &vec![&env, create_test_bytes(&env, "2222222222222222")]
+        ),
+        Err(Ok(PayoutDetailsError::InvalidEnvelope))
+    );
+
+    client.reseal_payout_details(&admin, &plan_id, &vec![&env, sealed_details(&env, 1)]);
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert_eq!(
+        plan.beneficiaries.get(0).unwrap().bank_account,
+        sealed_details(&env, 1)
+    );
+    assert_eq!(client.get_unsealed_plans(&admin, &None, &10).plans.len(), 0);
+    assert_eq!(
+        client.try_reseal_payout_details(&admin, &plan_id, &vec![&env, sealed_details(&env, 1)]),
+        Err(Ok(PayoutDetailsError::AlreadySealed))
+    );
+
+    // Rotating the key puts the plan back in the reseal queue.
+    client.set_settlement_key(&admin, &2);
+    assert_eq!(client.get_unsealed_plans(&admin, &None, &10).plans.len(), 1);
+    client.reseal_payout_details(&admin, &plan_id, &vec![&env, sealed_details(&env, 2)]);
+}
+
+// ─── Multi-Asset Tests ───────────────────────────
+
+fn two_beneficiaries(env: &Env) -> Vec<(String, String, u32, Bytes, u32, u32)> {
+    vec![
+        env,
+        (
+            String::from_str(env, "Alice"),
+            String::from_str(env, "alice@example.com"),
+            111111u32,
+            create_test_bytes(env, "1111111111111111"),
+            6000u32,
+            1u32,
+        ),
+        (
+            String::from_str(env, "Bob"),
+            String::from_str(env, "bob@example.com"),
+            222222u32,
+            create_test_bytes(env, "2222222222222222"),
+            4000u32,
+            2u32,
+        ),
+    ]
+}
+
+/// Whitelists the setup token as USDC plus a second token as XLM, funds the
+/// owner with XLM and creates a two-beneficiary plan.
+fn setup_multi_asset_plan(
+    env: &Env,
+) -> (
+    InheritanceContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    Address,
+    u64,
+) {
+    let (client, usdc, admin, owner) = setup_with_token_and_admin(env);
+    let xlm = env.register_contract(None, MockToken);
+    TestTokenHelper::new(env, &xlm).mint(&owner, &10_000_000i128);
+    client.set_whitelisted_asset(&admin, &usdc, &Some(Symbol::new(env, "USDC")));
+    client.set_whitelisted_asset(&admin, &xlm, &Some(Symbol::new(env, "XLM")));
+
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        env,
+        &owner,
+        &usdc,
+        "Will",
+        "Inheritance Plan",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &two_beneficiaries(env),
+    ));
+    (client, admin, usdc, xlm, owner, plan_id)
+}
+
+fn claim_as(
+    env: &Env,
+    client: &InheritanceContractClient<'_>,
+    admin: &Address,
+    plan_id: u64,
+    email: &str,
+    claim_code: u32,
+) -> Address {
+    let claimer = Address::generate(env);
+    client.submit_kyc(&claimer);
+    client.approve_kyc(admin, &claimer);
+    client.claim_inheritance_plan(
+        &plan_id,
+        &claimer,
+        &String::from_str(env, email),
+        &claim_code,
+    );
+    claimer
+}
+
+#[test]
+fn test_whitelist_gates_plan_assets() {
+    let env = Env::default();
+    let (client, admin, usdc, xlm, owner, plan_id) = setup_multi_asset_plan(&env);
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert_eq!(plan.asset_type, Symbol::new(&env, "USDC"));
+
+    // Unlisted tokens can neither fund a plan nor be deposited once a
+    // whitelist exists.
+    let other = env.register_contract(None, MockToken);
+    TestTokenHelper::new(&env, &other).mint(&owner, &10_000i128);
+    assert_eq!(
+        client.try_deposit(&owner, &other, &plan_id, &100u64),
+        Err(Ok(InheritanceError::InvalidAssetType))
+    );
+    assert_eq!(
+        client.try_create_inheritance_plan(&plan_params(
+            &env,
+            &owner,
+            &other,
+            "Will",
+            "Inheritance Plan",
+            1000u64,
+            DistributionMethod::LumpSum,
+            &default_beneficiaries(&env),
+        )),
+        Err(Ok(InheritanceError::InvalidAssetType))
+    );
+
+    // A plan funded in XLM is labelled with its code.
+    let xlm_plan = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &xlm,
+        "Will",
+        "Inheritance Plan",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+    assert_eq!(
+        client.get_plan_details(&xlm_plan).unwrap().asset_type,
+        Symbol::new(&env, "XLM")
+    );
+
+    assert_eq!(
+        client.try_set_whitelisted_asset(&owner, &other, &Some(Symbol::new(&env, "EURC"))),
+        Err(Ok(AssetError::NotAdmin))
+    );
+    assert_eq!(
+        client.try_set_whitelisted_asset(&admin, &other, &Some(Symbol::new(&env, "XLM"))),
+        Err(Ok(AssetError::AssetCodeInUse))
+    );
+    assert_eq!(client.get_whitelisted_assets().len(), 2);
+    assert!(client.get_whitelisted_assets().contains_key(usdc));
+}
+
+#[test]
+fn test_deposit_and_withdraw_per_asset() {
+    let env = Env::default();
+    let (client, admin, usdc, xlm, owner, plan_id) = setup_multi_asset_plan(&env);
+    let xlm_helper = TestTokenHelper::new(&env, &xlm);
+
+    client.deposit(&owner, &xlm, &plan_id, &5000u64);
+    client.deposit(&owner, &usdc, &plan_id, &20u64);
+    assert_eq!(xlm_helper.balance(&client.address), 5000);
+
+    let breakdown = client.get_claimable_amount(&plan_id);
+    assert_eq!(breakdown.len(), 2);
+    assert_eq!(
+        breakdown.get(0).unwrap(),
+        AssetAmount {
+            asset_code: Symbol::new(&env, "USDC"),
+            token: Some(usdc.clone()),
+            amount: 1000,
+        }
+    );
+    assert_eq!(
+        breakdown.get(1).unwrap(),
+        AssetAmount {
+            asset_code: Symbol::new(&env, "XLM"),
+            token: Some(xlm.clone()),
+            amount: 5000,
+        }
+    );
+    assert_eq!(
+        client.try_withdraw(&owner, &xlm, &plan_id, &5001u64),
+        Err(Ok(InheritanceError::InsufficientLiquidity))
+    );
+    client.withdraw(&owner, &xlm, &plan_id, &1000u64);
+    assert_eq!(
+        client.get_claimable_amount(&plan_id).get(1).unwrap().amount,
+        4000
+    );
+    assert_eq!(
+        client.get_plan_details(&plan_id).unwrap().total_amount,
+        1000
+    );
+
+    // Delisting stops new deposits but the balance can still leave.
+    client.set_whitelisted_asset(&admin, &xlm, &None);
+    assert_eq!(
+        client.try_deposit(&owner, &xlm, &plan_id, &10u64),
+        Err(Ok(InheritanceError::InvalidAssetType))
+    );
+    client.withdraw(&owner, &xlm, &plan_id, &4000u64);
+    assert_eq!(xlm_helper.balance(&client.address), 0);
+}
+
+#[test]
+fn test_primary_token_survives_whitelisting_other_assets() {
+    let env = Env::default();
+    let (client, usdc, admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &usdc,
+        "Will",
+        "Inheritance Plan",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+    let legacy_plan = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &usdc,
+        "Will",
+        "Inheritance Plan",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+    // Plans created before primary tokens were recorded have no entry.
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .remove(&crate::assets::AssetKey::PrimaryToken(legacy_plan));
+    });
+
+    // Whitelisting another asset must not strand the USDC plans.
+    let xlm = env.register_contract(None, MockToken);
+    client.set_whitelisted_asset(&admin, &xlm, &Some(Symbol::new(&env, "XLM")));
+
+    client.deposit(&owner, &usdc, &plan_id, &100u64);
+    client.withdraw(&owner, &usdc, &plan_id, &50u64);
+    assert_eq!(
+        client.get_claimable_amount(&plan_id).get(0).unwrap(),
+        AssetAmount {
+            asset_code: Symbol::new(&env, "USDC"),
+            token: Some(usdc.clone()),
+            amount: 1030,
+        }
+    );
+    assert_eq!(
+        client.try_withdraw(&owner, &usdc, &legacy_plan, &50u64),
+        Err(Ok(InheritanceError::InvalidAssetType))
+    );
+
+    assert_eq!(
+        client.try_backfill_primary_tokens(&owner, &usdc, &None, &10u32),
+        Err(Ok(AssetError::NotAdmin))
+    );
+    assert_eq!(
+        client.backfill_primary_tokens(&admin, &usdc, &None, &10u32),
+        None
+    );
+    client.withdraw(&owner, &usdc, &legacy_plan, &50u64);
+    client.deposit(&owner, &usdc, &legacy_plan, &20u64);
+    assert_eq!(
+        client.get_plan_details(&legacy_plan).unwrap().total_amount,
+        950
+    );
+    // Another token cannot feed the primary balance.
+    assert_eq!(
+        client.try_withdraw(&owner, &xlm, &legacy_plan, &50u64),
+        Err(Ok(InheritanceError::InsufficientLiquidity))
+    );
+}
+
+#[test]
+fn test_claim_pays_out_assets_pro_rata() {
+    let env = Env::default();
+    let (client, admin, _usdc, xlm, owner, plan_id) = setup_multi_asset_plan(&env);
+    client.deposit(&owner, &xlm, &plan_id, &5000u64);
+
+    let shares = client.get_beneficiary_assets(&plan_id, &1);
+    assert_eq!(shares.get(0).unwrap().amount, 2000);
+
+    let alice = claim_as(&env, &client, &admin, plan_id, "alice@example.com", 111111);
+    let bob = claim_as(&env, &client, &admin, plan_id, "bob@example.com", 222222);
+
+    let xlm_helper = TestTokenHelper::new(&env, &xlm);
+    assert_eq!(xlm_helper.balance(&alice), 3000);
+    assert_eq!(xlm_helper.balance(&bob), 2000);
+    assert_eq!(
+        client.get_claimable_amount(&plan_id).get(1).unwrap().amount,
+        0
+    );
+    assert_eq!(
+        client
+            .get_beneficiary_assets(&plan_id, &0)
+            .get(0)
+            .unwrap()
+            .amount,
+        0
+    );
+}
+
+#[test]
+fn test_per_asset_allocation_overrides_pro_rata() {
+    let env = Env::default();
+    let (client, admin, usdc, xlm, owner, plan_id) = setup_multi_asset_plan(&env);
+    client.deposit(&owner, &xlm, &plan_id, &5000u64);
+
+    assert_eq!(
+        client.try_set_asset_allocation(&owner, &plan_id, &usdc, &vec![&env, 0u32, 10000u32]),
+        Err(Ok(AssetError::TokenNotWhitelisted))
+    );
+    assert_eq!(
+        client.try_set_asset_allocation(&owner, &plan_id, &xlm, &vec![&env, 10000u32]),
+        Err(Ok(AssetError::InvalidAllocation))
+    );
+    assert_eq!(
+        client.try_set_asset_allocation(&owner, &plan_id, &xlm, &vec![&env, 6000u32, 6000u32]),
+        Err(Ok(AssetError::InvalidAllocation))
+    );
+
+    // All of the XLM goes to Bob.
+    client.set_asset_allocation(&owner, &plan_id, &xlm, &vec![&env, 0u32, 10000u32]);
+    assert_eq!(
+        client.get_asset_allocation(&plan_id, &xlm),
+        vec![&env, 0u32, 10000u32]
+    );
+
+    let alice = claim_as(&env, &client, &admin, plan_id, "alice@example.com", 111111);
+    let xlm_helper = TestTokenHelper::new(&env, &xlm);
+    assert_eq!(xlm_helper.balance(&alice), 0);
+
+    // Nothing paid out yet, so the split can still change; after a payout it
+    // is locked.
+    client.set_asset_allocation(&owner, &plan_id, &xlm, &vec![&env, 0u32, 5000u32]);
+    let bob = claim_as(&env, &client, &admin, plan_id, "bob@example.com", 222222);
+    assert_eq!(xlm_helper.balance(&bob), 2500);
+    assert_eq!(
+        client.try_set_asset_allocation(&owner, &plan_id, &xlm, &Vec::new(&env)),
+        Err(Ok(AssetError::AllocationLocked))
+    );
+}
+
+// ─── Beneficiary Vesting Tests ───────────────────
+
+const YEAR_SECS: u64 = 365 * 24 * 60 * 60;
+
+/// A plan of 1000 (980 after the fee) left entirely to Alice.
+fn setup_vesting_plan(env: &Env) -> (InheritanceContractClient<'_>, Address, Address, u64) {
+    let (client, token, admin, owner) = setup_with_token_and_admin(env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        env,
+        &owner,
+        &token,
+        "Will",
+        "Inheritance Plan",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(env),
+    ));
+    (client, admin, owner, plan_id)
+}
+
+fn try_claim_alice(
+    env: &Env,
+    client: &InheritanceContractClient<'_>,
+    admin: &Address,
+    plan_id: u64,
+) -> Result<(), Result<InheritanceError, soroban_sdk::InvokeError>> {
+    let claimer = Address::generate(env);
+    client.submit_kyc(&claimer);
+    client.approve_kyc(admin, &claimer);
+    client
+        .try_claim_inheritance_plan(
+            &plan_id,
+            &claimer,
+            &String::from_str(env, "alice@example.com"),
+            &111111u32,
+        )
+        .map(|_| ())
+}
+
+#[test]
+fn test_unlock_date_gates_claim() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = setup_vesting_plan(&env);
+    let eighteenth_birthday = env.ledger().timestamp() + 10 * YEAR_SECS;
+    client.set_beneficiary_vesting(
+        &owner,
+        &plan_id,
+        &0u32,
+        &VestingRelease::UnlockAt(eighteenth_birthday),
+    );
+
+    let status = client.get_beneficiary_vesting(&plan_id, &0u32);
+    assert_eq!(status.entitlement, 980);
+    assert_eq!(status.unlocked, 0);
+    assert_eq!(status.locked, 980);
+    assert_eq!(status.next_unlock_at, eighteenth_birthday);
+    assert_eq!(
+        try_claim_alice(&env, &client, &admin, plan_id),
+        Err(Ok(InheritanceError::VestingScheduleActive))
+    );
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = eighteenth_birthday);
+    assert_eq!(
+        client.get_beneficiary_vesting(&plan_id, &0u32).unlocked,
+        980
+    );
+    try_claim_alice(&env, &client, &admin, plan_id).unwrap();
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert!(plan.beneficiaries.get(0).unwrap().is_claimed);
+    assert_eq!(plan.total_amount, 0);
+}
+
+#[test]
+fn test_staged_release_pays_each_stage_once() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = setup_vesting_plan(&env);
+    let at_18 = env.ledger().timestamp() + YEAR_SECS;
+    let at_21 = at_18 + 3 * YEAR_SECS;
+    let at_25 = at_21 + 4 * YEAR_SECS;
+    let stage = |unlock_at: u64, cumulative_bp: u32| VestingStage {
+        unlock_at,
+        cumulative_bp,
+    };
+    client.set_beneficiary_vesting(
+        &owner,
+        &plan_id,
+        &0u32,
+        &VestingRelease::Staged(vec![
+            &env,
+            stage(at_18, 2500),
+            stage(at_21, 7500),
+            stage(at_25, 10000),
+        ]),
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = at_18);
+    try_claim_alice(&env, &client, &admin, plan_id).unwrap();
+    assert_eq!(client.get_plan_details(&plan_id).unwrap().total_amount, 735);
+    assert_eq!(
+        try_claim_alice(&env, &client, &admin, plan_id),
+        Err(Ok(InheritanceError::VestingScheduleActive))
+    );
+
+    // Further releases are shares of the entitlement fixed at the first one.
+    env.ledger().with_mut(|li| li.timestamp = at_21 + 1);
+    let status = client.get_beneficiary_vesting(&plan_id, &0u32);
+    assert_eq!(status.entitlement, 980);
+    assert_eq!(status.released, 245);
+    assert_eq!(status.unlocked, 490);
+    assert_eq!(status.locked, 245);
+    assert_eq!(status.next_unlock_at, at_25);
+    try_claim_alice(&env, &client, &admin, plan_id).unwrap();
+
+    // Locked once anything has been released.
+    assert_eq!(
+        client.try_set_beneficiary_vesting(&owner, &plan_id, &0u32, &VestingRelease::Immediate),
+        Err(Ok(VestingError::ScheduleLocked))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = at_25);
+    try_claim_alice(&env, &client, &admin, plan_id).unwrap();
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert_eq!(plan.total_amount, 0);
+    assert!(plan.beneficiaries.get(0).unwrap().is_claimed);
+    assert_eq!(
+        try_claim_alice(&env, &client, &admin, plan_id),
+        Err(Ok(InheritanceError::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_withdraw_leaves_vesting_entitlement_in_place() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = setup_vesting_plan(&env);
+    let token = env
+        .as_contract(&client.address, || assets::primary_token(&env, plan_id))
+        .unwrap();
+    let at_18 = env.ledger().timestamp() + YEAR_SECS;
+    let at_21 = at_18 + 3 * YEAR_SECS;
+    let stage = |unlock_at: u64, cumulative_bp: u32| VestingStage {
+        unlock_at,
+        cumulative_bp,
+    };
+    client.set_beneficiary_vesting(
+        &owner,
+        &plan_id,
+        &0u32,
+        &VestingRelease::Staged(vec![&env, stage(at_18, 2500), stage(at_21, 10000)]),
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = at_18);
+    try_claim_alice(&env, &client, &admin, plan_id).unwrap();
+
+    // The 735 still owed to Alice cannot be withdrawn.
+    assert_eq!(client.get_plan_details(&plan_id).unwrap().total_amount, 735);
+    assert_eq!(
+        client.try_withdraw(&owner, &token, &plan_id, &1u64),
+        Err(Ok(InheritanceError::InsufficientLiquidity))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = at_21);
+    try_claim_alice(&env, &client, &admin, plan_id).unwrap();
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert_eq!(plan.total_amount, 0);
+    assert!(plan.beneficiaries.get(0).unwrap().is_claimed);
+}
+
+#[test]
+fn test_trustee_attestation_releases_claim() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = setup_vesting_plan(&env);
+    let trustee = Address::generate(&env);
+    client.set_beneficiary_vesting(
+        &owner,
+        &plan_id,
+        &0u32,
+        &VestingRelease::Trustee(trustee.clone()),
+    );
+    assert_eq!(
+        try_claim_alice(&env, &client, &admin, plan_id),
+        Err(Ok(InheritanceError::VestingScheduleActive))
+    );
+
+    assert_eq!(
+        client.try_attest_vesting_release(&owner, &plan_id, &0u32),
+        Err(Ok(VestingError::NotTrustee))
+    );
+    client.attest_vesting_release(&trustee, &plan_id, &0u32);
+    assert_eq!(
+        client.try_attest_vesting_release(&trustee, &plan_id, &0u32),
+        Err(Ok(VestingError::AlreadyAttested))
+    );
+
+    assert_eq!(
+        client.get_beneficiary_vesting(&plan_id, &0u32).unlocked,
+        980
+    );
+    try_claim_alice(&env, &client, &admin, plan_id).unwrap();
+}
+
+#[test]
+fn test_vesting_schedule_validation() {
+    let env = Env::default();
+    let (client, token, _admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token,
+        "Will",
+        "Inheritance Plan",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &two_beneficiaries(&env),
+    ));
+    let stage = |unlock_at: u64, cumulative_bp: u32| VestingStage {
+        unlock_at,
+        cumulative_bp,
+    };
+
+    for invalid in [
+        VestingRelease::Staged(Vec::new(&env)),
+        VestingRelease::Staged(vec![&env, stage(100, 5000)]),
+        VestingRelease::Staged(vec![&env, stage(200, 5000), stage(100, 10000)]),
+        VestingRelease::Staged(vec![&env, stage(100, 5000), stage(200, 5000)]),
+    ] {
+        assert_eq!(
+            client.try_set_beneficiary_vesting(&owner, &plan_id, &0u32, &invalid),
+            Err(Ok(VestingError::InvalidSchedule))
+        );
+    }
+    assert_eq!(
+        client.try_set_beneficiary_vesting(
+            &Address::generate(&env),
+            &plan_id,
+            &0u32,
+            &VestingRelease::UnlockAt(100)
+        ),
+        Err(Ok(VestingError::Unauthorized))
+    );
+
+    // Beneficiaries added later can carry their condition with them.
+    client.remove_beneficiary(&owner, &plan_id, &1u32);
+    let input = |vesting: VestingRelease| BeneficiaryInput {
+        name: String::from_str(&env, "Charlie"),
+        email: String::from_str(&env, "charlie@example.com"),
+        claim_code: 333333,
+        bank_account: create_test_bytes(&env, "3333333333333333"),
+        allocation_bp: 4000,
+        priority: 2,
+        vesting,
+    };
+    assert_eq!(
+        client.try_add_beneficiary(
+            &owner,
+            &plan_id,
+            &input(VestingRelease::Staged(Vec::new(&env)))
+        ),
+        Err(Ok(InheritanceError::InvalidBeneficiaryData))
+    );
+    client.add_beneficiary(&owner, &plan_id, &input(VestingRelease::UnlockAt(500)));
+    assert_eq!(
+        client.get_beneficiary_vesting(&plan_id, &1u32).release,
+        VestingRelease::UnlockAt(500)
+    );
+}
+
+// ─── Guardian Recovery Tests ─────────────────────
+
+/// A funded plan with three guardians, two of whom must approve a recovery.
+fn setup_recovery_plan(
+    env: &Env,
+) -> (
+    InheritanceContractClient<'_>,
+    Address,
+    Address,
+    u64,
+    Vec<Address>,
+) {
+    let (client, token, admin, owner) = setup_with_token_and_admin(env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        env,
+        &owner,
+        &token,
+        "Will",
+        "Inheritance Plan",
+        1000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(env),
+    ));
+    let guardians = vec![
+        env,
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ];
+    client.set_guardians(&owner, &plan_id, &guardians, &2u32);
+    (client, admin, owner, plan_id, guardians)
+}
+
+#[test]
+fn test_guardian_recovery_moves_ownership_after_delay() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, guardians) = setup_recovery_plan(&env);
+    let new_owner = Address::generate(&env);
+
+    let proposal = client.propose_recovery(&guardians.get(0).unwrap(), &plan_id, &new_owner);
+    assert_eq!(proposal.executable_at, 0);
+    assert_eq!(
+        client.try_execute_recovery(&new_owner, &plan_id),
+        Err(Ok(RecoveryError::ThresholdNotMet))
+    );
+
+    let proposal = client.approve_recovery(&guardians.get(1).unwrap(), &plan_id);
+    assert_eq!(proposal.approvals.len(), 2);
+    assert_eq!(
+        proposal.executable_at,
+        env.ledger().timestamp() + recovery::RECOVERY_DELAY_SECS
+    );
+    assert_eq!(
+        client.try_execute_recovery(&new_owner, &plan_id),
+        Err(Ok(RecoveryError::DelayNotElapsed))
+    );
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = proposal.executable_at);
+    assert_eq!(
+        client.try_execute_recovery(&Address::generate(&env), &plan_id),
+        Err(Ok(RecoveryError::RecoveryNotFound))
+    );
+    client.execute_recovery(&new_owner, &plan_id);
+
+    let events = env.events().all();
+    let transferred = events.iter().any(|(_, topics, data)| {
+        topics
+            == vec![
+                &env,
+                symbol_short!("PLAN").into_val(&env),
+                symbol_short!("TRANSFER").into_val(&env),
+            ]
+            && PlanOwnershipTransferredEvent::from_val(&env, &data).new_owner == new_owner
+    });
+    assert!(transferred);
+
+    assert_eq!(client.get_plan_details(&plan_id).unwrap().owner, new_owner);
+    assert_eq!(client.get_user_plans(&new_owner).len(), 1);
+    assert_eq!(client.get_user_plans(&owner).len(), 0);
+    assert_eq!(client.get_recovery_proposal(&plan_id), None);
+}
+
+#[test]
+fn test_owner_can_cancel_guardian_recovery() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, guardians) = setup_recovery_plan(&env);
+    let new_owner = Address::generate(&env);
+
+    client.propose_recovery(&guardians.get(0).unwrap(), &plan_id, &new_owner);
+    client.approve_recovery(&guardians.get(1).unwrap(), &plan_id);
+    assert_eq!(
+        client.try_cancel_recovery(&new_owner, &plan_id),
+        Err(Ok(RecoveryError::Unauthorized))
+    );
+    client.cancel_recovery(&owner, &plan_id);
+    assert_eq!(client.get_recovery_proposal(&plan_id), None);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += recovery::RECOVERY_DELAY_SECS);
+    assert_eq!(
+        client.try_execute_recovery(&new_owner, &plan_id),
+        Err(Ok(RecoveryError::RecoveryNotFound))
+    );
+    assert_eq!(client.get_plan_details(&plan_id).unwrap().owner, owner);
+}
+
+#[test]
+fn test_guardian_threshold_can_withdraw_recovery() {
+    let env = Env::default();
+    let (client, _admin, _owner, plan_id, guardians) = setup_recovery_plan(&env);
+    let mistyped = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let (first, second) = (guardians.get(0).unwrap(), guardians.get(1).unwrap());
+
+    client.propose_recovery(&first, &plan_id, &mistyped);
+    assert_eq!(
+        client.try_cancel_recovery(&Address::generate(&env), &plan_id),
+        Err(Ok(RecoveryError::Unauthorized))
+    );
+
+    // One vote is below the threshold; the proposal stands.
+    client.cancel_recovery(&first, &plan_id);
+    assert_eq!(
+        client.try_cancel_recovery(&first, &plan_id),
+        Err(Ok(RecoveryError::AlreadyApproved))
+    );
+    assert_eq!(
+        client.get_recovery_proposal(&plan_id).unwrap().cancel_votes,
+        vec![&env, first.clone()]
+    );
+    assert_eq!(
+        client.try_propose_recovery(&second, &plan_id, &new_owner),
+        Err(Ok(RecoveryError::RecoveryPending))
+    );
+
+    // A second vote withdraws it and a replacement can be proposed.
+    client.cancel_recovery(&second, &plan_id);
+    assert_eq!(client.get_recovery_proposal(&plan_id), None);
+    let proposal = client.propose_recovery(&second, &plan_id, &new_owner);
+    assert_eq!(proposal.new_owner, new_owner);
+    assert!(proposal.cancel_votes.is_empty());
+}
+
+#[test]
+fn test_recovery_proposal_expires() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, guardians) = setup_recovery_plan(&env);
+    let stale = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let (first, second) = (guardians.get(0).unwrap(), guardians.get(1).unwrap());
+
+    // A proposal that never reaches the threshold lapses.
+    client.propose_recovery(&first, &plan_id, &stale);
+    env.ledger()
+        .with_mut(|li| li.timestamp += recovery::RECOVERY_EXPIRY_SECS);
+    assert_eq!(client.get_recovery_proposal(&plan_id), None);
+    assert_eq!(
+        client.try_approve_recovery(&second, &plan_id),
+        Err(Ok(RecoveryError::RecoveryExpired))
+    );
+
+    // It no longer blocks a new proposal.
+    client.propose_recovery(&second, &plan_id, &new_owner);
+    let proposal = client.approve_recovery(&first, &plan_id);
+
+    // An approved proposal must be executed within the expiry window.
+    env.ledger()
+        .with_mut(|li| li.timestamp = proposal.executable_at + recovery::RECOVERY_EXPIRY_SECS);
+    assert_eq!(
+        client.try_execute_recovery(&new_owner, &plan_id),
+        Err(Ok(RecoveryError::RecoveryExpired))
+    );
+    assert_eq!(client.get_plan_details(&plan_id).unwrap().owner, owner);
+}
+
+#[test]
+fn test_guardian_recovery_rejections() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id, guardians) = setup_recovery_plan(&env);
+    let new_owner = Address::generate(&env);
+    let first = guardians.get(0).unwrap();
+
+    assert_eq!(
+        client.try_propose_recovery(&Address::generate(&env), &plan_id, &new_owner),
+        Err(Ok(RecoveryError::NotGuardian))
+    );
+    assert_eq!(
+        client.try_propose_recovery(&first, &plan_id, &owner),
+        Err(Ok(RecoveryError::InvalidNewOwner))
+    );
+    assert_eq!(
+        client.try_approve_recovery(&first, &plan_id),
+        Err(Ok(RecoveryError::RecoveryNotFound))
+    );
+
+    client.propose_recovery(&first, &plan_id, &new_owner);
+    assert_eq!(
+        client.try_propose_recovery(&guardians.get(1).unwrap(), &plan_id, &first),
+        Err(Ok(RecoveryError::RecoveryPending))
+    );
+    assert_eq!(
+        client.try_approve_recovery(&first, &plan_id),
+        Err(Ok(RecoveryError::AlreadyApproved))
+    );
+
+    // Changing the guardian set drops approvals from the old one.
+    client.set_guardians(&owner, &plan_id, &guardians, &2u32);
+    assert_eq!(client.get_recovery_proposal(&plan_id), None);
+
+    // Ownership is fixed once inheritance has been triggered.
+    client.propose_recovery(&first, &plan_id, &new_owner);
+    client.approve_recovery(&guardians.get(2).unwrap(), &plan_id);
+    env.ledger()
+        .with_mut(|li| li.timestamp += recovery::RECOVERY_DELAY_SECS);
+    client.trigger_inheritance(&admin, &plan_id);
+    assert_eq!(
+        client.try_execute_recovery(&new_owner, &plan_id),
+        Err(Ok(RecoveryError::PlanTriggered))
+    );
+}
+
+// ─── Two-Step Transfer Tests ─────────────────────
+
+#[test]
+fn test_plan_transfer_waits_for_acceptance() {
+    let env = Env::default();
+    let (client, token, _admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = create_plans(&env, &client, &token, &owner, 1)
+        .get(0)
+        .unwrap();
+    let typo = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+
+    client.transfer_plan_ownership(&owner, &typo, &plan_id);
+    assert_eq!(client.get_plan_details(&plan_id).unwrap().owner, owner);
+
+    // Re-proposing replaces the mistyped address.
+    client.transfer_plan_ownership(&owner, &new_owner, &plan_id);
+    let pending = client.get_pending_plan_transfer(&plan_id).unwrap();
+    assert_eq!(pending.current_owner, owner);
+    assert_eq!(pending.new_owner, new_owner);
+    assert_eq!(
+        client.try_accept_plan_ownership(&typo, &plan_id),
+        Err(Ok(OwnershipError::NoPendingTransfer))
+    );
+
+    client.accept_plan_ownership(&new_owner, &plan_id);
+    assert_eq!(client.get_plan_details(&plan_id).unwrap().owner, new_owner);
+    assert_eq!(client.get_pending_plan_transfer(&plan_id), None);
+    assert_eq!(
+        client.try_transfer_plan_ownership(&owner, &typo, &plan_id),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_plan_transfer_can_be_cancelled() {
+    let env = Env::default();
+    let (client, token, _admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = create_plans(&env, &client, &token, &owner, 1)
+        .get(0)
+        .unwrap();
+    let new_owner = Address::generate(&env);
+
+    assert_eq!(
+        client.try_cancel_plan_ownership_transfer(&owner, &plan_id),
+        Err(Ok(OwnershipError::NoPendingTransfer))
+    );
+    client.transfer_plan_ownership(&owner, &new_owner, &plan_id);
+    assert_eq!(
+        client.try_cancel_plan_ownership_transfer(&new_owner, &plan_id),
+        Err(Ok(OwnershipError::Unauthorized))
+    );
+    client.cancel_plan_ownership_transfer(&owner, &plan_id);
+    assert_eq!(
+        client.try_accept_plan_ownership(&new_owner, &plan_id),
+        Err(Ok(OwnershipError::NoPendingTransfer))
+    );
+    assert_eq!(client.get_plan_details(&plan_id).unwrap().owner, owner);
+}
+
+#[test]
+fn test_admin_transfer_is_two_step() {
+    let env = Env::default();
+    let (client, _token, admin, _owner) = setup_with_token_and_admin(&env);
+    let new_admin = Address::generate(&env);
+
+    assert_eq!(
+        client.try_assign_role(&admin, &new_admin, &access_control::Role::Admin),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_propose_admin_transfer(&new_admin, &new_admin),
+        Err(Ok(OwnershipError::NotAdmin))
+    );
+
+    client.propose_admin_transfer(&admin, &new_admin);
+    let pending = client.get_pending_admin_transfer().unwrap();
+    assert_eq!(pending.current_admin, admin);
+    assert_eq!(pending.new_admin, new_admin);
+    assert!(!client.has_role(&new_admin, &access_control::Role::Admin));
+    assert_eq!(
+        client.try_accept_admin_transfer(&Address::generate(&env)),
+        Err(Ok(OwnershipError::NoPendingTransfer))
+    );
+
+    client.accept_admin_transfer(&new_admin);
+    assert!(client.has_role(&new_admin, &access_control::Role::Admin));
+    assert!(!client.has_role(&admin, &access_control::Role::Admin));
+    assert_eq!(client.get_pending_admin_transfer(), None);
+
+    client.propose_admin_transfer(&new_admin, &admin);
+    client.cancel_admin_transfer(&new_admin);
+    assert_eq!(
+        client.try_accept_admin_transfer(&admin),
+        Err(Ok(OwnershipError::NoPendingTransfer))
+    );
+}
@@ -0,0 +1,255 @@
+use soroban_sdk::{contracterror, contracttype, Address, BytesN, Env, Vec};
+
+/// Most stages a staged release may have.
+pub const MAX_STAGES: u32 = 12;
+
+/// Errors raised when configuring or attesting beneficiary vesting.
+///
+/// Kept separate from `InheritanceError` (at the 50-case spec limit).
+/// Codes start at 700 so they never overlap the other module enums.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VestingError {
+    Unauthorized = 700,
+    PlanNotFound = 701,
+    InvalidBeneficiaryIndex = 702,
+    InvalidSchedule = 703,
+    ScheduleLocked = 704,
+    NotTrustee = 705,
+    AlreadyAttested = 706,
+}
+
+/// One step of a staged release: from `unlock_at` (unix seconds) the
+/// beneficiary may have received `cumulative_bp` of their entitlement.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingStage {
+    pub unlock_at: u64,
+    pub cumulative_bp: u32,
+}
+
+/// Trust-like condition on when a beneficiary's entitlement is released,
+/// on top of the plan's own claim conditions.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VestingRelease {
+    /// No condition: the entitlement is released with the plan.
+    Immediate,
+    /// Everything unlocks at this unix timestamp (e.g. an 18th birthday).
+    UnlockAt(u64),
+    /// Staged releases with increasing dates and cumulative basis points,
+    /// the last stage reaching 10000.
+    Staged(Vec<VestingStage>),
+    /// Everything unlocks once this trustee attests the release.
+    Trustee(Address),
+}
+
+/// How much of a vesting beneficiary's entitlement has been paid. The
+/// entitlement is fixed at their first release so later stages are not
+/// skewed by other beneficiaries' claims.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingProgress {
+    pub entitlement: u64,
+    pub released: u64,
+}
+
+/// Per-beneficiary view of locked and unlocked amounts.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingStatus {
+    pub plan_id: u64,
+    pub beneficiary_index: u32,
+    pub release: VestingRelease,
+    pub entitlement: u64,
+    pub released: u64,
+    pub unlocked: u64,       // claimable now
+    pub locked: u64,         // not yet released by the schedule
+    pub next_unlock_at: u64, // 0 if nothing further unlocks on a date
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingAttestedEvent {
+    pub plan_id: u64,
+    pub hashed_email: BytesN<32>,
+    pub trustee: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum VestingKey {
+    Release(u64, BytesN<32>),  // (plan_id, hashed_email) -> VestingRelease
+    Attested(u64, BytesN<32>), // (plan_id, hashed_email) -> u64 (attested at)
+    Progress(u64, BytesN<32>), // (plan_id, hashed_email) -> VestingProgress
+}
+
+/// Check a release condition is well formed.
+pub fn is_valid(release: &VestingRelease) -> bool {
+    match release {
+        VestingRelease::Immediate | VestingRelease::UnlockAt(_) | VestingRelease::Trustee(_) => {
+            true
+        }
+        VestingRelease::Staged(stages) => {
+            if stages.is_empty() || stages.len() > MAX_STAGES {
+                return false;
+            }
+            let mut previous: Option<VestingStage> = None;
+            for stage in stages.iter() {
+                if stage.cumulative_bp == 0 || stage.cumulative_bp > 10000 {
+                    return false;
+                }
+                if let Some(prev) = previous {
+                    if stage.unlock_at <= prev.unlock_at
+                        || stage.cumulative_bp <= prev.cumulative_bp
+                    {
+                        return false;
+                    }
+                }
+                previous = Some(stage);
+            }
+            previous.is_some_and(|last| last.cumulative_bp == 10000)
+        }
+    }
+}
+
+/// The beneficiary's release condition, if it is anything but `Immediate`.
+/// Conditions are keyed by hashed email rather than index because removing
+/// a beneficiary swaps the last one into its slot.
+pub fn release(env: &Env, plan_id: u64, hashed_email: &BytesN<32>) -> Option<VestingRelease> {
+    env.storage()
+        .persistent()
+        .get(&VestingKey::Release(plan_id, hashed_email.clone()))
+}
+
+pub fn set_release(env: &Env, plan_id: u64, hashed_email: &BytesN<32>, release: &VestingRelease) {
+    let key = VestingKey::Release(plan_id, hashed_email.clone());
+    match release {
+        VestingRelease::Immediate => env.storage().persistent().remove(&key),
+        release => {
+            env.storage().persistent().set(&key, release);
+            access_control::extend_persistent_ttl(env, &key);
+        }
+    }
+    env.storage()
+        .persistent()
+        .remove(&VestingKey::Attested(plan_id, hashed_email.clone()));
+}
+
+pub fn attested_at(env: &Env, plan_id: u64, hashed_email: &BytesN<32>) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&VestingKey::Attested(plan_id, hashed_email.clone()))
+}
+
+pub fn record_attestation(env: &Env, plan_id: u64, hashed_email: &BytesN<32>) {
+    let key = VestingKey::Attested(plan_id, hashed_email.clone());
+    env.storage()
+        .persistent()
+        .set(&key, &env.ledger().timestamp());
+    access_control::extend_persistent_ttl(env, &key);
+}
+
+pub fn progress(env: &Env, plan_id: u64, hashed_email: &BytesN<32>) -> Option<VestingProgress> {
+    env.storage()
+        .persistent()
+        .get(&VestingKey::Progress(plan_id, hashed_email.clone()))
+}
+
+pub fn set_progress(
+    env: &Env,
+    plan_id: u64,
+    hashed_email: &BytesN<32>,
+    progress: &VestingProgress,
+) {
+    let key = VestingKey::Progress(plan_id, hashed_email.clone());
+    env.storage().persistent().set(&key, progress);
+    access_control::extend_persistent_ttl(env, &key);
+}
+
+/// Drop every vesting record of a removed beneficiary.
+pub fn clear(env: &Env, plan_id: u64, hashed_email: &BytesN<32>) {
+    for key in [
+        VestingKey::Release(plan_id, hashed_email.clone()),
+        VestingKey::Attested(plan_id, hashed_email.clone()),
+        VestingKey::Progress(plan_id, hashed_email.clone()),
+    ] {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Basis points of the entitlement `release` has unlocked at `now`.
+pub fn unlocked_bp(
+    env: &Env,
+    plan_id: u64,
+    hashed_email: &BytesN<32>,
+    release: &VestingRelease,
+    now: u64,
+) -> u32 {
+    match release {
+        VestingRelease::Immediate => 10000,
+        VestingRelease::UnlockAt(unlock_at) if now >= *unlock_at => 10000,
+        VestingRelease::UnlockAt(_) => 0,
+        VestingRelease::Staged(stages) => stages
+            .iter()
+            .filter(|stage| stage.unlock_at <= now)
+            .map(|stage| stage.cumulative_bp)
+            .max()
+            .unwrap_or(0),
+        VestingRelease::Trustee(_) => {
+            if attested_at(env, plan_id, hashed_email).is_some() {
+                10000
+            } else {
+                0
+            }
+        }
+    }
+}
+
+/// The next date after `now` on which more of the entitlement unlocks.
+pub fn next_unlock_at(release: &VestingRelease, now: u64) -> u64 {
+    match release {
+        VestingRelease::UnlockAt(unlock_at) if *unlock_at > now => *unlock_at,
+        VestingRelease::Staged(stages) => stages
+            .iter()
+            .map(|stage| stage.unlock_at)
+            .find(|unlock_at| *unlock_at > now)
+            .unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// The part of `entitlement` unlocked by `bp`.
+pub fn unlocked_amount(entitlement: u64, bp: u32) -> u64 {
+    (entitlement as u128)
+        .checked_mul(bp as u128)
+        .and_then(|v| v.checked_div(10000))
+        .unwrap_or(0) as u64
+}
+
+/// Renew the TTL of the beneficiary's vesting records, if any.
+pub fn extend_ttl(env: &Env, plan_id: u64, hashed_email: &BytesN<32>) {
+    for key in [
+        VestingKey::Release(plan_id, hashed_email.clone()),
+        VestingKey::Attested(plan_id, hashed_email.clone()),
+        VestingKey::Progress(plan_id, hashed_email.clone()),
+    ] {
+        access_control::renew_persistent_ttl(env, &key);
+    }
+}
+
+/// Move the beneficiary's vesting records from one hashed email to another.
+pub fn rekey(env: &Env, plan_id: u64, from: &BytesN<32>, to: &BytesN<32>) {
+    if let Some(release) = release(env, plan_id, from) {
+        set_release(env, plan_id, to, &release);
+    }
+    if let Some(at) = attested_at(env, plan_id, from) {
+        let key = VestingKey::Attested(plan_id, to.clone());
+        env.storage().persistent().set(&key, &at);
+        access_control::extend_persistent_ttl(env, &key);
+    }
+    if let Some(progress) = progress(env, plan_id, from) {
+        set_progress(env, plan_id, to, &progress);
+    }
+    clear(env, plan_id, from);
+}
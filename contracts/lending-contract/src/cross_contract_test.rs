@@ -1,7 +1,8 @@
 #![cfg(test)]
 
-use crate::{LendingContract, LendingContractClient};
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use crate::{LendingContract, LendingContractClient, LendingError};
+use mock_token::{MockToken, MockTokenClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
 
 #[test]
 fn test_set_and_get_contracts() {
@@ -30,3 +31,51 @@ fn test_set_and_get_contracts() {
         Some(governance_contract.clone())
     );
 }
+
+fn setup_with_mock_token(env: &Env) -> (LendingContractClient<'_>, MockTokenClient<'_>) {
+    let admin = Address::generate(env);
+    let token_id = env.register_contract(None, MockToken);
+    let token = MockTokenClient::new(env, &token_id);
+    token.initialize(
+        &admin,
+        &6,
+        &String::from_str(env, "USD Coin"),
+        &String::from_str(env, "USDC"),
+    );
+
+    let contract_id = env.register_contract(None, LendingContract);
+    let client = LendingContractClient::new(env, &contract_id);
+    client.initialize(&admin, &token_id, &500u32, &2000u32, &15000u32, &10000u32);
+    (client, token)
+}
+
+#[test]
+fn test_deposit_with_mock_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token) = setup_with_mock_token(&env);
+    let depositor = Address::generate(&env);
+    token.mint(&depositor, &10_000);
+
+    client.deposit(&depositor, &token.address, &2000u64);
+    assert_eq!(token.balance(&depositor), 8_000);
+    assert_eq!(token.balance(&client.address), 2_000);
+    assert_eq!(client.get_pool_state(&token.address).total_deposits, 2000);
+}
+
+#[test]
+fn test_token_transfer_failure_rejects_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token) = setup_with_mock_token(&env);
+    let depositor = Address::generate(&env);
+    token.mint(&depositor, &10_000);
+
+    token.set_fail_transfers(&true);
+    assert_eq!(
+        client.try_deposit(&depositor, &token.address, &2000u64),
+        Err(Ok(LendingError::TransferFailed))
+    );
+    assert_eq!(token.balance(&depositor), 10_000);
+    assert_eq!(client.get_pool_state(&token.address).total_deposits, 0);
+}
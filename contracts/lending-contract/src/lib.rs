@@ -1,12 +1,15 @@
 #![no_std]
-use access_control::{self, Role};
+use access_control::{self, PendingAdminTransfer, Role, RoleGrant};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, log, symbol_short, token, vec, Address,
     Env, IntoVal, InvokeError, Val, Vec,
 };
 
+mod oracle;
 mod reserves;
 
+pub use price_oracle::{Asset, OracleConfig, PriceData, PriceOracleClient, PriceOracleInterface};
+
 // ─────────────────────────────────────────────────
 // Constants
 // ─────────────────────────────────────────────────
@@ -17,12 +20,14 @@ const BAD_DEBT_RESERVE_BPS: u32 = 5000; // 50% of protocol share routed to reser
 const DEFAULT_GRACE_PERIOD_SECONDS: u64 = 259_200; // 3 days
 const DEFAULT_LATE_FEE_RATE_BPS: u32 = 500; // 5% per day = 0.058% per second (approx)
 const REFINANCING_FEE_BPS: u32 = 50; // 0.5% refinancing fee
+const MAX_LOANS_PER_BORROWER: u32 = 10; // Open loans a single borrower may hold at once
 const DEFAULT_REWARD_RATE: u64 = 1_000_000_000; // Default reward rate per second (1 reward per second with 9 decimals)
 const REWARD_PRECISION: u64 = 1_000_000_000; // 9 decimals for reward calculations
 
 // Insurance constants
 const DEFAULT_INSURANCE_PREMIUM_RATE_BPS: u32 = 200; // 2% premium of loan principal
 const INSURANCE_CLAIM_PAYBACK_BPS: u32 = 10000; // 100% coverage
+const INSURANCE_CLAIM_WINDOW_SECONDS: u64 = 86_400; // Claims must be filed within a day of coverage ending
 
 // ─────────────────────────────────────────────────
 // Data Types
@@ -62,6 +67,17 @@ pub struct LoanRecord {
     pub interest_rate_bps: u32,
 }
 
+/// Aggregate position of a borrower across all open loans, priced in the
+/// oracle quote currency.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountHealth {
+    pub loan_count: u32,
+    pub collateral_value: u128,
+    pub debt_value: u128,   // Principal plus accrued interest
+    pub health_factor: u32, // collateral_value / debt_value in bps, u32::MAX with no debt
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RefinanceTerms {
@@ -110,8 +126,8 @@ pub struct InsuranceFund {
 #[soroban_sdk::contractclient(name = "LoanNFTClient")]
 pub trait LoanNFTInterface {
     fn initialize(env: Env, admin: Address);
-    fn mint(env: Env, to: Address, metadata: LoanMetadata);
-    fn burn(env: Env, loan_id: u64);
+    fn mint(env: Env, admin: Address, to: Address, metadata: LoanMetadata);
+    fn burn(env: Env, admin: Address, loan_id: u64);
     fn get_metadata(env: Env, loan_id: u64) -> Option<LoanMetadata>;
     fn owner_of(env: Env, loan_id: u64) -> Option<Address>;
 }
@@ -448,6 +464,11 @@ pub enum LendingError {
     InvalidInsuranceAmount = 31,
     InvalidRateModel = 32,
     ContractPaused = 33,
+    PriceUnavailable = 34,
+    StalePrice = 35,
+    NoPendingAdminTransfer = 36,
+    InvalidAdminTransfer = 37,
+    ValuationOverflow = 38,
 }
 
 // ─────────────────────────────────────────────────
@@ -461,7 +482,6 @@ pub enum DataKey {
     SupportedAssets, // Vec<Address>
     PoolState(Address),
     Shares(Address, Address), // (User, Asset)
-    Loan(Address),
     NextLoanId,
     LoanById(u64),
     CollateralRatio,
@@ -504,6 +524,7 @@ impl LendingContract {
         collateral_ratio_bps: u32,
         utilization_cap_bps: u32,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         admin.require_auth();
         if env.storage().instance().has(&DataKey::Admin) {
             return Err(LendingError::AlreadyInitialized);
@@ -512,6 +533,8 @@ impl LendingContract {
 
         // Store the primary token address for insurance operations
         env.storage().instance().set(&DataKey::Token, &token);
+        // Pool amounts are quoted in the primary token unless an admin says otherwise
+        price_oracle::set_quote_asset(&env, &token);
 
         let mut assets = Vec::new(&env);
         assets.push_back(token.clone());
@@ -554,6 +577,7 @@ impl LendingContract {
             },
         );
 
+        access_control::assign_role(&env, &admin, Role::Admin);
         Ok(())
     }
 
@@ -567,6 +591,7 @@ impl LendingContract {
         multiplier_bps: u32,
         utilization_cap_bps: u32,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
 
         let mut assets: Vec<Address> = env
@@ -634,42 +659,138 @@ impl LendingContract {
             &DEFAULT_INSURANCE_PREMIUM_RATE_BPS,
         );
 
-        access_control::assign_role(&env, &admin, Role::Admin);
         Ok(())
     }
 
-    /// Assign a role to an address. Admin-only.
+    /// Assign a role to an address. Callable by admins and by holders of the
+    /// role's admin role (see `set_role_admin`). The Admin role itself is
+    /// handed over with `propose_admin_transfer` / `accept_admin_transfer`.
     pub fn assign_role(
         env: Env,
         admin: Address,
         address: Address,
         role: Role,
     ) -> Result<(), LendingError> {
-        Self::require_admin(&env, &admin)?;
-        access_control::assign_role(&env, &address, role);
+        Self::assign_role_until(env, admin, address, role, 0)
+    }
+
+    /// Like `assign_role`, but the grant lapses at `expires_at` (unix
+    /// seconds; 0 for never). Re-assigning replaces the expiry.
+    pub fn assign_role_until(
+        env: Env,
+        admin: Address,
+        address: Address,
+        role: Role,
+        expires_at: u64,
+    ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role_admin(&env, &admin, role.clone(), LendingError::NotAdmin)?;
+        if role == Role::Admin {
+            return Err(LendingError::InvalidAdminTransfer);
+        }
+        access_control::assign_role_until(&env, &address, role, expires_at);
         Ok(())
     }
 
-    /// Revoke a role from an address. Admin-only.
+    /// Revoke a role from an address. Callable by admins and by holders of
+    /// the role's admin role. The Admin role only moves with the two-step
+    /// admin transfer, so it cannot be revoked here.
     pub fn revoke_role(
         env: Env,
         admin: Address,
         address: Address,
         role: Role,
     ) -> Result<(), LendingError> {
-        Self::require_admin(&env, &admin)?;
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role_admin(&env, &admin, role.clone(), LendingError::NotAdmin)?;
+        if role == Role::Admin {
+            return Err(LendingError::InvalidAdminTransfer);
+        }
         access_control::revoke_role(&env, &address, role);
         Ok(())
     }
 
-    /// Pause or unpause a specific asset pool.
+    /// Let holders of `admin_role` grant and revoke `role`. Admin-only; who
+    /// manages the Admin role cannot be changed.
+    pub fn set_role_admin(
+        env: Env,
+        admin: Address,
+        role: Role,
+        admin_role: Role,
+    ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin)?;
+        if role == Role::Admin {
+            return Err(LendingError::InvalidAdminTransfer);
+        }
+        access_control::set_role_admin(&env, role, admin_role);
+        Ok(())
+    }
+
+    /// The role whose holders may grant and revoke `role`.
+    pub fn get_role_admin(env: Env, role: Role) -> Role {
+        access_control::extend_instance_ttl(&env);
+        access_control::role_admin(&env, role)
+    }
+
+    /// Up to `limit` holders of `role`, from position `start`.
+    pub fn get_role_members(env: Env, role: Role, start: u32, limit: u32) -> Vec<RoleGrant> {
+        access_control::extend_instance_ttl(&env);
+        access_control::role_members(&env, role, start, limit)
+    }
+
+    /// Propose `new_admin` as the pool's admin. Nothing changes until they
+    /// call `accept_admin_transfer`; proposing again replaces the proposal.
+    pub fn propose_admin_transfer(
+        env: Env,
+        admin: Address,
+        new_admin: Address,
+    ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin)?;
+        if new_admin == admin {
+            return Err(LendingError::InvalidAdminTransfer);
+        }
+        access_control::propose_admin_transfer(&env, &admin, &new_admin);
+        Ok(())
+    }
+
+    /// Accept a pending admin transfer, taking over the Admin role from the
+    /// admin who proposed it.
+    pub fn accept_admin_transfer(env: Env, new_admin: Address) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
+        new_admin.require_auth();
+        access_control::accept_admin_transfer(&env, &new_admin)
+            .ok_or(LendingError::NoPendingAdminTransfer)?;
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        Ok(())
+    }
+
+    /// Cancel a pending admin transfer. Admin-only.
+    pub fn cancel_admin_transfer(env: Env, admin: Address) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin)?;
+        access_control::cancel_admin_transfer(&env).ok_or(LendingError::NoPendingAdminTransfer)?;
+        Ok(())
+    }
+
+    /// The admin transfer waiting for acceptance, if any.
+    pub fn get_pending_admin_transfer(env: Env) -> Option<PendingAdminTransfer> {
+        access_control::extend_instance_ttl(&env);
+        access_control::pending_admin_transfer(&env)
+    }
+
+    /// Pause or unpause a specific asset pool (admin or pauser).
     pub fn pause_asset_pool(
         env: Env,
         admin: Address,
         asset: Address,
         paused: bool,
     ) -> Result<(), LendingError> {
-        Self::require_admin(&env, &admin)?;
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::Pauser)?;
         let mut pool = Self::get_pool(&env, &asset)?;
         pool.is_paused = paused;
         Self::set_pool(&env, &asset, &pool);
@@ -678,11 +799,13 @@ impl LendingContract {
 
     /// View pool state for a specific asset.
     pub fn get_pool_for_asset(env: Env, asset: Address) -> Result<PoolState, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::get_pool(&env, &asset)
     }
 
     /// List all assets supported by the protocol.
     pub fn get_supported_assets(env: Env) -> Vec<Address> {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::SupportedAssets)
@@ -691,19 +814,18 @@ impl LendingContract {
 
     /// Check whether an address holds a given role.
     pub fn has_role(env: Env, address: Address, role: Role) -> bool {
+        access_control::extend_instance_ttl(&env);
         access_control::has_role(&env, &address, role)
     }
 
     /// Return all roles held by an address.
     pub fn get_roles(env: Env, address: Address) -> Vec<Role> {
-        use access_control::AccessControlKey;
-        env.storage()
-            .persistent()
-            .get(&AccessControlKey::Roles(address))
-            .unwrap_or(Vec::new(&env))
+        access_control::extend_instance_ttl(&env);
+        access_control::roles_of(&env, &address)
     }
 
     pub fn set_nft_token(env: Env, admin: Address, nft_token: Address) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
         env.storage().instance().set(&DataKey::NFTToken, &nft_token);
         Ok(())
@@ -718,18 +840,21 @@ impl LendingContract {
     }
 
     pub fn pause(env: Env, admin: Address) -> Result<(), LendingError> {
-        Self::require_admin(&env, &admin)?;
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::Pauser)?;
         access_control::pause_contract(&env);
         Ok(())
     }
 
     pub fn unpause(env: Env, admin: Address) -> Result<(), LendingError> {
-        Self::require_admin(&env, &admin)?;
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::Pauser)?;
         access_control::unpause_contract(&env);
         Ok(())
     }
 
     pub fn is_paused(env: Env) -> bool {
+        access_control::extend_instance_ttl(&env);
         access_control::is_contract_paused(&env)
     }
 
@@ -772,6 +897,7 @@ impl LendingContract {
         env.storage()
             .persistent()
             .set(&DataKey::Shares(owner.clone(), asset.clone()), &shares);
+        access_control::extend_persistent_ttl(env, &DataKey::Shares(owner.clone(), asset.clone()));
     }
 
     fn get_next_loan_id(env: &Env) -> u64 {
@@ -804,6 +930,7 @@ impl LendingContract {
     }
 
     pub fn get_admin(env: Env) -> Option<Address> {
+        access_control::extend_instance_ttl(&env);
         env.storage().instance().get(&DataKey::Admin)
     }
 
@@ -820,6 +947,7 @@ impl LendingContract {
         env.storage()
             .persistent()
             .set(&DataKey::UserLoans(user.clone()), &loans);
+        access_control::extend_persistent_ttl(env, &DataKey::UserLoans(user.clone()));
     }
 
     fn remove_user_loan(env: &Env, user: &Address, loan_id: u64) {
@@ -838,7 +966,126 @@ impl LendingContract {
             env.storage()
                 .persistent()
                 .set(&DataKey::UserLoans(user.clone()), &new_loans);
+            access_control::extend_persistent_ttl(env, &DataKey::UserLoans(user.clone()));
+        }
+    }
+
+    fn load_loan(env: &Env, loan_id: u64) -> Result<LoanRecord, LendingError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LoanById(loan_id))
+            .ok_or(LendingError::LoanNotFound)
+    }
+
+    /// Load a loan and check that it belongs to `borrower`.
+    fn load_borrower_loan(
+        env: &Env,
+        borrower: &Address,
+        loan_id: u64,
+    ) -> Result<LoanRecord, LendingError> {
+        let loan = Self::load_loan(env, loan_id)?;
+        if loan.borrower != *borrower {
+            return Err(LendingError::Unauthorized);
+        }
+        Ok(loan)
+    }
+
+    /// Persist a newly created loan and index it under its borrower.
+    fn open_loan(env: &Env, loan: &LoanRecord) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::LoanById(loan.loan_id), loan);
+        access_control::extend_persistent_ttl(env, &DataKey::LoanById(loan.loan_id));
+        Self::add_user_loan(env, &loan.borrower, loan.loan_id);
+    }
+
+    /// Remove a loan record and its borrower index entry.
+    fn close_loan(env: &Env, loan: &LoanRecord) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::LoanById(loan.loan_id));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::LateFeesAccrued(loan.loan_id));
+        Self::remove_user_loan(env, &loan.borrower, loan.loan_id);
+    }
+
+    fn loan_in_grace_period(env: &Env, loan: &LoanRecord) -> Result<bool, LendingError> {
+        let pool = Self::get_pool(env, &loan.asset)?;
+        let grace_period_end = loan.due_date + pool.grace_period_seconds;
+        Ok(env.ledger().timestamp() <= grace_period_end)
+    }
+
+    /// Late fees owed on a loan: the daily late fee rate applied to each full
+    /// day overdue after the grace period.
+    fn loan_late_fee(env: &Env, loan: &LoanRecord) -> Result<u64, LendingError> {
+        let pool = Self::get_pool(env, &loan.asset)?;
+        let current_time = env.ledger().timestamp();
+        let grace_period_end = loan.due_date + pool.grace_period_seconds;
+
+        if current_time <= grace_period_end {
+            return Ok(0);
+        }
+
+        let days_overdue = (current_time - grace_period_end) / (24 * 60 * 60);
+        if days_overdue == 0 {
+            return Ok(0);
         }
+
+        // Look up any previously accrued late fees for this loan
+        let accrued_fees: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LateFeesAccrued(loan.loan_id))
+            .unwrap_or(0u64);
+
+        if accrued_fees > 0 {
+            return Ok(accrued_fees);
+        }
+
+        // Calculate new late fees: principal * rate_per_day * days_overdue / 10000
+        let daily_fee = ((loan.principal as u128)
+            .checked_mul(pool.late_fee_rate_bps as u128)
+            .and_then(|v| v.checked_div(10000))
+            .unwrap_or(0)) as u64;
+
+        let total_late_fee = (daily_fee as u128)
+            .checked_mul(days_overdue as u128)
+            .unwrap_or(0) as u64;
+
+        Ok(total_late_fee)
+    }
+
+    /// Sum collateral and outstanding debt over all of a borrower's open loans.
+    fn account_health(env: &Env, borrower: &Address) -> Result<AccountHealth, LendingError> {
+        let loan_ids = Self::get_user_loans(env, borrower);
+        let mut collateral_value = 0u128;
+        let mut debt_value = 0u128;
+        for loan_id in loan_ids.iter() {
+            let loan = Self::load_loan(env, loan_id)?;
+            collateral_value = collateral_value.saturating_add(oracle::quote_value(
+                env,
+                &loan.collateral_token,
+                loan.collateral_amount,
+            )?);
+            let outstanding = Self::calculate_outstanding_balance(env, &loan);
+            debt_value =
+                debt_value.saturating_add(oracle::quote_value(env, &loan.asset, outstanding)?);
+        }
+        Ok(AccountHealth {
+            loan_count: loan_ids.len(),
+            collateral_value,
+            debt_value,
+            health_factor: Self::health_factor_bps(collateral_value, debt_value),
+        })
+    }
+
+    fn health_factor_bps(collateral_value: u128, debt_value: u128) -> u32 {
+        if debt_value == 0 {
+            return u32::MAX;
+        }
+        let ratio = collateral_value.saturating_mul(10000) / debt_value;
+        ratio.min(u32::MAX as u128) as u32
     }
 
     // ─── Reward Farming Helpers ────────────────────────
@@ -972,6 +1219,12 @@ impl LendingContract {
         access_control::require_role(env, caller, Role::Admin, LendingError::NotAdmin)
     }
 
+    /// Require that `caller` is an admin or holds the operational `role`.
+    fn require_admin_or(env: &Env, caller: &Address, role: Role) -> Result<(), LendingError> {
+        caller.require_auth();
+        access_control::require_any_role(env, caller, &[Role::Admin, role], LendingError::NotAdmin)
+    }
+
     fn transfer(
         env: &Env,
         token: &Address,
@@ -1073,6 +1326,7 @@ impl LendingContract {
         asset: Address,
         amount: u64,
     ) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_not_paused(&env)?;
         Self::require_initialized(&env)?;
         Self::enter_reentrancy_guard(&env)?;
@@ -1139,6 +1393,7 @@ impl LendingContract {
         asset: Address,
         shares: u64,
     ) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_not_paused(&env)?;
         Self::require_initialized(&env)?;
         Self::enter_reentrancy_guard(&env)?;
@@ -1210,6 +1465,7 @@ impl LendingContract {
         collateral_amount: u64,
         duration_seconds: u64,
     ) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_not_paused(&env)?;
         Self::require_initialized(&env)?;
         Self::enter_reentrancy_guard(&env)?;
@@ -1229,19 +1485,27 @@ impl LendingContract {
             return Err(LendingError::CollateralNotWhitelisted);
         }
 
-        // Check if borrower already has existing loans
-        let existing_loans = Self::get_user_loans(&env, &borrower);
-        if !existing_loans.is_empty() {
-            return Err(LendingError::LoanAlreadyExists);
+        let existing = Self::account_health(&env, &borrower)?;
+        if existing.loan_count >= MAX_LOANS_PER_BORROWER {
+            return Err(LendingError::TooManyLoans);
         }
 
-        // Check collateral ratio (collateral_amount must be >= amount * ratio / 10000)
-        let required_collateral = (amount as u128)
-            .checked_mul(Self::get_collateral_ratio(&env) as u128)
-            .and_then(|v| v.checked_div(10000))
-            .unwrap_or(0) as u64;
-
-        if collateral_amount < required_collateral {
+        // Check collateral ratio, with collateral and debt priced in the quote currency.
+        // The new loan must be covered on its own, and the borrower's whole
+        // position must stay covered once it is added.
+        let min_ratio_bps = Self::get_collateral_ratio(&env);
+        let new_collateral_value = oracle::quote_value(&env, &collateral_token, collateral_amount)?;
+        let new_debt_value = oracle::quote_value(&env, &asset, amount)?;
+        if Self::health_factor_bps(new_collateral_value, new_debt_value) < min_ratio_bps {
+            return Err(LendingError::InsufficientCollateral);
+        }
+        let aggregate_ratio_bps = Self::health_factor_bps(
+            existing
+                .collateral_value
+                .saturating_add(new_collateral_value),
+            existing.debt_value.saturating_add(new_debt_value),
+        );
+        if aggregate_ratio_bps < min_ratio_bps {
             return Err(LendingError::InsufficientCollateral);
         }
 
@@ -1291,18 +1555,13 @@ impl LendingContract {
             interest_rate_bps: dynamic_rate_bps,
         };
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Loan(borrower.clone()), &loan);
-        env.storage()
-            .persistent()
-            .set(&DataKey::LoanById(loan_id), &loan);
-        Self::add_user_loan(&env, &borrower, loan_id);
+        Self::open_loan(&env, &loan);
 
         // Mint NFT if token is set
         if let Some(nft_token) = Self::get_nft_token(&env) {
             let nft_client = LoanNFTClient::new(&env, &nft_token);
             nft_client.mint(
+                &env.current_contract_address(),
                 &borrower,
                 &LoanMetadata {
                     borrower: borrower.clone(),
@@ -1350,25 +1609,22 @@ impl LendingContract {
         Ok(loan_id)
     }
 
-    /// Repay the full outstanding loan for the caller.
+    /// Repay loan `loan_id` in full. The caller must be the loan's borrower.
     /// Restores liquidity to the pool, returns collateral, and closes the loan record.
     /// Includes principal, interest, and any accumulated late fees in the repayment.
     /// Returns the total amount repaid (principal + interest + late fees).
-    pub fn repay(env: Env, borrower: Address) -> Result<u64, LendingError> {
+    pub fn repay(env: Env, borrower: Address, loan_id: u64) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_not_paused(&env)?;
         Self::require_initialized(&env)?;
         Self::enter_reentrancy_guard(&env)?;
         borrower.require_auth();
 
-        let loan: LoanRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Loan(borrower.clone()))
-            .ok_or(LendingError::NoOpenLoan)?;
+        let loan = Self::load_borrower_loan(&env, &borrower, loan_id)?;
 
         let elapsed = env.ledger().timestamp().saturating_sub(loan.borrow_time);
         let interest = Self::calculate_interest(loan.principal, loan.interest_rate_bps, elapsed);
-        let late_fee = Self::calculate_late_fee(env.clone(), borrower.clone())?;
+        let late_fee = Self::loan_late_fee(&env, &loan)?;
         let total_repayment = loan.principal + interest + late_fee;
 
         let contract_id = env.current_contract_address();
@@ -1404,21 +1660,12 @@ impl LendingContract {
         pool.bad_debt_reserve += reserve_share;
         Self::set_pool(&env, &loan.asset, &pool);
 
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Loan(borrower.clone()));
-        env.storage()
-            .persistent()
-            .remove(&DataKey::LoanById(loan.loan_id));
-        Self::remove_user_loan(&env, &borrower, loan.loan_id);
-        env.storage()
-            .persistent()
-            .remove(&DataKey::LateFeesAccrued(loan.loan_id));
+        Self::close_loan(&env, &loan);
 
         // Burn NFT if token is set
         if let Some(nft_token) = Self::get_nft_token(&env) {
             let nft_client = LoanNFTClient::new(&env, &nft_token);
-            nft_client.burn(&loan.loan_id);
+            nft_client.burn(&env.current_contract_address(), &loan.loan_id);
         }
 
         // Emit late fee event if any late fees were charged
@@ -1469,66 +1716,46 @@ impl LendingContract {
     }
 
     /// Calculate the total amount (principal + interest + late fees) required to repay the loan.
-    pub fn get_repayment_amount(env: Env, borrower: Address) -> Result<u64, LendingError> {
-        let loan_opt: Option<LoanRecord> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Loan(borrower.clone()));
-
-        match loan_opt {
-            Some(loan) => {
-                let elapsed = env.ledger().timestamp().saturating_sub(loan.borrow_time);
-                let interest =
-                    Self::calculate_interest(loan.principal, loan.interest_rate_bps, elapsed);
-                let late_fee = Self::calculate_late_fee(env, borrower)?;
-                Ok(loan.principal + interest + late_fee)
-            }
-            None => Err(LendingError::NoOpenLoan),
-        }
+    pub fn get_repayment_amount(env: Env, loan_id: u64) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
+        let loan = Self::load_loan(&env, loan_id)?;
+        let late_fee = Self::loan_late_fee(&env, &loan)?;
+        Ok(Self::calculate_outstanding_balance(&env, &loan) + late_fee)
     }
 
     /// Calculate and emit an interest accrual event for a specific loan
-    pub fn emit_interest_accrual(env: Env, borrower: Address) -> Result<u64, LendingError> {
+    pub fn emit_interest_accrual(env: Env, loan_id: u64) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
 
-        let loan_opt: Option<LoanRecord> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Loan(borrower.clone()));
-
-        match loan_opt {
-            Some(loan) => {
-                let elapsed = env.ledger().timestamp().saturating_sub(loan.borrow_time);
-                let interest =
-                    Self::calculate_interest(loan.principal, loan.interest_rate_bps, elapsed);
-
-                env.events().publish(
-                    (symbol_short!("POOL"), symbol_short!("INTEREST")),
-                    InterestAccrualEvent {
-                        loan_id: loan.loan_id,
-                        borrower: borrower.clone(),
-                        asset: loan.asset.clone(),
-                        principal: loan.principal,
-                        interest_accrued: interest,
-                        interest_rate_bps: loan.interest_rate_bps,
-                        elapsed_seconds: elapsed,
-                        timestamp: env.ledger().timestamp(),
-                    },
-                );
+        let loan = Self::load_loan(&env, loan_id)?;
+        let elapsed = env.ledger().timestamp().saturating_sub(loan.borrow_time);
+        let interest = Self::calculate_interest(loan.principal, loan.interest_rate_bps, elapsed);
 
-                log!(
-                    &env,
-                    "Interest accrued for loan {}: {} interest on {} principal for asset {}",
-                    loan.loan_id,
-                    interest,
-                    loan.principal,
-                    loan.asset
-                );
+        env.events().publish(
+            (symbol_short!("POOL"), symbol_short!("INTEREST")),
+            InterestAccrualEvent {
+                loan_id: loan.loan_id,
+                borrower: loan.borrower.clone(),
+                asset: loan.asset.clone(),
+                principal: loan.principal,
+                interest_accrued: interest,
+                interest_rate_bps: loan.interest_rate_bps,
+                elapsed_seconds: elapsed,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
-                Ok(interest)
-            }
-            None => Err(LendingError::NoOpenLoan),
-        }
+        log!(
+            &env,
+            "Interest accrued for loan {}: {} interest on {} principal for asset {}",
+            loan.loan_id,
+            interest,
+            loan.principal,
+            loan.asset
+        );
+
+        Ok(interest)
     }
 
     /// Withdraw prioritized funds from the retained yield for a specific asset.
@@ -1539,6 +1766,7 @@ impl LendingContract {
         asset: Address,
         amount: u64,
     ) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_not_paused(&env)?;
         Self::require_initialized(&env)?;
         Self::enter_reentrancy_guard(&env)?;
@@ -1584,31 +1812,49 @@ impl LendingContract {
     /// Returns the current global pool state.
     /// Update get_pool_state to accept asset parameter
     pub fn get_pool_state(env: Env, asset: Address) -> Result<PoolState, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::get_pool(&env, &asset)
     }
 
     /// Returns the share balance of the given address for a specific asset.
     pub fn get_shares_of(env: Env, asset: Address, owner: Address) -> u64 {
+        access_control::extend_instance_ttl(&env);
         Self::get_shares(&env, &asset, &owner)
     }
 
-    /// Returns the outstanding loan record for the given borrower, if any.
-    pub fn get_loan(env: Env, borrower: Address) -> Option<LoanRecord> {
-        env.storage().persistent().get(&DataKey::Loan(borrower))
+    /// Returns every open loan held by the given borrower, oldest first.
+    pub fn get_borrower_loans(env: Env, borrower: Address) -> Vec<LoanRecord> {
+        access_control::extend_instance_ttl(&env);
+        let mut loans = Vec::new(&env);
+        for loan_id in Self::get_user_loans(&env, &borrower).iter() {
+            if let Ok(loan) = Self::load_loan(&env, loan_id) {
+                loans.push_back(loan);
+            }
+        }
+        loans
+    }
+
+    /// Aggregate collateral and debt across all of a borrower's open loans.
+    pub fn get_account_health(env: Env, borrower: Address) -> Result<AccountHealth, LendingError> {
+        access_control::extend_instance_ttl(&env);
+        Self::account_health(&env, &borrower)
     }
 
     /// Returns the loan record by unique loan ID, if any.
     pub fn get_loan_by_id(env: Env, loan_id: u64) -> Option<LoanRecord> {
+        access_control::extend_instance_ttl(&env);
         env.storage().persistent().get(&DataKey::LoanById(loan_id))
     }
 
     /// Returns all loan IDs for a given user
     pub fn get_user_loan_ids(env: Env, user: Address) -> Vec<u64> {
+        access_control::extend_instance_ttl(&env);
         Self::get_user_loans(&env, &user)
     }
 
     /// Returns the available (un-borrowed) liquidity in the pool for a specific asset.
     pub fn available_liquidity(env: Env, asset: Address) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         let pool = Self::get_pool(&env, &asset)?;
         Ok(pool.total_deposits.saturating_sub(pool.total_borrowed))
@@ -1616,6 +1862,7 @@ impl LendingContract {
 
     /// Returns the current dynamic interest rate that would be given to a new loan for a specific asset
     pub fn get_current_interest_rate(env: Env, asset: Address) -> Result<u32, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         let pool = Self::get_pool(&env, &asset)?;
         let utilization_bps = Self::get_utilization_bps(pool.total_borrowed, pool.total_deposits);
@@ -1629,85 +1876,27 @@ impl LendingContract {
     // ─── Grace Period & Late Fee Functions ────────────
 
     /// Check if a loan is currently in its grace period
-    pub fn is_in_grace_period(env: Env, borrower: Address) -> Result<bool, LendingError> {
+    pub fn is_in_grace_period(env: Env, loan_id: u64) -> Result<bool, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
-
-        let loan: LoanRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Loan(borrower))
-            .ok_or(LendingError::NoOpenLoan)?;
-
-        let pool = Self::get_pool(&env, &loan.asset)?;
-        let current_time = env.ledger().timestamp();
-        let grace_period_end = loan.due_date + pool.grace_period_seconds;
-
-        Ok(current_time <= grace_period_end)
+        let loan = Self::load_loan(&env, loan_id)?;
+        Self::loan_in_grace_period(&env, &loan)
     }
 
     /// Calculate late fees accumulated on a loan
     /// Daily late fee rate applied to days overdue after grace period
-    pub fn calculate_late_fee(env: Env, borrower: Address) -> Result<u64, LendingError> {
+    pub fn calculate_late_fee(env: Env, loan_id: u64) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
-
-        let loan: LoanRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Loan(borrower.clone()))
-            .ok_or(LendingError::NoOpenLoan)?;
-
-        let pool = Self::get_pool(&env, &loan.asset)?;
-        let current_time = env.ledger().timestamp();
-        let grace_period_end = loan.due_date + pool.grace_period_seconds;
-
-        if current_time <= grace_period_end {
-            return Ok(0);
-        }
-
-        let days_overdue = (current_time - grace_period_end) / (24 * 60 * 60);
-        if days_overdue == 0 {
-            return Ok(0);
-        }
-
-        // Look up any previously accrued late fees for this loan
-        let accrued_fees: u64 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::LateFeesAccrued(loan.loan_id))
-            .unwrap_or(0u64);
-
-        if accrued_fees > 0 {
-            return Ok(accrued_fees);
-        }
-
-        // Calculate new late fees: principal * rate_per_day * days_overdue / 10000
-        let daily_fee = ((loan.principal as u128)
-            .checked_mul(pool.late_fee_rate_bps as u128)
-            .and_then(|v| v.checked_div(10000))
-            .unwrap_or(0)) as u64;
-
-        let total_late_fee = (daily_fee as u128)
-            .checked_mul(days_overdue as u128)
-            .unwrap_or(0) as u64;
-
-        Ok(total_late_fee)
+        let loan = Self::load_loan(&env, loan_id)?;
+        Self::loan_late_fee(&env, &loan)
     }
 
     /// Get total repayment amount including principal, interest, and late fees
-    pub fn get_total_due_with_late_fees(env: Env, borrower: Address) -> Result<u64, LendingError> {
+    pub fn get_total_due_with_late_fees(env: Env, loan_id: u64) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
-
-        let loan: LoanRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Loan(borrower.clone()))
-            .ok_or(LendingError::NoOpenLoan)?;
-
-        let elapsed = env.ledger().timestamp().saturating_sub(loan.borrow_time);
-        let interest = Self::calculate_interest(loan.principal, loan.interest_rate_bps, elapsed);
-        let late_fee = Self::calculate_late_fee(env, borrower)?;
-
-        Ok(loan.principal + interest + late_fee)
+        Self::get_repayment_amount(env, loan_id)
     }
 
     // ─── Admin Functions ─────────────────────────────
@@ -1718,15 +1907,18 @@ impl LendingContract {
         admin: Address,
         token: Address,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
         env.storage()
             .persistent()
-            .set(&DataKey::WhitelistedCollateral(token), &true);
+            .set(&DataKey::WhitelistedCollateral(token.clone()), &true);
+        access_control::extend_persistent_ttl(&env, &DataKey::WhitelistedCollateral(token));
         Ok(())
     }
 
     /// Remove a collateral token from whitelist (admin only)
     pub fn remove_collateral(env: Env, admin: Address, token: Address) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
         env.storage()
             .persistent()
@@ -1736,14 +1928,90 @@ impl LendingContract {
 
     /// Check if a token is whitelisted
     pub fn is_whitelisted(env: Env, token: Address) -> bool {
+        access_control::extend_instance_ttl(&env);
         Self::is_collateral_whitelisted(&env, &token)
     }
 
     /// Get the current collateral ratio in basis points
     pub fn get_collateral_ratio_bps(env: Env) -> u32 {
+        access_control::extend_instance_ttl(&env);
         Self::get_collateral_ratio(&env)
     }
 
+    /// Price `token` with a SEP-40 oracle when checking collateral (admin or oracle).
+    /// Prices older than `max_age_secs` are rejected as stale.
+    pub fn set_price_oracle(
+        env: Env,
+        admin: Address,
+        token: Address,
+        oracle: Address,
+        max_age_secs: u64,
+    ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::Oracle)?;
+        if max_age_secs == 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+        price_oracle::set_config(
+            &env,
+            &admin,
+            &token,
+            &OracleConfig {
+                oracle,
+                max_age_secs,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stop pricing `token` with an oracle; unless it is the quote asset it
+    /// can then no longer be valued (admin or oracle).
+    pub fn remove_price_oracle(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin_or(&env, &admin, Role::Oracle)?;
+        price_oracle::remove_config(&env, &admin, &token);
+        Ok(())
+    }
+
+    /// Get the oracle configured for `token`, if any
+    pub fn get_price_oracle(env: Env, token: Address) -> Option<OracleConfig> {
+        access_control::extend_instance_ttl(&env);
+        price_oracle::get_config(&env, &token)
+    }
+
+    /// Value `token` 1:1 in the quote currency; every other token needs an
+    /// oracle to be priced (admin only).
+    pub fn set_quote_asset(env: Env, admin: Address, token: Address) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin)?;
+        price_oracle::set_quote_asset(&env, &token);
+        Ok(())
+    }
+
+    /// Get the token valued 1:1 in the quote currency
+    pub fn get_quote_asset(env: Env) -> Option<Address> {
+        access_control::extend_instance_ttl(&env);
+        price_oracle::get_quote_asset(&env)
+    }
+
+    /// Collateral value over outstanding principal for a loan, in basis points,
+    /// with both sides priced in the quote currency
+    pub fn get_health_factor(env: Env, loan_id: u64) -> Result<u32, LendingError> {
+        access_control::extend_instance_ttl(&env);
+        let loan = Self::load_loan(&env, loan_id)?;
+        oracle::collateral_ratio_bps(
+            &env,
+            &loan.collateral_token,
+            loan.collateral_amount,
+            &loan.asset,
+            loan.principal,
+        )
+    }
+
     /// Set the grace period for loans (admin only)
     /// Grace period is the time after due date during which no late fees accrue
     pub fn set_grace_period(
@@ -1752,6 +2020,7 @@ impl LendingContract {
         asset: Address,
         grace_period_seconds: u64,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
 
         let mut pool = Self::get_pool(&env, &asset)?;
@@ -1775,6 +2044,7 @@ impl LendingContract {
         asset: Address,
         late_fee_rate_bps: u32,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
 
         let mut pool = Self::get_pool(&env, &asset)?;
@@ -1792,17 +2062,20 @@ impl LendingContract {
 
     /// Get the current grace period in seconds
     pub fn get_grace_period(env: Env, asset: Address) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         let pool = Self::get_pool(&env, &asset)?;
         Ok(pool.grace_period_seconds)
     }
 
     /// Get the current late fee rate in basis points per day
     pub fn get_late_fee_rate(env: Env, asset: Address) -> Result<u32, LendingError> {
+        access_control::extend_instance_ttl(&env);
         let pool = Self::get_pool(&env, &asset)?;
         Ok(pool.late_fee_rate_bps)
     }
 
     pub fn get_flash_loan_fee(env: Env) -> u32 {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::FlashLoanFeeBps)
@@ -1810,6 +2083,7 @@ impl LendingContract {
     }
 
     pub fn set_flash_loan_fee(env: Env, admin: Address, fee_bps: u32) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
         env.storage()
             .instance()
@@ -1823,6 +2097,7 @@ impl LendingContract {
         asset: Address,
         amount: u64,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_not_paused(&env)?;
         Self::require_initialized(&env)?;
         Self::enter_reentrancy_guard(&env)?;
@@ -1897,6 +2172,7 @@ impl LendingContract {
         asset: Address,
         amount: u64,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         user.require_auth();
 
@@ -1980,6 +2256,7 @@ impl LendingContract {
         asset: Address,
         amount: u64,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         user.require_auth();
 
@@ -2058,6 +2335,7 @@ impl LendingContract {
 
     /// Claim accumulated rewards without unstaking for a specific asset
     pub fn claim_rewards(env: Env, user: Address, asset: Address) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         user.require_auth();
 
@@ -2104,6 +2382,7 @@ impl LendingContract {
 
     /// Get total staked in the reward pool for a specific asset
     pub fn get_total_staked(env: Env, asset: Address) -> u64 {
+        access_control::extend_instance_ttl(&env);
         let reward_pool: RewardPool = env
             .storage()
             .instance()
@@ -2120,6 +2399,7 @@ impl LendingContract {
 
     /// Get current reward rate for a specific asset
     pub fn get_reward_rate(env: Env, asset: Address) -> u64 {
+        access_control::extend_instance_ttl(&env);
         let reward_pool: RewardPool = env
             .storage()
             .instance()
@@ -2136,6 +2416,7 @@ impl LendingContract {
 
     /// Get user's staked balance for a specific asset
     pub fn get_staked_balance(env: Env, user: Address, asset: Address) -> u64 {
+        access_control::extend_instance_ttl(&env);
         let user_stake: UserStake = env
             .storage()
             .instance()
@@ -2151,6 +2432,7 @@ impl LendingContract {
 
     /// Get pending rewards for a user for a specific asset
     pub fn get_pending_rewards(env: Env, user: Address, asset: Address) -> u64 {
+        access_control::extend_instance_ttl(&env);
         Self::calculate_pending_rewards(&env, &user, &asset)
     }
 
@@ -2161,6 +2443,7 @@ impl LendingContract {
         asset: Address,
         new_rate: u64,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
 
         if new_rate == 0 {
@@ -2205,37 +2488,41 @@ impl LendingContract {
 
     /// Liquidate an underwater loan by paying part of the debt and seizing collateral
     /// Only callable if the loan's health factor is below a safe threshold AND grace period has expired
+    /// Open to anyone until the first `Liquidator` is assigned; then only liquidators may call it
     pub fn liquidate(
         env: Env,
         liquidator: Address,
-        borrower: Address,
+        loan_id: u64,
         amount: u64,
     ) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         Self::enter_reentrancy_guard(&env)?;
         liquidator.require_auth();
+        if !access_control::is_open_or_has_role(&env, &liquidator, Role::Liquidator) {
+            return Err(LendingError::Unauthorized);
+        }
 
-        let loan: LoanRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Loan(borrower.clone()))
-            .ok_or(LendingError::NoOpenLoan)?;
+        let mut loan = Self::load_loan(&env, loan_id)?;
 
         if amount == 0 || amount > loan.principal {
             return Err(LendingError::InvalidAmount);
         }
 
         // Check if grace period has expired before allowing liquidation
-        let is_in_grace = Self::is_in_grace_period(env.clone(), borrower.clone())?;
+        let is_in_grace = Self::loan_in_grace_period(&env, &loan)?;
         if is_in_grace {
             return Err(LendingError::InvalidAmount);
         }
 
-        // Calculate health factor (collateral / debt ratio)
-        let health_factor = (loan.collateral_amount as u128)
-            .checked_mul(10000)
-            .and_then(|v| v.checked_div(loan.principal as u128))
-            .unwrap_or(0) as u32;
+        // Calculate health factor (collateral / debt value ratio)
+        let health_factor = oracle::collateral_ratio_bps(
+            &env,
+            &loan.collateral_token,
+            loan.collateral_amount,
+            &loan.asset,
+            loan.principal,
+        )?;
 
         // Allow liquidation if health factor is below 150% (15000 basis points)
         let liquidation_threshold_bps = 15000u32;
@@ -2243,11 +2530,12 @@ impl LendingContract {
             return Err(LendingError::InvalidAmount);
         }
 
-        // Calculate collateral to seize (with small penalty/bonus to liquidator)
-        let collateral_to_seize = (amount as u128)
-            .checked_mul(15000) // 150% of the amount repaid
-            .and_then(|v| v.checked_div(10000))
-            .unwrap_or(amount as u128) as u64;
+        // Calculate collateral to seize (with small penalty/bonus to liquidator):
+        // collateral worth 150% of the amount repaid
+        let seize_value =
+            oracle::quote_value(&env, &loan.asset, amount)?.saturating_mul(15000) / 10000;
+        let collateral_to_seize =
+            oracle::token_amount_for(&env, &loan.collateral_token, seize_value)?;
 
         if collateral_to_seize > loan.collateral_amount {
             return Err(LendingError::InvalidAmount);
@@ -2272,12 +2560,38 @@ impl LendingContract {
         pool.total_deposits += amount;
         Self::set_pool(&env, &loan.asset, &pool);
 
+        // Shrink the loan by what was repaid and seized; once the principal is
+        // cleared, return any collateral left over and close the loan.
+        loan.principal -= amount;
+        loan.collateral_amount -= collateral_to_seize;
+        if loan.principal == 0 {
+            if loan.collateral_amount > 0 {
+                Self::transfer(
+                    &env,
+                    &loan.collateral_token,
+                    &contract_id,
+                    &loan.borrower,
+                    loan.collateral_amount,
+                )?;
+            }
+            Self::close_loan(&env, &loan);
+            if let Some(nft_token) = Self::get_nft_token(&env) {
+                let nft_client = LoanNFTClient::new(&env, &nft_token);
+                nft_client.burn(&env.current_contract_address(), &loan.loan_id);
+            }
+        } else {
+            env.storage()
+                .persistent()
+                .set(&DataKey::LoanById(loan.loan_id), &loan);
+            access_control::extend_persistent_ttl(&env, &DataKey::LoanById(loan.loan_id));
+        }
+
         // Emit liquidation event
         env.events().publish(
             (symbol_short!("POOL"), symbol_short!("LIQUIDATE")),
             LiquidationEvent {
                 loan_id: loan.loan_id,
-                borrower: borrower.clone(),
+                borrower: loan.borrower.clone(),
                 liquidator: liquidator.clone(),
                 asset: loan.asset.clone(),
                 amount_repaid: amount,
@@ -2311,16 +2625,13 @@ impl LendingContract {
     /// Get refinancing terms for an existing loan
     pub fn get_refinance_terms(
         env: Env,
-        borrower: Address,
+        loan_id: u64,
         new_duration_seconds: u64,
     ) -> Result<RefinanceTerms, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
 
-        let loan: LoanRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Loan(borrower.clone()))
-            .ok_or(LendingError::NoOpenLoan)?;
+        let loan = Self::load_loan(&env, loan_id)?;
 
         let outstanding_balance = Self::calculate_outstanding_balance(&env, &loan);
         let refinancing_fee = ((outstanding_balance as u128)
@@ -2354,25 +2665,23 @@ impl LendingContract {
     pub fn refinance_loan(
         env: Env,
         borrower: Address,
+        loan_id: u64,
         new_duration_seconds: u64,
     ) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         Self::enter_reentrancy_guard(&env)?;
         borrower.require_auth();
 
-        let old_loan: LoanRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Loan(borrower.clone()))
-            .ok_or(LendingError::NoOpenLoan)?;
+        let old_loan = Self::load_borrower_loan(&env, &borrower, loan_id)?;
 
         // Cannot refinance if currently in grace period or overdue
-        let is_in_grace = Self::is_in_grace_period(env.clone(), borrower.clone())?;
+        let is_in_grace = Self::loan_in_grace_period(&env, &old_loan)?;
         if !is_in_grace {
             return Err(LendingError::CannotRefinance);
         }
 
-        let terms = Self::get_refinance_terms(env.clone(), borrower.clone(), new_duration_seconds)?;
+        let terms = Self::get_refinance_terms(env.clone(), loan_id, new_duration_seconds)?;
 
         let contract_id = env.current_contract_address();
 
@@ -2386,18 +2695,12 @@ impl LendingContract {
         )?;
 
         // Close old loan
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Loan(borrower.clone()));
-        env.storage()
-            .persistent()
-            .remove(&DataKey::LoanById(old_loan.loan_id));
-        Self::remove_user_loan(&env, &borrower, old_loan.loan_id);
+        Self::close_loan(&env, &old_loan);
 
         // Burn old NFT if token is set
         if let Some(nft_token) = Self::get_nft_token(&env) {
             let nft_client = LoanNFTClient::new(&env, &nft_token);
-            nft_client.burn(&old_loan.loan_id);
+            nft_client.burn(&env.current_contract_address(), &old_loan.loan_id);
         }
 
         // Create new loan with updated terms
@@ -2416,18 +2719,13 @@ impl LendingContract {
             interest_rate_bps: terms.new_interest_rate_bps,
         };
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Loan(borrower.clone()), &new_loan);
-        env.storage()
-            .persistent()
-            .set(&DataKey::LoanById(new_loan_id), &new_loan);
-        Self::add_user_loan(&env, &borrower, new_loan_id);
+        Self::open_loan(&env, &new_loan);
 
         // Mint new NFT if token is set
         if let Some(nft_token) = Self::get_nft_token(&env) {
             let nft_client = LoanNFTClient::new(&env, &nft_token);
             nft_client.mint(
+                &env.current_contract_address(),
                 &borrower,
                 &LoanMetadata {
                     borrower: borrower.clone(),
@@ -2484,11 +2782,12 @@ impl LendingContract {
         loan_ids: Vec<u64>,
         new_duration_seconds: u64,
     ) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         Self::enter_reentrancy_guard(&env)?;
         borrower.require_auth();
 
-        if loan_ids.is_empty() || loan_ids.len() > 10 {
+        if loan_ids.is_empty() || loan_ids.len() > MAX_LOANS_PER_BORROWER {
             return Err(LendingError::InvalidAmount);
         }
 
@@ -2500,15 +2799,7 @@ impl LendingContract {
 
         // Validate all loans belong to borrower and calculate totals
         for loan_id in loan_ids.iter() {
-            let loan: LoanRecord = env
-                .storage()
-                .persistent()
-                .get(&DataKey::LoanById(loan_id))
-                .ok_or(LendingError::LoanNotFound)?;
-
-            if loan.borrower != borrower {
-                return Err(LendingError::Unauthorized);
-            }
+            let loan = Self::load_borrower_loan(&env, &borrower, loan_id)?;
 
             // Check if this specific loan is overdue (cannot consolidate overdue loans)
             let loan_grace_end =
@@ -2557,18 +2848,12 @@ impl LendingContract {
 
         // Remove old loans
         for loan in old_loans.iter() {
-            env.storage()
-                .persistent()
-                .remove(&DataKey::Loan(loan.borrower.clone()));
-            env.storage()
-                .persistent()
-                .remove(&DataKey::LoanById(loan.loan_id));
-            Self::remove_user_loan(&env, &borrower, loan.loan_id);
+            Self::close_loan(&env, &loan);
 
             // Burn old NFTs
             if let Some(nft_token) = Self::get_nft_token(&env) {
                 let nft_client = LoanNFTClient::new(&env, &nft_token);
-                nft_client.burn(&loan.loan_id);
+                nft_client.burn(&env.current_contract_address(), &loan.loan_id);
             }
         }
 
@@ -2594,18 +2879,13 @@ impl LendingContract {
             interest_rate_bps: new_interest_rate_bps,
         };
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Loan(borrower.clone()), &new_loan);
-        env.storage()
-            .persistent()
-            .set(&DataKey::LoanById(new_loan_id), &new_loan);
-        Self::add_user_loan(&env, &borrower, new_loan_id);
+        Self::open_loan(&env, &new_loan);
 
         // Mint new NFT
         if let Some(nft_token) = Self::get_nft_token(&env) {
             let nft_client = LoanNFTClient::new(&env, &nft_token);
             nft_client.mint(
+                &env.current_contract_address(),
                 &borrower,
                 &LoanMetadata {
                     borrower: borrower.clone(),
@@ -2656,9 +2936,11 @@ impl LendingContract {
     pub fn split_loan(
         env: Env,
         borrower: Address,
+        loan_id: u64,
         split_amounts: Vec<u64>,
         new_duration_seconds: u64,
     ) -> Result<Vec<u64>, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         Self::enter_reentrancy_guard(&env)?;
         borrower.require_auth();
@@ -2667,18 +2949,20 @@ impl LendingContract {
             return Err(LendingError::InvalidAmount);
         }
 
-        let old_loan: LoanRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Loan(borrower.clone()))
-            .ok_or(LendingError::NoOpenLoan)?;
+        let old_loan = Self::load_borrower_loan(&env, &borrower, loan_id)?;
 
         // Check if loan is in good standing
-        let is_in_grace = Self::is_in_grace_period(env.clone(), borrower.clone())?;
+        let is_in_grace = Self::loan_in_grace_period(&env, &old_loan)?;
         if !is_in_grace {
             return Err(LendingError::CannotRefinance);
         }
 
+        // The split replaces one loan with several
+        let open_loans = Self::get_user_loans(&env, &borrower).len();
+        if open_loans - 1 + split_amounts.len() > MAX_LOANS_PER_BORROWER {
+            return Err(LendingError::TooManyLoans);
+        }
+
         let outstanding = Self::calculate_outstanding_balance(&env, &old_loan);
         let total_split_amount: u64 = split_amounts.iter().sum();
 
@@ -2696,18 +2980,12 @@ impl LendingContract {
         Self::transfer(&env, &old_loan.asset, &borrower, &contract_id, split_fee)?;
 
         // Remove old loan
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Loan(borrower.clone()));
-        env.storage()
-            .persistent()
-            .remove(&DataKey::LoanById(old_loan.loan_id));
-        Self::remove_user_loan(&env, &borrower, old_loan.loan_id);
+        Self::close_loan(&env, &old_loan);
 
         // Burn old NFT
         if let Some(nft_token) = Self::get_nft_token(&env) {
             let nft_client = LoanNFTClient::new(&env, &nft_token);
-            nft_client.burn(&old_loan.loan_id);
+            nft_client.burn(&env.current_contract_address(), &old_loan.loan_id);
         }
 
         // Create new split loans
@@ -2744,20 +3022,13 @@ impl LendingContract {
                 interest_rate_bps: new_interest_rate_bps,
             };
 
-            // For split loans, only store the last one as the primary loan
-            // but all loans are accessible via LoanById
-            env.storage()
-                .persistent()
-                .set(&DataKey::Loan(borrower.clone()), &new_loan);
-            env.storage()
-                .persistent()
-                .set(&DataKey::LoanById(new_loan_id), &new_loan);
-            Self::add_user_loan(&env, &borrower, new_loan_id);
+            Self::open_loan(&env, &new_loan);
 
             // Mint NFT for each new loan
             if let Some(nft_token) = Self::get_nft_token(&env) {
                 let nft_client = LoanNFTClient::new(&env, &nft_token);
                 nft_client.mint(
+                    &env.current_contract_address(),
                     &borrower,
                     &LoanMetadata {
                         borrower: borrower.clone(),
@@ -2817,6 +3088,7 @@ impl LendingContract {
         asset: Address, // Added
         reserve_factor_bps: u32,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         admin.require_auth();
 
         // Verify admin
@@ -2846,16 +3118,19 @@ impl LendingContract {
     }
 
     pub fn get_reserve_factor(env: Env, asset: Address) -> Result<u32, LendingError> {
+        access_control::extend_instance_ttl(&env);
         let pool = Self::get_pool(&env, &asset)?;
         Ok(pool.reserve_factor_bps)
     }
 
     pub fn get_reserve_balance(env: Env, asset: Address) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         let pool = Self::get_pool(&env, &asset)?;
         Ok(pool.bad_debt_reserve)
     }
 
     pub fn get_protocol_revenue(env: Env, asset: Address) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         let pool = Self::get_pool(&env, &asset)?;
         Ok(pool.total_protocol_revenue)
     }
@@ -2866,6 +3141,7 @@ impl LendingContract {
         asset: Address,
         amount: u64,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         admin.require_auth();
 
         // Verify admin
@@ -2904,6 +3180,7 @@ impl LendingContract {
         amount: u64,
         insurance_fund: Address,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         admin.require_auth();
 
         // Verify admin
@@ -2949,12 +3226,8 @@ impl LendingContract {
 
     /// Accrue interest and split between depositors and protocol
     pub fn accrue_interest_with_reserve(env: Env, loan_id: u64) -> Result<(), LendingError> {
-        let loan_key = DataKey::LoanById(loan_id);
-        let loan = env
-            .storage()
-            .instance()
-            .get::<_, LoanRecord>(&loan_key)
-            .ok_or(LendingError::LoanNotFound)?; // Loan not found
+        access_control::extend_instance_ttl(&env);
+        let loan = Self::load_loan(&env, loan_id)?;
 
         let elapsed = env.ledger().timestamp().saturating_sub(loan.borrow_time);
         let total_interest =
@@ -3011,6 +3284,7 @@ impl LendingContract {
 
     /// Get insurance premium for a given loan amount
     pub fn get_insurance_premium(env: Env, loan_amount: u64) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::init_insurance_fund_if_needed(&env);
         let premium_rate_bps: u32 = env
             .storage()
@@ -3032,7 +3306,7 @@ impl LendingContract {
         admin: Address,
         premium_rate_bps: u32,
     ) -> Result<(), LendingError> {
-        admin.require_auth();
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
 
         if premium_rate_bps > 10000 {
@@ -3059,16 +3333,12 @@ impl LendingContract {
         borrower: Address,
         loan_id: u64,
     ) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         borrower.require_auth();
         Self::init_insurance_fund_if_needed(&env);
 
         // Get loan record
-        let loan_key = DataKey::LoanById(loan_id);
-        let loan = env
-            .storage()
-            .instance()
-            .get::<_, LoanRecord>(&loan_key)
-            .ok_or(LendingError::LoanNotFound)?;
+        let loan = Self::load_loan(&env, loan_id)?;
 
         // Verify borrower matches
         if loan.borrower != borrower {
@@ -3107,8 +3377,10 @@ impl LendingContract {
             &(premium as i128),
         );
 
-        // Coverage is 100% of principal
-        let coverage_amount = loan.principal;
+        let coverage_amount = (loan.principal as u128)
+            .checked_mul(INSURANCE_CLAIM_PAYBACK_BPS as u128)
+            .and_then(|v| v.checked_div(10000u128))
+            .ok_or(LendingError::InvalidAmount)? as u64;
 
         // Create insurance record
         let insurance = LoanInsurance {
@@ -3168,6 +3440,7 @@ impl LendingContract {
 
     /// Check if a loan has active insurance
     pub fn is_loan_insured(env: Env, loan_id: u64) -> Result<bool, LendingError> {
+        access_control::extend_instance_ttl(&env);
         let insurance_key = DataKey::Insurance(loan_id);
         let insurance: Option<LoanInsurance> = env.storage().instance().get(&insurance_key);
 
@@ -3182,6 +3455,7 @@ impl LendingContract {
 
     /// Get insurance coverage amount for a loan
     pub fn get_insurance_coverage(env: Env, loan_id: u64) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         let insurance_key = DataKey::Insurance(loan_id);
         let insurance: Option<LoanInsurance> = env.storage().instance().get(&insurance_key);
 
@@ -3199,12 +3473,14 @@ impl LendingContract {
         env: Env,
         loan_id: u64,
     ) -> Result<Option<LoanInsurance>, LendingError> {
+        access_control::extend_instance_ttl(&env);
         let insurance_key = DataKey::Insurance(loan_id);
         Ok(env.storage().instance().get(&insurance_key))
     }
 
     /// Claim insurance when loan defaults
     pub fn claim_insurance(env: Env, loan_id: u64) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         let insurance_key = DataKey::Insurance(loan_id);
         let mut insurance: LoanInsurance = env
             .storage()
@@ -3217,24 +3493,23 @@ impl LendingContract {
             return Err(LendingError::InsuranceAlreadyClaimed);
         }
 
-        let current_time = env.ledger().timestamp();
-        if current_time >= insurance.expires_at {
-            return Err(LendingError::InsuranceExpired);
-        }
-
         // Get loan to verify it exists and is in default
-        let loan_key = DataKey::LoanById(loan_id);
-        let loan = env
-            .storage()
-            .instance()
-            .get::<_, LoanRecord>(&loan_key)
-            .ok_or(LendingError::LoanNotFound)?;
+        let loan = Self::load_loan(&env, loan_id)?;
 
         // Verify loan is past due
+        let current_time = env.ledger().timestamp();
         if current_time <= loan.due_date {
             return Err(LendingError::InvalidAmount);
         }
 
+        // Coverage runs until the due date it was bought for; a default on
+        // a covered loan can be claimed for a short window afterwards.
+        if loan.due_date > insurance.expires_at
+            || current_time > insurance.expires_at + INSURANCE_CLAIM_WINDOW_SECONDS
+        {
+            return Err(LendingError::InsuranceExpired);
+        }
+
         // Get insurance fund and verify sufficient balance
         let mut fund: InsuranceFund = env
             .storage()
@@ -3257,13 +3532,8 @@ impl LendingContract {
         fund.available_balance = fund.available_balance.saturating_sub(claim_amount);
         env.storage().instance().set(&DataKey::InsuranceFund, &fund);
 
-        // Transfer claim amount to contract (funds holder for protocol)
-        // In a real system, this would be transferred to a claims reserve
-        let token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let token_client = token::Client::new(&env, &token);
-
-        // Transfer from contract to claims reserve (in this case, just update balance tracking)
-        // The actual transfer would happen when liquidation processes the claim
+        // The claimed amount stays in the contract; only the fund's balance
+        // tracking changes. The payout happens when liquidation processes the claim.
 
         // Emit event
         env.events().publish(
@@ -3294,6 +3564,7 @@ impl LendingContract {
         borrower: Address,
         loan_id: u64,
     ) -> Result<u64, LendingError> {
+        access_control::extend_instance_ttl(&env);
         borrower.require_auth();
 
         let insurance_key = DataKey::Insurance(loan_id);
@@ -3386,6 +3657,7 @@ impl LendingContract {
 
     /// Get insurance fund state
     pub fn get_insurance_fund_state(env: Env) -> Result<InsuranceFund, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::init_insurance_fund_if_needed(&env);
         Ok(env
             .storage()
@@ -3404,7 +3676,7 @@ impl LendingContract {
         admin: Address,
         amount: u64,
     ) -> Result<(), LendingError> {
-        admin.require_auth();
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
 
         if amount == 0 {
@@ -3448,7 +3720,7 @@ impl LendingContract {
         admin: Address,
         amount: u64,
     ) -> Result<(), LendingError> {
-        admin.require_auth();
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
 
         if amount == 0 {
@@ -3492,6 +3764,7 @@ impl LendingContract {
         admin: Address,
         contract: Address,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
         env.storage()
             .instance()
@@ -3510,6 +3783,7 @@ impl LendingContract {
     }
 
     pub fn get_inheritance_contract(env: Env) -> Option<Address> {
+        access_control::extend_instance_ttl(&env);
         env.storage().instance().get(&DataKey::InheritanceContract)
     }
 
@@ -3518,6 +3792,7 @@ impl LendingContract {
         admin: Address,
         contract: Address,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
         env.storage()
             .instance()
@@ -3536,6 +3811,7 @@ impl LendingContract {
     }
 
     pub fn get_governance_contract(env: Env) -> Option<Address> {
+        access_control::extend_instance_ttl(&env);
         env.storage().instance().get(&DataKey::GovernanceContract)
     }
 
@@ -3551,6 +3827,7 @@ impl LendingContract {
         slope2_bps: u32,
         reserve_factor_bps: u32,
     ) -> Result<(), LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_admin(&env, &admin)?;
 
         if optimal_utilization_bps == 0 || optimal_utilization_bps >= 10000 {
@@ -3588,6 +3865,7 @@ impl LendingContract {
 
     /// Get the base interest rate from the rate model, falling back to pool state.
     pub fn get_base_rate(env: Env) -> Result<u32, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         if let Some(model) = env
             .storage()
@@ -3602,6 +3880,7 @@ impl LendingContract {
 
     /// Get the optimal (target) utilization rate from the rate model.
     pub fn get_optimal_utilization(env: Env) -> Result<u32, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         if let Some(model) = env
             .storage()
@@ -3617,6 +3896,7 @@ impl LendingContract {
 
     /// Get slope1 — the rate increase per unit utilization before optimal utilization.
     pub fn get_slope1(env: Env) -> Result<u32, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         if let Some(model) = env
             .storage()
@@ -3632,6 +3912,7 @@ impl LendingContract {
 
     /// Get slope2 — the steep rate increase per unit utilization above optimal utilization.
     pub fn get_slope2(env: Env) -> Result<u32, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         if let Some(model) = env
             .storage()
@@ -3642,12 +3923,15 @@ impl LendingContract {
         }
         // Fallback: slope2 is 10× slope1 when not configured
         let token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        Ok(Self::get_pool(&env, &token)?.multiplier_bps.saturating_mul(10))
+        Ok(Self::get_pool(&env, &token)?
+            .multiplier_bps
+            .saturating_mul(10))
     }
 
     /// Get the current borrow rate using the two-slope model if configured,
     /// or the legacy linear model otherwise.
     pub fn get_borrow_rate(env: Env) -> Result<u32, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         let token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
         let pool = Self::get_pool(&env, &token)?;
@@ -3671,6 +3955,7 @@ impl LendingContract {
     /// Get the current supply (deposit) rate.
     /// supply_rate = borrow_rate × utilization × (1 − reserve_factor)
     pub fn get_supply_rate(env: Env) -> Result<u32, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         let token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
         let pool = Self::get_pool(&env, &token)?;
@@ -3701,6 +3986,7 @@ impl LendingContract {
     /// Simulate the borrow rate at an arbitrary utilization level (in basis points).
     /// Useful for modelling rate impact before taking on or repaying debt.
     pub fn simulate_rate(env: Env, utilization_bps: u32) -> Result<u32, LendingError> {
+        access_control::extend_instance_ttl(&env);
         Self::require_initialized(&env)?;
         if let Some(model) = env
             .storage()
@@ -3748,6 +4034,7 @@ impl LendingContract {
     }
 
     pub fn verify_plan_ownership(env: Env, plan_id: u64, caller: Address) -> bool {
+        access_control::extend_instance_ttl(&env);
         if let Some(inheritance_contract) = Self::get_inheritance_contract(env.clone()) {
             let client = InheritanceContractClient::new(&env, &inheritance_contract);
             client.verify_plan_ownership(&plan_id, &caller)
@@ -0,0 +1,45 @@
+//! Pool-side wrappers around the shared `price-oracle` crate: amounts are
+//! `u64` token units and failures are reported as `LendingError`.
+
+use crate::LendingError;
+use price_oracle::OracleError;
+use soroban_sdk::{Address, Env};
+
+impl From<OracleError> for LendingError {
+    fn from(err: OracleError) -> Self {
+        match err {
+            OracleError::PriceUnavailable => LendingError::PriceUnavailable,
+            OracleError::StalePrice => LendingError::StalePrice,
+            OracleError::Overflow => LendingError::ValuationOverflow,
+        }
+    }
+}
+
+/// Value `amount` of `token` in the quote currency.
+pub fn quote_value(env: &Env, token: &Address, amount: u64) -> Result<u128, LendingError> {
+    Ok(price_oracle::quote_value(env, token, amount as u128)?)
+}
+
+/// Amount of `token` worth `value` in the quote currency, rounded down.
+pub fn token_amount_for(env: &Env, token: &Address, value: u128) -> Result<u64, LendingError> {
+    let amount = price_oracle::token_amount_for(env, token, value)?;
+    u64::try_from(amount).map_err(|_| LendingError::ValuationOverflow)
+}
+
+/// Collateral value over debt value, in basis points. Zero debt is reported
+/// as `u32::MAX`.
+pub fn collateral_ratio_bps(
+    env: &Env,
+    collateral_token: &Address,
+    collateral_amount: u64,
+    debt_asset: &Address,
+    debt_amount: u64,
+) -> Result<u32, LendingError> {
+    Ok(price_oracle::collateral_ratio_bps(
+        env,
+        collateral_token,
+        collateral_amount as u128,
+        debt_asset,
+        debt_amount as u128,
+    )?)
+}
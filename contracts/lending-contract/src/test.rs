@@ -40,10 +40,23 @@ fn setup(env: &Env) -> (LendingContractClient<'_>, Address, Address, Address) {
 
     // Whitelist collateral token
     client.whitelist_collateral(&admin, &collateral_addr);
+    price_at_par(env, &client, &admin, &collateral_addr);
 
     (client, token_addr, collateral_addr, admin)
 }
 
+/// Price `token` at 1.0 quote unit with a feed that never goes stale, so
+/// tests that move the ledger clock keep a usable collateral price.
+fn price_at_par(env: &Env, client: &LendingContractClient<'_>, admin: &Address, token: &Address) {
+    let oracle_id = env.register_contract(None, mock_oracle::MockOracle);
+    mock_oracle::MockOracleClient::new(env, &oracle_id).set_price(
+        &mock_oracle::Asset::Stellar(token.clone()),
+        &10_000_000,
+        &env.ledger().timestamp(),
+    );
+    client.set_price_oracle(admin, token, &oracle_id, &u64::MAX);
+}
+
 // ─────────────────────────────────────────────────
 // Tests
 // ─────────────────────────────────────────────────
@@ -74,7 +87,7 @@ fn test_deposit_mints_shares() {
     assert_eq!(shares, 1000u64);
     assert_eq!(client.get_shares_of(&token_addr, &depositor), 1000u64);
 
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_deposits, 2000);
     assert_eq!(pool.total_shares, 2000);
     assert_eq!(pool.total_borrowed, 0);
@@ -99,7 +112,7 @@ fn test_second_deposit_proportional_shares() {
     let shares2 = client.deposit(&depositor2, &token_addr, &500u64);
     assert_eq!(shares2, 500u64);
 
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_deposits, 2500);
     assert_eq!(pool.total_shares, 2500);
 }
@@ -113,11 +126,11 @@ fn test_withdraw_burns_shares_and_returns_tokens() {
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 10_000);
 
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
     let balance_before = tok_client(&env, &token_addr).balance(&depositor);
 
     // Withdraw 500 shares → should get 500 tokens back
-    let returned = client.withdraw(&depositor, &token_addr, &500u64).unwrap();
+    let returned = client.withdraw(&depositor, &token_addr, &500u64);
     assert_eq!(returned, 500u64);
     assert_eq!(
         tok_client(&env, &token_addr).balance(&depositor),
@@ -125,7 +138,7 @@ fn test_withdraw_burns_shares_and_returns_tokens() {
     );
     assert_eq!(client.get_shares_of(&token_addr, &depositor), 500u64);
 
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_deposits, 1500);
     assert_eq!(pool.total_shares, 1500);
 }
@@ -138,7 +151,7 @@ fn test_withdraw_fails_not_enough_shares() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 10_000);
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
 
     // Try to withdraw more shares than owned
     let result = client.try_withdraw(&depositor, &token_addr, &2000u64);
@@ -156,7 +169,7 @@ fn test_borrow_reduces_available_liquidity() {
     mint_to(&env, &collateral_addr, &borrower, 100_000);
     mint_to(&env, &token_addr, &depositor, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 10_000);
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
 
     let borrow_amount = 400u64;
     let balance_before = tok_client(&env, &token_addr).balance(&borrower);
@@ -167,7 +180,7 @@ fn test_borrow_reduces_available_liquidity() {
         &collateral_addr,
         &600u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap(); // 30 days
+    ); // 30 days
 
     assert!(loan_id > 0);
     assert_eq!(
@@ -175,11 +188,11 @@ fn test_borrow_reduces_available_liquidity() {
         balance_before + 400
     );
 
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_borrowed, 400);
     assert_eq!(pool.total_deposits, 2000);
 
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 1600u64);
+    assert_eq!(client.available_liquidity(&token_addr), 1600u64);
 }
 
 #[test]
@@ -190,7 +203,7 @@ fn test_borrow_fails_if_insufficient_liquidity() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 10_000);
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
 
     let result = client.try_borrow(
         &depositor,
@@ -204,7 +217,7 @@ fn test_borrow_fails_if_insufficient_liquidity() {
 }
 
 #[test]
-fn test_borrow_fails_with_existing_loan() {
+fn test_borrower_can_hold_multiple_loans() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, token_addr, collateral_addr, _admin) = setup(&env);
@@ -213,17 +226,96 @@ fn test_borrow_fails_with_existing_loan() {
     let borrower = Address::generate(&env);
     mint_to(&env, &collateral_addr, &borrower, 100_000);
     mint_to(&env, &token_addr, &depositor, 10_000);
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
-    client.borrow(
+    mint_to(&env, &token_addr, &borrower, 10_000);
+    client.deposit(&depositor, &token_addr, &2000u64);
+    let loan_id1 = client.borrow(
         &borrower,
         &token_addr,
         &200u64,
         &collateral_addr,
         &300u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
+    let loan_id2 = client.borrow(
+        &borrower,
+        &token_addr,
+        &100u64,
+        &collateral_addr,
+        &150u64,
+        &(30 * 24 * 60 * 60),
+    );
+    assert_ne!(loan_id1, loan_id2);
 
-    // Second borrow should fail
+    let loans = client.get_borrower_loans(&borrower);
+    assert_eq!(loans.len(), 2);
+    assert_eq!(loans.get(0).unwrap().principal, 200u64);
+    assert_eq!(loans.get(1).unwrap().principal, 100u64);
+    assert_eq!(client.get_pool_state(&token_addr).total_borrowed, 300u64);
+
+    // Repaying one loan leaves the other open
+    client.repay(&borrower, &loan_id1);
+    assert!(client.get_loan_by_id(&loan_id1).is_none());
+    assert_eq!(client.get_loan_by_id(&loan_id2).unwrap().principal, 100u64);
+    assert_eq!(client.get_user_loan_ids(&borrower).len(), 1);
+}
+
+#[test]
+fn test_repay_rejects_other_borrowers_loan() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, _admin) = setup(&env);
+
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    mint_to(&env, &collateral_addr, &borrower, 1_000);
+    mint_to(&env, &token_addr, &depositor, 10_000);
+    mint_to(&env, &token_addr, &stranger, 10_000);
+    client.deposit(&depositor, &token_addr, &2000u64);
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &200u64,
+        &collateral_addr,
+        &300u64,
+        &(30 * 24 * 60 * 60),
+    );
+
+    let result = client.try_repay(&stranger, &loan_id);
+    assert_eq!(result, Err(Ok(LendingError::Unauthorized)));
+    assert!(client.get_loan_by_id(&loan_id).is_some());
+}
+
+#[test]
+fn test_aggregate_collateral_limits_new_loans() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, _admin) = setup(&env);
+
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    mint_to(&env, &collateral_addr, &borrower, 100_000);
+    mint_to(&env, &token_addr, &depositor, 10_000);
+    client.deposit(&depositor, &token_addr, &5000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1000u64,
+        &collateral_addr,
+        &1500u64,
+        &(30 * 24 * 60 * 60),
+    );
+
+    // A year passes: accrued interest pushes the first loan below 150%
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 365 * 24 * 60 * 60);
+    let health = client.get_account_health(&borrower);
+    assert_eq!(health.loan_count, 1);
+    assert_eq!(health.collateral_value, 1500u128);
+    assert!(health.debt_value > 1000u128);
+    assert!(health.health_factor < 15000u32);
+
+    // A new loan that is exactly 150% on its own cannot cover the shortfall
     let result = client.try_borrow(
         &borrower,
         &token_addr,
@@ -232,7 +324,54 @@ fn test_borrow_fails_with_existing_loan() {
         &150u64,
         &(30 * 24 * 60 * 60),
     );
-    assert!(result.is_err());
+    assert_eq!(result, Err(Ok(LendingError::InsufficientCollateral)));
+
+    // Extra collateral on the new loan restores the aggregate ratio
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &100u64,
+        &collateral_addr,
+        &1000u64,
+        &(30 * 24 * 60 * 60),
+    );
+    let health = client.get_account_health(&borrower);
+    assert_eq!(health.loan_count, 2);
+    assert_eq!(health.collateral_value, 2500u128);
+    assert!(health.health_factor >= 15000u32);
+}
+
+#[test]
+fn test_borrow_fails_with_too_many_loans() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, _admin) = setup(&env);
+
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    mint_to(&env, &collateral_addr, &borrower, 100_000);
+    mint_to(&env, &token_addr, &depositor, 10_000);
+    client.deposit(&depositor, &token_addr, &5000u64);
+    for _ in 0..10 {
+        client.borrow(
+            &borrower,
+            &token_addr,
+            &100u64,
+            &collateral_addr,
+            &150u64,
+            &(30 * 24 * 60 * 60),
+        );
+    }
+
+    let result = client.try_borrow(
+        &borrower,
+        &token_addr,
+        &100u64,
+        &collateral_addr,
+        &150u64,
+        &(30 * 24 * 60 * 60),
+    );
+    assert_eq!(result, Err(Ok(LendingError::TooManyLoans)));
 }
 
 #[test]
@@ -247,28 +386,28 @@ fn test_repay_restores_liquidity() {
     mint_to(&env, &token_addr, &depositor, 10_000);
     mint_to(&env, &token_addr, &borrower, 10_000); // pre-fund borrower for repayment
 
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
-    client.borrow(
+    client.deposit(&depositor, &token_addr, &2000u64);
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &400u64,
         &collateral_addr,
         &600u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
 
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 1600u64);
+    assert_eq!(client.available_liquidity(&token_addr), 1600u64);
 
-    let repaid = client.repay(&borrower).unwrap();
+    let repaid = client.repay(&borrower, &loan_id);
     assert_eq!(repaid, 400u64);
 
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_borrowed, 0);
     assert_eq!(pool.total_deposits, 2000);
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 2000u64);
+    assert_eq!(client.available_liquidity(&token_addr), 2000u64);
 
     // Loan should be gone
-    let loan = client.get_loan(&borrower);
+    let loan = client.get_loan_by_id(&loan_id);
     assert!(loan.is_none());
 }
 
@@ -278,8 +417,8 @@ fn test_repay_fails_with_no_loan() {
     env.mock_all_auths();
     let (client, _token_addr, _collateral_addr, admin) = setup(&env);
 
-    let result = client.try_repay(&admin);
-    assert!(result.is_err());
+    let result = client.try_repay(&admin, &1u64);
+    assert_eq!(result, Err(Ok(LendingError::LoanNotFound)));
 }
 
 #[test]
@@ -293,22 +432,24 @@ fn test_withdraw_fails_if_funds_are_borrowed() {
     mint_to(&env, &collateral_addr, &borrower, 100_000);
     mint_to(&env, &token_addr, &depositor, 10_000);
 
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
-    client.borrow(
+    client.deposit(&depositor, &token_addr, &2000u64);
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &1900u64,
         &collateral_addr,
         &2850u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap(); // only 100 tokens left un-borrowed
+    ); // only 100 tokens left un-borrowed
 
     // Depositor tries to withdraw 500 → only 100 available
     let result = client.try_withdraw(&depositor, &token_addr, &500u64);
     assert!(result.is_err());
 
     // Can still withdraw 100's worth of shares
-    assert!(client.try_withdraw(&depositor, &token_addr, &100u64).is_ok());
+    assert!(client
+        .try_withdraw(&depositor, &token_addr, &100u64)
+        .is_ok());
 }
 
 #[test]
@@ -323,23 +464,23 @@ fn test_available_liquidity_before_and_after() {
     mint_to(&env, &token_addr, &depositor, 10_000);
     mint_to(&env, &token_addr, &borrower, 10_000);
 
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 0u64);
+    assert_eq!(client.available_liquidity(&token_addr), 0u64);
 
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 2000u64);
+    client.deposit(&depositor, &token_addr, &2000u64);
+    assert_eq!(client.available_liquidity(&token_addr), 2000u64);
 
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &1500u64,
         &collateral_addr,
         &2250u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 500u64);
+    );
+    assert_eq!(client.available_liquidity(&token_addr), 500u64);
 
-    client.repay(&borrower).unwrap();
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 2000u64);
+    client.repay(&borrower, &loan_id);
+    assert_eq!(client.available_liquidity(&token_addr), 2000u64);
 }
 
 #[test]
@@ -349,8 +490,8 @@ fn test_get_loan_returns_none_when_no_loan() {
     let (client, _token_addr, _collateral_addr, _admin) = setup(&env);
 
     let no_loan_addr = Address::generate(&env);
-    let loan = client.get_loan(&no_loan_addr);
-    assert!(loan.is_none());
+    assert!(client.get_borrower_loans(&no_loan_addr).is_empty());
+    assert!(client.get_loan_by_id(&1u64).is_none());
 }
 
 #[test]
@@ -364,7 +505,7 @@ fn test_get_loan_returns_record_when_active() {
     mint_to(&env, &collateral_addr, &borrower, 100_000);
     mint_to(&env, &token_addr, &depositor, 10_000);
 
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
     let loan_id = client.borrow(
         &borrower,
         &token_addr,
@@ -372,9 +513,9 @@ fn test_get_loan_returns_record_when_active() {
         &collateral_addr,
         &450u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
 
-    let loan = client.get_loan(&borrower).unwrap();
+    let loan = client.get_loan_by_id(&loan_id).unwrap();
     assert_eq!(loan.loan_id, loan_id);
     assert_eq!(loan.principal, 300u64);
     assert_eq!(loan.borrower, borrower);
@@ -390,13 +531,20 @@ fn test_get_loan_returns_record_when_active() {
 fn test_invalid_amounts_rejected() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _token_addr, collateral_addr, admin) = setup(&env);
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
 
     let depositor = Address::generate(&env);
     assert!(client.try_deposit(&depositor, &token_addr, &0u64).is_err());
     assert!(client.try_withdraw(&depositor, &token_addr, &0u64).is_err());
     assert!(client
-        .try_borrow(&admin, &token_addr, &0u64, &collateral_addr, &0u64, &(30 * 24 * 60 * 60))
+        .try_borrow(
+            &admin,
+            &token_addr,
+            &0u64,
+            &collateral_addr,
+            &0u64,
+            &(30 * 24 * 60 * 60)
+        )
         .is_err());
 }
 #[test]
@@ -411,8 +559,10 @@ fn test_rounding_loss_exploit_prevented() {
     mint_to(&env, &token_addr, &victim, 10_000);
 
     // Attacker deposits minimum allowed to get some shares
-    assert!(client.try_deposit(&attacker, &token_addr, &1000u64).is_err());
-    let attack_shares = client.deposit(&attacker, &token_addr, &1001u64).unwrap();
+    assert!(client
+        .try_deposit(&attacker, &token_addr, &1000u64)
+        .is_err());
+    let attack_shares = client.deposit(&attacker, &token_addr, &1001u64);
     assert_eq!(attack_shares, 1);
 
     // Victim tries to deposit an amount that would yield 0 shares
@@ -434,21 +584,21 @@ fn test_interest_accrual() {
     mint_to(&env, &token_addr, &borrower, 100_000);
 
     // 1. Deposit 10,000 → 10,000 shares
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // 2. Borrow 5,000
     // Utilization = 5000 / 10000 = 50%.
     // Rate = 5% + (50% * 20%) = 15% (1500 bps)
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &5_000u64,
         &collateral_addr,
         &7500u64,
         &(365 * 24 * 60 * 60),
-    ).unwrap(); // 1 year duration
+    ); // 1 year duration
 
-    let current_rate = client.get_current_interest_rate(&token_addr).unwrap();
+    let current_rate = client.get_current_interest_rate(&token_addr);
     assert_eq!(current_rate, 1500u32);
 
     // 3. Jump time by 1 year (31,536,000 seconds)
@@ -456,14 +606,14 @@ fn test_interest_accrual() {
         .set_timestamp(env.ledger().timestamp() + 31_536_000);
 
     // 4. Expected interest: 5,000 * 0.15 * 1 year = 750
-    let repayment_amount = client.get_repayment_amount(&borrower).unwrap();
+    let repayment_amount = client.get_repayment_amount(&loan_id);
     assert_eq!(repayment_amount, 5_750u64);
 
     // 5. Repay
-    client.repay(&borrower).unwrap();
+    client.repay(&borrower, &loan_id);
 
     // 6. Verify pool state
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     // total_deposits should be 10,000 (initial) + 675 (90% of 750 interest) = 10,675
     assert_eq!(pool.total_deposits, 10_675);
     assert_eq!(pool.total_borrowed, 0);
@@ -473,7 +623,7 @@ fn test_interest_accrual() {
     // 7. Verify depositor can withdraw more than they put in
     // shares = 9,000, pool_shares = 10,000, pool_deposits = 10,675
     // amount = 9,000 * 10,675 / 10,000 = 9,607
-    let withdrawn = client.withdraw(&depositor, &token_addr, &9_000u64).unwrap();
+    let withdrawn = client.withdraw(&depositor, &token_addr, &9_000u64);
     assert_eq!(withdrawn, 9_607u64);
 }
 
@@ -489,19 +639,19 @@ fn test_interest_precision_short_time() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
-    client.borrow(
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &5_000u64,
         &collateral_addr,
         &7500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap(); // 30 days
+    ); // 30 days
 
     env.ledger().set_timestamp(env.ledger().timestamp() + 3600);
 
-    let repayment_amount = client.get_repayment_amount(&borrower).unwrap();
+    let repayment_amount = client.get_repayment_amount(&loan_id);
     assert_eq!(repayment_amount, 5_000u64);
 }
 
@@ -513,28 +663,28 @@ fn test_dynamic_interest_rate_increases_with_utilization() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 100_000);
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // At 0 utilization, rate should be base rate (500)
-    assert_eq!(client.get_current_interest_rate(&token_addr).unwrap(), 500u32);
+    assert_eq!(client.get_current_interest_rate(&token_addr), 500u32);
 
     let borrower1 = Address::generate(&env);
     mint_to(&env, &collateral_addr, &borrower1, 100_000);
     // Borrow 2,000 (20% utilization)
     // Dynamic rate should be 500 + (2000 * 2000 / 10000) = 500 + 400 = 900
-    client.borrow(
+    let loan_id1 = client.borrow(
         &borrower1,
         &token_addr,
         &2_000u64,
         &collateral_addr,
         &3000u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
-    let loan1 = client.get_loan(&borrower1).unwrap();
+    );
+    let loan1 = client.get_loan_by_id(&loan_id1).unwrap();
     assert_eq!(loan1.interest_rate_bps, 900u32);
 
     // Now utilization is 20%. The *next* borrower will get 900.
-    assert_eq!(client.get_current_interest_rate(&token_addr).unwrap(), 900u32);
+    assert_eq!(client.get_current_interest_rate(&token_addr), 900u32);
 
     let borrower2 = Address::generate(&env);
     mint_to(&env, &collateral_addr, &borrower2, 100_000);
@@ -544,15 +694,15 @@ fn test_dynamic_interest_rate_increases_with_utilization() {
     // Let's look at implementation: pool.total_borrowed += amount, THEN get_utilization_bps.
     // So for loan2, total_borrowed becomes 5,000. Utilization = 50%.
     // Rate = 500 + (5000 * 2000 / 10000) = 500 + 1000 = 1500.
-    client.borrow(
+    let loan_id2 = client.borrow(
         &borrower2,
         &token_addr,
         &3_000u64,
         &collateral_addr,
         &4500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
-    let loan2 = client.get_loan(&borrower2).unwrap();
+    );
+    let loan2 = client.get_loan_by_id(&loan_id2).unwrap();
     assert_eq!(loan2.interest_rate_bps, 1500u32);
 }
 
@@ -564,7 +714,7 @@ fn test_unique_loan_ids() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 100_000);
-    client.deposit(&depositor, &token_addr, &50_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &50_000u64);
 
     let borrower1 = Address::generate(&env);
     let borrower2 = Address::generate(&env);
@@ -581,11 +731,11 @@ fn test_unique_loan_ids() {
         &collateral_addr,
         &1500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
     assert_eq!(loan_id_1, 1);
 
     // Repay first loan
-    client.repay(&borrower1).unwrap();
+    client.repay(&borrower1, &loan_id_1);
 
     // Create second loan - should have different ID
     let loan_id_2 = client.borrow(
@@ -595,7 +745,7 @@ fn test_unique_loan_ids() {
         &collateral_addr,
         &3000u64,
         &(60 * 24 * 60 * 60),
-    ).unwrap();
+    );
     assert_eq!(loan_id_2, 2);
 
     // Verify loan can be retrieved by ID
@@ -617,14 +767,21 @@ fn test_loan_tracks_due_date() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     let duration = 30 * 24 * 60 * 60u64; // 30 days
     let borrow_time = env.ledger().timestamp();
 
-    client.borrow(&borrower, &token_addr, &1_000u64, &collateral_addr, &1_500u64, &duration).unwrap();
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &duration,
+    );
 
-    let loan = client.get_loan(&borrower).unwrap();
+    let loan = client.get_loan_by_id(&loan_id).unwrap();
     assert_eq!(loan.borrow_time, borrow_time);
     assert_eq!(loan.due_date, borrow_time + duration);
 }
@@ -641,7 +798,7 @@ fn test_repayment_updates_state_correctly() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
     let loan_id = client.borrow(
         &borrower,
         &token_addr,
@@ -649,28 +806,28 @@ fn test_repayment_updates_state_correctly() {
         &collateral_addr,
         &7500u64,
         &(365 * 24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Advance time to accrue interest
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 31_536_000); // 1 year
 
-    let pool_before = client.get_pool_state(&token_addr).unwrap();
+    let pool_before = client.get_pool_state(&token_addr);
     assert_eq!(pool_before.total_borrowed, 5_000);
 
     // Repay
-    let total_repaid = client.repay(&borrower).unwrap();
+    let total_repaid = client.repay(&borrower, &loan_id);
     assert_eq!(total_repaid, 5_750); // 5000 + 750 interest
 
     // Verify state updates
-    let pool_after = client.get_pool_state(&token_addr).unwrap();
+    let pool_after = client.get_pool_state(&token_addr);
     assert_eq!(pool_after.total_borrowed, 0);
     assert_eq!(pool_after.total_deposits, 10_675); // Original + 90% interest
     assert_eq!(pool_after.retained_yield, 38);
     assert_eq!(pool_after.bad_debt_reserve, 37);
 
     // Verify loan is removed
-    assert!(client.get_loan(&borrower).is_none());
+    assert!(client.get_loan_by_id(&loan_id).is_none());
     assert!(client.get_loan_by_id(&loan_id).is_none());
 }
 
@@ -682,7 +839,7 @@ fn test_repay_decreases_interest_rate() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 100_000);
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     let borrower = Address::generate(&env);
     mint_to(&env, &collateral_addr, &borrower, 100_000);
@@ -690,21 +847,21 @@ fn test_repay_decreases_interest_rate() {
     mint_to(&env, &collateral_addr, &borrower, 100_000);
 
     // Borrow 50% (5,000). Rate becomes 15% (1500)
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &5_000u64,
         &collateral_addr,
         &7500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
-    assert_eq!(client.get_current_interest_rate(&token_addr).unwrap(), 1500u32);
+    );
+    assert_eq!(client.get_current_interest_rate(&token_addr), 1500u32);
 
     // Repay immediately
-    client.repay(&borrower).unwrap();
+    client.repay(&borrower, &loan_id);
 
     // Utilization goes back to 0. Rate goes back to 5% (500)
-    assert_eq!(client.get_current_interest_rate(&token_addr).unwrap(), 500u32);
+    assert_eq!(client.get_current_interest_rate(&token_addr), 500u32);
 }
 
 #[test]
@@ -719,7 +876,7 @@ fn test_collateral_required() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &collateral_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Try to borrow without sufficient collateral (need 150% = 1500 for 1000 borrow)
     let result = client.try_borrow(
@@ -740,7 +897,7 @@ fn test_collateral_required() {
         &collateral_addr,
         &1_500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
     assert!(loan_id > 0);
 }
 
@@ -758,7 +915,7 @@ fn test_collateral_not_whitelisted() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &bad_collateral, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Try to borrow with non-whitelisted collateral
     let result = client.try_borrow(
@@ -785,18 +942,18 @@ fn test_collateral_returned_on_repay() {
     mint_to(&env, &token_addr, &borrower, 100_000);
     mint_to(&env, &collateral_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     let collateral_balance_before = tok_client(&env, &collateral_addr).balance(&borrower);
 
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &1_000u64,
         &collateral_addr,
         &1_500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Collateral should be locked
     assert_eq!(
@@ -804,7 +961,7 @@ fn test_collateral_returned_on_repay() {
         collateral_balance_before - 1_500
     );
 
-    client.repay(&borrower).unwrap();
+    client.repay(&borrower, &loan_id);
 
     // Collateral should be returned
     assert_eq!(
@@ -858,13 +1015,14 @@ fn test_utilization_cap_enforced() {
     let client = LendingContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &500u32, &2000u32, &15000u32, &8000u32); // 80% cap
     client.whitelist_collateral(&admin, &collateral_addr);
+    price_at_par(&env, &client, &admin, &collateral_addr);
 
     let depositor = Address::generate(&env);
     let borrower = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &collateral_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Try to borrow 8,001 (80.01% utilization) - should fail
     let result = client.try_borrow(
@@ -885,7 +1043,7 @@ fn test_utilization_cap_enforced() {
         &collateral_addr,
         &12_000u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
     assert!(loan_id > 0);
 }
 #[test]
@@ -908,7 +1066,7 @@ fn test_nft_minting_and_burning() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Borrow 1000
     let loan_id = client.borrow(
@@ -918,7 +1076,7 @@ fn test_nft_minting_and_burning() {
         &collateral_addr,
         &1_500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Verify NFT is minted
     assert_eq!(nft_client.owner_of(&loan_id), Some(borrower.clone()));
@@ -928,7 +1086,7 @@ fn test_nft_minting_and_burning() {
     assert_eq!(metadata.principal, 1_000u64);
 
     // Repay
-    client.repay(&borrower).unwrap();
+    client.repay(&borrower, &loan_id);
 
     // Verify NFT is burned
     assert_eq!(nft_client.owner_of(&loan_id), None);
@@ -945,7 +1103,7 @@ pub struct MaliciousNFT;
 #[contractimpl]
 impl MaliciousNFT {
     pub fn initialize(env: Env, admin: Address) {}
-    pub fn mint(env: Env, to: Address, metadata: LoanMetadata) {
+    pub fn mint(env: Env, admin: Address, to: Address, metadata: LoanMetadata) {
         let lending_contract = env
             .storage()
             .instance()
@@ -953,9 +1111,16 @@ impl MaliciousNFT {
             .unwrap();
         let client = LendingContractClient::new(&env, &lending_contract);
         // Attempt reentrant call to borrow
-        let _ = client.try_borrow(&to, &100, &metadata.collateral_token, &150, &3600);
+        let _ = client.try_borrow(
+            &to,
+            &metadata.asset,
+            &100,
+            &metadata.collateral_token,
+            &150,
+            &3600,
+        );
     }
-    pub fn burn(env: Env, loan_id: u64) {}
+    pub fn burn(env: Env, admin: Address, loan_id: u64) {}
     pub fn get_metadata(env: Env, loan_id: u64) -> Option<LoanMetadata> {
         None
     }
@@ -992,7 +1157,7 @@ fn test_reentrancy_attack_fails() {
     mint_to(&env, &collateral_addr, &borrower, 100_000);
     mint_to(&env, &token_addr, &depositor, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // This borrow will trigger MaliciousNFT::mint, which calls client.borrow again.
     // The inner borrow should return ReentrantCall error.
@@ -1002,19 +1167,19 @@ fn test_reentrancy_attack_fails() {
     // Let's modify MaliciousNFT to panic on reentrancy failure if we want to catch it specifically,
     // or just check that NO second loan was created.
 
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &1_000u64,
         &collateral_addr,
         &1_500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
 
     // If reentrancy was successful, next loan ID would be 3 (1 from first successful, 1 from reentrant).
     // If blocked, next loan ID should be 2.
     // Actually, in our implementation, pool.total_borrowed would be double if successful.
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_borrowed, 1000); // Only the first borrow succeeded
 }
 
@@ -1026,10 +1191,10 @@ fn test_reentrancy_attack_fails() {
 fn test_grace_period_defaults() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _token_addr, _collateral_addr, _admin) = setup(&env);
+    let (client, token_addr, _collateral_addr, _admin) = setup(&env);
 
-    let grace_period = client.get_grace_period();
-    let late_fee_rate = client.get_late_fee_rate();
+    let grace_period = client.get_grace_period(&token_addr);
+    let late_fee_rate = client.get_late_fee_rate(&token_addr);
 
     // Should have default values set
     assert_eq!(grace_period, 259_200u64); // 3 days
@@ -1040,40 +1205,40 @@ fn test_grace_period_defaults() {
 fn test_set_grace_period_admin_only() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _token_addr, _collateral_addr, admin) = setup(&env);
+    let (client, token_addr, _collateral_addr, admin) = setup(&env);
 
     let non_admin = Address::generate(&env);
 
     // Non-admin should fail
-    let result = client.try_set_grace_period(&non_admin, &(5 * 24 * 60 * 60));
+    let result = client.try_set_grace_period(&non_admin, &token_addr, &(5 * 24 * 60 * 60));
     assert!(result.is_err());
 
     // Admin should succeed
-    let result = client.try_set_grace_period(&admin, &(7 * 24 * 60 * 60));
+    let result = client.try_set_grace_period(&admin, &token_addr, &(7 * 24 * 60 * 60));
     assert!(result.is_ok());
 
     // Verify the new grace period
-    assert_eq!(client.get_grace_period(), 7 * 24 * 60 * 60);
+    assert_eq!(client.get_grace_period(&token_addr), 7 * 24 * 60 * 60);
 }
 
 #[test]
 fn test_set_late_fee_rate_admin_only() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _token_addr, _collateral_addr, admin) = setup(&env);
+    let (client, token_addr, _collateral_addr, admin) = setup(&env);
 
     let non_admin = Address::generate(&env);
 
     // Non-admin should fail
-    let result = client.try_set_late_fee_rate(&non_admin, &1000u32);
+    let result = client.try_set_late_fee_rate(&non_admin, &token_addr, &1000u32);
     assert!(result.is_err());
 
     // Admin should succeed
-    let result = client.try_set_late_fee_rate(&admin, &1000u32);
+    let result = client.try_set_late_fee_rate(&admin, &token_addr, &1000u32);
     assert!(result.is_ok());
 
     // Verify the new rate
-    assert_eq!(client.get_late_fee_rate(), 1000u32); // 10% per day
+    assert_eq!(client.get_late_fee_rate(&token_addr), 1000u32); // 10% per day
 }
 
 #[test]
@@ -1088,17 +1253,17 @@ fn test_no_late_fees_during_grace_period() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Borrow with 1 day duration
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &1_000u64,
         &collateral_addr,
         &1_500u64,
         &(24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Jump to just after due date (within grace period)
     // Grace period is 3 days (259200 seconds), so due_date + grace = due_date + 259200
@@ -1106,15 +1271,15 @@ fn test_no_late_fees_during_grace_period() {
         .set_timestamp(env.ledger().timestamp() + 2 * 24 * 60 * 60); // Jump 2 days
 
     // Should still be in grace period
-    let in_grace = client.is_in_grace_period(&borrower).unwrap();
+    let in_grace = client.is_in_grace_period(&loan_id);
     assert!(in_grace);
 
     // Late fees should be 0
-    let late_fee = client.calculate_late_fee(&borrower).unwrap();
+    let late_fee = client.calculate_late_fee(&loan_id);
     assert_eq!(late_fee, 0u64);
 
     // Total due should only include principal + interest, no late fees
-    let repayment = client.get_repayment_amount(&borrower).unwrap();
+    let repayment = client.get_repayment_amount(&loan_id);
     // 1000 principal at ~15% APY for ~2 days = 1000 + ~8 interest
     assert!(repayment < 1_100u64);
 }
@@ -1131,21 +1296,21 @@ fn test_late_fees_after_grace_period() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Set grace period to 1 day and late fee to 5% per day for easier testing
-    client.set_grace_period(&admin, &(24 * 60 * 60)).unwrap();
-    client.set_late_fee_rate(&admin, &500u32).unwrap(); // 5% per day
+    client.set_grace_period(&admin, &token_addr, &(24 * 60 * 60));
+    client.set_late_fee_rate(&admin, &token_addr, &500u32); // 5% per day
 
     // Borrow 10,000 (so late fees are 500 per day)
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &10_000u64,
         &collateral_addr,
         &15_000u64,
         &(24 * 60 * 60), // 1 day duration
-    ).unwrap();
+    );
 
     // Jump to 3 days after due date (2 days past grace period)
     // late fees = 10000 * 0.05 * 2 = 1000
@@ -1153,15 +1318,15 @@ fn test_late_fees_after_grace_period() {
         .set_timestamp(env.ledger().timestamp() + 4 * 24 * 60 * 60);
 
     // Should be out of grace period
-    let in_grace = client.is_in_grace_period(&borrower).unwrap();
+    let in_grace = client.is_in_grace_period(&loan_id);
     assert!(!in_grace);
 
     // Late fee should be ~1000 (2 days * 500 per day = 1000)
-    let late_fee = client.calculate_late_fee(&borrower).unwrap();
+    let late_fee = client.calculate_late_fee(&loan_id);
     assert_eq!(late_fee, 1_000u64);
 
     // Total due should include late fees
-    let repayment = client.get_repayment_amount(&borrower).unwrap();
+    let repayment = client.get_repayment_amount(&loan_id);
     // 10000 principal + interest (~825 for 4 days at ~15%) + 1000 late fees = ~11825
     assert!(repayment > 11_000u64);
 }
@@ -1179,20 +1344,20 @@ fn test_liquidation_blocked_during_grace_period() {
     mint_to(&env, &token_addr, &depositor, 50_000);
     mint_to(&env, &token_addr, &liquidator, 50_000);
 
-    client.deposit(&depositor, &token_addr, &20_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &20_000u64);
 
     // Borrow with very high collateral (so health factor starts good)
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &5_000u64,
         &collateral_addr,
         &7_500u64, // Exactly 150% collateral ratio
         &(24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Even though health factor might be bad, liquidation should fail during grace period
-    let result = client.try_liquidate(&liquidator, &borrower, &1_000u64);
+    let result = client.try_liquidate(&liquidator, &loan_id, &1_000u64);
     assert!(result.is_err()); // Should fail due to grace period, not health factor
 
     // Jump past grace period (4 days) - use absolute timestamp
@@ -1200,7 +1365,7 @@ fn test_liquidation_blocked_during_grace_period() {
     env.ledger().set_timestamp(current_time + 4 * 24 * 60 * 60);
 
     // Now liquidation can proceed (if health factor is bad)
-    let result = client.try_liquidate(&liquidator, &borrower, &1_000u64);
+    let result = client.try_liquidate(&liquidator, &loan_id, &1_000u64);
     // Result depends on health factor calculation, but grace period check shouldn't block
     let _ = result; // Just verify no panic
 }
@@ -1217,45 +1382,45 @@ fn test_late_fee_collected_on_repay() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Set simple rates for testing
-    client.set_grace_period(&admin, &(24 * 60 * 60)).unwrap();
-    client.set_late_fee_rate(&admin, &500u32).unwrap(); // 5% per day
+    client.set_grace_period(&admin, &token_addr, &(24 * 60 * 60));
+    client.set_late_fee_rate(&admin, &token_addr, &500u32); // 5% per day
 
     // Borrow 5000
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &5_000u64,
         &collateral_addr,
         &7_500u64,
         &(24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Jump 4 days (1 day maturity + 1 day grace + 2 days late)
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 4 * 24 * 60 * 60);
 
     // Late fees should be 5000 * 0.05 * 2 = 500
-    let late_fee = client.calculate_late_fee(&borrower).unwrap();
+    let late_fee = client.calculate_late_fee(&loan_id);
     assert_eq!(late_fee, 500u64);
 
     // Get pool state before repay
-    let pool_before = client.get_pool_state(&token_addr).unwrap();
+    let pool_before = client.get_pool_state(&token_addr);
 
     // Repay - should include late fees
-    client.repay(&borrower).unwrap();
+    client.repay(&borrower, &loan_id);
 
     // Get pool state after repay
-    let pool_after = client.get_pool_state(&token_addr).unwrap();
+    let pool_after = client.get_pool_state(&token_addr);
 
     // Late fee (500) should be added to retained_yield
     // Protocol gets 10% of interest, but 100% of late fees
     assert!(pool_after.retained_yield > pool_before.retained_yield);
 
     // Loan should be gone
-    let loan = client.get_loan(&borrower);
+    let loan = client.get_loan_by_id(&loan_id);
     assert!(loan.is_none());
 }
 
@@ -1271,33 +1436,33 @@ fn test_grace_period_expires_correctly() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Set 2 day grace period
-    client.set_grace_period(&admin, &(2 * 24 * 60 * 60)).unwrap();
+    client.set_grace_period(&admin, &token_addr, &(2 * 24 * 60 * 60));
 
     // Borrow with 1 day maturity
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &1_000u64,
         &collateral_addr,
         &1_500u64,
         &(24 * 60 * 60),
-    ).unwrap();
+    );
 
     // At 1.5 days: should be in grace period
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 36 * 60 * 60);
-    assert!(client.is_in_grace_period(&borrower).unwrap());
+    assert!(client.is_in_grace_period(&loan_id));
 
     // At 4.5 days: should be out of grace period (3 days) and have at least 1 day overdue
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 3 * 24 * 60 * 60); // Total 4.5 days
-    assert!(!client.is_in_grace_period(&borrower).unwrap());
+    assert!(!client.is_in_grace_period(&loan_id));
 
     // Late fees should start accruing
-    assert!(client.calculate_late_fee(&borrower).unwrap() > 0u64);
+    assert!(client.calculate_late_fee(&loan_id) > 0u64);
 }
 
 #[test]
@@ -1308,7 +1473,7 @@ fn test_multiple_loans_grace_period() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 200_000);
-    client.deposit(&depositor, &token_addr, &100_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &100_000u64);
 
     let borrower1 = Address::generate(&env);
     let borrower2 = Address::generate(&env);
@@ -1317,40 +1482,40 @@ fn test_multiple_loans_grace_period() {
     mint_to(&env, &token_addr, &borrower1, 100_000);
     mint_to(&env, &token_addr, &borrower2, 100_000);
 
-    client.set_grace_period(&admin, &(24 * 60 * 60)).unwrap();
+    client.set_grace_period(&admin, &token_addr, &(24 * 60 * 60));
 
     // Create two loans with different maturities
-    client.borrow(
+    let loan_id1 = client.borrow(
         &borrower1,
         &token_addr,
         &1_000u64,
         &collateral_addr,
         &1_500u64,
         &(24 * 60 * 60),
-    ).unwrap();
+    );
 
     env.ledger().set_timestamp(env.ledger().timestamp() + 1_000);
 
-    client.borrow(
+    let loan_id2 = client.borrow(
         &borrower2,
         &token_addr,
         &2_000u64,
         &collateral_addr,
         &3_000u64,
         &(2 * 24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Jump 3 days to ensure borrower1 is past grace period
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 3 * 24 * 60 * 60);
 
     // borrower1 should be out of grace period
-    assert!(!client.is_in_grace_period(&borrower1).unwrap());
-    assert!(client.calculate_late_fee(&borrower1).unwrap() > 0u64);
+    assert!(!client.is_in_grace_period(&loan_id1));
+    assert!(client.calculate_late_fee(&loan_id1) > 0u64);
 
     // borrower2 should still be in grace period (due_date is 2 days after borrow, grace = 1 day, so still in grace)
-    assert!(client.is_in_grace_period(&borrower2).unwrap());
-    assert_eq!(client.calculate_late_fee(&borrower2).unwrap(), 0u64);
+    assert!(client.is_in_grace_period(&loan_id2));
+    assert_eq!(client.calculate_late_fee(&loan_id2), 0u64);
 }
 
 // ─────────────────────────────────────────────────
@@ -1370,20 +1535,20 @@ fn test_get_refinance_terms() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Borrow 1000 with 1500 collateral for 30 days
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &1000u64,
         &collateral_addr,
         &1500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Get refinancing terms for 60 days
-    let terms = client.get_refinance_terms(&borrower, &(60 * 24 * 60 * 60)).unwrap();
+    let terms = client.get_refinance_terms(&loan_id, &(60 * 24 * 60 * 60));
 
     // Should have outstanding balance (principal + accrued interest)
     assert!(terms.outstanding_balance >= 1000u64);
@@ -1407,7 +1572,7 @@ fn test_refinance_loan() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Borrow 1000 with 1500 collateral for 30 days
     let old_loan_id = client.borrow(
@@ -1417,16 +1582,16 @@ fn test_refinance_loan() {
         &collateral_addr,
         &1500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Get initial loan details
-    let old_loan = client.get_loan(&borrower).unwrap();
+    let old_loan = client.get_loan_by_id(&old_loan_id).unwrap();
 
     // Refinance for 60 days
-    let new_loan_id = client.refinance_loan(&borrower, &(60 * 24 * 60 * 60)).unwrap();
+    let new_loan_id = client.refinance_loan(&borrower, &old_loan_id, &(60 * 24 * 60 * 60));
 
     // Verify new loan exists with different terms
-    let new_loan = client.get_loan(&borrower).unwrap();
+    let new_loan = client.get_loan_by_id(&new_loan_id).unwrap();
     assert_ne!(new_loan_id, old_loan_id);
     assert_eq!(new_loan.borrower, borrower);
     assert_eq!(new_loan.collateral_amount, old_loan.collateral_amount);
@@ -1452,24 +1617,24 @@ fn test_refinance_loan_fails_when_overdue() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Borrow 1000 for 1 day
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &1000u64,
         &collateral_addr,
         &1500u64,
         &(24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Jump past grace period
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 5 * 24 * 60 * 60);
 
     // Should fail to refinance when overdue
-    let result = client.try_refinance_loan(&borrower, &(30 * 24 * 60 * 60));
+    let result = client.try_refinance_loan(&borrower, &loan_id, &(30 * 24 * 60 * 60));
     assert_eq!(result.err(), Some(Ok(LendingError::CannotRefinance)));
 }
 
@@ -1486,13 +1651,8 @@ fn test_consolidate_loans() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
-    // Create multiple loans by using different borrowers first, then transferring
-    // For this test, we'll need a different approach since the contract only allows one loan per user
-
-    // Let's modify the contract to allow multiple loans for testing consolidation
-    // For now, let's test the consolidation logic with a single loan (edge case)
     let loan_id1 = client.borrow(
         &borrower,
         &token_addr,
@@ -1500,18 +1660,29 @@ fn test_consolidate_loans() {
         &collateral_addr,
         &1500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
+    let loan_id2 = client.borrow(
+        &borrower,
+        &token_addr,
+        &500u64,
+        &collateral_addr,
+        &750u64,
+        &(30 * 24 * 60 * 60),
+    );
 
-    // Try to consolidate single loan (should work but be similar to refinance)
     let mut loan_ids = Vec::new(&env);
     loan_ids.push_back(loan_id1);
+    loan_ids.push_back(loan_id2);
 
-    let new_loan_id = client.consolidate_loans(&borrower, &loan_ids, &(60 * 24 * 60 * 60)).unwrap();
+    let new_loan_id = client.consolidate_loans(&borrower, &loan_ids, &(60 * 24 * 60 * 60));
 
-    // Verify consolidation worked
-    let new_loan = client.get_loan(&borrower).unwrap();
-    assert_ne!(new_loan_id, loan_id1);
-    assert!(new_loan.principal > 1000u64); // Should include consolidation fee
+    // Both old loans are replaced by one loan holding their combined collateral
+    assert!(client.get_loan_by_id(&loan_id1).is_none());
+    assert!(client.get_loan_by_id(&loan_id2).is_none());
+    let new_loan = client.get_loan_by_id(&new_loan_id).unwrap();
+    assert!(new_loan.principal > 1500u64); // Should include consolidation fee
+    assert_eq!(new_loan.collateral_amount, 2250u64);
+    assert_eq!(client.get_user_loan_ids(&borrower).len(), 1);
 }
 
 #[test]
@@ -1527,7 +1698,7 @@ fn test_split_loan() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Borrow 2000 with 3000 collateral
     let old_loan_id = client.borrow(
@@ -1537,14 +1708,14 @@ fn test_split_loan() {
         &collateral_addr,
         &3000u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Jump forward a bit to accrue some interest
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 10 * 24 * 60 * 60);
 
     // Get current outstanding balance
-    let outstanding = client.get_repayment_amount(&borrower).unwrap();
+    let outstanding = client.get_repayment_amount(&old_loan_id);
 
     // Split into two loans: 60% and 40%
     let split1 = (outstanding * 60) / 100;
@@ -1554,7 +1725,12 @@ fn test_split_loan() {
     split_amounts.push_back(split1);
     split_amounts.push_back(split2);
 
-    let new_loan_ids = client.split_loan(&borrower, &split_amounts, &(45 * 24 * 60 * 60)).unwrap();
+    let new_loan_ids = client.split_loan(
+        &borrower,
+        &old_loan_id,
+        &split_amounts,
+        &(45 * 24 * 60 * 60),
+    );
 
     // Verify split worked
     assert_eq!(new_loan_ids.len(), 2);
@@ -1584,24 +1760,24 @@ fn test_split_loan_invalid_amounts() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Borrow 1000
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &1000u64,
         &collateral_addr,
         &1500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Try to split with amounts that don't sum to outstanding
     let mut split_amounts = Vec::new(&env);
     split_amounts.push_back(500u64);
     split_amounts.push_back(600u64); // Total 1100, should be more than outstanding
 
-    let result = client.try_split_loan(&borrower, &split_amounts, &(30 * 24 * 60 * 60));
+    let result = client.try_split_loan(&borrower, &loan_id, &split_amounts, &(30 * 24 * 60 * 60));
     assert_eq!(result.err(), Some(Ok(LendingError::InvalidSplitAmounts)));
 }
 
@@ -1627,7 +1803,7 @@ fn test_user_loan_tracking() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Initially should have no loans
     let user_loans = client.get_user_loan_ids(&borrower);
@@ -1641,7 +1817,7 @@ fn test_user_loan_tracking() {
         &collateral_addr,
         &1500u64,
         &(30 * 24 * 60 * 60),
-    ).unwrap();
+    );
 
     // Should have one loan
     let user_loans = client.get_user_loan_ids(&borrower);
@@ -1653,14 +1829,14 @@ fn test_user_loan_tracking() {
     split_amounts.push_back(500u64);
     split_amounts.push_back(500u64);
 
-    client.split_loan(&borrower, &split_amounts, &(30 * 24 * 60 * 60)).unwrap();
+    let new_loan_ids = client.split_loan(&borrower, &loan_id, &split_amounts, &(30 * 24 * 60 * 60));
 
     // Should have two loans
     let user_loans = client.get_user_loan_ids(&borrower);
     assert_eq!(user_loans.len(), 2);
 
-    // Repay one loan (by getting the primary loan and repaying)
-    client.repay(&borrower).unwrap();
+    // Repay one of the split loans
+    client.repay(&borrower, &new_loan_ids.get(0).unwrap());
 
     // Should have one loan left
     let user_loans = client.get_user_loan_ids(&borrower);
@@ -1708,7 +1884,7 @@ fn test_flash_loan_success() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 1_000_000);
-    client.deposit(&depositor, &token_addr, &100_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &100_000u64);
 
     let receiver_id = env.register_contract(None, MockFlashLoanReceiver);
 
@@ -1726,11 +1902,11 @@ fn test_flash_loan_success() {
     let flash_loan_amount = 50_000u64;
     let expected_fee = (50_000u64 * 9) / 10000;
 
-    let pool_before = client.get_pool_state(&token_addr).unwrap();
+    let pool_before = client.get_pool_state(&token_addr);
 
-    client.flash_loan(&token_addr, &receiver_id, &flash_loan_amount).unwrap();
+    client.flash_loan(&receiver_id, &token_addr, &flash_loan_amount);
 
-    let pool_after = client.get_pool_state(&token_addr).unwrap();
+    let pool_after = client.get_pool_state(&token_addr);
     assert_eq!(
         pool_after.total_deposits,
         pool_before.total_deposits + expected_fee
@@ -1753,8 +1929,8 @@ fn test_stake_lp_tokens() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to get shares
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Check initial state
     assert_eq!(client.get_staked_balance(&user, &token_addr), 0);
@@ -1762,7 +1938,7 @@ fn test_stake_lp_tokens() {
 
     // Stake LP tokens
     let stake_amount = 1000u64;
-    client.stake_lp_tokens(&user, &token_addr, &stake_amount).unwrap();
+    client.stake_lp_tokens(&user, &token_addr, &stake_amount);
 
     // Verify staking
     assert_eq!(client.get_staked_balance(&user, &token_addr), stake_amount);
@@ -1799,23 +1975,26 @@ fn test_unstake_lp_tokens() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
     let stake_amount = 1000u64;
-    client.stake_lp_tokens(&user, &token_addr, &stake_amount).unwrap();
+    client.stake_lp_tokens(&user, &token_addr, &stake_amount);
 
     // Jump forward in time to accumulate rewards
     env.ledger().set_timestamp(env.ledger().timestamp() + 1000);
 
     // Unstake
     let unstake_amount = 500u64;
-    client.unstake_lp_tokens(&user, &token_addr, &unstake_amount).unwrap();
+    client.unstake_lp_tokens(&user, &token_addr, &unstake_amount);
 
     // Verify unstaking
     assert_eq!(
         client.get_staked_balance(&user, &token_addr),
         stake_amount - unstake_amount
     );
-    assert_eq!(client.get_total_staked(&token_addr), stake_amount - unstake_amount);
+    assert_eq!(
+        client.get_total_staked(&token_addr),
+        stake_amount - unstake_amount
+    );
 }
 
 #[test]
@@ -1828,8 +2007,8 @@ fn test_unstake_lp_tokens_insufficient_stake() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake small amount
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
-    client.stake_lp_tokens(&user, &token_addr, &1000u64).unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
+    client.stake_lp_tokens(&user, &token_addr, &1000u64);
 
     // Try to unstake more than staked
     let result = client.try_unstake_lp_tokens(&user, &token_addr, &2000u64);
@@ -1846,8 +2025,8 @@ fn test_claim_rewards() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
-    client.stake_lp_tokens(&user, &token_addr, &1000u64).unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
+    client.stake_lp_tokens(&user, &token_addr, &1000u64);
 
     // Jump forward in time to accumulate rewards
     let time_jump = 10_000u64;
@@ -1859,7 +2038,7 @@ fn test_claim_rewards() {
     assert!(pending_before > 0);
 
     // Claim rewards
-    let claimed = client.claim_rewards(&user, &token_addr).unwrap();
+    let claimed = client.claim_rewards(&user, &token_addr);
     assert!(claimed > 0);
     assert_eq!(claimed, pending_before);
 
@@ -1877,8 +2056,8 @@ fn test_claim_rewards_no_rewards() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
-    client.stake_lp_tokens(&user, &token_addr, &1000u64).unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
+    client.stake_lp_tokens(&user, &token_addr, &1000u64);
 
     // Try to claim immediately (no time passed)
     let result = client.try_claim_rewards(&user, &token_addr);
@@ -1897,7 +2076,7 @@ fn test_set_reward_rate() {
 
     // Set new reward rate as admin
     let new_rate = 2000u64;
-    client.set_reward_rate(&admin, &token_addr, &new_rate).unwrap();
+    client.set_reward_rate(&admin, &token_addr, &new_rate);
 
     // Verify rate was updated
     assert_eq!(client.get_reward_rate(&token_addr), new_rate);
@@ -1931,10 +2110,10 @@ fn test_multiple_users_staking() {
     mint_to(&env, &token_addr, &user2, 10_000);
 
     // Both users deposit and stake
-    client.deposit(&user1, &token_addr, &5000u64).unwrap();
-    client.deposit(&user2, &token_addr, &5000u64).unwrap();
-    client.stake_lp_tokens(&user1, &token_addr, &1000u64).unwrap();
-    client.stake_lp_tokens(&user2, &token_addr, &2000u64).unwrap();
+    client.deposit(&user1, &token_addr, &5000u64);
+    client.deposit(&user2, &token_addr, &5000u64);
+    client.stake_lp_tokens(&user1, &token_addr, &1000u64);
+    client.stake_lp_tokens(&user2, &token_addr, &2000u64);
 
     // Verify total staked
     assert_eq!(client.get_total_staked(&token_addr), 3000u64);
@@ -1963,12 +2142,12 @@ fn test_reward_calculation_accuracy() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake known amount
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
     let stake_amount = 1000u64;
-    client.stake_lp_tokens(&user, &token_addr, &stake_amount).unwrap();
+    client.stake_lp_tokens(&user, &token_addr, &stake_amount);
 
     // Set known reward rate and jump exact time
-    client.set_reward_rate(&admin, &token_addr, &1_000_000_000u64).unwrap(); // 1 reward per second per token
+    client.set_reward_rate(&admin, &token_addr, &1_000_000_000u64); // 1 reward per second per token
     let time_elapsed = 1000u64; // 1000 seconds
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + time_elapsed);
@@ -1992,8 +2171,8 @@ fn test_partial_unstake_preserves_rewards() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
-    client.stake_lp_tokens(&user, &token_addr, &1000u64).unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
+    client.stake_lp_tokens(&user, &token_addr, &1000u64);
 
     // Jump forward to accumulate rewards
     env.ledger()
@@ -2003,7 +2182,7 @@ fn test_partial_unstake_preserves_rewards() {
     assert!(rewards_before > 0);
 
     // Partial unstake
-    client.unstake_lp_tokens(&user, &token_addr, &500u64).unwrap();
+    client.unstake_lp_tokens(&user, &token_addr, &500u64);
 
     // Should still have remaining stake and rewards preserved
     assert_eq!(client.get_staked_balance(&user, &token_addr), 500u64);
@@ -2021,15 +2200,15 @@ fn test_full_unstake_resets_tracking() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
-    client.stake_lp_tokens(&user, &token_addr, &1000u64).unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
+    client.stake_lp_tokens(&user, &token_addr, &1000u64);
 
     // Jump forward to accumulate rewards
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 10_000);
 
     // Full unstake
-    client.unstake_lp_tokens(&user, &token_addr, &1000u64).unwrap();
+    client.unstake_lp_tokens(&user, &token_addr, &1000u64);
 
     // Should have no stake left
     assert_eq!(client.get_staked_balance(&user, &token_addr), 0);
@@ -2071,15 +2250,15 @@ fn test_insurance_premium_calculation() {
     let (client, token_addr, collateral_addr, admin) = setup(&env);
 
     // Test premium calculation for various loan amounts
-    let premium_100 = client.get_insurance_premium(&100u64).unwrap();
+    let premium_100 = client.get_insurance_premium(&100u64);
     // 100 * 2% = 2
     assert_eq!(premium_100, 2u64);
 
-    let premium_1000 = client.get_insurance_premium(&1000u64).unwrap();
+    let premium_1000 = client.get_insurance_premium(&1000u64);
     // 1000 * 2% = 20
     assert_eq!(premium_1000, 20u64);
 
-    let premium_10000 = client.get_insurance_premium(&10000u64).unwrap();
+    let premium_10000 = client.get_insurance_premium(&10000u64);
     // 10000 * 2% = 200
     assert_eq!(premium_10000, 200u64);
 }
@@ -2091,9 +2270,9 @@ fn test_set_insurance_premium_rate() {
     let (client, token_addr, collateral_addr, admin) = setup(&env);
 
     // Admin can set premium rate
-    client.set_insurance_premium_rate(&admin, &500u32).unwrap(); // 5%
+    client.set_insurance_premium_rate(&admin, &500u32); // 5%
 
-    let premium = client.get_insurance_premium(&1000u64).unwrap();
+    let premium = client.get_insurance_premium(&1000u64);
     // 1000 * 5% = 50
     assert_eq!(premium, 50u64);
 }
@@ -2109,49 +2288,44 @@ fn test_purchase_loan_insurance_success() {
 
     // Setup: lender deposits
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     // Setup: borrower borrows with collateral
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
 
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &5_000u64,
         &collateral_addr,
         &10_000u64,
         &7_200u64, // 2 hours
-    ).unwrap();
+    );
 
     // Borrower has funds for premium
     mint_to(&env, &token_addr, &borrower, 1_000);
 
-    // Purchase insurance for loan 0
-    let premium = client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    // Purchase insurance for loan 1
+    let premium = client.purchase_loan_insurance(&borrower, &1u64);
     // Premium should be 2% of 5000 = 100
     assert_eq!(premium, 100u64);
 
     // Verify insurance exists
-    assert_eq!(client.is_loan_insured(&0u64), true);
+    assert!(client.is_loan_insured(&1u64));
 
-    let coverage = client.get_insurance_coverage(&0u64);
+    let coverage = client.get_insurance_coverage(&1u64);
     assert_eq!(coverage, 5_000u64); // 100% coverage
 
-    let insurance = client.get_insurance_details(&0u64);
+    let insurance = client.get_insurance_details(&1u64);
     assert!(insurance.is_some());
     let ins = insurance.unwrap();
-    assert_eq!(ins.loan_id, 0);
+    assert_eq!(ins.loan_id, 1);
     assert_eq!(ins.borrower, borrower);
     assert_eq!(ins.coverage_amount, 5_000u64);
     assert_eq!(ins.premium_paid, 100u64);
 }
 
-#[test]
-fn test_cannot_purchase_insurance_twice() {
-    // TODO: Implement test
-}
-
 // Interest Rate Model Tests (#489)
 // ─────────────────────────────────────────────────
 
@@ -2211,7 +2385,7 @@ fn test_get_borrow_rate_below_optimal() {
 }
 
 #[test]
-fn test_get_borrow_rate_at_optimal() {
+fn test_cannot_purchase_insurance_twice() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, token_addr, collateral_addr, admin) = setup(&env);
@@ -2221,12 +2395,12 @@ fn test_get_borrow_rate_at_optimal() {
 
     // Setup: lender deposits and borrower borrows
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &50_000u64);
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
 
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &5_000u64,
@@ -2238,10 +2412,10 @@ fn test_get_borrow_rate_at_optimal() {
     mint_to(&env, &token_addr, &borrower, 2_000);
 
     // Purchase insurance first time
-    client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    client.purchase_loan_insurance(&borrower, &1u64);
 
     // Try to purchase again - should fail
-    let result = client.try_purchase_loan_insurance(&borrower, &0u64);
+    let result = client.try_purchase_loan_insurance(&borrower, &1u64);
     assert!(result.is_err());
 }
 
@@ -2256,19 +2430,19 @@ fn test_insurance_fund_tracking() {
 
     // Setup
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
 
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &5_000u64,
         &collateral_addr,
         &10_000u64,
         &7_200u64,
-    ).unwrap();
+    );
 
     mint_to(&env, &token_addr, &borrower, 1_000);
 
@@ -2279,7 +2453,7 @@ fn test_insurance_fund_tracking() {
     assert_eq!(fund_before.available_balance, 0);
 
     // Purchase insurance
-    let premium = client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    let premium = client.purchase_loan_insurance(&borrower, &1u64);
 
     // Check fund state after purchase
     let fund_after = client.get_insurance_fund_state();
@@ -2297,36 +2471,40 @@ fn test_deposit_to_insurance_fund() {
     mint_to(&env, &token_addr, &admin, 10_000);
 
     // Deposit to insurance fund
-    client.deposit_to_insurance_fund(&admin, &5_000u64).unwrap();
+    client.deposit_to_insurance_fund(&admin, &5_000u64);
 
     let fund = client.get_insurance_fund_state();
     assert_eq!(fund.available_balance, 5_000u64);
 }
 
 #[test]
-fn test_claim_insurance_after_default() {
+fn test_get_borrow_rate_at_optimal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+
     // Deposit 2000 (must exceed MINIMUM_LIQUIDITY=1000 for first deposit)
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 2000);
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
 
     // Borrow 1600 → 80% utilization (1600/2000 = 80%)
     let borrower = Address::generate(&env);
     mint_to(&env, &collateral_addr, &borrower, 3000);
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &1600u64,
         &collateral_addr,
         &3000u64,
         &31536000u64,
-    ).unwrap();
+    );
 
     // Configure model: base=200, optimal=80%, slope1=800, slope2=10000
-    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32).unwrap();
+    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32);
 
     // rate = base + (8000 / 8000) * slope1 = 200 + 800 = 1000
-    let borrow_rate = client.get_borrow_rate().unwrap();
+    let borrow_rate = client.get_borrow_rate();
     assert_eq!(borrow_rate, 1000u32);
 }
 
@@ -2337,10 +2515,10 @@ fn test_simulate_rate_below_optimal() {
     let (client, _token_addr, _collateral_addr, admin) = setup(&env);
 
     // base=200, optimal=8000, slope1=800, slope2=10000
-    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32).unwrap();
+    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32);
 
     // At 40% utilization: rate = 200 + (4000 / 8000) * 800 = 200 + 400 = 600
-    let rate = client.simulate_rate(&4000u32).unwrap();
+    let rate = client.simulate_rate(&4000u32);
     assert_eq!(rate, 600u32);
 }
 
@@ -2351,12 +2529,12 @@ fn test_simulate_rate_above_optimal() {
     let (client, _token_addr, _collateral_addr, admin) = setup(&env);
 
     // base=200, optimal=8000, slope1=800, slope2=10000
-    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32).unwrap();
+    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32);
 
     // At 90% utilization (above optimal 80%):
     // excess = 9000 - 8000 = 1000, max_excess = 10000 - 8000 = 2000
     // rate = 200 + 800 + (1000 / 2000) * 10000 = 200 + 800 + 5000 = 6000
-    let rate = client.simulate_rate(&9000u32).unwrap();
+    let rate = client.simulate_rate(&9000u32);
     assert_eq!(rate, 6000u32);
 }
 
@@ -2368,12 +2546,52 @@ fn test_simulate_rate_fallback_to_legacy() {
 
     // No rate model set — uses legacy linear model (base_rate=500, multiplier=2000)
     // At 50% utilization: rate = 500 + (5000 * 2000) / 10000 = 500 + 1000 = 1500
-    let rate = client.simulate_rate(&5000u32).unwrap();
+    let rate = client.simulate_rate(&5000u32);
     assert_eq!(rate, 1500u32);
 }
 
 #[test]
-fn test_get_supply_rate() {
+fn test_insurance_claim_window_closes_a_day_after_coverage() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let lender = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    mint_to(&env, &token_addr, &lender, 100_000);
+    client.deposit(&lender, &token_addr, &50_000u64);
+    mint_to(&env, &token_addr, &borrower, 10_000);
+    mint_to(&env, &collateral_addr, &borrower, 50_000);
+    mint_to(&env, &token_addr, &admin, 20_000);
+    client.deposit_to_insurance_fund(&admin, &10_000u64);
+
+    let loan_duration = 7_200u64;
+    let due_date = env.ledger().timestamp() + loan_duration;
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &10_000u64,
+        &loan_duration,
+    );
+    client.purchase_loan_insurance(&borrower, &loan_id);
+
+    // Coverage ends at the due date, so a default can only be claimed after it.
+    env.ledger().set_timestamp(due_date);
+    assert_eq!(
+        client.try_claim_insurance(&loan_id),
+        Err(Ok(LendingError::InvalidAmount))
+    );
+
+    env.ledger().set_timestamp(due_date + 86_400 + 1);
+    assert_eq!(
+        client.try_claim_insurance(&loan_id),
+        Err(Ok(LendingError::InsuranceExpired))
+    );
+}
+
+#[test]
+fn test_claim_insurance_after_default() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, token_addr, collateral_addr, admin) = setup(&env);
@@ -2383,7 +2601,7 @@ fn test_get_supply_rate() {
 
     // Setup
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
@@ -2392,40 +2610,38 @@ fn test_get_supply_rate() {
     let loan_duration = 7_200u64; // 2 hours
     let due_date = env.ledger().timestamp() + loan_duration;
 
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &loan_amount,
         &collateral_addr,
         &10_000u64,
         &loan_duration,
-    ).unwrap();
+    );
 
     mint_to(&env, &token_addr, &borrower, 1_000);
 
     // Purchase insurance
-    let premium = client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    let premium = client.purchase_loan_insurance(&borrower, &1u64);
 
     // Fund the insurance pool for claims
     mint_to(&env, &token_addr, &admin, 20_000);
-    client
-        .deposit_to_insurance_fund(&admin, &10_000u64)
-        .unwrap();
+    client.deposit_to_insurance_fund(&admin, &10_000u64);
 
     // Jump past due date
     env.ledger().set_timestamp(due_date + 1);
 
     // Claim insurance
-    let claim_amount = client.claim_insurance(&0u64).unwrap();
+    let claim_amount = client.claim_insurance(&1u64);
     assert_eq!(claim_amount, loan_amount); // 100% coverage
 
     // Verify insurance is marked as claimed
-    assert_eq!(client.is_loan_insured(&0u64), false);
+    assert!(!client.is_loan_insured(&1u64));
 
     // Verify fund was updated
     let fund = client.get_insurance_fund_state();
     assert_eq!(fund.total_claims_paid, claim_amount);
-    assert_eq!(fund.available_balance, 10_000u64 - claim_amount);
+    assert_eq!(fund.available_balance, 10_000u64 + premium - claim_amount);
 }
 
 #[test]
@@ -2439,7 +2655,7 @@ fn test_cannot_claim_expired_insurance() {
 
     // Setup
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
@@ -2455,22 +2671,31 @@ fn test_cannot_claim_expired_insurance() {
         &collateral_addr,
         &10_000u64,
         &loan_duration,
-    ).unwrap();
+    );
 
     mint_to(&env, &token_addr, &borrower, 1_000);
 
     // Purchase insurance
-    client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    client.purchase_loan_insurance(&borrower, &1u64);
 
     // Jump past due date + some extra time
     env.ledger().set_timestamp(due_date + 100_000);
 
     // Try to claim - should fail because insurance expired
-    let result = client.try_claim_insurance(&0u64);
+    let result = client.try_claim_insurance(&1u64);
+    assert_eq!(result, Err(Ok(LendingError::InsuranceExpired)));
+}
+
+#[test]
+fn test_get_supply_rate() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+
     // Deposit 2000 (must exceed MINIMUM_LIQUIDITY=1000 for first deposit)
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 2000);
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
 
     // Borrow 1000 → 50% utilization (1000/2000 = 50%)
     let borrower = Address::generate(&env);
@@ -2482,14 +2707,14 @@ fn test_cannot_claim_expired_insurance() {
         &collateral_addr,
         &2000u64,
         &31536000u64,
-    ).unwrap();
+    );
 
     // Configure model: base=200, optimal=8000, slope1=800, slope2=10000, reserve=1000
-    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32).unwrap();
+    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32);
 
     // borrow_rate at 50% util = 200 + (5000/8000)*800 = 200 + 500 = 700
     // supply_rate = 700 * 5000 * (10000-1000) / 10000^2 = 700 * 5000 * 9000 / 100000000 = 315
-    let supply_rate = client.get_supply_rate().unwrap();
+    let supply_rate = client.get_supply_rate();
     // Value may differ slightly due to integer arithmetic — just ensure it's non-zero and less than borrow_rate
     assert!(supply_rate > 0);
     assert!(supply_rate < 700u32);
@@ -2518,11 +2743,18 @@ fn test_admin_can_assign_and_revoke_roles() {
 
     assert!(!client.has_role(&user, &access_control::Role::Beneficiary));
 
-    client.assign_role(&admin, &user, &access_control::Role::Beneficiary).unwrap();
+    client.assign_role(&admin, &user, &access_control::Role::Beneficiary);
     assert!(client.has_role(&user, &access_control::Role::Beneficiary));
 
-    client.revoke_role(&admin, &user, &access_control::Role::Beneficiary).unwrap();
+    client.revoke_role(&admin, &user, &access_control::Role::Beneficiary);
     assert!(!client.has_role(&user, &access_control::Role::Beneficiary));
+
+    // Admin only moves with the two-step transfer, never by revocation.
+    assert_eq!(
+        client.try_revoke_role(&admin, &admin, &access_control::Role::Admin),
+        Err(Ok(LendingError::InvalidAdminTransfer))
+    );
+    assert!(client.has_role(&admin, &access_control::Role::Admin));
 }
 
 #[test]
@@ -2537,6 +2769,60 @@ fn test_non_admin_cannot_assign_roles() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_admin_transfer_requires_acceptance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _token_addr, _collateral_addr, admin) = setup(&env);
+    let new_admin = Address::generate(&env);
+
+    assert_eq!(
+        client.try_assign_role(&admin, &new_admin, &access_control::Role::Admin),
+        Err(Ok(LendingError::InvalidAdminTransfer))
+    );
+
+    client.propose_admin_transfer(&admin, &new_admin);
+    assert_eq!(
+        client.get_pending_admin_transfer().unwrap().new_admin,
+        new_admin
+    );
+    assert!(!client.has_role(&new_admin, &access_control::Role::Admin));
+    assert_eq!(
+        client.try_accept_admin_transfer(&Address::generate(&env)),
+        Err(Ok(LendingError::NoPendingAdminTransfer))
+    );
+
+    client.accept_admin_transfer(&new_admin);
+    assert!(client.has_role(&new_admin, &access_control::Role::Admin));
+    assert!(!client.has_role(&admin, &access_control::Role::Admin));
+    assert_eq!(client.get_pending_admin_transfer(), None);
+
+    client.propose_admin_transfer(&new_admin, &admin);
+    client.cancel_admin_transfer(&new_admin);
+    assert_eq!(
+        client.try_accept_admin_transfer(&admin),
+        Err(Ok(LendingError::NoPendingAdminTransfer))
+    );
+}
+
+#[test]
+fn test_pauser_role_can_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _token_addr, _collateral_addr, admin) = setup(&env);
+    let pauser = Address::generate(&env);
+
+    assert_eq!(client.try_pause(&pauser), Err(Ok(LendingError::NotAdmin)));
+    client.assign_role(&admin, &pauser, &access_control::Role::Pauser);
+    client.pause(&pauser);
+    assert!(client.is_paused());
+    client.unpause(&pauser);
+    assert!(!client.is_paused());
+
+    client.revoke_role(&admin, &pauser, &access_control::Role::Pauser);
+    assert_eq!(client.try_pause(&pauser), Err(Ok(LendingError::NotAdmin)));
+}
+
 #[test]
 fn test_cancel_insurance_with_refund() {
     let env = Env::default();
@@ -2548,7 +2834,7 @@ fn test_cancel_insurance_with_refund() {
 
     // Setup
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
@@ -2557,19 +2843,19 @@ fn test_cancel_insurance_with_refund() {
     let loan_duration = 7_200u64;
     let due_date = env.ledger().timestamp() + loan_duration;
 
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &loan_amount,
         &collateral_addr,
         &10_000u64,
         &loan_duration,
-    ).unwrap();
+    );
 
     mint_to(&env, &token_addr, &borrower, 1_000);
 
     // Purchase insurance
-    let premium = client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    let premium = client.purchase_loan_insurance(&borrower, &1u64);
 
     let fund_after_purchase = client.get_insurance_fund_state();
     let initial_balance = fund_after_purchase.available_balance;
@@ -2579,14 +2865,14 @@ fn test_cancel_insurance_with_refund() {
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + half_duration);
 
-    let refund = client.cancel_insurance(&borrower, &0u64).unwrap();
+    let refund = client.cancel_insurance(&borrower, &1u64);
 
     // Refund should be approximately half the premium (pro-rata)
     assert!(refund > 0);
     assert!(refund < premium);
 
     // Verify insurance is removed
-    assert_eq!(client.is_loan_insured(&0u64), false);
+    assert!(!client.is_loan_insured(&1u64));
 
     // Verify fund was updated
     let fund_after_cancel = client.get_insurance_fund_state();
@@ -2607,7 +2893,7 @@ fn test_cancel_insurance_no_refund_after_expiry() {
 
     // Setup
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
@@ -2616,19 +2902,19 @@ fn test_cancel_insurance_no_refund_after_expiry() {
     let loan_duration = 7_200u64;
     let due_date = env.ledger().timestamp() + loan_duration;
 
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &loan_amount,
         &collateral_addr,
         &10_000u64,
         &loan_duration,
-    ).unwrap();
+    );
 
     mint_to(&env, &token_addr, &borrower, 1_000);
 
     // Purchase insurance
-    client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    client.purchase_loan_insurance(&borrower, &1u64);
 
     let fund_after_purchase = client.get_insurance_fund_state();
     let initial_balance = fund_after_purchase.available_balance;
@@ -2637,7 +2923,7 @@ fn test_cancel_insurance_no_refund_after_expiry() {
     env.ledger().set_timestamp(due_date + 1);
 
     // Cancel insurance after expiry
-    let refund = client.cancel_insurance(&borrower, &0u64).unwrap();
+    let refund = client.cancel_insurance(&borrower, &1u64);
 
     // No refund after expiry
     assert_eq!(refund, 0);
@@ -2659,27 +2945,27 @@ fn test_unauthorized_cancel_insurance() {
 
     // Setup
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
 
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &5_000u64,
         &collateral_addr,
         &10_000u64,
         &7_200u64,
-    ).unwrap();
+    );
 
     mint_to(&env, &token_addr, &borrower, 1_000);
 
     // Purchase insurance
-    client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    client.purchase_loan_insurance(&borrower, &1u64);
 
     // Try to cancel as unauthorized user
-    let result = client.try_cancel_insurance(&unauthorized_user, &0u64);
+    let result = client.try_cancel_insurance(&unauthorized_user, &1u64);
     assert!(result.is_err());
 }
 
@@ -2713,7 +2999,7 @@ fn test_pause_blocks_deposit() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, token_addr, _collateral_addr, admin) = setup(&env);
-    client.pause(&admin).unwrap();
+    client.pause(&admin);
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 1000);
     let result = client.try_deposit(&depositor, &token_addr, &500u64);
@@ -2729,15 +3015,13 @@ fn test_withdraw_from_insurance_fund() {
     mint_to(&env, &token_addr, &admin, 10_000);
 
     // Deposit to insurance fund
-    client.deposit_to_insurance_fund(&admin, &5_000u64).unwrap();
+    client.deposit_to_insurance_fund(&admin, &5_000u64);
 
     let fund_before = client.get_insurance_fund_state();
     assert_eq!(fund_before.available_balance, 5_000u64);
 
     // Withdraw from insurance fund
-    client
-        .withdraw_from_insurance_fund(&admin, &2_000u64)
-        .unwrap();
+    client.withdraw_from_insurance_fund(&admin, &2_000u64);
 
     let fund_after = client.get_insurance_fund_state();
     assert_eq!(fund_after.available_balance, 3_000u64);
@@ -2754,7 +3038,7 @@ fn test_insurance_lifecycle_complete() {
 
     // Step 1: Lender deposits
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     // Step 2: Borrower borrows
     mint_to(&env, &token_addr, &borrower, 10_000);
@@ -2763,26 +3047,24 @@ fn test_insurance_lifecycle_complete() {
     let loan_amount = 5_000u64;
     let loan_duration = 7_200u64;
 
-    client.borrow(
+    let loan_id = client.borrow(
         &borrower,
         &token_addr,
         &loan_amount,
         &collateral_addr,
         &10_000u64,
         &loan_duration,
-    ).unwrap();
+    );
 
     // Step 3: Borrower purchases insurance
     mint_to(&env, &token_addr, &borrower, 1_000);
-    let premium = client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    let premium = client.purchase_loan_insurance(&borrower, &1u64);
 
-    assert_eq!(client.is_loan_insured(&0u64), true);
+    assert!(client.is_loan_insured(&1u64));
 
     // Step 4: Fund insurance for potential claims
     mint_to(&env, &token_addr, &admin, 20_000);
-    client
-        .deposit_to_insurance_fund(&admin, &10_000u64)
-        .unwrap();
+    client.deposit_to_insurance_fund(&admin, &10_000u64);
 
     // Step 5: Verify fund state
     let fund = client.get_insurance_fund_state();
@@ -2794,11 +3076,11 @@ fn test_insurance_lifecycle_complete() {
         .set_timestamp(env.ledger().timestamp() + loan_duration + 1);
 
     // Step 7: Claim insurance
-    let claim_amount = client.claim_insurance(&0u64).unwrap();
+    let claim_amount = client.claim_insurance(&1u64);
     assert_eq!(claim_amount, loan_amount);
 
     // Step 8: Verify final state
-    assert_eq!(client.is_loan_insured(&0u64), false);
+    assert!(!client.is_loan_insured(&1u64));
 
     let final_fund = client.get_insurance_fund_state();
     assert_eq!(final_fund.total_claims_paid, claim_amount);
@@ -2813,11 +3095,11 @@ fn test_unpause_restores_deposit() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, token_addr, _collateral_addr, admin) = setup(&env);
-    client.pause(&admin).unwrap();
-    client.unpause(&admin).unwrap();
+    client.pause(&admin);
+    client.unpause(&admin);
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 2000);
-    let shares = client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    let shares = client.deposit(&depositor, &token_addr, &2000u64);
     assert!(shares > 0);
 }
 
@@ -2842,3 +3124,221 @@ fn test_is_paused_reflects_state_lending() {
     client.unpause(&admin);
     assert!(!client.is_paused());
 }
+
+// ─────────────────────────────────────────────────
+// Price oracle
+// ─────────────────────────────────────────────────
+
+fn setup_oracle(
+    env: &Env,
+    client: &LendingContractClient<'_>,
+    admin: &Address,
+    collateral_addr: &Address,
+) -> mock_oracle::MockOracleClient<'static> {
+    let oracle_id = env.register_contract(None, mock_oracle::MockOracle);
+    let oracle = mock_oracle::MockOracleClient::new(env, &oracle_id);
+    client.set_price_oracle(admin, collateral_addr, &oracle_id, &3600);
+    oracle
+}
+
+fn set_price(env: &Env, oracle: &mock_oracle::MockOracleClient<'_>, token: &Address, price: i128) {
+    // 7 decimals: 10_000_000 = 1.0 quote unit per token unit
+    oracle.set_price(
+        &mock_oracle::Asset::Stellar(token.clone()),
+        &price,
+        &env.ledger().timestamp(),
+    );
+}
+
+/// Deposits 2000 into the pool and returns a borrower holding 10_000
+/// collateral tokens.
+fn fund_pool(
+    env: &Env,
+    client: &LendingContractClient<'_>,
+    token_addr: &Address,
+    collateral_addr: &Address,
+) -> Address {
+    let depositor = Address::generate(env);
+    let borrower = Address::generate(env);
+    mint_to(env, token_addr, &depositor, 2000);
+    mint_to(env, collateral_addr, &borrower, 10_000);
+    client.deposit(&depositor, token_addr, &2000u64);
+    borrower
+}
+
+const THIRTY_DAYS: u64 = 30 * 24 * 60 * 60;
+
+#[test]
+fn test_borrow_uses_oracle_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let borrower = fund_pool(&env, &client, &token_addr, &collateral_addr);
+    let oracle = setup_oracle(&env, &client, &admin, &collateral_addr);
+    set_price(&env, &oracle, &collateral_addr, 5_000_000); // 0.5
+
+    // 600 collateral is only worth 300, below the 150% requirement
+    let result = client.try_borrow(
+        &borrower,
+        &token_addr,
+        &400u64,
+        &collateral_addr,
+        &600u64,
+        &THIRTY_DAYS,
+    );
+    assert_eq!(result, Err(Ok(LendingError::InsufficientCollateral)));
+
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &400u64,
+        &collateral_addr,
+        &1200u64,
+        &THIRTY_DAYS,
+    );
+    assert_eq!(client.get_health_factor(&loan_id), 15000);
+}
+
+#[test]
+fn test_price_deviation_moves_health_factor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let borrower = fund_pool(&env, &client, &token_addr, &collateral_addr);
+    let oracle = setup_oracle(&env, &client, &admin, &collateral_addr);
+    set_price(&env, &oracle, &collateral_addr, 10_000_000); // 1.0
+
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &400u64,
+        &collateral_addr,
+        &600u64,
+        &THIRTY_DAYS,
+    );
+    assert_eq!(client.get_health_factor(&loan_id), 15000);
+
+    // A 20% drop takes the loan below the 150% threshold
+    set_price(&env, &oracle, &collateral_addr, 8_000_000);
+    assert_eq!(client.get_health_factor(&loan_id), 12000);
+
+    // A rally doubles the collateral value
+    set_price(&env, &oracle, &collateral_addr, 20_000_000);
+    assert_eq!(client.get_health_factor(&loan_id), 30000);
+}
+
+#[test]
+fn test_stale_price_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 10_000);
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let borrower = fund_pool(&env, &client, &token_addr, &collateral_addr);
+    let oracle = setup_oracle(&env, &client, &admin, &collateral_addr);
+    set_price(&env, &oracle, &collateral_addr, 10_000_000);
+
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &400u64,
+        &collateral_addr,
+        &600u64,
+        &THIRTY_DAYS,
+    );
+
+    // Exactly max_age_secs old is still usable; one second more is not
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    assert_eq!(client.get_health_factor(&loan_id), 15000);
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    let result = client.try_get_health_factor(&loan_id);
+    assert_eq!(result, Err(Ok(LendingError::StalePrice)));
+    let result = client.try_borrow(
+        &borrower,
+        &token_addr,
+        &100u64,
+        &collateral_addr,
+        &600u64,
+        &THIRTY_DAYS,
+    );
+    assert_eq!(result, Err(Ok(LendingError::StalePrice)));
+
+    // Prices dated in the future are not trusted either
+    oracle.set_price(
+        &mock_oracle::Asset::Stellar(collateral_addr.clone()),
+        &10_000_000,
+        &(env.ledger().timestamp() + 60),
+    );
+    let result = client.try_get_health_factor(&loan_id);
+    assert_eq!(result, Err(Ok(LendingError::StalePrice)));
+}
+
+#[test]
+fn test_missing_price_feed_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let borrower = fund_pool(&env, &client, &token_addr, &collateral_addr);
+    let oracle = setup_oracle(&env, &client, &admin, &collateral_addr);
+
+    // Oracle configured but it has no price for the token
+    let result = client.try_borrow(
+        &borrower,
+        &token_addr,
+        &400u64,
+        &collateral_addr,
+        &600u64,
+        &THIRTY_DAYS,
+    );
+    assert_eq!(result, Err(Ok(LendingError::PriceUnavailable)));
+
+    // Non-positive prices are treated as missing
+    set_price(&env, &oracle, &collateral_addr, 0);
+    let result = client.try_borrow(
+        &borrower,
+        &token_addr,
+        &400u64,
+        &collateral_addr,
+        &600u64,
+        &THIRTY_DAYS,
+    );
+    assert_eq!(result, Err(Ok(LendingError::PriceUnavailable)));
+
+    // Without an oracle, collateral is not valued at par: only the pool
+    // token, the quote asset, is
+    client.remove_price_oracle(&admin, &collateral_addr);
+    assert!(client.get_price_oracle(&collateral_addr).is_none());
+    assert_eq!(client.get_quote_asset(), Some(token_addr.clone()));
+    let result = client.try_borrow(
+        &borrower,
+        &token_addr,
+        &400u64,
+        &collateral_addr,
+        &600u64,
+        &THIRTY_DAYS,
+    );
+    assert_eq!(result, Err(Ok(LendingError::PriceUnavailable)));
+}
+
+#[test]
+fn test_set_price_oracle_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _token_addr, collateral_addr, admin) = setup(&env);
+    let oracle_id = env.register_contract(None, mock_oracle::MockOracle);
+    let non_admin = Address::generate(&env);
+
+    let result = client.try_set_price_oracle(&non_admin, &collateral_addr, &oracle_id, &3600);
+    assert_eq!(result, Err(Ok(LendingError::NotAdmin)));
+
+    let result = client.try_set_price_oracle(&admin, &collateral_addr, &oracle_id, &0);
+    assert_eq!(result, Err(Ok(LendingError::InvalidAmount)));
+
+    client.set_price_oracle(&admin, &collateral_addr, &oracle_id, &3600);
+    assert_eq!(
+        client.get_price_oracle(&collateral_addr),
+        Some(OracleConfig {
+            oracle: oracle_id,
+            max_age_secs: 3600,
+        })
+    );
+}
@@ -1,11 +1,23 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Symbol};
+use access_control::{Role, RoleGrant};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Env, String,
+    Symbol, Vec,
+};
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoanNftError {
+    NotAdmin = 1,
+    InvalidRoleChange = 2,
+}
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LoanMetadata {
     pub loan_id: u64,
     pub borrower: Address,
+    pub asset: Address, // Asset being borrowed
     pub principal: u64,
     pub collateral_amount: u64,
     pub collateral_token: Address,
@@ -33,15 +45,106 @@ pub struct LoanNFT;
 #[contractimpl]
 impl LoanNFT {
     pub fn initialize(env: Env, admin: Address) {
+        access_control::extend_instance_ttl(&env);
         if env.storage().instance().has(&DataKey::Admin) {
             panic!("Already initialized");
         }
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::TotalSupply, &0u64);
+        access_control::assign_role(&env, &admin, Role::Admin);
+    }
+
+    pub fn has_role(env: Env, address: Address, role: Role) -> bool {
+        access_control::extend_instance_ttl(&env);
+        access_control::has_role(&env, &address, role)
+    }
+
+    pub fn get_roles(env: Env, address: Address) -> Vec<Role> {
+        access_control::extend_instance_ttl(&env);
+        access_control::roles_of(&env, &address)
+    }
+
+    /// Assign a role to an address. Callable by admins and by holders of the
+    /// role's admin role (see `set_role_admin`). The Admin role itself is
+    /// only granted at `initialize`.
+    pub fn assign_role(
+        env: Env,
+        admin: Address,
+        address: Address,
+        role: Role,
+    ) -> Result<(), LoanNftError> {
+        Self::assign_role_until(env, admin, address, role, 0)
+    }
+
+    /// Like `assign_role`, but the grant lapses at `expires_at` (unix
+    /// seconds; 0 for never). Re-assigning replaces the expiry.
+    pub fn assign_role_until(
+        env: Env,
+        admin: Address,
+        address: Address,
+        role: Role,
+        expires_at: u64,
+    ) -> Result<(), LoanNftError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role_admin(&env, &admin, role.clone(), LoanNftError::NotAdmin)?;
+        if role == Role::Admin {
+            return Err(LoanNftError::InvalidRoleChange);
+        }
+        access_control::assign_role_until(&env, &address, role, expires_at);
+        Ok(())
+    }
+
+    /// Revoke a role. Callable by admins and by holders of the role's admin
+    /// role.
+    pub fn revoke_role(
+        env: Env,
+        admin: Address,
+        address: Address,
+        role: Role,
+    ) -> Result<(), LoanNftError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role_admin(&env, &admin, role.clone(), LoanNftError::NotAdmin)?;
+        if role == Role::Admin {
+            return Err(LoanNftError::InvalidRoleChange);
+        }
+        access_control::revoke_role(&env, &address, role);
+        Ok(())
+    }
+
+    /// Let holders of `admin_role` grant and revoke `role`. Admin only.
+    pub fn set_role_admin(
+        env: Env,
+        admin: Address,
+        role: Role,
+        admin_role: Role,
+    ) -> Result<(), LoanNftError> {
+        access_control::extend_instance_ttl(&env);
+        admin.require_auth();
+        access_control::require_role(&env, &admin, Role::Admin, LoanNftError::NotAdmin)?;
+        if role == Role::Admin {
+            return Err(LoanNftError::InvalidRoleChange);
+        }
+        access_control::set_role_admin(&env, role, admin_role);
+        Ok(())
+    }
+
+    /// The role whose holders may grant and revoke `role`.
+    pub fn get_role_admin(env: Env, role: Role) -> Role {
+        access_control::extend_instance_ttl(&env);
+        access_control::role_admin(&env, role)
     }
 
-    pub fn mint(env: Env, to: Address, metadata: LoanMetadata) {
-        Self::check_admin(&env);
+    /// Up to `limit` holders of `role`, from position `start`.
+    pub fn get_role_members(env: Env, role: Role, start: u32, limit: u32) -> Vec<RoleGrant> {
+        access_control::extend_instance_ttl(&env);
+        access_control::role_members(&env, role, start, limit)
+    }
+
+    pub fn mint(env: Env, admin: Address, to: Address, metadata: LoanMetadata) {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin);
         Self::enter_reentrancy_guard(&env);
 
         let loan_id = metadata.loan_id;
@@ -54,12 +157,15 @@ impl LoanNFT {
         env.storage()
             .persistent()
             .set(&DataKey::Metadata(loan_id), &metadata);
+        access_control::extend_persistent_ttl(&env, &DataKey::Metadata(loan_id));
         env.storage()
             .persistent()
             .set(&DataKey::Owner(loan_id), &to);
+        access_control::extend_persistent_ttl(&env, &DataKey::Owner(loan_id));
         env.storage()
             .persistent()
             .set(&DataKey::Transferable(loan_id), &true);
+        access_control::extend_persistent_ttl(&env, &DataKey::Transferable(loan_id));
 
         // Update total supply (instance storage – single read+write)
         let total_supply: u64 = env
@@ -80,13 +186,15 @@ impl LoanNFT {
         env.storage()
             .persistent()
             .set(&DataKey::Balance(to.clone()), &(balance + 1));
+        access_control::extend_persistent_ttl(&env, &DataKey::Balance(to.clone()));
 
         Self::emit_mint_event(&env, to, loan_id);
         Self::exit_reentrancy_guard(&env);
     }
 
-    pub fn burn(env: Env, loan_id: u64) {
-        Self::check_admin(&env);
+    pub fn burn(env: Env, admin: Address, loan_id: u64) {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin);
         Self::enter_reentrancy_guard(&env);
 
         let owner =
@@ -128,6 +236,7 @@ impl LoanNFT {
         env.storage()
             .persistent()
             .set(&DataKey::Balance(owner.clone()), &balance.saturating_sub(1));
+        access_control::extend_persistent_ttl(&env, &DataKey::Balance(owner.clone()));
 
         Self::emit_burn_event(&env, owner, loan_id);
         Self::exit_reentrancy_guard(&env);
@@ -136,6 +245,7 @@ impl LoanNFT {
     // --- NFT Standard Functions ---
 
     pub fn transfer(env: Env, from: Address, to: Address, loan_id: u64) {
+        access_control::extend_instance_ttl(&env);
         from.require_auth();
         Self::enter_reentrancy_guard(&env);
         Self::check_transfer_restriction(&env, loan_id);
@@ -151,6 +261,7 @@ impl LoanNFT {
     }
 
     pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, loan_id: u64) {
+        access_control::extend_instance_ttl(&env);
         spender.require_auth();
         Self::enter_reentrancy_guard(&env);
         Self::check_transfer_restriction(&env, loan_id);
@@ -181,6 +292,7 @@ impl LoanNFT {
     }
 
     pub fn approve(env: Env, caller: Address, operator: Address, loan_id: u64) {
+        access_control::extend_instance_ttl(&env);
         caller.require_auth();
         let owner =
             Self::owner_of(env.clone(), loan_id).unwrap_or_else(|| panic!("NFT does not exist"));
@@ -201,10 +313,12 @@ impl LoanNFT {
         env.storage()
             .persistent()
             .set(&DataKey::Approved(loan_id), &operator);
+        access_control::extend_persistent_ttl(&env, &DataKey::Approved(loan_id));
         Self::emit_approval_event(&env, owner, operator, loan_id);
     }
 
     pub fn set_approval_for_all(env: Env, caller: Address, operator: Address, approved: bool) {
+        access_control::extend_instance_ttl(&env);
         caller.require_auth();
         if caller == operator {
             panic!("Cannot self-approve");
@@ -213,14 +327,20 @@ impl LoanNFT {
             &DataKey::Operator(caller.clone(), operator.clone()),
             &approved,
         );
+        access_control::extend_persistent_ttl(
+            &env,
+            &DataKey::Operator(caller.clone(), operator.clone()),
+        );
         Self::emit_approval_for_all_event(&env, caller, operator, approved);
     }
 
     pub fn get_approved(env: Env, loan_id: u64) -> Option<Address> {
+        access_control::extend_instance_ttl(&env);
         env.storage().persistent().get(&DataKey::Approved(loan_id))
     }
 
     pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .persistent()
             .get(&DataKey::Operator(owner, operator))
@@ -228,6 +348,7 @@ impl LoanNFT {
     }
 
     pub fn balance_of(env: Env, owner: Address) -> u64 {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .persistent()
             .get(&DataKey::Balance(owner))
@@ -235,6 +356,7 @@ impl LoanNFT {
     }
 
     pub fn total_supply(env: Env) -> u64 {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .get(&DataKey::TotalSupply)
@@ -242,39 +364,46 @@ impl LoanNFT {
     }
 
     pub fn token_uri(env: Env, loan_id: u64) -> String {
+        access_control::extend_instance_ttl(&env);
         env.storage()
             .persistent()
             .get(&DataKey::TokenUri(loan_id))
             .unwrap_or_else(|| String::from_str(&env, ""))
     }
 
-    pub fn set_token_uri(env: Env, loan_id: u64, uri: String) {
-        Self::check_admin(&env);
+    pub fn set_token_uri(env: Env, admin: Address, loan_id: u64, uri: String) {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin);
         if !env.storage().persistent().has(&DataKey::Metadata(loan_id)) {
             panic!("NFT does not exist");
         }
         env.storage()
             .persistent()
             .set(&DataKey::TokenUri(loan_id), &uri);
+        access_control::extend_persistent_ttl(&env, &DataKey::TokenUri(loan_id));
     }
 
     pub fn get_metadata(env: Env, loan_id: u64) -> Option<LoanMetadata> {
+        access_control::extend_instance_ttl(&env);
         env.storage().persistent().get(&DataKey::Metadata(loan_id))
     }
 
     pub fn owner_of(env: Env, loan_id: u64) -> Option<Address> {
+        access_control::extend_instance_ttl(&env);
         env.storage().persistent().get(&DataKey::Owner(loan_id))
     }
 
     // --- Transfer Restrictions ---
-    pub fn set_transferable(env: Env, loan_id: u64, is_transferable: bool) {
-        Self::check_admin(&env);
+    pub fn set_transferable(env: Env, admin: Address, loan_id: u64, is_transferable: bool) {
+        access_control::extend_instance_ttl(&env);
+        Self::require_admin(&env, &admin);
         if !env.storage().persistent().has(&DataKey::Metadata(loan_id)) {
             panic!("NFT does not exist");
         }
         env.storage()
             .persistent()
             .set(&DataKey::Transferable(loan_id), &is_transferable);
+        access_control::extend_persistent_ttl(&env, &DataKey::Transferable(loan_id));
     }
 
     fn check_transfer_restriction(env: &Env, loan_id: u64) {
@@ -293,6 +422,7 @@ impl LoanNFT {
         env.storage()
             .persistent()
             .set(&DataKey::Owner(loan_id), &to);
+        access_control::extend_persistent_ttl(env, &DataKey::Owner(loan_id));
         env.storage()
             .persistent()
             .remove(&DataKey::Approved(loan_id));
@@ -306,6 +436,7 @@ impl LoanNFT {
         env.storage()
             .persistent()
             .set(&DataKey::Balance(from.clone()), &balance_from);
+        access_control::extend_persistent_ttl(env, &DataKey::Balance(from.clone()));
 
         let mut balance_to: u64 = env
             .storage()
@@ -316,17 +447,18 @@ impl LoanNFT {
         env.storage()
             .persistent()
             .set(&DataKey::Balance(to.clone()), &balance_to);
+        access_control::extend_persistent_ttl(env, &DataKey::Balance(to.clone()));
 
         Self::emit_transfer_event(env, from, to, loan_id);
     }
 
-    fn check_admin(env: &Env) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
+    fn require_admin(env: &Env, admin: &Address) {
         admin.require_auth();
+        if let Err(err) =
+            access_control::require_role(env, admin, Role::Admin, LoanNftError::NotAdmin)
+        {
+            panic_with_error!(env, err);
+        }
     }
 
     fn emit_mint_event(env: &Env, to: Address, loan_id: u64) {
@@ -1,4 +1,5 @@
-use crate::{LoanMetadata, LoanNFT, LoanNFTClient};
+use crate::{LoanMetadata, LoanNFT, LoanNFTClient, LoanNftError};
+use access_control::Role;
 use soroban_sdk::{
     testutils::{Address as _, Events as _},
     Address, Env, IntoVal, String, Symbol,
@@ -21,19 +22,20 @@ fn test_mint_and_burn() {
     let metadata = LoanMetadata {
         loan_id: 1,
         borrower: user.clone(),
+        asset: token.clone(),
         principal: 1000,
         collateral_amount: 500,
         collateral_token: token.clone(),
         due_date: 0,
     };
 
-    client.mint(&user, &metadata);
+    client.mint(&admin, &user, &metadata);
 
     assert_eq!(client.owner_of(&1), Some(user.clone()));
     assert_eq!(client.balance_of(&user), 1);
     assert_eq!(client.total_supply(), 1);
 
-    client.burn(&1);
+    client.burn(&admin, &1);
 
     assert_eq!(client.owner_of(&1), None);
     assert_eq!(client.balance_of(&user), 0);
@@ -58,13 +60,14 @@ fn test_transfer() {
     let metadata = LoanMetadata {
         loan_id: 2,
         borrower: user1.clone(),
+        asset: token.clone(),
         principal: 1000,
         collateral_amount: 500,
         collateral_token: token.clone(),
         due_date: 0,
     };
 
-    client.mint(&user1, &metadata);
+    client.mint(&admin, &user1, &metadata);
     client.transfer(&user1, &user2, &2);
 
     assert_eq!(client.owner_of(&2), Some(user2.clone()));
@@ -90,13 +93,14 @@ fn test_approve_and_transfer_from() {
     let metadata = LoanMetadata {
         loan_id: 3,
         borrower: user1.clone(),
+        asset: token.clone(),
         principal: 1000,
         collateral_amount: 500,
         collateral_token: token.clone(),
         due_date: 0,
     };
 
-    client.mint(&user1, &metadata);
+    client.mint(&admin, &user1, &metadata);
 
     client.approve(&user1, &operator, &3);
     assert_eq!(client.get_approved(&3), Some(operator.clone()));
@@ -126,12 +130,13 @@ fn test_approval_for_all() {
     let metadata = LoanMetadata {
         loan_id: 4,
         borrower: user1.clone(),
+        asset: token.clone(),
         principal: 100,
         collateral_amount: 50,
         collateral_token: token.clone(),
         due_date: 0,
     };
-    client.mint(&user1, &metadata);
+    client.mint(&admin, &user1, &metadata);
 
     client.set_approval_for_all(&user1, &operator, &true);
     assert!(client.is_approved_for_all(&user1, &operator));
@@ -158,12 +163,13 @@ fn test_operator_can_approve_on_behalf_of_owner() {
     let metadata = LoanMetadata {
         loan_id: 44,
         borrower: owner.clone(),
+        asset: token.clone(),
         principal: 100,
         collateral_amount: 50,
         collateral_token: token,
         due_date: 0,
     };
-    client.mint(&owner, &metadata);
+    client.mint(&admin, &owner, &metadata);
 
     client.set_approval_for_all(&owner, &operator, &true);
     client.approve(&operator, &approved, &44);
@@ -204,12 +210,13 @@ fn test_approve_rejects_current_owner() {
     let metadata = LoanMetadata {
         loan_id: 45,
         borrower: owner.clone(),
+        asset: token.clone(),
         principal: 100,
         collateral_amount: 50,
         collateral_token: token,
         due_date: 0,
     };
-    client.mint(&owner, &metadata);
+    client.mint(&admin, &owner, &metadata);
 
     client.approve(&owner, &owner, &45);
 }
@@ -232,12 +239,13 @@ fn test_transfer_events_use_standard_names() {
     let metadata = LoanMetadata {
         loan_id: 46,
         borrower: owner.clone(),
+        asset: token.clone(),
         principal: 100,
         collateral_amount: 50,
         collateral_token: token,
         due_date: 0,
     };
-    client.mint(&owner, &metadata);
+    client.mint(&admin, &owner, &metadata);
     client.approve(&owner, &operator, &46);
     client.set_approval_for_all(&owner, &operator, &true);
     client.transfer_from(&operator, &owner, &recipient, &46);
@@ -285,14 +293,15 @@ fn test_transfer_restriction() {
     let metadata = LoanMetadata {
         loan_id: 5,
         borrower: user1.clone(),
+        asset: token.clone(),
         principal: 100,
         collateral_amount: 50,
         collateral_token: token.clone(),
         due_date: 0,
     };
-    client.mint(&user1, &metadata);
+    client.mint(&admin, &user1, &metadata);
 
-    client.set_transferable(&5, &false);
+    client.set_transferable(&admin, &5, &false);
     client.transfer(&user1, &user2, &5);
 }
 
@@ -312,15 +321,91 @@ fn test_token_uri() {
     let metadata = LoanMetadata {
         loan_id: 6,
         borrower: user1.clone(),
+        asset: token.clone(),
         principal: 100,
         collateral_amount: 50,
         collateral_token: token.clone(),
         due_date: 0,
     };
-    client.mint(&user1, &metadata);
+    client.mint(&admin, &user1, &metadata);
 
     let uri = String::from_str(&env, "https://example.com/nft/6");
-    client.set_token_uri(&6, &uri);
+    client.set_token_uri(&admin, &6, &uri);
 
     assert_eq!(client.token_uri(&6), uri);
 }
+
+#[test]
+fn test_mint_requires_admin_role() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, LoanNFT);
+    let client = LoanNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    client.initialize(&admin);
+    let metadata = LoanMetadata {
+        loan_id: 7,
+        borrower: user.clone(),
+        asset: token.clone(),
+        principal: 100,
+        collateral_amount: 50,
+        collateral_token: token.clone(),
+        due_date: 0,
+    };
+
+    assert_eq!(
+        client.try_mint(&user, &user, &metadata),
+        Err(Ok(LoanNftError::NotAdmin.into()))
+    );
+    client.mint(&admin, &user, &metadata);
+    assert_eq!(
+        client.try_set_token_uri(&user, &7, &String::from_str(&env, "x")),
+        Err(Ok(LoanNftError::NotAdmin.into()))
+    );
+    assert_eq!(
+        client.try_burn(&user, &7),
+        Err(Ok(LoanNftError::NotAdmin.into()))
+    );
+    assert_eq!(client.owner_of(&7), Some(user));
+}
+
+#[test]
+fn test_role_wrappers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, LoanNFT);
+    let client = LoanNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let pauser = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    client.assign_role(&admin, &pauser, &Role::Pauser);
+    assert!(client.has_role(&pauser, &Role::Pauser));
+    assert_eq!(client.get_role_members(&Role::Pauser, &0, &10).len(), 1);
+
+    assert_eq!(
+        client.try_assign_role(&outsider, &outsider, &Role::Pauser),
+        Err(Ok(LoanNftError::NotAdmin))
+    );
+    // Admin is only ever granted at initialize.
+    assert_eq!(
+        client.try_assign_role(&admin, &outsider, &Role::Admin),
+        Err(Ok(LoanNftError::InvalidRoleChange))
+    );
+
+    // A delegated role admin may manage the role without being an admin.
+    client.set_role_admin(&admin, &Role::Pauser, &Role::Guardian);
+    assert_eq!(client.get_role_admin(&Role::Pauser), Role::Guardian);
+    client.assign_role(&admin, &outsider, &Role::Guardian);
+    client.revoke_role(&outsider, &pauser, &Role::Pauser);
+    assert!(!client.has_role(&pauser, &Role::Pauser));
+}
@@ -0,0 +1,73 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol};
+
+const DEFAULT_DECIMALS: u32 = 7;
+const DEFAULT_RESOLUTION: u32 = 300;
+
+/// SEP-40 asset identifier.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// SEP-40 price record: `price` is scaled by `10^decimals()`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MockOracleDataKey {
+    Price(Asset),
+    Decimals,
+}
+
+/// Minimal SEP-40 price oracle for tests. Prices are set directly with
+/// `set_price`; there is no access control.
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn set_price(env: Env, asset: Asset, price: i128, timestamp: u64) {
+        env.storage().instance().set(
+            &MockOracleDataKey::Price(asset),
+            &PriceData { price, timestamp },
+        );
+    }
+
+    pub fn remove_price(env: Env, asset: Asset) {
+        env.storage()
+            .instance()
+            .remove(&MockOracleDataKey::Price(asset));
+    }
+
+    pub fn set_decimals(env: Env, decimals: u32) {
+        env.storage()
+            .instance()
+            .set(&MockOracleDataKey::Decimals, &decimals);
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&MockOracleDataKey::Decimals)
+            .unwrap_or(DEFAULT_DECIMALS)
+    }
+
+    pub fn resolution() -> u32 {
+        DEFAULT_RESOLUTION
+    }
+
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        env.storage()
+            .instance()
+            .get(&MockOracleDataKey::Price(asset))
+    }
+}
@@ -1,8 +1,18 @@
 #![no_std]
 
-use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env};
+//! SEP-41 token used by the contract test suites.
+//!
+//! The token works uninitialised, as it always has: anyone may mint and it
+//! reports 7 decimals. Calling `initialize` sets an admin who alone may then
+//! mint, change the admin or inject failures, plus the token's metadata.
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String,
+};
 
 const MAX_SUPPLY: i128 = 1_000_000_000_000_000_000;
+const DEFAULT_DECIMALS: u32 = 7;
+const MAX_DECIMALS: u32 = 18;
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -11,6 +21,11 @@ pub enum ContractError {
     InsufficientBalance = 2,
     Overflow = 3,
     ExceedsMaxSupply = 4,
+    AlreadyInitialized = 5,
+    InvalidDecimals = 6,
+    InsufficientAllowance = 7,
+    InvalidExpiration = 8,
+    TransferFailed = 9,
 }
 
 #[contracttype]
@@ -18,6 +33,25 @@ pub enum ContractError {
 pub enum MockTokenDataKey {
     Balance(Address),
     TotalSupply,
+    Admin,
+    Metadata,
+    Allowance(Address, Address), // (from, spender) -> AllowanceValue
+    FailTransfers,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    pub decimal: u32,
+    pub name: String,
+    pub symbol: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
 }
 
 #[contract]
@@ -25,6 +59,74 @@ pub struct MockToken;
 
 #[contractimpl]
 impl MockToken {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        decimal: u32,
+        name: String,
+        symbol: String,
+    ) -> Result<(), ContractError> {
+        if env.storage().instance().has(&MockTokenDataKey::Admin) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+        if decimal > MAX_DECIMALS {
+            return Err(ContractError::InvalidDecimals);
+        }
+        env.storage()
+            .instance()
+            .set(&MockTokenDataKey::Admin, &admin);
+        env.storage().instance().set(
+            &MockTokenDataKey::Metadata,
+            &TokenMetadata {
+                decimal,
+                name,
+                symbol,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&MockTokenDataKey::Admin)
+    }
+
+    pub fn set_admin(env: Env, new_admin: Address) {
+        let admin = Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&MockTokenDataKey::Admin, &new_admin);
+        env.events()
+            .publish((symbol_short!("set_admin"), admin), new_admin);
+    }
+
+    /// Make every `transfer` and `transfer_from` fail with `TransferFailed`
+    /// until switched off again, to exercise callers' failure handling.
+    pub fn set_fail_transfers(env: Env, fail: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&MockTokenDataKey::FailTransfers, &fail);
+    }
+
+    pub fn fails_transfers(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&MockTokenDataKey::FailTransfers)
+            .unwrap_or(false)
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        Self::metadata(&env).map_or(DEFAULT_DECIMALS, |m| m.decimal)
+    }
+
+    pub fn name(env: Env) -> String {
+        Self::metadata(&env).map_or_else(|| String::from_str(&env, "Mock Token"), |m| m.name)
+    }
+
+    pub fn symbol(env: Env) -> String {
+        Self::metadata(&env).map_or_else(|| String::from_str(&env, "MOCK"), |m| m.symbol)
+    }
+
     pub fn balance(env: Env, id: Address) -> i128 {
         let key = MockTokenDataKey::Balance(id);
         env.storage().instance().get(&key).unwrap_or(0)
@@ -39,43 +141,73 @@ impl MockToken {
         MAX_SUPPLY
     }
 
-    pub fn transfer(
+    /// What `spender` may still move out of `from`'s balance; 0 once the
+    /// approval's expiration ledger has passed.
+    pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        Self::live_allowance(&env, &from, &spender).amount
+    }
+
+    pub fn approve(
         env: Env,
         from: Address,
-        to: Address,
+        spender: Address,
         amount: i128,
+        expiration_ledger: u32,
     ) -> Result<(), ContractError> {
         from.require_auth();
 
         if amount < 0 {
             return Err(ContractError::NegativeAmount);
         }
-
-        let key_from = MockTokenDataKey::Balance(from.clone());
-        let key_to = MockTokenDataKey::Balance(to.clone());
-
-        let balance_from: i128 = env.storage().instance().get(&key_from).unwrap_or(0);
-        let balance_to: i128 = env.storage().instance().get(&key_to).unwrap_or(0);
-
-        if balance_from < amount {
-            return Err(ContractError::InsufficientBalance);
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            return Err(ContractError::InvalidExpiration);
         }
 
-        if balance_to.checked_add(amount).is_none() {
-            return Err(ContractError::Overflow);
-        }
+        env.storage().instance().set(
+            &MockTokenDataKey::Allowance(from.clone(), spender.clone()),
+            &AllowanceValue {
+                amount,
+                expiration_ledger,
+            },
+        );
+        env.events().publish(
+            (symbol_short!("approve"), from, spender),
+            (amount, expiration_ledger),
+        );
+        Ok(())
+    }
 
-        env.storage()
-            .instance()
-            .set(&key_from, &(balance_from - amount));
-        env.storage()
-            .instance()
-            .set(&key_to, &(balance_to + amount));
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        from.require_auth();
+        Self::check_transfers_enabled(&env)?;
+        Self::move_balance(&env, &from, &to, amount)
+    }
 
-        Ok(())
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        spender.require_auth();
+        Self::check_transfers_enabled(&env)?;
+        Self::spend_allowance(&env, &from, &spender, amount)?;
+        Self::move_balance(&env, &from, &to, amount)
     }
 
+    /// Mint new tokens. Admin-only once the token is initialised.
     pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), ContractError> {
+        let admin = Self::admin(env.clone());
+        if let Some(admin) = &admin {
+            admin.require_auth();
+        }
+
         if amount < 0 {
             return Err(ContractError::NegativeAmount);
         }
@@ -103,12 +235,131 @@ impl MockToken {
         env.storage().instance().set(&key, &(balance + amount));
         env.storage().instance().set(&supply_key, &new_total);
 
+        let minter = admin.unwrap_or_else(|| env.current_contract_address());
+        env.events()
+            .publish((symbol_short!("mint"), minter, to), amount);
+
         Ok(())
     }
 
     pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), ContractError> {
         from.require_auth();
+        Self::burn_balance(&env, &from, amount)
+    }
+
+    pub fn burn_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        spender.require_auth();
+        Self::spend_allowance(&env, &from, &spender, amount)?;
+        Self::burn_balance(&env, &from, amount)
+    }
+}
+
+impl MockToken {
+    fn metadata(env: &Env) -> Option<TokenMetadata> {
+        env.storage().instance().get(&MockTokenDataKey::Metadata)
+    }
+
+    /// Require the admin's auth, returning the admin. An uninitialised token
+    /// has no admin and lets anyone through.
+    fn require_admin(env: &Env) -> Address {
+        match Self::admin(env.clone()) {
+            Some(admin) => {
+                admin.require_auth();
+                admin
+            }
+            None => env.current_contract_address(),
+        }
+    }
+
+    fn check_transfers_enabled(env: &Env) -> Result<(), ContractError> {
+        if Self::fails_transfers(env.clone()) {
+            return Err(ContractError::TransferFailed);
+        }
+        Ok(())
+    }
 
+    fn live_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+        let key = MockTokenDataKey::Allowance(from.clone(), spender.clone());
+        match env.storage().instance().get::<_, AllowanceValue>(&key) {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+            _ => AllowanceValue {
+                amount: 0,
+                expiration_ledger: 0,
+            },
+        }
+    }
+
+    fn spend_allowance(
+        env: &Env,
+        from: &Address,
+        spender: &Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        if amount < 0 {
+            return Err(ContractError::NegativeAmount);
+        }
+        let allowance = Self::live_allowance(env, from, spender);
+        if allowance.amount < amount {
+            return Err(ContractError::InsufficientAllowance);
+        }
+        if amount > 0 {
+            env.storage().instance().set(
+                &MockTokenDataKey::Allowance(from.clone(), spender.clone()),
+                &AllowanceValue {
+                    amount: allowance.amount - amount,
+                    expiration_ledger: allowance.expiration_ledger,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    fn move_balance(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        if amount < 0 {
+            return Err(ContractError::NegativeAmount);
+        }
+
+        let key_from = MockTokenDataKey::Balance(from.clone());
+        let key_to = MockTokenDataKey::Balance(to.clone());
+
+        let balance_from: i128 = env.storage().instance().get(&key_from).unwrap_or(0);
+        let balance_to: i128 = env.storage().instance().get(&key_to).unwrap_or(0);
+
+        if balance_from < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+
+        if from != to {
+            if balance_to.checked_add(amount).is_none() {
+                return Err(ContractError::Overflow);
+            }
+
+            env.storage()
+                .instance()
+                .set(&key_from, &(balance_from - amount));
+            env.storage()
+                .instance()
+                .set(&key_to, &(balance_to + amount));
+        }
+
+        env.events().publish(
+            (symbol_short!("transfer"), from.clone(), to.clone()),
+            amount,
+        );
+        Ok(())
+    }
+
+    fn burn_balance(env: &Env, from: &Address, amount: i128) -> Result<(), ContractError> {
         if amount < 0 {
             return Err(ContractError::NegativeAmount);
         }
@@ -132,10 +383,139 @@ impl MockToken {
             .instance()
             .set(&supply_key, &(total_supply - amount));
 
+        env.events()
+            .publish((symbol_short!("burn"), from.clone()), amount);
         Ok(())
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    fn setup(env: &Env) -> (MockTokenClient<'_>, Address) {
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, MockToken);
+        let client = MockTokenClient::new(env, &contract_id);
+        let admin = Address::generate(env);
+        client.initialize(
+            &admin,
+            &6,
+            &String::from_str(env, "USD Coin"),
+            &String::from_str(env, "USDC"),
+        );
+        (client, admin)
+    }
+
+    #[test]
+    fn test_metadata_defaults_and_initialize() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, MockToken);
+        let client = MockTokenClient::new(&env, &contract_id);
+        assert_eq!(client.decimals(), 7);
+        assert_eq!(client.symbol(), String::from_str(&env, "MOCK"));
+
+        let (client, admin) = setup(&env);
+        assert_eq!(client.decimals(), 6);
+        assert_eq!(client.name(), String::from_str(&env, "USD Coin"));
+        assert_eq!(client.symbol(), String::from_str(&env, "USDC"));
+        assert_eq!(
+            client.try_initialize(
+                &admin,
+                &6,
+                &String::from_str(&env, "x"),
+                &String::from_str(&env, "x")
+            ),
+            Err(Ok(ContractError::AlreadyInitialized))
+        );
+    }
+
+    #[test]
+    fn test_mint_requires_admin_once_initialized() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        let user = Address::generate(&env);
+
+        client.mint(&user, &100);
+        assert_eq!(
+            env.auths()[0].0,
+            admin,
+            "mint must be authorised by the admin"
+        );
+        assert_eq!(client.balance(&user), 100);
+    }
+
+    #[test]
+    fn test_allowance_flow() {
+        let env = Env::default();
+        let (client, _admin) = setup(&env);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        client.mint(&owner, &1_000);
+
+        client.approve(&owner, &spender, &300, &100);
+        assert_eq!(client.allowance(&owner, &spender), 300);
+
+        client.transfer_from(&spender, &owner, &recipient, &200);
+        assert_eq!(client.balance(&recipient), 200);
+        assert_eq!(client.allowance(&owner, &spender), 100);
+        assert_eq!(
+            client.try_transfer_from(&spender, &owner, &recipient, &101),
+            Err(Ok(ContractError::InsufficientAllowance))
+        );
+
+        client.burn_from(&spender, &owner, &100);
+        assert_eq!(client.balance(&owner), 700);
+        assert_eq!(client.total_supply(), 900);
+        assert_eq!(client.allowance(&owner, &spender), 0);
+    }
+
+    #[test]
+    fn test_allowance_expires() {
+        let env = Env::default();
+        let (client, _admin) = setup(&env);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+        client.mint(&owner, &1_000);
+
+        env.ledger().with_mut(|li| li.sequence_number = 10);
+        assert_eq!(
+            client.try_approve(&owner, &spender, &50, &9),
+            Err(Ok(ContractError::InvalidExpiration))
+        );
+        client.approve(&owner, &spender, &50, &20);
+
+        env.ledger().with_mut(|li| li.sequence_number = 21);
+        assert_eq!(client.allowance(&owner, &spender), 0);
+        assert_eq!(
+            client.try_transfer_from(&spender, &owner, &spender, &1),
+            Err(Ok(ContractError::InsufficientAllowance))
+        );
+    }
+
+    #[test]
+    fn test_injected_transfer_failure() {
+        let env = Env::default();
+        let (client, _admin) = setup(&env);
+        let owner = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        client.mint(&owner, &1_000);
+
+        client.set_fail_transfers(&true);
+        assert!(client.fails_transfers());
+        assert_eq!(
+            client.try_transfer(&owner, &recipient, &10),
+            Err(Ok(ContractError::TransferFailed))
+        );
+
+        client.set_fail_transfers(&false);
+        client.transfer(&owner, &recipient, &10);
+        assert_eq!(client.balance(&recipient), 10);
+    }
+}
+
 #[cfg(all(test, not(target_family = "wasm")))]
 mod property_tests {
     use super::*;
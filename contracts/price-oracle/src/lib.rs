@@ -0,0 +1,192 @@
+#![no_std]
+
+//! SEP-40 price oracle integration shared by the lending and borrowing
+//! contracts.
+//!
+//! Each token can be priced by its own oracle. Values are computed in the
+//! oracle's quote currency. Only the quote asset itself is valued
+//! one-for-one; any other token without an oracle cannot be priced. Until an
+//! admin sets the quote asset, nothing without an oracle can be priced, so
+//! contracts deployed before quote assets existed must set one.
+//! Valuations that overflow fail instead of being clamped.
+
+use soroban_sdk::{contractclient, contracttype, symbol_short, Address, Env, Symbol};
+
+/// SEP-40 asset identifier.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// SEP-40 price record: `price` is scaled by `10^decimals()`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracleInterface {
+    fn decimals(env: Env) -> u32;
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+}
+
+/// Oracle used to price a token in the quote currency.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleConfig {
+    pub oracle: Address,
+    pub max_age_secs: u64, // Prices older than this are rejected as stale
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceOracleUpdatedEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub oracle: Option<Address>, // None when the oracle was removed
+    pub max_age_secs: u64,
+    pub timestamp: u64,
+}
+
+/// Storage keys for per-token oracle configuration.
+#[contracttype]
+#[derive(Clone)]
+pub enum PriceOracleKey {
+    Config(Address), // token -> OracleConfig
+    QuoteAsset,      // token the quote currency is denominated in
+}
+
+/// Why a token could not be priced. Contracts map these onto their own
+/// error enums.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OracleError {
+    /// The token has no oracle, or the oracle has no usable price for it.
+    PriceUnavailable,
+    /// The latest price is older than the configured `max_age_secs`, or
+    /// dated in the future.
+    StalePrice,
+    /// The valuation does not fit in the result type.
+    Overflow,
+}
+
+pub fn get_config(env: &Env, token: &Address) -> Option<OracleConfig> {
+    env.storage()
+        .persistent()
+        .get(&PriceOracleKey::Config(token.clone()))
+}
+
+/// Price `token` with `config` from now on and publish the change.
+pub fn set_config(env: &Env, admin: &Address, token: &Address, config: &OracleConfig) {
+    let key = PriceOracleKey::Config(token.clone());
+    env.storage().persistent().set(&key, config);
+    access_control::extend_persistent_ttl(env, &key);
+    env.events().publish(
+        (symbol_short!("ORACLE"), symbol_short!("SET")),
+        PriceOracleUpdatedEvent {
+            admin: admin.clone(),
+            token: token.clone(),
+            oracle: Some(config.oracle.clone()),
+            max_age_secs: config.max_age_secs,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+/// Stop pricing `token` with an oracle and publish the change.
+pub fn remove_config(env: &Env, admin: &Address, token: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&PriceOracleKey::Config(token.clone()));
+    env.events().publish(
+        (symbol_short!("ORACLE"), symbol_short!("REMOVE")),
+        PriceOracleUpdatedEvent {
+            admin: admin.clone(),
+            token: token.clone(),
+            oracle: None,
+            max_age_secs: 0,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+/// Token valued one-for-one in the quote currency, if one has been set.
+pub fn get_quote_asset(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&PriceOracleKey::QuoteAsset)
+}
+
+/// Value `token` one-for-one in the quote currency from now on.
+pub fn set_quote_asset(env: &Env, token: &Address) {
+    env.storage()
+        .instance()
+        .set(&PriceOracleKey::QuoteAsset, token);
+}
+
+/// Same rule as the inheritance contract's `verify_price_freshness`: prices
+/// from the future or older than `max_age_secs` are not usable.
+fn is_fresh(env: &Env, price_timestamp: u64, max_age_secs: u64) -> bool {
+    let now = env.ledger().timestamp();
+    price_timestamp <= now && now - price_timestamp <= max_age_secs
+}
+
+/// Fresh price of `token` as `(price, 10^decimals)`, or None when `token` is
+/// the quote asset and has no oracle of its own.
+fn price_of(env: &Env, token: &Address) -> Result<Option<(u128, u128)>, OracleError> {
+    let Some(config) = get_config(env, token) else {
+        return match get_quote_asset(env) {
+            Some(quote) if quote == *token => Ok(None),
+            _ => Err(OracleError::PriceUnavailable),
+        };
+    };
+    let client = PriceOracleClient::new(env, &config.oracle);
+    let data = client
+        .lastprice(&Asset::Stellar(token.clone()))
+        .ok_or(OracleError::PriceUnavailable)?;
+    if data.price <= 0 {
+        return Err(OracleError::PriceUnavailable);
+    }
+    if !is_fresh(env, data.timestamp, config.max_age_secs) {
+        return Err(OracleError::StalePrice);
+    }
+    let scale = 10u128
+        .checked_pow(client.decimals())
+        .ok_or(OracleError::PriceUnavailable)?;
+    Ok(Some((data.price as u128, scale)))
+}
+
+/// Value `amount` of `token` in the quote currency, rounded down.
+pub fn quote_value(env: &Env, token: &Address, amount: u128) -> Result<u128, OracleError> {
+    Ok(match price_of(env, token)? {
+        Some((price, scale)) => amount.checked_mul(price).ok_or(OracleError::Overflow)? / scale,
+        None => amount,
+    })
+}
+
+/// Amount of `token` worth `value` in the quote currency, rounded down.
+pub fn token_amount_for(env: &Env, token: &Address, value: u128) -> Result<u128, OracleError> {
+    Ok(match price_of(env, token)? {
+        Some((price, scale)) => value.checked_mul(scale).ok_or(OracleError::Overflow)? / price,
+        None => value,
+    })
+}
+
+/// Collateral value over debt value, in basis points, with both sides priced
+/// in the quote currency. Zero debt is reported as `u32::MAX`.
+pub fn collateral_ratio_bps(
+    env: &Env,
+    collateral_token: &Address,
+    collateral_amount: u128,
+    debt_asset: &Address,
+    debt_amount: u128,
+) -> Result<u32, OracleError> {
+    let debt_value = quote_value(env, debt_asset, debt_amount)?;
+    if debt_value == 0 {
+        return Ok(u32::MAX);
+    }
+    let collateral_value = quote_value(env, collateral_token, collateral_amount)?;
+    let ratio = collateral_value.saturating_mul(10000) / debt_value;
+    Ok(ratio.min(u32::MAX as u128) as u32)
+}